@@ -1,4 +1,4 @@
-use clap::{command, Parser};
+use clap::{command, Parser, ValueEnum};
 
 /// Global arguments used by all subcommands
 #[derive(Debug, Parser)]
@@ -11,4 +11,29 @@ pub struct GlobalArgs {
     /// Path to Kora configuration file (TOML format)
     #[arg(long, default_value = "kora.toml")]
     pub config: String,
+
+    /// Read the configuration from `KORA_*` environment variables instead of `--config`
+    /// (e.g. `KORA_VALIDATION_MAX_ALLOWED_LAMPORTS`, `KORA_KORA_RATE_LIMIT`)
+    #[arg(long, conflicts_with = "config")]
+    pub config_from_env: bool,
+}
+
+/// Output format of the `config validate` commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable report on stdout
+    #[default]
+    Text,
+    /// `{ "success", "errors", "warnings" }` on stdout, the human-readable report on stderr
+    Json,
+}
+
+/// Private key format written by the `keypair` commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum KeypairFormat {
+    /// Solana CLI keypair file, a JSON array of the 64 keypair bytes
+    #[default]
+    Json,
+    /// Unencrypted PKCS#8 PEM
+    Pem,
 }
@@ -1,19 +1,35 @@
 mod args;
 mod rent_manager;
 
-use args::GlobalArgs;
+use args::{GlobalArgs, KeypairFormat, OutputFormat};
 use clap::{Parser, Subcommand};
 use kora_lib::{
-    admin::token_util::initialize_atas,
+    admin::{rebalance::rebalance_signers, token_util::initialize_atas},
+    constant::DEFAULT_LOG_FILTER_OVERRIDES,
     error::KoraError,
     log::LoggingFormat,
+    metrics::build_dashboard,
     rpc::get_rpc_client,
-    rpc_server::{run_rpc_server, server::ServerHandles, KoraRpc, RpcArgs},
-    signer::init::init_signers,
-    state::init_config,
-    validator::config_validator::ConfigValidator,
+    rpc_server::{
+        method::set_log_level::init_log_filter_reload_handle, run_rpc_server,
+        server::ServerHandles, KoraRpc, RpcArgs,
+    },
+    signer::{init::init_signers, KeypairUtil},
+    state::{init_config, set_default_signer_key},
+    validator::{config_validator::ConfigValidator, signer_validator::SignerValidator},
     CacheUtil, Config,
 };
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::signer::Signer;
+use std::{
+    collections::BTreeSet,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter};
 
 #[cfg(feature = "docs")]
 use kora_lib::rpc_server::openapi::docs;
@@ -37,6 +53,11 @@ enum Commands {
         #[command(subcommand)]
         rent_command: RentManagerCommands,
     },
+    /// Keypair generation and conversion
+    Keypair {
+        #[command(subcommand)]
+        keypair_command: KeypairCommands,
+    },
     /// Generate OpenAPI documentation
     #[cfg(feature = "docs")]
     Openapi {
@@ -53,6 +74,12 @@ pub enum RentManagerCommands {
         rpc_args: Box<RpcArgs>,
         #[arg(long, default_value_t = false)]
         all: bool,
+        /// Print plain-text output instead of the interactive TUI (e.g. in CI)
+        #[arg(long, default_value_t = false)]
+        no_tui: bool,
+        /// Accounts to never close (defaults to ~/.kora/rent_whitelist.json)
+        #[arg(long)]
+        whitelist_file: Option<std::path::PathBuf>,
     },
     Reclaim {
         #[command(flatten)]
@@ -61,6 +88,12 @@ pub enum RentManagerCommands {
         execute: bool,
         #[arg(long, default_value_t = false)]
         force_all: bool,
+        /// Print plain-text output instead of the interactive TUI (e.g. in CI)
+        #[arg(long, default_value_t = false)]
+        no_tui: bool,
+        /// Accounts to never close (defaults to ~/.kora/rent_whitelist.json)
+        #[arg(long)]
+        whitelist_file: Option<std::path::PathBuf>,
     },
     /// Run as a background bot
     Run {
@@ -70,12 +103,66 @@ pub enum RentManagerCommands {
         /// Check interval (e.g., "6h", "30m", "1d")
         #[arg(long, default_value = "6h")]
         interval: String,
+
+        /// Print plain-text output instead of the interactive TUI (e.g. in CI)
+        #[arg(long, default_value_t = false)]
+        no_tui: bool,
+
+        /// Accounts to never close (defaults to ~/.kora/rent_whitelist.json)
+        #[arg(long)]
+        whitelist_file: Option<std::path::PathBuf>,
+    },
+    /// Reclaim on a cron schedule, evaluated in UTC
+    Schedule {
+        #[command(flatten)]
+        rpc_args: Box<RpcArgs>,
+
+        /// Cron expression with a seconds field (e.g., "0 0 2 * * *" for every day at 2 AM UTC)
+        cron_expr: String,
+
+        #[arg(long, default_value_t = false)]
+        execute: bool,
+
+        /// Print plain-text output instead of the interactive TUI (e.g. in CI)
+        #[arg(long, default_value_t = false)]
+        no_tui: bool,
+
+        /// Accounts to never close (defaults to ~/.kora/rent_whitelist.json)
+        #[arg(long)]
+        whitelist_file: Option<std::path::PathBuf>,
+    },
+    /// Add accounts to the list of accounts to never close, or clear it
+    Whitelist {
+        /// Accounts to add, the current whitelist is printed when none are given
+        accounts: Vec<String>,
+        /// Remove every account from the whitelist before adding `accounts`
+        #[arg(long, default_value_t = false)]
+        clear: bool,
+        /// Whitelist file (defaults to ~/.kora/rent_whitelist.json)
+        #[arg(long)]
+        whitelist_file: Option<std::path::PathBuf>,
     },
     /// Show rent efficiency statistics
     Stats {
         #[command(flatten)]
         rpc_args: Box<RpcArgs>,
     },
+    /// Recover rent from empty accounts owned by a specific program
+    Recover {
+        #[command(flatten)]
+        rpc_args: Box<RpcArgs>,
+
+        /// Program that owns the accounts to recover
+        #[arg(long)]
+        program_id: String,
+
+        /// Base64-encoded data of the program's close instruction
+        #[arg(long)]
+        close_instruction_data: Option<String>,
+
+        #[arg(long, default_value_t = false)]
+        execute: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -85,12 +172,58 @@ enum ConfigCommands {
         /// Path to signers configuration file (optional)
         #[arg(long)]
         signers_config: Option<std::path::PathBuf>,
+        /// Print the result as JSON, for CI pipelines
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output_format: OutputFormat,
     },
     /// Validate configuration file with RPC validation (slower but more thorough)
     ValidateWithRpc {
         /// Path to signers configuration file (optional)
         #[arg(long)]
         signers_config: Option<std::path::PathBuf>,
+        /// Print the result as JSON, for CI pipelines
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output_format: OutputFormat,
+        /// Keep revalidating every `interval` seconds, printing the errors and warnings that
+        /// changed since the previous run
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: Option<u64>,
+        /// Exit with a non-zero code as soon as a run differs from the previous one
+        #[arg(long, requires = "interval")]
+        alert_on_change: bool,
+    },
+    /// Validate only the signers configuration file (no RPC calls, kora.toml is not loaded)
+    ValidateSignersOnly {
+        /// Path to signers configuration file
+        #[arg(long)]
+        signers_config: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeypairCommands {
+    /// Generate a new keypair
+    Generate {
+        /// Format of the private key
+        #[arg(long, value_enum, default_value_t = KeypairFormat::Json)]
+        format: KeypairFormat,
+        /// Write the private key to this new file instead of stdout, existing files are not
+        /// overwritten
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+    },
+    /// Convert a private key to another format
+    Export {
+        /// Private key as base58, a U8Array, a PKCS#8 PEM, or a path to a JSON keypair or PEM file
+        #[arg(long, env = "KORA_PRIVATE_KEY", hide_env_values = true)]
+        private_key: String,
+        /// Format to export the private key in
+        #[arg(long, value_enum)]
+        format: KeypairFormat,
+        /// Write the private key to this new file instead of stdout, existing files are not
+        /// overwritten
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
     },
 }
 
@@ -104,6 +237,11 @@ enum RpcCommands {
     Start {
         #[command(flatten)]
         rpc_args: Box<RpcArgs>,
+
+        /// Signer (pubkey or name from the signers config) used for every request without its
+        /// own signer_key, instead of the pool's selection strategy
+        #[arg(long, help_heading = "Signer Options")]
+        signer_key: Option<String>,
     },
     /// Initialize ATAs for all allowed payment tokens
     #[command(
@@ -114,9 +252,10 @@ enum RpcCommands {
         #[command(flatten)]
         rpc_args: Box<RpcArgs>,
 
-        /// Signer key to use as fee payer (defaults to first signer if not specified)
-        #[arg(long, help_heading = "Signer Options")]
-        fee_payer_key: Option<String>,
+        /// Signer (pubkey or name from the signers config) to use as fee payer (defaults to the
+        /// pool's selection strategy if not specified)
+        #[arg(long, alias = "fee-payer-key", help_heading = "Signer Options")]
+        signer_key: Option<String>,
 
         /// Compute unit price for priority fees (in micro-lamports)
         #[arg(long, help_heading = "Transaction Options")]
@@ -130,6 +269,33 @@ enum RpcCommands {
         #[arg(long, help_heading = "Transaction Options")]
         chunk_size: Option<usize>,
     },
+    /// Move SOL between signers so each one holds the target balance
+    #[command(
+        about = "Distribute SOL evenly across signers",
+        long_about = "Compute System transfers from over-funded to under-funded signers so each signer in the pool holds the target balance.\n\nWithout --execute the transfer plan is only printed."
+    )]
+    RebalanceSigners {
+        #[command(flatten)]
+        rpc_args: Box<RpcArgs>,
+
+        /// Balance every signer should end up with (in lamports)
+        #[arg(long)]
+        target_balance_lamports: u64,
+
+        /// Submit the transfers instead of printing the plan
+        #[arg(long, default_value_t = false)]
+        execute: bool,
+    },
+    /// Write a Grafana dashboard for Kora's Prometheus metrics
+    #[command(
+        about = "Export a Grafana dashboard for Kora's metrics",
+        long_about = "Write a Grafana dashboard JSON with panels for request rates, latency, signer balances, signer queue depth, signing rates and cache size.\n\nThe dashboard reads from a Prometheus datasource with uid `prometheus`."
+    )]
+    ExportDashboard {
+        /// Output path for the dashboard JSON file
+        #[arg(short = 'o', long, default_value = "grafana.json")]
+        output: PathBuf,
+    },
 }
 
 #[derive(Parser)]
@@ -147,7 +313,30 @@ async fn main() -> Result<(), KoraError> {
     dotenv::dotenv().ok();
     let cli = Cli::parse();
 
-    let config = Config::load_config(&cli.global_args.config).unwrap_or_else(|e| {
+    // Signers are validated on their own, before any config or global state is set up
+    if let Some(Commands::Config {
+        config_command: ConfigCommands::ValidateSignersOnly { signers_config },
+    }) = &cli.command
+    {
+        let exit_code = match SignerValidator::validate_signers_only(signers_config) {
+            Ok(_) => 0,
+            Err(_) => 1,
+        };
+        std::process::exit(exit_code);
+    }
+
+    // Keypair commands only work on local keys and don't need a config
+    if let Some(Commands::Keypair { keypair_command }) = &cli.command {
+        handle_keypair_command(keypair_command);
+        std::process::exit(0);
+    }
+
+    let config = if cli.global_args.config_from_env {
+        Config::load_from_env()
+    } else {
+        Config::load_config(&cli.global_args.config)
+    };
+    let config = config.unwrap_or_else(|e| {
         print_error(&format!("Failed to load config: {e}"));
         std::process::exit(1);
     });
@@ -162,28 +351,40 @@ async fn main() -> Result<(), KoraError> {
     match cli.command {
         Some(Commands::Config { config_command }) => {
             match config_command {
-                ConfigCommands::Validate { signers_config } => {
-                    let _ = ConfigValidator::validate_with_result_and_signers(
-                        rpc_client.as_ref(),
-                        true,
-                        signers_config.as_ref(),
-                    )
-                    .await;
+                ConfigCommands::Validate { signers_config, output_format } => {
+                    let _ =
+                        validate_config(rpc_client.as_ref(), true, signers_config, output_format)
+                            .await;
                 }
-                ConfigCommands::ValidateWithRpc { signers_config } => {
-                    let _ = ConfigValidator::validate_with_result_and_signers(
+                ConfigCommands::ValidateWithRpc {
+                    signers_config,
+                    output_format,
+                    interval: Some(interval),
+                    alert_on_change,
+                } => {
+                    watch_config(
                         rpc_client.as_ref(),
-                        false,
-                        signers_config.as_ref(),
+                        signers_config,
+                        output_format,
+                        Duration::from_secs(interval),
+                        alert_on_change,
                     )
                     .await;
                 }
+                ConfigCommands::ValidateWithRpc { signers_config, output_format, .. } => {
+                    let _ =
+                        validate_config(rpc_client.as_ref(), false, signers_config, output_format)
+                            .await;
+                }
+                ConfigCommands::ValidateSignersOnly { .. } => {
+                    unreachable!("validate-signers-only is handled before the config is loaded")
+                }
             }
             std::process::exit(0);
         }
         Some(Commands::Rpc { rpc_command }) => {
             match rpc_command {
-                RpcCommands::Start { rpc_args } => {
+                RpcCommands::Start { rpc_args, signer_key } => {
                     // Validate config and signers before starting server
                     match ConfigValidator::validate_with_result_and_signers(
                         rpc_client.as_ref(),
@@ -215,6 +416,13 @@ async fn main() -> Result<(), KoraError> {
                         });
                     }
 
+                    if let Some(signer_key) = &signer_key {
+                        if let Err(e) = set_default_signer_key(signer_key) {
+                            print_error(&format!("Invalid --signer-key {signer_key}: {e}"));
+                            std::process::exit(1);
+                        }
+                    }
+
                     // Initialize cache
                     if let Err(e) = CacheUtil::init().await {
                         print_error(&format!("Failed to initialize cache: {e}"));
@@ -225,8 +433,13 @@ async fn main() -> Result<(), KoraError> {
 
                     let kora_rpc = KoraRpc::new(rpc_client);
 
-                    let ServerHandles { rpc_handle, metrics_handle, balance_tracker_handle } =
-                        run_rpc_server(kora_rpc, rpc_args.port).await?;
+                    let ServerHandles {
+                        rpc_handle,
+                        ws_handle,
+                        metrics_handle,
+                        balance_tracker_handle,
+                        push_gateway_handle,
+                    } = run_rpc_server(kora_rpc, rpc_args.port, rpc_args.ws_port).await?;
 
                     if let Err(e) = tokio::signal::ctrl_c().await {
                         panic!("Error waiting for Ctrl+C signal: {e:?}");
@@ -239,11 +452,24 @@ async fn main() -> Result<(), KoraError> {
                         handle.abort();
                     }
 
+                    // Stop the metrics push gateway task
+                    if let Some(handle) = push_gateway_handle {
+                        log::info!("Stopping metrics push gateway background task...");
+                        handle.abort();
+                    }
+
                     // Stop the RPC server
                     if let Err(e) = rpc_handle.stop() {
                         panic!("Error stopping RPC server: {e:?}");
                     }
 
+                    // Stop the WebSocket server if running
+                    if let Some(handle) = ws_handle {
+                        if let Err(e) = handle.stop() {
+                            panic!("Error stopping WebSocket server: {e:?}");
+                        }
+                    }
+
                     // Stop the metrics server if running
                     if let Some(handle) = metrics_handle {
                         if let Err(e) = handle.stop() {
@@ -253,7 +479,7 @@ async fn main() -> Result<(), KoraError> {
                 }
                 RpcCommands::InitializeAtas {
                     rpc_args,
-                    fee_payer_key,
+                    signer_key,
                     compute_unit_price,
                     compute_unit_limit,
                     chunk_size,
@@ -280,7 +506,7 @@ async fn main() -> Result<(), KoraError> {
                         compute_unit_price,
                         compute_unit_limit,
                         chunk_size,
-                        fee_payer_key,
+                        signer_key,
                     )
                     .await
                     {
@@ -289,11 +515,50 @@ async fn main() -> Result<(), KoraError> {
                     }
                     println!("Successfully initialized all payment ATAs");
                 }
+                RpcCommands::RebalanceSigners { rpc_args, target_balance_lamports, execute } => {
+                    if rpc_args.skip_signer {
+                        print_error("Cannot rebalance signers without a signer.");
+                        std::process::exit(1);
+                    }
+                    init_signers(&rpc_args).await.unwrap_or_else(|e| {
+                        print_error(&format!("Failed to initialize signer(s): {e}"));
+                        std::process::exit(1);
+                    });
+
+                    println!("Signer balances:");
+                    if let Err(e) =
+                        rebalance_signers(rpc_client.as_ref(), target_balance_lamports, execute)
+                            .await
+                    {
+                        print_error(&format!("Failed to rebalance signers: {e}"));
+                        std::process::exit(1);
+                    }
+                }
+                RpcCommands::ExportDashboard { output } => {
+                    let json =
+                        serde_json::to_string_pretty(&build_dashboard()).unwrap_or_else(|e| {
+                            print_error(&format!("Failed to serialize Grafana dashboard: {e}"));
+                            std::process::exit(1);
+                        });
+
+                    std::fs::write(&output, json).unwrap_or_else(|e| {
+                        print_error(&format!(
+                            "Failed to write Grafana dashboard to {}: {e}",
+                            output.display()
+                        ));
+                        std::process::exit(1);
+                    });
+
+                    println!("Grafana dashboard written to: {}", output.display());
+                }
             }
         }
         Some(Commands::RentManager { rent_command }) => {
             rent_manager::handle_rent_manager(rent_command, rpc_client).await?;
         }
+        Some(Commands::Keypair { .. }) => {
+            unreachable!("keypair commands are handled before the config is loaded")
+        }
         #[cfg(feature = "docs")]
         Some(Commands::Openapi { output }) => {
             docs::update_docs();
@@ -316,9 +581,14 @@ async fn main() -> Result<(), KoraError> {
             println!("Available commands:");
             println!("  config validate          - Validate configuration");
             println!("  config validate-with-rpc - Validate configuration with RPC calls");
+            println!("  config validate-signers-only - Validate signers configuration only");
             println!("  rpc start                - Start RPC server");
             println!("  rpc initialize-atas      - Initialize ATAs for payment tokens");
+            println!("  rpc rebalance-signers    - Distribute SOL evenly across signers");
+            println!("  rpc export-dashboard     - Export a Grafana dashboard for Kora's metrics");
             println!("  rent-manager             - Manage rent reclamation");
+            println!("  keypair generate         - Generate a new keypair");
+            println!("  keypair export           - Convert a private key to JSON or PEM");
             #[cfg(feature = "docs")]
             println!("  openapi                  - Generate OpenAPI documentation");
         }
@@ -327,17 +597,192 @@ async fn main() -> Result<(), KoraError> {
     Ok(())
 }
 
+#[derive(Serialize)]
+struct ValidationOutput {
+    success: bool,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+async fn validate_config(
+    rpc_client: &RpcClient,
+    skip_rpc_validation: bool,
+    signers_config: Option<PathBuf>,
+    output_format: OutputFormat,
+) -> Result<Vec<String>, Vec<String>> {
+    if output_format == OutputFormat::Text {
+        return ConfigValidator::validate_with_result_and_signers(
+            rpc_client,
+            skip_rpc_validation,
+            signers_config.as_ref(),
+        )
+        .await;
+    }
+
+    // Only the JSON document goes to stdout so it can be piped into a parser
+    let result = ConfigValidator::validate_with_result_and_signers_to(
+        rpc_client,
+        skip_rpc_validation,
+        signers_config.as_ref(),
+        &mut std::io::stderr(),
+    )
+    .await;
+    let output = match result.clone() {
+        Ok(warnings) => ValidationOutput { success: true, errors: vec![], warnings },
+        Err(errors) => ValidationOutput { success: false, errors, warnings: vec![] },
+    };
+    match serde_json::to_string(&output) {
+        Ok(json) => println!("{json}"),
+        Err(e) => {
+            print_error(&format!("Failed to serialize validation result: {e}"));
+            std::process::exit(1);
+        }
+    }
+    result
+}
+
+/// Validate with RPC calls every `interval`, reporting what changed since the previous run
+async fn watch_config(
+    rpc_client: &RpcClient,
+    signers_config: Option<PathBuf>,
+    output_format: OutputFormat,
+    interval: Duration,
+    alert_on_change: bool,
+) -> ! {
+    let mut previous =
+        validate_config(rpc_client, false, signers_config.clone(), output_format).await;
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let current = ConfigValidator::validate_with_result_and_signers_to(
+            rpc_client,
+            false,
+            signers_config.as_ref(),
+            &mut std::io::sink(),
+        )
+        .await;
+        let (added, removed) = validation_changes(&previous, &current);
+        let timestamp = humantime::format_rfc3339_seconds(SystemTime::now());
+
+        // Keep stdout to the JSON document with --output-format json
+        let report = |line: String| match output_format {
+            OutputFormat::Text => println!("{line}"),
+            OutputFormat::Json => eprintln!("{line}"),
+        };
+        if added.is_empty() && removed.is_empty() {
+            report(format!("[{timestamp}] No changes since the previous validation"));
+        } else {
+            report(format!(
+                "[{timestamp}] {} change(s) since the previous validation:",
+                added.len() + removed.len()
+            ));
+            added.iter().for_each(|entry| report(format!("  + {entry}")));
+            removed.iter().for_each(|entry| report(format!("  - {entry}")));
+        }
+
+        if output_format == OutputFormat::Json {
+            let output = match &current {
+                Ok(warnings) => {
+                    ValidationOutput { success: true, errors: vec![], warnings: warnings.clone() }
+                }
+                Err(errors) => {
+                    ValidationOutput { success: false, errors: errors.clone(), warnings: vec![] }
+                }
+            };
+            if let Ok(json) = serde_json::to_string(&output) {
+                println!("{json}");
+            }
+        }
+
+        if alert_on_change && !(added.is_empty() && removed.is_empty()) {
+            std::process::exit(1);
+        }
+        previous = current;
+    }
+}
+
+/// Entries of `current` missing from `previous`, and entries of `previous` gone from `current`
+fn validation_changes(
+    previous: &Result<Vec<String>, Vec<String>>,
+    current: &Result<Vec<String>, Vec<String>>,
+) -> (Vec<String>, Vec<String>) {
+    let entries = |result: &Result<Vec<String>, Vec<String>>| -> BTreeSet<String> {
+        match result {
+            Ok(warnings) => warnings.iter().map(|w| format!("warning: {w}")).collect(),
+            Err(errors) => errors.iter().map(|e| format!("error: {e}")).collect(),
+        }
+    };
+    let (previous, current) = (entries(previous), entries(current));
+
+    (
+        current.difference(&previous).cloned().collect(),
+        previous.difference(&current).cloned().collect(),
+    )
+}
+
+fn handle_keypair_command(command: &KeypairCommands) {
+    let (keypair, format, output) = match command {
+        KeypairCommands::Generate { format, output } => {
+            (KeypairUtil::generate_keypair(), *format, output)
+        }
+        KeypairCommands::Export { private_key, format, output } => {
+            let keypair = KeypairUtil::from_private_key_string(private_key).unwrap_or_else(|e| {
+                print_error(&format!("Failed to read private key: {e}"));
+                std::process::exit(1);
+            });
+            (keypair, *format, output)
+        }
+    };
+
+    let exported = match format {
+        KeypairFormat::Json => KeypairUtil::export_to_json(&keypair),
+        KeypairFormat::Pem => KeypairUtil::export_to_pem(&keypair),
+    };
+
+    match output {
+        Some(output) => {
+            write_private_key(output, &exported).unwrap_or_else(|e| {
+                print_error(&format!("Failed to write keypair to {}: {e}", output.display()));
+                std::process::exit(1);
+            });
+            println!("Keypair {} written to: {}", keypair.pubkey(), output.display());
+        }
+        None => {
+            // Only the key goes to stdout so it can be redirected to a file
+            eprintln!("Public key: {}", keypair.pubkey());
+            println!("{}", exported.trim_end());
+        }
+    }
+}
+
+/// Create the file readable by its owner only, it holds a private key
+fn write_private_key(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
 fn print_error(message: &str) {
     eprintln!("Error: {message}");
 }
 
 fn setup_logging(format: &LoggingFormat) {
     let env_filter = std::env::var("RUST_LOG")
-        .unwrap_or_else(|_| "info,sqlx=error,sea_orm_migration=error,jsonrpsee_server=warn".into());
+        .unwrap_or_else(|_| format!("info,{DEFAULT_LOG_FILTER_OVERRIDES}"));
 
-    let subscriber = tracing_subscriber::fmt().with_env_filter(env_filter);
+    // Reloadable so the kora_setLogLevel admin method can change verbosity at runtime
+    let (filter, reload_handle) = reload::Layer::new(EnvFilter::new(env_filter));
+    let registry = tracing_subscriber::registry().with(filter);
     match format {
-        LoggingFormat::Standard => subscriber.init(),
-        LoggingFormat::Json => subscriber.json().init(),
+        LoggingFormat::Standard => registry.with(tracing_subscriber::fmt::layer()).init(),
+        LoggingFormat::Json => registry.with(tracing_subscriber::fmt::layer().json()).init(),
+    }
+
+    if let Err(e) = init_log_filter_reload_handle(reload_handle) {
+        log::warn!("Runtime log level changes unavailable: {e}");
     }
 }
@@ -4,6 +4,8 @@ pub const GRACE_PERIOD_SECONDS: u64 = 24 * 60 * 60; // 24 hours
 // File paths
 pub const TRACKER_FILE: &str = "grace_period.json";
 pub const AUDIT_FILE: &str = "audit_log.csv";
+// Relative to the home directory, used when --whitelist-file is not set
+pub const WHITELIST_FILE: &str = ".kora/rent_whitelist.json";
 
 // Thresholds
 pub const HIGH_RENT_THRESHOLD_SOL: f64 = 1.0; 
@@ -1,52 +1,56 @@
+use std::future::Future;
 use std::sync::Arc;
+use std::str::FromStr;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use base64::{Engine as _, engine::general_purpose};
 use tokio::sync::mpsc;
+use chrono::Utc;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use ratatui::style::Color;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::pubkey::Pubkey;
-use kora_lib::{error::KoraError, signer::pool::SignerPool};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    rent::Rent,
+};
+use solana_system_interface::{instruction::transfer, program::ID as SYSTEM_PROGRAM_ID};
+use kora_lib::{error::KoraError, signer::pool::SignerPool, SolanaSigner};
 
 use super::config::{GRACE_PERIOD_SECONDS, HIGH_RENT_THRESHOLD_SOL, HEARTBEAT_INTERVAL_SECS};
-use super::types::{UiEvent, OperationMode, ReclaimReason, AuditRecord};
-use super::state::{GracePeriodTracker, AppState};
-use super::tui::ui;
+use super::types::{UiEvent, OperationMode, ReclaimReason, AuditRecord, AccountRentStatus};
+use super::state::{GracePeriodTracker, AppState, RentWhitelist};
+use super::tui::{plain_event_line, ui};
 use super::utils::{
-    fetch_all_token_accounts, close_account, get_allowed_tokens,
-    lamports_to_sol, log_to_audit_trail, send_telegram_alert
+    fetch_all_token_accounts, fetch_empty_program_accounts, close_account, send_instruction,
+    get_allowed_tokens, lamports_to_sol, log_to_audit_trail, send_telegram_alert
 };
 use crate::log_output; // Import the macro
 
-// --- Main TUI Task ---
-// Runs the TUI and spawns background worker tasks
-pub async fn run_tui_task(
+// --- Background Worker ---
+// Spawns the task running the operation, its events feed either the TUI or plain output
+fn spawn_worker(
     rpc_client: Arc<RpcClient>,
-    signer_pool: Arc<SignerPool>, 
+    signer_pool: Arc<SignerPool>,
     mode: OperationMode,
-) -> Result<(), KoraError> {
-    enable_raw_mode().unwrap();
-    let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture).unwrap();
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend).unwrap();
+    whitelist: RentWhitelist,
+) -> mpsc::UnboundedReceiver<UiEvent> {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    let rpc_bg = rpc_client;
+    let pool_bg = signer_pool;
 
-    let (tx, mut rx) = mpsc::unbounded_channel();
-    
-    let rpc_bg = rpc_client.clone();
-    let pool_bg = signer_pool.clone();
-    
     tokio::spawn(async move {
         let mut tracker = GracePeriodTracker::load();
 
         match mode {
             OperationMode::Scan { all } => {
                 let _ = tx.send(UiEvent::Status("🔍 Scanning...".to_string()));
-                if let Err(e) = scan_accounts(rpc_bg, &pool_bg, all, &mut tracker, Some(tx.clone())).await {
+                if let Err(e) = scan_accounts(rpc_bg, &pool_bg, all, &whitelist, &mut tracker, Some(tx.clone())).await {
                     let _ = tx.send(UiEvent::Log("System".to_string(), format!("Error: {}", e), Color::Red));
                 }
                 let _ = tx.send(UiEvent::Status("✅ Scan Complete. Press 'q' to quit.".to_string()));
@@ -57,7 +61,7 @@ pub async fn run_tui_task(
                 let _ = tx.send(UiEvent::Status(format!("⚡ {}...", mode_str)));
                 
                 // Manual reclaim is verbose (show_skipped = true)
-                if let Err(e) = reclaim_rent(rpc_bg, &pool_bg, execute, force_all, true, &mut tracker, Some(tx.clone())).await {
+                if let Err(e) = reclaim_rent(rpc_bg, &pool_bg, execute, force_all, true, &whitelist, &mut tracker, Some(tx.clone())).await {
                     let _ = tx.send(UiEvent::Log("System".to_string(), format!("Error: {}", e), Color::Red));
                 }
                 tracker.save();
@@ -78,7 +82,7 @@ pub async fn run_tui_task(
                     let mut daemon_tracker = GracePeriodTracker::load();
                     
                     // Daemon is quiet (show_skipped = false) and safe (execute = false)
-                    match reclaim_rent(rpc_bg.clone(), &pool_bg, false, false, false, &mut daemon_tracker, Some(tx.clone())).await {
+                    match reclaim_rent(rpc_bg.clone(), &pool_bg, false, false, false, &whitelist, &mut daemon_tracker, Some(tx.clone())).await {
                         Ok(_) => {
                             daemon_tracker.save();
                         },
@@ -112,10 +116,100 @@ pub async fn run_tui_task(
                         tokio::time::sleep(sleep_step).await;
                     }
                 }
+            },
+            OperationMode::Scheduled { schedule, execute } => {
+                let (rpc_bg, pool_bg, whitelist, cycle_tx) = (&rpc_bg, &pool_bg, &whitelist, &tx);
+                run_on_schedule(&schedule, &tx, None, move || async move {
+                    let mode_str = if execute { "RECLAIMING" } else { "DRY RUN" };
+                    let _ = cycle_tx.send(UiEvent::Status(format!("⏰ Scheduled Cycle Starting ({})...", mode_str)));
+                    let mut cycle_tracker = GracePeriodTracker::load();
+
+                    // Scheduled runs are quiet (show_skipped = false), like the daemon
+                    match reclaim_rent(rpc_bg.clone(), pool_bg, execute, false, false, whitelist, &mut cycle_tracker, Some(cycle_tx.clone())).await {
+                        Ok(_) => cycle_tracker.save(),
+                        Err(e) => {
+                            let _ = cycle_tx.send(UiEvent::Log("System".to_string(), format!("⚠️ Job Failed: {}", e), Color::Red));
+                        }
+                    }
+                }).await;
+
+                let _ = tx.send(UiEvent::Status("✅ Schedule has no upcoming runs. Press 'q' to quit.".to_string()));
+                let _ = tx.send(UiEvent::TaskComplete);
             }
         }
     });
 
+    rx
+}
+
+// --- Cron Scheduling ---
+// Sleeps until each upcoming time of the schedule and runs a cycle, stopping after `max_cycles`.
+// Times are computed in UTC, so DST changes never skip or repeat a run.
+async fn run_on_schedule<F, Fut>(
+    schedule: &cron::Schedule,
+    tx: &mpsc::UnboundedSender<UiEvent>,
+    max_cycles: Option<usize>,
+    mut cycle: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut cycles = 0;
+    while max_cycles.is_none_or(|max| cycles < max) {
+        let Some(next_run) = schedule.upcoming(Utc).next() else { break };
+        let _ = tx.send(UiEvent::NextRun(next_run.format("%Y-%m-%d %H:%M:%S UTC").to_string()));
+
+        loop {
+            let remaining = (next_run - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+            if remaining.is_zero() { break; }
+
+            let _ = tx.send(UiEvent::Status(format!("💤 Waiting... Next scheduled run in {}s", remaining.as_secs())));
+            tokio::time::sleep(remaining.min(Duration::from_secs(1))).await;
+        }
+
+        cycle().await;
+        cycles += 1;
+    }
+}
+
+// --- Plain Output Task ---
+// Prints worker events as plain text, for CI and other non-interactive environments
+pub async fn run_plain_task(
+    rpc_client: Arc<RpcClient>,
+    signer_pool: Arc<SignerPool>,
+    mode: OperationMode,
+    whitelist: RentWhitelist,
+) -> Result<(), KoraError> {
+    let mut rx = spawn_worker(rpc_client, signer_pool, mode, whitelist);
+
+    while let Some(event) = rx.recv().await {
+        if matches!(event, UiEvent::TaskComplete) {
+            break;
+        }
+        if let Some(line) = plain_event_line(&event) {
+            println!("{}", line);
+        }
+    }
+
+    Ok(())
+}
+
+// --- Main TUI Task ---
+// Runs the TUI and spawns background worker tasks
+pub async fn run_tui_task(
+    rpc_client: Arc<RpcClient>,
+    signer_pool: Arc<SignerPool>, 
+    mode: OperationMode,
+    whitelist: RentWhitelist,
+) -> Result<(), KoraError> {
+    enable_raw_mode().unwrap();
+    let mut stdout = std::io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture).unwrap();
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    let mut rx = spawn_worker(rpc_client, signer_pool, mode, whitelist);
+
     let mut app = AppState::default();
 
     loop {
@@ -132,6 +226,7 @@ pub async fn run_tui_task(
                     app.reclaimed_count += count;
                 },
                 UiEvent::Status(msg) => app.status_msg = msg,
+                UiEvent::NextRun(at) => app.next_run = Some(at),
                 UiEvent::TaskComplete => app.is_working = false,
                 UiEvent::Alert(is_active, amount) => { 
                     app.is_high_rent = is_active;
@@ -146,7 +241,7 @@ pub async fn run_tui_task(
 
         if event::poll(Duration::from_millis(100)).unwrap() {
             if let Event::Key(key) = event::read().unwrap() {
-                if key.code == KeyCode::Char('q') {
+                if key.kind == KeyEventKind::Press && app.handle_key(key.code) {
                     break;
                 }
             }
@@ -162,17 +257,41 @@ pub async fn run_tui_task(
 
 // --- Core Logic Functions ---
 
+// Classify an account's rent status from its owner, data length and lamports
+pub fn classify_account_rent_status(
+    owner: &Pubkey,
+    data_len: usize,
+    lamports: u64,
+    rent: &Rent,
+) -> AccountRentStatus {
+    // Zero-data System accounts are plain wallets, there is no rent deposit to reclaim
+    if *owner == SYSTEM_PROGRAM_ID && data_len == 0 {
+        return AccountRentStatus::SystemAccount;
+    }
+
+    let rent_exempt_minimum = rent.minimum_balance(data_len);
+    if lamports == rent_exempt_minimum {
+        AccountRentStatus::ExactlyRentExempt
+    } else if lamports > rent_exempt_minimum {
+        AccountRentStatus::RentExempt { excess_lamports: lamports - rent_exempt_minimum }
+    } else {
+        AccountRentStatus::BelowRentExempt { shortfall_lamports: rent_exempt_minimum - lamports }
+    }
+}
+
 // Scan accounts and report reclaimable ones
 async fn scan_accounts(
     rpc_client: Arc<RpcClient>,
     signer_pool: &SignerPool,
     show_all: bool,
+    whitelist: &RentWhitelist,
     tracker: &mut GracePeriodTracker,
     tx: Option<mpsc::UnboundedSender<UiEvent>>,
 ) -> Result<(), KoraError> {
     let signers_info = signer_pool.get_signers_info();
-    let (allowed_tokens, is_all_allowed) = get_allowed_tokens()?;
+    let allowed_tokens = get_allowed_tokens()?;
     let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let rent = Rent::default();
 
     let mut total_rent = 0;
     let mut total_count = 0;
@@ -185,10 +304,15 @@ async fn scan_accounts(
 
         for acc in accounts {
             let pubkey_str = acc.pubkey.to_string();
-            let is_allowed = is_all_allowed || allowed_tokens.contains(&acc.mint);
+            let is_allowed = allowed_tokens.has_token(&acc.mint.to_string());
             let is_empty = acc.amount == 0;
+            let rent_status = classify_account_rent_status(&acc.owner, acc.data_len, acc.lamports, &rent);
             
-            let (reason, is_actionable) = if !is_empty {
+            let (reason, is_actionable) = if whitelist.contains(&pubkey_str) {
+                // Whitelisted accounts never enter the grace period
+                tracker.pending_closures.remove(&pubkey_str);
+                (ReclaimReason::Whitelisted, false)
+            } else if !is_empty {
                 if tracker.pending_closures.remove(&pubkey_str).is_some() {
                     (ReclaimReason::FundedIgnored, false)
                 } else {
@@ -196,6 +320,9 @@ async fn scan_accounts(
                 }
             } else if is_allowed {
                 (ReclaimReason::AllowedPaymentToken, false)
+            } else if !rent_status.is_reclaimable() {
+                tracker.pending_closures.remove(&pubkey_str);
+                (ReclaimReason::RentNotReclaimable, false)
             } else {
                 if let Some(&timestamp) = tracker.pending_closures.get(&pubkey_str) {
                     let age = now.saturating_sub(timestamp);
@@ -210,7 +337,7 @@ async fn scan_accounts(
                 }
             };
 
-            if is_actionable || show_all || reason == ReclaimReason::NewDetection || reason == ReclaimReason::AllowedPaymentToken {
+            if is_actionable || show_all || matches!(reason, ReclaimReason::NewDetection | ReclaimReason::AllowedPaymentToken | ReclaimReason::Whitelisted) {
                 let status_str = match reason {
                     ReclaimReason::ZeroBalance => "RECLAIMABLE",
                     ReclaimReason::InactiveGracePeriodPassed => "RECLAIMABLE (Safe)",
@@ -219,9 +346,18 @@ async fn scan_accounts(
                     ReclaimReason::FundedIgnored => "FUNDED",
                     ReclaimReason::NewDetection => "PENDING",
                     ReclaimReason::ForceClosed => "FORCE CLOSED",
+                    ReclaimReason::RentNotReclaimable => "NOT RECLAIMABLE",
+                    ReclaimReason::ProgramAccountRecovered => "RECOVERED",
+                    ReclaimReason::Whitelisted => "KEEP (Whitelisted)",
                 };
 
-                let color = if is_actionable { Color::Green } else { Color::Yellow };
+                let color = if is_actionable {
+                    Color::Green
+                } else if reason == ReclaimReason::Whitelisted {
+                    Color::Blue
+                } else {
+                    Color::Yellow
+                };
 
                 let details = format!(
                     "[{}] Mint: {} | Rent: {:.4} SOL | Bal: {}",
@@ -249,12 +385,14 @@ async fn reclaim_rent(
     execute: bool,
     force_all: bool,
     show_skipped: bool,
+    whitelist: &RentWhitelist,
     tracker: &mut GracePeriodTracker,
     tx: Option<mpsc::UnboundedSender<UiEvent>>,
 ) -> Result<(), KoraError> {
     let signers_info = signer_pool.get_signers_info();
-    let (allowed_tokens, is_all_allowed) = get_allowed_tokens()?;
+    let allowed_tokens = get_allowed_tokens()?;
     let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let rent = Rent::default();
 
     let mut reclaimed_rent = 0;
     let mut reclaimed_count = 0;
@@ -273,6 +411,20 @@ async fn reclaim_rent(
         for acc in accounts {
             let pubkey_str = acc.pubkey.to_string();
 
+            // Whitelisted accounts are never closed, not even with --force-all
+            if whitelist.contains(&pubkey_str) {
+                if show_skipped {
+                    let details = format!(
+                        "[SKIP: Whitelisted] Mint: {} | Rent: {:.4} SOL | Bal: {}",
+                        acc.mint, lamports_to_sol(acc.lamports), acc.amount
+                    );
+                    log_output!(&tx, acc.pubkey.to_string(), details, Color::Blue);
+                }
+
+                tracker.pending_closures.remove(&pubkey_str);
+                continue;
+            }
+
             if acc.amount == 0 {
                 locked_rent_accumulated += acc.lamports;
             }
@@ -292,7 +444,22 @@ async fn reclaim_rent(
                 continue; 
             }
 
-            let is_allowed = is_all_allowed || allowed_tokens.contains(&acc.mint);
+            // System-owned or under-funded accounts have no rent deposit to recover
+            let rent_status = classify_account_rent_status(&acc.owner, acc.data_len, acc.lamports, &rent);
+            if !rent_status.is_reclaimable() {
+                if !execute && show_skipped {
+                    let details = format!(
+                        "[SKIP: Not Reclaimable] Mint: {} | Rent: {:.4} SOL | Status: {:?}",
+                        acc.mint, lamports_to_sol(acc.lamports), rent_status
+                    );
+                    log_output!(&tx, acc.pubkey.to_string(), details, Color::DarkGray);
+                }
+
+                tracker.pending_closures.remove(&pubkey_str);
+                continue;
+            }
+
+            let is_allowed = allowed_tokens.has_token(&acc.mint.to_string());
             
             let (is_safe_time, reason) = if let Some(&timestamp) = tracker.pending_closures.get(&pubkey_str) {
                 if (now.saturating_sub(timestamp)) >= GRACE_PERIOD_SECONDS {
@@ -328,6 +495,7 @@ async fn reclaim_rent(
                 if execute {
                     match close_account(&rpc_client, &signer, &acc, &signer_pubkey).await {
                         Ok(sig) => {
+                            signer_pool.record_signature(&signer_pubkey);
                             log_output!(&tx, acc.pubkey.to_string(), format!("[CLOSED] Sig: {}", sig), Color::Green);
                             reclaimed_rent += acc.lamports;
                             reclaimed_count += 1;
@@ -345,6 +513,7 @@ async fn reclaim_rent(
                                 reason: format!("{:?}", final_reason),
                                 rent_reclaimed_sol: rent_in_sol,
                                 signature: sig,
+                                signer: signer_pubkey.to_string(),
                             });
 
                             tracker.pending_closures.remove(&pubkey_str);
@@ -400,4 +569,150 @@ async fn reclaim_rent(
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Recover rent from empty accounts owned by a specific program
+pub async fn recover_program_accounts(
+    rpc_client: Arc<RpcClient>,
+    signer_pool: &SignerPool,
+    program_id: &str,
+    close_instruction_data: Option<String>,
+    execute: bool,
+) -> Result<(), KoraError> {
+    // Recover runs without the TUI, output goes straight to stdout
+    let tx: Option<mpsc::UnboundedSender<UiEvent>> = None;
+
+    let program_id = Pubkey::from_str(program_id)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid program id {}: {}", program_id, e)))?;
+
+    // Without close instruction data only system-owned accounts can be closed (plain lamport transfer)
+    let close_data = match close_instruction_data {
+        Some(data) => Some(general_purpose::STANDARD.decode(&data).map_err(|e| {
+            KoraError::ValidationError(format!("Invalid --close-instruction-data: {}", e))
+        })?),
+        None if program_id == SYSTEM_PROGRAM_ID => None,
+        None => {
+            return Err(KoraError::ValidationError(format!(
+                "No close instruction known for program {}, pass --close-instruction-data",
+                program_id
+            )));
+        }
+    };
+
+    let payer = signer_pool.get_next_signer()?;
+    let payer_pubkey = payer.pubkey();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+    let accounts = fetch_empty_program_accounts(&rpc_client, &program_id).await?;
+    let mut recoverable = Vec::new();
+
+    for acc in accounts {
+        // A system-owned account can only be drained with its own signature
+        if close_data.is_none() {
+            if acc.pubkey == payer_pubkey {
+                continue;
+            }
+            if signer_pool.get_signer_by_pubkey(&acc.pubkey.to_string()).is_err() {
+                let details = format!("[SKIP: No Signer] Rent: {:.4} SOL", lamports_to_sol(acc.lamports));
+                log_output!(&tx, acc.pubkey.to_string(), details, Color::DarkGray);
+                continue;
+            }
+        }
+
+        let details = format!("[RECOVERABLE] Program: {} | Rent: {:.4} SOL", program_id, lamports_to_sol(acc.lamports));
+        log_output!(&tx, acc.pubkey.to_string(), details, Color::Green);
+        recoverable.push(acc);
+    }
+
+    let total_lamports: u64 = recoverable.iter().map(|acc| acc.lamports).sum();
+    log_output!(&tx, "SUMMARY".to_string(), format!("{} Recoverable ({:.4} SOL)", recoverable.len(), lamports_to_sol(total_lamports)), Color::Cyan);
+
+    if !execute {
+        log_output!(&tx, "DRY RUN".to_string(), "Pass --execute to recover these accounts.".to_string(), Color::Yellow);
+        return Ok(());
+    }
+
+    let mut recovered_lamports = 0;
+    let mut recovered_count = 0;
+
+    for acc in recoverable {
+        let pubkey_str = acc.pubkey.to_string();
+
+        let result = match &close_data {
+            Some(data) => {
+                let ix = Instruction::new_with_bytes(
+                    program_id,
+                    data,
+                    vec![AccountMeta::new(acc.pubkey, false), AccountMeta::new(payer_pubkey, true)],
+                );
+                send_instruction(&rpc_client, ix, &payer_pubkey, &[payer.clone()]).await
+            }
+            None => {
+                let account_signer = signer_pool.get_signer_by_pubkey(&pubkey_str)?;
+                let ix = transfer(&acc.pubkey, &payer_pubkey, acc.lamports);
+                send_instruction(&rpc_client, ix, &payer_pubkey, &[payer.clone(), account_signer]).await
+            }
+        };
+
+        match result {
+            Ok(sig) => {
+                signer_pool.record_signature(&payer_pubkey);
+                if close_data.is_none() {
+                    signer_pool.record_signature(&acc.pubkey);
+                }
+                log_output!(&tx, pubkey_str.clone(), format!("[CLOSED] Sig: {}", sig), Color::Green);
+                recovered_lamports += acc.lamports;
+                recovered_count += 1;
+
+                log_to_audit_trail(&AuditRecord {
+                    timestamp: now,
+                    date_utc: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+                    account: pubkey_str,
+                    mint: program_id.to_string(),
+                    action: "RECOVERED".to_string(),
+                    reason: format!("{:?}", ReclaimReason::ProgramAccountRecovered),
+                    rent_reclaimed_sol: lamports_to_sol(acc.lamports),
+                    signature: sig,
+                    signer: payer_pubkey.to_string(),
+                });
+            }
+            Err(e) => log_output!(&tx, pubkey_str, format!("[FAILED] {}", e), Color::Red),
+        }
+    }
+
+    log_output!(&tx, "RECOVERED".to_string(), format!("{} Accts ({:.4} SOL)", recovered_count, lamports_to_sol(recovered_lamports)), Color::Green);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_on_schedule_runs_one_cycle() {
+        // Every second, so the next run is at most a second away
+        let schedule = cron::Schedule::from_str("* * * * * *").unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        run_on_schedule(&schedule, &tx, Some(1), || {
+            let tx = tx.clone();
+            async move {
+                let _ = tx.send(UiEvent::StatsUpdate { reclaimed: 0.00203928, count: 1 });
+            }
+        }).await;
+        drop(tx);
+
+        let mut next_run = None;
+        let mut reclaimed_count = 0;
+        while let Some(event) = rx.recv().await {
+            match event {
+                UiEvent::NextRun(at) => next_run = Some(at),
+                UiEvent::StatsUpdate { count, .. } => reclaimed_count += count,
+                _ => {}
+            }
+        }
+
+        assert!(next_run.is_some_and(|at| at.ends_with(" UTC")));
+        assert_eq!(reclaimed_count, 1);
+    }
+}
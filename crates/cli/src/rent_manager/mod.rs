@@ -5,15 +5,19 @@ pub mod tui;
 pub mod logic;
 pub mod utils;
 
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use kora_lib::error::KoraError;
 use kora_lib::signer::init::init_signers;
+use kora_lib::signer::pool::SignerPool;
 use kora_lib::state::get_signer_pool;
 use crate::RentManagerCommands;
-use self::logic::run_tui_task;
+use self::logic::{run_plain_task, run_tui_task, recover_program_accounts};
 use self::utils::show_stats;
 use self::types::OperationMode;
+use self::state::RentWhitelist;
 
 // --- Main Handler ---
 
@@ -21,12 +25,19 @@ pub async fn handle_rent_manager(
     command: RentManagerCommands,
     rpc_client: Arc<RpcClient>,
 ) -> Result<(), KoraError> {
-    
+    // Editing the whitelist needs no signer
+    if let RentManagerCommands::Whitelist { accounts, clear, whitelist_file } = command {
+        return update_whitelist(accounts, clear, whitelist_file);
+    }
+
     let rpc_args = match &command {
         RentManagerCommands::Scan { rpc_args, .. } => rpc_args,
         RentManagerCommands::Reclaim { rpc_args, .. } => rpc_args,
         RentManagerCommands::Run { rpc_args, .. } => rpc_args,
+        RentManagerCommands::Schedule { rpc_args, .. } => rpc_args,
         RentManagerCommands::Stats { rpc_args } => rpc_args,
+        RentManagerCommands::Recover { rpc_args, .. } => rpc_args,
+        RentManagerCommands::Whitelist { .. } => unreachable!("whitelist is handled before signer setup"),
     };
 
     if !rpc_args.skip_signer {
@@ -43,16 +54,73 @@ pub async fn handle_rent_manager(
         RentManagerCommands::Stats { .. } => {
             show_stats(rpc_client, &signer_pool).await?;
         },
-        RentManagerCommands::Scan { all, .. } => {
-            run_tui_task(rpc_client, signer_pool, OperationMode::Scan { all }).await?;
+        RentManagerCommands::Scan { all, no_tui, whitelist_file, .. } => {
+            let whitelist = load_whitelist(whitelist_file)?;
+            run_task(rpc_client, signer_pool, OperationMode::Scan { all }, no_tui, whitelist).await?;
+        },
+        RentManagerCommands::Reclaim { execute, force_all, no_tui, whitelist_file, .. } => {
+            let whitelist = load_whitelist(whitelist_file)?;
+            run_task(rpc_client, signer_pool, OperationMode::Reclaim { execute, force_all }, no_tui, whitelist).await?;
         },
-        RentManagerCommands::Reclaim { execute, force_all, .. } => {
-            run_tui_task(rpc_client, signer_pool, OperationMode::Reclaim { execute, force_all }).await?;
+        RentManagerCommands::Run { interval, no_tui, whitelist_file, .. } => {
+            let whitelist = load_whitelist(whitelist_file)?;
+            run_task(rpc_client, signer_pool, OperationMode::Daemon { interval }, no_tui, whitelist).await?;
         },
-        RentManagerCommands::Run { interval, .. } => {
-            run_tui_task(rpc_client, signer_pool, OperationMode::Daemon { interval }).await?;
+        RentManagerCommands::Schedule { cron_expr, execute, no_tui, whitelist_file, .. } => {
+            let schedule = cron::Schedule::from_str(&cron_expr).map_err(|e| {
+                KoraError::ValidationError(format!("Invalid cron expression '{}': {}", cron_expr, e))
+            })?;
+            let whitelist = load_whitelist(whitelist_file)?;
+            run_task(rpc_client, signer_pool, OperationMode::Scheduled { schedule, execute }, no_tui, whitelist).await?;
+        },
+        RentManagerCommands::Recover { program_id, close_instruction_data, execute, .. } => {
+            recover_program_accounts(rpc_client, &signer_pool, &program_id, close_instruction_data, execute).await?;
         }
+        RentManagerCommands::Whitelist { .. } => unreachable!("whitelist is handled before signer setup"),
+    }
+
+    Ok(())
+}
+
+// Run an operation in the TUI, or with plain-text output when --no-tui is set
+async fn run_task(
+    rpc_client: Arc<RpcClient>,
+    signer_pool: Arc<SignerPool>,
+    mode: OperationMode,
+    no_tui: bool,
+    whitelist: RentWhitelist,
+) -> Result<(), KoraError> {
+    if no_tui {
+        run_plain_task(rpc_client, signer_pool, mode, whitelist).await
+    } else {
+        run_tui_task(rpc_client, signer_pool, mode, whitelist).await
     }
+}
+
+fn load_whitelist(whitelist_file: Option<PathBuf>) -> Result<RentWhitelist, KoraError> {
+    RentWhitelist::load(&RentWhitelist::resolve_path(whitelist_file))
+}
+
+// Clear and/or extend the whitelist, then print it
+fn update_whitelist(
+    accounts: Vec<String>,
+    clear: bool,
+    whitelist_file: Option<PathBuf>,
+) -> Result<(), KoraError> {
+    let path = RentWhitelist::resolve_path(whitelist_file);
+    let mut whitelist = RentWhitelist::load(&path)?;
 
+    if clear {
+        whitelist.accounts.clear();
+    }
+    whitelist.add(&accounts)?;
+    if clear || !accounts.is_empty() {
+        whitelist.save(&path)?;
+    }
+
+    println!("Whitelist {} ({} accounts):", path.display(), whitelist.accounts.len());
+    for account in &whitelist.accounts {
+        println!("  {}", account);
+    }
     Ok(())
 }
\ No newline at end of file
@@ -1,9 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use serde::{Deserialize, Serialize};
+use crossterm::event::KeyCode;
 use ratatui::style::Color;
-use super::config::TRACKER_FILE;
+use solana_sdk::pubkey::Pubkey;
+use kora_lib::error::KoraError;
+use super::config::{TRACKER_FILE, WHITELIST_FILE};
+use super::types::{LogColumn, SortDirection};
 
 
 // --- Grace Period Tracker ---
@@ -28,6 +33,66 @@ impl GracePeriodTracker {
     }
 }
 
+// --- Rent Whitelist ---
+/// Accounts the scan and reclaim never mark for closing, managed with `rent-manager whitelist`
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct RentWhitelist {
+    pub accounts: BTreeSet<String>,
+}
+
+impl RentWhitelist {
+    // --whitelist-file if set, ~/.kora/rent_whitelist.json otherwise
+    pub fn resolve_path(path: Option<PathBuf>) -> PathBuf {
+        path.unwrap_or_else(|| {
+            let home = std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(PathBuf::from)
+                .unwrap_or_default();
+            home.join(WHITELIST_FILE)
+        })
+    }
+
+    // A missing file is an empty whitelist, an unreadable one is an error so nothing gets
+    // closed by accident
+    pub fn load(path: &Path) -> Result<Self, KoraError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = fs::read_to_string(path).map_err(|e| {
+            KoraError::InternalServerError(format!("Failed to read whitelist {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&data).map_err(|e| {
+            KoraError::ValidationError(format!("Invalid whitelist {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), KoraError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| KoraError::InternalServerError(e.to_string()))?;
+        }
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|e| KoraError::SerializationError(e.to_string()))?;
+        fs::write(path, json).map_err(|e| {
+            KoraError::InternalServerError(format!("Failed to write whitelist {}: {}", path.display(), e))
+        })
+    }
+
+    // Adds the accounts, rejecting the whole batch if any of them is not a valid address
+    pub fn add(&mut self, accounts: &[String]) -> Result<(), KoraError> {
+        for account in accounts {
+            Pubkey::from_str(account).map_err(|e| {
+                KoraError::ValidationError(format!("Invalid account {}: {}", account, e))
+            })?;
+        }
+        self.accounts.extend(accounts.iter().cloned());
+        Ok(())
+    }
+
+    pub fn contains(&self, account: &str) -> bool {
+        self.accounts.contains(account)
+    }
+}
+
 // --- Application State for TUI ---
 pub struct AppState {
     pub logs: Vec<(String, String, Color)>, 
@@ -38,6 +103,11 @@ pub struct AppState {
     pub is_working: bool,
     pub is_high_rent: bool,       
     pub current_locked_rent: f64, 
+    pub next_run: Option<String>,
+    pub selected: usize,
+    pub sort: Option<(LogColumn, SortDirection)>,
+    pub search_query: String,
+    pub is_searching: bool,
 }
 
 impl Default for AppState {
@@ -51,6 +121,122 @@ impl Default for AppState {
             is_working: true,
             is_high_rent: false, 
             current_locked_rent: 0.0,
+            next_run: None,
+            selected: 0,
+            sort: None,
+            search_query: String::new(),
+            is_searching: false,
+        }
+    }
+}
+
+impl AppState {
+    // Logs as displayed: filtered by the search query, then sorted (newest first when unsorted)
+    pub fn visible_logs(&self) -> Vec<&(String, String, Color)> {
+        let query = self.search_query.to_lowercase();
+        let mut logs: Vec<_> = self.logs.iter().rev()
+            .filter(|(acc, details, _)| {
+                query.is_empty()
+                    || acc.to_lowercase().contains(&query)
+                    || details.to_lowercase().contains(&query)
+            })
+            .collect();
+
+        if let Some((column, direction)) = self.sort {
+            logs.sort_by(|a, b| {
+                let ordering = match column {
+                    LogColumn::Account => a.0.cmp(&b.0),
+                    LogColumn::Details => a.1.cmp(&b.1),
+                };
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }
+
+        logs
+    }
+
+    // First press sorts ascending, pressing the same column again flips the direction
+    pub fn toggle_sort(&mut self, column: LogColumn) {
+        self.sort = match self.sort {
+            Some((current, SortDirection::Ascending)) if current == column => {
+                Some((column, SortDirection::Descending))
+            },
+            _ => Some((column, SortDirection::Ascending)),
+        };
+        self.selected = 0;
+    }
+
+    // Apply a key press, returns true when the user asked to quit
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.is_searching {
+            match code {
+                KeyCode::Enter => self.is_searching = false,
+                KeyCode::Esc => {
+                    self.is_searching = false;
+                    self.search_query.clear();
+                },
+                KeyCode::Backspace => { self.search_query.pop(); },
+                KeyCode::Char(c) => self.search_query.push(c),
+                _ => {}
+            }
+            self.selected = 0;
+            return false;
         }
+
+        let last = self.visible_logs().len().saturating_sub(1);
+        match code {
+            KeyCode::Char('q') => return true,
+            KeyCode::Char('j') | KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('g') => self.selected = 0,
+            KeyCode::Char('G') => self.selected = last,
+            KeyCode::Char('/') => self.is_searching = true,
+            KeyCode::Esc => {
+                self.search_query.clear();
+                self.selected = 0;
+            },
+            KeyCode::Char(c) => {
+                if let Some(column) = LogColumn::ALL.into_iter().find(|column| column.key() == c) {
+                    self.toggle_sort(column);
+                }
+            },
+            _ => {}
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rent_whitelist_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("kora_whitelist_test_{}", std::process::id()))
+            .join("rent_whitelist.json");
+        let account = Pubkey::new_unique().to_string();
+
+        // Missing file loads as empty
+        assert_eq!(RentWhitelist::load(&path).unwrap(), RentWhitelist::default());
+
+        let mut whitelist = RentWhitelist::default();
+        assert!(whitelist.add(&[account.clone(), "not-a-pubkey".to_string()]).is_err());
+        assert!(whitelist.accounts.is_empty());
+
+        whitelist.add(&[account.clone()]).unwrap();
+        whitelist.save(&path).unwrap();
+
+        let loaded = RentWhitelist::load(&path).unwrap();
+        assert!(loaded.contains(&account));
+        assert_eq!(loaded, whitelist);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(RentWhitelist::load(&path).is_err());
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
     }
-}
\ No newline at end of file
+}
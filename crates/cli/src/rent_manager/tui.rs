@@ -1,8 +1,9 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table, TableState},
 };
 use super::state::AppState;
+use super::types::{LogColumn, SortDirection, UiEvent};
 
 
 // --- UI Rendering Function ---
@@ -36,11 +37,14 @@ pub fn ui(f: &mut Frame, app: &AppState) {
         (Color::Green, " Performance Metrics ")
     };
 
-    let kpi_text = vec![
+    let mut kpi_text = vec![
         Line::from(vec![Span::raw("Reclaimed SOL:   "), Span::styled(format!("{:.4}", app.total_reclaimed_sol), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))]),
         Line::from(vec![Span::raw("Current Locked:  "), Span::styled(format!("{:.4} SOL", app.current_locked_rent), Style::default().fg(alert_color).add_modifier(Modifier::BOLD))]),
         Line::from(vec![Span::raw("Accounts Closed: "), Span::styled(format!("{}", app.reclaimed_count), Style::default().fg(Color::Yellow))]),
     ];
+    if let Some(next_run) = &app.next_run {
+        kpi_text.push(Line::from(vec![Span::raw("Next Run:        "), Span::styled(next_run.clone(), Style::default().fg(Color::Cyan))]));
+    }
     let kpi_block = Paragraph::new(kpi_text)
         .block(Block::default().title(alert_title).borders(Borders::ALL).border_style(Style::default().fg(alert_color)));
     f.render_widget(kpi_block, stats_chunks[0]);
@@ -52,12 +56,22 @@ pub fn ui(f: &mut Frame, app: &AppState) {
         .label(if app.total_reclaimed_sol > 0.0 { "OPTIMIZED" } else { "IDLE" });
     f.render_widget(gauge, stats_chunks[1]);
 
-    let header_cells = ["Account", "Details"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+    let header_cells = LogColumn::ALL.iter().map(|column| {
+        let indicator = match app.sort {
+            Some((sorted, SortDirection::Ascending)) if sorted == *column => " ▲",
+            Some((sorted, SortDirection::Descending)) if sorted == *column => " ▼",
+            _ => "",
+        };
+        Cell::from(format!("{} [{}]{}", column.title(), column.key(), indicator))
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+    });
     let table_header = Row::new(header_cells).height(1).bottom_margin(1);
 
-    let rows = app.logs.iter().rev().map(|(acc, details, color)| {
+    let visible_logs = app.visible_logs();
+    let mut table_state = TableState::default()
+        .with_selected((!visible_logs.is_empty()).then(|| app.selected.min(visible_logs.len() - 1)));
+
+    let rows = visible_logs.iter().map(|(acc, details, color)| {
         let cells = vec![
             Cell::from(acc.clone()).style(Style::default().fg(*color).add_modifier(Modifier::BOLD)),
             Cell::from(details.clone()).style(Style::default().fg(*color)),
@@ -65,17 +79,66 @@ pub fn ui(f: &mut Frame, app: &AppState) {
         Row::new(cells)
     });
 
+    let table_title = if app.search_query.is_empty() {
+        " Live Logs ".to_string()
+    } else {
+        format!(" Live Logs (filter: {}) ", app.search_query)
+    };
+
     let t = Table::new(rows, [
             Constraint::Percentage(30),
             Constraint::Percentage(70),
         ])
         .header(table_header)
-        .block(Block::default().borders(Borders::ALL).title(" Live Logs "))
+        .block(Block::default().borders(Borders::ALL).title(table_title))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
         .column_spacing(1);
-    f.render_widget(t, chunks[2]);
+    f.render_stateful_widget(t, chunks[2], &mut table_state);
 
-    let footer = Paragraph::new(format!(" {} | Press 'q' to quit ", app.status_msg))
+    let footer_text = if app.is_searching {
+        format!(" /{} | Enter to apply, Esc to clear ", app.search_query)
+    } else {
+        format!(" {} | j/k move, g/G top/bottom, / search, a/d sort, q quit ", app.status_msg)
+    };
+    let footer = Paragraph::new(footer_text)
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     f.render_widget(footer, chunks[3]);
-}
\ No newline at end of file
+}
+
+// --- Plain Text Output ---
+// One line per worker event, used instead of the TUI with --no-tui
+pub fn plain_event_line(event: &UiEvent) -> Option<String> {
+    match event {
+        UiEvent::Log(acc, details, _) => Some(format!("{} | {}", acc, details)),
+        UiEvent::StatsUpdate { reclaimed, count } => {
+            Some(format!("Reclaimed {:.4} SOL from {} account(s)", reclaimed, count))
+        },
+        UiEvent::Status(msg) => Some(format!("[status] {}", msg)),
+        UiEvent::Alert(true, amount) => Some(format!("[alert] High rent locked: {:.4} SOL", amount)),
+        UiEvent::Alert(false, amount) => Some(format!("[alert] Rent locked back to normal: {:.4} SOL", amount)),
+        UiEvent::NextRun(at) => Some(format!("[schedule] Next run at {}", at)),
+        UiEvent::TaskComplete => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_event_output() {
+        let events = vec![
+            UiEvent::Status("🔍 Scanning...".to_string()),
+            UiEvent::Log("7xKXtg2C...".to_string(), "Zero balance, reclaimable".to_string(), Color::Green),
+            UiEvent::StatsUpdate { reclaimed: 0.00203928, count: 1 },
+            UiEvent::Alert(true, 1.5),
+            UiEvent::Alert(false, 0.25),
+            UiEvent::TaskComplete,
+        ];
+
+        let output = events.iter().filter_map(plain_event_line).collect::<Vec<_>>().join("\n");
+
+        insta::assert_snapshot!("plain_event_output", output);
+    }
+}
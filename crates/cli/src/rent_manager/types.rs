@@ -9,6 +9,32 @@ pub struct TokenAccountInfo {
     pub amount: u64,
     pub lamports: u64,
     pub program_id: Pubkey,
+    pub owner: Pubkey,
+    pub data_len: usize,
+}
+
+/// An account owned by an arbitrary program, as returned by `getProgramAccounts`
+pub struct ProgramAccountInfo {
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+}
+
+/// Rent classification of an account, derived from its owner, data length and lamports
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AccountRentStatus {
+    /// Zero-data account owned by the System program, exempt under system account rules
+    SystemAccount,
+    /// Lamports exactly equal the rent-exempt minimum
+    ExactlyRentExempt,
+    RentExempt { excess_lamports: u64 },
+    BelowRentExempt { shortfall_lamports: u64 },
+}
+
+impl AccountRentStatus {
+    /// Only rent-exempt program-owned accounts hold rent we can recover by closing them
+    pub fn is_reclaimable(&self) -> bool {
+        matches!(self, AccountRentStatus::ExactlyRentExempt | AccountRentStatus::RentExempt { .. })
+    }
 }
 
 /// Internal enum to track why an account is being processed or skipped
@@ -21,6 +47,9 @@ pub enum ReclaimReason {
     FundedIgnored,             
     NewDetection,              
     ForceClosed,               
+    RentNotReclaimable,
+    ProgramAccountRecovered,
+    Whitelisted,
 }
 
 /// Events sent from the Background Worker Thread -> UI Main Thread
@@ -30,6 +59,40 @@ pub enum UiEvent {
     Status(String),                             
     TaskComplete,                               
     Alert(bool, f64),                           
+    /// Time of the next scheduled cycle, in UTC
+    NextRun(String),
+}
+
+/// Columns of the live log table, each sortable by pressing its key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogColumn {
+    Account,
+    Details,
+}
+
+impl LogColumn {
+    pub const ALL: [LogColumn; 2] = [LogColumn::Account, LogColumn::Details];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            LogColumn::Account => "Account",
+            LogColumn::Details => "Details",
+        }
+    }
+
+    /// Key sorting the table by this column, shown next to the column title
+    pub fn key(&self) -> char {
+        match self {
+            LogColumn::Account => 'a',
+            LogColumn::Details => 'd',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
 }
 
 /// Defines what logic the worker thread executes
@@ -37,6 +100,7 @@ pub enum OperationMode {
     Scan { all: bool },
     Reclaim { execute: bool, force_all: bool },
     Daemon { interval: String },
+    Scheduled { schedule: cron::Schedule, execute: bool },
 }
 
 /// Structure for the CSV Audit Log.
@@ -50,4 +114,7 @@ pub struct AuditRecord {
     pub reason: String,
     pub rent_reclaimed_sol: f64,
     pub signature: String,
+    /// Signer the rent went back to, empty for records written before it was tracked
+    #[serde(default)]
+    pub signer: String,
 }
\ No newline at end of file
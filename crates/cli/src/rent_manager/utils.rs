@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::path::Path;
 use std::env;
@@ -15,9 +16,11 @@ use solana_sdk::{
 };
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::RpcFilterType,
     rpc_request::TokenAccountsFilter,
 };
-use solana_account_decoder::UiAccountData;
+use solana_account_decoder::{UiAccountData, UiAccountEncoding};
 use kora_lib::{
     error::KoraError,
     SolanaSigner,
@@ -25,7 +28,7 @@ use kora_lib::{
     config::SplTokenConfig,
     signer::pool::SignerPool,
 };
-use super::types::{TokenAccountInfo, AuditRecord};
+use super::types::{TokenAccountInfo, ProgramAccountInfo, AuditRecord};
 use super::config::AUDIT_FILE;
 
 // --- Macros ---
@@ -98,8 +101,10 @@ pub async fn fetch_all_token_accounts(
         for keyed in accounts {
             if let Some((amount, mint)) = parse_token_account_data(&keyed.account.data) {
                 if let Ok(pubkey) = Pubkey::from_str(&keyed.pubkey) {
+                    let owner = Pubkey::from_str(&keyed.account.owner).unwrap_or(program_id);
+                    let data_len = detect_account_data_len(keyed.account.space, &keyed.account.data);
                     all_accounts.push(TokenAccountInfo {
-                        pubkey, mint, amount, lamports: keyed.account.lamports, program_id,
+                        pubkey, mint, amount, lamports: keyed.account.lamports, program_id, owner, data_len,
                     });
                 }
             }
@@ -108,6 +113,31 @@ pub async fn fetch_all_token_accounts(
     Ok(all_accounts)
 }
 
+// Fetch all zero-data accounts owned by a program
+pub async fn fetch_empty_program_accounts(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+) -> Result<Vec<ProgramAccountInfo>, KoraError> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::DataSize(0)]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let accounts = rpc_client
+        .get_program_accounts_with_config(program_id, config)
+        .await
+        .map_err(|e| KoraError::InternalServerError(format!("RPC Error: {}", e)))?;
+
+    Ok(accounts
+        .into_iter()
+        .map(|(pubkey, account)| ProgramAccountInfo { pubkey, lamports: account.lamports })
+        .collect())
+}
+
 // Parse token account data from UiAccountData
 fn parse_token_account_data(data: &UiAccountData) -> Option<(u64, Pubkey)> {
     match data {
@@ -131,6 +161,21 @@ fn parse_token_account_data(data: &UiAccountData) -> Option<(u64, Pubkey)> {
     }
 }
 
+// Detect the account data length, preferring the size reported by the RPC node
+fn detect_account_data_len(space: Option<u64>, data: &UiAccountData) -> usize {
+    if let Some(space) = space {
+        return space as usize;
+    }
+    match data {
+        UiAccountData::Binary(data_str, _) => general_purpose::STANDARD
+            .decode(data_str)
+            .map(|bytes| bytes.len())
+            .unwrap_or(spl_token_interface::state::Account::LEN),
+        // jsonParsed responses drop the raw bytes, fall back to the base token account size
+        _ => spl_token_interface::state::Account::LEN,
+    }
+}
+
 // Close a token account
 pub async fn close_account(
     rpc_client: &RpcClient,
@@ -164,14 +209,40 @@ pub async fn close_account(
         .map_err(|e| KoraError::InternalServerError(e.to_string()))
 }
 
+// Sign and send a single instruction, every signer fills its own signature slot
+pub async fn send_instruction(
+    rpc_client: &RpcClient,
+    ix: Instruction,
+    payer: &Pubkey,
+    signers: &[Arc<impl SolanaSigner>],
+) -> Result<String, KoraError> {
+    let recent_blockhash = rpc_client.get_latest_blockhash().await
+        .map_err(|e| KoraError::InternalServerError(e.to_string()))?;
+
+    let mut tx = Transaction::new_with_payer(&[ix], Some(payer));
+    tx.message.recent_blockhash = recent_blockhash;
+
+    let message_bytes = tx.message.serialize();
+    let num_signers = tx.message.header.num_required_signatures as usize;
+
+    for signer in signers {
+        let position = tx.message.account_keys[..num_signers]
+            .iter()
+            .position(|key| *key == signer.pubkey())
+            .ok_or_else(|| KoraError::InternalServerError(format!("{} is not a required signer", signer.pubkey())))?;
+
+        tx.signatures[position] = signer.sign_message(&message_bytes).await
+            .map_err(|e| KoraError::InternalServerError(e.to_string()))?;
+    }
+
+    rpc_client.send_and_confirm_transaction(&tx).await
+        .map(|s| s.to_string())
+        .map_err(|e| KoraError::InternalServerError(e.to_string()))
+}
+
 // Get allowed SPL tokens from config
-pub fn get_allowed_tokens() -> Result<(Vec<Pubkey>, bool), KoraError> {
-    let config = get_config()?;
-    let is_all = matches!(config.validation.allowed_spl_paid_tokens, SplTokenConfig::All);
-    let tokens = if is_all { vec![] } else {
-        config.validation.allowed_spl_paid_tokens.as_slice().iter().filter_map(|t| t.parse().ok()).collect()
-    };
-    Ok((tokens, is_all))
+pub fn get_allowed_tokens() -> Result<SplTokenConfig, KoraError> {
+    Ok(get_config()?.validation.allowed_spl_paid_tokens)
 }
 
 // Convert lamports to SOL
@@ -186,6 +257,7 @@ pub async fn show_stats(
 ) -> Result<(), KoraError> {
     let signers_info = signer_pool.get_signers_info();
     
+    let mut signer_balances = HashMap::new();
     let mut total_accounts = 0;
     let mut idle_accounts = 0;
     let mut rent_locked_lamports = 0;
@@ -194,6 +266,10 @@ pub async fn show_stats(
 
     for signer_info in signers_info {
         let signer_pubkey = signer_info.public_key.parse::<Pubkey>().unwrap();
+        let balance = rpc_client.get_balance(&signer_pubkey).await
+            .map_err(|e| KoraError::InternalServerError(format!("RPC Error: {}", e)))?;
+        signer_balances.insert(signer_info.public_key.clone(), balance);
+
         let accounts = fetch_all_token_accounts(&rpc_client, &signer_pubkey).await?;
         
         for acc in accounts {
@@ -207,10 +283,13 @@ pub async fn show_stats(
 
     let mut rent_reclaimed_30d = 0.0;
     let mut total_reclaimed_ever = 0.0;
+    // Signer pubkey -> (SOL recovered, last audit timestamp)
+    let mut reclaimed_by_signer: HashMap<String, (f64, u64)> = HashMap::new();
     
     if Path::new(AUDIT_FILE).exists() {
         let file = File::open(AUDIT_FILE).map_err(|e| KoraError::InternalServerError(e.to_string()))?;
-        let mut rdr = csv::Reader::from_reader(file);
+        // Records written before the signer column existed have one field less
+        let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(file);
         
         let now = SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
         let thirty_days_ago = now.saturating_sub(30 * 24 * 60 * 60);
@@ -219,6 +298,10 @@ pub async fn show_stats(
             if let Ok(record) = result {
                 let record: AuditRecord = record;
                 total_reclaimed_ever += record.rent_reclaimed_sol;
+
+                let entry = reclaimed_by_signer.entry(record.signer.clone()).or_default();
+                entry.0 += record.rent_reclaimed_sol;
+                entry.1 = entry.1.max(record.timestamp);
                 
                 if record.timestamp >= thirty_days_ago {
                     rent_reclaimed_30d += record.rent_reclaimed_sol;
@@ -245,5 +328,62 @@ pub async fn show_stats(
     println!("Efficiency Gain:          {:.2}%", efficiency);
     println!("--------------------------");
 
+    let rows: Vec<SignerStatsRow> = signer_pool.get_signers_info()
+        .into_iter()
+        .map(|info| {
+            let (recovered_sol, last_reclaim) = reclaimed_by_signer.get(&info.public_key).copied().unwrap_or_default();
+            SignerStatsRow {
+                balance_sol: lamports_to_sol(signer_balances.get(&info.public_key).copied().unwrap_or_default()),
+                tx_count: info.tx_count,
+                recovered_sol,
+                last_active: info.last_active.max(last_reclaim),
+                name: info.name,
+                pubkey: info.public_key,
+            }
+        })
+        .collect();
+
+    println!("\n🔑 SIGNER POOL STATS");
+    for line in format_signer_stats_table(&rows) {
+        println!("{}", line);
+    }
+
     Ok(())
+}
+
+/// One line of the per-signer table printed by `show_stats`
+pub struct SignerStatsRow {
+    pub name: String,
+    pub pubkey: String,
+    pub tx_count: u64,
+    pub recovered_sol: f64,
+    pub balance_sol: f64,
+    /// Unix timestamp, 0 if the signer was never active
+    pub last_active: u64,
+}
+
+// Render the per-signer stats with every column aligned
+pub fn format_signer_stats_table(rows: &[SignerStatsRow]) -> Vec<String> {
+    let name_width = rows.iter().map(|row| row.name.len()).max().unwrap_or(0).max("Signer".len());
+    let pubkey_width = rows.iter().map(|row| row.pubkey.len()).max().unwrap_or(0).max("Pubkey".len());
+
+    let header = format!(
+        "{:<name_width$}  {:<pubkey_width$}  {:>8}  {:>15}  {:>13}  {}",
+        "Signer", "Pubkey", "Signed", "Recovered (SOL)", "Balance (SOL)", "Last Active",
+    );
+    let mut lines = vec![header.clone(), "-".repeat(header.chars().count())];
+
+    for row in rows {
+        let last_active = if row.last_active == 0 {
+            "never".to_string()
+        } else {
+            humantime::format_rfc3339_seconds(std::time::UNIX_EPOCH + std::time::Duration::from_secs(row.last_active)).to_string()
+        };
+        lines.push(format!(
+            "{:<name_width$}  {:<pubkey_width$}  {:>8}  {:>15.4}  {:>13.4}  {}",
+            row.name, row.pubkey, row.tx_count, row.recovered_sol, row.balance_sol, last_active,
+        ));
+    }
+
+    lines
 }
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+const KORA_TOML: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../kora.toml");
+
+fn validate(extra_args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_kora"))
+        .args(["--config", KORA_TOML, "config", "validate", "--output-format", "json"])
+        .args(extra_args)
+        .output()
+        .expect("failed to run kora")
+}
+
+#[test]
+fn test_config_validate_json_output() {
+    let output = validate(&[]);
+    assert!(output.status.success());
+
+    let result: Value = serde_json::from_slice(&output.stdout).expect("stdout is not JSON");
+    let errors = result["errors"].as_array().unwrap();
+    assert_eq!(result["success"].as_bool().unwrap(), errors.is_empty());
+    assert!(result["warnings"].is_array());
+
+    // The human-readable report moves to stderr
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("=== Configuration Validation ==="));
+}
+
+#[test]
+fn test_config_validate_json_output_errors() {
+    let output = validate(&["--signers-config", "does-not-exist.toml"]);
+    assert!(output.status.success());
+
+    let result: Value = serde_json::from_slice(&output.stdout).expect("stdout is not JSON");
+    assert_eq!(result["success"], false);
+    assert!(result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|e| e.as_str().unwrap().contains("Failed to load signers config")));
+}
@@ -0,0 +1,108 @@
+//! Cache access for 100 keys, one Redis round-trip per key vs. a single `MGET` or `SETEX`
+//! pipeline as used by `CacheUtil::batch_get` and `CacheUtil::batch_set`.
+//!
+//! Needs a running Redis, `REDIS_URL` defaults to `redis://127.0.0.1:6379`. Skipped otherwise.
+//!
+//! Run with `cargo bench -p kora-lib --bench cache_batch`
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use redis::{aio::MultiplexedConnection, AsyncCommands};
+use tokio::runtime::Runtime;
+
+const KEYS: usize = 100;
+const TTL_SECONDS: u64 = 60;
+const MIN_SPEEDUP: f64 = 5.0;
+
+async fn single_set(conn: &mut MultiplexedConnection, keys: &[String]) {
+    for key in keys {
+        conn.set_ex::<_, _, ()>(key, key, TTL_SECONDS).await.unwrap();
+    }
+}
+
+async fn batch_set(conn: &mut MultiplexedConnection, keys: &[String]) {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.set_ex(key, key, TTL_SECONDS).ignore();
+    }
+    pipe.query_async::<()>(conn).await.unwrap();
+}
+
+async fn single_get(conn: &mut MultiplexedConnection, keys: &[String]) -> Vec<Option<String>> {
+    let mut values = Vec::with_capacity(keys.len());
+    for key in keys {
+        values.push(conn.get(key).await.unwrap());
+    }
+    values
+}
+
+async fn batch_get(conn: &mut MultiplexedConnection, keys: &[String]) -> Vec<Option<String>> {
+    redis::cmd("MGET").arg(keys).query_async(conn).await.unwrap()
+}
+
+fn connect(rt: &Runtime) -> Option<MultiplexedConnection> {
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+    let client = redis::Client::open(url).ok()?;
+    rt.block_on(client.get_multiplexed_async_connection()).ok()
+}
+
+/// Time `rounds` runs of `f`, used for the speedup check outside of criterion's sampling
+fn time_rounds<F: FnMut()>(rounds: u32, mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..rounds {
+        f();
+    }
+    start.elapsed()
+}
+
+fn cache_batch(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let Some(mut conn) = connect(&rt) else {
+        eprintln!("Redis is not reachable, skipping the cache_batch benchmark");
+        return;
+    };
+
+    let keys: Vec<String> = (0..KEYS).map(|i| format!("kora_bench:{i}")).collect();
+    rt.block_on(batch_set(&mut conn, &keys));
+
+    // Every key must round-trip through both paths before comparing them
+    let expected: Vec<Option<String>> = keys.iter().cloned().map(Some).collect();
+    assert_eq!(rt.block_on(single_get(&mut conn, &keys)), expected);
+    assert_eq!(rt.block_on(batch_get(&mut conn, &keys)), expected);
+
+    let single = time_rounds(20, || {
+        rt.block_on(single_get(&mut conn, &keys));
+    });
+    let batch = time_rounds(20, || {
+        rt.block_on(batch_get(&mut conn, &keys));
+    });
+    let speedup = single.as_secs_f64() / batch.as_secs_f64();
+    assert!(speedup >= MIN_SPEEDUP, "batch get is only {speedup:.1}x faster than single gets");
+
+    let mut group = c.benchmark_group("cache_batch");
+
+    group.bench_function("single_get", |b| {
+        b.iter(|| rt.block_on(single_get(&mut conn, black_box(&keys))))
+    });
+
+    group.bench_function("batch_get", |b| {
+        b.iter(|| rt.block_on(batch_get(&mut conn, black_box(&keys))))
+    });
+
+    group.bench_function("single_set", |b| {
+        b.iter(|| rt.block_on(single_set(&mut conn, black_box(&keys))))
+    });
+
+    group.bench_function("batch_set", |b| {
+        b.iter(|| rt.block_on(batch_set(&mut conn, black_box(&keys))))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, cache_batch);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+//! Disallowed account lookups for a 10 account transaction against a 1,000 entry blocklist,
+//! scanning the list as a `Vec` vs. the `HashSet` built when the config is initialized.
+//!
+//! Run with `cargo bench -p kora-lib --bench disallowed_accounts`
+
+use std::{collections::HashSet, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use solana_sdk::pubkey::Pubkey;
+
+const DISALLOWED_ACCOUNTS: usize = 1_000;
+const TRANSACTION_ACCOUNTS: usize = 10;
+
+fn disallowed_accounts_lookup(c: &mut Criterion) {
+    let disallowed: Vec<Pubkey> = (0..DISALLOWED_ACCOUNTS).map(|_| Pubkey::new_unique()).collect();
+    let disallowed_set: HashSet<Pubkey> = disallowed.iter().copied().collect();
+
+    // None of the accounts are disallowed, the worst case for a linear scan
+    let transaction_accounts: Vec<Pubkey> =
+        (0..TRANSACTION_ACCOUNTS).map(|_| Pubkey::new_unique()).collect();
+
+    let mut group = c.benchmark_group("disallowed_accounts_lookup");
+
+    group.bench_function("vec", |b| {
+        b.iter(|| {
+            black_box(&transaction_accounts).iter().any(|account| disallowed.contains(account))
+        })
+    });
+
+    group.bench_function("hash_set", |b| {
+        b.iter(|| {
+            black_box(&transaction_accounts).iter().any(|account| disallowed_set.contains(account))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, disallowed_accounts_lookup);
+criterion_main!(benches);
@@ -0,0 +1,8 @@
+use vergen::EmitBuilder;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Exposes VERGEN_GIT_SHA and VERGEN_BUILD_TIMESTAMP to the getVersion method.
+    // Falls back to placeholder values when building outside of a git checkout.
+    EmitBuilder::builder().build_timestamp().git_sha(false).emit()?;
+    Ok(())
+}
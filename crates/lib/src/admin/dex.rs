@@ -0,0 +1,350 @@
+use crate::{error::KoraError, transaction::IxUtils};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address;
+use utoipa::ToSchema;
+
+/*
+OpenBook v2 (Serum v4) market order placement.
+
+The market account is read with fixed offsets instead of pulling in the OpenBook crate, only the
+fields needed to build a `PlaceOrder` instruction are parsed.
+*/
+
+const MARKET_ACCOUNT_NAME: &str = "Market";
+const PLACE_ORDER_INSTRUCTION_NAME: &str = "place_order";
+const OPEN_ORDERS_SEED: &[u8] = b"OpenOrders";
+
+// Open orders accounts are numbered from 1 by the owner's open orders indexer
+pub const DEFAULT_OPEN_ORDERS_ACCOUNT_NUM: u32 = 1;
+
+// Offsets of the `Market` account fields, including the 8 bytes Anchor discriminator
+const MARKET_OPEN_ORDERS_ADMIN_OFFSET: usize = 88;
+const MARKET_BIDS_OFFSET: usize = 200;
+const MARKET_ASKS_OFFSET: usize = 232;
+const MARKET_EVENT_HEAP_OFFSET: usize = 264;
+const MARKET_ORACLE_A_OFFSET: usize = 296;
+const MARKET_ORACLE_B_OFFSET: usize = 328;
+const MARKET_BASE_MINT_OFFSET: usize = 576;
+const MARKET_QUOTE_MINT_OFFSET: usize = 608;
+const MARKET_BASE_VAULT_OFFSET: usize = 640;
+const MARKET_QUOTE_VAULT_OFFSET: usize = 680;
+pub const MARKET_ACCOUNT_MIN_LEN: usize = MARKET_QUOTE_VAULT_OFFSET + 32;
+
+// `PlaceOrderType::Market`, `SelfTradeBehavior::DecrementTake`
+const ORDER_TYPE_MARKET: u8 = 3;
+const SELF_TRADE_BEHAVIOR_DECREMENT_TAKE: u8 = 0;
+// Maximum number of orders matched against before the order stops
+const ORDER_MATCH_LIMIT: u8 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl OrderSide {
+    /// `Side::Bid` or `Side::Ask` in the OpenBook program
+    fn as_u8(&self) -> u8 {
+        match self {
+            OrderSide::Buy => 0,
+            OrderSide::Sell => 1,
+        }
+    }
+}
+
+/// Accounts of an OpenBook v2 market used to place orders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenBookMarket {
+    pub address: Pubkey,
+    pub program_id: Pubkey,
+    pub open_orders_admin: Option<Pubkey>,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_heap: Pubkey,
+    pub oracle_a: Option<Pubkey>,
+    pub oracle_b: Option<Pubkey>,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+}
+
+pub struct DexUtil;
+
+impl DexUtil {
+    /// Discriminator prefixing the data of the Anchor account `account_name`, the first
+    /// 8 bytes of `sha256("account:<account_name>")`
+    pub fn anchor_account_discriminator(account_name: &str) -> [u8; 8] {
+        let hash = Sha256::digest(format!("account:{account_name}").as_bytes());
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash[..8]);
+        discriminator
+    }
+
+    /// Parse the market account `address` owned by the OpenBook program `program_id`
+    pub fn parse_market(
+        address: &Pubkey,
+        program_id: &Pubkey,
+        data: &[u8],
+    ) -> Result<OpenBookMarket, KoraError> {
+        if data.len() < MARKET_ACCOUNT_MIN_LEN
+            || data[..8] != Self::anchor_account_discriminator(MARKET_ACCOUNT_NAME)
+        {
+            return Err(KoraError::InvalidRequest(format!(
+                "Account {address} is not an OpenBook market"
+            )));
+        }
+
+        Ok(OpenBookMarket {
+            address: *address,
+            program_id: *program_id,
+            open_orders_admin: read_optional_pubkey(data, MARKET_OPEN_ORDERS_ADMIN_OFFSET),
+            bids: read_pubkey(data, MARKET_BIDS_OFFSET),
+            asks: read_pubkey(data, MARKET_ASKS_OFFSET),
+            event_heap: read_pubkey(data, MARKET_EVENT_HEAP_OFFSET),
+            oracle_a: read_optional_pubkey(data, MARKET_ORACLE_A_OFFSET),
+            oracle_b: read_optional_pubkey(data, MARKET_ORACLE_B_OFFSET),
+            base_mint: read_pubkey(data, MARKET_BASE_MINT_OFFSET),
+            quote_mint: read_pubkey(data, MARKET_QUOTE_MINT_OFFSET),
+            base_vault: read_pubkey(data, MARKET_BASE_VAULT_OFFSET),
+            quote_vault: read_pubkey(data, MARKET_QUOTE_VAULT_OFFSET),
+        })
+    }
+
+    /// Open orders account `account_num` of `owner` in the OpenBook program `program_id`
+    pub fn open_orders_address(program_id: &Pubkey, owner: &Pubkey, account_num: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[OPEN_ORDERS_SEED, owner.as_ref(), &account_num.to_le_bytes()],
+            program_id,
+        )
+        .0
+    }
+
+    /// Build a `PlaceOrder` market order of `size` base lots for `owner`, paid from the owner's
+    /// associated token account of the quote mint (buys) or the base mint (sells).
+    ///
+    /// `max_price` is the price limit in quote lots per base lot, the highest price for buys and
+    /// the lowest for sells. Without it the order takes whatever the book offers.
+    pub fn build_place_order_instruction(
+        market: &OpenBookMarket,
+        owner: &Pubkey,
+        side: OrderSide,
+        size: u64,
+        max_price: Option<u64>,
+    ) -> Result<Instruction, KoraError> {
+        if market.open_orders_admin.is_some() {
+            return Err(KoraError::InvalidRequest(format!(
+                "Market {} requires an open orders admin signature",
+                market.address
+            )));
+        }
+        if size == 0 {
+            return Err(KoraError::InvalidRequest("Order size must be greater than 0".to_string()));
+        }
+
+        let max_base_lots = i64::try_from(size)
+            .map_err(|_| KoraError::InvalidRequest(format!("Order size {size} is too large")))?;
+        let price_lots = match max_price {
+            Some(0) => {
+                return Err(KoraError::InvalidRequest(
+                    "Order max_price must be greater than 0".to_string(),
+                ))
+            }
+            Some(price) => i64::try_from(price).map_err(|_| {
+                KoraError::InvalidRequest(format!("Order max_price {price} is too large"))
+            })?,
+            None => match side {
+                OrderSide::Buy => i64::MAX,
+                OrderSide::Sell => 1,
+            },
+        };
+
+        let (payment_mint, market_vault) = match side {
+            OrderSide::Buy => (market.quote_mint, market.quote_vault),
+            OrderSide::Sell => (market.base_mint, market.base_vault),
+        };
+        let program_id = market.program_id;
+
+        // Anchor expects the program ID in place of optional accounts that are not set
+        let accounts = vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(
+                Self::open_orders_address(&program_id, owner, DEFAULT_OPEN_ORDERS_ACCOUNT_NUM),
+                false,
+            ),
+            AccountMeta::new_readonly(program_id, false),
+            AccountMeta::new(get_associated_token_address(owner, &payment_mint), false),
+            AccountMeta::new(market.address, false),
+            AccountMeta::new(market.bids, false),
+            AccountMeta::new(market.asks, false),
+            AccountMeta::new(market.event_heap, false),
+            AccountMeta::new(market_vault, false),
+            AccountMeta::new_readonly(market.oracle_a.unwrap_or(program_id), false),
+            AccountMeta::new_readonly(market.oracle_b.unwrap_or(program_id), false),
+            AccountMeta::new_readonly(spl_token_interface::id(), false),
+        ];
+
+        let mut data = IxUtils::anchor_discriminator(PLACE_ORDER_INSTRUCTION_NAME).to_vec();
+        data.push(side.as_u8());
+        data.extend_from_slice(&price_lots.to_le_bytes());
+        data.extend_from_slice(&max_base_lots.to_le_bytes());
+        // max_quote_lots_including_fees, bounded by max_base_lots and price_lots instead
+        data.extend_from_slice(&i64::MAX.to_le_bytes());
+        // client_order_id
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(ORDER_TYPE_MARKET);
+        // expiry_timestamp, 0 never expires
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(SELF_TRADE_BEHAVIOR_DECREMENT_TAKE);
+        data.push(ORDER_MATCH_LIMIT);
+
+        Ok(Instruction { program_id, accounts, data })
+    }
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[offset..offset + 32]);
+    Pubkey::new_from_array(bytes)
+}
+
+/// `NonZeroPubkeyOption`, the default pubkey means unset
+fn read_optional_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    Some(read_pubkey(data, offset)).filter(|pubkey| *pubkey != Pubkey::default())
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::*;
+
+    /// Account data of an OpenBook market with every field set to a unique key
+    pub fn market_account_data(market: &OpenBookMarket) -> Vec<u8> {
+        let mut data = vec![0u8; MARKET_ACCOUNT_MIN_LEN];
+        data[..8].copy_from_slice(&DexUtil::anchor_account_discriminator(MARKET_ACCOUNT_NAME));
+
+        let mut write = |offset: usize, pubkey: Option<Pubkey>| {
+            data[offset..offset + 32].copy_from_slice(pubkey.unwrap_or_default().as_ref());
+        };
+        write(MARKET_OPEN_ORDERS_ADMIN_OFFSET, market.open_orders_admin);
+        write(MARKET_BIDS_OFFSET, Some(market.bids));
+        write(MARKET_ASKS_OFFSET, Some(market.asks));
+        write(MARKET_EVENT_HEAP_OFFSET, Some(market.event_heap));
+        write(MARKET_ORACLE_A_OFFSET, market.oracle_a);
+        write(MARKET_ORACLE_B_OFFSET, market.oracle_b);
+        write(MARKET_BASE_MINT_OFFSET, Some(market.base_mint));
+        write(MARKET_QUOTE_MINT_OFFSET, Some(market.quote_mint));
+        write(MARKET_BASE_VAULT_OFFSET, Some(market.base_vault));
+        write(MARKET_QUOTE_VAULT_OFFSET, Some(market.quote_vault));
+
+        data
+    }
+
+    pub fn mock_market(program_id: Pubkey) -> OpenBookMarket {
+        OpenBookMarket {
+            address: Pubkey::new_unique(),
+            program_id,
+            open_orders_admin: None,
+            bids: Pubkey::new_unique(),
+            asks: Pubkey::new_unique(),
+            event_heap: Pubkey::new_unique(),
+            oracle_a: Some(Pubkey::new_unique()),
+            oracle_b: None,
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_utils::*, *};
+
+    #[test]
+    fn test_parse_market() {
+        let market = mock_market(Pubkey::new_unique());
+        let data = market_account_data(&market);
+
+        let parsed = DexUtil::parse_market(&market.address, &market.program_id, &data).unwrap();
+        assert_eq!(parsed, market);
+
+        // Wrong discriminator or truncated data
+        let mut other = data.clone();
+        other[0] ^= 1;
+        assert!(DexUtil::parse_market(&market.address, &market.program_id, &other).is_err());
+        assert!(DexUtil::parse_market(&market.address, &market.program_id, &data[..100]).is_err());
+    }
+
+    #[test]
+    fn test_build_place_order_instruction() {
+        let market = mock_market(Pubkey::new_unique());
+        let owner = Pubkey::new_unique();
+
+        let ix = DexUtil::build_place_order_instruction(&market, &owner, OrderSide::Buy, 5, None)
+            .unwrap();
+
+        assert_eq!(ix.program_id, market.program_id);
+        assert_eq!(ix.accounts.len(), 12);
+        assert_eq!(ix.accounts[0], AccountMeta::new_readonly(owner, true));
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            DexUtil::open_orders_address(&market.program_id, &owner, 1)
+        );
+        assert_eq!(ix.accounts[2].pubkey, market.program_id);
+        assert_eq!(ix.accounts[3].pubkey, get_associated_token_address(&owner, &market.quote_mint));
+        assert_eq!(ix.accounts[8].pubkey, market.quote_vault);
+        assert_eq!(ix.accounts[9].pubkey, market.oracle_a.unwrap());
+        assert_eq!(ix.accounts[10].pubkey, market.program_id);
+
+        assert_eq!(ix.data[..8], IxUtils::anchor_discriminator("place_order"));
+        assert_eq!(ix.data.len(), 8 + 1 + 8 * 4 + 1 + 8 + 1 + 1);
+        assert_eq!(ix.data[8], 0);
+        assert_eq!(ix.data[9..17], i64::MAX.to_le_bytes());
+        assert_eq!(ix.data[17..25], 5i64.to_le_bytes());
+        assert_eq!(ix.data[41], ORDER_TYPE_MARKET);
+
+        let ix =
+            DexUtil::build_place_order_instruction(&market, &owner, OrderSide::Sell, 5, Some(42))
+                .unwrap();
+        assert_eq!(ix.data[8], 1);
+        assert_eq!(ix.data[9..17], 42i64.to_le_bytes());
+        assert_eq!(ix.accounts[3].pubkey, get_associated_token_address(&owner, &market.base_mint));
+        assert_eq!(ix.accounts[8].pubkey, market.base_vault);
+    }
+
+    #[test]
+    fn test_build_place_order_instruction_rejects_invalid_orders() {
+        let market = mock_market(Pubkey::new_unique());
+        let owner = Pubkey::new_unique();
+
+        assert!(DexUtil::build_place_order_instruction(&market, &owner, OrderSide::Buy, 0, None)
+            .is_err());
+        assert!(DexUtil::build_place_order_instruction(
+            &market,
+            &owner,
+            OrderSide::Buy,
+            u64::MAX,
+            None
+        )
+        .is_err());
+        assert!(DexUtil::build_place_order_instruction(
+            &market,
+            &owner,
+            OrderSide::Buy,
+            1,
+            Some(0)
+        )
+        .is_err());
+
+        let gated = OpenBookMarket { open_orders_admin: Some(Pubkey::new_unique()), ..market };
+        assert!(DexUtil::build_place_order_instruction(&gated, &owner, OrderSide::Buy, 1, None)
+            .is_err());
+    }
+}
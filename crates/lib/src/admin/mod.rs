@@ -1 +1,4 @@
+#[cfg(feature = "dex")]
+pub mod dex;
+pub mod rebalance;
 pub mod token_util;
@@ -0,0 +1,243 @@
+use crate::{
+    constant::LAMPORTS_PER_SIGNATURE, error::KoraError, state::get_signer_pool,
+    transaction::TransactionUtil,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_keychain::SolanaSigner;
+use solana_message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_system_interface::instruction::transfer;
+use std::{fmt::Display, str::FromStr};
+
+/*
+Only the transfer plan is unit tested, submitting transfers requires a validator running.
+*/
+
+pub struct SignerBalance {
+    pub name: String,
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalanceTransfer {
+    pub from_name: String,
+    pub from: Pubkey,
+    pub to_name: String,
+    pub to: Pubkey,
+    pub lamports: u64,
+}
+
+impl Display for RebalanceTransfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} lamports from {} ({}) to {} ({})",
+            self.lamports, self.from_name, self.from, self.to_name, self.to
+        )
+    }
+}
+
+/// Compute the System transfers that bring every signer as close as possible to `target_balance_lamports`
+///
+/// Over-funded signers only give away what they hold above the target, minus the signature fee they
+/// pay for each transfer they send. Largest surpluses are matched with largest deficits first.
+pub fn compute_rebalance_plan(
+    balances: &[SignerBalance],
+    target_balance_lamports: u64,
+) -> Vec<RebalanceTransfer> {
+    let mut surpluses: Vec<(&SignerBalance, u64)> = balances
+        .iter()
+        .filter(|b| b.lamports > target_balance_lamports)
+        .map(|b| (b, b.lamports - target_balance_lamports))
+        .collect();
+    let mut deficits: Vec<(&SignerBalance, u64)> = balances
+        .iter()
+        .filter(|b| b.lamports < target_balance_lamports)
+        .map(|b| (b, target_balance_lamports - b.lamports))
+        .collect();
+
+    surpluses.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.pubkey.cmp(&b.0.pubkey)));
+    deficits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.pubkey.cmp(&b.0.pubkey)));
+
+    let mut plan = Vec::new();
+    let mut surplus_iter = surpluses.into_iter();
+    let mut current_surplus = surplus_iter.next();
+
+    for (receiver, mut deficit) in deficits {
+        while deficit > 0 {
+            let Some((sender, surplus)) = current_surplus.as_mut() else {
+                return plan;
+            };
+
+            // The sender pays the transaction fee out of its surplus
+            let available = surplus.saturating_sub(LAMPORTS_PER_SIGNATURE);
+            if available == 0 {
+                current_surplus = surplus_iter.next();
+                continue;
+            }
+
+            let lamports = available.min(deficit);
+            plan.push(RebalanceTransfer {
+                from_name: sender.name.clone(),
+                from: sender.pubkey,
+                to_name: receiver.name.clone(),
+                to: receiver.pubkey,
+                lamports,
+            });
+
+            *surplus -= lamports + LAMPORTS_PER_SIGNATURE;
+            deficit -= lamports;
+        }
+    }
+
+    plan
+}
+
+/// Bring every signer in the pool to `target_balance_lamports` by moving SOL between signers
+///
+/// Prints the transfer plan and only submits the transfers when `execute` is set
+pub async fn rebalance_signers(
+    rpc_client: &RpcClient,
+    target_balance_lamports: u64,
+    execute: bool,
+) -> Result<(), KoraError> {
+    let pool = get_signer_pool()?;
+
+    let mut balances = Vec::new();
+    for info in pool.get_signers_info() {
+        let pubkey = Pubkey::from_str(&info.public_key).map_err(|e| {
+            KoraError::InternalServerError(format!(
+                "Invalid signer pubkey {}: {e}",
+                info.public_key
+            ))
+        })?;
+        let lamports = rpc_client.get_balance(&pubkey).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to get balance for signer {}: {e}", info.name))
+        })?;
+
+        println!("  - {} ({pubkey}): {lamports} lamports", info.name);
+        balances.push(SignerBalance { name: info.name, pubkey, lamports });
+    }
+
+    let plan = compute_rebalance_plan(&balances, target_balance_lamports);
+    if plan.is_empty() {
+        println!("✓ No transfers needed to reach {target_balance_lamports} lamports per signer");
+        return Ok(());
+    }
+
+    println!("\nTransfer plan ({} transfer(s)):", plan.len());
+    for planned_transfer in &plan {
+        println!("  - {planned_transfer}");
+    }
+
+    if !execute {
+        println!("\nDry run, re-run with --execute to submit the transfers.");
+        return Ok(());
+    }
+
+    for (idx, rebalance_transfer) in plan.iter().enumerate() {
+        let transfer_num = idx + 1;
+        let signer = pool.get_signer_by_pubkey(&rebalance_transfer.from.to_string())?;
+
+        let blockhash = rpc_client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| KoraError::RpcError(format!("Failed to get blockhash: {e}")))?;
+
+        let instruction =
+            transfer(&rebalance_transfer.from, &rebalance_transfer.to, rebalance_transfer.lamports);
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[instruction],
+            Some(&rebalance_transfer.from),
+            &blockhash,
+        ));
+
+        let mut tx = TransactionUtil::new_unsigned_versioned_transaction(message);
+        let signature = signer
+            .sign_message(&tx.message.serialize())
+            .await
+            .map_err(|e| KoraError::SigningError(e.to_string()))?;
+        tx.signatures = vec![signature];
+
+        match rpc_client.send_and_confirm_transaction_with_spinner(&tx).await {
+            Ok(signature) => {
+                println!(
+                    "✓ Transfer {transfer_num}/{} successful. Transaction signature: {signature}",
+                    plan.len()
+                );
+            }
+            Err(e) => {
+                println!("✗ Transfer {transfer_num}/{} failed: {e}", plan.len());
+                return Err(KoraError::RpcError(format!(
+                    "Failed to send rebalance transfer {transfer_num}/{}: {e}",
+                    plan.len()
+                )));
+            }
+        }
+    }
+
+    println!("\n🎉 Signer rebalance completed successfully!");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(name: &str, lamports: u64) -> SignerBalance {
+        SignerBalance { name: name.to_string(), pubkey: Pubkey::new_unique(), lamports }
+    }
+
+    #[test]
+    fn test_compute_rebalance_plan_balanced() {
+        let balances = vec![balance("a", 1_000_000), balance("b", 2_000_000)];
+
+        assert!(compute_rebalance_plan(&balances, 1_000_000).is_empty());
+    }
+
+    #[test]
+    fn test_compute_rebalance_plan_single_transfer() {
+        let balances = vec![balance("rich", 5_000_000), balance("poor", 1_000_000)];
+
+        let plan = compute_rebalance_plan(&balances, 2_000_000);
+
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].from, balances[0].pubkey);
+        assert_eq!(plan[0].to, balances[1].pubkey);
+        assert_eq!(plan[0].lamports, 1_000_000);
+    }
+
+    #[test]
+    fn test_compute_rebalance_plan_splits_across_senders() {
+        let balances =
+            vec![balance("rich", 3_000_000), balance("richer", 4_000_000), balance("poor", 0)];
+
+        let plan = compute_rebalance_plan(&balances, 2_000_000);
+
+        // Largest surplus first, then the remainder from the next sender
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].from_name, "richer");
+        assert_eq!(plan[0].lamports, 2_000_000 - LAMPORTS_PER_SIGNATURE);
+        assert_eq!(plan[1].from_name, "rich");
+        assert_eq!(plan[1].lamports, LAMPORTS_PER_SIGNATURE);
+        assert!(plan.iter().all(|t| t.to_name == "poor"));
+    }
+
+    #[test]
+    fn test_compute_rebalance_plan_insufficient_surplus() {
+        let balances =
+            vec![balance("rich", 1_500_000), balance("poor_1", 0), balance("poor_2", 500_000)];
+
+        let plan = compute_rebalance_plan(&balances, 1_000_000);
+
+        // Only 500_000 is available above target, minus the fee for the single transfer
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].to_name, "poor_1");
+        assert_eq!(plan[0].lamports, 500_000 - LAMPORTS_PER_SIGNATURE);
+        // Sender never drops below target
+        let sent: u64 = plan.iter().map(|t| t.lamports + LAMPORTS_PER_SIGNATURE).sum();
+        assert!(1_500_000 - sent >= 1_000_000);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     error::KoraError,
+    signer::KoraSigner,
     state::{get_request_signer_with_signer_key, get_signer_pool},
     token::token::TokenType,
     transaction::TransactionUtil,
@@ -85,7 +86,7 @@ pub async fn initialize_atas(
 /// This function does not use cache and directly checks on-chain
 pub async fn initialize_atas_with_chunk_size(
     rpc_client: &RpcClient,
-    fee_payer: &Arc<solana_keychain::Signer>,
+    fee_payer: &Arc<KoraSigner>,
     addresses_to_initialize_atas: &Vec<Pubkey>,
     compute_unit_price: Option<u64>,
     compute_unit_limit: Option<u32>,
@@ -121,7 +122,7 @@ pub async fn initialize_atas_with_chunk_size(
 /// Helper function to create ATAs for a single signer
 async fn create_atas_for_signer(
     rpc_client: &RpcClient,
-    fee_payer: &Arc<solana_keychain::Signer>,
+    fee_payer: &Arc<KoraSigner>,
     address: &Pubkey,
     atas_to_create: &[ATAToCreate],
     compute_unit_price: Option<u64>,
@@ -310,6 +311,7 @@ mod tests {
         },
         config_mock::{ConfigMockBuilder, ValidationConfigBuilder},
     };
+    use solana_sdk::hash::Hash;
     use std::{
         collections::VecDeque,
         sync::{Arc, Mutex},
@@ -402,7 +404,10 @@ mod tests {
             },
         ];
 
-        let rpc_client = RpcMockBuilder::new().with_blockhash().with_send_transaction().build();
+        let rpc_client = RpcMockBuilder::new()
+            .with_blockhash(Hash::new_unique(), 1000)
+            .with_send_transaction()
+            .build();
 
         let result = create_atas_for_signer(
             &rpc_client,
@@ -0,0 +1,277 @@
+use std::{
+    collections::HashMap,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use prometheus::{IntGauge, Opts};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::KoraError,
+    transaction::{ParsedSPLInstructionData, ParsedSPLInstructionType},
+};
+
+// Global cache shared by the fee validation path and the signing methods that invalidate it
+pub static TOKEN_BALANCE_CACHE: Lazy<TokenBalanceCache> = Lazy::new(TokenBalanceCache::new);
+
+static TOKEN_BALANCE_CACHE_SIZE: OnceLock<Option<IntGauge>> = OnceLock::new();
+
+fn cache_size_gauge() -> Option<&'static IntGauge> {
+    TOKEN_BALANCE_CACHE_SIZE
+        .get_or_init(|| {
+            let gauge = IntGauge::with_opts(
+                Opts::new("token_balance_cache_size", "Number of cached token account balances")
+                    .namespace("kora"),
+            )
+            .and_then(|gauge| prometheus::register(Box::new(gauge.clone())).map(|_| gauge));
+
+            match gauge {
+                Ok(gauge) => Some(gauge),
+                Err(e) => {
+                    log::error!("Failed to register token_balance_cache_size metric: {e:?}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedTokenBalance {
+    amount: u64,
+    cached_at: Instant,
+}
+
+/// In-memory cache of user token balances keyed by (owner, mint)
+pub struct TokenBalanceCache {
+    entries: RwLock<HashMap<(Pubkey, Pubkey), CachedTokenBalance>>,
+}
+
+impl Default for TokenBalanceCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenBalanceCache {
+    pub fn new() -> Self {
+        Self { entries: RwLock::new(HashMap::new()) }
+    }
+
+    /// Get the balance of `token_account`, only hitting the RPC when the cached entry is older than `ttl`
+    pub async fn get_balance(
+        &self,
+        rpc_client: &RpcClient,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        token_account: &Pubkey,
+        ttl: Duration,
+    ) -> Result<u64, KoraError> {
+        if let Some(cached) = self.entries.read().get(&(*owner, *mint)) {
+            if cached.cached_at.elapsed() < ttl {
+                return Ok(cached.amount);
+            }
+        }
+
+        let balance = rpc_client
+            .get_token_account_balance(token_account)
+            .await
+            .map_err(|e| KoraError::RpcError(e.to_string()))?;
+
+        let amount = balance.amount.parse::<u64>().map_err(|e| {
+            KoraError::RpcError(format!("Invalid token balance for {token_account}: {e}"))
+        })?;
+
+        if !ttl.is_zero() {
+            let mut entries = self.entries.write();
+            entries
+                .insert((*owner, *mint), CachedTokenBalance { amount, cached_at: Instant::now() });
+            self.update_size_metric(entries.len());
+        }
+
+        Ok(amount)
+    }
+
+    pub fn invalidate(&self, owner: &Pubkey, mint: &Pubkey) {
+        let mut entries = self.entries.write();
+        if entries.remove(&(*owner, *mint)).is_some() {
+            self.update_size_metric(entries.len());
+        }
+    }
+
+    /// Drop every cached balance of `owner`, used when the mint of a transfer is unknown
+    pub fn invalidate_owner(&self, owner: &Pubkey) {
+        let mut entries = self.entries.write();
+        let len_before = entries.len();
+        entries.retain(|(cached_owner, _), _| cached_owner != owner);
+        if entries.len() != len_before {
+            self.update_size_metric(entries.len());
+        }
+    }
+
+    /// Invalidate the balances of every token transfer source in a transaction submitted by Kora
+    pub fn invalidate_transfer_sources(
+        &self,
+        parsed_spl_instructions: &HashMap<ParsedSPLInstructionType, Vec<ParsedSPLInstructionData>>,
+    ) {
+        for instruction in parsed_spl_instructions
+            .get(&ParsedSPLInstructionType::SplTokenTransfer)
+            .into_iter()
+            .flatten()
+        {
+            if let ParsedSPLInstructionData::SplTokenTransfer { owner, mint, .. } = instruction {
+                match mint {
+                    Some(mint) => self.invalidate(owner, mint),
+                    None => self.invalidate_owner(owner),
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+
+    fn update_size_metric(&self, size: usize) {
+        if let Some(gauge) = cache_size_gauge() {
+            gauge.set(size as i64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server, ServerGuard};
+
+    async fn create_balance_server(
+        amount: u64,
+        expected_calls: usize,
+    ) -> (ServerGuard, mockito::Mock) {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJsonString(
+                r#"{"method": "getTokenAccountBalance"}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "context": { "slot": 1 },
+                        "value": {
+                            "amount": amount.to_string(),
+                            "decimals": 6,
+                            "uiAmount": amount as f64 / 1_000_000.0,
+                            "uiAmountString": (amount as f64 / 1_000_000.0).to_string(),
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect(expected_calls)
+            .create_async()
+            .await;
+        (server, mock)
+    }
+
+    #[tokio::test]
+    async fn test_rapid_lookups_hit_rpc_once() {
+        let (server, mock) = create_balance_server(1_000_000, 1).await;
+        let rpc_client = RpcClient::new(server.url());
+        let cache = TokenBalanceCache::new();
+        let (owner, mint, token_account) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        for _ in 0..2 {
+            let balance = cache
+                .get_balance(&rpc_client, &owner, &mint, &token_account, Duration::from_secs(5))
+                .await
+                .unwrap();
+            assert_eq!(balance, 1_000_000);
+        }
+
+        assert_eq!(cache.len(), 1);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_is_refetched() {
+        let (server, mock) = create_balance_server(500, 2).await;
+        let rpc_client = RpcClient::new(server.url());
+        let cache = TokenBalanceCache::new();
+        let (owner, mint, token_account) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        for _ in 0..2 {
+            cache
+                .get_balance(&rpc_client, &owner, &mint, &token_account, Duration::from_millis(10))
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalidation_forces_refetch() {
+        let (server, mock) = create_balance_server(500, 2).await;
+        let rpc_client = RpcClient::new(server.url());
+        let cache = TokenBalanceCache::new();
+        let (owner, mint, token_account) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ttl = Duration::from_secs(5);
+
+        cache.get_balance(&rpc_client, &owner, &mint, &token_account, ttl).await.unwrap();
+        cache.invalidate(&owner, &mint);
+        assert!(cache.is_empty());
+        cache.get_balance(&rpc_client, &owner, &mint, &token_account, ttl).await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_transfer_sources() {
+        let (server, _mock) = create_balance_server(500, 3).await;
+        let rpc_client = RpcClient::new(server.url());
+        let cache = TokenBalanceCache::new();
+        let ttl = Duration::from_secs(5);
+        let (owner, other_owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let token_account = Pubkey::new_unique();
+
+        cache.get_balance(&rpc_client, &owner, &mint, &token_account, ttl).await.unwrap();
+        cache.get_balance(&rpc_client, &owner, &other_mint, &token_account, ttl).await.unwrap();
+        cache.get_balance(&rpc_client, &other_owner, &mint, &token_account, ttl).await.unwrap();
+        assert_eq!(cache.len(), 3);
+
+        // Transfer without a mint (plain `Transfer`) drops every balance of the owner
+        let parsed = HashMap::from([(
+            ParsedSPLInstructionType::SplTokenTransfer,
+            vec![ParsedSPLInstructionData::SplTokenTransfer {
+                amount: 100,
+                owner,
+                mint: None,
+                source_address: token_account,
+                destination_address: Pubkey::new_unique(),
+                is_2022: false,
+            }],
+        )]);
+        cache.invalidate_transfer_sources(&parsed);
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.entries.read().contains_key(&(other_owner, mint)));
+    }
+}
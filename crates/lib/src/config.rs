@@ -1,22 +1,31 @@
 use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 use spl_token_2022_interface::extension::ExtensionType;
-use std::{fs, path::Path, str::FromStr};
+use std::{collections::HashSet, fs, path::Path, str::FromStr, sync::Arc};
 use toml;
 use utoipa::ToSchema;
 
 use crate::{
     constant::{
-        DEFAULT_CACHE_ACCOUNT_TTL, DEFAULT_CACHE_DEFAULT_TTL,
-        DEFAULT_FEE_PAYER_BALANCE_METRICS_EXPIRY_SECONDS, DEFAULT_MAX_REQUEST_BODY_SIZE,
-        DEFAULT_MAX_TIMESTAMP_AGE, DEFAULT_METRICS_ENDPOINT, DEFAULT_METRICS_PORT,
-        DEFAULT_METRICS_SCRAPE_INTERVAL, DEFAULT_USAGE_LIMIT_FALLBACK_IF_UNAVAILABLE,
-        DEFAULT_USAGE_LIMIT_MAX_TRANSACTIONS,
+        ADMIN_METHODS, BATCH_REQUEST_ENVELOPE_SIZE, CONFIG_ENV_PREFIX, DEFAULT_CACHE_ACCOUNT_TTL,
+        DEFAULT_CACHE_DEFAULT_TTL, DEFAULT_FEE_PAYER_BALANCE_METRICS_EXPIRY_SECONDS,
+        DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_REQUEST_BODY_SIZE, DEFAULT_MAX_TIMESTAMP_AGE,
+        DEFAULT_METRICS_ENDPOINT, DEFAULT_METRICS_PORT, DEFAULT_METRICS_SCRAPE_INTERVAL,
+        DEFAULT_PREFLIGHT_SIMULATION_COMMITMENT, DEFAULT_PUSH_GATEWAY_INTERVAL_SECONDS,
+        DEFAULT_PUSH_GATEWAY_JOB_NAME, DEFAULT_TOKEN_BALANCE_CACHE_TTL,
+        DEFAULT_TOKEN_METADATA_CACHE_TTL, DEFAULT_USAGE_LIMIT_CLEANUP_INTERVAL_SECONDS,
+        DEFAULT_USAGE_LIMIT_FALLBACK_IF_UNAVAILABLE, DEFAULT_USAGE_LIMIT_MAX_TRANSACTIONS,
+        DEFAULT_USAGE_LIMIT_WINDOW_SECONDS, MAX_ENCODED_TRANSACTION_SIZE,
     },
     error::KoraError,
-    fee::price::{PriceConfig, PriceModel},
-    oracle::PriceSource,
+    fee::{
+        fee::FeeEstimationStrategy,
+        price::{PriceConfig, PriceModel},
+    },
+    oracle::{MockOracleConfig, PriceSource},
     sanitize_error,
+    validator::account_validator::AccountType,
 };
 
 #[derive(Clone, Deserialize)]
@@ -35,6 +44,9 @@ pub struct MetricsConfig {
     pub scrape_interval: u64,
     #[serde(default)]
     pub fee_payer_balance: FeePayerBalanceMetricsConfig,
+    /// Push metrics to a Prometheus push gateway instead of exposing a scrape endpoint
+    #[serde(default)]
+    pub push_gateway: Option<PushGatewayConfig>,
 }
 
 impl Default for MetricsConfig {
@@ -45,10 +57,37 @@ impl Default for MetricsConfig {
             port: DEFAULT_METRICS_PORT,
             scrape_interval: DEFAULT_METRICS_SCRAPE_INTERVAL,
             fee_payer_balance: FeePayerBalanceMetricsConfig::default(),
+            push_gateway: None,
         }
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct PushGatewayConfig {
+    /// Push gateway base URL (e.g., "http://pushgateway:9091")
+    pub url: String,
+    #[serde(default = "default_push_gateway_job_name")]
+    pub job_name: String,
+    #[serde(default = "default_push_gateway_interval_seconds")]
+    pub push_interval_seconds: u64,
+    #[serde(default)]
+    pub auth: Option<BasicAuth>,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+fn default_push_gateway_job_name() -> String {
+    DEFAULT_PUSH_GATEWAY_JOB_NAME.to_string()
+}
+
+fn default_push_gateway_interval_seconds() -> u64 {
+    DEFAULT_PUSH_GATEWAY_INTERVAL_SECONDS
+}
+
 #[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct FeePayerBalanceMetricsConfig {
     pub enabled: bool,
@@ -64,6 +103,8 @@ impl Default for FeePayerBalanceMetricsConfig {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SplTokenConfig {
     All,
+    /// Every token except the listed ones
+    Blocklist(Vec<String>),
     #[serde(untagged)]
     Allowlist(Vec<String>),
 }
@@ -74,15 +115,13 @@ impl Default for SplTokenConfig {
     }
 }
 
+/// Iterates the explicitly allowed tokens, nothing for `All` and `Blocklist`
 impl<'a> IntoIterator for &'a SplTokenConfig {
     type Item = &'a String;
     type IntoIter = std::slice::Iter<'a, String>;
 
     fn into_iter(self) -> Self::IntoIter {
-        match self {
-            SplTokenConfig::All => [].iter(),
-            SplTokenConfig::Allowlist(tokens) => tokens.iter(),
-        }
+        self.as_slice().iter()
     }
 }
 
@@ -90,23 +129,33 @@ impl SplTokenConfig {
     pub fn has_token(&self, token: &str) -> bool {
         match self {
             SplTokenConfig::All => true,
+            SplTokenConfig::Blocklist(tokens) => !tokens.iter().any(|s| s == token),
             SplTokenConfig::Allowlist(tokens) => tokens.iter().any(|s| s == token),
         }
     }
 
     pub fn has_tokens(&self) -> bool {
         match self {
-            SplTokenConfig::All => true,
+            SplTokenConfig::All | SplTokenConfig::Blocklist(_) => true,
             SplTokenConfig::Allowlist(tokens) => !tokens.is_empty(),
         }
     }
 
+    /// Explicitly allowed tokens, empty for `All` and `Blocklist`
     pub fn as_slice(&self) -> &[String] {
         match self {
-            SplTokenConfig::All => &[],
+            SplTokenConfig::All | SplTokenConfig::Blocklist(_) => &[],
             SplTokenConfig::Allowlist(v) => v.as_slice(),
         }
     }
+
+    /// Tokens rejected by a `Blocklist`, empty for the other variants
+    pub fn blocked_tokens(&self) -> &[String] {
+        match self {
+            SplTokenConfig::Blocklist(v) => v.as_slice(),
+            _ => &[],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -124,6 +173,60 @@ pub struct ValidationConfig {
     pub price: PriceConfig,
     #[serde(default)]
     pub token_2022: Token2022Config,
+    /// Reject token payments whose source account can't cover the transferred amount
+    #[serde(default)]
+    pub validate_user_token_balance: bool,
+    /// When set, every non-program account referenced by a transaction must be of a listed type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_type_allowlist: Option<Vec<AccountType>>,
+    /// Reject transactions whose inner instructions couldn't all be resolved from simulation, so
+    /// every transfer made through CPI is checked against the fee payer policy and allowlists
+    #[serde(default)]
+    pub follow_cpi: bool,
+    /// Prices quoted by the `Mock` price source
+    #[serde(default)]
+    pub mock_oracle: MockOracleConfig,
+    /// Add the Token-2022 `TransferFeeConfig` fee withheld from payments to the fee estimate,
+    /// so Kora still receives the full fee after the mint takes its cut
+    #[serde(default)]
+    pub account_for_transfer_fee: bool,
+    /// Reject transactions whose fee payer is already set to a key that isn't one of Kora's signers
+    #[serde(default)]
+    pub strict_fee_payer: bool,
+    /// Reject transactions referencing Metaplex metadata accounts whose update authority is
+    /// the fee payer
+    #[serde(default)]
+    pub validate_nft_metadata: bool,
+    /// How the base fee of a transaction is estimated, `getFeeForMessage` by default
+    #[serde(default)]
+    pub fee_estimation_strategy: FeeEstimationStrategy,
+    /// Maximum number of accounts a transaction may load from address lookup tables, summed over
+    /// every table it references. Unlimited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_lookup_table_accounts: Option<usize>,
+    /// Allow BPF Upgradeable Loader `Upgrade` and `SetAuthority` instructions, rejected by default
+    /// since they need an upgrade authority signature Kora should never sponsor
+    #[serde(default)]
+    pub allow_program_upgrades: bool,
+    /// Programs transactions may not invoke. When `allowed_programs` is empty, every program not
+    /// listed here is allowed
+    #[serde(default)]
+    pub disallowed_programs: Vec<String>,
+    /// Anchor programs that may only be invoked through the listed instructions
+    #[serde(default)]
+    pub anchor_programs: Vec<AnchorProgramConfig>,
+    /// Reject transactions whose blockhash is more than this many slots old, narrowing the
+    /// window in which a captured signed transaction can be replayed. Only limited by the
+    /// network's 150 slots when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_blockhash_age_slots: Option<u64>,
+    /// Price the base fee of fee estimates with `getFeeForMessage` at the network's current
+    /// rate, falling back to a static calculation from the compute budget when the call fails
+    #[serde(default)]
+    pub use_rpc_fee_estimate: bool,
+    /// `disallowed_accounts` parsed once by `init_disallowed_accounts`, shared by every validator
+    #[serde(skip)]
+    pub parsed_disallowed_accounts: Option<Arc<HashSet<Pubkey>>>,
 }
 
 impl ValidationConfig {
@@ -134,6 +237,40 @@ impl ValidationConfig {
     pub fn supports_token(&self, token: &str) -> bool {
         self.allowed_spl_paid_tokens.has_token(token)
     }
+
+    /// Parse `disallowed_accounts` into a set, so checking an account doesn't scan the list
+    pub fn init_disallowed_accounts(&mut self) -> Result<(), KoraError> {
+        self.parsed_disallowed_accounts =
+            Some(Arc::new(Self::parse_disallowed_accounts(&self.disallowed_accounts)?));
+        Ok(())
+    }
+
+    /// Disallowed accounts as a set, parsed on the fly when the config was not initialized
+    pub fn get_disallowed_accounts(&self) -> Result<Arc<HashSet<Pubkey>>, KoraError> {
+        match &self.parsed_disallowed_accounts {
+            Some(accounts) => Ok(Arc::clone(accounts)),
+            None => Self::parse_disallowed_accounts(&self.disallowed_accounts).map(Arc::new),
+        }
+    }
+
+    fn parse_disallowed_accounts(accounts: &[String]) -> Result<HashSet<Pubkey>, KoraError> {
+        accounts
+            .iter()
+            .map(|addr| Pubkey::from_str(addr))
+            .collect::<Result<HashSet<Pubkey>, _>>()
+            .map_err(|e| {
+                KoraError::InternalServerError(format!("Invalid disallowed account address: {e}"))
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AnchorProgramConfig {
+    /// Address of the Anchor program
+    pub program_id: String,
+    /// Names of the instructions that may be invoked, matched by their 8-byte discriminator
+    /// (`sha256("global:<name>")`), every other instruction of the program is rejected
+    pub allowed_instructions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
@@ -144,6 +281,87 @@ pub struct FeePayerPolicy {
     pub spl_token: SplTokenInstructionPolicy,
     #[serde(default)]
     pub token_2022: Token2022InstructionPolicy,
+    /// When set, the fee payer can only be used in the listed instruction types
+    /// (e.g. "SystemTransfer", "SplTokenTransfer"), regardless of the category-level flags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_type_allowlist: Option<Vec<String>>,
+    /// Reject instructions to programs the policy doesn't parse (anything but System, SPL Token
+    /// and Token2022) that require the fee payer's signature
+    #[serde(default)]
+    pub reject_fee_payer_as_unknown_signer: bool,
+    /// Per-instruction rules for programs the policy doesn't parse. Once a program has a rule,
+    /// the fee payer can only be a signer or writable in its instructions that a rule allows
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub program_instruction_allowlist: Vec<ProgramInstructionRule>,
+}
+
+impl FeePayerPolicy {
+    /// Whether the fee payer may be used in `instruction_type`, `category_allowed` being the
+    /// category-level flag that applies when no allowlist is configured
+    pub fn allows_instruction_type(&self, instruction_type: &str, category_allowed: bool) -> bool {
+        match &self.instruction_type_allowlist {
+            Some(allowlist) => allowlist.iter().any(|allowed| allowed == instruction_type),
+            None => category_allowed,
+        }
+    }
+
+    /// Whether `program_instruction_allowlist` lets the fee payer take part in `instruction` as a
+    /// signer and/or writable account. `None` when no rule covers the program, the other policy
+    /// checks decide then
+    pub fn allows_program_instruction(
+        &self,
+        instruction: &Instruction,
+        as_signer: bool,
+        as_writable: bool,
+    ) -> Option<bool> {
+        let program_id = instruction.program_id.to_string();
+        let mut rules = self
+            .program_instruction_allowlist
+            .iter()
+            .filter(|rule| rule.program_id == program_id)
+            .peekable();
+        rules.peek()?;
+
+        Some(rules.any(|rule| {
+            rule.matches(&instruction.data)
+                && (!as_signer || rule.allows_fee_payer_as(ProgramInstructionRule::SIGNER))
+                && (!as_writable || rule.allows_fee_payer_as(ProgramInstructionRule::WRITABLE))
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProgramInstructionRule {
+    /// Address of the program
+    pub program_id: String,
+    /// Hex-encoded prefix of the instruction data (e.g. an 8 byte Anchor discriminator), the
+    /// rule covers every instruction of the program when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_discriminator: Option<String>,
+    /// How the fee payer may take part in matching instructions, "signer" and/or "writable". As
+    /// the transaction fee payer it is a writable signer in every instruction that references it
+    #[serde(default)]
+    pub allow_fee_payer_as: Vec<String>,
+}
+
+impl ProgramInstructionRule {
+    pub const SIGNER: &'static str = "signer";
+    pub const WRITABLE: &'static str = "writable";
+    pub const FEE_PAYER_ROLES: [&'static str; 2] = [Self::SIGNER, Self::WRITABLE];
+
+    /// Whether the rule covers an instruction with `data`
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match &self.instruction_discriminator {
+            Some(discriminator) => {
+                hex::decode(discriminator).is_ok_and(|prefix| data.starts_with(&prefix))
+            }
+            None => true,
+        }
+    }
+
+    pub fn allows_fee_payer_as(&self, role: &str) -> bool {
+        self.allow_fee_payer_as.iter().any(|allowed| allowed == role)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
@@ -159,6 +377,14 @@ pub struct SystemInstructionPolicy {
     /// Nested policy for nonce account operations
     #[serde(default)]
     pub nonce: NonceInstructionPolicy,
+    /// Only allow System Transfer/TransferWithSeed instructions to these receivers, whoever the
+    /// sender is. Any receiver is allowed when unset or empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_receivers: Option<Vec<String>>,
+    /// Reject System Transfer/TransferWithSeed instructions moving fewer lamports, whoever the
+    /// sender is. Unlike `max_allowed_lamports` this applies to each transfer on its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_allowed_lamports: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
@@ -200,6 +426,10 @@ pub struct SplTokenInstructionPolicy {
     pub allow_freeze_account: bool,
     /// Allow fee payer to be the freeze authority in SPL Token ThawAccount instructions
     pub allow_thaw_account: bool,
+    /// Only allow SPL Token Transfer/TransferChecked instructions to these destination token
+    /// accounts, whoever the owner is. Any destination is allowed when unset or empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_receivers: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
@@ -228,16 +458,36 @@ pub struct Token2022InstructionPolicy {
     pub allow_freeze_account: bool,
     /// Allow fee payer to be the freeze authority in Token2022 ThawAccount instructions
     pub allow_thaw_account: bool,
+    /// Only allow Token2022 Transfer/TransferChecked instructions to these destination token
+    /// accounts, whoever the owner is. Any destination is allowed when unset or empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_receivers: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Token2022Config {
     pub blocked_mint_extensions: Vec<String>,
     pub blocked_account_extensions: Vec<String>,
+    /// Mint and account extensions allowed on payment tokens, every other extension is blocked.
+    /// Cannot be combined with the blocklists
+    #[serde(default)]
+    pub allowed_extensions: Vec<String>,
+    /// Extensions every Token2022 payment mint must have
+    #[serde(default)]
+    pub required_mint_extensions: Vec<String>,
+    /// Extensions the payment token accounts of the payment address must have
+    #[serde(default)]
+    pub required_account_extensions: Vec<String>,
     #[serde(skip)]
     parsed_blocked_mint_extensions: Option<Vec<ExtensionType>>,
     #[serde(skip)]
     parsed_blocked_account_extensions: Option<Vec<ExtensionType>>,
+    #[serde(skip)]
+    parsed_allowed_extensions: Option<Vec<ExtensionType>>,
+    #[serde(skip)]
+    parsed_required_mint_extensions: Option<Vec<ExtensionType>>,
+    #[serde(skip)]
+    parsed_required_account_extensions: Option<Vec<ExtensionType>>,
 }
 
 impl Default for Token2022Config {
@@ -245,8 +495,14 @@ impl Default for Token2022Config {
         Self {
             blocked_mint_extensions: Vec::new(),
             blocked_account_extensions: Vec::new(),
+            allowed_extensions: Vec::new(),
+            required_mint_extensions: Vec::new(),
+            required_account_extensions: Vec::new(),
             parsed_blocked_mint_extensions: Some(Vec::new()),
             parsed_blocked_account_extensions: Some(Vec::new()),
+            parsed_allowed_extensions: Some(Vec::new()),
+            parsed_required_mint_extensions: Some(Vec::new()),
+            parsed_required_account_extensions: Some(Vec::new()),
         }
     }
 }
@@ -255,6 +511,12 @@ impl Token2022Config {
     /// Initialize and parse extension strings into ExtensionTypes
     /// This should be called after deserialization to populate the cached fields
     pub fn initialize(&mut self) -> Result<(), String> {
+        if self.has_allowlist() && self.has_blocklist() {
+            return Err("allowed_extensions cannot be combined with blocked_mint_extensions or \
+                 blocked_account_extensions"
+                .to_string());
+        }
+
         let mut mint_extensions = Vec::new();
         for name in &self.blocked_mint_extensions {
             match crate::token::spl_token_2022_util::parse_mint_extension_string(name) {
@@ -289,9 +551,82 @@ impl Token2022Config {
         }
         self.parsed_blocked_account_extensions = Some(account_extensions);
 
+        let mut allowed_extensions = Vec::new();
+        for name in &self.allowed_extensions {
+            match crate::token::spl_token_2022_util::parse_mint_extension_string(name)
+                .or_else(|| crate::token::spl_token_2022_util::parse_account_extension_string(name))
+            {
+                Some(ext) => {
+                    allowed_extensions.push(ext);
+                }
+                None => {
+                    return Err(format!(
+                        "Invalid allowed extension name: '{}'. Valid names are: {:?} {:?}",
+                        name,
+                        crate::token::spl_token_2022_util::get_all_mint_extension_names(),
+                        crate::token::spl_token_2022_util::get_all_account_extension_names()
+                    ));
+                }
+            }
+        }
+        self.parsed_allowed_extensions = Some(allowed_extensions);
+
+        let mut required_mint_extensions = Vec::new();
+        for name in &self.required_mint_extensions {
+            match crate::token::spl_token_2022_util::parse_mint_extension_string(name) {
+                Some(ext) if self.is_mint_extension_blocked(ext) => {
+                    return Err(format!("Required mint extension '{name}' is also blocked"));
+                }
+                Some(ext) => {
+                    required_mint_extensions.push(ext);
+                }
+                None => {
+                    return Err(format!(
+                        "Invalid required mint extension name: '{}'. Valid names are: {:?}",
+                        name,
+                        crate::token::spl_token_2022_util::get_all_mint_extension_names()
+                    ));
+                }
+            }
+        }
+        self.parsed_required_mint_extensions = Some(required_mint_extensions);
+
+        let mut required_account_extensions = Vec::new();
+        for name in &self.required_account_extensions {
+            match crate::token::spl_token_2022_util::parse_account_extension_string(name) {
+                Some(ext) if self.is_account_extension_blocked(ext) => {
+                    return Err(format!("Required account extension '{name}' is also blocked"));
+                }
+                Some(ext) => {
+                    required_account_extensions.push(ext);
+                }
+                None => {
+                    return Err(format!(
+                        "Invalid required account extension name: '{}'. Valid names are: {:?}",
+                        name,
+                        crate::token::spl_token_2022_util::get_all_account_extension_names()
+                    ));
+                }
+            }
+        }
+        self.parsed_required_account_extensions = Some(required_account_extensions);
+
         Ok(())
     }
 
+    pub fn has_allowlist(&self) -> bool {
+        !self.allowed_extensions.is_empty()
+    }
+
+    pub fn has_blocklist(&self) -> bool {
+        !self.blocked_mint_extensions.is_empty() || !self.blocked_account_extensions.is_empty()
+    }
+
+    /// Get all allowed extensions as ExtensionType
+    pub fn get_allowed_extensions(&self) -> &[ExtensionType] {
+        self.parsed_allowed_extensions.as_deref().unwrap_or(&[])
+    }
+
     /// Get all blocked mint extensions as ExtensionType
     pub fn get_blocked_mint_extensions(&self) -> &[ExtensionType] {
         self.parsed_blocked_mint_extensions.as_deref().unwrap_or(&[])
@@ -302,13 +637,29 @@ impl Token2022Config {
         self.parsed_blocked_account_extensions.as_deref().unwrap_or(&[])
     }
 
+    /// Get all required mint extensions as ExtensionType
+    pub fn get_required_mint_extensions(&self) -> &[ExtensionType] {
+        self.parsed_required_mint_extensions.as_deref().unwrap_or(&[])
+    }
+
+    /// Get all required account extensions as ExtensionType
+    pub fn get_required_account_extensions(&self) -> &[ExtensionType] {
+        self.parsed_required_account_extensions.as_deref().unwrap_or(&[])
+    }
+
     /// Check if a mint extension is blocked
     pub fn is_mint_extension_blocked(&self, ext: ExtensionType) -> bool {
+        if self.has_allowlist() {
+            return !self.get_allowed_extensions().contains(&ext);
+        }
         self.get_blocked_mint_extensions().contains(&ext)
     }
 
     /// Check if an account extension is blocked
     pub fn is_account_extension_blocked(&self, ext: ExtensionType) -> bool {
+        if self.has_allowlist() {
+            return !self.get_allowed_extensions().contains(&ext);
+        }
         self.get_blocked_account_extensions().contains(&ext)
     }
 }
@@ -324,6 +675,34 @@ pub struct EnabledMethods {
     pub transfer_transaction: bool,
     pub get_blockhash: bool,
     pub get_config: bool,
+    /// Opt-in WebSocket subscription, disabled when omitted from the config file
+    #[serde(default)]
+    pub subscribe_transaction_events: bool,
+    /// Enabled when omitted from the config file
+    #[serde(default = "default_get_version")]
+    pub get_version: bool,
+    /// Enabled when omitted from the config file
+    #[serde(default = "default_get_instruction_template")]
+    pub get_instruction_template: bool,
+    /// Enabled when omitted from the config file
+    #[serde(default = "default_get_token_metadata")]
+    pub get_token_metadata: bool,
+    /// Opt-in `kora_getAccountInfo` proxy, disabled when omitted from the config file
+    #[serde(default)]
+    pub get_account_info: bool,
+    /// `kora_getNetworkStatus` and `kora_isNetworkCongested`, enabled when omitted from the
+    /// config file
+    #[serde(default = "default_get_network_status")]
+    pub get_network_status: bool,
+    /// Opt-in `signMessage` for off-chain messages, disabled when omitted from the config file
+    #[serde(default)]
+    pub sign_message: bool,
+    /// Opt-in `signTransactionBatch`, disabled when omitted from the config file
+    #[serde(default)]
+    pub sign_transaction_batch: bool,
+    /// `kora_simulateTransaction`, enabled when omitted from the config file
+    #[serde(default = "default_simulate_transaction")]
+    pub simulate_transaction: bool,
 }
 
 impl EnabledMethods {
@@ -338,6 +717,15 @@ impl EnabledMethods {
             self.transfer_transaction,
             self.get_blockhash,
             self.get_config,
+            self.subscribe_transaction_events,
+            self.get_version,
+            self.get_instruction_template,
+            self.get_token_metadata,
+            self.get_account_info,
+            self.get_network_status,
+            self.sign_message,
+            self.sign_transaction_batch,
+            self.simulate_transaction,
         ]
         .into_iter()
     }
@@ -347,15 +735,18 @@ impl EnabledMethods {
         let mut methods = Vec::new();
         if self.liveness {
             methods.push("liveness".to_string());
+            methods.push("healthz".to_string());
         }
         if self.estimate_transaction_fee {
             methods.push("estimateTransactionFee".to_string());
+            methods.push("estimateTransactionFeeTemplate".to_string());
         }
         if self.get_supported_tokens {
             methods.push("getSupportedTokens".to_string());
         }
         if self.get_payer_signer {
             methods.push("getPayerSigner".to_string());
+            methods.push("kora_getSignerPublicKey".to_string());
         }
         if self.sign_transaction {
             methods.push("signTransaction".to_string());
@@ -372,13 +763,42 @@ impl EnabledMethods {
         if self.get_config {
             methods.push("getConfig".to_string());
         }
+        if self.get_version {
+            methods.push("getVersion".to_string());
+        }
+        if self.get_instruction_template {
+            methods.push("getInstructionTemplate".to_string());
+        }
+        if self.get_token_metadata {
+            methods.push("getTokenMetadata".to_string());
+        }
+        if self.subscribe_transaction_events {
+            methods.push("kora_subscribeTransactionEvents".to_string());
+            methods.push("kora_unsubscribeTransactionEvents".to_string());
+        }
+        if self.get_account_info {
+            methods.push("kora_getAccountInfo".to_string());
+        }
+        if self.get_network_status {
+            methods.push("kora_getNetworkStatus".to_string());
+            methods.push("kora_isNetworkCongested".to_string());
+        }
+        if self.sign_message {
+            methods.push("signMessage".to_string());
+        }
+        if self.sign_transaction_batch {
+            methods.push("signTransactionBatch".to_string());
+        }
+        if self.simulate_transaction {
+            methods.push("kora_simulateTransaction".to_string());
+        }
         methods
     }
 }
 
 impl IntoIterator for &EnabledMethods {
     type Item = bool;
-    type IntoIter = std::array::IntoIter<bool, 9>;
+    type IntoIter = std::array::IntoIter<bool, 18>;
 
     fn into_iter(self) -> Self::IntoIter {
         [
@@ -391,6 +811,15 @@ impl IntoIterator for &EnabledMethods {
             self.transfer_transaction,
             self.get_blockhash,
             self.get_config,
+            self.subscribe_transaction_events,
+            self.get_version,
+            self.get_instruction_template,
+            self.get_token_metadata,
+            self.get_account_info,
+            self.get_network_status,
+            self.sign_message,
+            self.sign_transaction_batch,
+            self.simulate_transaction,
         ]
         .into_iter()
     }
@@ -408,6 +837,15 @@ impl Default for EnabledMethods {
             transfer_transaction: true,
             get_blockhash: true,
             get_config: true,
+            subscribe_transaction_events: true,
+            get_version: true,
+            get_instruction_template: true,
+            get_token_metadata: true,
+            get_account_info: true,
+            get_network_status: true,
+            sign_message: true,
+            sign_transaction_batch: true,
+            simulate_transaction: true,
         }
     }
 }
@@ -420,6 +858,38 @@ fn default_max_request_body_size() -> usize {
     DEFAULT_MAX_REQUEST_BODY_SIZE
 }
 
+fn default_max_batch_size() -> usize {
+    DEFAULT_MAX_BATCH_SIZE
+}
+
+fn default_get_version() -> bool {
+    true
+}
+
+fn default_get_instruction_template() -> bool {
+    true
+}
+
+fn default_get_token_metadata() -> bool {
+    true
+}
+
+fn default_get_network_status() -> bool {
+    true
+}
+
+fn default_simulate_transaction() -> bool {
+    true
+}
+
+fn default_token_balance_ttl() -> u64 {
+    DEFAULT_TOKEN_BALANCE_CACHE_TTL
+}
+
+fn default_token_metadata_ttl() -> u64 {
+    DEFAULT_TOKEN_METADATA_CACHE_TTL
+}
+
 #[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct CacheConfig {
     /// Redis URL for caching (e.g., "redis://localhost:6379")
@@ -430,6 +900,12 @@ pub struct CacheConfig {
     pub default_ttl: u64,
     /// TTL for account data cache in seconds
     pub account_ttl: u64,
+    /// TTL for the in-memory token balance cache in seconds
+    #[serde(default = "default_token_balance_ttl")]
+    pub token_balance_ttl: u64,
+    /// TTL for the in-memory token metadata cache in seconds, 0 disables it
+    #[serde(default = "default_token_metadata_ttl")]
+    pub token_metadata_ttl: u64,
 }
 
 impl Default for CacheConfig {
@@ -439,6 +915,8 @@ impl Default for CacheConfig {
             enabled: false,
             default_ttl: DEFAULT_CACHE_DEFAULT_TTL,
             account_ttl: DEFAULT_CACHE_ACCOUNT_TTL,
+            token_balance_ttl: DEFAULT_TOKEN_BALANCE_CACHE_TTL,
+            token_metadata_ttl: DEFAULT_TOKEN_METADATA_CACHE_TTL,
         }
     }
 }
@@ -458,6 +936,47 @@ pub struct KoraConfig {
     pub cache: CacheConfig,
     #[serde(default)]
     pub usage_limit: UsageLimitConfig,
+    /// Optional webhook notified of transaction lifecycle events
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    /// Address lookup table that transactions built by Kora are compiled against, accounts
+    /// found in the table are loaded from it instead of being listed in the message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_add_lookup_table: Option<String>,
+    /// Compute budget instructions added to transactions built by Kora
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_compute_budget: Option<AutoComputeBudgetConfig>,
+    /// Simulate transactions on the RPC node before `signTransaction` signs them, so
+    /// transactions that would fail on-chain are rejected instead of signed
+    #[serde(default)]
+    pub preflight_simulate: bool,
+    /// Commitment the pre-flight simulation runs at
+    #[serde(default = "default_preflight_simulation_commitment")]
+    pub preflight_simulation_commitment: String,
+    /// Forward the Solana RPC methods in `proxy_allowed_methods` to the backend RPC, so clients
+    /// can use Kora as their only RPC endpoint
+    #[serde(default)]
+    pub enable_rpc_proxy: bool,
+    /// Solana RPC methods forwarded to the backend RPC when `enable_rpc_proxy` is set, Kora
+    /// methods are never forwarded
+    #[serde(default)]
+    pub proxy_allowed_methods: Vec<String>,
+    /// How long a signing request waits for a slot of a signer at its `concurrent_signing_limit`
+    /// before being rejected, 0 (the default) rejects it right away
+    #[serde(default)]
+    pub backpressure_timeout_ms: u64,
+    /// Let `signMessage` sign payloads that deserialize as a Solana transaction message. Off by
+    /// default, such signatures would be valid transaction signatures that skipped validation
+    #[serde(default)]
+    pub sign_message_allow_transaction_bytes: bool,
+    /// Most transactions a single `signTransactionBatch` request may contain, each of them counts
+    /// as one request against `rate_limit`
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+}
+
+fn default_preflight_simulation_commitment() -> String {
+    DEFAULT_PREFLIGHT_SIMULATION_COMMITMENT.to_string()
 }
 
 impl Default for KoraConfig {
@@ -470,6 +989,16 @@ impl Default for KoraConfig {
             payment_address: None,
             cache: CacheConfig::default(),
             usage_limit: UsageLimitConfig::default(),
+            webhook: None,
+            auto_add_lookup_table: None,
+            auto_compute_budget: None,
+            preflight_simulate: false,
+            preflight_simulation_commitment: default_preflight_simulation_commitment(),
+            enable_rpc_proxy: false,
+            proxy_allowed_methods: Vec::new(),
+            backpressure_timeout_ms: 0,
+            sign_message_allow_transaction_bytes: false,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
         }
     }
 }
@@ -484,6 +1013,23 @@ pub struct UsageLimitConfig {
     pub max_transactions: u64,
     /// Fallback behavior when cache is unavailable
     pub fallback_if_unavailable: bool,
+    /// How transactions are counted against `max_transactions`
+    #[serde(default)]
+    pub window_type: WindowType,
+    /// Length of the window in seconds, only used by sliding windows
+    #[serde(default = "default_usage_limit_window_seconds")]
+    pub window_seconds: u64,
+    /// How often entries whose window has expired are dropped from in-memory stores (0 = never)
+    #[serde(default = "default_usage_limit_cleanup_interval_seconds")]
+    pub cleanup_interval_seconds: u64,
+}
+
+fn default_usage_limit_window_seconds() -> u64 {
+    DEFAULT_USAGE_LIMIT_WINDOW_SECONDS
+}
+
+fn default_usage_limit_cleanup_interval_seconds() -> u64 {
+    DEFAULT_USAGE_LIMIT_CLEANUP_INTERVAL_SECONDS
 }
 
 impl Default for UsageLimitConfig {
@@ -493,21 +1039,104 @@ impl Default for UsageLimitConfig {
             cache_url: None,
             max_transactions: DEFAULT_USAGE_LIMIT_MAX_TRANSACTIONS,
             fallback_if_unavailable: DEFAULT_USAGE_LIMIT_FALLBACK_IF_UNAVAILABLE,
+            window_type: WindowType::default(),
+            window_seconds: DEFAULT_USAGE_LIMIT_WINDOW_SECONDS,
+            cleanup_interval_seconds: DEFAULT_USAGE_LIMIT_CLEANUP_INTERVAL_SECONDS,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WindowType {
+    /// A single counter per wallet that is never reset
+    #[default]
+    Fixed,
+    /// Only transactions from the last `window_seconds` are counted, timestamps are rounded
+    /// up to `precision_ms` so the effective window is never shorter than configured
+    Sliding { precision_ms: u64 },
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookConfig {
+    /// Endpoint receiving a POST request for every subscribed event
+    pub url: String,
+    /// Secret used to sign payloads, the HMAC-SHA256 of the body is sent in `X-Kora-Signature`
+    pub secret: String,
+    /// Events delivered to the endpoint, defaults to all of them
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<WebhookEvent>,
+}
+
+/// `SetComputeUnitLimit` and `SetComputeUnitPrice` values for transactions built by Kora
+/// (`transferTransaction`), each only added when the transaction doesn't set it already
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AutoComputeBudgetConfig {
+    /// Compute unit limit, at most 1,400,000
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>,
+    /// Micro-lamports per compute unit
+    #[serde(default)]
+    pub compute_unit_price: Option<u64>,
+}
+
+fn default_webhook_events() -> Vec<WebhookEvent> {
+    vec![
+        WebhookEvent::Signed,
+        WebhookEvent::Submitted,
+        WebhookEvent::Confirmed,
+        WebhookEvent::Failed,
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// Transaction signed by `signTransaction`
+    Signed,
+    /// Transaction sent to the network by `signAndSendTransaction`
+    Submitted,
+    /// Transaction sent by `signAndSendTransaction` reached the client's commitment level
+    Confirmed,
+    /// `signAndSendTransaction` failed to sign or land the transaction
+    Failed,
+}
+
 #[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct AuthConfig {
     pub api_key: Option<String>,
     pub hmac_secret: Option<String>,
+    /// Key required in the `x-admin-api-key` header to call admin methods (e.g. `kora_setLogLevel`),
+    /// admin methods are not registered when unset
+    pub admin_api_key: Option<String>,
     #[serde(default = "default_max_timestamp_age")]
     pub max_timestamp_age: i64,
+    /// RS256 public key (PEM) verifying `Authorization: Bearer` JWTs, requests must carry a
+    /// valid token when set. The `sub` claim is the caller the usage limit counts against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt_public_key: Option<String>,
+    /// Audience the JWTs must be issued for, required with `jwt_public_key`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt_audience: Option<String>,
 }
 
 impl Default for AuthConfig {
     fn default() -> Self {
-        Self { api_key: None, hmac_secret: None, max_timestamp_age: DEFAULT_MAX_TIMESTAMP_AGE }
+        Self {
+            api_key: None,
+            hmac_secret: None,
+            admin_api_key: None,
+            max_timestamp_age: DEFAULT_MAX_TIMESTAMP_AGE,
+            jwt_public_key: None,
+            jwt_audience: None,
+        }
+    }
+}
+
+impl AuthConfig {
+    /// Whether client requests need any credentials, the admin API key only guards admin methods
+    pub fn has_client_auth(&self) -> bool {
+        self.api_key.is_some() || self.hmac_secret.is_some() || self.jwt_public_key.is_some()
     }
 }
 
@@ -520,13 +1149,47 @@ impl Config {
             ))
         })?;
 
-        let mut config: Config = toml::from_str(&contents).map_err(|e| {
+        let config: Config = toml::from_str(&contents).map_err(|e| {
             KoraError::InternalServerError(format!(
                 "Failed to parse config file: {}",
                 sanitize_error!(e)
             ))
         })?;
 
+        Self::initialize(config)
+    }
+
+    /// Load the config from `KORA_*` environment variables instead of a TOML file, see
+    /// `Config::env_var_name` for how variables are named
+    pub fn load_from_env() -> Result<Config, KoraError> {
+        Self::load_from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Environment variable holding the TOML key `key`, e.g. `kora.rate_limit` is read from
+    /// `KORA_KORA_RATE_LIMIT`
+    pub fn env_var_name(key: &str) -> String {
+        format!("{CONFIG_ENV_PREFIX}{}", key.replace('.', "_").to_uppercase())
+    }
+
+    fn load_from_vars(lookup: impl Fn(&str) -> Option<String>) -> Result<Config, KoraError> {
+        let mut table = toml::Table::new();
+        for key in Self::env_keys() {
+            if let Some(raw) = lookup(&Self::env_var_name(key)) {
+                insert_env_value(&mut table, key, parse_env_value(&raw));
+            }
+        }
+
+        let config: Config = toml::Value::Table(table).try_into().map_err(|e| {
+            KoraError::InternalServerError(format!(
+                "Failed to parse config from environment: {}",
+                sanitize_error!(e)
+            ))
+        })?;
+
+        Self::initialize(config)
+    }
+
+    fn initialize(mut config: Config) -> Result<Config, KoraError> {
         // Initialize Token2022Config to parse and cache extensions
         config.validation.token_2022.initialize().map_err(|e| {
             KoraError::InternalServerError(format!(
@@ -539,7 +1202,253 @@ impl Config {
     }
 }
 
+/// Values are read as TOML (`true`, `100`, `["a", "b"]`, `{ type = "free" }`), anything that
+/// doesn't parse is taken as a plain string. Strings that look like numbers or booleans must
+/// be quoted.
+fn parse_env_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+fn insert_env_value(table: &mut toml::Table, key: &str, value: toml::Value) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(field) = parts.pop() else {
+        return;
+    };
+
+    let mut current = table;
+    for part in parts {
+        current = match current
+            .entry(part.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        {
+            toml::Value::Table(inner) => inner,
+            _ => return,
+        };
+    }
+    current.insert(field.to_string(), value);
+}
+
+/// Declares the TOML key paths `Config::load_from_env` reads, nested groups are the TOML
+/// tables. Enums and lists are single keys taking an inline TOML value.
+macro_rules! config_env_keys {
+    (@push $keys:ident [$($prefix:tt)*]) => {};
+    (@push $keys:ident [$($prefix:tt)*] $field:ident { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        config_env_keys!(@push $keys [$($prefix)* $field .] $($inner)*);
+        config_env_keys!(@push $keys [$($prefix)*] $($($rest)*)?);
+    };
+    (@push $keys:ident [$($prefix:tt)*] $field:ident $(, $($rest:tt)*)?) => {
+        $keys.push(concat!($(stringify!($prefix),)* stringify!($field)));
+        config_env_keys!(@push $keys [$($prefix)*] $($($rest)*)?);
+    };
+    ($($tree:tt)*) => {
+        impl Config {
+            /// Every TOML key path that can be set through `Config::env_var_name`
+            pub fn env_keys() -> Vec<&'static str> {
+                let mut keys = Vec::new();
+                config_env_keys!(@push keys [] $($tree)*);
+                keys
+            }
+        }
+    };
+}
+
+config_env_keys! {
+    validation {
+        max_allowed_lamports,
+        max_signatures,
+        allowed_programs,
+        allowed_tokens,
+        allowed_spl_paid_tokens,
+        disallowed_accounts,
+        price_source,
+        fee_payer_policy {
+            system {
+                allow_transfer,
+                allow_assign,
+                allow_create_account,
+                allow_allocate,
+                min_allowed_lamports,
+                nonce { allow_initialize, allow_advance, allow_withdraw, allow_authorize },
+            },
+            spl_token {
+                allow_transfer,
+                allow_burn,
+                allow_close_account,
+                allow_approve,
+                allow_revoke,
+                allow_set_authority,
+                allow_mint_to,
+                allow_initialize_mint,
+                allow_initialize_account,
+                allow_initialize_multisig,
+                allow_freeze_account,
+                allow_thaw_account,
+            },
+            token_2022 {
+                allow_transfer,
+                allow_burn,
+                allow_close_account,
+                allow_approve,
+                allow_revoke,
+                allow_set_authority,
+                allow_mint_to,
+                allow_initialize_mint,
+                allow_initialize_account,
+                allow_initialize_multisig,
+                allow_freeze_account,
+                allow_thaw_account,
+            },
+            instruction_type_allowlist,
+            reject_fee_payer_as_unknown_signer,
+        },
+        price { type, margin, amount, token, strict },
+        token_2022 {
+            blocked_mint_extensions,
+            blocked_account_extensions,
+            allowed_extensions,
+            required_mint_extensions,
+            required_account_extensions,
+        },
+        validate_user_token_balance,
+        account_type_allowlist,
+        follow_cpi,
+        mock_oracle { base_price_usd, volatility_percent, seed },
+        account_for_transfer_fee,
+        strict_fee_payer,
+        validate_nft_metadata,
+        fee_estimation_strategy,
+        max_lookup_table_accounts,
+        allow_program_upgrades,
+        disallowed_programs,
+        anchor_programs,
+        max_blockhash_age_slots,
+        use_rpc_fee_estimate,
+    },
+    kora {
+        rate_limit,
+        max_request_body_size,
+        enabled_methods {
+            liveness,
+            estimate_transaction_fee,
+            get_supported_tokens,
+            get_payer_signer,
+            sign_transaction,
+            sign_and_send_transaction,
+            transfer_transaction,
+            get_blockhash,
+            get_config,
+            subscribe_transaction_events,
+            get_version,
+            get_instruction_template,
+            get_token_metadata,
+            get_account_info,
+            get_network_status,
+            sign_message,
+            sign_transaction_batch,
+            simulate_transaction,
+        },
+        auth {
+            api_key,
+            hmac_secret,
+            admin_api_key,
+            max_timestamp_age,
+            jwt_public_key,
+            jwt_audience,
+        },
+        payment_address,
+        cache { url, enabled, default_ttl, account_ttl, token_balance_ttl, token_metadata_ttl },
+        usage_limit {
+            enabled,
+            cache_url,
+            max_transactions,
+            fallback_if_unavailable,
+            window_type,
+            window_seconds,
+            cleanup_interval_seconds,
+        },
+        webhook { url, secret, events },
+        auto_add_lookup_table,
+        auto_compute_budget { compute_unit_limit, compute_unit_price },
+        preflight_simulate,
+        preflight_simulation_commitment,
+        enable_rpc_proxy,
+        proxy_allowed_methods,
+        backpressure_timeout_ms,
+        sign_message_allow_transaction_bytes,
+        max_batch_size,
+    },
+    metrics {
+        enabled,
+        endpoint,
+        port,
+        scrape_interval,
+        fee_payer_balance { enabled, expiry_seconds },
+        push_gateway { url, job_name, push_interval_seconds, auth { username, password } },
+    },
+}
+
 impl KoraConfig {
+    /// Parsed `auto_add_lookup_table`, if configured
+    pub fn get_auto_add_lookup_table(&self) -> Result<Option<Pubkey>, KoraError> {
+        self.auto_add_lookup_table
+            .as_deref()
+            .map(|address| {
+                Pubkey::from_str(address).map_err(|_| {
+                    KoraError::InternalServerError(
+                        "Invalid auto_add_lookup_table format".to_string(),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Parsed `preflight_simulation_commitment`
+    pub fn get_preflight_simulation_commitment(&self) -> Result<CommitmentConfig, KoraError> {
+        CommitmentConfig::from_str(&self.preflight_simulation_commitment).map_err(|_| {
+            KoraError::InternalServerError(
+                "Invalid preflight_simulation_commitment format".to_string(),
+            )
+        })
+    }
+
+    /// `max_request_body_size`, raised to fit a full `signTransactionBatch` request when that
+    /// method is enabled. Only batch requests may use the raised limit
+    pub fn get_max_request_body_size(&self) -> usize {
+        if !self.enabled_methods.sign_transaction_batch {
+            return self.max_request_body_size;
+        }
+        let batch_request_size = self
+            .max_batch_size
+            .saturating_mul(MAX_ENCODED_TRANSACTION_SIZE + 3) // Quotes and separating comma
+            .saturating_add(BATCH_REQUEST_ENVELOPE_SIZE);
+        self.max_request_body_size.max(batch_request_size)
+    }
+
+    /// Methods forwarded to the backend RPC, empty unless `enable_rpc_proxy` is set
+    pub fn get_proxy_method_names(&self) -> Vec<String> {
+        if !self.enable_rpc_proxy {
+            return Vec::new();
+        }
+        self.proxy_allowed_methods
+            .iter()
+            .filter(|method| !Self::is_kora_method(method))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether `method` is served by Kora itself, enabled or not
+    pub fn is_kora_method(method: &str) -> bool {
+        method.starts_with("kora_")
+            || ADMIN_METHODS.contains(&method)
+            || EnabledMethods::default()
+                .get_enabled_method_names()
+                .iter()
+                .any(|name| name == method)
+    }
+
     /// Get the payment address from config or fallback to signer address
     pub fn get_payment_address(&self, signer_pubkey: &Pubkey) -> Result<Pubkey, KoraError> {
         if let Some(payment_address_str) = &self.payment_address {
@@ -559,6 +1468,7 @@ mod tests {
         fee::price::PriceModel,
         tests::toml_mock::{create_invalid_config, ConfigBuilder},
     };
+    use rust_decimal_macros::dec;
 
     use super::*;
 
@@ -617,6 +1527,15 @@ mod tests {
         assert!(config.kora.enabled_methods.transfer_transaction);
         assert!(config.kora.enabled_methods.get_blockhash);
         assert!(config.kora.enabled_methods.get_config);
+        assert!(!config.kora.enabled_methods.subscribe_transaction_events);
+        assert!(config.kora.enabled_methods.get_version);
+        assert!(config.kora.enabled_methods.get_instruction_template);
+        assert!(config.kora.enabled_methods.get_token_metadata);
+        assert!(!config.kora.enabled_methods.get_account_info);
+        assert!(config.kora.enabled_methods.get_network_status);
+        assert!(!config.kora.enabled_methods.sign_message);
+        assert!(!config.kora.enabled_methods.sign_transaction_batch);
+        assert!(config.kora.enabled_methods.simulate_transaction);
     }
 
     #[test]
@@ -637,6 +1556,31 @@ mod tests {
             ConfigBuilder::new().with_spl_paid_tokens(SplTokenConfig::All).build_config().unwrap();
 
         assert_eq!(config.validation.allowed_spl_paid_tokens, SplTokenConfig::All);
+
+        let config = ConfigBuilder::new()
+            .with_spl_paid_tokens(SplTokenConfig::Blocklist(vec!["token3".to_string()]))
+            .build_config()
+            .unwrap();
+
+        assert_eq!(
+            config.validation.allowed_spl_paid_tokens,
+            SplTokenConfig::Blocklist(vec!["token3".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_spl_token_config_blocklist() {
+        let blocklist = SplTokenConfig::Blocklist(vec!["blocked".to_string()]);
+
+        assert!(!blocklist.has_token("blocked"));
+        assert!(blocklist.has_token("any_other_token"));
+        assert!(blocklist.has_tokens());
+        assert!(blocklist.as_slice().is_empty());
+        assert_eq!(blocklist.into_iter().count(), 0);
+        assert_eq!(blocklist.blocked_tokens(), ["blocked".to_string()]);
+
+        // An empty blocklist accepts everything
+        assert!(SplTokenConfig::Blocklist(vec![]).has_token("blocked"));
     }
 
     #[test]
@@ -645,7 +1589,7 @@ mod tests {
 
         match &config.validation.price.model {
             PriceModel::Margin { margin } => {
-                assert_eq!(*margin, 0.1);
+                assert_eq!(*margin, dec!(0.1));
             }
             _ => panic!("Expected Margin price model"),
         }
@@ -687,7 +1631,7 @@ mod tests {
         // Should default to Margin with 0.0 margin
         match &config.validation.price.model {
             PriceModel::Margin { margin } => {
-                assert_eq!(*margin, 0.0);
+                assert!(margin.is_zero());
             }
             _ => panic!("Expected default Margin price model with 0.0 margin"),
         }
@@ -757,6 +1701,32 @@ mod tests {
         assert!(config.validation.token_2022.get_blocked_account_extensions().is_empty());
     }
 
+    #[test]
+    fn test_init_disallowed_accounts() {
+        let account = Pubkey::new_unique();
+        let address = account.to_string();
+        let mut config = ConfigBuilder::new()
+            .with_disallowed_accounts(vec![address.as_str(), address.as_str()])
+            .build_config()
+            .unwrap();
+
+        // Parsed on the fly until the config is initialized
+        assert!(config.validation.parsed_disallowed_accounts.is_none());
+        assert!(config.validation.get_disallowed_accounts().unwrap().contains(&account));
+
+        config.validation.init_disallowed_accounts().unwrap();
+        let disallowed = config.validation.get_disallowed_accounts().unwrap();
+        assert_eq!(disallowed.len(), 1);
+        assert!(disallowed.contains(&account));
+
+        let mut config =
+            ConfigBuilder::new().with_disallowed_accounts(vec!["account1"]).build_config().unwrap();
+        assert!(matches!(
+            config.validation.init_disallowed_accounts(),
+            Err(KoraError::InternalServerError(_))
+        ));
+    }
+
     #[test]
     fn test_token2022_extension_blocking_check() {
         let config = ConfigBuilder::new()
@@ -865,4 +1835,127 @@ mod tests {
 
         assert_eq!(config.kora.max_request_body_size, custom_size);
     }
+
+    #[test]
+    fn test_get_max_request_body_size_fits_batches() {
+        let mut kora = KoraConfig::default();
+        kora.enabled_methods.sign_transaction_batch = false;
+        kora.max_batch_size = 5_000;
+        assert_eq!(kora.get_max_request_body_size(), DEFAULT_MAX_REQUEST_BODY_SIZE);
+
+        // The default batch size fits in the default body size
+        kora.enabled_methods.sign_transaction_batch = true;
+        kora.max_batch_size = DEFAULT_MAX_BATCH_SIZE;
+        assert_eq!(kora.get_max_request_body_size(), DEFAULT_MAX_REQUEST_BODY_SIZE);
+
+        kora.max_batch_size = 5_000;
+        let batch_request_size =
+            5_000 * (MAX_ENCODED_TRANSACTION_SIZE + 3) + BATCH_REQUEST_ENVELOPE_SIZE;
+        assert!(batch_request_size > DEFAULT_MAX_REQUEST_BODY_SIZE);
+        assert_eq!(kora.get_max_request_body_size(), batch_request_size);
+    }
+
+    #[test]
+    fn test_get_proxy_method_names() {
+        let mut kora = KoraConfig {
+            proxy_allowed_methods: vec![
+                "getBalance".to_string(),
+                "signTransaction".to_string(),
+                "kora_getAccountInfo".to_string(),
+                "addSigner".to_string(),
+                "getProgramAccounts".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert!(kora.get_proxy_method_names().is_empty());
+
+        kora.enable_rpc_proxy = true;
+        assert_eq!(
+            kora.get_proxy_method_names(),
+            vec!["getBalance".to_string(), "getProgramAccounts".to_string()]
+        );
+    }
+
+    fn load_from_test_vars(vars: &[(&str, &str)]) -> Result<Config, KoraError> {
+        let vars: std::collections::HashMap<String, String> =
+            vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        Config::load_from_vars(|name| vars.get(name).cloned())
+    }
+
+    const REQUIRED_ENV_VARS: &[(&str, &str)] = &[
+        ("KORA_VALIDATION_MAX_ALLOWED_LAMPORTS", "1000000000"),
+        ("KORA_VALIDATION_MAX_SIGNATURES", "10"),
+        ("KORA_VALIDATION_ALLOWED_PROGRAMS", r#"["11111111111111111111111111111111"]"#),
+        ("KORA_VALIDATION_ALLOWED_TOKENS", "[]"),
+        ("KORA_VALIDATION_ALLOWED_SPL_PAID_TOKENS", "All"),
+        ("KORA_VALIDATION_DISALLOWED_ACCOUNTS", "[]"),
+        ("KORA_VALIDATION_PRICE_SOURCE", "Mock"),
+        ("KORA_KORA_RATE_LIMIT", "50"),
+    ];
+
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(
+            Config::env_var_name("validation.max_allowed_lamports"),
+            "KORA_VALIDATION_MAX_ALLOWED_LAMPORTS"
+        );
+        assert_eq!(Config::env_var_name("kora.rate_limit"), "KORA_KORA_RATE_LIMIT");
+
+        let keys = Config::env_keys();
+        assert!(keys.contains(&"validation.fee_payer_policy.system.nonce.allow_withdraw"));
+        assert!(keys.contains(&"metrics.push_gateway.auth.password"));
+        let names: HashSet<String> = keys.iter().map(|key| Config::env_var_name(key)).collect();
+        assert_eq!(names.len(), keys.len(), "env var names must be unique");
+    }
+
+    #[test]
+    fn test_load_from_env() {
+        let mut vars = REQUIRED_ENV_VARS.to_vec();
+        vars.extend([
+            ("KORA_VALIDATION_PRICE_TYPE", "free"),
+            ("KORA_VALIDATION_FEE_PAYER_POLICY_SYSTEM_ALLOW_TRANSFER", "false"),
+            (
+                "KORA_VALIDATION_FEE_ESTIMATION_STRATEGY",
+                r#"{ type = "simulation", commitment = "confirmed" }"#,
+            ),
+            ("KORA_KORA_AUTH_API_KEY", "secret-key"),
+            ("KORA_KORA_AUTH_HMAC_SECRET", r#""12345""#),
+            ("KORA_KORA_CACHE_URL", "redis://localhost:6379"),
+            ("KORA_METRICS_PORT", "9000"),
+            ("KORA_UNRELATED_SETTING", "ignored"),
+        ]);
+
+        let config = load_from_test_vars(&vars).unwrap();
+
+        assert_eq!(config.validation.max_allowed_lamports, 1_000_000_000);
+        assert_eq!(config.validation.allowed_programs, vec!["11111111111111111111111111111111"]);
+        assert_eq!(config.validation.allowed_spl_paid_tokens, SplTokenConfig::All);
+        assert_eq!(config.validation.price_source, PriceSource::Mock);
+        assert!(matches!(config.validation.price.model, PriceModel::Free));
+        assert!(!config.validation.fee_payer_policy.system.allow_transfer);
+        assert!(config.validation.fee_payer_policy.system.allow_assign);
+        assert!(matches!(
+            config.validation.fee_estimation_strategy,
+            FeeEstimationStrategy::Simulation { ref commitment } if commitment == "confirmed"
+        ));
+        assert_eq!(config.kora.rate_limit, 50);
+        assert_eq!(config.kora.auth.api_key.as_deref(), Some("secret-key"));
+        assert_eq!(config.kora.auth.hmac_secret.as_deref(), Some("12345"));
+        assert_eq!(config.kora.cache.url.as_deref(), Some("redis://localhost:6379"));
+        assert_eq!(config.kora.cache.default_ttl, DEFAULT_CACHE_DEFAULT_TTL);
+        assert_eq!(config.metrics.port, 9000);
+        assert!(!config.metrics.enabled);
+    }
+
+    #[test]
+    fn test_load_from_env_errors() {
+        let result = load_from_test_vars(&REQUIRED_ENV_VARS[1..]);
+        assert!(matches!(result, Err(KoraError::InternalServerError(msg))
+            if msg.contains("max_allowed_lamports")));
+
+        let mut vars = REQUIRED_ENV_VARS.to_vec();
+        vars.push(("KORA_KORA_AUTH_API_KEY", "12345"));
+        let result = load_from_test_vars(&vars);
+        assert!(matches!(result, Err(KoraError::InternalServerError(_))));
+    }
 }
@@ -0,0 +1,45 @@
+//! Stable error codes returned under `data.code` in JSON-RPC error responses, one per
+//! `KoraError` variant. Clients should match on these instead of error messages.
+
+/// An account the request depends on does not exist
+pub const ACCOUNT_NOT_FOUND: &str = "KORA_ACCOUNT_NOT_FOUND";
+/// The Solana RPC node returned an error
+pub const RPC_ERROR: &str = "KORA_RPC_ERROR";
+/// The signer could not sign the transaction
+pub const SIGNER_SIGNING_FAILED: &str = "KORA_SIGNER_SIGNING_FAILED";
+/// Every signing slot of the signer is taken, retry later
+pub const SIGNER_BUSY: &str = "KORA_SIGNER_BUSY";
+/// The transaction is malformed or rejected by Kora's checks
+pub const TRANSACTION_INVALID: &str = "KORA_TRANSACTION_INVALID";
+/// The transaction failed on-chain
+pub const TRANSACTION_EXECUTION_FAILED: &str = "KORA_TRANSACTION_EXECUTION_FAILED";
+/// The pre-flight simulation of the transaction failed, program logs are under `data.logs`
+pub const TRANSACTION_SIMULATION_FAILED: &str = "KORA_TRANSACTION_SIMULATION_FAILED";
+/// The fee of the transaction could not be estimated
+pub const FEE_ESTIMATION_FAILED: &str = "KORA_FEE_ESTIMATION_FAILED";
+/// The token is not accepted for fee payment
+pub const FEE_UNSUPPORTED_TOKEN: &str = "KORA_FEE_UNSUPPORTED_TOKEN";
+/// The payer can't cover the fee or the transferred amount
+pub const FEE_INSUFFICIENT_BALANCE: &str = "KORA_FEE_INSUFFICIENT_BALANCE";
+/// Unexpected server side failure
+pub const INTERNAL_ERROR: &str = "KORA_INTERNAL_ERROR";
+/// The transaction or request breaks the validation config
+pub const VALIDATION_FAILED: &str = "KORA_VALIDATION_FAILED";
+/// Data could not be encoded or decoded
+pub const SERIALIZATION_ERROR: &str = "KORA_SERIALIZATION_ERROR";
+/// A token swap failed
+pub const SWAP_FAILED: &str = "KORA_SWAP_FAILED";
+/// A token program operation failed
+pub const TOKEN_OPERATION_FAILED: &str = "KORA_TOKEN_OPERATION_FAILED";
+/// The request parameters are invalid
+pub const INVALID_REQUEST: &str = "KORA_INVALID_REQUEST";
+/// Missing or wrong credentials
+pub const UNAUTHORIZED: &str = "KORA_UNAUTHORIZED";
+/// Too many requests in a short time
+pub const RATE_LIMIT_EXCEEDED: &str = "KORA_RATE_LIMIT_EXCEEDED";
+/// The sender reached its limit for the current window, retry after `data.retry_after_ms`
+pub const RATE_LIMITED: &str = "KORA_RATE_LIMITED";
+/// The sender used up its transaction allowance
+pub const USAGE_LIMIT_EXCEEDED: &str = "KORA_USAGE_LIMIT_EXCEEDED";
+/// The Kora configuration is invalid
+pub const CONFIG_ERROR: &str = "KORA_CONFIG_ERROR";
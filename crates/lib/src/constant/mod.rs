@@ -1,6 +1,11 @@
+pub mod error_codes;
+
 pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
 pub const NATIVE_SOL: &str = "11111111111111111111111111111111";
 pub const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+pub const MICRO_LAMPORTS_PER_LAMPORT: u64 = 1_000_000;
+pub const DEFAULT_COMPUTE_UNITS_PER_INSTRUCTION: u64 = 200_000;
+pub const MAX_COMPUTE_UNITS_PER_TRANSACTION: u64 = 1_400_000;
 pub const ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION: u64 = 50;
 pub const MIN_BALANCE_FOR_RENT_EXEMPTION: u64 = 2_039_280;
 pub const DEFAULT_INTEREST_MULTIPLIER: u128 = 100 * 24 * 60 * 60 / 10000 / (365 * 24 * 60 * 60);
@@ -9,8 +14,30 @@ pub const DEFAULT_INTEREST_MULTIPLIER: u128 = 100 * 24 * 60 * 60 / 10000 / (365
 pub const X_API_KEY: &str = "x-api-key";
 pub const X_HMAC_SIGNATURE: &str = "x-hmac-signature";
 pub const X_TIMESTAMP: &str = "x-timestamp";
+pub const X_ADMIN_API_KEY: &str = "x-admin-api-key";
+pub const X_KORA_SIGNATURE: &str = "x-kora-signature";
 pub const DEFAULT_MAX_TIMESTAMP_AGE: i64 = 300;
 
+// Prefix of the environment variables read by `Config::load_from_env`
+pub const CONFIG_ENV_PREFIX: &str = "KORA_";
+
+// Admin methods, only registered when an admin API key is configured
+#[cfg(not(feature = "dex"))]
+pub const ADMIN_METHODS: &[&str] =
+    &["addSigner", "kora_getDeadLetterQueue", "kora_getProgramAccounts", "kora_setLogLevel"];
+#[cfg(feature = "dex")]
+pub const ADMIN_METHODS: &[&str] = &[
+    "addSigner",
+    "kora_getDeadLetterQueue",
+    "kora_getProgramAccounts",
+    "kora_placeMarketOrder",
+    "kora_setLogLevel",
+];
+
+// Logging
+pub const DEFAULT_LOG_FILTER_OVERRIDES: &str =
+    "sqlx=error,sea_orm_migration=error,jsonrpsee_server=warn";
+
 // External Services
 pub const JUPITER_API_LITE_URL: &str = "https://lite-api.jup.ag";
 pub const JUPITER_API_PRO_URL: &str = "https://api.jup.ag";
@@ -19,18 +46,63 @@ pub const JUPITER_API_PRO_URL: &str = "https://api.jup.ag";
 pub const DEFAULT_METRICS_ENDPOINT: &str = "/metrics";
 pub const DEFAULT_METRICS_PORT: u16 = 8080;
 pub const DEFAULT_METRICS_SCRAPE_INTERVAL: u64 = 60;
+pub const DEFAULT_PUSH_GATEWAY_JOB_NAME: &str = "kora";
+pub const DEFAULT_PUSH_GATEWAY_INTERVAL_SECONDS: u64 = 15;
 
 // Cache
 pub const DEFAULT_CACHE_DEFAULT_TTL: u64 = 300; // 5 minutes
 pub const DEFAULT_CACHE_ACCOUNT_TTL: u64 = 60; // 1 minute for account data
+pub const DEFAULT_TOKEN_BALANCE_CACHE_TTL: u64 = 5; // 5 seconds for in-memory token balances
+pub const DEFAULT_TOKEN_METADATA_CACHE_TTL: u64 = 3600; // 1 hour for in-memory token metadata
 pub const DEFAULT_FEE_PAYER_BALANCE_METRICS_EXPIRY_SECONDS: u64 = 30; // 30 seconds
 
 pub const DEFAULT_USAGE_LIMIT_MAX_TRANSACTIONS: u64 = 0; // 0 = unlimited
 pub const DEFAULT_USAGE_LIMIT_FALLBACK_IF_UNAVAILABLE: bool = false;
+pub const DEFAULT_USAGE_LIMIT_WINDOW_SECONDS: u64 = 86400; // 24 hours, only used by sliding windows
+pub const DEFAULT_USAGE_LIMIT_CLEANUP_INTERVAL_SECONDS: u64 = 60; // 1 minute
 
 // Request body size limit
 pub const DEFAULT_MAX_REQUEST_BODY_SIZE: usize = 2 * 1024 * 1024; // 2 MB
 
+// Transaction batches of signTransactionBatch
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 20;
+pub const MAX_ENCODED_TRANSACTION_SIZE: usize = 1644; // Base64 of the 1232 byte packet limit
+pub const BATCH_REQUEST_ENVELOPE_SIZE: usize = 1024; // JSON-RPC envelope and other parameters
+
+// Transaction confirmation, used by signAndSendTransaction with `wait_for_commitment`
+pub const DEFAULT_CONFIRMATION_TIMEOUT_MS: u64 = 30_000;
+pub const MAX_CONFIRMATION_TIMEOUT_MS: u64 = 120_000;
+pub const CONFIRMATION_POLL_INTERVAL_MS: u64 = 500;
+
+// Pre-flight simulation of signTransaction, only used with `preflight_simulate`
+pub const DEFAULT_PREFLIGHT_SIMULATION_COMMITMENT: &str = "confirmed";
+
+// Signing requests allowed to wait for a signing slot, only used with `backpressure_timeout_ms`
+pub const DEFAULT_BACKPRESSURE_QUEUE_CAPACITY: usize = 1000;
+
+// Failed signing attempts kept for the kora_getDeadLetterQueue admin method
+pub const DEFAULT_DEAD_LETTER_QUEUE_CAPACITY: usize = 100;
+
+// Signer health checks, only run with `health_check.interval_seconds` in the signers config
+pub const DEFAULT_SIGNER_UNHEALTHY_THRESHOLD: u32 = 3;
+pub const SIGNER_HEALTH_CHECK_TIMEOUT_SECONDS: u64 = 10;
+
+// Subscriptions
+pub const DEFAULT_TRANSACTION_EVENT_CHANNEL_CAPACITY: usize = 100;
+
+// Webhooks
+pub const DEFAULT_WEBHOOK_CHANNEL_CAPACITY: usize = 1000;
+pub const DEFAULT_WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+
+// RPC proxy, only used with `enable_rpc_proxy`
+pub const DEFAULT_RPC_PROXY_TIMEOUT_SECONDS: u64 = 30;
+
+// Network status of kora_getNetworkStatus and kora_isNetworkCongested
+pub const NETWORK_STATUS_CACHE_TTL_SECONDS: u64 = 5;
+pub const NETWORK_STATUS_PERFORMANCE_SAMPLES: usize = 5; // Samples cover 60 seconds each
+pub const NETWORK_CONGESTION_MEDIUM_TPS: u64 = 2_500;
+pub const NETWORK_CONGESTION_HIGH_TPS: u64 = 4_000;
+
 // Account Indexes within instructions
 // Instruction indexes for the instructions that we support to parse from the transaction
 pub mod instruction_indexes {
@@ -95,7 +167,11 @@ pub mod instruction_indexes {
         pub const NONCE_AUTHORITY_INDEX: usize = 1;
     }
 
-    // Note: system_upgrade_nonce_account not included - no authority parameter, cannot validate
+    pub mod system_upgrade_nonce_account {
+        pub const REQUIRED_NUMBER_OF_ACCOUNTS: usize = 1;
+        pub const NONCE_ACCOUNT_INDEX: usize = 0;
+        // No authority, anyone can upgrade a nonce account
+    }
 
     pub mod spl_token_transfer {
         pub const REQUIRED_NUMBER_OF_ACCOUNTS: usize = 3;
@@ -134,7 +210,10 @@ pub mod instruction_indexes {
 
     pub mod spl_token_revoke {
         pub const REQUIRED_NUMBER_OF_ACCOUNTS: usize = 2;
+        pub const SOURCE_ADDRESS_INDEX: usize = 0;
         pub const OWNER_INDEX: usize = 1;
+        // Multisig signers are accounts from index 2 onwards (when owner is a multisig account)
+        pub const MULTISIG_SIGNERS_START_INDEX: usize = 2;
     }
 
     pub mod spl_token_set_authority {
@@ -1,5 +1,8 @@
-use crate::sanitize::sanitize_message;
-use jsonrpsee::{core::Error as RpcError, types::error::CallError};
+use crate::{constant::error_codes, sanitize::sanitize_message};
+use jsonrpsee::{
+    core::Error as RpcError,
+    types::error::{CallError, ErrorObject, CALL_EXECUTION_FAILED_CODE},
+};
 use serde::{Deserialize, Serialize};
 use solana_client::client_error::ClientError;
 use solana_program::program_error::ProgramError;
@@ -18,12 +21,18 @@ pub enum KoraError {
     #[error("Signing error: {0}")]
     SigningError(String),
 
+    #[error("Signer busy: {0}")]
+    SignerBusy(String),
+
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(String),
 
     #[error("Transaction execution failed: {0}")]
     TransactionExecutionFailed(String),
 
+    #[error("Transaction simulation failed")]
+    TransactionSimulationFailed { logs: Vec<String> },
+
     #[error("Fee estimation failed: {0}")]
     FeeEstimationFailed(String),
 
@@ -57,6 +66,9 @@ pub enum KoraError {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    #[error("Rate limit exceeded, retry after {retry_after_ms}ms")]
+    RateLimited { retry_after_ms: u64 },
+
     #[error("Usage limit exceeded: {0}")]
     UsageLimitExceeded(String),
 
@@ -64,6 +76,37 @@ pub enum KoraError {
     ConfigError,
 }
 
+impl KoraError {
+    /// Stable machine-readable code of the error, see `constant::error_codes`
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            KoraError::AccountNotFound(_) => error_codes::ACCOUNT_NOT_FOUND,
+            KoraError::RpcError(_) => error_codes::RPC_ERROR,
+            KoraError::SigningError(_) => error_codes::SIGNER_SIGNING_FAILED,
+            KoraError::SignerBusy(_) => error_codes::SIGNER_BUSY,
+            KoraError::InvalidTransaction(_) => error_codes::TRANSACTION_INVALID,
+            KoraError::TransactionExecutionFailed(_) => error_codes::TRANSACTION_EXECUTION_FAILED,
+            KoraError::TransactionSimulationFailed { .. } => {
+                error_codes::TRANSACTION_SIMULATION_FAILED
+            }
+            KoraError::FeeEstimationFailed(_) => error_codes::FEE_ESTIMATION_FAILED,
+            KoraError::UnsupportedFeeToken(_) => error_codes::FEE_UNSUPPORTED_TOKEN,
+            KoraError::InsufficientFunds(_) => error_codes::FEE_INSUFFICIENT_BALANCE,
+            KoraError::InternalServerError(_) => error_codes::INTERNAL_ERROR,
+            KoraError::ValidationError(_) => error_codes::VALIDATION_FAILED,
+            KoraError::SerializationError(_) => error_codes::SERIALIZATION_ERROR,
+            KoraError::SwapError(_) => error_codes::SWAP_FAILED,
+            KoraError::TokenOperationError(_) => error_codes::TOKEN_OPERATION_FAILED,
+            KoraError::InvalidRequest(_) => error_codes::INVALID_REQUEST,
+            KoraError::Unauthorized(_) => error_codes::UNAUTHORIZED,
+            KoraError::RateLimitExceeded => error_codes::RATE_LIMIT_EXCEEDED,
+            KoraError::RateLimited { .. } => error_codes::RATE_LIMITED,
+            KoraError::UsageLimitExceeded(_) => error_codes::USAGE_LIMIT_EXCEEDED,
+            KoraError::ConfigError => error_codes::CONFIG_ERROR,
+        }
+    }
+}
+
 impl From<ClientError> for KoraError {
     fn from(e: ClientError) -> Self {
         let error_string = e.to_string();
@@ -215,11 +258,32 @@ impl From<KoraError> for RpcError {
 }
 
 pub fn invalid_request(e: KoraError) -> RpcError {
-    RpcError::Call(CallError::from_std_error(e))
+    call_error_with_code(e)
 }
 
 pub fn internal_server_error(e: KoraError) -> RpcError {
-    RpcError::Call(CallError::from_std_error(e))
+    call_error_with_code(e)
+}
+
+/// Same code and message as `CallError::from_std_error`, plus the error code under `data.code`
+/// and, for rate limits, the backoff under `data.retry_after_ms`, for failed simulations, the
+/// program logs under `data.logs`
+fn call_error_with_code(e: KoraError) -> RpcError {
+    let mut data = serde_json::json!({ "code": e.error_code() });
+    match &e {
+        KoraError::RateLimited { retry_after_ms } => {
+            data["retry_after_ms"] = (*retry_after_ms).into();
+        }
+        KoraError::TransactionSimulationFailed { logs } => {
+            data["logs"] = logs.clone().into();
+        }
+        _ => {}
+    }
+    RpcError::Call(CallError::Custom(ErrorObject::owned(
+        CALL_EXECUTION_FAILED_CODE,
+        e.to_string(),
+        Some(data),
+    )))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -458,12 +522,14 @@ mod tests {
             KoraError::RpcError("test".to_string()),
             KoraError::SigningError("test".to_string()),
             KoraError::TransactionExecutionFailed("test".to_string()),
+            KoraError::TransactionSimulationFailed { logs: vec!["test".to_string()] },
             KoraError::FeeEstimationFailed("test".to_string()),
             KoraError::SwapError("test".to_string()),
             KoraError::TokenOperationError("test".to_string()),
             KoraError::InvalidRequest("test".to_string()),
             KoraError::Unauthorized("test".to_string()),
             KoraError::RateLimitExceeded,
+            KoraError::RateLimited { retry_after_ms: 1000 },
         ];
 
         for kora_error in other_errors {
@@ -472,6 +538,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_error_codes() {
+        let all_errors = vec![
+            KoraError::AccountNotFound("test".to_string()),
+            KoraError::RpcError("test".to_string()),
+            KoraError::SigningError("test".to_string()),
+            KoraError::SignerBusy("test".to_string()),
+            KoraError::InvalidTransaction("test".to_string()),
+            KoraError::TransactionExecutionFailed("test".to_string()),
+            KoraError::TransactionSimulationFailed { logs: vec![] },
+            KoraError::FeeEstimationFailed("test".to_string()),
+            KoraError::UnsupportedFeeToken("test".to_string()),
+            KoraError::InsufficientFunds("test".to_string()),
+            KoraError::InternalServerError("test".to_string()),
+            KoraError::ValidationError("test".to_string()),
+            KoraError::SerializationError("test".to_string()),
+            KoraError::SwapError("test".to_string()),
+            KoraError::TokenOperationError("test".to_string()),
+            KoraError::InvalidRequest("test".to_string()),
+            KoraError::Unauthorized("test".to_string()),
+            KoraError::RateLimitExceeded,
+            KoraError::RateLimited { retry_after_ms: 1000 },
+            KoraError::UsageLimitExceeded("test".to_string()),
+            KoraError::ConfigError,
+        ];
+
+        let mut codes = std::collections::HashSet::new();
+        for error in &all_errors {
+            let code = error.error_code();
+            assert!(code.starts_with("KORA_") && code.len() > "KORA_".len(), "{error:?}: {code}");
+            assert!(
+                code.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+                "{error:?}: {code}"
+            );
+            codes.insert(code);
+        }
+        assert_eq!(codes.len(), all_errors.len(), "error codes must be unique");
+    }
+
+    #[test]
+    fn test_rpc_error_includes_error_code() {
+        let rpc_error: RpcError = KoraError::UnsupportedFeeToken("mint".to_string()).into();
+        let RpcError::Call(CallError::Custom(error_object)) = rpc_error else {
+            panic!("expected a custom call error");
+        };
+
+        assert_eq!(error_object.code(), CALL_EXECUTION_FAILED_CODE);
+        assert_eq!(error_object.message(), "Token mint is not supported for fee payment");
+        let data: serde_json::Value =
+            serde_json::from_str(error_object.data().unwrap().get()).unwrap();
+        assert_eq!(data["code"], error_codes::FEE_UNSUPPORTED_TOKEN);
+    }
+
+    #[test]
+    fn test_rpc_error_includes_retry_after() {
+        let rpc_error: RpcError = KoraError::RateLimited { retry_after_ms: 1500 }.into();
+        let RpcError::Call(CallError::Custom(error_object)) = rpc_error else {
+            panic!("expected a custom call error");
+        };
+
+        let data: serde_json::Value =
+            serde_json::from_str(error_object.data().unwrap().get()).unwrap();
+        assert_eq!(data["code"], error_codes::RATE_LIMITED);
+        assert_eq!(data["retry_after_ms"], 1500);
+    }
+
+    #[test]
+    fn test_rpc_error_includes_simulation_logs() {
+        let logs = vec![
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Transfer: insufficient lamports 0, need 1000".to_string(),
+        ];
+        let rpc_error: RpcError = KoraError::TransactionSimulationFailed { logs }.into();
+        let RpcError::Call(CallError::Custom(error_object)) = rpc_error else {
+            panic!("expected a custom call error");
+        };
+
+        let data: serde_json::Value =
+            serde_json::from_str(error_object.data().unwrap().get()).unwrap();
+        assert_eq!(data["code"], error_codes::TRANSACTION_SIMULATION_FAILED);
+        assert_eq!(data["logs"][1], "Transfer: insufficient lamports 0, need 1000");
+    }
+
     #[test]
     fn test_invalid_request_function() {
         let error = KoraError::ValidationError("invalid input".to_string());
@@ -1,9 +1,12 @@
 use std::str::FromStr;
 
 use crate::{
-    constant::{ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION, LAMPORTS_PER_SIGNATURE},
+    constant::{
+        ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION, LAMPORTS_PER_SIGNATURE,
+        MICRO_LAMPORTS_PER_LAMPORT,
+    },
     error::KoraError,
-    fee::price::PriceModel,
+    fee::{price::PriceModel, priced::PricedFeeCalculator},
     oracle::PriceSource,
     token::{
         spl_token_2022::Token2022Mint,
@@ -11,9 +14,10 @@ use crate::{
         TokenState,
     },
     transaction::{
-        ParsedSPLInstructionData, ParsedSPLInstructionType, ParsedSystemInstructionData,
+        IxUtils, ParsedSPLInstructionData, ParsedSPLInstructionType, ParsedSystemInstructionData,
         ParsedSystemInstructionType, VersionedTransactionResolved,
     },
+    validator::transaction_validator::ValidationContext,
 };
 
 #[cfg(not(test))]
@@ -21,9 +25,26 @@ use {crate::cache::CacheUtil, crate::state::get_config};
 
 #[cfg(test)]
 use crate::tests::{cache_mock::MockCacheUtil as CacheUtil, config_mock::mock_state::get_config};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use serde::{Deserialize, Serialize};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_commitment_config::CommitmentConfig;
 use solana_message::VersionedMessage;
 use solana_sdk::pubkey::Pubkey;
+use utoipa::ToSchema;
+
+/// How the base fee (signatures and priority fee) of a transaction is estimated
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeEstimationStrategy {
+    /// Ask the RPC node with `getFeeForMessage`
+    #[default]
+    GetFeeForMessage,
+    /// Price `compute_units` at `priority_fee` micro-lamports per unit, without any RPC call
+    StaticCalculation { compute_units: u64, priority_fee: u64 },
+    /// Simulate the transaction at `commitment` and price the compute units it consumed at
+    /// its own `SetComputeUnitPrice`
+    Simulation { commitment: String },
+}
 
 #[derive(Debug, Clone)]
 pub struct TotalFeeCalculation {
@@ -133,8 +154,41 @@ impl FeeConfigUtil {
         }
     }
 
+    /// Value in lamports of the fee a Token2022 mint's `TransferFeeConfig` withholds from a
+    /// transfer of `amount`, 0 when the mint has no transfer fee
+    async fn calculate_transfer_fee_in_lamports(
+        rpc_client: &RpcClient,
+        mint_pubkey: &Pubkey,
+        amount: u64,
+        price_source: &PriceSource,
+    ) -> Result<u64, KoraError> {
+        let mint_account = CacheUtil::get_account(rpc_client, mint_pubkey, true).await?;
+
+        let token_program = TokenType::get_token_program_from_owner(&mint_account.owner)?;
+        let mint_state = token_program.unpack_mint(mint_pubkey, &mint_account.data)?;
+
+        let Some(token2022_mint) = mint_state.as_any().downcast_ref::<Token2022Mint>() else {
+            return Ok(0);
+        };
+
+        let current_epoch = rpc_client.get_epoch_info().await?.epoch;
+
+        match token2022_mint.calculate_transfer_fee(amount, current_epoch)? {
+            Some(fee_amount) if fee_amount > 0 => {
+                TokenUtil::calculate_token_value_in_lamports(
+                    fee_amount,
+                    mint_pubkey,
+                    price_source.clone(),
+                    rpc_client,
+                )
+                .await
+            }
+            _ => Ok(0),
+        }
+    }
+
     /// Analyze payment instructions in transaction
-    /// Returns (has_payment, total_transfer_fees)
+    /// Returns (has_payment, total_transfer_fees) with transfer fees in lamports
     async fn analyze_payment_instructions(
         resolved_transaction: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
@@ -171,39 +225,28 @@ impl FeeConfigUtil {
                 if payment_info.is_some() {
                     has_payment = true;
 
-                    // Calculate Token2022 transfer fees if applicable
-                    if *is_2022 {
+                    // Token2022 mints with a transfer fee withhold part of the payment
+                    if config.validation.account_for_transfer_fee && *is_2022 {
                         if let Some(mint_pubkey) = mint {
-                            let mint_account =
-                                CacheUtil::get_account(rpc_client, mint_pubkey, true).await?;
-
-                            let token_program =
-                                TokenType::get_token_program_from_owner(&mint_account.owner)?;
-                            let mint_state =
-                                token_program.unpack_mint(mint_pubkey, &mint_account.data)?;
-
-                            if let Some(token2022_mint) =
-                                mint_state.as_any().downcast_ref::<Token2022Mint>()
-                            {
-                                let current_epoch = rpc_client.get_epoch_info().await?.epoch;
-
-                                if let Some(fee_amount) =
-                                    token2022_mint.calculate_transfer_fee(*amount, current_epoch)?
-                                {
-                                    total_transfer_fees = total_transfer_fees
-                                        .checked_add(fee_amount)
-                                        .ok_or_else(|| {
-                                            log::error!(
-                                                "Transfer fee accumulation overflow: total={}, new_fee={}",
-                                                total_transfer_fees,
-                                                fee_amount
-                                            );
-                                            KoraError::ValidationError(
-                                                "Transfer fee accumulation overflow".to_string(),
-                                            )
-                                        })?;
-                                }
-                            }
+                            let fee_amount = Self::calculate_transfer_fee_in_lamports(
+                                rpc_client,
+                                mint_pubkey,
+                                *amount,
+                                &config.validation.price_source,
+                            )
+                            .await?;
+
+                            total_transfer_fees =
+                                total_transfer_fees.checked_add(fee_amount).ok_or_else(|| {
+                                    log::error!(
+                                        "Transfer fee accumulation overflow: total={}, new_fee={}",
+                                        total_transfer_fees,
+                                        fee_amount
+                                    );
+                                    KoraError::ValidationError(
+                                        "Transfer fee accumulation overflow".to_string(),
+                                    )
+                                })?;
                         }
                     }
                 }
@@ -219,9 +262,15 @@ impl FeeConfigUtil {
         fee_payer: &Pubkey,
         is_payment_required: bool,
     ) -> Result<TotalFeeCalculation, KoraError> {
+        let config = get_config()?;
+
         // Get base transaction fee using resolved transaction to handle lookup tables
-        let base_fee =
-            TransactionFeeUtil::get_estimate_fee_resolved(rpc_client, transaction).await?;
+        let strategy = &config.validation.fee_estimation_strategy;
+        let base_fee = if config.validation.use_rpc_fee_estimate {
+            PricedFeeCalculator::estimate_base_fee(rpc_client, transaction, strategy).await?
+        } else {
+            TransactionFeeUtil::estimate_base_fee(rpc_client, transaction, strategy).await?
+        };
 
         // Priority fees are now included in the calculate done by the RPC getFeeForMessage
         // ATA and Token account creation fees are captured in the calculate fee payer outflow (System Transfer)
@@ -233,12 +282,12 @@ impl FeeConfigUtil {
         }
 
         // Calculate fee payer outflow if fee payer is provided, to better estimate the potential fee
-        let config = get_config()?;
         let fee_payer_outflow = FeeConfigUtil::calculate_fee_payer_outflow(
             fee_payer,
             transaction,
             rpc_client,
             &config.validation.price_source,
+            &ValidationContext::new(),
         )
         .await?;
 
@@ -383,6 +432,7 @@ impl FeeConfigUtil {
         transaction: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
         price_source: &PriceSource,
+        validation_ctx: &ValidationContext,
     ) -> Result<u64, KoraError> {
         let mut total = 0u64;
 
@@ -458,6 +508,7 @@ impl FeeConfigUtil {
                 fee_payer_pubkey,
                 price_source,
                 rpc_client,
+                validation_ctx,
             )
             .await?;
 
@@ -486,6 +537,11 @@ impl TransactionFeeUtil {
     }
 
     /// Get fee estimate for a resolved transaction, handling V0 transactions with lookup tables
+    ///
+    /// The message is sent as-is: the RPC node expands lookup tables itself when pricing a V0
+    /// message, and the base fee only depends on signatures and compute budget, not on how many
+    /// accounts are loaded. The resolved accounts matter for the rest of the estimate (signer
+    /// detection and fee payer outflow), which read `all_account_keys`.
     pub async fn get_estimate_fee_resolved(
         rpc_client: &RpcClient,
         resolved_transaction: &VersionedTransactionResolved,
@@ -501,6 +557,95 @@ impl TransactionFeeUtil {
         }
         .map_err(|e| KoraError::RpcError(e.to_string()))
     }
+
+    /// Base fee of a resolved transaction, estimated with the configured `strategy`
+    pub async fn estimate_base_fee(
+        rpc_client: &RpcClient,
+        resolved_transaction: &VersionedTransactionResolved,
+        strategy: &FeeEstimationStrategy,
+    ) -> Result<u64, KoraError> {
+        let num_signatures =
+            resolved_transaction.transaction.message.header().num_required_signatures as u64;
+
+        match strategy {
+            FeeEstimationStrategy::GetFeeForMessage => {
+                Self::get_estimate_fee_resolved(rpc_client, resolved_transaction).await
+            }
+            FeeEstimationStrategy::StaticCalculation { compute_units, priority_fee } => {
+                Self::calculate_fee(num_signatures, *compute_units, *priority_fee)
+            }
+            FeeEstimationStrategy::Simulation { commitment } => {
+                let compute_units =
+                    Self::simulate_compute_units(rpc_client, resolved_transaction, commitment)
+                        .await?;
+                let compute_unit_price =
+                    IxUtils::parse_compute_budget_instructions(resolved_transaction)?
+                        .price_micro_lamports
+                        .unwrap_or(0);
+
+                Self::calculate_fee(num_signatures, compute_units, compute_unit_price)
+            }
+        }
+    }
+
+    /// Signature fees plus `compute_units` priced at `micro_lamports_per_unit`, rounded up
+    pub fn calculate_fee(
+        num_signatures: u64,
+        compute_units: u64,
+        micro_lamports_per_unit: u64,
+    ) -> Result<u64, KoraError> {
+        let priority_fee = (compute_units as u128 * micro_lamports_per_unit as u128)
+            .div_ceil(MICRO_LAMPORTS_PER_LAMPORT as u128);
+
+        num_signatures
+            .checked_mul(LAMPORTS_PER_SIGNATURE)
+            .and_then(|signature_fee| {
+                u64::try_from(priority_fee).ok().and_then(|fee| signature_fee.checked_add(fee))
+            })
+            .ok_or_else(|| {
+                log::error!(
+                    "Base fee calculation overflow: signatures={}, compute_units={}, price={}",
+                    num_signatures,
+                    compute_units,
+                    micro_lamports_per_unit
+                );
+                KoraError::ValidationError("Fee calculation overflow".to_string())
+            })
+    }
+
+    async fn simulate_compute_units(
+        rpc_client: &RpcClient,
+        resolved_transaction: &VersionedTransactionResolved,
+        commitment: &str,
+    ) -> Result<u64, KoraError> {
+        let commitment = CommitmentConfig::from_str(commitment).map_err(|_| {
+            KoraError::ValidationError(format!("Invalid simulation commitment: {commitment}"))
+        })?;
+
+        // Estimates are usually requested for unsigned transactions with a placeholder blockhash
+        let simulation_result = rpc_client
+            .simulate_transaction_with_config(
+                &resolved_transaction.transaction,
+                RpcSimulateTransactionConfig {
+                    commitment: Some(commitment),
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| KoraError::RpcError(format!("Failed to simulate transaction: {e}")))?;
+
+        if let Some(err) = simulation_result.value.err {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Transaction simulation failed: {err}"
+            )));
+        }
+
+        simulation_result.value.units_consumed.ok_or_else(|| {
+            KoraError::RpcError("Simulation did not report consumed compute units".to_string())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -512,21 +657,30 @@ mod tests {
         tests::{
             common::{
                 create_mock_rpc_client_with_account, create_mock_token_account,
-                setup_or_get_test_config, setup_or_get_test_signer,
+                create_transfer_fee_config, setup_or_get_test_config, setup_or_get_test_signer,
+                MintAccountMockBuilder, TokenAccountMockBuilder,
             },
             config_mock::ConfigMockBuilder,
             rpc_mock::RpcMockBuilder,
+            transaction_mock::TransactionMockBuilder,
+        },
+        token::{
+            interface::TokenInterface, spl_token::TokenProgram, spl_token_2022::Token2022Program,
         },
-        token::{interface::TokenInterface, spl_token::TokenProgram},
         transaction::TransactionUtil,
     };
-    use solana_message::{v0, Message, VersionedMessage};
+    use base64::Engine;
+    use serde_json::json;
+    use solana_address_lookup_table_interface::state::{AddressLookupTable, LookupTableMeta};
+    use solana_client::rpc_request::RpcRequest;
+    use solana_message::{v0, AddressLookupTableAccount, Message, VersionedMessage};
     use solana_sdk::{
         account::Account,
         hash::Hash,
         instruction::Instruction,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
+        transaction::VersionedTransaction,
     };
     use solana_system_interface::{
         instruction::{
@@ -633,6 +787,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -650,6 +805,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -667,6 +823,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -698,6 +855,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -722,6 +880,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -750,6 +909,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -768,6 +928,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -800,6 +961,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -829,6 +991,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -850,6 +1013,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -882,6 +1046,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -912,6 +1077,7 @@ mod tests {
             &mut resolved_transaction,
             &mocked_rpc_client,
             &crate::oracle::PriceSource::Mock,
+            &ValidationContext::new(),
         )
         .await
         .unwrap();
@@ -1038,6 +1204,67 @@ mod tests {
         assert_eq!(transfer_fees, 0, "Should have no transfer fees");
     }
 
+    #[tokio::test]
+    async fn test_analyze_payment_instructions_with_token2022_transfer_fee() {
+        let signer = setup_or_get_test_signer();
+        let sender = Keypair::new();
+        let mint = Pubkey::new_unique();
+
+        // 1% transfer fee, capped well above the fee for this payment
+        let mint_account = MintAccountMockBuilder::new()
+            .with_decimals(6)
+            .with_transfer_fee_config(create_transfer_fee_config(100, 1_000_000))
+            .build_token2022();
+        let payment_account =
+            TokenAccountMockBuilder::new().with_owner(&signer).with_mint(&mint).build_token2022();
+
+        // Token prices are read through the real cache util, which falls back to the RPC client
+        let rpc_client =
+            RpcMockBuilder::new().with_account_info(&mint_account).with_epoch_info_mock().build();
+
+        let sender_token_account = get_associated_token_address(&sender.pubkey(), &mint);
+        let payment_token_account = get_associated_token_address(&signer, &mint);
+        let transfer_instruction = Token2022Program::new()
+            .create_transfer_checked_instruction(
+                &sender_token_account,
+                &mint,
+                &payment_token_account,
+                &sender.pubkey(),
+                1_000_000,
+                6,
+            )
+            .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(&[transfer_instruction], None));
+
+        for (account_for_transfer_fee, expected_fee) in [(false, 0), (true, 10_000)] {
+            let _m = ConfigMockBuilder::new()
+                .with_account_for_transfer_fee(account_for_transfer_fee)
+                .build_and_setup();
+            let cache_ctx = CacheUtil::get_account_context();
+            cache_ctx.checkpoint();
+            let (mint_account, payment_account) = (mint_account.clone(), payment_account.clone());
+            cache_ctx.expect().returning(move |_, pubkey, _| {
+                Ok(if *pubkey == mint { mint_account.clone() } else { payment_account.clone() })
+            });
+
+            let mut resolved_transaction =
+                TransactionUtil::new_unsigned_versioned_transaction_resolved(message.clone())
+                    .unwrap();
+
+            let (has_payment, transfer_fees) = FeeConfigUtil::analyze_payment_instructions(
+                &mut resolved_transaction,
+                &rpc_client,
+                &signer,
+            )
+            .await
+            .unwrap();
+
+            assert!(has_payment);
+            // 1% of 1 token is 10_000 base units, 0.01 token * 0.001 SOL = 10_000 lamports
+            assert_eq!(transfer_fees, expected_fee);
+        }
+    }
+
     #[tokio::test]
     async fn test_estimate_transaction_fee_basic() {
         let _m = ConfigMockBuilder::new().build_and_setup();
@@ -1070,6 +1297,41 @@ mod tests {
         assert_eq!(result.total_fee_lamports, 105_000, "Should return base fee + outflow");
     }
 
+    #[tokio::test]
+    async fn test_estimate_transaction_fee_with_rpc_fee_estimate() {
+        // The static strategy alone would price the transfer at 5000 lamports
+        let strategy =
+            FeeEstimationStrategy::StaticCalculation { compute_units: 0, priority_fee: 0 };
+        let fee_payer = Keypair::new();
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&fee_payer.pubkey(), &Pubkey::new_unique(), 100_000)],
+            Some(&fee_payer.pubkey()),
+        ));
+        let mocked_rpc_client = RpcMockBuilder::new().with_fee_estimate(7_500).build();
+
+        for (use_rpc_fee_estimate, expected_base_fee) in [(false, 5_000), (true, 7_500)] {
+            let _m = ConfigMockBuilder::new()
+                .with_fee_estimation_strategy(strategy.clone())
+                .with_use_rpc_fee_estimate(use_rpc_fee_estimate)
+                .build_and_setup();
+            let mut resolved_transaction =
+                TransactionUtil::new_unsigned_versioned_transaction_resolved(message.clone())
+                    .unwrap();
+
+            let result = FeeConfigUtil::estimate_transaction_fee(
+                &mocked_rpc_client,
+                &mut resolved_transaction,
+                &fee_payer.pubkey(),
+                false,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.base_fee, expected_base_fee);
+            assert_eq!(result.total_fee_lamports, expected_base_fee + 100_000);
+        }
+    }
+
     #[tokio::test]
     async fn test_estimate_transaction_fee_kora_signer_not_in_signers() {
         let _m = ConfigMockBuilder::new().build_and_setup();
@@ -1141,6 +1403,103 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_estimate_transaction_fee_v0_with_lookup_table() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+
+        let fee_payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let lookup_table_key = Pubkey::new_unique();
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta {
+                deactivation_slot: u64::MAX,
+                last_extended_slot: 0,
+                last_extended_slot_start_index: 0,
+                authority: Some(Pubkey::new_unique()),
+                _padding: 0,
+            },
+            addresses: vec![recipient].into(),
+        };
+
+        // Recipient is only reachable through the lookup table
+        let transfer_instruction = transfer(&fee_payer.pubkey(), &recipient, 100_000);
+        let v0_message = v0::Message::try_compile(
+            &fee_payer.pubkey(),
+            &[transfer_instruction.clone()],
+            &[AddressLookupTableAccount { key: lookup_table_key, addresses: vec![recipient] }],
+            Hash::default(),
+        )
+        .expect("Failed to compile V0 message");
+        assert!(!v0_message.account_keys.contains(&recipient));
+
+        let transaction =
+            VersionedTransaction::try_new(VersionedMessage::V0(v0_message), &[&fee_payer]).unwrap();
+
+        let encoded_lookup_table = base64::engine::general_purpose::STANDARD
+            .encode(lookup_table.serialize_for_tests().unwrap());
+        let rpc_client = RpcMockBuilder::new()
+            .with_fee_estimate(5000)
+            .with_custom_mock(
+                RpcRequest::GetAccountInfo,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "data": [encoded_lookup_table, "base64"],
+                        "executable": false,
+                        "lamports": 0,
+                        "owner": "AddressLookupTab1e1111111111111111111111111",
+                        "rentEpoch": 0
+                    }
+                }),
+            )
+            .with_custom_mock(
+                RpcRequest::SimulateTransaction,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": { "err": null, "logs": [], "accounts": null, "unitsConsumed": 1000, "innerInstructions": [] }
+                }),
+            )
+            .build();
+
+        let mut resolved_transaction =
+            VersionedTransactionResolved::from_transaction(&transaction, &rpc_client, true)
+                .await
+                .unwrap();
+        assert!(resolved_transaction.all_account_keys.contains(&recipient));
+
+        let v0_result = FeeConfigUtil::estimate_transaction_fee(
+            &rpc_client,
+            &mut resolved_transaction,
+            &fee_payer.pubkey(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        // The transfer to the lookup table account is counted as fee payer outflow
+        assert_eq!(v0_result.fee_payer_outflow, 100_000);
+        assert_eq!(v0_result.kora_signature_fee, 0);
+
+        let legacy_message = VersionedMessage::Legacy(Message::new(
+            &[transfer_instruction],
+            Some(&fee_payer.pubkey()),
+        ));
+        let mut legacy_transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(legacy_message).unwrap();
+        let legacy_result = FeeConfigUtil::estimate_transaction_fee(
+            &rpc_client,
+            &mut legacy_transaction,
+            &fee_payer.pubkey(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        // Fees are per signature, loading the recipient through a lookup table doesn't change them
+        assert_eq!(v0_result.total_fee_lamports, legacy_result.total_fee_lamports);
+        assert_eq!(v0_result.total_fee_lamports, 105_000);
+    }
+
     #[tokio::test]
     async fn test_analyze_payment_instructions_with_multiple_payments() {
         let _m = ConfigMockBuilder::new().build_and_setup();
@@ -1234,4 +1593,137 @@ mod tests {
 
         assert_eq!(result, 12500, "Should return mocked base fee for V0 message");
     }
+
+    #[tokio::test]
+    async fn test_calculate_fee_in_token_with_blocklist() {
+        use crate::{
+            config::SplTokenConfig,
+            oracle::utils::{USDC_DEVNET_MINT, WSOL_DEVNET_MINT},
+        };
+
+        let _m = ConfigMockBuilder::new()
+            .with_allowed_spl_paid_tokens(SplTokenConfig::Blocklist(vec![
+                USDC_DEVNET_MINT.to_string()
+            ]))
+            .build_and_setup();
+        let rpc_client = RpcMockBuilder::new().with_mint_account(9).build();
+
+        // Tokens missing from the blocklist are accepted without being listed anywhere
+        let fee = FeeConfigUtil::calculate_fee_in_token(
+            &rpc_client,
+            1_000_000_000,
+            Some(WSOL_DEVNET_MINT),
+        )
+        .await
+        .unwrap();
+        assert_eq!(fee, Some(1_000_000_000));
+
+        let result =
+            FeeConfigUtil::calculate_fee_in_token(&rpc_client, 5000, Some(USDC_DEVNET_MINT)).await;
+        assert!(
+            matches!(result, Err(KoraError::InvalidRequest(msg)) if msg.contains("not supported"))
+        );
+    }
+
+    fn create_priced_transfer(compute_unit_price: Option<u64>) -> VersionedTransactionResolved {
+        let fee_payer = Pubkey::new_unique();
+        let mut builder = TransactionMockBuilder::new().with_fee_payer(fee_payer);
+        if let Some(price) = compute_unit_price {
+            builder = builder.with_compute_budget(200_000, price);
+        }
+        builder.with_sol_transfer(&fee_payer, &Pubkey::new_unique(), 50_000).build_resolved()
+    }
+
+    #[test]
+    fn test_calculate_fee_rounds_priority_fee_up() {
+        assert_eq!(TransactionFeeUtil::calculate_fee(1, 0, 1_000).unwrap(), 5000);
+        assert_eq!(TransactionFeeUtil::calculate_fee(2, 1, 1).unwrap(), 10_001);
+        assert_eq!(TransactionFeeUtil::calculate_fee(1, 200_000, 1_500).unwrap(), 5300);
+        assert!(TransactionFeeUtil::calculate_fee(u64::MAX, 0, 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_base_fee_static_calculation() {
+        // No RPC call is mocked, the estimate comes from the config alone
+        let rpc_client = RpcMockBuilder::new().build();
+        let transaction = create_priced_transfer(Some(1_000_000));
+
+        let fee = TransactionFeeUtil::estimate_base_fee(
+            &rpc_client,
+            &transaction,
+            &FeeEstimationStrategy::StaticCalculation {
+                compute_units: 200_000,
+                priority_fee: 1_500,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fee, LAMPORTS_PER_SIGNATURE + 300);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_base_fee_simulation() {
+        let strategy = FeeEstimationStrategy::Simulation { commitment: "confirmed".to_string() };
+
+        let rpc_client = RpcMockBuilder::new().with_simulation(150_000).build();
+        let transaction = create_priced_transfer(Some(10_000));
+        let fee = TransactionFeeUtil::estimate_base_fee(&rpc_client, &transaction, &strategy)
+            .await
+            .unwrap();
+        assert_eq!(fee, LAMPORTS_PER_SIGNATURE + 1_500);
+
+        // Without SetComputeUnitPrice there is no priority fee
+        let rpc_client = RpcMockBuilder::new().with_simulation(150_000).build();
+        let transaction = create_priced_transfer(None);
+        let fee = TransactionFeeUtil::estimate_base_fee(&rpc_client, &transaction, &strategy)
+            .await
+            .unwrap();
+        assert_eq!(fee, LAMPORTS_PER_SIGNATURE);
+
+        let invalid = FeeEstimationStrategy::Simulation { commitment: "invalid".to_string() };
+        let result =
+            TransactionFeeUtil::estimate_base_fee(&rpc_client, &transaction, &invalid).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fee_estimation_strategies_accuracy() {
+        let compute_unit_price = 25_000;
+        let static_strategy = FeeEstimationStrategy::StaticCalculation {
+            compute_units: 200_000,
+            priority_fee: compute_unit_price,
+        };
+        let simulation_strategy =
+            FeeEstimationStrategy::Simulation { commitment: "processed".to_string() };
+
+        for units_consumed in [3_000u64, 150_000, 200_000, 600_000] {
+            let transaction = create_priced_transfer(Some(compute_unit_price));
+            let actual_fee =
+                TransactionFeeUtil::calculate_fee(1, units_consumed, compute_unit_price).unwrap();
+
+            let rpc_client = RpcMockBuilder::new().with_simulation(units_consumed).build();
+            let simulated = TransactionFeeUtil::estimate_base_fee(
+                &rpc_client,
+                &transaction,
+                &simulation_strategy,
+            )
+            .await
+            .unwrap();
+            let static_fee =
+                TransactionFeeUtil::estimate_base_fee(&rpc_client, &transaction, &static_strategy)
+                    .await
+                    .unwrap();
+
+            // Simulation tracks the units actually consumed, the static estimate is off by the
+            // priority fee of the difference between the configured and consumed units
+            assert_eq!(simulated, actual_fee, "simulation at {units_consumed} units");
+            assert!(simulated.abs_diff(actual_fee) <= static_fee.abs_diff(actual_fee));
+            let unit_error = 200_000u64.abs_diff(units_consumed);
+            let expected_error =
+                TransactionFeeUtil::calculate_fee(0, unit_error, compute_unit_price).unwrap();
+            // Both sides round up on their own, so the error can be off by one lamport
+            assert!(static_fee.abs_diff(actual_fee).abs_diff(expected_error) <= 1);
+        }
+    }
 }
@@ -1,2 +1,3 @@
 pub mod fee;
 pub mod price;
+pub mod priced;
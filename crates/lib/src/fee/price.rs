@@ -3,7 +3,7 @@ use rust_decimal::{
     prelude::{FromPrimitive, ToPrimitive},
     Decimal,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
@@ -12,17 +12,58 @@ use utoipa::ToSchema;
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum PriceModel {
-    Margin { margin: f64 },
-    Fixed { amount: u64, token: String, strict: bool },
+    Margin {
+        /// Fraction added on top of the network fee, kept as a `Decimal` so `0.1` is exactly 10%
+        #[serde(serialize_with = "serialize_margin", deserialize_with = "deserialize_margin")]
+        #[schema(value_type = f64)]
+        margin: Decimal,
+    },
+    Fixed {
+        amount: u64,
+        token: String,
+        strict: bool,
+    },
     Free,
 }
 
 impl Default for PriceModel {
     fn default() -> Self {
-        Self::Margin { margin: 0.0 }
+        Self::Margin { margin: Decimal::ZERO }
     }
 }
 
+fn serialize_margin<S: Serializer>(margin: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    let margin = margin.to_f64().ok_or_else(|| {
+        serde::ser::Error::custom(format!("Margin {margin} is not a valid float"))
+    })?;
+    serializer.serialize_f64(margin)
+}
+
+/// Margins are written as TOML floats, parsing their shortest decimal form keeps `0.1` from
+/// becoming `0.1000000000000000055511151231`
+fn deserialize_margin<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MarginValue {
+        Integer(i64),
+        Float(f64),
+        String(String),
+    }
+
+    let margin = match MarginValue::deserialize(deserializer)? {
+        MarginValue::Integer(margin) => return Ok(Decimal::from(margin)),
+        MarginValue::Float(margin) if !margin.is_finite() => {
+            return Err(serde::de::Error::custom(format!("Invalid margin: {margin}")));
+        }
+        MarginValue::Float(margin) => margin.to_string(),
+        MarginValue::String(margin) => margin,
+    };
+
+    Decimal::from_str(&margin)
+        .or_else(|_| Decimal::from_scientific(&margin))
+        .map_err(|e| serde::de::Error::custom(format!("Invalid margin {margin}: {e}")))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
 pub struct PriceConfig {
     #[serde(flatten)]
@@ -57,19 +98,14 @@ impl PriceConfig {
         min_transaction_fee: u64,
     ) -> Result<u64, KoraError> {
         if let PriceModel::Margin { margin } = &self.model {
-            let margin_decimal = Decimal::from_f64(*margin)
-                .ok_or_else(|| KoraError::ValidationError("Invalid margin".to_string()))?;
-
-            let multiplier = Decimal::from_u64(1u64)
-                .and_then(|result| result.checked_add(margin_decimal))
-                .ok_or_else(|| {
-                    log::error!(
-                        "Multiplier calculation overflow: min_transaction_fee={}, margin={}",
-                        min_transaction_fee,
-                        margin,
-                    );
-                    KoraError::ValidationError("Multiplier calculation overflow".to_string())
-                })?;
+            let multiplier = Decimal::ONE.checked_add(*margin).ok_or_else(|| {
+                log::error!(
+                    "Multiplier calculation overflow: min_transaction_fee={}, margin={}",
+                    min_transaction_fee,
+                    margin,
+                );
+                KoraError::ValidationError("Multiplier calculation overflow".to_string())
+            })?;
 
             let result = Decimal::from_u64(min_transaction_fee)
                 .and_then(|result| result.checked_mul(multiplier))
@@ -101,12 +137,16 @@ impl PriceConfig {
 mod tests {
 
     use super::*;
-    use crate::tests::{common::create_mock_rpc_client_with_mint, config_mock::ConfigMockBuilder};
+    use crate::{
+        oracle::MockOracleConfig,
+        tests::{common::create_mock_rpc_client_with_mint, config_mock::ConfigMockBuilder},
+    };
+    use rust_decimal_macros::dec;
 
     #[tokio::test]
     async fn test_margin_model_get_required_lamports() {
         // Test margin of 0.1 (10%)
-        let price_config = PriceConfig { model: PriceModel::Margin { margin: 0.1 } };
+        let price_config = PriceConfig { model: PriceModel::Margin { margin: dec!(0.1) } };
 
         let min_transaction_fee = 5000u64; // 5000 lamports base fee
         let expected_lamports = (5000.0 * 1.1) as u64; // 5500 lamports
@@ -120,7 +160,7 @@ mod tests {
     #[tokio::test]
     async fn test_margin_model_get_required_lamports_zero_margin() {
         // Test margin of 0.0 (no margin)
-        let price_config = PriceConfig { model: PriceModel::Margin { margin: 0.0 } };
+        let price_config = PriceConfig { model: PriceModel::Margin { margin: Decimal::ZERO } };
 
         let min_transaction_fee = 5000u64;
 
@@ -210,14 +250,99 @@ mod tests {
         assert_eq!(result, 100);
     }
 
+    #[tokio::test]
+    async fn test_fixed_model_get_required_lamports_follows_oracle_price() {
+        let token = Pubkey::new_unique().to_string();
+        let price_config =
+            PriceConfig { model: PriceModel::Fixed { amount: 1_000_000, token, strict: false } };
+
+        let mut required_lamports = vec![];
+        for mock_oracle in [
+            MockOracleConfig { base_price_usd: 0.001, ..Default::default() },
+            MockOracleConfig { base_price_usd: 0.002, ..Default::default() },
+            MockOracleConfig { base_price_usd: 0.001, volatility_percent: 50.0, seed: 7 },
+        ] {
+            let _m = ConfigMockBuilder::new().with_mock_oracle(mock_oracle).build_and_setup();
+            let rpc_client = create_mock_rpc_client_with_mint(6);
+
+            required_lamports.push(
+                price_config
+                    .get_required_lamports_with_fixed(&rpc_client, PriceSource::Mock)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        // 1 token * 0.001 SOL/token = 1,000,000 lamports, doubling the price doubles the fee
+        assert_eq!(required_lamports[0], 1_000_000);
+        assert_eq!(required_lamports[1], 2_000_000);
+        // A price swing within +/- 50% moves the fee with it
+        assert_ne!(required_lamports[2], 1_000_000);
+        assert!((500_000..=1_500_000).contains(&required_lamports[2]));
+    }
+
     #[tokio::test]
     async fn test_default_price_config() {
         // Test that default creates Margin with 0.0 margin
         let default_config = PriceConfig::default();
 
         match default_config.model {
-            PriceModel::Margin { margin } => assert_eq!(margin, 0.0),
+            PriceModel::Margin { margin } => assert_eq!(margin, Decimal::ZERO),
             _ => panic!("Default should be Margin with 0.0 margin"),
         }
     }
+
+    fn margin_from_toml(margin: &str) -> Decimal {
+        let config: PriceConfig =
+            toml::from_str(&format!("type = \"margin\"\nmargin = {margin}\n")).unwrap();
+        match config.model {
+            PriceModel::Margin { margin } => margin,
+            _ => panic!("Expected Margin price model"),
+        }
+    }
+
+    #[test]
+    fn test_margin_deserializes_exactly_from_toml_float() {
+        assert_eq!(margin_from_toml("0.1"), dec!(0.1));
+        assert_eq!(margin_from_toml("0.07"), dec!(0.07));
+        assert_eq!(margin_from_toml("1"), Decimal::ONE);
+        assert_eq!(margin_from_toml("1e-3"), dec!(0.001));
+        assert_eq!(margin_from_toml("\"0.15\""), dec!(0.15));
+
+        // The float value itself is slightly above 0.1
+        assert_ne!(Decimal::from_f64_retain(0.1).unwrap(), dec!(0.1));
+    }
+
+    #[test]
+    fn test_margin_serializes_as_float() {
+        let config = PriceConfig { model: PriceModel::Margin { margin: dec!(0.1) } };
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value, serde_json::json!({ "type": "margin", "margin": 0.1 }));
+
+        let config: PriceConfig = serde_json::from_value(value).unwrap();
+        assert!(matches!(config.model, PriceModel::Margin { margin } if margin == dec!(0.1)));
+    }
+
+    #[tokio::test]
+    async fn test_margin_model_precision_edge_cases() {
+        // Float math overshoots these and rounds up a lamport, 100 * 1.1 = 110.00000000000001
+        assert_eq!((100.0 * (1.0 + 0.1_f64)).ceil(), 111.0);
+        assert_eq!((5_000.0 * (1.0 + 0.12_f64)).ceil(), 5_601.0);
+
+        for (fee, margin, expected) in [
+            (3, "0.1", 4),
+            (100, "0.1", 110),
+            (100, "0.12", 112),
+            (5_000, "0.12", 5_600),
+            (1_000, "0.07", 1_070),
+        ] {
+            let price_config =
+                PriceConfig { model: PriceModel::Margin { margin: margin_from_toml(margin) } };
+            assert_eq!(
+                price_config.get_required_lamports_with_margin(fee).await.unwrap(),
+                expected,
+                "fee {fee} with margin {margin}"
+            );
+        }
+    }
 }
@@ -0,0 +1,186 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{
+    constant::{DEFAULT_COMPUTE_UNITS_PER_INSTRUCTION, MAX_COMPUTE_UNITS_PER_TRANSACTION},
+    error::KoraError,
+    fee::fee::{FeeEstimationStrategy, TransactionFeeUtil},
+    transaction::{IxUtils, VersionedTransactionResolved},
+};
+
+/// Base fee of a transaction at the network's current lamports-per-signature rate, priced by the
+/// RPC node with `getFeeForMessage`. Used by fee estimates when `use_rpc_fee_estimate` is set
+pub struct PricedFeeCalculator {}
+
+impl PricedFeeCalculator {
+    /// Price the transaction as sent, before Kora's fee payment instruction is added. Falls back
+    /// to `calculate_static_fee` when the RPC call fails
+    pub async fn estimate_base_fee(
+        rpc_client: &RpcClient,
+        resolved_transaction: &VersionedTransactionResolved,
+        strategy: &FeeEstimationStrategy,
+    ) -> Result<u64, KoraError> {
+        match TransactionFeeUtil::get_estimate_fee_resolved(rpc_client, resolved_transaction).await
+        {
+            Ok(fee) => Ok(fee),
+            Err(e) => {
+                log::warn!("getFeeForMessage failed, using the static fee calculation: {e}");
+                Self::calculate_static_fee(resolved_transaction, strategy)
+            }
+        }
+    }
+
+    /// Signature fees plus the priority fee of the transaction's own compute budget, or of the
+    /// configured `StaticCalculation` strategy when there is one
+    pub fn calculate_static_fee(
+        resolved_transaction: &VersionedTransactionResolved,
+        strategy: &FeeEstimationStrategy,
+    ) -> Result<u64, KoraError> {
+        let num_signatures =
+            resolved_transaction.transaction.message.header().num_required_signatures as u64;
+
+        if let FeeEstimationStrategy::StaticCalculation { compute_units, priority_fee } = strategy {
+            return TransactionFeeUtil::calculate_fee(
+                num_signatures,
+                *compute_units,
+                *priority_fee,
+            );
+        }
+
+        let compute_budget = IxUtils::parse_compute_budget_instructions(resolved_transaction)?;
+
+        // Without a limit the runtime grants a fixed amount per non compute budget instruction
+        let compute_units = match compute_budget.limit {
+            Some(limit) => limit as u64,
+            None => {
+                resolved_transaction
+                    .outer_instructions()
+                    .iter()
+                    .filter(|ix| ix.program_id != solana_compute_budget_interface::ID)
+                    .count() as u64
+                    * DEFAULT_COMPUTE_UNITS_PER_INSTRUCTION
+            }
+        }
+        .min(MAX_COMPUTE_UNITS_PER_TRANSACTION);
+
+        TransactionFeeUtil::calculate_fee(
+            num_signatures,
+            compute_units,
+            compute_budget.price_micro_lamports.unwrap_or(0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constant::LAMPORTS_PER_SIGNATURE,
+        tests::{rpc_mock::RpcMockBuilder, transaction_mock::TransactionMockBuilder},
+    };
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn create_transfer(compute_budget: Option<(u32, u64)>) -> VersionedTransactionResolved {
+        let fee_payer = Pubkey::new_unique();
+        let mut builder = TransactionMockBuilder::new().with_fee_payer(fee_payer);
+        if let Some((limit, price)) = compute_budget {
+            builder = builder.with_compute_budget(limit, price);
+        }
+        builder.with_sol_transfer(&fee_payer, &Pubkey::new_unique(), 50_000).build_resolved()
+    }
+
+    fn failing_fee_rpc_client() -> std::sync::Arc<RpcClient> {
+        // A null value is returned for unknown blockhashes
+        RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetFeeForMessage,
+                json!({ "context": { "slot": 1 }, "value": null }),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_estimate_base_fee_uses_rpc_fee() {
+        // The network rate wins over the static 5000 lamports per signature
+        let rpc_client = RpcMockBuilder::new().with_fee_estimate(7_500).build();
+        let transaction = create_transfer(None);
+
+        let fee = PricedFeeCalculator::estimate_base_fee(
+            &rpc_client,
+            &transaction,
+            &FeeEstimationStrategy::GetFeeForMessage,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fee, 7_500);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_base_fee_falls_back_to_static_calculation() {
+        let rpc_client = failing_fee_rpc_client();
+
+        // 200,000 units at 1,500 micro-lamports is a 300 lamport priority fee
+        let priced = create_transfer(Some((200_000, 1_500)));
+        let fee = PricedFeeCalculator::estimate_base_fee(
+            &rpc_client,
+            &priced,
+            &FeeEstimationStrategy::GetFeeForMessage,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fee, LAMPORTS_PER_SIGNATURE + 300);
+
+        // A configured static strategy takes precedence over the transaction's compute budget
+        let fee = PricedFeeCalculator::estimate_base_fee(
+            &rpc_client,
+            &priced,
+            &FeeEstimationStrategy::StaticCalculation { compute_units: 400_000, priority_fee: 10 },
+        )
+        .await
+        .unwrap();
+        assert_eq!(fee, LAMPORTS_PER_SIGNATURE + 4);
+
+        let unpriced = create_transfer(None);
+        let fee = PricedFeeCalculator::estimate_base_fee(
+            &rpc_client,
+            &unpriced,
+            &FeeEstimationStrategy::GetFeeForMessage,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fee, LAMPORTS_PER_SIGNATURE);
+    }
+
+    #[test]
+    fn test_calculate_static_fee_default_compute_units() {
+        // Without a limit, the price applies to the 200,000 default units of the transfer
+        let fee_payer = Pubkey::new_unique();
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_instruction(ComputeBudgetInstruction::set_compute_unit_price(2_000))
+            .with_sol_transfer(&fee_payer, &Pubkey::new_unique(), 50_000)
+            .build_resolved();
+        assert_eq!(
+            PricedFeeCalculator::calculate_static_fee(
+                &transaction,
+                &FeeEstimationStrategy::GetFeeForMessage
+            )
+            .unwrap(),
+            LAMPORTS_PER_SIGNATURE + 400
+        );
+
+        // Limits above the transaction maximum are capped, at 1 lamport per unit here
+        let transaction = create_transfer(Some((2_000_000, 1_000_000)));
+        assert_eq!(
+            PricedFeeCalculator::calculate_static_fee(
+                &transaction,
+                &FeeEstimationStrategy::GetFeeForMessage
+            )
+            .unwrap(),
+            LAMPORTS_PER_SIGNATURE + MAX_COMPUTE_UNITS_PER_TRANSACTION
+        );
+    }
+}
@@ -55,22 +55,35 @@ impl BalanceTracker {
         if let Some(gauge_vec) = SIGNER_BALANCE_GAUGES.get() {
             let mut balance_results = Vec::new();
 
+            let pubkeys = signers_info
+                .iter()
+                .map(|signer_info| {
+                    Pubkey::from_str(&signer_info.public_key).map_err(|e| {
+                        KoraError::InternalServerError(format!(
+                            "Invalid signer pubkey {}: {e}",
+                            signer_info.public_key
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
             // Batch fetch all signer balances
-            for signer_info in &signers_info {
-                let pubkey = Pubkey::from_str(&signer_info.public_key).map_err(|e| {
-                    KoraError::InternalServerError(format!(
-                        "Invalid signer pubkey {}: {e}",
-                        signer_info.public_key
-                    ))
-                })?;
-
-                match CacheUtil::get_account(rpc_client, &pubkey, false).await {
-                    Ok(account) => {
+            let accounts = match CacheUtil::get_accounts(rpc_client, &pubkeys).await {
+                Ok(accounts) => accounts,
+                Err(e) => {
+                    log::warn!("Failed to get signer balances: {e}");
+                    vec![None; pubkeys.len()]
+                }
+            };
+
+            for (signer_info, account) in signers_info.iter().zip(accounts) {
+                match account {
+                    Some(account) => {
                         balance_results.push((signer_info, account.lamports));
                     }
-                    Err(e) => {
+                    None => {
                         log::warn!(
-                            "Failed to get balance for signer {} ({}): {e}",
+                            "Failed to get balance for signer {} ({})",
                             signer_info.name,
                             signer_info.public_key
                         );
@@ -169,8 +182,8 @@ mod tests {
         let external_signer2 = Signer::from_memory(&keypair2.to_base58_string()).unwrap();
 
         let pool = SignerPool::new(vec![
-            SignerWithMetadata::new("signer_1".to_string(), Arc::new(external_signer1), 1),
-            SignerWithMetadata::new("signer_2".to_string(), Arc::new(external_signer2), 2),
+            SignerWithMetadata::new("signer_1".to_string(), Arc::new(external_signer1.into()), 1),
+            SignerWithMetadata::new("signer_2".to_string(), Arc::new(external_signer2.into()), 2),
         ]);
 
         let _ = update_signer_pool(pool);
@@ -0,0 +1,192 @@
+use serde_json::{json, Value};
+
+const DASHBOARD_UID: &str = "kora";
+const DASHBOARD_TITLE: &str = "Kora";
+const SCHEMA_VERSION: u32 = 38;
+const PROMETHEUS_DATASOURCE_UID: &str = "prometheus";
+
+const PANEL_HEIGHT: u32 = 8;
+const PANEL_WIDTH: u32 = 12;
+/// Grafana lays panels out on a 24 column grid
+const GRID_WIDTH: u32 = 24;
+
+/// A timeseries panel plotting one Prometheus query
+struct Panel {
+    title: &'static str,
+    description: &'static str,
+    expr: &'static str,
+    legend: &'static str,
+    unit: &'static str,
+}
+
+const PANELS: &[Panel] = &[
+    Panel {
+        title: "RPC Request Rate",
+        description: "JSON-RPC requests per second by method",
+        expr: "sum by (method) (rate(kora_http_requests_total[5m]))",
+        legend: "{{method}}",
+        unit: "reqps",
+    },
+    Panel {
+        title: "p99 Latency",
+        description: "99th percentile request duration by method",
+        expr: "histogram_quantile(0.99, sum by (method, le) \
+               (rate(kora_http_request_duration_seconds_bucket[5m])))",
+        legend: "{{method}}",
+        unit: "s",
+    },
+    Panel {
+        title: "Fee Payer SOL Balance",
+        description: "SOL balance of each signer, requires metrics.fee_payer_balance",
+        expr: "signer_balance_lamports / 1e9",
+        legend: "{{signer_name}}",
+        unit: "none",
+    },
+    Panel {
+        title: "Signer Queue Depth",
+        description: "In-flight signing operations per signer",
+        expr: "kora_signer_queue_depth",
+        legend: "{{signer}}",
+        unit: "short",
+    },
+    Panel {
+        title: "Signing Rate",
+        description: "Successful and failed signing attempts per second",
+        expr: "sum by (result) (rate(kora_signatures_total[5m]))",
+        legend: "{{result}}",
+        unit: "ops",
+    },
+    Panel {
+        title: "Token Balance Cache Size",
+        description: "Token account balances cached for fee validation",
+        expr: "kora_token_balance_cache_size",
+        legend: "entries",
+        unit: "short",
+    },
+];
+
+/// Grafana dashboard for the metrics Kora exports, backed by a Prometheus datasource with uid
+/// `prometheus`. `rpc export-dashboard` writes it to a file for import
+pub fn build_dashboard() -> Value {
+    let panels_per_row = GRID_WIDTH / PANEL_WIDTH;
+    let panels: Vec<Value> = PANELS
+        .iter()
+        .enumerate()
+        .map(|(index, panel)| {
+            let index = index as u32;
+            build_timeseries_panel(
+                index + 1,
+                panel,
+                (index % panels_per_row) * PANEL_WIDTH,
+                (index / panels_per_row) * PANEL_HEIGHT,
+            )
+        })
+        .collect();
+
+    json!({
+        "annotations": {
+            "list": [{
+                "builtIn": 1,
+                "datasource": { "type": "grafana", "uid": "-- Grafana --" },
+                "enable": true,
+                "hide": true,
+                "iconColor": "rgba(0, 211, 255, 1)",
+                "name": "Annotations & Alerts",
+                "type": "dashboard"
+            }]
+        },
+        "editable": true,
+        "graphTooltip": 1,
+        "id": null,
+        "links": [],
+        "panels": panels,
+        "refresh": "30s",
+        "schemaVersion": SCHEMA_VERSION,
+        "tags": ["kora"],
+        "templating": { "list": [] },
+        "time": { "from": "now-1h", "to": "now" },
+        "timezone": "",
+        "title": DASHBOARD_TITLE,
+        "uid": DASHBOARD_UID,
+        "version": 1
+    })
+}
+
+fn build_timeseries_panel(id: u32, panel: &Panel, x: u32, y: u32) -> Value {
+    let datasource = json!({ "type": "prometheus", "uid": PROMETHEUS_DATASOURCE_UID });
+
+    json!({
+        "datasource": datasource,
+        "description": panel.description,
+        "fieldConfig": {
+            "defaults": { "unit": panel.unit },
+            "overrides": []
+        },
+        "gridPos": { "h": PANEL_HEIGHT, "w": PANEL_WIDTH, "x": x, "y": y },
+        "id": id,
+        "options": {
+            "legend": { "displayMode": "list", "placement": "bottom", "showLegend": true },
+            "tooltip": { "mode": "multi", "sort": "desc" }
+        },
+        "targets": [{
+            "datasource": datasource,
+            "expr": panel.expr,
+            "legendFormat": panel.legend,
+            "refId": "A"
+        }],
+        "title": panel.title,
+        "type": "timeseries"
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const EXPORTED_DASHBOARD: &str = include_str!("dashboards/kora.json");
+
+    #[test]
+    fn test_build_dashboard_is_valid_grafana_dashboard() {
+        let dashboard = build_dashboard();
+
+        assert_eq!(dashboard["uid"], DASHBOARD_UID);
+        assert!(dashboard["title"].is_string());
+        assert!(dashboard["schemaVersion"].is_u64());
+        assert!(dashboard["id"].is_null());
+
+        let panels = dashboard["panels"].as_array().unwrap();
+        assert_eq!(panels.len(), PANELS.len());
+
+        let mut ids = HashSet::new();
+        for panel in panels {
+            assert!(ids.insert(panel["id"].as_u64().unwrap()), "Duplicate panel id");
+            assert_eq!(panel["type"], "timeseries");
+            assert!(panel["title"].is_string());
+
+            let grid_pos = &panel["gridPos"];
+            for key in ["h", "w", "x", "y"] {
+                assert!(grid_pos[key].is_u64(), "gridPos.{key} missing");
+            }
+            assert!(
+                grid_pos["x"].as_u64().unwrap() + grid_pos["w"].as_u64().unwrap()
+                    <= GRID_WIDTH as u64
+            );
+
+            let targets = panel["targets"].as_array().unwrap();
+            assert!(!targets.is_empty());
+            for target in targets {
+                assert!(target["expr"].as_str().is_some_and(|expr| !expr.is_empty()));
+                assert!(target["refId"].is_string());
+                assert_eq!(target["datasource"]["uid"], PROMETHEUS_DATASOURCE_UID);
+            }
+        }
+    }
+
+    #[test]
+    fn test_exported_dashboard_is_up_to_date() {
+        // Regenerate with `kora rpc export-dashboard --output src/metrics/dashboards/kora.json`
+        let exported: Value = serde_json::from_str(EXPORTED_DASHBOARD).unwrap();
+        assert_eq!(exported, build_dashboard());
+    }
+}
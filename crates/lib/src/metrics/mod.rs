@@ -1,15 +1,27 @@
 pub mod balance;
+pub mod dashboards;
 pub mod handler;
 pub mod middleware;
+pub mod signer;
 
 pub use balance::BalanceTracker;
+pub use dashboards::build_dashboard;
 pub use handler::{MetricsHandlerLayer, MetricsHandlerService};
 pub use middleware::{HttpMetricsLayer, HttpMetricsService};
 pub use prometheus;
+pub use signer::SignerMetrics;
+use reqwest::Client;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use tokio::task::JoinHandle;
+use tokio::{
+    task::JoinHandle,
+    time::{interval, Duration},
+};
 
-use crate::{config::MetricsConfig, state::get_config};
+use crate::{
+    config::{MetricsConfig, PushGatewayConfig},
+    error::KoraError,
+    state::get_config,
+};
 use jsonrpsee::{
     server::{ServerBuilder, ServerHandle},
     RpcModule,
@@ -53,6 +65,15 @@ pub async fn run_metrics_server_if_required(
         BalanceTracker::start_background_tracking(rpc_client).await
     };
 
+    // Metrics are pushed to the gateway, so only collect them and don't expose a scrape endpoint
+    if metrics_config.push_gateway.is_some() {
+        let metrics_layers = MetricsLayers {
+            http_metrics_layer: Some(HttpMetricsLayer::new()),
+            metrics_handler_layer: None,
+        };
+        return Ok((None, Some(metrics_layers), balance_tracker_handle));
+    }
+
     // If running on the same port as the RPC server, we don't need to run a separate metrics server
     if metrics_config.port == rpc_port {
         log::info!("Metrics endpoint enabled at {} on RPC server", metrics_config.endpoint);
@@ -96,3 +117,130 @@ pub fn gather() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     encoder.encode(&metric_families, &mut buffer)?;
     String::from_utf8(buffer).map_err(Into::into)
 }
+
+/// Push the gathered metrics to the configured Prometheus push gateway
+pub async fn push_metrics(
+    client: &Client,
+    push_gateway: &PushGatewayConfig,
+) -> Result<(), KoraError> {
+    let metrics = gather()
+        .map_err(|e| KoraError::InternalServerError(format!("Failed to gather metrics: {e}")))?;
+
+    let url =
+        format!("{}/metrics/job/{}", push_gateway.url.trim_end_matches('/'), push_gateway.job_name);
+    let mut request =
+        client.post(&url).header("content-type", "text/plain; version=0.0.4").body(metrics);
+
+    if let Some(auth) = &push_gateway.auth {
+        request = request.basic_auth(&auth.username, Some(&auth.password));
+    }
+
+    let response = request.send().await.map_err(|e| {
+        KoraError::InternalServerError(format!("Failed to push metrics to {url}: {e}"))
+    })?;
+
+    if !response.status().is_success() {
+        return Err(KoraError::InternalServerError(format!(
+            "Push gateway {url} responded with status {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Start a background task that pushes metrics to the push gateway at regular intervals
+/// Returns a JoinHandle to allow for proper task shutdown
+pub fn start_push_gateway_if_required() -> Result<Option<JoinHandle<()>>, anyhow::Error> {
+    let metrics_config = get_config()?.metrics.clone();
+
+    let push_gateway = match metrics_config.push_gateway {
+        Some(push_gateway) if metrics_config.enabled => push_gateway,
+        _ => return Ok(None),
+    };
+
+    log::info!(
+        "Pushing metrics to {} as job {} every {}s",
+        push_gateway.url,
+        push_gateway.job_name,
+        push_gateway.push_interval_seconds
+    );
+
+    let handle = tokio::spawn(async move {
+        let client = Client::new();
+        let mut interval = interval(Duration::from_secs(push_gateway.push_interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            // Don't let a failed push crash the loop, the gateway may just be temporarily unavailable
+            if let Err(e) = push_metrics(&client, &push_gateway).await {
+                log::warn!("Failed to push metrics: {e}");
+            }
+        }
+    });
+
+    Ok(Some(handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::BasicAuth, metrics::middleware::HttpMetrics};
+    use mockito::{Matcher, Server};
+
+    fn create_push_gateway_config(url: String, auth: Option<BasicAuth>) -> PushGatewayConfig {
+        PushGatewayConfig { url, job_name: "kora".to_string(), push_interval_seconds: 15, auth }
+    }
+
+    #[tokio::test]
+    async fn test_push_metrics_posts_kora_metrics() {
+        HttpMetrics::get().requests_total.with_label_values(&["signTransaction", "200"]).inc();
+
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/metrics/job/kora")
+            .match_header("content-type", "text/plain; version=0.0.4")
+            .match_body(Matcher::Regex("kora_http_requests_total".to_string()))
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let result =
+            push_metrics(&Client::new(), &create_push_gateway_config(server.url(), None)).await;
+
+        assert!(result.is_ok(), "Push should succeed: {result:?}");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_push_metrics_with_basic_auth() {
+        let mut server = Server::new_async().await;
+        // base64("kora:secret")
+        let mock = server
+            .mock("POST", "/metrics/job/kora")
+            .match_header("authorization", "Basic a29yYTpzZWNyZXQ=")
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let auth = BasicAuth { username: "kora".to_string(), password: "secret".to_string() };
+        let result =
+            push_metrics(&Client::new(), &create_push_gateway_config(server.url(), Some(auth)))
+                .await;
+
+        assert!(result.is_ok(), "Push should succeed: {result:?}");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_push_metrics_gateway_error() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("POST", "/metrics/job/kora").with_status(500).create_async().await;
+
+        let result =
+            push_metrics(&Client::new(), &create_push_gateway_config(server.url(), None)).await;
+
+        assert!(matches!(result, Err(KoraError::InternalServerError(_))));
+    }
+}
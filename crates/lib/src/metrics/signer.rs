@@ -0,0 +1,112 @@
+use prometheus::{Histogram, HistogramOpts, IntCounterVec, IntGauge, IntGaugeVec, Opts};
+use std::sync::OnceLock;
+
+static SIGNER_METRICS: OnceLock<SignerMetrics> = OnceLock::new();
+
+pub struct SignerMetrics {
+    /// Signing operations currently in flight per signer alias
+    pub queue_depth: IntGaugeVec,
+    /// Failed signing attempts currently held in the dead-letter queue
+    pub dead_letter_queue_size: IntGauge,
+    /// Signing attempts per signer alias and result (`success` or `error`)
+    pub signatures_total: IntCounterVec,
+    /// Signing requests waiting for a signing slot, see `BackpressureController`
+    pub backpressure_queue_depth: IntGauge,
+    /// Time signing requests spent waiting for a signing slot
+    pub queue_wait_duration_seconds: Histogram,
+}
+
+impl SignerMetrics {
+    fn new() -> Self {
+        let queue_depth = IntGaugeVec::new(
+            Opts::new("signer_queue_depth", "Number of in-flight signing operations per signer")
+                .namespace("kora"),
+            &["signer"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create signer_queue_depth metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(queue_depth.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register signer_queue_depth metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let dead_letter_queue_size = IntGauge::with_opts(
+            Opts::new(
+                "signer_dead_letter_queue_size",
+                "Number of failed signing attempts held in the dead-letter queue",
+            )
+            .namespace("kora"),
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create signer_dead_letter_queue_size metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(dead_letter_queue_size.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register signer_dead_letter_queue_size metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let signatures_total = IntCounterVec::new(
+            Opts::new("signatures_total", "Total number of signing attempts per signer and result")
+                .namespace("kora"),
+            &["signer", "result"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create signatures_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(signatures_total.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register signatures_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let backpressure_queue_depth = IntGauge::with_opts(
+            Opts::new("queue_depth", "Number of signing requests waiting for a signing slot")
+                .namespace("kora"),
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create queue_depth metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(backpressure_queue_depth.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register queue_depth metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let queue_wait_duration_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "queue_wait_duration_seconds",
+                "Time signing requests waited for a signing slot in seconds",
+            )
+            .namespace("kora")
+            .buckets(vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0]),
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create queue_wait_duration_seconds metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(queue_wait_duration_seconds.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register queue_wait_duration_seconds metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        Self {
+            queue_depth,
+            dead_letter_queue_size,
+            signatures_total,
+            backpressure_queue_depth,
+            queue_wait_duration_seconds,
+        }
+    }
+
+    pub fn get() -> &'static SignerMetrics {
+        SIGNER_METRICS.get_or_init(SignerMetrics::new)
+    }
+}
@@ -20,6 +20,7 @@ pub mod token;
 pub mod transaction;
 pub mod usage_limit;
 pub mod validator;
+pub mod webhook;
 pub use cache::CacheUtil;
 pub use config::Config;
 pub use error::KoraError;
@@ -0,0 +1,154 @@
+use crate::{
+    error::KoraError,
+    oracle::{
+        utils::{
+            DEFAULT_MOCKED_PRICE, DEFAULT_MOCKED_USDC_PRICE, DEFAULT_MOCKED_WSOL_PRICE,
+            USDC_DEVNET_MINT, WSOL_DEVNET_MINT,
+        },
+        PriceOracle, PriceSource, TokenPrice,
+    },
+};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reqwest::Client;
+use rust_decimal::{
+    prelude::{FromPrimitive, ToPrimitive},
+    Decimal,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+/// Settings of the `Mock` price source
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct MockOracleConfig {
+    /// Price of tokens other than devnet USDC and wSOL, before volatility is applied
+    #[serde(default = "default_base_price_usd")]
+    pub base_price_usd: f64,
+    /// Every quoted price is moved by a random amount within +/- this percentage
+    #[serde(default)]
+    pub volatility_percent: f64,
+    /// Seed of the RNG, the same seed always produces the same sequence of prices
+    #[serde(default)]
+    pub seed: u64,
+}
+
+fn default_base_price_usd() -> f64 {
+    DEFAULT_MOCKED_PRICE.to_f64().unwrap_or_default()
+}
+
+impl Default for MockOracleConfig {
+    fn default() -> Self {
+        Self { base_price_usd: default_base_price_usd(), volatility_percent: 0.0, seed: 0 }
+    }
+}
+
+/// Price oracle for tests and local setups, quoting fixed prices with seeded volatility
+pub struct MockOracle {
+    base_price_usd: f64,
+    volatility_percent: f64,
+    /// Seeded from `MockOracleConfig::seed`
+    rng: Mutex<StdRng>,
+}
+
+impl MockOracle {
+    pub fn new(config: &MockOracleConfig) -> Self {
+        Self {
+            base_price_usd: config.base_price_usd,
+            volatility_percent: config.volatility_percent,
+            rng: Mutex::new(StdRng::seed_from_u64(config.seed)),
+        }
+    }
+
+    fn base_price(&self, mint_address: &str) -> Result<Decimal, KoraError> {
+        match mint_address {
+            USDC_DEVNET_MINT => Ok(DEFAULT_MOCKED_USDC_PRICE),
+            WSOL_DEVNET_MINT => Ok(DEFAULT_MOCKED_WSOL_PRICE),
+            _ => Decimal::from_f64(self.base_price_usd).ok_or_else(|| {
+                KoraError::InternalServerError(format!(
+                    "Invalid mock base price: {}",
+                    self.base_price_usd
+                ))
+            }),
+        }
+    }
+
+    /// Base price of `mint_address` moved by the next random swing within the volatility band
+    fn next_price(&self, mint_address: &str) -> Result<TokenPrice, KoraError> {
+        let mut price = self.base_price(mint_address)?;
+
+        if self.volatility_percent > 0.0 {
+            let swing =
+                self.rng.lock().random_range(-self.volatility_percent..=self.volatility_percent);
+            let factor = Decimal::from_f64(1.0 + swing / 100.0).ok_or_else(|| {
+                KoraError::InternalServerError(format!("Invalid mock price swing: {swing}"))
+            })?;
+            price *= factor;
+        }
+
+        Ok(TokenPrice { price, confidence: 1.0, source: PriceSource::Mock })
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceOracle for MockOracle {
+    async fn get_price(
+        &self,
+        _client: &Client,
+        mint_address: &str,
+    ) -> Result<TokenPrice, KoraError> {
+        self.next_price(mint_address)
+    }
+
+    async fn get_prices(
+        &self,
+        _client: &Client,
+        mint_addresses: &[String],
+    ) -> Result<HashMap<String, TokenPrice>, KoraError> {
+        mint_addresses
+            .iter()
+            .map(|mint_address| Ok((mint_address.clone(), self.next_price(mint_address)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_mock_oracle_default_prices() {
+        let oracle = MockOracle::new(&MockOracleConfig::default());
+        let client = Client::new();
+
+        let usdc_price = oracle.get_price(&client, USDC_DEVNET_MINT).await.unwrap();
+        assert_eq!(usdc_price.price, DEFAULT_MOCKED_USDC_PRICE);
+        assert_eq!(usdc_price.confidence, 1.0);
+        assert_eq!(usdc_price.source, PriceSource::Mock);
+
+        let sol_price = oracle.get_price(&client, WSOL_DEVNET_MINT).await.unwrap();
+        assert_eq!(sol_price.price, DEFAULT_MOCKED_WSOL_PRICE);
+
+        // Unknown tokens get the base price
+        let unknown_price = oracle.get_price(&client, "unknown_token").await.unwrap();
+        assert_eq!(unknown_price.price, DEFAULT_MOCKED_PRICE);
+    }
+
+    #[tokio::test]
+    async fn test_mock_oracle_volatility_is_seeded_and_bounded() {
+        let config = MockOracleConfig { base_price_usd: 2.0, volatility_percent: 10.0, seed: 42 };
+        let client = Client::new();
+        let mints: Vec<String> = (0..20).map(|i| format!("mint_{i}")).collect();
+
+        let first = MockOracle::new(&config).get_prices(&client, &mints).await.unwrap();
+        let second = MockOracle::new(&config).get_prices(&client, &mints).await.unwrap();
+
+        for mint in &mints {
+            let price = first[mint].price;
+            assert_eq!(price, second[mint].price, "same seed should quote the same prices");
+            assert!(price >= dec!(1.8) && price <= dec!(2.2), "{price} outside the band");
+        }
+        assert!(mints.iter().any(|mint| first[mint].price != dec!(2.0)));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod jupiter;
+pub mod mock;
 pub mod oracle;
 pub mod utils;
 
+pub use mock::{MockOracle, MockOracleConfig};
 pub use oracle::*;
@@ -1,6 +1,6 @@
 use crate::{
     error::KoraError,
-    oracle::{jupiter::JupiterPriceOracle, utils::OracleUtil},
+    oracle::{jupiter::JupiterPriceOracle, mock::MockOracle},
 };
 use mockall::automock;
 use reqwest::Client;
@@ -9,6 +9,12 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::time::sleep;
 
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "docs", derive(utoipa::ToSchema))]
 pub struct TokenPrice {
@@ -44,11 +50,15 @@ pub struct RetryingPriceOracle {
     oracle: Arc<dyn PriceOracle + Send + Sync>,
 }
 
-pub fn get_price_oracle(source: PriceSource) -> Arc<dyn PriceOracle + Send + Sync> {
-    match source {
+/// Oracle for `source`, the `Mock` oracle is set up from `validation.mock_oracle`
+pub fn get_price_oracle(
+    source: PriceSource,
+) -> Result<Arc<dyn PriceOracle + Send + Sync>, KoraError> {
+    let oracle: Arc<dyn PriceOracle + Send + Sync> = match source {
         PriceSource::Jupiter => Arc::new(JupiterPriceOracle::new()),
-        PriceSource::Mock => OracleUtil::get_mock_oracle_price(),
-    }
+        PriceSource::Mock => Arc::new(MockOracle::new(&get_config()?.validation.mock_oracle)),
+    };
+    Ok(oracle)
 }
 
 impl RetryingPriceOracle {
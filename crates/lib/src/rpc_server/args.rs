@@ -9,6 +9,11 @@ pub struct RpcArgs {
     #[arg(short = 'p', long, default_value = "8080")]
     pub port: u16,
 
+    /// WebSocket port for `kora_subscribeTransactionEvents`, only bound when
+    /// `subscribe_transaction_events` is enabled
+    #[arg(long, default_value = "8081")]
+    pub ws_port: u16,
+
     /// Output format for logs (standard or json)
     #[arg(long, default_value = "standard")]
     pub logging_format: LoggingFormat,
@@ -0,0 +1,187 @@
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::{
+    constant::DEFAULT_BACKPRESSURE_QUEUE_CAPACITY,
+    error::KoraError,
+    metrics::SignerMetrics,
+    signer::{pool::SigningPermit, SignerPool},
+};
+
+static BACKPRESSURE_CONTROLLER: Lazy<BackpressureController> =
+    Lazy::new(|| BackpressureController::new(DEFAULT_BACKPRESSURE_QUEUE_CAPACITY));
+
+/// Queues signing requests while their signer is at its `concurrent_signing_limit` instead of
+/// rejecting them. A queued request waits up to `backpressure_timeout_ms` for a signing slot,
+/// requests arriving while the queue is full are still rejected with `SignerBusy`
+pub struct BackpressureController {
+    queue_slots: Semaphore,
+}
+
+/// A request waiting in the queue, leaves the queue depth when dropped
+struct QueuedRequest<'a> {
+    _slot: SemaphorePermit<'a>,
+}
+
+impl<'a> QueuedRequest<'a> {
+    fn new(slot: SemaphorePermit<'a>) -> Self {
+        SignerMetrics::get().backpressure_queue_depth.inc();
+        Self { _slot: slot }
+    }
+}
+
+impl Drop for QueuedRequest<'_> {
+    fn drop(&mut self) {
+        SignerMetrics::get().backpressure_queue_depth.dec();
+    }
+}
+
+impl BackpressureController {
+    pub fn new(queue_capacity: usize) -> Self {
+        Self { queue_slots: Semaphore::new(queue_capacity) }
+    }
+
+    /// Controller shared by every request of the server
+    pub fn global() -> &'static Self {
+        &BACKPRESSURE_CONTROLLER
+    }
+
+    /// Take a signing slot of the signer with `pubkey`, waiting up to `timeout` in the queue
+    /// when all of them are in use. A zero `timeout` fails with `SignerBusy` right away
+    pub async fn acquire_signing_permit(
+        &self,
+        pool: &SignerPool,
+        pubkey: &Pubkey,
+        timeout: Duration,
+    ) -> Result<Option<SigningPermit>, KoraError> {
+        let busy_message = match pool.try_acquire_signing_permit(pubkey) {
+            Err(KoraError::SignerBusy(message)) if !timeout.is_zero() => message,
+            result => return result,
+        };
+
+        let Ok(slot) = self.queue_slots.try_acquire() else {
+            return Err(KoraError::SignerBusy(format!(
+                "{busy_message} and the signing queue is full"
+            )));
+        };
+        let _queued = QueuedRequest::new(slot);
+
+        let started = Instant::now();
+        let result = tokio::time::timeout(timeout, pool.acquire_signing_permit(pubkey)).await;
+        SignerMetrics::get().queue_wait_duration_seconds.observe(started.elapsed().as_secs_f64());
+
+        result.unwrap_or_else(|_| {
+            Err(KoraError::SignerBusy(format!(
+                "{busy_message}, no signing slot was freed within {}ms",
+                timeout.as_millis()
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::pool::SignerWithMetadata;
+    use solana_sdk::signature::{Keypair, Signer};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    fn limited_pool(limit: usize) -> (Arc<SignerPool>, Pubkey) {
+        let keypair = Keypair::new();
+        let signer = solana_keychain::Signer::from_memory(&keypair.to_base58_string()).unwrap();
+        let pool = SignerPool::new(vec![SignerWithMetadata::new(
+            "backpressure_signer".to_string(),
+            Arc::new(signer.into()),
+            1,
+        )
+        .with_signing_limit(limit)]);
+        (Arc::new(pool), keypair.pubkey())
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_queues_requests_under_load() {
+        let (pool, pubkey) = limited_pool(50);
+        let controller = Arc::new(BackpressureController::new(DEFAULT_BACKPRESSURE_QUEUE_CAPACITY));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..200)
+            .map(|_| {
+                let (pool, controller) = (Arc::clone(&pool), Arc::clone(&controller));
+                let (in_flight, max_in_flight) =
+                    (Arc::clone(&in_flight), Arc::clone(&max_in_flight));
+                tokio::spawn(async move {
+                    let permit = controller
+                        .acquire_signing_permit(&pool, &pubkey, Duration::from_secs(10))
+                        .await?;
+
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                    Ok::<_, KoraError>(permit.is_some())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.await.unwrap().unwrap(), "request was rejected or got no permit");
+        }
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 50);
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_times_out() {
+        let (pool, pubkey) = limited_pool(1);
+        let controller = BackpressureController::new(DEFAULT_BACKPRESSURE_QUEUE_CAPACITY);
+        let _held = pool.try_acquire_signing_permit(&pubkey).unwrap();
+
+        let result =
+            controller.acquire_signing_permit(&pool, &pubkey, Duration::from_millis(20)).await;
+        match result {
+            Err(KoraError::SignerBusy(message)) => assert!(message.contains("within 20ms")),
+            other => panic!("Expected SignerBusy, got {:?}", other.map(|p| p.is_some())),
+        }
+
+        // Without a timeout requests are rejected as before
+        let result = controller.acquire_signing_permit(&pool, &pubkey, Duration::ZERO).await;
+        assert!(matches!(result, Err(KoraError::SignerBusy(_))));
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_rejects_when_queue_full() {
+        let (pool, pubkey) = limited_pool(1);
+        let controller = Arc::new(BackpressureController::new(1));
+        let held = pool.try_acquire_signing_permit(&pubkey).unwrap();
+
+        let waiter = {
+            let (pool, controller) = (Arc::clone(&pool), Arc::clone(&controller));
+            tokio::spawn(async move {
+                controller
+                    .acquire_signing_permit(&pool, &pubkey, Duration::from_secs(10))
+                    .await
+                    .map(|permit| permit.is_some())
+            })
+        };
+        while controller.queue_slots.available_permits() > 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let result =
+            controller.acquire_signing_permit(&pool, &pubkey, Duration::from_secs(10)).await;
+        match result {
+            Err(KoraError::SignerBusy(message)) => assert!(message.contains("queue is full")),
+            other => panic!("Expected SignerBusy, got {:?}", other.map(|p| p.is_some())),
+        }
+
+        // The queued request gets the slot once it is released
+        drop(held);
+        assert!(waiter.await.unwrap().unwrap());
+    }
+}
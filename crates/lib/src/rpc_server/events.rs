@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use futures_util::Stream;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use tokio::sync::broadcast::{self, error::RecvError};
+use utoipa::ToSchema;
+
+use crate::constant::DEFAULT_TRANSACTION_EVENT_CHANNEL_CAPACITY;
+
+// Global broker used by the signing methods and the WebSocket subscription handler
+pub static TRANSACTION_EVENT_BROKER: Lazy<TransactionEventBroker> =
+    Lazy::new(|| TransactionEventBroker::new(DEFAULT_TRANSACTION_EVENT_CHANNEL_CAPACITY));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionEventType {
+    Signed,
+    Submitted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionEvent {
+    pub event_type: TransactionEventType,
+    /// User public key the event was published for
+    pub user: String,
+    /// Public key of the Kora signer that signed the transaction
+    pub signer_pubkey: String,
+    /// Base64-encoded signed transaction
+    pub transaction: String,
+    /// Transaction signature, only present once the transaction has been submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Pub/sub broker fanning out transaction events to subscribers, one broadcast channel per user
+pub struct TransactionEventBroker {
+    channels: RwLock<HashMap<Pubkey, broadcast::Sender<TransactionEvent>>>,
+    capacity: usize,
+}
+
+impl TransactionEventBroker {
+    pub fn new(capacity: usize) -> Self {
+        Self { channels: RwLock::new(HashMap::new()), capacity }
+    }
+
+    pub fn subscribe(&self, user: Pubkey) -> broadcast::Receiver<TransactionEvent> {
+        self.channels
+            .write()
+            .entry(user)
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe()
+    }
+
+    /// Publish an event to every subscriber of `user`, returning the number of receivers reached
+    pub fn publish(&self, user: &Pubkey, event: TransactionEvent) -> usize {
+        let result = match self.channels.read().get(user) {
+            Some(sender) => sender.send(event),
+            None => return 0,
+        };
+
+        match result {
+            Ok(receivers) => receivers,
+            Err(_) => {
+                // Every subscriber is gone, drop the channel so the map does not grow unbounded
+                let mut channels = self.channels.write();
+                if channels.get(user).is_some_and(|sender| sender.receiver_count() == 0) {
+                    channels.remove(user);
+                }
+                0
+            }
+        }
+    }
+
+    /// Publish an event to every user signer of the transaction (all required signers except Kora's)
+    pub fn publish_transaction_event(
+        &self,
+        transaction: &VersionedTransaction,
+        signer_pubkey: &Pubkey,
+        event_type: TransactionEventType,
+        encoded_transaction: &str,
+        signature: Option<String>,
+    ) {
+        let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+
+        for user in transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .take(num_required_signatures)
+            .filter(|key| *key != signer_pubkey)
+        {
+            self.publish(
+                user,
+                TransactionEvent {
+                    event_type,
+                    user: user.to_string(),
+                    signer_pubkey: signer_pubkey.to_string(),
+                    transaction: encoded_transaction.to_string(),
+                    signature: signature.clone(),
+                },
+            );
+        }
+    }
+
+    pub fn subscriber_count(&self, user: &Pubkey) -> usize {
+        self.channels.read().get(user).map_or(0, |sender| sender.receiver_count())
+    }
+}
+
+/// Turn a broadcast receiver into a stream, skipping events dropped because the subscriber lagged
+pub fn transaction_event_stream(
+    receiver: broadcast::Receiver<TransactionEvent>,
+) -> impl Stream<Item = TransactionEvent> {
+    futures_util::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Transaction event subscriber lagged, skipped {skipped} events");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use solana_message::{Message, VersionedMessage};
+    use solana_system_interface::instruction::transfer;
+
+    use crate::transaction::TransactionUtil;
+
+    fn create_event(user: &Pubkey, event_type: TransactionEventType) -> TransactionEvent {
+        TransactionEvent {
+            event_type,
+            user: user.to_string(),
+            signer_pubkey: Pubkey::new_unique().to_string(),
+            transaction: "encoded".to_string(),
+            signature: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_reaches_only_matching_subscribers() {
+        let broker = TransactionEventBroker::new(10);
+        let user = Pubkey::new_unique();
+        let other_user = Pubkey::new_unique();
+
+        let mut receiver = broker.subscribe(user);
+        let mut other_receiver = broker.subscribe(other_user);
+
+        let event = create_event(&user, TransactionEventType::Signed);
+        assert_eq!(broker.publish(&user, event.clone()), 1);
+
+        assert_eq!(receiver.recv().await.unwrap(), event);
+        assert!(other_receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_drops_channel() {
+        let broker = TransactionEventBroker::new(10);
+        let user = Pubkey::new_unique();
+
+        assert_eq!(broker.publish(&user, create_event(&user, TransactionEventType::Signed)), 0);
+
+        let receiver = broker.subscribe(user);
+        assert_eq!(broker.subscriber_count(&user), 1);
+        drop(receiver);
+
+        assert_eq!(broker.publish(&user, create_event(&user, TransactionEventType::Signed)), 0);
+        assert!(broker.channels.read().get(&user).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_publish_transaction_event_skips_kora_signer() {
+        let broker = TransactionEventBroker::new(10);
+        let fee_payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        let instruction = transfer(&user, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+
+        let mut user_receiver = broker.subscribe(user);
+        let mut fee_payer_receiver = broker.subscribe(fee_payer);
+
+        broker.publish_transaction_event(
+            &transaction,
+            &fee_payer,
+            TransactionEventType::Submitted,
+            "encoded",
+            Some("signature".to_string()),
+        );
+
+        let event = user_receiver.recv().await.unwrap();
+        assert_eq!(event.event_type, TransactionEventType::Submitted);
+        assert_eq!(event.user, user.to_string());
+        assert_eq!(event.signer_pubkey, fee_payer.to_string());
+        assert_eq!(event.signature, Some("signature".to_string()));
+        assert!(fee_payer_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_event_stream_skips_lagged_events() {
+        let broker = TransactionEventBroker::new(1);
+        let user = Pubkey::new_unique();
+        let receiver = broker.subscribe(user);
+
+        broker.publish(&user, create_event(&user, TransactionEventType::Signed));
+        broker.publish(&user, create_event(&user, TransactionEventType::Submitted));
+
+        let mut stream = Box::pin(transaction_event_stream(receiver));
+        let event = stream.next().await.unwrap();
+        assert_eq!(event.event_type, TransactionEventType::Submitted);
+    }
+}
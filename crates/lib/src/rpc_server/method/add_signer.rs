@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{error::KoraError, signer::config::SignerTypeConfig, state::get_signer_pool};
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddSignerRequest {
+    /// Unique name of the signer in the pool
+    pub name: String,
+    /// Weight for the weighted selection strategy (defaults to 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+    /// Signer configuration, same format as a `[[signers]]` entry in signers.toml
+    #[schema(value_type = Object)]
+    pub signer: SignerTypeConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddSignerResponse {
+    pub signer_pubkey: String,
+    pub name: String,
+    pub weight: u32,
+    /// Number of signers in the pool after the addition
+    pub signer_count: usize,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "addSigner", user_pubkey = tracing::field::Empty)
+)]
+pub async fn add_signer(request: AddSignerRequest) -> Result<AddSignerResponse, KoraError> {
+    let pool = get_signer_pool()?;
+    let weight = request.weight.unwrap_or(1);
+
+    pool.hot_add_signer(request.signer, request.name.clone(), weight).await?;
+
+    let signer_pubkey = pool
+        .get_signers_info()
+        .into_iter()
+        .find(|info| info.name == request.name)
+        .map(|info| info.public_key)
+        .ok_or_else(|| {
+            KoraError::InternalServerError(format!("Signer {} missing after add", request.name))
+        })?;
+
+    Ok(AddSignerResponse { signer_pubkey, name: request.name, weight, signer_count: pool.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_signer_request_deserialization() {
+        let request: AddSignerRequest = serde_json::from_value(serde_json::json!({
+            "name": "hot_signer",
+            "signer": { "type": "memory", "private_key_env": "HOT_SIGNER_PRIVATE_KEY" },
+        }))
+        .unwrap();
+
+        assert_eq!(request.name, "hot_signer");
+        assert_eq!(request.weight, None);
+        assert!(matches!(
+            request.signer,
+            SignerTypeConfig::Memory { config } if config.private_key_env == "HOT_SIGNER_PRIVATE_KEY"
+        ));
+    }
+}
@@ -1,13 +1,23 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use solana_keychain::SolanaSigner;
-use std::sync::Arc;
+use solana_message::Message;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+use std::{str::FromStr, sync::Arc};
 use utoipa::ToSchema;
 
 use crate::{
     error::KoraError,
     fee::fee::FeeConfigUtil,
-    rpc_server::middleware_utils::default_sig_verify,
+    rpc_server::middleware_utils::{default_sig_verify, record_transaction_user},
+    signer::KoraSigner,
     state::get_request_signer_with_signer_key,
-    transaction::{TransactionUtil, VersionedTransactionResolved},
+    transaction::{IxUtils, TransactionUtil, VersionedTransactionResolved},
 };
 
 use serde::{Deserialize, Serialize};
@@ -40,27 +50,172 @@ pub struct EstimateTransactionFeeResponse {
     pub signer_pubkey: String,
     /// Public key of the payment destination
     pub payment_address: String,
+    /// Whether the fee was priced with the transaction's own compute budget instructions
+    pub uses_existing_compute_budget: bool,
+    /// Part of `fee_in_lamports` covering Token2022 transfer fees withheld from the payment,
+    /// only set when `validation.account_for_transfer_fee` is enabled
+    pub transfer_fee_lamports: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountMetaTemplate {
+    pub pubkey: String,
+    #[serde(default)]
+    pub is_signer: bool,
+    #[serde(default)]
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstructionTemplate {
+    pub program_id: String,
+    #[serde(default)]
+    pub accounts: Vec<AccountMetaTemplate>,
+    /// Base64 encoded instruction data
+    #[serde(default)]
+    pub data: String,
+}
+
+impl InstructionTemplate {
+    pub fn to_instruction(&self) -> Result<Instruction, KoraError> {
+        let program_id = Pubkey::from_str(&self.program_id)
+            .map_err(|e| KoraError::ValidationError(format!("Invalid program id: {e}")))?;
+
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|account| {
+                let pubkey = Pubkey::from_str(&account.pubkey).map_err(|e| {
+                    KoraError::ValidationError(format!("Invalid account pubkey: {e}"))
+                })?;
+                Ok(AccountMeta {
+                    pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+            })
+            .collect::<Result<Vec<_>, KoraError>>()?;
+
+        let data = STANDARD.decode(&self.data).map_err(|e| {
+            KoraError::ValidationError(format!("Failed to decode base64 instruction data: {e}"))
+        })?;
+
+        Ok(Instruction { program_id, accounts, data })
+    }
+}
+
+impl From<&Instruction> for InstructionTemplate {
+    fn from(instruction: &Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|account| AccountMetaTemplate {
+                    pubkey: account.pubkey.to_string(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: STANDARD.encode(&instruction.data),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EstimateTransactionFeeTemplateRequest {
+    pub instructions: Vec<InstructionTemplate>,
+    #[serde(default)]
+    pub fee_token: Option<String>,
+    /// Optional signer signer_key to ensure consistency across related RPC calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "estimateTransactionFee", user_pubkey = tracing::field::Empty)
+)]
 pub async fn estimate_transaction_fee(
     rpc_client: &Arc<RpcClient>,
     request: EstimateTransactionFeeRequest,
 ) -> Result<EstimateTransactionFeeResponse, KoraError> {
     let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+    record_transaction_user(&transaction);
+    let signer = get_request_signer_with_signer_key(request.signer_key.as_deref())?;
 
+    estimate_fee_for_transaction(
+        rpc_client,
+        &transaction,
+        &signer,
+        request.fee_token.as_deref(),
+        request.sig_verify,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "estimateTransactionFeeTemplate", user_pubkey = tracing::field::Empty)
+)]
+pub async fn estimate_transaction_fee_template(
+    rpc_client: &Arc<RpcClient>,
+    request: EstimateTransactionFeeTemplateRequest,
+) -> Result<EstimateTransactionFeeResponse, KoraError> {
     let signer = get_request_signer_with_signer_key(request.signer_key.as_deref())?;
+    let transaction = build_transaction_from_template(&request.instructions, &signer.pubkey())?;
+    record_transaction_user(&transaction);
+
+    // The template transaction is never signed, so signatures can't be verified
+    estimate_fee_for_transaction(
+        rpc_client,
+        &transaction,
+        &signer,
+        request.fee_token.as_deref(),
+        false,
+    )
+    .await
+}
+
+/// Assemble a minimal unsigned transaction paid by `fee_payer` from instruction templates.
+/// The blockhash is a placeholder since the transaction is only used for estimation.
+pub fn build_transaction_from_template(
+    instructions: &[InstructionTemplate],
+    fee_payer: &Pubkey,
+) -> Result<VersionedTransaction, KoraError> {
+    if instructions.is_empty() {
+        return Err(KoraError::ValidationError("At least one instruction is required".to_string()));
+    }
+
+    let instructions = instructions
+        .iter()
+        .map(InstructionTemplate::to_instruction)
+        .collect::<Result<Vec<_>, KoraError>>()?;
+
+    let message = Message::new_with_blockhash(&instructions, Some(fee_payer), &Hash::default());
+
+    Ok(TransactionUtil::new_unsigned_versioned_transaction(VersionedMessage::Legacy(message)))
+}
+
+async fn estimate_fee_for_transaction(
+    rpc_client: &Arc<RpcClient>,
+    transaction: &VersionedTransaction,
+    signer: &Arc<KoraSigner>,
+    fee_token: Option<&str>,
+    sig_verify: bool,
+) -> Result<EstimateTransactionFeeResponse, KoraError> {
     let config = get_config()?;
     let payment_destination = config.kora.get_payment_address(&signer.pubkey())?;
 
     let validation_config = &config.validation;
     let fee_payer = signer.pubkey();
 
-    let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
-        &transaction,
-        rpc_client,
-        request.sig_verify,
-    )
-    .await?;
+    let mut resolved_transaction =
+        VersionedTransactionResolved::from_transaction(transaction, rpc_client, sig_verify).await?;
+
+    // The base fee comes from getFeeForMessage, which already prices the transaction's own
+    // unit price and limit, so Kora never adds compute budget instructions on top of them
+    let compute_budget = IxUtils::parse_compute_budget_instructions(&resolved_transaction)?;
 
     let fee_calculation = FeeConfigUtil::estimate_kora_fee(
         rpc_client,
@@ -74,18 +229,16 @@ pub async fn estimate_transaction_fee(
     let fee_in_lamports = fee_calculation.total_fee_lamports;
 
     // Calculate fee in token if requested
-    let fee_in_token = FeeConfigUtil::calculate_fee_in_token(
-        rpc_client,
-        fee_in_lamports,
-        request.fee_token.as_deref(),
-    )
-    .await?;
+    let fee_in_token =
+        FeeConfigUtil::calculate_fee_in_token(rpc_client, fee_in_lamports, fee_token).await?;
 
     Ok(EstimateTransactionFeeResponse {
         fee_in_lamports,
         fee_in_token,
         signer_pubkey: fee_payer.to_string(),
         payment_address: payment_destination.to_string(),
+        uses_existing_compute_budget: compute_budget.is_set(),
+        transfer_fee_lamports: fee_calculation.transfer_fee_amount,
     })
 }
 
@@ -96,6 +249,7 @@ mod tests {
         common::{setup_or_get_test_config, setup_or_get_test_signer, RpcMockBuilder},
         transaction_mock::create_mock_encoded_transaction,
     };
+    use solana_system_interface::instruction::transfer;
 
     #[tokio::test]
     async fn test_estimate_transaction_fee_decode_error() {
@@ -161,4 +315,110 @@ mod tests {
             "Should return InvalidTransaction error due to invalid mint parsing"
         );
     }
+
+    fn create_transfer_template(source: &Pubkey, destination: &Pubkey) -> InstructionTemplate {
+        let instruction = transfer(source, destination, 1000);
+        InstructionTemplate {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|account| AccountMetaTemplate {
+                    pubkey: account.pubkey.to_string(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: STANDARD.encode(&instruction.data),
+        }
+    }
+
+    #[test]
+    fn test_build_transaction_from_template() {
+        let fee_payer = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+
+        let transaction = build_transaction_from_template(
+            &[create_transfer_template(&source, &destination)],
+            &fee_payer,
+        )
+        .unwrap();
+
+        let message = &transaction.message;
+        assert_eq!(message.static_account_keys()[0], fee_payer);
+        assert_eq!(message.header().num_required_signatures, 2);
+        assert_eq!(message.instructions().len(), 1);
+        assert_eq!(*message.recent_blockhash(), Hash::default());
+        assert_eq!(transaction.signatures.len(), 2);
+
+        let expected = transfer(&source, &destination, 1000);
+        assert_eq!(message.instructions()[0].data, expected.data);
+    }
+
+    #[test]
+    fn test_build_transaction_from_template_empty_instructions() {
+        let result = build_transaction_from_template(&[], &Pubkey::new_unique());
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_build_transaction_from_template_invalid_program_id() {
+        let mut template = create_transfer_template(&Pubkey::new_unique(), &Pubkey::new_unique());
+        template.program_id = "invalid_program".to_string();
+
+        let result = build_transaction_from_template(&[template], &Pubkey::new_unique());
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_build_transaction_from_template_invalid_data() {
+        let mut template = create_transfer_template(&Pubkey::new_unique(), &Pubkey::new_unique());
+        template.data = "not-base64!".to_string();
+
+        let result = build_transaction_from_template(&[template], &Pubkey::new_unique());
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_estimate_transaction_fee_template_request_deserialization() {
+        let program_id = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let json = format!(
+            r#"{{"instructions": [{{"program_id": "{program_id}", "accounts": [{{"pubkey": "{account}", "is_writable": true}}], "data": "AQID"}}]}}"#
+        );
+
+        let request: EstimateTransactionFeeTemplateRequest = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(request.instructions.len(), 1);
+        assert!(request.fee_token.is_none());
+        let instruction = request.instructions[0].to_instruction().unwrap();
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts, vec![AccountMeta::new(account, false)]);
+        assert_eq!(instruction.data, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_transaction_fee_template_invalid_signer_key() {
+        let _ = setup_or_get_test_config();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+
+        let request = EstimateTransactionFeeTemplateRequest {
+            instructions: vec![create_transfer_template(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+            )],
+            fee_token: None,
+            signer_key: Some("invalid_pubkey".to_string()),
+        };
+
+        let result = estimate_transaction_fee_template(&rpc_client, request).await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
 }
@@ -0,0 +1,149 @@
+use std::{str::FromStr, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use crate::{error::KoraError, state::get_signer_pool};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GetAccountInfoRequest {
+    /// One of the allowed tokens or programs, or a signer of the pool
+    pub pubkey: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetAccountInfoResponse {
+    pub lamports: u64,
+    pub owner: String,
+    pub data_base64: String,
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_getAccountInfo", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_account_info(
+    rpc_client: &Arc<RpcClient>,
+    request: GetAccountInfoRequest,
+) -> Result<GetAccountInfoResponse, KoraError> {
+    let pubkey = Pubkey::from_str(&request.pubkey).map_err(|e| {
+        KoraError::InvalidRequest(format!("Invalid pubkey {}: {e}", request.pubkey))
+    })?;
+
+    if !is_account_accessible(&pubkey)? {
+        return Err(KoraError::InvalidRequest(format!(
+            "Account {pubkey} is not an allowed token, program or fee payer"
+        )));
+    }
+
+    let account = rpc_client.get_account(&pubkey).await.map_err(|e| match KoraError::from(e) {
+        KoraError::AccountNotFound(_) => KoraError::AccountNotFound(pubkey.to_string()),
+        other => other,
+    })?;
+
+    Ok(GetAccountInfoResponse {
+        lamports: account.lamports,
+        owner: account.owner.to_string(),
+        data_base64: STANDARD.encode(&account.data),
+        executable: account.executable,
+        rent_epoch: account.rent_epoch,
+    })
+}
+
+/// Only accounts Kora already exposes can be read, so the node can't be used as an open proxy
+fn is_account_accessible(pubkey: &Pubkey) -> Result<bool, KoraError> {
+    let config = get_config()?;
+    let address = pubkey.to_string();
+    if config.validation.allowed_tokens.contains(&address)
+        || config.validation.allowed_programs.contains(&address)
+    {
+        return Ok(true);
+    }
+
+    Ok(get_signer_pool()?.contains_signer(pubkey))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        common::{setup_or_get_test_signer, RpcMockBuilder},
+        config_mock::ConfigMockBuilder,
+    };
+    use serial_test::serial;
+    use solana_sdk::account::Account;
+
+    fn request(pubkey: &Pubkey) -> GetAccountInfoRequest {
+        GetAccountInfoRequest { pubkey: pubkey.to_string() }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_account_info_allowed_token() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let _m =
+            ConfigMockBuilder::new().with_allowed_tokens(vec![mint.to_string()]).build_and_setup();
+        let rpc_client = RpcMockBuilder::new()
+            .with_account_info(&Account {
+                lamports: 1_461_600,
+                data: vec![1, 2, 3, 4],
+                owner,
+                executable: false,
+                rent_epoch: 42,
+            })
+            .build();
+
+        let response = get_account_info(&rpc_client, request(&mint)).await.unwrap();
+
+        assert_eq!(response.lamports, 1_461_600);
+        assert_eq!(response.owner, owner.to_string());
+        assert_eq!(response.data_base64, STANDARD.encode([1, 2, 3, 4]));
+        assert!(!response.executable);
+        assert_eq!(response.rent_epoch, 42);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_account_info_fee_payer() {
+        let fee_payer = setup_or_get_test_signer();
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let rpc_client = RpcMockBuilder::new()
+            .with_account_info(&Account { lamports: 5_000_000, ..Default::default() })
+            .build();
+
+        let response = get_account_info(&rpc_client, request(&fee_payer)).await.unwrap();
+
+        assert_eq!(response.lamports, 5_000_000);
+        assert_eq!(response.data_base64, "");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_account_info_rejects_other_accounts() {
+        setup_or_get_test_signer();
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let rpc_client = RpcMockBuilder::new()
+            .with_account_info(&Account { lamports: 1, ..Default::default() })
+            .build();
+
+        let result = get_account_info(&rpc_client, request(&Pubkey::new_unique())).await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+
+        let result = get_account_info(
+            &rpc_client,
+            GetAccountInfoRequest { pubkey: "not-a-pubkey".to_string() },
+        )
+        .await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+}
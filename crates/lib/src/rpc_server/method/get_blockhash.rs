@@ -1,37 +1,162 @@
 use crate::error::KoraError;
 use nonblocking::rpc_client::RpcClient;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use solana_client::nonblocking;
 use solana_commitment_config::CommitmentConfig;
 use utoipa::ToSchema;
 
+/// Commitment level used when fetching the latest blockhash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockhashCommitment {
+    Processed,
+    #[default]
+    Confirmed,
+    Finalized,
+}
+
+impl From<BlockhashCommitment> for CommitmentConfig {
+    fn from(commitment: BlockhashCommitment) -> Self {
+        match commitment {
+            BlockhashCommitment::Processed => CommitmentConfig::processed(),
+            BlockhashCommitment::Confirmed => CommitmentConfig::confirmed(),
+            BlockhashCommitment::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct GetBlockhashRequest {
+    /// Optional commitment level for the blockhash (defaults to confirmed)
+    #[serde(default)]
+    pub commitment: BlockhashCommitment,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct GetBlockhashResponse {
     pub blockhash: String,
+    /// Last block height at which the blockhash is valid for transactions
+    pub last_valid_block_height: u64,
 }
 
-pub async fn get_blockhash(rpc_client: &RpcClient) -> Result<GetBlockhashResponse, KoraError> {
-    let blockhash = rpc_client
-        .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getBlockhash", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_blockhash(
+    rpc_client: &RpcClient,
+    request: GetBlockhashRequest,
+) -> Result<GetBlockhashResponse, KoraError> {
+    let (blockhash, last_valid_block_height) = rpc_client
+        .get_latest_blockhash_with_commitment(request.commitment.into())
         .await
         .map_err(|e| KoraError::RpcError(e.to_string()))?;
-    Ok(GetBlockhashResponse { blockhash: blockhash.0.to_string() })
+    Ok(GetBlockhashResponse { blockhash: blockhash.to_string(), last_valid_block_height })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tests::{config_mock::ConfigMockBuilder, rpc_mock::RpcMockBuilder};
+    use solana_client::client_error::{ClientError, ClientErrorKind};
+    use solana_sdk::hash::Hash;
 
     #[tokio::test]
     async fn test_get_blockhash_success() {
         let _m = ConfigMockBuilder::new().build_and_setup();
-        let rpc_client = RpcMockBuilder::new().with_blockhash().build();
+        let blockhash = Hash::new_unique();
+        let rpc_client = RpcMockBuilder::new().with_blockhash(blockhash, 1000).build();
 
-        let result = get_blockhash(&rpc_client).await;
+        let result = get_blockhash(&rpc_client, GetBlockhashRequest::default()).await;
 
         assert!(result.is_ok(), "Should successfully get blockhash");
         let response = result.unwrap();
-        assert!(!response.blockhash.is_empty(), "Blockhash should not be empty");
+        assert_eq!(response.blockhash, blockhash.to_string());
+        assert_eq!(response.last_valid_block_height, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_get_blockhash_rpc_error() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let rpc_client = RpcMockBuilder::new()
+            .with_blockhash(Hash::new_unique(), 1000)
+            .with_blockhash_error(ClientError::from(ClientErrorKind::Custom(
+                "blockhash unavailable".to_string(),
+            )))
+            .build();
+
+        let result = get_blockhash(&rpc_client, GetBlockhashRequest::default()).await;
+        assert!(matches!(result, Err(KoraError::RpcError(_))), "Unexpected result: {result:?}");
+
+        // Only the first call fails, a retry gets the blockhash
+        let result = get_blockhash(&rpc_client, GetBlockhashRequest::default()).await;
+        assert_eq!(result.unwrap().last_valid_block_height, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_get_blockhash_slow_response() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let rpc_client = RpcMockBuilder::new()
+            .with_blockhash(Hash::new_unique(), 1000)
+            .with_blockhash_delay(100)
+            .build();
+
+        let start = std::time::Instant::now();
+        let result = get_blockhash(&rpc_client, GetBlockhashRequest::default()).await;
+
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+        assert_eq!(result.unwrap().last_valid_block_height, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_get_blockhash_each_commitment_level() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+
+        for commitment in [
+            BlockhashCommitment::Processed,
+            BlockhashCommitment::Confirmed,
+            BlockhashCommitment::Finalized,
+        ] {
+            let rpc_client = RpcMockBuilder::new().with_blockhash(Hash::new_unique(), 1000).build();
+
+            let result = get_blockhash(&rpc_client, GetBlockhashRequest { commitment }).await;
+
+            assert!(result.is_ok(), "Should get blockhash at {commitment:?} commitment");
+            let response = result.unwrap();
+            assert!(!response.blockhash.is_empty(), "Blockhash should not be empty");
+            assert_eq!(response.last_valid_block_height, 1000);
+        }
+    }
+
+    #[test]
+    fn test_get_blockhash_request_deserialization() {
+        let request: GetBlockhashRequest =
+            serde_json::from_str(r#"{"commitment": "finalized"}"#).unwrap();
+        assert_eq!(request.commitment, BlockhashCommitment::Finalized);
+
+        let request: GetBlockhashRequest =
+            serde_json::from_str(r#"{"commitment": "processed"}"#).unwrap();
+        assert_eq!(request.commitment, BlockhashCommitment::Processed);
+
+        let request: GetBlockhashRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.commitment, BlockhashCommitment::Confirmed);
+
+        assert!(serde_json::from_str::<GetBlockhashRequest>(r#"{"commitment": "max"}"#).is_err());
+    }
+
+    #[test]
+    fn test_blockhash_commitment_to_commitment_config() {
+        assert_eq!(
+            CommitmentConfig::from(BlockhashCommitment::Processed),
+            CommitmentConfig::processed()
+        );
+        assert_eq!(
+            CommitmentConfig::from(BlockhashCommitment::Confirmed),
+            CommitmentConfig::confirmed()
+        );
+        assert_eq!(
+            CommitmentConfig::from(BlockhashCommitment::Finalized),
+            CommitmentConfig::finalized()
+        );
     }
 }
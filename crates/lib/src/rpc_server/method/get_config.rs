@@ -17,8 +17,14 @@ pub struct GetConfigResponse {
     pub fee_payers: Vec<String>,
     pub validation_config: ValidationConfig,
     pub enabled_methods: EnabledMethods,
+    /// Fee payers skipped by the signer pool after failing their health checks
+    pub unhealthy_fee_payers: Vec<String>,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getConfig", user_pubkey = tracing::field::Empty)
+)]
 pub async fn get_config() -> Result<GetConfigResponse, KoraError> {
     let config = state::get_config()?;
 
@@ -27,13 +33,20 @@ pub async fn get_config() -> Result<GetConfigResponse, KoraError> {
         .map_err(|e| KoraError::InternalServerError(format!("Signer pool not initialized: {e}")))?;
 
     // Get all fee payer public keys from the signer pool
+    let signers_info = pool.get_signers_info();
     let fee_payers: Vec<String> =
-        pool.get_signers_info().iter().map(|signer| signer.public_key.clone()).collect();
+        signers_info.iter().map(|signer| signer.public_key.clone()).collect();
+    let unhealthy_fee_payers: Vec<String> = signers_info
+        .iter()
+        .filter(|signer| !signer.healthy)
+        .map(|signer| signer.public_key.clone())
+        .collect();
 
     Ok(GetConfigResponse {
         fee_payers,
         validation_config: config.validation.clone(),
         enabled_methods: config.kora.enabled_methods.clone(),
+        unhealthy_fee_payers,
     })
 }
 
@@ -58,6 +71,7 @@ mod tests {
         // Assert fee payers
         assert!(!response.fee_payers.is_empty(), "Should have at least one fee payer");
         assert!(!response.fee_payers[0].is_empty(), "Fee payer pubkey should not be empty");
+        assert!(response.unhealthy_fee_payers.is_empty(), "Signers start out healthy");
 
         // Assert ValidationConfig defaults
         assert_eq!(response.validation_config.max_allowed_lamports, 1_000_000_000);
@@ -128,13 +142,15 @@ mod tests {
         assert!(!response.validation_config.fee_payer_policy.token_2022.allow_thaw_account);
         // Assert PriceConfig default (check margin value)
         match response.validation_config.price.model {
-            crate::fee::price::PriceModel::Margin { margin } => assert_eq!(margin, 0.0),
+            crate::fee::price::PriceModel::Margin { margin } => assert!(margin.is_zero()),
             _ => panic!("Expected Margin price model"),
         }
 
         // Assert Token2022Config defaults (only public fields)
         assert_eq!(response.validation_config.token_2022.blocked_mint_extensions.len(), 0);
         assert_eq!(response.validation_config.token_2022.blocked_account_extensions.len(), 0);
+        assert!(response.validation_config.token_2022.required_mint_extensions.is_empty());
+        assert!(response.validation_config.token_2022.required_account_extensions.is_empty());
 
         // Assert EnabledMethods defaults
         assert!(response.enabled_methods.liveness);
@@ -146,5 +162,14 @@ mod tests {
         assert!(response.enabled_methods.transfer_transaction);
         assert!(response.enabled_methods.get_blockhash);
         assert!(response.enabled_methods.get_config);
+        assert!(response.enabled_methods.subscribe_transaction_events);
+        assert!(response.enabled_methods.get_version);
+        assert!(response.enabled_methods.get_instruction_template);
+        assert!(response.enabled_methods.get_token_metadata);
+        assert!(response.enabled_methods.get_account_info);
+        assert!(response.enabled_methods.get_network_status);
+        assert!(response.enabled_methods.sign_message);
+        assert!(response.enabled_methods.sign_transaction_batch);
+        assert!(response.enabled_methods.simulate_transaction);
     }
 }
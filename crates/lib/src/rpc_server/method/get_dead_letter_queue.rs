@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{error::KoraError, signer::DeadLetterEntry, state::get_signer_pool};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetDeadLetterQueueResponse {
+    /// Failed signing attempts, oldest first. They are removed from the queue once returned
+    pub entries: Vec<DeadLetterEntry>,
+    /// Maximum number of entries kept, older ones are dropped first
+    pub capacity: usize,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_getDeadLetterQueue", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_dead_letter_queue() -> Result<GetDeadLetterQueueResponse, KoraError> {
+    let pool = get_signer_pool()?;
+    let queue = pool.dead_letter_queue();
+
+    Ok(GetDeadLetterQueueResponse { capacity: queue.capacity(), entries: queue.drain() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::common::setup_or_get_test_signer;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_dead_letter_queue() {
+        let fee_payer = setup_or_get_test_signer();
+        let pool = get_signer_pool().unwrap();
+        pool.dead_letter_queue().drain();
+
+        pool.record_signing_failure(&fee_payer, "Remote signer unavailable", &[1, 2, 3]);
+
+        let response = get_dead_letter_queue().await.unwrap();
+        assert_eq!(response.entries.len(), 1);
+        assert_eq!(response.entries[0].error, "Remote signer unavailable");
+        assert_eq!(response.capacity, pool.dead_letter_queue().capacity());
+
+        // Returned entries are removed from the queue
+        let response = get_dead_letter_queue().await.unwrap();
+        assert!(response.entries.is_empty());
+    }
+}
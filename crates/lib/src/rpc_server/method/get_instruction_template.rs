@@ -0,0 +1,306 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use solana_system_interface::instruction::transfer;
+use spl_associated_token_account_interface::instruction::create_associated_token_account;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use crate::{error::KoraError, rpc_server::method::estimate_transaction_fee::InstructionTemplate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InstructionTemplateType {
+    /// System `Transfer`, params: `from`, `to`, `lamports`
+    SolTransfer,
+    /// SPL Token `Transfer`, params: `source`, `destination`, `authority`, `amount`
+    SplTransfer,
+    /// SPL Token `TransferChecked`, params: `source`, `mint`, `destination`, `authority`,
+    /// `amount`, `decimals`
+    SplTransferChecked,
+    /// Token-2022 `TransferChecked`, same params as `spl_transfer_checked`
+    Token2022TransferChecked,
+    /// Associated token account `Create`, params: `payer`, `wallet`, `mint` and an optional
+    /// `token_program` (SPL Token by default)
+    CreateAta,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GetInstructionTemplateRequest {
+    pub instruction_type: InstructionTemplateType,
+    /// Addresses are base58 strings, amounts are raw token amounts
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GetInstructionTemplateResponse {
+    /// Assembled instruction, in the format accepted by `estimateTransactionFeeTemplate`
+    pub instruction: InstructionTemplate,
+}
+
+#[derive(Deserialize)]
+struct SolTransferParams {
+    from: String,
+    to: String,
+    lamports: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenTransferParams {
+    source: String,
+    destination: String,
+    authority: String,
+    amount: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenTransferCheckedParams {
+    source: String,
+    mint: String,
+    destination: String,
+    authority: String,
+    amount: u64,
+    decimals: u8,
+}
+
+#[derive(Deserialize)]
+struct CreateAtaParams {
+    payer: String,
+    wallet: String,
+    mint: String,
+    #[serde(default)]
+    token_program: Option<String>,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getInstructionTemplate", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_instruction_template(
+    request: GetInstructionTemplateRequest,
+) -> Result<GetInstructionTemplateResponse, KoraError> {
+    let instruction = build_instruction(request.instruction_type, request.params)?;
+
+    Ok(GetInstructionTemplateResponse { instruction: InstructionTemplate::from(&instruction) })
+}
+
+fn build_instruction(
+    instruction_type: InstructionTemplateType,
+    params: serde_json::Value,
+) -> Result<Instruction, KoraError> {
+    match instruction_type {
+        InstructionTemplateType::SolTransfer => {
+            let params: SolTransferParams = parse_params(params)?;
+            Ok(transfer(
+                &parse_pubkey("from", &params.from)?,
+                &parse_pubkey("to", &params.to)?,
+                params.lamports,
+            ))
+        }
+        InstructionTemplateType::SplTransfer => {
+            let params: TokenTransferParams = parse_params(params)?;
+            spl_token_interface::instruction::transfer(
+                &spl_token_interface::id(),
+                &parse_pubkey("source", &params.source)?,
+                &parse_pubkey("destination", &params.destination)?,
+                &parse_pubkey("authority", &params.authority)?,
+                &[],
+                params.amount,
+            )
+            .map_err(|e| KoraError::TokenOperationError(e.to_string()))
+        }
+        InstructionTemplateType::SplTransferChecked => {
+            let params: TokenTransferCheckedParams = parse_params(params)?;
+            spl_token_interface::instruction::transfer_checked(
+                &spl_token_interface::id(),
+                &parse_pubkey("source", &params.source)?,
+                &parse_pubkey("mint", &params.mint)?,
+                &parse_pubkey("destination", &params.destination)?,
+                &parse_pubkey("authority", &params.authority)?,
+                &[],
+                params.amount,
+                params.decimals,
+            )
+            .map_err(|e| KoraError::TokenOperationError(e.to_string()))
+        }
+        InstructionTemplateType::Token2022TransferChecked => {
+            let params: TokenTransferCheckedParams = parse_params(params)?;
+            spl_token_2022_interface::instruction::transfer_checked(
+                &spl_token_2022_interface::id(),
+                &parse_pubkey("source", &params.source)?,
+                &parse_pubkey("mint", &params.mint)?,
+                &parse_pubkey("destination", &params.destination)?,
+                &parse_pubkey("authority", &params.authority)?,
+                &[],
+                params.amount,
+                params.decimals,
+            )
+            .map_err(|e| KoraError::TokenOperationError(e.to_string()))
+        }
+        InstructionTemplateType::CreateAta => {
+            let params: CreateAtaParams = parse_params(params)?;
+            let token_program = match params.token_program.as_deref() {
+                Some(token_program) => parse_pubkey("token_program", token_program)?,
+                None => spl_token_interface::id(),
+            };
+            if token_program != spl_token_interface::id()
+                && token_program != spl_token_2022_interface::id()
+            {
+                return Err(KoraError::ValidationError(format!(
+                    "Invalid token_program: {token_program} is not a token program"
+                )));
+            }
+
+            Ok(create_associated_token_account(
+                &parse_pubkey("payer", &params.payer)?,
+                &parse_pubkey("wallet", &params.wallet)?,
+                &parse_pubkey("mint", &params.mint)?,
+                &token_program,
+            ))
+        }
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: serde_json::Value) -> Result<T, KoraError> {
+    serde_json::from_value(params)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid instruction params: {e}")))
+}
+
+fn parse_pubkey(name: &str, value: &str) -> Result<Pubkey, KoraError> {
+    Pubkey::from_str(value)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid {name} address: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+
+    async fn template(
+        instruction_type: InstructionTemplateType,
+        params: serde_json::Value,
+    ) -> Result<InstructionTemplate, KoraError> {
+        get_instruction_template(GetInstructionTemplateRequest { instruction_type, params })
+            .await
+            .map(|response| response.instruction)
+    }
+
+    #[tokio::test]
+    async fn test_get_instruction_template_round_trips() {
+        let (source, destination, authority, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let cases = [
+            (
+                InstructionTemplateType::SolTransfer,
+                json!({ "from": authority.to_string(), "to": destination.to_string(), "lamports": 1000 }),
+                transfer(&authority, &destination, 1000),
+            ),
+            (
+                InstructionTemplateType::SplTransfer,
+                json!({
+                    "source": source.to_string(),
+                    "destination": destination.to_string(),
+                    "authority": authority.to_string(),
+                    "amount": 50,
+                }),
+                spl_token_interface::instruction::transfer(
+                    &spl_token_interface::id(),
+                    &source,
+                    &destination,
+                    &authority,
+                    &[],
+                    50,
+                )
+                .unwrap(),
+            ),
+            (
+                InstructionTemplateType::Token2022TransferChecked,
+                json!({
+                    "source": source.to_string(),
+                    "mint": mint.to_string(),
+                    "destination": destination.to_string(),
+                    "authority": authority.to_string(),
+                    "amount": 50,
+                    "decimals": 6,
+                }),
+                spl_token_2022_interface::instruction::transfer_checked(
+                    &spl_token_2022_interface::id(),
+                    &source,
+                    &mint,
+                    &destination,
+                    &authority,
+                    &[],
+                    50,
+                    6,
+                )
+                .unwrap(),
+            ),
+        ];
+
+        for (instruction_type, params, expected) in cases {
+            let template = template(instruction_type, params).await.unwrap();
+            assert_eq!(template.to_instruction().unwrap(), expected, "{instruction_type:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_instruction_template_create_ata() {
+        let (payer, wallet, mint) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let params = json!({
+            "payer": payer.to_string(),
+            "wallet": wallet.to_string(),
+            "mint": mint.to_string(),
+            "token_program": spl_token_2022_interface::id().to_string(),
+        });
+
+        let instruction = template(InstructionTemplateType::CreateAta, params)
+            .await
+            .unwrap()
+            .to_instruction()
+            .unwrap();
+
+        let ata = get_associated_token_address_with_program_id(
+            &wallet,
+            &mint,
+            &spl_token_2022_interface::id(),
+        );
+        assert_eq!(instruction.accounts[0].pubkey, payer);
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[1].pubkey, ata);
+
+        let params = json!({
+            "payer": payer.to_string(),
+            "wallet": wallet.to_string(),
+            "mint": mint.to_string(),
+            "token_program": Pubkey::new_unique().to_string(),
+        });
+        let result = template(InstructionTemplateType::CreateAta, params).await;
+        assert!(
+            matches!(result, Err(KoraError::ValidationError(msg)) if msg.contains("token_program"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_instruction_template_invalid_params() {
+        let result =
+            template(InstructionTemplateType::SolTransfer, json!({ "from": "invalid" })).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(msg)) if msg.contains("params")));
+
+        let params =
+            json!({ "from": "invalid", "to": Pubkey::new_unique().to_string(), "lamports": 1 });
+        let result = template(InstructionTemplateType::SolTransfer, params).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(msg)) if msg.contains("from")));
+
+        let request: Result<GetInstructionTemplateRequest, _> =
+            serde_json::from_value(json!({ "instruction_type": "unknown", "params": {} }));
+        assert!(request.is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use utoipa::ToSchema;
+
+use crate::{
+    constant::{
+        NETWORK_CONGESTION_HIGH_TPS, NETWORK_CONGESTION_MEDIUM_TPS,
+        NETWORK_STATUS_CACHE_TTL_SECONDS, NETWORK_STATUS_PERFORMANCE_SAMPLES,
+    },
+    error::KoraError,
+};
+
+/// Network status shared by `kora_getNetworkStatus` and `kora_isNetworkCongested`
+static NETWORK_STATUS_CACHE: Lazy<NetworkStatusCache> =
+    Lazy::new(|| NetworkStatusCache::new(Duration::from_secs(NETWORK_STATUS_CACHE_TTL_SECONDS)));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CongestionLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl CongestionLevel {
+    pub fn from_tps(tps: u64) -> Self {
+        if tps >= NETWORK_CONGESTION_HIGH_TPS {
+            CongestionLevel::High
+        } else if tps >= NETWORK_CONGESTION_MEDIUM_TPS {
+            CongestionLevel::Medium
+        } else {
+            CongestionLevel::Low
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct GetNetworkStatusResponse {
+    /// Transactions per second over the recent performance samples, votes included
+    pub tps: u64,
+    /// Average slot duration over the recent performance samples
+    pub avg_slot_time_ms: f64,
+    pub current_slot: u64,
+    pub congestion_level: CongestionLevel,
+}
+
+impl GetNetworkStatusResponse {
+    pub fn is_congested(&self) -> bool {
+        self.congestion_level == CongestionLevel::High
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IsNetworkCongestedResponse {
+    /// Whether the congestion level is high
+    pub congested: bool,
+}
+
+/// Network status computed from `getRecentPerformanceSamples`, reused for `ttl` so clients
+/// polling it don't each cost an RPC round-trip
+pub struct NetworkStatusCache {
+    ttl: Duration,
+    cached: RwLock<Option<(GetNetworkStatusResponse, Instant)>>,
+}
+
+impl NetworkStatusCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, cached: RwLock::new(None) }
+    }
+
+    pub fn global() -> &'static Self {
+        &NETWORK_STATUS_CACHE
+    }
+
+    pub async fn get_network_status(
+        &self,
+        rpc_client: &RpcClient,
+    ) -> Result<GetNetworkStatusResponse, KoraError> {
+        if let Some(status) = self.get_cached() {
+            return Ok(status);
+        }
+
+        let status = fetch_network_status(rpc_client).await?;
+        *self.cached.write() = Some((status.clone(), Instant::now()));
+        Ok(status)
+    }
+
+    fn get_cached(&self) -> Option<GetNetworkStatusResponse> {
+        self.cached
+            .read()
+            .as_ref()
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.ttl)
+            .map(|(status, _)| status.clone())
+    }
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_getNetworkStatus", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_network_status(
+    rpc_client: &RpcClient,
+) -> Result<GetNetworkStatusResponse, KoraError> {
+    NetworkStatusCache::global().get_network_status(rpc_client).await
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_isNetworkCongested", user_pubkey = tracing::field::Empty)
+)]
+pub async fn is_network_congested(
+    rpc_client: &RpcClient,
+) -> Result<IsNetworkCongestedResponse, KoraError> {
+    let status = NetworkStatusCache::global().get_network_status(rpc_client).await?;
+    Ok(IsNetworkCongestedResponse { congested: status.is_congested() })
+}
+
+async fn fetch_network_status(
+    rpc_client: &RpcClient,
+) -> Result<GetNetworkStatusResponse, KoraError> {
+    let samples =
+        rpc_client.get_recent_performance_samples(Some(NETWORK_STATUS_PERFORMANCE_SAMPLES)).await?;
+    let current_slot = rpc_client.get_slot().await?;
+
+    let (transactions, slots, seconds) =
+        samples.iter().fold((0u64, 0u64, 0u64), |(transactions, slots, seconds), sample| {
+            (
+                transactions.saturating_add(sample.num_transactions),
+                slots.saturating_add(sample.num_slots),
+                seconds.saturating_add(sample.sample_period_secs as u64),
+            )
+        });
+    if slots == 0 || seconds == 0 {
+        return Err(KoraError::RpcError("No recent performance samples".to_string()));
+    }
+
+    let tps = transactions / seconds;
+    Ok(GetNetworkStatusResponse {
+        tps,
+        avg_slot_time_ms: (seconds * 1000) as f64 / slots as f64,
+        current_slot,
+        congestion_level: CongestionLevel::from_tps(tps),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::rpc_mock::RpcMockBuilder;
+    use serde_json::{json, Value};
+    use solana_client::rpc_request::RpcRequest;
+    use std::sync::Arc;
+
+    /// Mock client whose performance samples each cover 60 seconds and 150 slots
+    fn network_rpc_client(transactions_per_sample: &[u64], slot: u64) -> Arc<RpcClient> {
+        let samples: Vec<Value> = transactions_per_sample
+            .iter()
+            .enumerate()
+            .map(|(i, transactions)| {
+                json!({
+                    "slot": slot - i as u64 * 150,
+                    "numTransactions": transactions,
+                    "numNonVoteTransactions": transactions / 4,
+                    "numSlots": 150,
+                    "samplePeriodSecs": 60
+                })
+            })
+            .collect();
+
+        RpcMockBuilder::new()
+            .with_custom_mock(RpcRequest::GetRecentPerformanceSamples, json!(samples))
+            .with_custom_mock(RpcRequest::GetSlot, json!(slot))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_get_network_status_from_performance_samples() {
+        let rpc_client = network_rpc_client(&[200_000, 160_000], 300_000);
+        let cache = NetworkStatusCache::new(Duration::ZERO);
+
+        let status = cache.get_network_status(&rpc_client).await.unwrap();
+
+        assert_eq!(status.tps, 3_000);
+        assert_eq!(status.avg_slot_time_ms, 400.0);
+        assert_eq!(status.current_slot, 300_000);
+        assert_eq!(status.congestion_level, CongestionLevel::Medium);
+        assert!(!status.is_congested());
+    }
+
+    #[tokio::test]
+    async fn test_get_network_status_is_cached() {
+        let quiet_rpc_client = network_rpc_client(&[60_000], 1_000);
+        let busy_rpc_client = network_rpc_client(&[300_000], 2_000);
+
+        let cache = NetworkStatusCache::new(Duration::from_secs(5));
+        cache.get_network_status(&quiet_rpc_client).await.unwrap();
+        let status = cache.get_network_status(&busy_rpc_client).await.unwrap();
+        assert_eq!(status.current_slot, 1_000);
+        assert_eq!(status.congestion_level, CongestionLevel::Low);
+
+        let cache = NetworkStatusCache::new(Duration::ZERO);
+        cache.get_network_status(&quiet_rpc_client).await.unwrap();
+        let status = cache.get_network_status(&busy_rpc_client).await.unwrap();
+        assert_eq!(status.current_slot, 2_000);
+        assert_eq!(status.congestion_level, CongestionLevel::High);
+        assert!(status.is_congested());
+    }
+
+    #[tokio::test]
+    async fn test_get_network_status_without_samples() {
+        let rpc_client = network_rpc_client(&[], 1_000);
+        let cache = NetworkStatusCache::new(Duration::from_secs(5));
+
+        let result = cache.get_network_status(&rpc_client).await;
+
+        assert!(matches!(result, Err(KoraError::RpcError(_))));
+        assert!(cache.get_cached().is_none());
+    }
+
+    #[test]
+    fn test_congestion_level_from_tps() {
+        assert_eq!(CongestionLevel::from_tps(0), CongestionLevel::Low);
+        assert_eq!(
+            CongestionLevel::from_tps(NETWORK_CONGESTION_MEDIUM_TPS - 1),
+            CongestionLevel::Low
+        );
+        assert_eq!(
+            CongestionLevel::from_tps(NETWORK_CONGESTION_MEDIUM_TPS),
+            CongestionLevel::Medium
+        );
+        assert_eq!(
+            CongestionLevel::from_tps(NETWORK_CONGESTION_HIGH_TPS - 1),
+            CongestionLevel::Medium
+        );
+        assert_eq!(CongestionLevel::from_tps(NETWORK_CONGESTION_HIGH_TPS), CongestionLevel::High);
+    }
+
+    #[test]
+    fn test_get_network_status_response_serialization() {
+        let response = GetNetworkStatusResponse {
+            tps: 4_200,
+            avg_slot_time_ms: 412.5,
+            current_slot: 42,
+            congestion_level: CongestionLevel::High,
+        };
+
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({
+                "tps": 4_200,
+                "avg_slot_time_ms": 412.5,
+                "current_slot": 42,
+                "congestion_level": "high"
+            })
+        );
+    }
+}
@@ -14,6 +14,10 @@ pub struct GetPayerSignerResponse {
     pub payment_address: String,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getPayerSigner", user_pubkey = tracing::field::Empty)
+)]
 pub async fn get_payer_signer() -> Result<GetPayerSignerResponse, KoraError> {
     let config = get_config()?;
     let pool = get_signer_pool()?;
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_keychain::SolanaSigner;
+use solana_program_pack::Pack;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use spl_token_2022_interface::{state::Account as Token2022Account, ID as TOKEN_2022_PROGRAM_ID};
+use spl_token_interface::{state::Account as SplTokenAccount, ID as SPL_TOKEN_PROGRAM_ID};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::{
+    error::KoraError, state::get_request_signer_with_signer_key,
+    validator::account_validator::AccountType,
+};
+
+/// Owner of a token account, right after the mint
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+/// Version (4 bytes), state (4 bytes), authority (32 bytes), durable nonce (32 bytes) and
+/// lamports per signature (8 bytes)
+const NONCE_ACCOUNT_LEN: u64 = 80;
+const NONCE_AUTHORITY_OFFSET: usize = 8;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct GetProgramAccountsRequest {
+    /// Signer whose accounts are listed, picked by the pool strategy when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedAccountType {
+    TokenAccount,
+    NonceAccount,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ManagedAccount {
+    pub pubkey: String,
+    /// Program owning the account
+    pub program_id: String,
+    pub account_type: ManagedAccountType,
+    pub lamports: u64,
+    /// Mint of a token account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+    /// Raw token amount of a token account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetProgramAccountsResponse {
+    pub signer_pubkey: String,
+    /// Token accounts owned by the signer and nonce accounts it is the authority of
+    pub accounts: Vec<ManagedAccount>,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_getProgramAccounts", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_program_accounts(
+    rpc_client: &Arc<RpcClient>,
+    request: GetProgramAccountsRequest,
+) -> Result<GetProgramAccountsResponse, KoraError> {
+    let signer = get_request_signer_with_signer_key(request.signer_key.as_deref())?;
+    let fee_payer = signer.pubkey();
+
+    let queries = [
+        (
+            SPL_TOKEN_PROGRAM_ID,
+            vec![
+                RpcFilterType::DataSize(SplTokenAccount::LEN as u64),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    TOKEN_ACCOUNT_OWNER_OFFSET,
+                    fee_payer.as_ref(),
+                )),
+            ],
+        ),
+        // Token2022 accounts may carry extensions, so their size varies
+        (
+            TOKEN_2022_PROGRAM_ID,
+            vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                TOKEN_ACCOUNT_OWNER_OFFSET,
+                fee_payer.as_ref(),
+            ))],
+        ),
+        (
+            SYSTEM_PROGRAM_ID,
+            vec![
+                RpcFilterType::DataSize(NONCE_ACCOUNT_LEN),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    NONCE_AUTHORITY_OFFSET,
+                    fee_payer.as_ref(),
+                )),
+            ],
+        ),
+    ];
+
+    let mut accounts = Vec::new();
+    for (program_id, filters) in queries {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let program_accounts =
+            rpc_client.get_program_accounts_with_config(&program_id, config).await.map_err(
+                |e| KoraError::RpcError(format!("Failed to get {program_id} accounts: {e}")),
+            )?;
+
+        accounts.extend(
+            program_accounts
+                .iter()
+                .filter_map(|(pubkey, account)| decode_managed_account(pubkey, account)),
+        );
+    }
+
+    Ok(GetProgramAccountsResponse { signer_pubkey: fee_payer.to_string(), accounts })
+}
+
+/// Decode an account returned by one of the queries, `None` for anything that is neither a
+/// token account nor a nonce account
+fn decode_managed_account(pubkey: &Pubkey, account: &Account) -> Option<ManagedAccount> {
+    let mut managed = ManagedAccount {
+        pubkey: pubkey.to_string(),
+        program_id: account.owner.to_string(),
+        account_type: ManagedAccountType::NonceAccount,
+        lamports: account.lamports,
+        mint: None,
+        amount: None,
+    };
+
+    if account.owner == SYSTEM_PROGRAM_ID {
+        return (account.data.len() as u64 == NONCE_ACCOUNT_LEN).then_some(managed);
+    }
+
+    if AccountType::detect(account, pubkey) != Some(AccountType::TokenAccount) {
+        return None;
+    }
+
+    let (mint, amount) = if account.owner == TOKEN_2022_PROGRAM_ID {
+        let token_account = Token2022Account::unpack_from_slice(&account.data).ok()?;
+        (token_account.mint, token_account.amount)
+    } else {
+        let token_account = SplTokenAccount::unpack_from_slice(&account.data).ok()?;
+        (token_account.mint, token_account.amount)
+    };
+
+    managed.account_type = ManagedAccountType::TokenAccount;
+    managed.mint = Some(mint.to_string());
+    managed.amount = Some(amount);
+    Some(managed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::account_mock::{
+        create_mock_account_with_owner, create_mock_spl_mint_account, AccountMockBuilder,
+        TokenAccountMockBuilder,
+    };
+
+    #[test]
+    fn test_decode_managed_account() {
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        for token_account in [
+            TokenAccountMockBuilder::new()
+                .with_owner(&owner)
+                .with_mint(&mint)
+                .with_amount(42)
+                .build(),
+            TokenAccountMockBuilder::new()
+                .with_owner(&owner)
+                .with_mint(&mint)
+                .with_amount(42)
+                .build_token2022(),
+        ] {
+            let managed = decode_managed_account(&pubkey, &token_account).unwrap();
+            assert_eq!(managed.account_type, ManagedAccountType::TokenAccount);
+            assert_eq!(managed.program_id, token_account.owner.to_string());
+            assert_eq!(managed.mint, Some(mint.to_string()));
+            assert_eq!(managed.amount, Some(42));
+        }
+
+        let nonce_account = AccountMockBuilder::new()
+            .with_owner(SYSTEM_PROGRAM_ID)
+            .with_data(vec![0u8; NONCE_ACCOUNT_LEN as usize])
+            .build();
+        let managed = decode_managed_account(&pubkey, &nonce_account).unwrap();
+        assert_eq!(managed.account_type, ManagedAccountType::NonceAccount);
+        assert_eq!(managed.mint, None);
+
+        // Mints and plain system accounts are not managed accounts
+        assert_eq!(decode_managed_account(&pubkey, &create_mock_spl_mint_account(6)), None);
+        assert_eq!(
+            decode_managed_account(&pubkey, &create_mock_account_with_owner(SYSTEM_PROGRAM_ID)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_program_accounts_request_deserialization() {
+        let request: GetProgramAccountsRequest =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(request.signer_key, None);
+
+        let signer_key = Pubkey::new_unique().to_string();
+        let request: GetProgramAccountsRequest =
+            serde_json::from_value(serde_json::json!({ "signer_key": signer_key })).unwrap();
+        assert_eq!(request.signer_key, Some(signer_key));
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use solana_keychain::SolanaSigner;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use crate::{
+    error::KoraError,
+    state::{get_signer_pool, peek_request_signer},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetSignerPublicKeyResponse {
+    /// Public key to include in the transaction, the payment address when one is configured,
+    /// otherwise the signer that will pay for the next transaction
+    pub pubkey: String,
+    /// Name of that signer in the signers config
+    pub alias: String,
+}
+
+/// The fee payer of the next transaction signed without a signer_key. Unlike `getPayerSigner`,
+/// this doesn't advance the signer selection, with round-robin the next transaction is paid by
+/// the returned signer
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_getSignerPublicKey", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_signer_public_key() -> Result<GetSignerPublicKeyResponse, KoraError> {
+    let config = get_config()?;
+    let signer_pubkey = peek_request_signer()?.pubkey();
+
+    let alias = get_signer_pool()?
+        .get_signer_name(&signer_pubkey)
+        .unwrap_or_else(|| signer_pubkey.to_string());
+    let pubkey = config.kora.get_payment_address(&signer_pubkey)?;
+
+    Ok(GetSignerPublicKeyResponse { pubkey: pubkey.to_string(), alias })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::get_request_signer_with_signer_key,
+        tests::{common::setup_or_get_test_signer, config_mock::ConfigMockBuilder},
+        transaction::TransactionUtil,
+    };
+    use serial_test::serial;
+    use solana_message::Message;
+    use solana_sdk::{hash::Hash, message::VersionedMessage, pubkey::Pubkey, signature::Signature};
+    use solana_system_interface::instruction::transfer;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_signer_public_key_matches_signed_transaction() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let response = get_signer_public_key().await.unwrap();
+        let fee_payer = Pubkey::from_str(&response.pubkey).unwrap();
+        assert_eq!(
+            get_signer_pool().unwrap().get_signer_name(&fee_payer).as_deref(),
+            Some(response.alias.as_str())
+        );
+
+        // The signer picked for the next request signs as the returned fee payer
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[transfer(&fee_payer, &Pubkey::new_unique(), 1)],
+            Some(&fee_payer),
+            &Hash::new_unique(),
+        ));
+        let mut transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+        let signer = get_request_signer_with_signer_key(None).unwrap();
+        transaction.signatures[0] =
+            signer.sign_message(&transaction.message.serialize()).await.unwrap();
+
+        assert_ne!(transaction.signatures[0], Signature::default());
+        assert!(transaction.verify_with_results().iter().all(|verified| *verified));
+        assert_eq!(transaction.message.static_account_keys()[0], signer.pubkey());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_signer_public_key_returns_payment_address() {
+        let payment_address = Pubkey::new_unique();
+        let _m = ConfigMockBuilder::new()
+            .with_payment_address(Some(payment_address.to_string()))
+            .build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+
+        let response = get_signer_public_key().await.unwrap();
+
+        assert_eq!(response.pubkey, payment_address.to_string());
+        assert_eq!(
+            get_signer_pool().unwrap().get_signer_name(&signer_pubkey),
+            Some(response.alias)
+        );
+    }
+}
@@ -7,6 +7,10 @@ pub struct GetSupportedTokensResponse {
     pub tokens: Vec<String>,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getSupportedTokens", user_pubkey = tracing::field::Empty)
+)]
 pub async fn get_supported_tokens() -> Result<GetSupportedTokensResponse, KoraError> {
     let config = &get_config()?;
     let tokens = &config.validation.allowed_tokens;
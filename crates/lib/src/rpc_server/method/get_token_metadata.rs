@@ -0,0 +1,244 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use borsh::BorshDeserialize;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use crate::{
+    error::KoraError,
+    token::token::TokenType,
+    validator::account_validator::{AccountType, METAPLEX_METADATA_PROGRAM_ID},
+    CacheUtil,
+};
+
+/// Key (1 byte), update authority and mint (32 bytes each), followed by the name, symbol and uri
+const METADATA_NAME_OFFSET: usize = 1 + 32 + 32;
+
+/// Metadata of the allowed tokens, kept for `cache.token_metadata_ttl`
+static TOKEN_METADATA_CACHE: Lazy<RwLock<HashMap<Pubkey, (TokenMetadata, Instant)>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TokenMetadata {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    /// Off-chain JSON metadata, usually holding the image URI
+    pub uri: String,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetTokenMetadataResponse {
+    /// Allowed tokens with a Metaplex metadata account, tokens without one are left out
+    pub tokens: Vec<TokenMetadata>,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getTokenMetadata", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_token_metadata(
+    rpc_client: &Arc<RpcClient>,
+) -> Result<GetTokenMetadataResponse, KoraError> {
+    let config = get_config()?;
+    let ttl = Duration::from_secs(config.kora.cache.token_metadata_ttl);
+    let mints = config
+        .validation
+        .allowed_tokens
+        .iter()
+        .map(|mint| {
+            Pubkey::from_str(mint)
+                .map_err(|e| KoraError::InternalServerError(format!("Invalid token {mint}: {e}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut found: HashMap<Pubkey, TokenMetadata> = HashMap::new();
+    {
+        let cache = TOKEN_METADATA_CACHE.read();
+        for mint in &mints {
+            if let Some((metadata, cached_at)) = cache.get(mint) {
+                if cached_at.elapsed() < ttl {
+                    found.insert(*mint, metadata.clone());
+                }
+            }
+        }
+    }
+
+    let missing: Vec<Pubkey> =
+        mints.iter().filter(|mint| !found.contains_key(mint)).copied().collect();
+    if !missing.is_empty() {
+        // Mints first, then their metadata accounts, in a single round-trip
+        let mut pubkeys = missing.clone();
+        pubkeys.extend(missing.iter().map(find_metadata_address));
+        let accounts = CacheUtil::get_accounts(rpc_client, &pubkeys).await?;
+        let (mint_accounts, metadata_accounts) = accounts.split_at(missing.len());
+
+        let mut cache = TOKEN_METADATA_CACHE.write();
+        for ((mint, mint_account), metadata_account) in
+            missing.iter().zip(mint_accounts).zip(metadata_accounts)
+        {
+            let (Some(mint_account), Some(metadata_account)) = (mint_account, metadata_account)
+            else {
+                log::warn!("No mint or metadata account found for token {mint}");
+                continue;
+            };
+
+            match decode_token_metadata(mint, mint_account, metadata_account) {
+                Ok(metadata) => {
+                    if !ttl.is_zero() {
+                        cache.insert(*mint, (metadata.clone(), Instant::now()));
+                    }
+                    found.insert(*mint, metadata);
+                }
+                Err(e) => log::warn!("Failed to decode metadata of token {mint}: {e}"),
+            }
+        }
+    }
+
+    // Keep the order of allowed_tokens
+    let tokens = mints.iter().filter_map(|mint| found.remove(mint)).collect();
+
+    Ok(GetTokenMetadataResponse { tokens })
+}
+
+/// Metaplex metadata PDA of `mint`
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", METAPLEX_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METAPLEX_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+fn decode_token_metadata(
+    mint: &Pubkey,
+    mint_account: &Account,
+    metadata_account: &Account,
+) -> Result<TokenMetadata, KoraError> {
+    let decimals = TokenType::get_token_program_from_owner(&mint_account.owner)?
+        .unpack_mint(mint, &mint_account.data)
+        .map_err(|e| KoraError::TokenOperationError(format!("Failed to unpack mint: {e}")))?
+        .decimals();
+
+    let metadata_address = find_metadata_address(mint);
+    AccountType::NftMetadata.validate_account_type(metadata_account, &metadata_address)?;
+
+    // Metaplex pads the borsh strings with trailing NUL bytes up to their maximum length
+    let mut data = &metadata_account.data[METADATA_NAME_OFFSET..];
+    let mut read_string = || {
+        String::deserialize(&mut data)
+            .map(|value| value.trim_end_matches('\0').to_string())
+            .map_err(|e| KoraError::SerializationError(format!("Invalid metadata account: {e}")))
+    };
+
+    Ok(TokenMetadata {
+        mint: mint.to_string(),
+        name: read_string()?,
+        symbol: read_string()?,
+        uri: read_string()?,
+        decimals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        account_mock::{create_mock_spl_mint_account, create_mock_token_metadata_account},
+        common::RpcMockBuilder,
+        config_mock::ConfigMockBuilder,
+    };
+    use serial_test::serial;
+
+    fn setup(mints: &[Pubkey]) -> std::sync::MutexGuard<'static, ()> {
+        TOKEN_METADATA_CACHE.write().clear();
+        ConfigMockBuilder::new()
+            .with_allowed_tokens(mints.iter().map(|mint| mint.to_string()).collect())
+            .build_and_setup()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_token_metadata() {
+        let mint = Pubkey::new_unique();
+        let _m = setup(&[mint]);
+        let rpc_client = RpcMockBuilder::new()
+            .with_multiple_accounts(&[
+                Some(create_mock_spl_mint_account(6)),
+                Some(create_mock_token_metadata_account(&mint, "USD Coin", "USDC", "https://usdc")),
+            ])
+            .build();
+
+        let response = get_token_metadata(&rpc_client).await.unwrap();
+
+        assert_eq!(
+            response.tokens,
+            vec![TokenMetadata {
+                mint: mint.to_string(),
+                name: "USD Coin".to_string(),
+                symbol: "USDC".to_string(),
+                uri: "https://usdc".to_string(),
+                decimals: 6,
+            }]
+        );
+        assert!(TOKEN_METADATA_CACHE.read().contains_key(&mint));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_token_metadata_partial_results() {
+        let (with_metadata, without_metadata) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let _m = setup(&[with_metadata, without_metadata]);
+        let rpc_client = RpcMockBuilder::new()
+            .with_multiple_accounts(&[
+                Some(create_mock_spl_mint_account(9)),
+                Some(create_mock_spl_mint_account(6)),
+                Some(create_mock_token_metadata_account(&with_metadata, "Token", "TKN", "")),
+                None,
+            ])
+            .build();
+
+        let response = get_token_metadata(&rpc_client).await.unwrap();
+
+        assert_eq!(response.tokens.len(), 1);
+        assert_eq!(response.tokens[0].mint, with_metadata.to_string());
+        assert_eq!(response.tokens[0].symbol, "TKN");
+        assert_eq!(response.tokens[0].decimals, 9);
+        assert!(!TOKEN_METADATA_CACHE.read().contains_key(&without_metadata));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_token_metadata_uses_cache() {
+        let mint = Pubkey::new_unique();
+        let _m = setup(&[mint]);
+        let cached = TokenMetadata {
+            mint: mint.to_string(),
+            name: "Cached".to_string(),
+            symbol: "CCH".to_string(),
+            uri: String::new(),
+            decimals: 2,
+        };
+        TOKEN_METADATA_CACHE.write().insert(mint, (cached.clone(), Instant::now()));
+
+        // The RPC has no accounts, only the cache can answer
+        let rpc_client = RpcMockBuilder::new().with_multiple_accounts(&[None, None]).build();
+
+        let response = get_token_metadata(&rpc_client).await.unwrap();
+        assert_eq!(response.tokens, vec![cached]);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::KoraError;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetVersionResponse {
+    /// Kora crate version
+    pub version: String,
+    /// Git commit hash the server was built from
+    pub commit: String,
+    /// Timestamp of the build
+    pub build_date: String,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "getVersion", user_pubkey = tracing::field::Empty)
+)]
+pub async fn get_version() -> Result<GetVersionResponse, KoraError> {
+    Ok(GetVersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        commit: env!("VERGEN_GIT_SHA").to_string(),
+        build_date: env!("VERGEN_BUILD_TIMESTAMP").to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_version() {
+        let response = get_version().await.unwrap();
+
+        let json = serde_json::to_value(&response).unwrap();
+        let parsed: GetVersionResponse = serde_json::from_value(json).unwrap();
+
+        assert!(!parsed.version.is_empty(), "Version should not be empty");
+        assert_eq!(parsed.version, env!("CARGO_PKG_VERSION"));
+        assert!(!parsed.commit.is_empty(), "Commit should not be empty");
+        assert!(!parsed.build_date.is_empty(), "Build date should not be empty");
+    }
+}
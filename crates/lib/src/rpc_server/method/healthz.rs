@@ -0,0 +1,185 @@
+use crate::{
+    cache::CacheUtil, error::KoraError, signer::KoraSigner, state::get_signer_pool,
+    validator::signer_validator::SIGNING_CAPABILITY_TEST_MESSAGE,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_keychain::SolanaSigner;
+use utoipa::ToSchema;
+
+/// Overall health of the Kora node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Every backend is reachable
+    Ok,
+    /// Requests can still be served, but an optional backend (Redis) is unreachable
+    Degraded,
+    /// The RPC node or every signer is unreachable, requests cannot be served
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct HealthChecks {
+    /// RPC node answered `getHealth`
+    pub rpc: bool,
+    /// Redis answered `PING` (always true when Redis is not configured)
+    pub redis: bool,
+    /// At least one signer in the pool could sign a test message
+    pub signers: bool,
+}
+
+impl HealthChecks {
+    pub fn status(&self) -> HealthStatus {
+        if !self.rpc || !self.signers {
+            HealthStatus::Down
+        } else if !self.redis {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthzResponse {
+    pub status: HealthStatus,
+    pub checks: HealthChecks,
+}
+
+impl HealthzResponse {
+    /// HTTP status returned by the `/healthz` endpoint, 503 only when the node is down
+    pub fn http_status(&self) -> StatusCode {
+        match self.status {
+            HealthStatus::Ok | HealthStatus::Degraded => StatusCode::OK,
+            HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "healthz", user_pubkey = tracing::field::Empty)
+)]
+pub async fn healthz(rpc_client: &RpcClient) -> Result<HealthzResponse, KoraError> {
+    let (rpc, redis, signers) =
+        tokio::join!(check_rpc(rpc_client), CacheUtil::is_healthy(), check_signers());
+
+    let checks = HealthChecks { rpc, redis, signers };
+    Ok(HealthzResponse { status: checks.status(), checks })
+}
+
+async fn check_rpc(rpc_client: &RpcClient) -> bool {
+    match rpc_client.get_health().await {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Health check: RPC node is unhealthy: {e}");
+            false
+        }
+    }
+}
+
+async fn check_signers() -> bool {
+    let pool = match get_signer_pool() {
+        Ok(pool) => pool,
+        Err(e) => {
+            log::warn!("Health check: signer pool unavailable: {e}");
+            return false;
+        }
+    };
+
+    for signer_info in pool.get_signers_info() {
+        let Ok(signer) = pool.get_signer_by_pubkey(&signer_info.public_key) else {
+            continue;
+        };
+
+        // The Solana app only signs transactions confirmed on the device
+        if matches!(*signer, KoraSigner::Ledger(_)) {
+            if signer.is_available().await {
+                return true;
+            }
+            log::warn!("Health check: Ledger signer '{}' is unreachable", signer_info.name);
+            continue;
+        }
+
+        match signer.sign_message(SIGNING_CAPABILITY_TEST_MESSAGE).await {
+            Ok(signature)
+                if signature.verify(signer.pubkey().as_ref(), SIGNING_CAPABILITY_TEST_MESSAGE) =>
+            {
+                return true;
+            }
+            Ok(_) => {
+                log::warn!(
+                    "Health check: signer '{}' returned an invalid signature",
+                    signer_info.name
+                )
+            }
+            Err(e) => log::warn!("Health check: signer '{}' is unreachable: {e}", signer_info.name),
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        common::setup_or_get_test_signer, config_mock::ConfigMockBuilder, rpc_mock::RpcMockBuilder,
+    };
+
+    #[tokio::test]
+    async fn test_healthz_all_backends_healthy() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let rpc_client = RpcMockBuilder::new().with_health().build();
+
+        let response = healthz(&rpc_client).await.unwrap();
+
+        assert_eq!(response.status, HealthStatus::Ok);
+        assert_eq!(response.checks, HealthChecks { rpc: true, redis: true, signers: true });
+        assert_eq!(response.http_status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_healthz_broken_rpc() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let rpc_client = RpcMockBuilder::new().with_unhealthy_rpc().build();
+
+        let response = healthz(&rpc_client).await.unwrap();
+
+        assert_eq!(response.status, HealthStatus::Down);
+        assert!(!response.checks.rpc);
+        assert!(response.checks.signers);
+        assert_eq!(response.http_status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn test_health_checks_status() {
+        let checks = HealthChecks { rpc: true, redis: false, signers: true };
+        assert_eq!(checks.status(), HealthStatus::Degraded);
+
+        let checks = HealthChecks { rpc: true, redis: true, signers: false };
+        assert_eq!(checks.status(), HealthStatus::Down);
+
+        let checks = HealthChecks { rpc: false, redis: false, signers: false };
+        assert_eq!(checks.status(), HealthStatus::Down);
+    }
+
+    #[test]
+    fn test_healthz_response_serialization() {
+        let checks = HealthChecks { rpc: true, redis: false, signers: true };
+        let response = HealthzResponse { status: checks.status(), checks };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "status": "degraded",
+                "checks": { "rpc": true, "redis": false, "signers": true }
+            })
+        );
+    }
+}
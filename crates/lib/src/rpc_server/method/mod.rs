@@ -1,8 +1,25 @@
+pub mod add_signer;
 pub mod estimate_transaction_fee;
+pub mod get_account_info;
 pub mod get_blockhash;
 pub mod get_config;
+pub mod get_dead_letter_queue;
+pub mod get_instruction_template;
+pub mod get_network_status;
 pub mod get_payer_signer;
+pub mod get_program_accounts;
+pub mod get_signer_public_key;
 pub mod get_supported_tokens;
+pub mod get_token_metadata;
+pub mod get_version;
+pub mod healthz;
+#[cfg(feature = "dex")]
+pub mod place_market_order;
 pub mod sign_and_send_transaction;
+pub mod set_log_level;
+pub mod sign_message;
 pub mod sign_transaction;
+pub mod sign_transaction_batch;
+pub mod simulate_transaction;
+pub mod subscribe_transaction_events;
 pub mod transfer_transaction;
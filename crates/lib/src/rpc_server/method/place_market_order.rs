@@ -0,0 +1,232 @@
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_keychain::SolanaSigner;
+use solana_message::Message;
+use solana_sdk::{message::VersionedMessage, pubkey::Pubkey};
+use std::{str::FromStr, sync::Arc};
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use crate::{
+    admin::dex::{DexUtil, OrderSide},
+    rpc_server::method::sign_and_send_transaction::{
+        sign_and_send_transaction, SignAndSendTransactionRequest, SignAndSendTransactionResponse,
+    },
+    state::get_request_signer_with_signer_key,
+    transaction::TransactionUtil,
+    KoraError,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PlaceMarketOrderRequest {
+    /// Address of the OpenBook v2 market
+    pub market: String,
+    pub side: OrderSide,
+    /// Order size in base lots
+    pub size: u64,
+    /// Price limit in quote lots per base lot, the highest price for buys and the lowest for
+    /// sells. Unset places an unbounded market order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<u64>,
+    /// Optional signer signer_key to ensure consistency across related RPC calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+}
+
+/// Place a market order for the Kora signer on an OpenBook v2 market. The signer trades from its
+/// first open orders account, which must already exist, and its associated token accounts.
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_placeMarketOrder", user_pubkey = tracing::field::Empty)
+)]
+pub async fn place_market_order(
+    rpc_client: &Arc<RpcClient>,
+    request: PlaceMarketOrderRequest,
+) -> Result<SignAndSendTransactionResponse, KoraError> {
+    let signer = get_request_signer_with_signer_key(request.signer_key.as_deref())?;
+    let transaction =
+        build_market_order_transaction(rpc_client, &request, &signer.pubkey()).await?;
+
+    sign_and_send_transaction(
+        rpc_client,
+        SignAndSendTransactionRequest {
+            transaction,
+            signer_key: Some(signer.pubkey().to_string()),
+            // The transaction is unsigned until it goes through the signing pipeline
+            sig_verify: false,
+            wait_for_commitment: None,
+            confirmation_timeout_ms: None,
+        },
+    )
+    .await
+}
+
+/// Unsigned base64 transaction placing the order, paid and signed by `owner`
+async fn build_market_order_transaction(
+    rpc_client: &RpcClient,
+    request: &PlaceMarketOrderRequest,
+    owner: &Pubkey,
+) -> Result<String, KoraError> {
+    let market_address = Pubkey::from_str(&request.market).map_err(|e| {
+        KoraError::ValidationError(format!("Invalid market address {}: {e}", request.market))
+    })?;
+
+    let account =
+        rpc_client.get_account(&market_address).await.map_err(|e| match KoraError::from(e) {
+            KoraError::AccountNotFound(_) => KoraError::AccountNotFound(market_address.to_string()),
+            other => other,
+        })?;
+
+    if !get_config()?.validation.allowed_programs.contains(&account.owner.to_string()) {
+        return Err(KoraError::InvalidRequest(format!(
+            "Market program {} is not in allowed_programs",
+            account.owner
+        )));
+    }
+
+    let market = DexUtil::parse_market(&market_address, &account.owner, &account.data)?;
+    let instruction = DexUtil::build_place_order_instruction(
+        &market,
+        owner,
+        request.side,
+        request.size,
+        request.max_price,
+    )?;
+
+    let blockhash =
+        rpc_client.get_latest_blockhash_with_commitment(CommitmentConfig::finalized()).await?;
+    let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+        &[instruction],
+        Some(owner),
+        &blockhash.0,
+    ));
+
+    TransactionUtil::encode_versioned_transaction(
+        &TransactionUtil::new_unsigned_versioned_transaction(message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        admin::dex::test_utils::{market_account_data, mock_market},
+        tests::{
+            common::{setup_or_get_test_signer, RpcMockBuilder},
+            config_mock::ConfigMockBuilder,
+        },
+    };
+    use serial_test::serial;
+    use solana_sdk::account::Account;
+
+    fn request(market: &Pubkey) -> PlaceMarketOrderRequest {
+        PlaceMarketOrderRequest {
+            market: market.to_string(),
+            side: OrderSide::Buy,
+            size: 10,
+            max_price: Some(1_000),
+            signer_key: None,
+        }
+    }
+
+    fn market_rpc_client(program_id: Pubkey, data: Vec<u8>) -> Arc<RpcClient> {
+        RpcMockBuilder::new()
+            .with_account_info(&Account {
+                lamports: 1_000_000,
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            })
+            .build()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_build_market_order_transaction() {
+        let owner = setup_or_get_test_signer();
+        let program_id = Pubkey::new_unique();
+        let market = mock_market(program_id);
+        let _m = ConfigMockBuilder::new()
+            .with_allowed_programs(vec![program_id.to_string()])
+            .build_and_setup();
+        let rpc_client = market_rpc_client(program_id, market_account_data(&market));
+
+        let encoded =
+            build_market_order_transaction(&rpc_client, &request(&market.address), &owner)
+                .await
+                .unwrap();
+
+        let transaction = TransactionUtil::decode_b64_transaction(&encoded).unwrap();
+        let account_keys = transaction.message.static_account_keys();
+        assert_eq!(account_keys[0], owner);
+        assert_eq!(transaction.message.header().num_required_signatures, 1);
+        assert!(account_keys.contains(&market.address));
+        assert!(account_keys.contains(&market.bids));
+        assert!(account_keys.contains(&market.quote_vault));
+
+        let instruction = &transaction.message.instructions()[0];
+        assert_eq!(account_keys[instruction.program_id_index as usize], program_id);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_build_market_order_transaction_rejects_disallowed_program() {
+        let owner = setup_or_get_test_signer();
+        let program_id = Pubkey::new_unique();
+        let market = mock_market(program_id);
+        let _m = ConfigMockBuilder::new().with_allowed_programs(vec![]).build_and_setup();
+        let rpc_client = market_rpc_client(program_id, market_account_data(&market));
+
+        let result =
+            build_market_order_transaction(&rpc_client, &request(&market.address), &owner).await;
+
+        match result {
+            Err(KoraError::InvalidRequest(message)) => {
+                assert!(message.contains("is not in allowed_programs"))
+            }
+            other => panic!("Expected InvalidRequest, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_build_market_order_transaction_rejects_non_market_account() {
+        let owner = setup_or_get_test_signer();
+        let program_id = Pubkey::new_unique();
+        let _m = ConfigMockBuilder::new()
+            .with_allowed_programs(vec![program_id.to_string()])
+            .build_and_setup();
+        let rpc_client = market_rpc_client(program_id, vec![0; 64]);
+
+        let result =
+            build_market_order_transaction(&rpc_client, &request(&Pubkey::new_unique()), &owner)
+                .await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_place_market_order_request_deserialization() {
+        let request: PlaceMarketOrderRequest = serde_json::from_value(serde_json::json!({
+            "market": "market",
+            "side": "sell",
+            "size": 3,
+        }))
+        .unwrap();
+
+        assert_eq!(request.side, OrderSide::Sell);
+        assert_eq!(request.max_price, None);
+
+        assert!(serde_json::from_value::<PlaceMarketOrderRequest>(serde_json::json!({
+            "market": "market",
+            "side": "bid",
+            "size": 3,
+        }))
+        .is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{constant::DEFAULT_LOG_FILTER_OVERRIDES, error::KoraError};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tracing_subscriber::{filter::LevelFilter, reload, EnvFilter, Registry};
+use utoipa::ToSchema;
+
+pub type LogFilterReloadHandle = reload::Handle<EnvFilter, Registry>;
+
+// Set once by the CLI when the tracing subscriber is installed
+static LOG_FILTER_RELOAD_HANDLE: OnceLock<LogFilterReloadHandle> = OnceLock::new();
+
+pub fn init_log_filter_reload_handle(handle: LogFilterReloadHandle) -> Result<(), KoraError> {
+    LOG_FILTER_RELOAD_HANDLE.set(handle).map_err(|_| {
+        KoraError::InternalServerError("Log filter reload handle already initialized".to_string())
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+
+    fn as_log_level_filter(&self) -> log::LevelFilter {
+        match self {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetLogLevelRequest {
+    pub level: LogLevel,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SetLogLevelResponse {
+    /// Most verbose level enabled before the change ("off" when logging was disabled)
+    pub previous_level: String,
+    pub level: LogLevel,
+}
+
+/// Most verbose level currently enabled by the reloadable filter
+pub fn get_current_log_level() -> Result<String, KoraError> {
+    let handle = get_reload_handle()?;
+    handle
+        .with_current(|filter| filter.max_level_hint().unwrap_or(LevelFilter::TRACE).to_string())
+        .map_err(|e| KoraError::InternalServerError(format!("Failed to read log filter: {e}")))
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_setLogLevel", user_pubkey = tracing::field::Empty)
+)]
+pub async fn set_log_level(request: SetLogLevelRequest) -> Result<SetLogLevelResponse, KoraError> {
+    let handle = get_reload_handle()?;
+    let previous_level = get_current_log_level()?;
+
+    let filter =
+        EnvFilter::try_new(format!("{},{DEFAULT_LOG_FILTER_OVERRIDES}", request.level.as_str()))
+            .map_err(|e| KoraError::InternalServerError(format!("Invalid log filter: {e}")))?;
+    handle
+        .reload(filter)
+        .map_err(|e| KoraError::InternalServerError(format!("Failed to reload log filter: {e}")))?;
+
+    // `log` records are bridged to tracing, but the `log` crate applies its own max level first
+    log::set_max_level(request.level.as_log_level_filter());
+
+    log::warn!("Log level changed from {previous_level} to {}", request.level.as_str());
+
+    Ok(SetLogLevelResponse { previous_level, level: request.level })
+}
+
+fn get_reload_handle() -> Result<&'static LogFilterReloadHandle, KoraError> {
+    LOG_FILTER_RELOAD_HANDLE.get().ok_or_else(|| {
+        KoraError::InternalServerError("Log level cannot be changed at runtime".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[tokio::test]
+    async fn test_set_log_level() {
+        let (filter, handle) = reload::Layer::new(EnvFilter::new("info"));
+        // Keep the subscriber alive, the handle only holds a weak reference to the filter
+        let _subscriber =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(filter));
+        init_log_filter_reload_handle(handle).unwrap();
+
+        assert_eq!(get_current_log_level().unwrap(), "info");
+
+        let response = set_log_level(SetLogLevelRequest { level: LogLevel::Debug }).await.unwrap();
+
+        assert_eq!(response.previous_level, "info");
+        assert_eq!(response.level, LogLevel::Debug);
+        assert_eq!(get_current_log_level().unwrap(), "debug");
+        assert_eq!(log::max_level(), log::LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_set_log_level_request_deserialization() {
+        let request: SetLogLevelRequest =
+            serde_json::from_value(serde_json::json!({ "level": "trace" })).unwrap();
+        assert_eq!(request.level, LogLevel::Trace);
+
+        assert!(serde_json::from_value::<SetLogLevelRequest>(
+            serde_json::json!({ "level": "verbose" })
+        )
+        .is_err());
+    }
+}
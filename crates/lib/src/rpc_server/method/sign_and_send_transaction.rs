@@ -1,13 +1,34 @@
-use crate::{rpc_server::middleware_utils::default_sig_verify, usage_limit::UsageTracker};
+use crate::{
+    cache::token_balance_cache::TOKEN_BALANCE_CACHE,
+    config::WebhookEvent,
+    rpc_server::{
+        events::{TransactionEventType, TRANSACTION_EVENT_BROKER},
+        method::sign_transaction::validate_fee_payer,
+        middleware_utils::{default_sig_verify, record_transaction_user},
+    },
+    usage_limit::UsageTracker,
+    webhook::{self, WebhookPayload},
+};
+use dashmap::{mapref::entry::Entry, DashMap};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_keychain::SolanaSigner;
-use std::sync::Arc;
+use solana_sdk::transaction::VersionedTransaction;
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::sync::watch;
 use utoipa::ToSchema;
 
 use crate::{
-    state::get_request_signer_with_signer_key,
-    transaction::{TransactionUtil, VersionedTransactionOps, VersionedTransactionResolved},
+    constant::{
+        CONFIRMATION_POLL_INTERVAL_MS, DEFAULT_CONFIRMATION_TIMEOUT_MS, MAX_CONFIRMATION_TIMEOUT_MS,
+    },
+    signer::KoraSigner,
+    state::get_request_signer_with_guard,
+    transaction::{
+        ConfirmationUtil, SignatureConfirmation, TransactionHash, TransactionUtil,
+        VersionedTransactionOps, VersionedTransactionResolved, WaitForCommitment,
+    },
     KoraError,
 };
 
@@ -20,25 +41,63 @@ pub struct SignAndSendTransactionRequest {
     /// Whether to verify signatures during simulation (defaults to true)
     #[serde(default = "default_sig_verify")]
     pub sig_verify: bool,
+    /// Commitment to poll for before returning, when unset the transaction is confirmed
+    /// with the RPC client's default commitment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for_commitment: Option<WaitForCommitment>,
+    /// How long to wait for `wait_for_commitment` in milliseconds (defaults to 30000, max 120000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation_timeout_ms: Option<u64>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct SignAndSendTransactionResponse {
     pub signed_transaction: String,
     /// Public key of the signer used (for client consistency)
     pub signer_pubkey: String,
+    pub signature: String,
+    /// Whether the transaction reached the requested commitment
+    pub confirmed: bool,
+    /// Slot the transaction landed in, only known when `wait_for_commitment` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Why the transaction is not confirmed (timeout or on-chain error)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "signAndSendTransaction", user_pubkey = tracing::field::Empty)
+)]
 pub async fn sign_and_send_transaction(
     rpc_client: &Arc<RpcClient>,
     request: SignAndSendTransactionRequest,
 ) -> Result<SignAndSendTransactionResponse, KoraError> {
     let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+    record_transaction_user(&transaction);
+    validate_fee_payer(&transaction)?;
 
+    // Retries of a transaction that is still being sent wait for the first attempt instead of
+    // signing and submitting it again
+    PENDING_TRANSACTIONS
+        .coalesce(
+            TransactionUtil::hash_message(&transaction.message),
+            sign_and_send_decoded_transaction(rpc_client, transaction, request),
+        )
+        .await
+}
+
+async fn sign_and_send_decoded_transaction(
+    rpc_client: &Arc<RpcClient>,
+    transaction: VersionedTransaction,
+    request: SignAndSendTransactionRequest,
+) -> Result<SignAndSendTransactionResponse, KoraError> {
     // Check usage limit for transaction sender
     UsageTracker::check_transaction_usage_limit(&transaction).await?;
 
-    let signer = get_request_signer_with_signer_key(request.signer_key.as_deref())?;
+    // Held until the transaction is sent, slow RPC paths keep the signer busy for least-busy
+    let (signer, _in_flight) = get_request_signer_with_guard(request.signer_key.as_deref())?;
 
     let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
         &transaction,
@@ -47,15 +106,181 @@ pub async fn sign_and_send_transaction(
     )
     .await?;
 
-    let (_, signed_transaction) =
-        resolved_transaction.sign_and_send_transaction(&signer, rpc_client).await?;
+    let sent = match request.wait_for_commitment {
+        Some(commitment) => {
+            let timeout = Duration::from_millis(
+                request
+                    .confirmation_timeout_ms
+                    .unwrap_or(DEFAULT_CONFIRMATION_TIMEOUT_MS)
+                    .min(MAX_CONFIRMATION_TIMEOUT_MS),
+            );
+            sign_send_and_wait(&mut resolved_transaction, &signer, rpc_client, commitment, timeout)
+                .await
+        }
+        None => resolved_transaction.sign_and_send_transaction(&signer, rpc_client).await.map(
+            |(signature, signed_transaction)| {
+                // send_and_confirm_transaction only returns once the transaction is confirmed
+                let confirmation = SignatureConfirmation {
+                    confirmed: true,
+                    failed: false,
+                    slot: None,
+                    error: None,
+                };
+                (signature, signed_transaction, confirmation)
+            },
+        ),
+    };
+
+    let (signature, signed_transaction, confirmation) = match sent {
+        Ok(result) => result,
+        Err(e) => {
+            webhook::notify(
+                WebhookPayload::new(WebhookEvent::Failed, &signer.pubkey(), &request.transaction)
+                    .with_error(e.to_string()),
+            );
+            return Err(e);
+        }
+    };
+
+    // Balances of the token accounts Kora just transferred from are stale now
+    match resolved_transaction.get_or_parse_spl_instructions() {
+        Ok(parsed) => TOKEN_BALANCE_CACHE.invalidate_transfer_sources(parsed),
+        Err(e) => log::warn!("Failed to invalidate cached token balances: {e}"),
+    }
+
+    TRANSACTION_EVENT_BROKER.publish_transaction_event(
+        &transaction,
+        &signer.pubkey(),
+        TransactionEventType::Submitted,
+        &signed_transaction,
+        Some(signature.clone()),
+    );
+
+    let mut webhook_payloads =
+        vec![WebhookPayload::new(WebhookEvent::Submitted, &signer.pubkey(), &signed_transaction)];
+    if confirmation.confirmed {
+        webhook_payloads.push(WebhookPayload::new(
+            WebhookEvent::Confirmed,
+            &signer.pubkey(),
+            &signed_transaction,
+        ));
+    } else if confirmation.failed {
+        webhook_payloads.push(
+            WebhookPayload::new(WebhookEvent::Failed, &signer.pubkey(), &signed_transaction)
+                .with_error(confirmation.error.clone().unwrap_or_default()),
+        );
+    }
+    for payload in webhook_payloads {
+        webhook::notify(payload.with_signature(signature.clone()));
+    }
 
     Ok(SignAndSendTransactionResponse {
         signed_transaction,
         signer_pubkey: signer.pubkey().to_string(),
+        signature,
+        confirmed: confirmation.confirmed,
+        slot: confirmation.slot,
+        error: confirmation.error,
     })
 }
 
+type PendingResult = Option<Result<SignAndSendTransactionResponse, KoraError>>;
+
+static PENDING_TRANSACTIONS: Lazy<PendingTxSet> = Lazy::new(PendingTxSet::default);
+
+/// Transactions currently being signed and sent, keyed by message hash. An entry only lives
+/// while its first request is in flight, so a retry after it finished is sent again
+#[derive(Default)]
+struct PendingTxSet {
+    pending: DashMap<TransactionHash, watch::Receiver<PendingResult>>,
+}
+
+impl PendingTxSet {
+    /// Run `send` unless the same transaction is already in flight, in which case return the
+    /// result of that request. Duplicates share the first request's response, including its
+    /// `wait_for_commitment` outcome
+    async fn coalesce<F>(
+        &self,
+        hash: TransactionHash,
+        send: F,
+    ) -> Result<SignAndSendTransactionResponse, KoraError>
+    where
+        F: Future<Output = Result<SignAndSendTransactionResponse, KoraError>>,
+    {
+        let (sender, receiver) = watch::channel(None);
+        let in_flight = match self.pending.entry(hash) {
+            Entry::Occupied(entry) => Some(entry.get().clone()),
+            Entry::Vacant(entry) => {
+                entry.insert(receiver);
+                None
+            }
+        };
+        if let Some(receiver) = in_flight {
+            return Self::wait_for_result(receiver).await;
+        }
+
+        // Removes the entry even if this request is dropped before it finishes
+        let _guard = PendingTxGuard { set: self, hash };
+        let result = send.await;
+        sender.send_replace(Some(result.clone()));
+        result
+    }
+
+    async fn wait_for_result(
+        mut receiver: watch::Receiver<PendingResult>,
+    ) -> Result<SignAndSendTransactionResponse, KoraError> {
+        let cancelled = || {
+            KoraError::InternalServerError(
+                "Identical transaction request was cancelled before it was sent".to_string(),
+            )
+        };
+
+        let result = receiver.wait_for(Option::is_some).await.map_err(|_| cancelled())?;
+        result.clone().unwrap_or_else(|| Err(cancelled()))
+    }
+}
+
+struct PendingTxGuard<'a> {
+    set: &'a PendingTxSet,
+    hash: TransactionHash,
+}
+
+impl Drop for PendingTxGuard<'_> {
+    fn drop(&mut self) {
+        self.set.pending.remove(&self.hash);
+    }
+}
+
+/// Send without the client's built-in confirmation, then poll until `commitment` or `timeout`.
+/// The transaction is already sent when polling fails, so that is reported as unconfirmed.
+async fn sign_send_and_wait(
+    resolved_transaction: &mut VersionedTransactionResolved,
+    signer: &Arc<KoraSigner>,
+    rpc_client: &RpcClient,
+    commitment: WaitForCommitment,
+    timeout: Duration,
+) -> Result<(String, String, SignatureConfirmation), KoraError> {
+    let (signature, signed_transaction) =
+        resolved_transaction.sign_and_send_transaction_unconfirmed(signer, rpc_client).await?;
+
+    let confirmation = ConfirmationUtil::wait_for_signature(
+        rpc_client,
+        &signature,
+        commitment,
+        timeout,
+        Duration::from_millis(CONFIRMATION_POLL_INTERVAL_MS),
+    )
+    .await
+    .unwrap_or_else(|e| SignatureConfirmation {
+        confirmed: false,
+        failed: false,
+        slot: None,
+        error: Some(e.to_string()),
+    });
+
+    Ok((signature.to_string(), signed_transaction, confirmation))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +289,30 @@ mod tests {
         config_mock::ConfigMockBuilder,
         transaction_mock::create_mock_encoded_transaction,
     };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Notify;
+
+    async fn counted_send(
+        sends: Arc<AtomicUsize>,
+        release: Arc<Notify>,
+    ) -> Result<SignAndSendTransactionResponse, KoraError> {
+        let attempt = sends.fetch_add(1, Ordering::SeqCst);
+        release.notified().await;
+        Ok(SignAndSendTransactionResponse {
+            signed_transaction: "signed_transaction".to_string(),
+            signer_pubkey: "signer".to_string(),
+            signature: format!("signature_{attempt}"),
+            confirmed: true,
+            slot: None,
+            error: None,
+        })
+    }
+
+    async fn wait_until_pending(pending: &PendingTxSet, hash: &TransactionHash) {
+        while !pending.pending.contains_key(hash) {
+            tokio::task::yield_now().await;
+        }
+    }
 
     #[tokio::test]
     async fn test_sign_and_send_transaction_decode_error() {
@@ -78,6 +327,8 @@ mod tests {
             transaction: "invalid_base64!@#$".to_string(),
             signer_key: None,
             sig_verify: true,
+            wait_for_commitment: None,
+            confirmation_timeout_ms: None,
         };
 
         let result = sign_and_send_transaction(&rpc_client, request).await;
@@ -98,6 +349,8 @@ mod tests {
             transaction: create_mock_encoded_transaction(),
             signer_key: Some("invalid_pubkey".to_string()),
             sig_verify: true,
+            wait_for_commitment: None,
+            confirmation_timeout_ms: None,
         };
 
         let result = sign_and_send_transaction(&rpc_client, request).await;
@@ -106,4 +359,88 @@ mod tests {
         let error = result.unwrap_err();
         assert!(matches!(error, KoraError::ValidationError(_)), "Should return ValidationError");
     }
+
+    #[test]
+    fn test_sign_and_send_transaction_request_wait_for_commitment() {
+        let request: SignAndSendTransactionRequest = serde_json::from_value(serde_json::json!({
+            "transaction": "tx",
+            "wait_for_commitment": "confirmed",
+            "confirmation_timeout_ms": 5000,
+        }))
+        .unwrap();
+
+        assert_eq!(request.wait_for_commitment, Some(WaitForCommitment::Confirmed));
+        assert_eq!(request.confirmation_timeout_ms, Some(5000));
+
+        let request: SignAndSendTransactionRequest =
+            serde_json::from_value(serde_json::json!({ "transaction": "tx" })).unwrap();
+        assert_eq!(request.wait_for_commitment, None);
+        assert!(request.sig_verify);
+    }
+
+    #[tokio::test]
+    async fn test_pending_tx_set_coalesces_concurrent_duplicates() {
+        let pending = Arc::new(PendingTxSet::default());
+        let sends = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(Notify::new());
+        let hash = [1u8; 32];
+
+        let requests: Vec<_> = (0..5)
+            .map(|_| {
+                let pending = pending.clone();
+                let send = counted_send(sends.clone(), release.clone());
+                tokio::spawn(async move { pending.coalesce(hash, send).await })
+            })
+            .collect();
+
+        wait_until_pending(&pending, &hash).await;
+        // Let every duplicate reach the wait before the first request finishes
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        release.notify_waiters();
+
+        for request in requests {
+            let response = request.await.unwrap().unwrap();
+            assert_eq!(response.signature, "signature_0");
+        }
+        assert_eq!(sends.load(Ordering::SeqCst), 1);
+        assert!(pending.pending.is_empty());
+
+        // A retry after the first request finished is sent again
+        release.notify_one();
+        let response =
+            pending.coalesce(hash, counted_send(sends.clone(), release.clone())).await.unwrap();
+        assert_eq!(response.signature, "signature_1");
+    }
+
+    #[tokio::test]
+    async fn test_pending_tx_set_duplicate_fails_when_first_request_is_dropped() {
+        let pending = Arc::new(PendingTxSet::default());
+        let sends = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(Notify::new());
+        let hash = [2u8; 32];
+
+        let first = tokio::spawn({
+            let pending = pending.clone();
+            let send = counted_send(sends.clone(), release.clone());
+            async move { pending.coalesce(hash, send).await }
+        });
+        wait_until_pending(&pending, &hash).await;
+
+        let duplicate = tokio::spawn({
+            let pending = pending.clone();
+            let send = counted_send(sends.clone(), release.clone());
+            async move { pending.coalesce(hash, send).await }
+        });
+        tokio::task::yield_now().await;
+
+        first.abort();
+        let _ = first.await;
+
+        let result = duplicate.await.unwrap();
+        assert!(matches!(result, Err(KoraError::InternalServerError(_))));
+        assert_eq!(sends.load(Ordering::SeqCst), 1);
+        assert!(pending.pending.is_empty());
+    }
 }
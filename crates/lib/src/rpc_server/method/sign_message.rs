@@ -0,0 +1,219 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_keychain::SolanaSigner;
+use solana_message::VersionedMessage;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use crate::{
+    rpc_server::backpressure::BackpressureController,
+    state::{get_request_signer_with_guard, get_signer_pool},
+    KoraError,
+};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SignMessageRequest {
+    /// Base64 encoded bytes to sign, e.g. a login challenge
+    pub message: String,
+    /// Optional signer signer_key to ensure consistency across related RPC calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SignMessageResponse {
+    /// Base58 encoded ed25519 signature of the message bytes
+    pub signature: String,
+    /// Public key of the signer used (for client consistency)
+    pub signer_pubkey: String,
+}
+
+/// Sign an off-chain message with a fee payer. Payloads that are Solana transaction messages are
+/// rejected unless `sign_message_allow_transaction_bytes` is set, the signature would let them
+/// land on-chain without the validation of `signTransaction`
+#[tracing::instrument(
+    skip_all,
+    fields(method = "signMessage", user_pubkey = tracing::field::Empty)
+)]
+pub async fn sign_message(request: SignMessageRequest) -> Result<SignMessageResponse, KoraError> {
+    let message = STANDARD
+        .decode(&request.message)
+        .map_err(|e| KoraError::InvalidRequest(format!("Failed to decode base64 message: {e}")))?;
+    if message.is_empty() {
+        return Err(KoraError::InvalidRequest("Message is empty".to_string()));
+    }
+
+    let config = get_config()?;
+    if !config.kora.sign_message_allow_transaction_bytes && is_transaction_message(&message) {
+        return Err(KoraError::ValidationError(
+            "Message is a Solana transaction message, use signTransaction to sign transactions"
+                .to_string(),
+        ));
+    }
+
+    let (signer, _in_flight) = get_request_signer_with_guard(request.signer_key.as_deref())?;
+    let signer_pubkey = signer.pubkey();
+
+    // Hold one of the signer's signing slots until it completes
+    let pool = get_signer_pool()?;
+    let _signing_permit = BackpressureController::global()
+        .acquire_signing_permit(
+            &pool,
+            &signer_pubkey,
+            Duration::from_millis(config.kora.backpressure_timeout_ms),
+        )
+        .await?;
+
+    let signature = signer.sign_message(&message).await.map_err(|e| {
+        pool.record_signing_failure(&signer_pubkey, &e, &message);
+        KoraError::SigningError(e.to_string())
+    })?;
+    pool.record_signature(&signer_pubkey);
+
+    Ok(SignMessageResponse {
+        signature: signature.to_string(),
+        signer_pubkey: signer_pubkey.to_string(),
+    })
+}
+
+/// Whether `bytes` deserialize as a valid legacy or v0 transaction message
+fn is_transaction_message(bytes: &[u8]) -> bool {
+    bincode::deserialize::<VersionedMessage>(bytes).is_ok_and(|message| message.sanitize().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        common::setup_or_get_test_signer,
+        config_mock::{ConfigMockBuilder, KoraConfigBuilder},
+    };
+    use serde_json::json;
+    use serial_test::serial;
+    use solana_message::{v0, Message};
+    use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature};
+    use solana_system_interface::instruction::transfer;
+    use std::str::FromStr;
+
+    fn sign_message_request(message: &[u8]) -> SignMessageRequest {
+        SignMessageRequest { message: STANDARD.encode(message), signer_key: None }
+    }
+
+    fn transfer_message(fee_payer: &Pubkey) -> Message {
+        Message::new_with_blockhash(
+            &[transfer(fee_payer, &Pubkey::new_unique(), 1)],
+            Some(fee_payer),
+            &Hash::new_unique(),
+        )
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_message() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+        let challenge = b"example.com wants you to sign in with your Solana account";
+
+        let response = sign_message(sign_message_request(challenge)).await.unwrap();
+
+        assert_eq!(response.signer_pubkey, signer_pubkey.to_string());
+        let signature = Signature::from_str(&response.signature).unwrap();
+        assert!(signature.verify(signer_pubkey.as_ref(), challenge));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_message_with_signer_key() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+
+        let request = SignMessageRequest {
+            message: STANDARD.encode(b"challenge"),
+            signer_key: Some(signer_pubkey.to_string()),
+        };
+        let response = sign_message(request).await.unwrap();
+        assert_eq!(response.signer_pubkey, signer_pubkey.to_string());
+
+        let request = SignMessageRequest {
+            message: STANDARD.encode(b"challenge"),
+            signer_key: Some(Pubkey::new_unique().to_string()),
+        };
+        assert!(matches!(sign_message(request).await, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_message_rejects_transaction_messages() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+
+        let legacy = transfer_message(&signer_pubkey).serialize();
+        let result = sign_message(sign_message_request(&legacy)).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+
+        let v0 = VersionedMessage::V0(
+            v0::Message::try_compile(
+                &signer_pubkey,
+                &[transfer(&signer_pubkey, &Pubkey::new_unique(), 1)],
+                &[],
+                Hash::new_unique(),
+            )
+            .unwrap(),
+        )
+        .serialize();
+        let result = sign_message(sign_message_request(&v0)).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_message_allows_transaction_bytes_when_configured() {
+        let kora_config =
+            KoraConfigBuilder::new().with_sign_message_allow_transaction_bytes(true).build();
+        let _m = ConfigMockBuilder::new().with_kora(kora_config).build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+        let message = transfer_message(&signer_pubkey).serialize();
+
+        let response = sign_message(sign_message_request(&message)).await.unwrap();
+
+        let signature = Signature::from_str(&response.signature).unwrap();
+        assert!(signature.verify(signer_pubkey.as_ref(), &message));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_message_invalid_payload() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let request = SignMessageRequest { message: "not base64!".to_string(), signer_key: None };
+        assert!(matches!(sign_message(request).await, Err(KoraError::InvalidRequest(_))));
+
+        let result = sign_message(sign_message_request(&[])).await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_sign_message_request_response_serialization() {
+        let request: SignMessageRequest =
+            serde_json::from_value(json!({ "message": "aGVsbG8=" })).unwrap();
+        assert_eq!(request.message, "aGVsbG8=");
+        assert_eq!(request.signer_key, None);
+
+        let response = SignMessageResponse {
+            signature: Signature::default().to_string(),
+            signer_pubkey: Pubkey::default().to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({
+                "signature": Signature::default().to_string(),
+                "signer_pubkey": Pubkey::default().to_string(),
+            })
+        );
+    }
+}
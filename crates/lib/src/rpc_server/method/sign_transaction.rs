@@ -1,16 +1,32 @@
 use crate::{
-    rpc_server::middleware_utils::default_sig_verify,
-    state::get_request_signer_with_signer_key,
-    transaction::{TransactionUtil, VersionedTransactionOps, VersionedTransactionResolved},
+    config::WebhookEvent,
+    rpc_server::{
+        events::{TransactionEventType, TRANSACTION_EVENT_BROKER},
+        middleware_utils::{default_sig_verify, record_transaction_user},
+    },
+    state::{get_request_signer_with_guard, get_signer_pool},
+    transaction::{
+        LookupTableUtil, TransactionUtil, VersionedTransactionOps, VersionedTransactionResolved,
+    },
     usage_limit::UsageTracker,
+    webhook::{self, WebhookPayload},
     KoraError,
 };
 use serde::{Deserialize, Serialize};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_commitment_config::CommitmentConfig;
 use solana_keychain::SolanaSigner;
+use solana_message::VersionedMessage;
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
 use std::sync::Arc;
 use utoipa::ToSchema;
 
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SignTransactionRequest {
     pub transaction: String,
@@ -27,45 +43,220 @@ pub struct SignTransactionResponse {
     pub signed_transaction: String,
     /// Public key of the signer used (for client consistency)
     pub signer_pubkey: String,
+    /// Required signers that still don't have a valid signature after Kora signed
+    pub missing_signers: Vec<String>,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "signTransaction", user_pubkey = tracing::field::Empty)
+)]
 pub async fn sign_transaction(
     rpc_client: &Arc<RpcClient>,
     request: SignTransactionRequest,
 ) -> Result<SignTransactionResponse, KoraError> {
     let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+    record_transaction_user(&transaction);
+    validate_fee_payer(&transaction)?;
 
     // Check usage limit for transaction sender
     UsageTracker::check_transaction_usage_limit(&transaction).await?;
 
-    let signer = get_request_signer_with_signer_key(request.signer_key.as_deref())?;
+    let (signer, _in_flight) = get_request_signer_with_guard(request.signer_key.as_deref())?;
+
+    let config = get_config()?;
+    if config.kora.preflight_simulate {
+        preflight_simulate(
+            rpc_client,
+            &transaction,
+            config.kora.get_preflight_simulation_commitment()?,
+        )
+        .await?;
+    }
 
-    let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
-        &transaction,
-        rpc_client,
-        request.sig_verify,
-    )
-    .await?;
+    let mut resolved_transaction =
+        resolve_transaction(&transaction, rpc_client, request.sig_verify).await?;
 
     let (signed_transaction, _) =
         resolved_transaction.sign_transaction(&signer, rpc_client).await?;
 
     let encoded = TransactionUtil::encode_versioned_transaction(&signed_transaction)?;
 
+    TRANSACTION_EVENT_BROKER.publish_transaction_event(
+        &signed_transaction,
+        &signer.pubkey(),
+        TransactionEventType::Signed,
+        &encoded,
+        None,
+    );
+    webhook::notify(WebhookPayload::new(WebhookEvent::Signed, &signer.pubkey(), &encoded));
+
     Ok(SignTransactionResponse {
         signed_transaction: encoded,
         signer_pubkey: signer.pubkey().to_string(),
+        missing_signers: get_missing_signers(&signed_transaction),
     })
 }
 
+/// Resolve the lookup table addresses and inner instructions of a client transaction
+pub async fn resolve_transaction(
+    transaction: &VersionedTransaction,
+    rpc_client: &RpcClient,
+    sig_verify: bool,
+) -> Result<VersionedTransactionResolved, KoraError> {
+    match expand_lookup_tables(transaction, rpc_client).await? {
+        Some(lookup_addresses) => {
+            VersionedTransactionResolved::from_transaction_with_lookup_addresses(
+                transaction,
+                rpc_client,
+                sig_verify,
+                lookup_addresses,
+            )
+            .await
+        }
+        None => {
+            VersionedTransactionResolved::from_transaction(transaction, rpc_client, sig_verify)
+                .await
+        }
+    }
+}
+
+/// V0 messages built against lookup tables the client never loaded reference account indexes
+/// past the static keys. Fetch all of their lookup tables with one `getMultipleAccounts` call
+/// and check the expanded account list covers every index, `None` when no expansion is needed.
+pub async fn expand_lookup_tables(
+    transaction: &VersionedTransaction,
+    rpc_client: &RpcClient,
+) -> Result<Option<Vec<Pubkey>>, KoraError> {
+    let VersionedMessage::V0(message) = &transaction.message else {
+        return Ok(None);
+    };
+
+    let max_index = message
+        .instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter().chain(std::iter::once(&ix.program_id_index)))
+        .max()
+        .map_or(0, |&index| index as usize);
+    if max_index < message.account_keys.len() {
+        return Ok(None);
+    }
+
+    LookupTableUtil::validate_lookup_table_account_count(&message.address_table_lookups)?;
+
+    let table_addresses: Vec<Pubkey> =
+        message.address_table_lookups.iter().map(|lookup| lookup.account_key).collect();
+    let lookup_tables = LookupTableUtil::fetch_lookup_tables(rpc_client, &table_addresses).await?;
+    let lookup_addresses = LookupTableUtil::resolve_addresses_from_tables(
+        &message.address_table_lookups,
+        &lookup_tables,
+    )?;
+
+    let account_count = message.account_keys.len() + lookup_addresses.len();
+    if max_index >= account_count {
+        return Err(KoraError::InvalidTransaction(format!(
+            "Instruction references account index {max_index}, but the transaction only has {account_count} accounts including lookup tables"
+        )));
+    }
+
+    Ok(Some(lookup_addresses))
+}
+
+/// Simulate the transaction without verifying signatures, Kora hasn't signed it yet, and reject
+/// it with the program logs when it would fail on-chain
+pub async fn preflight_simulate(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+    commitment: CommitmentConfig,
+) -> Result<(), KoraError> {
+    let simulation_result = rpc_client
+        .simulate_transaction_with_config(
+            transaction,
+            RpcSimulateTransactionConfig {
+                commitment: Some(commitment),
+                sig_verify: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| KoraError::RpcError(format!("Failed to simulate transaction: {e}")))?;
+
+    if let Some(err) = simulation_result.value.err {
+        log::warn!("Pre-flight simulation failed: {err}");
+        return Err(KoraError::TransactionSimulationFailed {
+            logs: simulation_result.value.logs.unwrap_or_default(),
+        });
+    }
+
+    Ok(())
+}
+
+/// With `strict_fee_payer` enabled, reject transactions whose fee payer is set to a key that
+/// isn't one of Kora's signers instead of signing them anyway
+pub fn validate_fee_payer(transaction: &VersionedTransaction) -> Result<(), KoraError> {
+    if !get_config()?.validation.strict_fee_payer {
+        return Ok(());
+    }
+
+    let fee_payer = match transaction.message.static_account_keys().first() {
+        Some(fee_payer) if *fee_payer != Pubkey::default() => fee_payer,
+        _ => return Ok(()),
+    };
+
+    if !get_signer_pool()?.contains_signer(fee_payer) {
+        return Err(KoraError::ValidationError(
+            "Fee payer already set to an unknown key".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Required signers of the message whose signature slot is empty or doesn't verify
+pub fn get_missing_signers(transaction: &VersionedTransaction) -> Vec<String> {
+    let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+    let message_bytes = transaction.message.serialize();
+
+    transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .take(num_required_signatures)
+        .enumerate()
+        .filter(|(index, pubkey)| {
+            !transaction
+                .signatures
+                .get(*index)
+                .is_some_and(|signature| signature.verify(pubkey.as_ref(), &message_bytes))
+        })
+        .map(|(_, pubkey)| pubkey.to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tests::{
+        account_mock::AccountMockBuilder,
         common::{setup_or_get_test_signer, setup_or_get_test_usage_limiter, RpcMockBuilder},
         config_mock::ConfigMockBuilder,
         transaction_mock::create_mock_encoded_transaction,
     };
+    use serde_json::json;
+    use solana_address_lookup_table_interface::state::{AddressLookupTable, LookupTableMeta};
+    use solana_client::rpc_request::RpcRequest;
+    use solana_message::{
+        compiled_instruction::CompiledInstruction,
+        v0::{self, MessageAddressTableLookup},
+        Message, MessageHeader,
+    };
+    use solana_sdk::{
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+    use solana_system_interface::instruction::transfer;
+    use tracing_test::traced_test;
 
     #[tokio::test]
     async fn test_sign_transaction_decode_error() {
@@ -108,4 +299,252 @@ mod tests {
         let error = result.unwrap_err();
         assert!(matches!(error, KoraError::ValidationError(_)), "Should return ValidationError");
     }
+
+    fn failed_simulation_rpc_client() -> Arc<RpcClient> {
+        Arc::new(
+            RpcMockBuilder::new()
+                .with_custom_mock(
+                    RpcRequest::SimulateTransaction,
+                    json!({
+                        "context": { "slot": 1 },
+                        "value": {
+                            "err": { "InstructionError": [0, { "Custom": 1 }] },
+                            "logs": [
+                                "Program 11111111111111111111111111111111 invoke [1]",
+                                "Transfer: insufficient lamports 0, need 1000000000",
+                                "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                            ],
+                            "accounts": null,
+                            "unitsConsumed": 150
+                        }
+                    }),
+                )
+                .build(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_preflight_simulation_failure() {
+        let _m = ConfigMockBuilder::new().with_preflight_simulate(true).build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let _ = setup_or_get_test_usage_limiter().await;
+        let rpc_client = failed_simulation_rpc_client();
+
+        let request = SignTransactionRequest {
+            transaction: create_mock_encoded_transaction(),
+            signer_key: None,
+            sig_verify: false,
+        };
+        let error = sign_transaction(&rpc_client, request).await.unwrap_err();
+
+        let KoraError::TransactionSimulationFailed { logs } = error else {
+            panic!("expected a failed simulation, got {error:?}");
+        };
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[1], "Transfer: insufficient lamports 0, need 1000000000");
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_preflight_simulation_disabled() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let _ = setup_or_get_test_usage_limiter().await;
+        let rpc_client = failed_simulation_rpc_client();
+
+        let request = SignTransactionRequest {
+            transaction: create_mock_encoded_transaction(),
+            signer_key: None,
+            sig_verify: false,
+        };
+        let error = sign_transaction(&rpc_client, request).await.unwrap_err();
+
+        // Still rejected when resolving inner instructions, without the program logs
+        assert!(matches!(error, KoraError::InvalidTransaction(_)), "got {error:?}");
+    }
+
+    #[test]
+    fn test_validate_fee_payer_default_allows_unknown_fee_payer() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let transaction =
+            create_two_signer_transaction(&Pubkey::new_unique(), &Pubkey::new_unique());
+
+        assert!(validate_fee_payer(&transaction).is_ok());
+    }
+
+    #[test]
+    fn test_validate_fee_payer_strict() {
+        let _m = ConfigMockBuilder::new().with_strict_fee_payer(true).build_and_setup();
+        let kora_signer = setup_or_get_test_signer();
+        let user = Pubkey::new_unique();
+
+        let transaction = create_two_signer_transaction(&Pubkey::new_unique(), &user);
+        assert_eq!(
+            validate_fee_payer(&transaction).unwrap_err(),
+            KoraError::ValidationError("Fee payer already set to an unknown key".to_string())
+        );
+
+        let transaction = create_two_signer_transaction(&kora_signer, &user);
+        assert!(validate_fee_payer(&transaction).is_ok());
+
+        // An unset (all zero) fee payer is left for Kora to fill in
+        let transaction = create_two_signer_transaction(&Pubkey::default(), &user);
+        assert!(validate_fee_payer(&transaction).is_ok());
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_sign_transaction_span_records_method_and_user() {
+        let _m = ConfigMockBuilder::new().with_strict_fee_payer(true).build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+        let user = Pubkey::new_unique();
+
+        // Rejected by the fee payer check, after the user is recorded on the span
+        let transaction = create_two_signer_transaction(&Pubkey::new_unique(), &user);
+        let request = SignTransactionRequest {
+            transaction: TransactionUtil::encode_versioned_transaction(&transaction).unwrap(),
+            signer_key: None,
+            sig_verify: false,
+        };
+        assert!(sign_transaction(&rpc_client, request).await.is_err());
+
+        assert!(logs_contain("sign_transaction{"));
+        assert!(logs_contain("method=\"signTransaction\""));
+        assert!(logs_contain(&format!("user_pubkey={user}")));
+    }
+
+    fn create_two_signer_transaction(fee_payer: &Pubkey, user: &Pubkey) -> VersionedTransaction {
+        let instruction = transfer(user, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(fee_payer)));
+        TransactionUtil::new_unsigned_versioned_transaction(message)
+    }
+
+    #[test]
+    fn test_get_missing_signers_after_fee_payer_signs() {
+        let fee_payer = Keypair::new();
+        let user = Keypair::new();
+        let mut transaction = create_two_signer_transaction(&fee_payer.pubkey(), &user.pubkey());
+
+        transaction.signatures[0] = fee_payer.sign_message(&transaction.message.serialize());
+
+        assert_eq!(get_missing_signers(&transaction), vec![user.pubkey().to_string()]);
+    }
+
+    #[test]
+    fn test_get_missing_signers_all_signed() {
+        let fee_payer = Keypair::new();
+        let user = Keypair::new();
+        let mut transaction = create_two_signer_transaction(&fee_payer.pubkey(), &user.pubkey());
+
+        let message_bytes = transaction.message.serialize();
+        transaction.signatures[0] = fee_payer.sign_message(&message_bytes);
+        transaction.signatures[1] = user.sign_message(&message_bytes);
+
+        assert!(get_missing_signers(&transaction).is_empty());
+    }
+
+    #[test]
+    fn test_get_missing_signers_rejects_invalid_signature() {
+        let fee_payer = Keypair::new();
+        let user = Keypair::new();
+        let mut transaction = create_two_signer_transaction(&fee_payer.pubkey(), &user.pubkey());
+
+        let message_bytes = transaction.message.serialize();
+        transaction.signatures[0] = fee_payer.sign_message(&message_bytes);
+        // Signed by the wrong key, so it doesn't count as the user's signature
+        transaction.signatures[1] = Keypair::new().sign_message(&message_bytes);
+
+        assert_eq!(get_missing_signers(&transaction), vec![user.pubkey().to_string()]);
+    }
+
+    #[test]
+    fn test_get_missing_signers_unsigned() {
+        let fee_payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let transaction = create_two_signer_transaction(&fee_payer, &user);
+
+        assert_eq!(
+            get_missing_signers(&transaction),
+            vec![fee_payer.to_string(), user.to_string()]
+        );
+    }
+
+    fn create_lookup_table_account(addresses: Vec<Pubkey>) -> solana_sdk::account::Account {
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta {
+                deactivation_slot: u64::MAX,
+                last_extended_slot: 0,
+                last_extended_slot_start_index: 0,
+                authority: Some(Pubkey::new_unique()),
+                _padding: 0,
+            },
+            addresses: addresses.into(),
+        };
+        AccountMockBuilder::new().with_data(lookup_table.serialize_for_tests().unwrap()).build()
+    }
+
+    /// V0 transfer to a recipient that is only in the lookup table at `lookup_index`
+    fn create_lookup_table_transaction(
+        lookup_table: &Pubkey,
+        lookup_index: u8,
+    ) -> VersionedTransaction {
+        let message = v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![Pubkey::new_unique(), solana_system_interface::program::ID],
+            recent_blockhash: Hash::new_unique(),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0, 2],
+                data: vec![2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0],
+            }],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: *lookup_table,
+                writable_indexes: vec![lookup_index],
+                readonly_indexes: vec![],
+            }],
+        };
+        TransactionUtil::new_unsigned_versioned_transaction(VersionedMessage::V0(message))
+    }
+
+    #[tokio::test]
+    async fn test_expand_lookup_tables() {
+        let lookup_table = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let rpc_client = RpcMockBuilder::new()
+            .with_multiple_accounts(&[Some(create_lookup_table_account(vec![
+                Pubkey::new_unique(),
+                recipient,
+            ]))])
+            .build();
+
+        let transaction = create_lookup_table_transaction(&lookup_table, 1);
+        let expanded = expand_lookup_tables(&transaction, &rpc_client).await.unwrap();
+        assert_eq!(expanded, Some(vec![recipient]));
+
+        // Index 2 is past the end of the lookup table
+        let transaction = create_lookup_table_transaction(&lookup_table, 2);
+        let result = expand_lookup_tables(&transaction, &rpc_client).await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+
+        // Legacy messages never reference lookup tables
+        let legacy = TransactionUtil::new_unsigned_versioned_transaction(VersionedMessage::Legacy(
+            Message::new(&[transfer(&recipient, &Pubkey::new_unique(), 1)], Some(&recipient)),
+        ));
+        assert_eq!(expand_lookup_tables(&legacy, &rpc_client).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_expand_lookup_tables_missing_table() {
+        let rpc_client = RpcMockBuilder::new().with_multiple_accounts(&[None]).build();
+        let transaction = create_lookup_table_transaction(&Pubkey::new_unique(), 0);
+
+        let err = expand_lookup_tables(&transaction, &rpc_client).await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "Unexpected error: {err}");
+    }
 }
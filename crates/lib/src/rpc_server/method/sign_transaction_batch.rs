@@ -0,0 +1,291 @@
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use crate::{
+    rpc_server::{
+        method::sign_transaction::{
+            sign_transaction, SignTransactionRequest, SignTransactionResponse,
+        },
+        middleware_utils::default_sig_verify,
+    },
+    KoraError,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SignTransactionBatchRequest {
+    /// Base64 encoded transactions, at most `max_batch_size` of them
+    pub transactions: Vec<String>,
+    /// Optional signer signer_key, signs every transaction of the batch instead of drawing fee
+    /// payers from the pool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+    /// Whether to verify signatures during simulation (defaults to true)
+    #[serde(default = "default_sig_verify")]
+    pub sig_verify: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignTransactionBatchResponse {
+    /// One result per transaction, in the order of the request
+    pub results: Vec<SignTransactionBatchResult>,
+}
+
+/// Either the signed transaction, with the same fields as a `signTransaction` response, or the
+/// error that rejected it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignTransactionBatchResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_transaction: Option<String>,
+    /// Public key of the signer used (for client consistency)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_pubkey: Option<String>,
+    /// Required signers that still don't have a valid signature after Kora signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_signers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SignTransactionBatchError>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignTransactionBatchError {
+    /// Same code as the `data.code` of a failed `signTransaction` call
+    pub code: String,
+    pub message: String,
+}
+
+impl From<Result<SignTransactionResponse, KoraError>> for SignTransactionBatchResult {
+    fn from(result: Result<SignTransactionResponse, KoraError>) -> Self {
+        match result {
+            Ok(response) => Self {
+                signed_transaction: Some(response.signed_transaction),
+                signer_pubkey: Some(response.signer_pubkey),
+                missing_signers: Some(response.missing_signers),
+                error: None,
+            },
+            Err(e) => Self {
+                signed_transaction: None,
+                signer_pubkey: None,
+                missing_signers: None,
+                error: Some(SignTransactionBatchError {
+                    code: e.error_code().to_string(),
+                    message: e.to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// Sign each transaction of the batch as `signTransaction` would. Every transaction draws its own
+/// fee payer from the pool, so the selection strategy spreads the batch over the signers, and a
+/// rejected transaction only fails its own result
+#[tracing::instrument(skip_all, fields(method = "signTransactionBatch"))]
+pub async fn sign_transaction_batch(
+    rpc_client: &Arc<RpcClient>,
+    request: SignTransactionBatchRequest,
+) -> Result<SignTransactionBatchResponse, KoraError> {
+    if request.transactions.is_empty() {
+        return Err(KoraError::InvalidRequest("Batch contains no transactions".to_string()));
+    }
+
+    let max_batch_size = get_config()?.kora.max_batch_size;
+    if request.transactions.len() > max_batch_size {
+        return Err(KoraError::InvalidRequest(format!(
+            "Batch of {} transactions exceeds max_batch_size of {max_batch_size}",
+            request.transactions.len()
+        )));
+    }
+
+    let results = join_all(request.transactions.into_iter().map(|transaction| {
+        sign_transaction(
+            rpc_client,
+            SignTransactionRequest {
+                transaction,
+                signer_key: request.signer_key.clone(),
+                sig_verify: request.sig_verify,
+            },
+        )
+    }))
+    .await;
+
+    Ok(SignTransactionBatchResponse {
+        results: results.into_iter().map(SignTransactionBatchResult::from).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constant::error_codes,
+        fee::price::PriceModel,
+        tests::{
+            common::{setup_or_get_test_signer, setup_or_get_test_usage_limiter, RpcMockBuilder},
+            config_mock::{ConfigMockBuilder, KoraConfigBuilder},
+            transaction_mock::TransactionMockBuilder,
+        },
+        transaction::TransactionUtil,
+    };
+    use serde_json::json;
+    use serial_test::serial;
+    use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+    fn batch_request(transactions: Vec<String>) -> SignTransactionBatchRequest {
+        SignTransactionBatchRequest { transactions, signer_key: None, sig_verify: false }
+    }
+
+    /// Mock client whose simulations succeed without inner instructions
+    fn batch_rpc_client() -> Arc<RpcClient> {
+        RpcMockBuilder::new().with_simulation(1_000).with_fee_estimate(5_000).build()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_transaction_batch_mixed_results() {
+        let _m = ConfigMockBuilder::new().with_price_model(PriceModel::Free).build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+        let _ = setup_or_get_test_usage_limiter().await;
+        let rpc_client = batch_rpc_client();
+
+        let valid = TransactionMockBuilder::new()
+            .with_fee_payer(signer_pubkey)
+            .with_sol_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000)
+            .build_encoded();
+        let disallowed_program = TransactionMockBuilder::new()
+            .with_fee_payer(signer_pubkey)
+            .with_instruction(Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]))
+            .build_encoded();
+
+        let response = sign_transaction_batch(
+            &rpc_client,
+            batch_request(vec![
+                valid.clone(),
+                "invalid_base64!@#$".to_string(),
+                disallowed_program,
+                valid,
+            ]),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.results.len(), 4);
+        for index in [0, 3] {
+            let result = &response.results[index];
+            assert!(result.error.is_none(), "transaction {index} failed: {:?}", result.error);
+            assert_eq!(result.signer_pubkey, Some(signer_pubkey.to_string()));
+
+            let signed = TransactionUtil::decode_b64_transaction(
+                result.signed_transaction.as_ref().unwrap(),
+            )
+            .unwrap();
+            let message_bytes = signed.message.serialize();
+            assert!(signed.signatures[0].verify(signer_pubkey.as_ref(), &message_bytes));
+            // The transfer sender still has to sign
+            assert_eq!(result.missing_signers.as_ref().unwrap().len(), 1);
+        }
+
+        let decode_error = response.results[1].error.as_ref().unwrap();
+        assert!(response.results[1].signed_transaction.is_none());
+        assert_eq!(decode_error.code, error_codes::TRANSACTION_INVALID);
+
+        let program_error = response.results[2].error.as_ref().unwrap();
+        assert!(response.results[2].signed_transaction.is_none());
+        assert!(
+            program_error.message.contains("not in the allowed list"),
+            "{}",
+            program_error.message
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_transaction_batch_with_signer_key() {
+        let _m = ConfigMockBuilder::new().with_price_model(PriceModel::Free).build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+        let _ = setup_or_get_test_usage_limiter().await;
+        let rpc_client = batch_rpc_client();
+
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(signer_pubkey)
+            .with_sol_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000)
+            .build_encoded();
+        let request = SignTransactionBatchRequest {
+            transactions: vec![transaction.clone(), transaction],
+            signer_key: Some(Pubkey::new_unique().to_string()),
+            sig_verify: false,
+        };
+
+        let response = sign_transaction_batch(&rpc_client, request).await.unwrap();
+
+        // An unknown signer key fails every transaction, not the request
+        assert_eq!(response.results.len(), 2);
+        for result in response.results {
+            assert!(result.signed_transaction.is_none());
+            assert_eq!(result.error.unwrap().code, error_codes::VALIDATION_FAILED);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_transaction_batch_size_limits() {
+        let kora_config = KoraConfigBuilder::new().with_max_batch_size(2).build();
+        let _m = ConfigMockBuilder::new().with_kora(kora_config).build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let rpc_client = batch_rpc_client();
+
+        let result = sign_transaction_batch(&rpc_client, batch_request(vec![])).await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+
+        let transactions = vec!["invalid_base64!@#$".to_string(); 3];
+        let error =
+            sign_transaction_batch(&rpc_client, batch_request(transactions)).await.unwrap_err();
+        assert_eq!(
+            error,
+            KoraError::InvalidRequest(
+                "Batch of 3 transactions exceeds max_batch_size of 2".to_string()
+            )
+        );
+
+        let transactions = vec!["invalid_base64!@#$".to_string(); 2];
+        let response =
+            sign_transaction_batch(&rpc_client, batch_request(transactions)).await.unwrap();
+        assert_eq!(response.results.len(), 2);
+    }
+
+    #[test]
+    fn test_sign_transaction_batch_result_serialization() {
+        let signed = SignTransactionBatchResult::from(Ok(SignTransactionResponse {
+            signed_transaction: "AQ==".to_string(),
+            signer_pubkey: Pubkey::default().to_string(),
+            missing_signers: vec![],
+        }));
+        assert_eq!(
+            serde_json::to_value(signed).unwrap(),
+            json!({
+                "signed_transaction": "AQ==",
+                "signer_pubkey": Pubkey::default().to_string(),
+                "missing_signers": [],
+            })
+        );
+
+        let failed = SignTransactionBatchResult::from(Err(KoraError::InvalidTransaction(
+            "bad transaction".to_string(),
+        )));
+        assert_eq!(
+            serde_json::to_value(failed).unwrap(),
+            json!({
+                "error": {
+                    "code": error_codes::TRANSACTION_INVALID,
+                    "message": KoraError::InvalidTransaction("bad transaction".to_string())
+                        .to_string(),
+                }
+            })
+        );
+    }
+}
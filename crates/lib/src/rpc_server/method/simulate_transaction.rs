@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig,
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_keychain::SolanaSigner;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::{
+    rpc_server::{
+        method::sign_transaction::{resolve_transaction, validate_fee_payer},
+        middleware_utils::record_transaction_user,
+    },
+    state::{get_request_signer_with_signer_key, peek_request_signer},
+    transaction::{TransactionUtil, VersionedTransactionOps},
+    KoraError,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimulateTransactionRequest {
+    pub transaction: String,
+    /// Optional signer signer_key to ensure consistency across related RPC calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulateTransactionResponse {
+    /// Compute units consumed by the simulation, a precise `compute_unit_limit` for the
+    /// transaction
+    pub units_consumed: Option<u64>,
+    /// Program logs of the simulation
+    pub logs: Vec<String>,
+    /// Why the transaction failed, unset when it would succeed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Public key of the signer used as fee payer (for client consistency)
+    pub signer_pubkey: String,
+}
+
+impl SimulateTransactionResponse {
+    fn from_simulation(simulation: RpcSimulateTransactionResult, signer_pubkey: &Pubkey) -> Self {
+        Self {
+            units_consumed: simulation.units_consumed,
+            logs: simulation.logs.unwrap_or_default(),
+            error: simulation.err.map(|err| err.to_string()),
+            signer_pubkey: signer_pubkey.to_string(),
+        }
+    }
+}
+
+/// Dry run of `signAndSendTransaction`: validate the transaction and its fee payment as signing
+/// would, then simulate it with the fee payer Kora would sign as. Nothing is signed or sent, and
+/// transactions rejected by validation are never simulated
+#[tracing::instrument(
+    skip_all,
+    fields(method = "kora_simulateTransaction", user_pubkey = tracing::field::Empty)
+)]
+pub async fn simulate_transaction(
+    rpc_client: &Arc<RpcClient>,
+    request: SimulateTransactionRequest,
+) -> Result<SimulateTransactionResponse, KoraError> {
+    let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+    record_transaction_user(&transaction);
+    validate_fee_payer(&transaction)?;
+
+    // Simulating must not advance the pool's selection strategy for the signing call that follows
+    let signer = match request.signer_key.as_deref() {
+        Some(signer_key) => get_request_signer_with_signer_key(Some(signer_key))?,
+        None => peek_request_signer()?,
+    };
+    let fee_payer = signer.pubkey();
+
+    // Kora hasn't signed yet, so signatures are never verified
+    let mut resolved_transaction = resolve_transaction(&transaction, rpc_client, false).await?;
+    let transaction = resolved_transaction.validate_for_signing(&fee_payer, rpc_client).await?;
+
+    let simulation = rpc_client
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                commitment: Some(rpc_client.commitment()),
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| KoraError::RpcError(format!("Failed to simulate transaction: {e}")))?;
+
+    Ok(SimulateTransactionResponse::from_simulation(simulation.value, &fee_payer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fee::price::PriceModel,
+        tests::{
+            common::{setup_or_get_test_signer, RpcMockBuilder},
+            config_mock::ConfigMockBuilder,
+            transaction_mock::TransactionMockBuilder,
+        },
+    };
+    use serde_json::json;
+    use serial_test::serial;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::instruction::Instruction;
+
+    const PROGRAM_LOGS: [&str; 2] = [
+        "Program 11111111111111111111111111111111 invoke [1]",
+        "Program 11111111111111111111111111111111 success",
+    ];
+
+    fn simulation_rpc_client(units_consumed: u64) -> Arc<RpcClient> {
+        RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::SimulateTransaction,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "err": null,
+                        "logs": PROGRAM_LOGS,
+                        "accounts": null,
+                        "unitsConsumed": units_consumed,
+                        "innerInstructions": []
+                    }
+                }),
+            )
+            .with_fee_estimate(5_000)
+            .build()
+    }
+
+    fn simulate_request(transaction: String) -> SimulateTransactionRequest {
+        SimulateTransactionRequest { transaction, signer_key: None }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_simulate_transaction_returns_units_and_logs() {
+        let _m = ConfigMockBuilder::new().with_price_model(PriceModel::Free).build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+        let rpc_client = simulation_rpc_client(2_350);
+
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(signer_pubkey)
+            .with_sol_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000)
+            .build_encoded();
+        let response = simulate_transaction(&rpc_client, simulate_request(transaction)).await;
+
+        let response = response.unwrap();
+        assert_eq!(response.units_consumed, Some(2_350));
+        assert_eq!(response.logs, PROGRAM_LOGS);
+        assert_eq!(response.error, None);
+        assert_eq!(response.signer_pubkey, signer_pubkey.to_string());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_simulate_transaction_validation_failure() {
+        let _m = ConfigMockBuilder::new().with_price_model(PriceModel::Free).build_and_setup();
+        let signer_pubkey = setup_or_get_test_signer();
+        let rpc_client = simulation_rpc_client(2_350);
+
+        // Rejected by the program allowlist, so no simulation result is returned
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(signer_pubkey)
+            .with_instruction(Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]))
+            .build_encoded();
+        let error =
+            simulate_transaction(&rpc_client, simulate_request(transaction)).await.unwrap_err();
+
+        let KoraError::InvalidTransaction(message) = error else {
+            panic!("expected an invalid transaction, got {error:?}");
+        };
+        assert!(message.contains("not in the allowed list"), "{message}");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_simulate_transaction_strict_fee_payer() {
+        let _m = ConfigMockBuilder::new().with_strict_fee_payer(true).build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let rpc_client = simulation_rpc_client(2_350);
+
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(Pubkey::new_unique())
+            .with_sol_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000)
+            .build_encoded();
+        let error =
+            simulate_transaction(&rpc_client, simulate_request(transaction)).await.unwrap_err();
+
+        assert_eq!(
+            error,
+            KoraError::ValidationError("Fee payer already set to an unknown key".to_string())
+        );
+    }
+
+    #[test]
+    fn test_simulate_transaction_response_from_failed_simulation() {
+        let simulation: RpcSimulateTransactionResult = serde_json::from_value(json!({
+            "err": { "InstructionError": [0, { "Custom": 1 }] },
+            "logs": ["Transfer: insufficient lamports 0, need 1000"],
+            "unitsConsumed": 150
+        }))
+        .unwrap();
+        let signer_pubkey = Pubkey::new_unique();
+
+        let response = SimulateTransactionResponse::from_simulation(simulation, &signer_pubkey);
+
+        assert_eq!(response.units_consumed, Some(150));
+        assert_eq!(response.logs, vec!["Transfer: insufficient lamports 0, need 1000"]);
+        assert!(response.error.unwrap().contains("custom program error: 0x1"));
+        assert_eq!(response.signer_pubkey, signer_pubkey.to_string());
+    }
+}
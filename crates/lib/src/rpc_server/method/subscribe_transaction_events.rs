@@ -0,0 +1,58 @@
+use crate::rpc_server::{
+    events::{transaction_event_stream, TRANSACTION_EVENT_BROKER},
+    middleware_utils::record_user_pubkey,
+};
+use jsonrpsee::{
+    types::{
+        error::{ErrorObject, INVALID_PARAMS_CODE},
+        Params, SubscriptionResult,
+    },
+    SubscriptionSink,
+};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use tracing::Instrument;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubscribeTransactionEventsRequest {
+    /// User public key to receive transaction events for
+    pub user: String,
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(method = "subscribeTransactionEvents", user_pubkey = tracing::field::Empty)
+)]
+pub fn subscribe_transaction_events(
+    params: Params,
+    mut sink: SubscriptionSink,
+) -> SubscriptionResult {
+    let user = match params
+        .parse::<SubscribeTransactionEventsRequest>()
+        .map_err(|e| e.to_string())
+        .and_then(|request| {
+            Pubkey::from_str(&request.user).map_err(|e| format!("Invalid user public key: {e}"))
+        }) {
+        Ok(user) => user,
+        Err(message) => {
+            sink.reject(ErrorObject::owned(INVALID_PARAMS_CODE, message, None::<()>))?;
+            return Ok(());
+        }
+    };
+
+    record_user_pubkey(&user);
+    log::info!("Transaction events subscription for user {user}");
+    let receiver = TRANSACTION_EVENT_BROKER.subscribe(user);
+
+    tokio::spawn(
+        async move {
+            sink.pipe_from_stream(Box::pin(transaction_event_stream(receiver))).await;
+            log::debug!("Transaction events subscription for user {user} closed");
+        }
+        .in_current_span(),
+    );
+
+    Ok(())
+}
@@ -10,9 +10,11 @@ use utoipa::ToSchema;
 
 use crate::{
     constant::NATIVE_SOL,
-    state::get_request_signer_with_signer_key,
+    rpc_server::middleware_utils::record_user_pubkey,
+    state::{get_config, get_request_signer_with_signer_key, get_signer_pool},
     transaction::{
-        TransactionUtil, VersionedMessageExt, VersionedTransactionOps, VersionedTransactionResolved,
+        LookupTableUtil, TransactionUtil, VersionedMessageExt, VersionedTransactionOps,
+        VersionedTransactionResolved,
     },
     validator::transaction_validator::TransactionValidator,
     CacheUtil, KoraError,
@@ -38,6 +40,10 @@ pub struct TransferTransactionResponse {
     pub signer_pubkey: String,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(method = "transferTransaction", user_pubkey = tracing::field::Empty)
+)]
 pub async fn transfer_transaction(
     rpc_client: &Arc<RpcClient>,
     request: TransferTransactionRequest,
@@ -49,6 +55,7 @@ pub async fn transfer_transaction(
 
     let source = Pubkey::from_str(&request.source)
         .map_err(|e| KoraError::ValidationError(format!("Invalid source address: {e}")))?;
+    record_user_pubkey(&source);
     let destination = Pubkey::from_str(&request.destination)
         .map_err(|e| KoraError::ValidationError(format!("Invalid destination address: {e}")))?;
     let token_mint = Pubkey::from_str(&request.token)
@@ -125,6 +132,16 @@ pub async fn transfer_transaction(
     let mut resolved_transaction =
         VersionedTransactionResolved::from_kora_built_transaction(&transaction)?;
 
+    let config = get_config()?;
+    if let Some(auto_compute_budget) = &config.kora.auto_compute_budget {
+        resolved_transaction.add_compute_budget_instructions(auto_compute_budget)?;
+    }
+
+    if let Some(lookup_table) = config.kora.get_auto_add_lookup_table()? {
+        let lookup_table = LookupTableUtil::fetch_lookup_table(rpc_client, &lookup_table).await?;
+        resolved_transaction.compress_with_lookup_table(&lookup_table)?;
+    }
+
     // validate transaction before signing
     validator.validate_transaction(&mut resolved_transaction, rpc_client).await?;
 
@@ -132,15 +149,17 @@ pub async fn transfer_transaction(
     let fee_payer_position = resolved_transaction.find_signer_position(&fee_payer)?;
 
     let message_bytes = resolved_transaction.transaction.message.serialize();
-    let signature = signer
-        .sign_message(&message_bytes)
-        .await
-        .map_err(|e| KoraError::SigningError(e.to_string()))?;
+    let signature = signer.sign_message(&message_bytes).await.map_err(|e| {
+        if let Ok(pool) = get_signer_pool() {
+            pool.record_signing_failure(&fee_payer, &e, &message_bytes);
+        }
+        KoraError::SigningError(e.to_string())
+    })?;
 
     resolved_transaction.transaction.signatures[fee_payer_position] = signature;
 
     let encoded = resolved_transaction.encode_b64_transaction()?;
-    let message_encoded = transaction.message.encode_b64_message()?;
+    let message_encoded = resolved_transaction.transaction.message.encode_b64_message()?;
 
     Ok(TransferTransactionResponse {
         transaction: encoded,
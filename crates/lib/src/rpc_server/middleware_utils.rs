@@ -1,27 +1,61 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use futures_util::TryStreamExt;
-use http::{Request, Response, StatusCode};
+use http::{header, HeaderValue, Request, Response, StatusCode};
 use jsonrpsee::server::logger::Body;
+use parking_lot::Mutex;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use tokio::time::Instant;
 
-use crate::KoraError;
+use crate::{
+    rpc_server::{
+        auth::{has_admin_api_key, AuthenticatedCaller},
+        method::{
+            add_signer::{add_signer, AddSignerRequest},
+            healthz::healthz,
+        },
+    },
+    usage_limit::UsageTracker,
+    KoraError,
+};
 
 pub fn default_sig_verify() -> bool {
     false
 }
 
+/// Record `user` as the `user_pubkey` field of the current request span
+pub fn record_user_pubkey(user: &Pubkey) {
+    tracing::Span::current().record("user_pubkey", tracing::field::display(user));
+    tracing::debug!("Request from user {user}");
+}
+
+/// Record the first signer after the fee payer of `transaction` as the request user
+pub fn record_transaction_user(transaction: &VersionedTransaction) {
+    let num_signers = transaction.message.header().num_required_signatures as usize;
+    if let Some(user) = transaction.message.static_account_keys().iter().take(num_signers).nth(1) {
+        record_user_pubkey(user);
+    }
+}
+
 pub async fn extract_parts_and_body_bytes(
     request: Request<Body>,
 ) -> (http::request::Parts, Vec<u8>) {
     let (parts, body) = request.into_parts();
-    let body_bytes = body
-        .try_fold(Vec::new(), |mut acc, chunk| async move {
-            acc.extend_from_slice(&chunk);
-            Ok(acc)
-        })
-        .await
-        .unwrap_or_default();
-    (parts, body_bytes)
+    (parts, collect_body_bytes(body).await)
+}
+
+async fn collect_body_bytes(body: Body) -> Vec<u8> {
+    body.try_fold(Vec::new(), |mut acc, chunk| async move {
+        acc.extend_from_slice(&chunk);
+        Ok(acc)
+    })
+    .await
+    .unwrap_or_default()
 }
 
 pub fn get_jsonrpc_method(body_bytes: &[u8]) -> Option<String> {
@@ -44,6 +78,20 @@ pub fn verify_jsonrpc_method(
     Err(KoraError::InvalidRequest("Method not allowed".to_string()))
 }
 
+/// `retry_after_ms` of a JSON-RPC error response, set for `KoraError::RateLimited`
+pub fn get_retry_after_ms(body_bytes: &[u8]) -> Option<u64> {
+    let val = serde_json::from_slice::<serde_json::Value>(body_bytes).ok()?;
+    val.get("error")?.get("data")?.get("retry_after_ms")?.as_u64()
+}
+
+pub fn is_websocket_upgrade<B>(request: &Request<B>) -> bool {
+    request
+        .headers()
+        .get(http::header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+}
+
 pub fn build_response_with_graceful_error(
     headers: Option<HashMap<String, String>>,
     status_code: StatusCode,
@@ -114,17 +162,29 @@ where
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
+            // Calls over a WebSocket would skip this validation, subscriptions have their own server
+            if is_websocket_upgrade(&request) {
+                return Ok(build_response_with_graceful_error(
+                    None,
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "",
+                ));
+            }
+
             let (parts, body_bytes) = extract_parts_and_body_bytes(request).await;
 
-            match verify_jsonrpc_method(&body_bytes, &allowed_methods) {
-                Ok(_) => {}
-                Err(_) => {
-                    return Ok(build_response_with_graceful_error(
-                        None,
-                        StatusCode::METHOD_NOT_ALLOWED,
-                        "",
-                    ));
-                }
+            // POST /admin/signers is the REST form of addSigner
+            let is_allowed = if AdminSignersHandlerLayer::matches(&parts.method, parts.uri.path()) {
+                allowed_methods.contains(AdminSignersHandlerLayer::METHOD)
+            } else {
+                verify_jsonrpc_method(&body_bytes, &allowed_methods).is_ok()
+            };
+            if !is_allowed {
+                return Ok(build_response_with_graceful_error(
+                    None,
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "",
+                ));
             }
 
             let new_body = Body::from(body_bytes);
@@ -134,9 +194,537 @@ where
     }
 }
 
+/// Layer handling the request with its `AuthenticatedCaller` as the caller the usage limiter
+/// counts transactions against, method handlers don't see the request extensions
+#[derive(Clone, Default)]
+pub struct AuthenticatedCallerLayer;
+
+#[derive(Clone)]
+pub struct AuthenticatedCallerService<S> {
+    inner: S,
+}
+
+impl<S> tower::Layer<S> for AuthenticatedCallerLayer {
+    type Service = AuthenticatedCallerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthenticatedCallerService { inner }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for AuthenticatedCallerService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let caller =
+            request.extensions().get::<AuthenticatedCaller>().map(|caller| caller.0.clone());
+        let future = self.inner.call(request);
+
+        Box::pin(UsageTracker::with_caller(caller, future))
+    }
+}
+
+/// Layer answering rate limited JSON-RPC calls with HTTP 429 and a `Retry-After` header in
+/// seconds, the error body is left untouched
+#[derive(Clone, Default)]
+pub struct RateLimitedResponseLayer;
+
+#[derive(Clone)]
+pub struct RateLimitedResponseService<S> {
+    inner: S,
+}
+
+impl<S> tower::Layer<S> for RateLimitedResponseLayer {
+    type Service = RateLimitedResponseService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitedResponseService { inner }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for RateLimitedResponseService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            if is_websocket_upgrade(&request) {
+                return inner.call(request).await;
+            }
+
+            let (mut parts, body) = inner.call(request).await?.into_parts();
+            let body_bytes = collect_body_bytes(body).await;
+
+            if let Some(retry_after_ms) = get_retry_after_ms(&body_bytes) {
+                parts.status = StatusCode::TOO_MANY_REQUESTS;
+                parts.headers.insert(
+                    header::RETRY_AFTER,
+                    HeaderValue::from(retry_after_ms.div_ceil(1000).max(1)),
+                );
+            }
+
+            Ok(Response::from_parts(parts, Body::from(body_bytes)))
+        })
+    }
+}
+
+/// Layer holding every method but `signTransactionBatch` to `max_request_body_size`, the server
+/// wide limit is raised to fit full batches
+#[derive(Clone)]
+pub struct RequestBodyLimitLayer {
+    max_body_size: usize,
+}
+
+impl RequestBodyLimitLayer {
+    pub const BATCH_METHOD: &'static str = "signTransactionBatch";
+
+    pub fn new(max_body_size: usize) -> Self {
+        Self { max_body_size }
+    }
+}
+
+#[derive(Clone)]
+pub struct RequestBodyLimitService<S> {
+    inner: S,
+    max_body_size: usize,
+}
+
+impl<S> tower::Layer<S> for RequestBodyLimitLayer {
+    type Service = RequestBodyLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestBodyLimitService { inner, max_body_size: self.max_body_size }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for RequestBodyLimitService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let max_body_size = self.max_body_size;
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            let (parts, body_bytes) = extract_parts_and_body_bytes(request).await;
+
+            if body_bytes.len() > max_body_size
+                && get_jsonrpc_method(&body_bytes).as_deref()
+                    != Some(RequestBodyLimitLayer::BATCH_METHOD)
+            {
+                return Ok(build_response_with_graceful_error(
+                    None,
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "",
+                ));
+            }
+
+            inner.call(Request::from_parts(parts, Body::from(body_bytes))).await
+        })
+    }
+}
+
+/// Requests per window of one connection. `RequestRateLimitLayer` counts every request against it,
+/// `BatchRateLimitLayer` the remaining transactions of a `signTransactionBatch`
+pub struct RequestRateLimiter {
+    rate: u64,
+    per: Duration,
+    /// End of the current window and the requests left in it
+    window: Mutex<(Instant, u64)>,
+}
+
+impl RequestRateLimiter {
+    pub fn new(rate: u64, per: Duration) -> Self {
+        Self { rate, per, window: Mutex::new((Instant::now(), 0)) }
+    }
+
+    /// Wait until `weight` requests fit the current window, a weight above the rate takes a whole
+    /// window
+    pub async fn acquire(&self, weight: u64) {
+        let weight = weight.min(self.rate.max(1));
+        loop {
+            let until = {
+                let mut window = self.window.lock();
+                let now = Instant::now();
+                if now >= window.0 {
+                    *window = (now + self.per, self.rate);
+                }
+                if window.1 >= weight {
+                    window.1 -= weight;
+                    return;
+                }
+                window.0
+            };
+            tokio::time::sleep_until(until).await;
+        }
+    }
+}
+
+/// Layer holding each connection to `rate` requests per `per`, like `tower::limit::RateLimitLayer`.
+/// The limiter is passed on in the request extensions so `BatchRateLimitLayer` can count batches
+#[derive(Clone)]
+pub struct RequestRateLimitLayer {
+    rate: u64,
+    per: Duration,
+}
+
+impl RequestRateLimitLayer {
+    pub fn new(rate: u64, per: Duration) -> Self {
+        Self { rate, per }
+    }
+}
+
+#[derive(Clone)]
+pub struct RequestRateLimitService<S> {
+    inner: S,
+    limiter: Arc<RequestRateLimiter>,
+}
+
+impl<S> tower::Layer<S> for RequestRateLimitLayer {
+    type Service = RequestRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestRateLimitService {
+            inner,
+            limiter: Arc::new(RequestRateLimiter::new(self.rate, self.per)),
+        }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for RequestRateLimitService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        let limiter = self.limiter.clone();
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            limiter.acquire(1).await;
+            request.extensions_mut().insert(limiter);
+            inner.call(request).await
+        })
+    }
+}
+
+/// Number of transactions in a `signTransactionBatch` request, by name or positional params
+pub fn get_batch_transaction_count(body_bytes: &[u8]) -> Option<usize> {
+    let val = serde_json::from_slice::<serde_json::Value>(body_bytes).ok()?;
+    if val.get("method")?.as_str()? != RequestBodyLimitLayer::BATCH_METHOD {
+        return None;
+    }
+    let params = val.get("params")?;
+    let transactions = params.get("transactions").or_else(|| params.get(0))?;
+    transactions.as_array().map(Vec::len)
+}
+
+/// Layer counting every transaction of a `signTransactionBatch` against the rate limit, the
+/// request itself was already counted once by `RequestRateLimitLayer`. Sits below the body limit
+/// so only bodies of an allowed size are parsed
+#[derive(Clone, Default)]
+pub struct BatchRateLimitLayer;
+
+#[derive(Clone)]
+pub struct BatchRateLimitService<S> {
+    inner: S,
+}
+
+impl<S> tower::Layer<S> for BatchRateLimitLayer {
+    type Service = BatchRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BatchRateLimitService { inner }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for BatchRateLimitService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            let Some(limiter) = request.extensions().get::<Arc<RequestRateLimiter>>().cloned()
+            else {
+                return inner.call(request).await;
+            };
+
+            let (parts, body_bytes) = extract_parts_and_body_bytes(request).await;
+            if let Some(count) = get_batch_transaction_count(&body_bytes) {
+                limiter.acquire(count.saturating_sub(1) as u64).await;
+            }
+
+            inner.call(Request::from_parts(parts, Body::from(body_bytes))).await
+        })
+    }
+}
+
+/// Layer that answers `GET /healthz` with backend health checks, 200 when serving and 503 when down
+#[derive(Clone)]
+pub struct HealthzHandlerLayer {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl HealthzHandlerLayer {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+}
+
+#[derive(Clone)]
+pub struct HealthzHandlerService<S> {
+    inner: S,
+    rpc_client: Arc<RpcClient>,
+}
+
+impl<S> tower::Layer<S> for HealthzHandlerLayer {
+    type Service = HealthzHandlerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HealthzHandlerService { inner, rpc_client: self.rpc_client.clone() }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for HealthzHandlerService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        if request.uri().path() != "/healthz" || request.method() != http::Method::GET {
+            let mut inner = self.inner.clone();
+            return Box::pin(async move { inner.call(request).await });
+        }
+
+        let rpc_client = self.rpc_client.clone();
+        Box::pin(async move {
+            let json_headers =
+                Some(HashMap::from([("content-type".to_string(), "application/json".to_string())]));
+
+            let response = match healthz(&rpc_client).await.and_then(|response| {
+                serde_json::to_string(&response)
+                    .map(|body| (response.http_status(), body))
+                    .map_err(|e| KoraError::SerializationError(e.to_string()))
+            }) {
+                Ok((status_code, body)) => {
+                    build_response_with_graceful_error(json_headers, status_code, &body)
+                }
+                Err(e) => build_response_with_graceful_error(
+                    None,
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    &format!("Health check failed: {e}"),
+                ),
+            };
+            Ok(response)
+        })
+    }
+}
+
+/// Layer that answers `POST /admin/signers` by adding the signer in the JSON body to the pool,
+/// same request and response as the `addSigner` method. Sits behind the same method validation,
+/// body limit and authentication as JSON-RPC calls, and additionally requires the admin API key
+#[derive(Clone)]
+pub struct AdminSignersHandlerLayer {
+    admin_api_key: String,
+}
+
+impl AdminSignersHandlerLayer {
+    pub const PATH: &'static str = "/admin/signers";
+    pub const METHOD: &'static str = "addSigner";
+
+    pub fn new(admin_api_key: String) -> Self {
+        Self { admin_api_key }
+    }
+
+    pub fn matches(method: &http::Method, path: &str) -> bool {
+        method == http::Method::POST && path == Self::PATH
+    }
+}
+
+#[derive(Clone)]
+pub struct AdminSignersHandlerService<S> {
+    inner: S,
+    admin_api_key: String,
+}
+
+impl<S> tower::Layer<S> for AdminSignersHandlerLayer {
+    type Service = AdminSignersHandlerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AdminSignersHandlerService { inner, admin_api_key: self.admin_api_key.clone() }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for AdminSignersHandlerService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        if !AdminSignersHandlerLayer::matches(request.method(), request.uri().path()) {
+            let mut inner = self.inner.clone();
+            return Box::pin(async move { inner.call(request).await });
+        }
+
+        let has_admin_key = has_admin_api_key(&request, &self.admin_api_key);
+        Box::pin(async move {
+            if !has_admin_key {
+                return Ok(build_response_with_graceful_error(None, StatusCode::UNAUTHORIZED, ""));
+            }
+
+            let (_, body_bytes) = extract_parts_and_body_bytes(request).await;
+            let add_signer_request = match serde_json::from_slice::<AddSignerRequest>(&body_bytes) {
+                Ok(add_signer_request) => add_signer_request,
+                Err(e) => {
+                    return Ok(build_response_with_graceful_error(
+                        None,
+                        StatusCode::BAD_REQUEST,
+                        &format!("Invalid add signer request: {e}"),
+                    ));
+                }
+            };
+
+            log::info!("Add signer request: {}", add_signer_request.name);
+            let response = match add_signer(add_signer_request).await.and_then(|response| {
+                serde_json::to_string(&response)
+                    .map_err(|e| KoraError::SerializationError(e.to_string()))
+            }) {
+                Ok(body) => build_response_with_graceful_error(
+                    Some(HashMap::from([(
+                        "content-type".to_string(),
+                        "application/json".to_string(),
+                    )])),
+                    StatusCode::OK,
+                    &body,
+                ),
+                Err(e @ (KoraError::ValidationError(_) | KoraError::InvalidRequest(_))) => {
+                    build_response_with_graceful_error(
+                        None,
+                        StatusCode::BAD_REQUEST,
+                        &e.to_string(),
+                    )
+                }
+                Err(e) => build_response_with_graceful_error(
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    &format!("Failed to add signer: {e}"),
+                ),
+            };
+            Ok(response)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        constant::X_ADMIN_API_KEY,
+        tests::{
+            common::setup_or_get_test_signer, config_mock::ConfigMockBuilder,
+            rpc_mock::RpcMockBuilder,
+        },
+    };
     use http::Method;
     use std::{
         future::Ready,
@@ -228,4 +816,313 @@ mod tests {
             assert_eq!(response.status(), StatusCode::OK, "Method {} should be allowed", method);
         }
     }
+
+    #[tokio::test]
+    async fn test_method_validation_admin_signers_route() {
+        let body = r#"{"name":"signer_3","weight":1}"#;
+        let admin_signers_request = || {
+            Request::builder()
+                .method(Method::POST)
+                .uri(AdminSignersHandlerLayer::PATH)
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let mut service =
+            MethodValidationLayer::new(vec!["liveness".to_string()]).layer(MockService);
+        let response = service.ready().await.unwrap().call(admin_signers_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let allowed_methods = vec!["liveness".to_string(), "addSigner".to_string()];
+        let mut service = MethodValidationLayer::new(allowed_methods).layer(MockService);
+        let response = service.ready().await.unwrap().call(admin_signers_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_caller_layer_scopes_caller() {
+        let mut service =
+            AuthenticatedCallerLayer.layer(tower::service_fn(|_: Request<Body>| async {
+                let caller = UsageTracker::current_caller().unwrap_or_default();
+                Ok::<_, std::convert::Infallible>(Response::new(Body::from(caller)))
+            }));
+
+        let mut request = rpc_request();
+        request.extensions_mut().insert(AuthenticatedCaller("user-1".to_string()));
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        let body = collect_body_bytes(response.into_body()).await;
+        assert_eq!(body, b"user-1");
+
+        let response = service.ready().await.unwrap().call(rpc_request()).await.unwrap();
+        assert!(collect_body_bytes(response.into_body()).await.is_empty());
+    }
+
+    fn websocket_upgrade_request() -> Request<Body> {
+        Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .header(http::header::CONNECTION, "Upgrade")
+            .header(http::header::UPGRADE, "websocket")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_method_validation_websocket_upgrade_rejected() {
+        for allowed_methods in [
+            vec!["liveness".to_string(), "getConfig".to_string()],
+            vec!["liveness".to_string(), "kora_subscribeTransactionEvents".to_string()],
+        ] {
+            let layer = MethodValidationLayer::new(allowed_methods);
+            let mut service = layer.layer(MockService);
+
+            let response =
+                service.ready().await.unwrap().call(websocket_upgrade_request()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        }
+    }
+
+    // Mock service answering every call with the same JSON-RPC body
+    #[derive(Clone)]
+    struct JsonResponseService(&'static str);
+
+    impl tower::Service<Request<Body>> for JsonResponseService {
+        type Response = Response<Body>;
+        type Error = std::convert::Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: Request<Body>) -> Self::Future {
+            std::future::ready(Ok(Response::builder()
+                .status(200)
+                .body(Body::from(self.0))
+                .unwrap()))
+        }
+    }
+
+    fn rpc_request() -> Request<Body> {
+        let body = r#"{"jsonrpc":"2.0","method":"signTransaction","id":1}"#;
+        Request::builder().method(Method::POST).uri("/").body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_response_returns_429_with_retry_after() {
+        let error: jsonrpsee::core::Error = KoraError::RateLimited { retry_after_ms: 1500 }.into();
+        let jsonrpsee::core::Error::Call(jsonrpsee::types::error::CallError::Custom(error)) = error
+        else {
+            panic!("expected a custom call error");
+        };
+        let body = serde_json::json!({ "jsonrpc": "2.0", "error": error, "id": 1 }).to_string();
+        let mut service = RateLimitedResponseLayer.layer(JsonResponseService(body.leak()));
+
+        let response = service.ready().await.unwrap().call(rpc_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+
+        let (_, body_bytes) =
+            extract_parts_and_body_bytes(Request::new(response.into_body())).await;
+        let retry_after_ms = get_retry_after_ms(&body_bytes).unwrap();
+        assert!(retry_after_ms > 0);
+        assert_eq!(retry_after_ms, 1500);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_response_passes_through_other_responses() {
+        let body = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Invalid transaction","data":{"code":"KORA_TRANSACTION_INVALID"}},"id":1}"#;
+        let mut service = RateLimitedResponseLayer.layer(JsonResponseService(body));
+
+        let response = service.ready().await.unwrap().call(rpc_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+
+        let (_, body_bytes) =
+            extract_parts_and_body_bytes(Request::new(response.into_body())).await;
+        assert_eq!(body_bytes, body.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_request_body_limit_only_raised_for_batches() {
+        let padding = "x".repeat(256);
+        let mut service = RequestBodyLimitLayer::new(128).layer(MockService);
+
+        let response = service.ready().await.unwrap().call(rpc_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","method":"signTransaction","params":{{"transaction":"{padding}"}},"id":1}}"#
+        );
+        let request = Request::builder().method(Method::POST).uri("/").body(Body::from(body));
+        let response = service.ready().await.unwrap().call(request.unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","method":"signTransactionBatch","params":{{"transactions":["{padding}"]}},"id":1}}"#
+        );
+        let request = Request::builder().method(Method::POST).uri("/").body(Body::from(body));
+        let response = service.ready().await.unwrap().call(request.unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn batch_request(transactions: usize) -> Request<Body> {
+        let transactions = vec!["tx"; transactions];
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "signTransactionBatch",
+            "params": { "transactions": transactions },
+            "id": 1
+        });
+        Request::builder().method(Method::POST).uri("/").body(Body::from(body.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_get_batch_transaction_count() {
+        let body = r#"{"jsonrpc":"2.0","method":"signTransactionBatch","params":{"transactions":["a","b"]},"id":1}"#;
+        assert_eq!(get_batch_transaction_count(body.as_bytes()), Some(2));
+        let body =
+            r#"{"jsonrpc":"2.0","method":"signTransactionBatch","params":[["a","b","c"]],"id":1}"#;
+        assert_eq!(get_batch_transaction_count(body.as_bytes()), Some(3));
+        let body = r#"{"jsonrpc":"2.0","method":"signTransaction","params":{"transactions":["a"]},"id":1}"#;
+        assert_eq!(get_batch_transaction_count(body.as_bytes()), None);
+    }
+
+    #[tokio::test]
+    async fn test_batch_rate_limit_counts_every_transaction() {
+        let mut service = tower::ServiceBuilder::new()
+            .layer(RequestRateLimitLayer::new(3, Duration::from_secs(60)))
+            .layer(BatchRateLimitLayer)
+            .service(MockService);
+
+        // A batch of 3 takes the whole window, the request itself counting as the first
+        let response = service.ready().await.unwrap().call(batch_request(3)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let call = service.ready().await.unwrap().call(rpc_request());
+        assert!(tokio::time::timeout(Duration::from_millis(100), call).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_rate_limit_waits_for_batch_over_remaining_window() {
+        let mut service = tower::ServiceBuilder::new()
+            .layer(RequestRateLimitLayer::new(4, Duration::from_secs(60)))
+            .layer(BatchRateLimitLayer)
+            .service(MockService);
+
+        let response = service.ready().await.unwrap().call(rpc_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = service.ready().await.unwrap().call(rpc_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 2 requests left in the window, a batch of 3 needs 3
+        let call = service.ready().await.unwrap().call(batch_request(3));
+        assert!(tokio::time::timeout(Duration::from_millis(100), call).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_rate_limit_passes_through_without_limiter() {
+        let mut service = BatchRateLimitLayer.layer(MockService);
+
+        let response = service.ready().await.unwrap().call(batch_request(3)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn healthz_request() -> Request<Body> {
+        Request::builder().method(Method::GET).uri("/healthz").body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_healthz_handler_returns_503_when_rpc_is_down() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let layer = HealthzHandlerLayer::new(RpcMockBuilder::new().with_unhealthy_rpc().build());
+        let mut service = layer.layer(MockService);
+
+        let response = service.ready().await.unwrap().call(healthz_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let (_, body_bytes) =
+            extract_parts_and_body_bytes(Request::new(response.into_body())).await;
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["status"], "down");
+        assert_eq!(body["checks"]["rpc"], false);
+    }
+
+    #[tokio::test]
+    async fn test_healthz_handler_returns_200_when_healthy() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let layer = HealthzHandlerLayer::new(RpcMockBuilder::new().with_health().build());
+        let mut service = layer.layer(MockService);
+
+        let response = service.ready().await.unwrap().call(healthz_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_healthz_handler_passes_through_other_paths() {
+        let layer = HealthzHandlerLayer::new(RpcMockBuilder::new().with_unhealthy_rpc().build());
+        let mut service = layer.layer(MockService);
+
+        let request = Request::builder().method(Method::GET).uri("/").body(Body::empty()).unwrap();
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn add_signer_request(admin_key: Option<&str>, body: &str) -> Request<Body> {
+        let mut builder =
+            Request::builder().method(Method::POST).uri(AdminSignersHandlerLayer::PATH);
+        if let Some(admin_key) = admin_key {
+            builder = builder.header(X_ADMIN_API_KEY, admin_key);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_admin_signers_handler_requires_admin_key() {
+        let mut service = AdminSignersHandlerLayer::new("admin-key".to_string()).layer(MockService);
+
+        let response =
+            service.ready().await.unwrap().call(add_signer_request(None, "{}")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = add_signer_request(Some("wrong-key"), "{}");
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_admin_signers_handler_rejects_invalid_requests() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        let mut service = AdminSignersHandlerLayer::new("admin-key".to_string()).layer(MockService);
+
+        let request = add_signer_request(Some("admin-key"), "not json");
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Memory signers must name the variable holding their private key
+        let body = r#"{"name":"rest_signer","signer":{"type":"memory","private_key_env":""}}"#;
+        let request = add_signer_request(Some("admin-key"), body);
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_admin_signers_handler_passes_through_other_requests() {
+        let mut service = AdminSignersHandlerLayer::new("admin-key".to_string()).layer(MockService);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(AdminSignersHandlerLayer::PATH)
+            .body(Body::empty())
+            .unwrap();
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder().method(Method::POST).uri("/").body(Body::empty()).unwrap();
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
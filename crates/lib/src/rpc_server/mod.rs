@@ -1,9 +1,12 @@
 pub mod args;
 pub mod auth;
+pub mod backpressure;
+pub mod events;
 pub mod method;
 pub mod middleware_utils;
 #[cfg(feature = "docs")]
 pub mod openapi;
+pub mod proxy;
 pub mod rpc;
 pub mod server;
 
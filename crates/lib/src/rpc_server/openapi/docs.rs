@@ -1,7 +1,15 @@
 use crate::{
-    config::{EnabledMethods, FeePayerPolicy, ValidationConfig},
-    fee::price::{PriceConfig, PriceModel},
-    oracle::oracle::{PriceSource, TokenPrice},
+    config::{AnchorProgramConfig, EnabledMethods, FeePayerPolicy, ValidationConfig},
+    fee::{
+        fee::FeeEstimationStrategy,
+        price::{PriceConfig, PriceModel},
+    },
+    oracle::{
+        oracle::{PriceSource, TokenPrice},
+        MockOracleConfig,
+    },
+    signer::DeadLetterEntry,
+    validator::account_validator::AccountType,
 };
 use std::path::PathBuf;
 use utoipa::{
@@ -14,14 +22,39 @@ use utoipa::{
 
 use crate::rpc_server::{
     method::{
-        get_blockhash::GetBlockhashResponse,
+        add_signer::{AddSignerRequest, AddSignerResponse},
+        estimate_transaction_fee::{AccountMetaTemplate, InstructionTemplate},
+        get_account_info::{GetAccountInfoRequest, GetAccountInfoResponse},
+        get_blockhash::{BlockhashCommitment, GetBlockhashRequest, GetBlockhashResponse},
         get_config::GetConfigResponse,
+        get_dead_letter_queue::GetDeadLetterQueueResponse,
+        get_instruction_template::{
+            GetInstructionTemplateRequest, GetInstructionTemplateResponse, InstructionTemplateType,
+        },
+        get_network_status::{
+            CongestionLevel, GetNetworkStatusResponse, IsNetworkCongestedResponse,
+        },
         get_payer_signer::GetPayerSignerResponse,
+        get_program_accounts::{
+            GetProgramAccountsRequest, GetProgramAccountsResponse, ManagedAccount,
+            ManagedAccountType,
+        },
+        get_signer_public_key::GetSignerPublicKeyResponse,
         get_supported_tokens::GetSupportedTokensResponse,
+        get_token_metadata::{GetTokenMetadataResponse, TokenMetadata},
+        get_version::GetVersionResponse,
+        healthz::{HealthChecks, HealthStatus, HealthzResponse},
+        set_log_level::{LogLevel, SetLogLevelRequest, SetLogLevelResponse},
         sign_and_send_transaction::{
             SignAndSendTransactionRequest, SignAndSendTransactionResponse,
         },
+        sign_message::{SignMessageRequest, SignMessageResponse},
         sign_transaction::{SignTransactionRequest, SignTransactionResponse},
+        sign_transaction_batch::{
+            SignTransactionBatchError, SignTransactionBatchRequest, SignTransactionBatchResponse,
+            SignTransactionBatchResult,
+        },
+        simulate_transaction::{SimulateTransactionRequest, SimulateTransactionResponse},
         transfer_transaction::{TransferTransactionRequest, TransferTransactionResponse},
     },
     KoraRpc,
@@ -41,20 +74,62 @@ const JSON_CONTENT_TYPE: &str = "application/json";
     ),
     components(schemas(
         ValidationConfig,
+        AccountType,
+        AnchorProgramConfig,
         FeePayerPolicy,
         EnabledMethods,
         PriceConfig,
         PriceModel,
+        FeeEstimationStrategy,
         TokenPrice,
         PriceSource,
+        MockOracleConfig,
+        AccountMetaTemplate,
+        AddSignerRequest,
+        AddSignerResponse,
+        InstructionTemplate,
+        GetAccountInfoRequest,
+        GetAccountInfoResponse,
+        BlockhashCommitment,
+        GetBlockhashRequest,
         GetBlockhashResponse,
         GetConfigResponse,
+        DeadLetterEntry,
+        GetDeadLetterQueueResponse,
+        GetInstructionTemplateRequest,
+        GetInstructionTemplateResponse,
+        InstructionTemplateType,
+        CongestionLevel,
+        GetNetworkStatusResponse,
+        IsNetworkCongestedResponse,
         GetPayerSignerResponse,
+        GetProgramAccountsRequest,
+        GetProgramAccountsResponse,
+        ManagedAccount,
+        ManagedAccountType,
+        GetSignerPublicKeyResponse,
         GetSupportedTokensResponse,
+        GetTokenMetadataResponse,
+        TokenMetadata,
+        GetVersionResponse,
+        HealthChecks,
+        HealthStatus,
+        HealthzResponse,
+        LogLevel,
+        SetLogLevelRequest,
+        SetLogLevelResponse,
         SignAndSendTransactionRequest,
         SignAndSendTransactionResponse,
+        SignMessageRequest,
+        SignMessageResponse,
         SignTransactionRequest,
         SignTransactionResponse,
+        SignTransactionBatchError,
+        SignTransactionBatchRequest,
+        SignTransactionBatchResponse,
+        SignTransactionBatchResult,
+        SimulateTransactionRequest,
+        SimulateTransactionResponse,
         TransferTransactionRequest,
         TransferTransactionResponse,
     ))
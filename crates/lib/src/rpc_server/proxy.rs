@@ -0,0 +1,268 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use http::{header, Request, Response, StatusCode};
+use jsonrpsee::server::logger::Body;
+use reqwest::Client;
+
+use crate::{
+    constant::DEFAULT_RPC_PROXY_TIMEOUT_SECONDS,
+    rpc_server::middleware_utils::{
+        build_response_with_graceful_error, extract_parts_and_body_bytes, get_jsonrpc_method,
+        is_websocket_upgrade,
+    },
+    sanitize_error, KoraError,
+};
+
+/// Layer forwarding allowlisted Solana JSON-RPC methods verbatim to the backend RPC, every
+/// other request goes on to Kora
+#[derive(Clone)]
+pub struct ProxyLayer {
+    http_client: Client,
+    backend_url: String,
+    proxy_methods: Arc<HashSet<String>>,
+}
+
+impl ProxyLayer {
+    pub fn new(backend_url: String, proxy_methods: Vec<String>) -> Result<Self, KoraError> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_RPC_PROXY_TIMEOUT_SECONDS))
+            .build()
+            .map_err(|e| {
+                KoraError::InternalServerError(format!("Failed to build RPC proxy client: {e}"))
+            })?;
+
+        Ok(Self {
+            http_client,
+            backend_url,
+            proxy_methods: Arc::new(proxy_methods.into_iter().collect()),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ProxyService<S> {
+    inner: S,
+    layer: ProxyLayer,
+}
+
+impl<S> tower::Layer<S> for ProxyLayer {
+    type Service = ProxyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProxyService { inner, layer: self.clone() }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for ProxyService<S>
+where
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let layer = self.layer.clone();
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            if is_websocket_upgrade(&request) {
+                return inner.call(request).await;
+            }
+
+            let (parts, body_bytes) = extract_parts_and_body_bytes(request).await;
+
+            match get_jsonrpc_method(&body_bytes) {
+                Some(method) if layer.proxy_methods.contains(&method) => {
+                    Ok(forward_to_backend(&layer, &method, body_bytes).await)
+                }
+                _ => inner.call(Request::from_parts(parts, Body::from(body_bytes))).await,
+            }
+        })
+    }
+}
+
+/// POST the request body unchanged to the backend RPC and relay its status and body
+async fn forward_to_backend(
+    layer: &ProxyLayer,
+    method: &str,
+    body_bytes: Vec<u8>,
+) -> Response<Body> {
+    let result = async {
+        let response = layer
+            .http_client
+            .post(&layer.backend_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body_bytes)
+            .send()
+            .await?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await?;
+        Ok::<_, reqwest::Error>((status, body))
+    }
+    .await;
+
+    match result {
+        Ok((status, body)) => Response::builder()
+            .status(StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_vec()))
+            .unwrap_or_else(|e| {
+                log::error!("Failed to build proxied response, error: {e:?}");
+                build_response_with_graceful_error(None, StatusCode::BAD_GATEWAY, "")
+            }),
+        Err(e) => {
+            log::error!("Failed to proxy {method} to the backend RPC: {}", sanitize_error!(e));
+            build_response_with_graceful_error(None, StatusCode::BAD_GATEWAY, "")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_server::middleware_utils::MethodValidationLayer;
+    use http::Method;
+    use mockito::{Matcher, Server};
+    use std::{
+        future::Ready,
+        task::{Context, Poll},
+    };
+    use tower::{Service, ServiceBuilder, ServiceExt};
+
+    // Stands in for the Kora RPC module
+    #[derive(Clone)]
+    struct MockService;
+
+    impl tower::Service<Request<Body>> for MockService {
+        type Response = Response<Body>;
+        type Error = std::convert::Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: Request<Body>) -> Self::Future {
+            std::future::ready(Ok(Response::builder()
+                .status(200)
+                .body(Body::from(r#"{"jsonrpc":"2.0","result":"kora","id":1}"#))
+                .unwrap()))
+        }
+    }
+
+    fn request(body: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn body_string(response: Response<Body>) -> String {
+        let (_, body_bytes) =
+            extract_parts_and_body_bytes(Request::new(response.into_body())).await;
+        String::from_utf8(body_bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_proxy_forwards_allowlisted_method() {
+        let mut server = Server::new_async().await;
+        let body = r#"{"jsonrpc":"2.0","method":"getBalance","params":["11111111111111111111111111111111"],"id":7}"#;
+        let backend_response =
+            r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":42},"id":7}"#;
+        let mock = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::Exact(body.to_string()))
+            .with_status(200)
+            .with_body(backend_response)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let layer = ProxyLayer::new(server.url(), vec!["getBalance".to_string()]).unwrap();
+        let mut service = tower::Layer::layer(&layer, MockService);
+
+        let response = service.ready().await.unwrap().call(request(body)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_string(response).await, backend_response);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_proxy_passes_other_methods_to_kora() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/").expect(0).create_async().await;
+
+        let layer = ProxyLayer::new(server.url(), vec!["getBalance".to_string()]).unwrap();
+        let mut service = tower::Layer::layer(&layer, MockService);
+
+        let response = service
+            .ready()
+            .await
+            .unwrap()
+            .call(request(r#"{"jsonrpc":"2.0","method":"getConfig","id":1}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(body_string(response).await, r#"{"jsonrpc":"2.0","result":"kora","id":1}"#);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_proxy_rejects_non_allowlisted_method() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/").expect(0).create_async().await;
+
+        // Mirrors the server stack, proxied methods are added to the method whitelist
+        let proxy_methods = vec!["getBalance".to_string()];
+        let mut allowed_methods = vec!["getConfig".to_string()];
+        allowed_methods.extend(proxy_methods.clone());
+        let mut service = ServiceBuilder::new()
+            .layer(MethodValidationLayer::new(allowed_methods))
+            .layer(ProxyLayer::new(server.url(), proxy_methods).unwrap())
+            .service(MockService);
+
+        let response = service
+            .ready()
+            .await
+            .unwrap()
+            .call(request(r#"{"jsonrpc":"2.0","method":"getProgramAccounts","id":1}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_proxy_backend_unreachable() {
+        let layer =
+            ProxyLayer::new("http://127.0.0.1:1".to_string(), vec!["getBalance".to_string()])
+                .unwrap();
+        let mut service = tower::Layer::layer(&layer, MockService);
+
+        let response = service
+            .ready()
+            .await
+            .unwrap()
+            .call(request(r#"{"jsonrpc":"2.0","method":"getBalance","id":1}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+}
@@ -9,18 +9,46 @@ use utoipa::{
     ToSchema,
 };
 
+#[cfg(feature = "dex")]
+use crate::rpc_server::method::place_market_order::{place_market_order, PlaceMarketOrderRequest};
 use crate::rpc_server::method::{
+    add_signer::{add_signer, AddSignerRequest, AddSignerResponse},
     estimate_transaction_fee::{
-        estimate_transaction_fee, EstimateTransactionFeeRequest, EstimateTransactionFeeResponse,
+        estimate_transaction_fee, estimate_transaction_fee_template, EstimateTransactionFeeRequest,
+        EstimateTransactionFeeResponse, EstimateTransactionFeeTemplateRequest,
     },
-    get_blockhash::{get_blockhash, GetBlockhashResponse},
+    get_account_info::{get_account_info, GetAccountInfoRequest, GetAccountInfoResponse},
+    get_blockhash::{get_blockhash, GetBlockhashRequest, GetBlockhashResponse},
     get_config::{get_config, GetConfigResponse},
+    get_dead_letter_queue::{get_dead_letter_queue, GetDeadLetterQueueResponse},
+    get_instruction_template::{
+        get_instruction_template, GetInstructionTemplateRequest, GetInstructionTemplateResponse,
+    },
+    get_network_status::{
+        get_network_status, is_network_congested, GetNetworkStatusResponse,
+        IsNetworkCongestedResponse,
+    },
     get_payer_signer::{get_payer_signer, GetPayerSignerResponse},
+    get_program_accounts::{
+        get_program_accounts, GetProgramAccountsRequest, GetProgramAccountsResponse,
+    },
+    get_signer_public_key::{get_signer_public_key, GetSignerPublicKeyResponse},
     get_supported_tokens::{get_supported_tokens, GetSupportedTokensResponse},
+    get_token_metadata::{get_token_metadata, GetTokenMetadataResponse},
+    get_version::{get_version, GetVersionResponse},
+    healthz::{healthz, HealthzResponse},
+    set_log_level::{set_log_level, SetLogLevelRequest, SetLogLevelResponse},
     sign_and_send_transaction::{
         sign_and_send_transaction, SignAndSendTransactionRequest, SignAndSendTransactionResponse,
     },
+    sign_message::{sign_message, SignMessageRequest, SignMessageResponse},
     sign_transaction::{sign_transaction, SignTransactionRequest, SignTransactionResponse},
+    sign_transaction_batch::{
+        sign_transaction_batch, SignTransactionBatchRequest, SignTransactionBatchResponse,
+    },
+    simulate_transaction::{
+        simulate_transaction, SimulateTransactionRequest, SimulateTransactionResponse,
+    },
     transfer_transaction::{
         transfer_transaction, TransferTransactionRequest, TransferTransactionResponse,
     },
@@ -46,6 +74,7 @@ impl KoraRpc {
         &self.rpc_client
     }
 
+    #[tracing::instrument(skip_all, fields(method = "liveness"))]
     pub async fn liveness(&self) -> Result<(), KoraError> {
         info!("Liveness request received");
         let result = Ok(());
@@ -53,6 +82,13 @@ impl KoraRpc {
         result
     }
 
+    pub async fn healthz(&self) -> Result<HealthzResponse, KoraError> {
+        info!("Healthz request received");
+        let result = healthz(&self.rpc_client).await;
+        info!("Healthz response: {result:?}");
+        result
+    }
+
     pub async fn estimate_transaction_fee(
         &self,
         request: EstimateTransactionFeeRequest,
@@ -63,6 +99,16 @@ impl KoraRpc {
         result
     }
 
+    pub async fn estimate_transaction_fee_template(
+        &self,
+        request: EstimateTransactionFeeTemplateRequest,
+    ) -> Result<EstimateTransactionFeeResponse, KoraError> {
+        info!("Estimate transaction fee template request: {request:?}");
+        let result = estimate_transaction_fee_template(&self.rpc_client, request).await;
+        info!("Estimate transaction fee template response: {result:?}");
+        result
+    }
+
     pub async fn get_supported_tokens(&self) -> Result<GetSupportedTokensResponse, KoraError> {
         info!("Get supported tokens request received");
         let result = get_supported_tokens().await;
@@ -70,6 +116,23 @@ impl KoraRpc {
         result
     }
 
+    pub async fn get_token_metadata(&self) -> Result<GetTokenMetadataResponse, KoraError> {
+        info!("Get token metadata request received");
+        let result = get_token_metadata(&self.rpc_client).await;
+        info!("Get token metadata response: {result:?}");
+        result
+    }
+
+    pub async fn get_account_info(
+        &self,
+        request: GetAccountInfoRequest,
+    ) -> Result<GetAccountInfoResponse, KoraError> {
+        info!("Get account info request: {request:?}");
+        let result = get_account_info(&self.rpc_client, request).await;
+        info!("Get account info response: {result:?}");
+        result
+    }
+
     pub async fn get_payer_signer(&self) -> Result<GetPayerSignerResponse, KoraError> {
         info!("Get payer signer request received");
         let result = get_payer_signer().await;
@@ -77,6 +140,37 @@ impl KoraRpc {
         result
     }
 
+    pub async fn get_signer_public_key(&self) -> Result<GetSignerPublicKeyResponse, KoraError> {
+        info!("Get signer public key request received");
+        let result = get_signer_public_key().await;
+        info!("Get signer public key response: {result:?}");
+        result
+    }
+
+    pub async fn get_network_status(&self) -> Result<GetNetworkStatusResponse, KoraError> {
+        info!("Get network status request received");
+        let result = get_network_status(&self.rpc_client).await;
+        info!("Get network status response: {result:?}");
+        result
+    }
+
+    pub async fn is_network_congested(&self) -> Result<IsNetworkCongestedResponse, KoraError> {
+        info!("Is network congested request received");
+        let result = is_network_congested(&self.rpc_client).await;
+        info!("Is network congested response: {result:?}");
+        result
+    }
+
+    pub async fn sign_message(
+        &self,
+        request: SignMessageRequest,
+    ) -> Result<SignMessageResponse, KoraError> {
+        info!("Sign message request: {request:?}");
+        let result = sign_message(request).await;
+        info!("Sign message response: {result:?}");
+        result
+    }
+
     pub async fn sign_transaction(
         &self,
         request: SignTransactionRequest,
@@ -87,6 +181,26 @@ impl KoraRpc {
         result
     }
 
+    pub async fn sign_transaction_batch(
+        &self,
+        request: SignTransactionBatchRequest,
+    ) -> Result<SignTransactionBatchResponse, KoraError> {
+        info!("Sign transaction batch request: {request:?}");
+        let result = sign_transaction_batch(&self.rpc_client, request).await;
+        info!("Sign transaction batch response: {result:?}");
+        result
+    }
+
+    pub async fn simulate_transaction(
+        &self,
+        request: SimulateTransactionRequest,
+    ) -> Result<SimulateTransactionResponse, KoraError> {
+        info!("Simulate transaction request: {request:?}");
+        let result = simulate_transaction(&self.rpc_client, request).await;
+        info!("Simulate transaction response: {result:?}");
+        result
+    }
+
     pub async fn sign_and_send_transaction(
         &self,
         request: SignAndSendTransactionRequest,
@@ -107,9 +221,12 @@ impl KoraRpc {
         result
     }
 
-    pub async fn get_blockhash(&self) -> Result<GetBlockhashResponse, KoraError> {
-        info!("Get blockhash request received");
-        let result = get_blockhash(&self.rpc_client).await;
+    pub async fn get_blockhash(
+        &self,
+        request: GetBlockhashRequest,
+    ) -> Result<GetBlockhashResponse, KoraError> {
+        info!("Get blockhash request: {request:?}");
+        let result = get_blockhash(&self.rpc_client, request).await;
         info!("Get blockhash response: {result:?}");
         result
     }
@@ -121,17 +238,97 @@ impl KoraRpc {
         result
     }
 
+    pub async fn get_version(&self) -> Result<GetVersionResponse, KoraError> {
+        info!("Get version request received");
+        let result = get_version().await;
+        info!("Get version response: {result:?}");
+        result
+    }
+
+    pub async fn get_instruction_template(
+        &self,
+        request: GetInstructionTemplateRequest,
+    ) -> Result<GetInstructionTemplateResponse, KoraError> {
+        info!("Get instruction template request: {request:?}");
+        let result = get_instruction_template(request).await;
+        info!("Get instruction template response: {result:?}");
+        result
+    }
+
+    pub async fn set_log_level(
+        &self,
+        request: SetLogLevelRequest,
+    ) -> Result<SetLogLevelResponse, KoraError> {
+        info!("Set log level request: {request:?}");
+        let result = set_log_level(request).await;
+        info!("Set log level response: {result:?}");
+        result
+    }
+
+    pub async fn add_signer(
+        &self,
+        request: AddSignerRequest,
+    ) -> Result<AddSignerResponse, KoraError> {
+        info!("Add signer request: {}", request.name);
+        let result = add_signer(request).await;
+        info!("Add signer response: {result:?}");
+        result
+    }
+
+    pub async fn get_dead_letter_queue(&self) -> Result<GetDeadLetterQueueResponse, KoraError> {
+        info!("Get dead letter queue request");
+        let result = get_dead_letter_queue().await;
+        info!("Get dead letter queue response: {result:?}");
+        result
+    }
+
+    #[cfg(feature = "dex")]
+    pub async fn place_market_order(
+        &self,
+        request: PlaceMarketOrderRequest,
+    ) -> Result<SignAndSendTransactionResponse, KoraError> {
+        info!("Place market order request: {request:?}");
+        let result = place_market_order(&self.rpc_client, request).await;
+        info!("Place market order response: {result:?}");
+        result
+    }
+
+    pub async fn get_program_accounts(
+        &self,
+        request: GetProgramAccountsRequest,
+    ) -> Result<GetProgramAccountsResponse, KoraError> {
+        info!("Get program accounts request: {request:?}");
+        let result = get_program_accounts(&self.rpc_client, request).await;
+        info!("Get program accounts response: {result:?}");
+        result
+    }
+
     #[cfg(feature = "docs")]
     pub fn build_docs_spec() -> Vec<OpenApiSpec> {
         vec![
+            OpenApiSpec {
+                name: "addSigner".to_string(),
+                request: Some(AddSignerRequest::schema().1),
+                response: AddSignerResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "estimateTransactionFee".to_string(),
                 request: Some(EstimateTransactionFeeRequest::schema().1),
                 response: EstimateTransactionFeeResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "estimateTransactionFeeTemplate".to_string(),
+                request: Some(EstimateTransactionFeeTemplateRequest::schema().1),
+                response: EstimateTransactionFeeResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "kora_getAccountInfo".to_string(),
+                request: Some(GetAccountInfoRequest::schema().1),
+                response: GetAccountInfoResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "getBlockhash".to_string(),
-                request: None,
+                request: Some(GetBlockhashRequest::schema().1),
                 response: GetBlockhashResponse::schema().1,
             },
             OpenApiSpec {
@@ -139,21 +336,86 @@ impl KoraRpc {
                 request: None,
                 response: GetConfigResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "kora_getDeadLetterQueue".to_string(),
+                request: None,
+                response: GetDeadLetterQueueResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "getInstructionTemplate".to_string(),
+                request: Some(GetInstructionTemplateRequest::schema().1),
+                response: GetInstructionTemplateResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "getVersion".to_string(),
+                request: None,
+                response: GetVersionResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "healthz".to_string(),
+                request: None,
+                response: HealthzResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "getSupportedTokens".to_string(),
                 request: None,
                 response: GetSupportedTokensResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "getTokenMetadata".to_string(),
+                request: None,
+                response: GetTokenMetadataResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "getPayerSigner".to_string(),
                 request: None,
                 response: GetPayerSignerResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "kora_getNetworkStatus".to_string(),
+                request: None,
+                response: GetNetworkStatusResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "kora_isNetworkCongested".to_string(),
+                request: None,
+                response: IsNetworkCongestedResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "kora_getSignerPublicKey".to_string(),
+                request: None,
+                response: GetSignerPublicKeyResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "kora_getProgramAccounts".to_string(),
+                request: Some(GetProgramAccountsRequest::schema().1),
+                response: GetProgramAccountsResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "kora_setLogLevel".to_string(),
+                request: Some(SetLogLevelRequest::schema().1),
+                response: SetLogLevelResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "signMessage".to_string(),
+                request: Some(SignMessageRequest::schema().1),
+                response: SignMessageResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "signTransaction".to_string(),
                 request: Some(SignTransactionRequest::schema().1),
                 response: SignTransactionResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "signTransactionBatch".to_string(),
+                request: Some(SignTransactionBatchRequest::schema().1),
+                response: SignTransactionBatchResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "kora_simulateTransaction".to_string(),
+                request: Some(SimulateTransactionRequest::schema().1),
+                response: SimulateTransactionResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "signAndSendTransaction".to_string(),
                 request: Some(SignAndSendTransactionRequest::schema().1),
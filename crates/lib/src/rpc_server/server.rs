@@ -1,12 +1,20 @@
 use crate::{
-    constant::{X_API_KEY, X_HMAC_SIGNATURE, X_TIMESTAMP},
-    metrics::run_metrics_server_if_required,
+    config::AuthConfig,
+    constant::{ADMIN_METHODS, X_ADMIN_API_KEY, X_API_KEY, X_HMAC_SIGNATURE, X_TIMESTAMP},
+    metrics::{run_metrics_server_if_required, start_push_gateway_if_required},
     rpc_server::{
-        auth::{ApiKeyAuthLayer, HmacAuthLayer},
-        middleware_utils::MethodValidationLayer,
+        auth::{AdminApiKeyAuthLayer, ApiKeyAuthLayer, HmacAuthLayer, JwtAuthLayer},
+        method::subscribe_transaction_events::subscribe_transaction_events,
+        middleware_utils::{
+            AdminSignersHandlerLayer, AuthenticatedCallerLayer, BatchRateLimitLayer,
+            HealthzHandlerLayer, MethodValidationLayer, RateLimitedResponseLayer,
+            RequestBodyLimitLayer, RequestRateLimitLayer,
+        },
+        proxy::ProxyLayer,
         rpc::KoraRpc,
     },
     usage_limit::UsageTracker,
+    webhook::init_webhook_dispatcher,
 };
 
 #[cfg(not(test))]
@@ -26,8 +34,10 @@ use tower_http::cors::CorsLayer;
 
 pub struct ServerHandles {
     pub rpc_handle: ServerHandle,
+    pub ws_handle: Option<ServerHandle>,
     pub metrics_handle: Option<ServerHandle>,
     pub balance_tracker_handle: Option<JoinHandle<()>>,
+    pub push_gateway_handle: Option<JoinHandle<()>>,
 }
 
 // We'll always prioritize the environment variable over the config value
@@ -35,7 +45,15 @@ fn get_value_by_priority(env_var: &str, config_value: Option<String>) -> Option<
     std::env::var(env_var).ok().or(config_value)
 }
 
-pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, anyhow::Error> {
+fn get_admin_api_key(auth: &AuthConfig) -> Option<String> {
+    get_value_by_priority("KORA_ADMIN_API_KEY", auth.admin_api_key.clone())
+}
+
+pub async fn run_rpc_server(
+    rpc: KoraRpc,
+    port: u16,
+    ws_port: u16,
+) -> Result<ServerHandles, anyhow::Error> {
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
     log::info!("RPC server started on {addr}, port {port}");
 
@@ -45,6 +63,12 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
         return Err(anyhow::anyhow!("Usage limiter initialization failed: {e}"));
     }
 
+    // Start delivering webhook notifications if configured
+    if let Err(e) = init_webhook_dispatcher() {
+        log::error!("Failed to initialize webhook dispatcher: {e}");
+        return Err(anyhow::anyhow!("Webhook dispatcher initialization failed: {e}"));
+    }
+
     // Build middleware stack with tracing and CORS
     let cors = CorsLayer::new()
         .allow_origin(tower_http::cors::Any)
@@ -54,6 +78,8 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
             header::HeaderName::from_static(X_API_KEY),
             header::HeaderName::from_static(X_HMAC_SIGNATURE),
             header::HeaderName::from_static(X_TIMESTAMP),
+            header::HeaderName::from_static(X_ADMIN_API_KEY),
+            header::AUTHORIZATION,
         ])
         .max_age(Duration::from_secs(3600));
 
@@ -63,40 +89,91 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
     let rpc_client = rpc.get_rpc_client().clone();
 
     let (metrics_handle, metrics_layers, balance_tracker_handle) =
-        run_metrics_server_if_required(port, rpc_client).await?;
+        run_metrics_server_if_required(port, rpc_client.clone()).await?;
+    let push_gateway_handle = start_push_gateway_if_required()?;
+
+    let admin_api_key = get_admin_api_key(&config.kora.auth);
 
     // Build whitelist of allowed methods from enabled_methods config
-    let allowed_methods = config.kora.enabled_methods.get_enabled_method_names();
+    let mut allowed_methods = config.kora.enabled_methods.get_enabled_method_names();
+    if admin_api_key.is_some() {
+        allowed_methods.extend(ADMIN_METHODS.iter().map(|method| method.to_string()));
+    }
+
+    // Solana RPC methods forwarded to the backend RPC when the proxy is enabled
+    let proxy_methods = config.kora.get_proxy_method_names();
+    let proxy_layer = if proxy_methods.is_empty() {
+        None
+    } else {
+        log::info!("Proxying {} to the backend RPC", proxy_methods.join(", "));
+        allowed_methods.extend(proxy_methods.iter().cloned());
+        Some(ProxyLayer::new(rpc_client.url(), proxy_methods)?)
+    };
+
+    // Header based authentication, shared by HTTP requests and WebSocket upgrades
+    let auth_layers = tower::ServiceBuilder::new()
+        // Add authentication layer for API key if configured
+        .option_layer(
+            (get_value_by_priority("KORA_API_KEY", config.kora.auth.api_key.clone()))
+                .map(ApiKeyAuthLayer::new),
+        )
+        // Add authentication layer for HMAC if configured
+        .option_layer(
+            (get_value_by_priority("KORA_HMAC_SECRET", config.kora.auth.hmac_secret.clone()))
+                .map(|secret| HmacAuthLayer::new(secret, config.kora.auth.max_timestamp_age)),
+        )
+        // Add authentication layer for JWT Bearer tokens if configured
+        .option_layer(JwtAuthLayer::from_config(&config.kora.auth)?)
+        .into_inner();
+
+    // The server wide limit is raised to fit a full signTransactionBatch request, every other
+    // method stays under max_request_body_size
+    let max_request_body_size = config.kora.get_max_request_body_size();
+    let request_body_limit_layer = (max_request_body_size > config.kora.max_request_body_size)
+        .then(|| RequestBodyLimitLayer::new(config.kora.max_request_body_size));
+    let max_request_body_size = u32::try_from(max_request_body_size).map_err(|_| {
+        anyhow::anyhow!("max_request_body_size of {} bytes exceeds u32::MAX", max_request_body_size)
+    })?;
 
     let middleware = tower::ServiceBuilder::new()
         // Add metrics handler first (before other layers) so it can intercept /metrics
         .layer(ProxyGetRequestLayer::new("/liveness", "liveness")?)
-        .layer(RateLimitLayer::new(config.kora.rate_limit, Duration::from_secs(1)))
+        .layer(RequestRateLimitLayer::new(config.kora.rate_limit, Duration::from_secs(1)))
+        // Answer /healthz with backend checks and a 200/503 status for load balancer probes. Below
+        // the rate limiter, every probe hits the RPC, Redis and signer backends
+        .option_layer(
+            config.kora.enabled_methods.liveness.then(|| HealthzHandlerLayer::new(rpc_client)),
+        )
         // Add metrics handler layer for Prometheus metrics
         .option_layer(
             metrics_layers.as_ref().and_then(|layers| layers.metrics_handler_layer.clone()),
         )
         .layer(cors)
+        // Hold non-batch requests to max_request_body_size
+        .option_layer(request_body_limit_layer)
+        // Count every transaction of a signTransactionBatch against the rate limit
+        .layer(BatchRateLimitLayer)
         // Method validation layer -  to fail fast
         .layer(MethodValidationLayer::new(allowed_methods.clone()))
         // Add metrics collection layer
         .option_layer(metrics_layers.as_ref().and_then(|layers| layers.http_metrics_layer.clone()))
-        // Add authentication layer for API key if configured
-        .option_layer(
-            (get_value_by_priority("KORA_API_KEY", config.kora.auth.api_key.clone()))
-                .map(ApiKeyAuthLayer::new),
-        )
-        // Add authentication layer for HMAC if configured
-        .option_layer(
-            (get_value_by_priority("KORA_HMAC_SECRET", config.kora.auth.hmac_secret.clone()))
-                .map(|secret| HmacAuthLayer::new(secret, config.kora.auth.max_timestamp_age)),
-        );
+        .layer(auth_layers.clone())
+        // Count transactions against the JWT subject of the request in the usage limiter
+        .layer(AuthenticatedCallerLayer)
+        // Answer POST /admin/signers once it passed the same checks as the addSigner method
+        .option_layer(admin_api_key.clone().map(AdminSignersHandlerLayer::new))
+        // Admin methods additionally require the admin API key
+        .option_layer(admin_api_key.map(AdminApiKeyAuthLayer::new))
+        // Forward allowlisted Solana RPC methods once the request is authenticated
+        .option_layer(proxy_layer)
+        // Answer rate limited calls with 429 and Retry-After so HTTP clients can back off
+        .layer(RateLimitedResponseLayer);
 
     // Configure and build the server with HTTP support
     let server = ServerBuilder::default()
-        .max_request_body_size(config.kora.max_request_body_size as u32)
+        .max_request_body_size(max_request_body_size)
         .set_middleware(middleware)
-        .http_only() // Explicitly enable HTTP
+        .http_only()
         .build(addr)
         .await?;
 
@@ -107,7 +184,34 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
         .start(rpc_module)
         .map_err(|e| anyhow::anyhow!("Failed to start RPC server: {}", e))?;
 
-    Ok(ServerHandles { rpc_handle, metrics_handle, balance_tracker_handle })
+    // Messages on a WebSocket connection skip the middleware, only the upgrade request goes
+    // through it. Subscriptions get their own server and module so no other method is reachable
+    // without method validation, rate limiting and HMAC
+    let ws_handle = if config.kora.enabled_methods.subscribe_transaction_events {
+        let ws_addr = SocketAddr::from(([0, 0, 0, 0], ws_port));
+        let ws_middleware = tower::ServiceBuilder::new()
+            .layer(RateLimitLayer::new(config.kora.rate_limit, Duration::from_secs(1)))
+            .layer(auth_layers);
+        let ws_server =
+            ServerBuilder::default().set_middleware(ws_middleware).ws_only().build(ws_addr).await?;
+        log::info!("WebSocket server started on {ws_addr}, port {ws_port}");
+
+        Some(
+            ws_server
+                .start(build_ws_rpc_module()?)
+                .map_err(|e| anyhow::anyhow!("Failed to start WebSocket server: {}", e))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ServerHandles {
+        rpc_handle,
+        ws_handle,
+        metrics_handle,
+        balance_tracker_handle,
+        push_gateway_handle,
+    })
 }
 
 macro_rules! register_method_if_enabled {
@@ -135,13 +239,27 @@ macro_rules! register_method_if_enabled {
                 });
         }
     };
+
+    // For methods whose parameters may be omitted entirely
+    ($module:expr, $enabled_methods:expr, $field:ident, $method_name:expr, $rpc_method:ident, with_optional_params) => {
+        if $enabled_methods.$field {
+            let _ =
+                $module.register_async_method($method_name, |rpc_params, rpc_context| async move {
+                    let rpc = rpc_context.as_ref();
+                    let params = rpc_params.parse::<Option<_>>()?.unwrap_or_default();
+                    rpc.$rpc_method(params).await.map_err(Into::into)
+                });
+        }
+    };
 }
 
-fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
+pub(crate) fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
     let mut module = RpcModule::new(rpc.clone());
-    let enabled_methods = &get_config()?.kora.enabled_methods;
+    let config = get_config()?;
+    let enabled_methods = &config.kora.enabled_methods;
 
     register_method_if_enabled!(module, enabled_methods, liveness, "liveness", liveness);
+    register_method_if_enabled!(module, enabled_methods, liveness, "healthz", healthz);
 
     register_method_if_enabled!(
         module,
@@ -151,6 +269,14 @@ fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
         estimate_transaction_fee,
         with_params
     );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        estimate_transaction_fee,
+        "estimateTransactionFeeTemplate",
+        estimate_transaction_fee_template,
+        with_params
+    );
     register_method_if_enabled!(
         module,
         enabled_methods,
@@ -165,6 +291,13 @@ fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
         "getPayerSigner",
         get_payer_signer
     );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_payer_signer,
+        "kora_getSignerPublicKey",
+        get_signer_public_key
+    );
     register_method_if_enabled!(
         module,
         enabled_methods,
@@ -194,9 +327,124 @@ fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
         enabled_methods,
         get_blockhash,
         "getBlockhash",
-        get_blockhash
+        get_blockhash,
+        with_optional_params
     );
     register_method_if_enabled!(module, enabled_methods, get_config, "getConfig", get_config);
+    register_method_if_enabled!(module, enabled_methods, get_version, "getVersion", get_version);
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_instruction_template,
+        "getInstructionTemplate",
+        get_instruction_template,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_token_metadata,
+        "getTokenMetadata",
+        get_token_metadata
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_account_info,
+        "kora_getAccountInfo",
+        get_account_info,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_network_status,
+        "kora_getNetworkStatus",
+        get_network_status
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_network_status,
+        "kora_isNetworkCongested",
+        is_network_congested
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        sign_message,
+        "signMessage",
+        sign_message,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        sign_transaction_batch,
+        "signTransactionBatch",
+        sign_transaction_batch,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        simulate_transaction,
+        "kora_simulateTransaction",
+        simulate_transaction,
+        with_params
+    );
+
+    if get_admin_api_key(&config.kora.auth).is_some() {
+        let _ = module.register_async_method("addSigner", |rpc_params, rpc_context| async move {
+            let rpc = rpc_context.as_ref();
+            let params = rpc_params.parse()?;
+            rpc.add_signer(params).await.map_err(Into::into)
+        });
+        let _ =
+            module.register_async_method("kora_getDeadLetterQueue", |_, rpc_context| async move {
+                let rpc = rpc_context.as_ref();
+                rpc.get_dead_letter_queue().await.map_err(Into::into)
+            });
+        let _ = module.register_async_method(
+            "kora_getProgramAccounts",
+            |rpc_params, rpc_context| async move {
+                let rpc = rpc_context.as_ref();
+                let params = rpc_params.parse::<Option<_>>()?.unwrap_or_default();
+                rpc.get_program_accounts(params).await.map_err(Into::into)
+            },
+        );
+        #[cfg(feature = "dex")]
+        let _ = module.register_async_method(
+            "kora_placeMarketOrder",
+            |rpc_params, rpc_context| async move {
+                let rpc = rpc_context.as_ref();
+                let params = rpc_params.parse()?;
+                rpc.place_market_order(params).await.map_err(Into::into)
+            },
+        );
+        let _ = module.register_async_method(
+            "kora_setLogLevel",
+            |rpc_params, rpc_context| async move {
+                let rpc = rpc_context.as_ref();
+                let params = rpc_params.parse()?;
+                rpc.set_log_level(params).await.map_err(Into::into)
+            },
+        );
+    }
+
+    Ok(module)
+}
+
+/// Module of the WebSocket server, only the transaction events subscription. Calls over the
+/// connection bypass the HTTP middleware, so no other method may be registered here
+pub(crate) fn build_ws_rpc_module() -> Result<RpcModule<()>, anyhow::Error> {
+    let mut module = RpcModule::new(());
+    module.register_subscription(
+        "kora_subscribeTransactionEvents",
+        "kora_transactionEvent",
+        "kora_unsubscribeTransactionEvents",
+        |params, sink, _| subscribe_transaction_events(params, sink),
+    )?;
 
     Ok(module)
 }
@@ -206,13 +454,21 @@ mod tests {
     use super::*;
     use crate::{
         config::EnabledMethods,
+        rpc_server::events::{TransactionEventType, TRANSACTION_EVENT_BROKER},
         tests::{
-            common::setup_or_get_test_signer,
+            common::{setup_or_get_test_signer, TestHarness},
             config_mock::{ConfigMockBuilder, KoraConfigBuilder},
             rpc_mock::RpcMockBuilder,
+            transaction_mock::TransactionMockBuilder,
         },
+        transaction::TransactionUtil,
     };
+    use futures_util::{SinkExt, StreamExt};
+    use solana_message::{Message, VersionedMessage};
+    use solana_sdk::pubkey::Pubkey;
+    use solana_system_interface::instruction::transfer;
     use std::env;
+    use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
 
     #[test]
     fn test_get_value_by_priority_env_var_takes_precedence() {
@@ -259,16 +515,39 @@ mod tests {
         // Verify that the module has the expected methods
         let module = result.unwrap();
         let method_names: Vec<&str> = module.method_names().collect();
-        assert_eq!(method_names.len(), 9);
+        assert_eq!(method_names.len(), 21);
         assert!(method_names.contains(&"liveness"));
+        assert!(method_names.contains(&"healthz"));
         assert!(method_names.contains(&"estimateTransactionFee"));
+        assert!(method_names.contains(&"estimateTransactionFeeTemplate"));
         assert!(method_names.contains(&"getSupportedTokens"));
         assert!(method_names.contains(&"getPayerSigner"));
+        assert!(method_names.contains(&"kora_getSignerPublicKey"));
         assert!(method_names.contains(&"signTransaction"));
         assert!(method_names.contains(&"signAndSendTransaction"));
         assert!(method_names.contains(&"transferTransaction"));
         assert!(method_names.contains(&"getBlockhash"));
         assert!(method_names.contains(&"getConfig"));
+        assert!(method_names.contains(&"getVersion"));
+        assert!(method_names.contains(&"getInstructionTemplate"));
+        assert!(method_names.contains(&"getTokenMetadata"));
+        assert!(method_names.contains(&"kora_getAccountInfo"));
+        assert!(method_names.contains(&"kora_getNetworkStatus"));
+        assert!(method_names.contains(&"kora_isNetworkCongested"));
+        assert!(method_names.contains(&"signMessage"));
+        assert!(method_names.contains(&"signTransactionBatch"));
+        assert!(method_names.contains(&"kora_simulateTransaction"));
+    }
+
+    #[test]
+    fn test_build_ws_rpc_module_only_subscriptions() {
+        let module = build_ws_rpc_module().unwrap();
+        let mut method_names: Vec<&str> = module.method_names().collect();
+        method_names.sort_unstable();
+        assert_eq!(
+            method_names,
+            vec!["kora_subscribeTransactionEvents", "kora_unsubscribeTransactionEvents"]
+        );
     }
 
     #[test]
@@ -284,6 +563,15 @@ mod tests {
             get_blockhash: false,
             get_config: false,
             liveness: false,
+            subscribe_transaction_events: false,
+            get_version: false,
+            get_instruction_template: false,
+            get_token_metadata: false,
+            get_account_info: false,
+            get_network_status: false,
+            sign_message: false,
+            sign_transaction_batch: false,
+            simulate_transaction: false,
         };
 
         let kora_config = KoraConfigBuilder::new().with_enabled_methods(enabled_methods).build();
@@ -314,6 +602,15 @@ mod tests {
             sign_and_send_transaction: false,
             transfer_transaction: false,
             get_blockhash: false,
+            subscribe_transaction_events: false,
+            get_version: false,
+            get_instruction_template: false,
+            get_token_metadata: false,
+            get_account_info: false,
+            get_network_status: false,
+            sign_message: false,
+            sign_transaction_batch: false,
+            simulate_transaction: false,
         };
 
         let kora_config = KoraConfigBuilder::new().with_enabled_methods(enabled_methods).build();
@@ -331,9 +628,158 @@ mod tests {
         // Verify that only the expected methods are registered
         let module = result.unwrap();
         let method_names: Vec<&str> = module.method_names().collect();
-        assert_eq!(method_names.len(), 3);
+        assert_eq!(method_names.len(), 4);
         assert!(method_names.contains(&"liveness"));
+        assert!(method_names.contains(&"healthz"));
         assert!(method_names.contains(&"getConfig"));
         assert!(method_names.contains(&"getSupportedTokens"));
     }
+
+    #[test]
+    fn test_build_rpc_module_admin_methods_require_admin_key() {
+        let _ = setup_or_get_test_signer();
+
+        {
+            let _m = ConfigMockBuilder::new().build_and_setup();
+            let module = build_rpc_module(KoraRpc::new(RpcMockBuilder::new().build())).unwrap();
+            for method in ADMIN_METHODS {
+                assert!(!module.method_names().any(|name| name == *method), "{method} registered");
+            }
+        }
+
+        let _m = ConfigMockBuilder::new()
+            .with_admin_api_key_auth("admin-key".to_string())
+            .build_and_setup();
+        let module = build_rpc_module(KoraRpc::new(RpcMockBuilder::new().build())).unwrap();
+        for method in ADMIN_METHODS {
+            assert!(module.method_names().any(|name| name == *method), "{method} not registered");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_transaction_events_over_websocket() {
+        let harness = TestHarness::builder().build().await.unwrap();
+
+        let (mut ws_stream, _) = connect_async(harness.ws_url()).await.unwrap();
+
+        let user = Pubkey::new_unique();
+        let subscribe_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "kora_subscribeTransactionEvents",
+            "params": { "user": user.to_string() }
+        });
+        ws_stream.send(WsMessage::text(subscribe_request.to_string())).await.unwrap();
+
+        let response = ws_stream.next().await.unwrap().unwrap();
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+        assert!(response.get("result").is_some(), "Subscription should be accepted: {response}");
+
+        // Emit the same event a successful signTransaction call publishes
+        let fee_payer = Pubkey::new_unique();
+        let instruction = transfer(&user, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+        TRANSACTION_EVENT_BROKER.publish_transaction_event(
+            &transaction,
+            &fee_payer,
+            TransactionEventType::Signed,
+            "encoded_transaction",
+            None,
+        );
+
+        let notification = tokio::time::timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .expect("Should receive a transaction event")
+            .unwrap()
+            .unwrap();
+        let notification: serde_json::Value =
+            serde_json::from_str(notification.to_text().unwrap()).unwrap();
+
+        assert_eq!(notification["method"], "kora_transactionEvent");
+        let event = &notification["params"]["result"];
+        assert_eq!(event["event_type"], "signed");
+        assert_eq!(event["user"], user.to_string());
+        assert_eq!(event["signer_pubkey"], fee_payer.to_string());
+        assert_eq!(event["transaction"], "encoded_transaction");
+
+        harness.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_websocket_only_serves_subscriptions() {
+        let harness = TestHarness::builder().build().await.unwrap();
+
+        // The HTTP server doesn't accept WebSocket upgrades
+        assert!(connect_async(format!("ws://{}", harness.addr())).await.is_err());
+
+        let (mut ws_stream, _) = connect_async(harness.ws_url()).await.unwrap();
+
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(setup_or_get_test_signer())
+            .with_sol_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000)
+            .build_encoded();
+        let sign_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "signTransaction",
+            "params": { "transaction": transaction }
+        });
+        ws_stream.send(WsMessage::text(sign_request.to_string())).await.unwrap();
+
+        let response = ws_stream.next().await.unwrap().unwrap();
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+        assert!(response.get("result").is_none(), "signTransaction served over WS: {response}");
+        assert_eq!(response["error"]["code"], -32601);
+
+        harness.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_transaction_events_without_admin_key() {
+        let config =
+            ConfigMockBuilder::new().with_admin_api_key_auth("admin-key".to_string()).build();
+        let harness = TestHarness::builder().with_config(config).build().await.unwrap();
+
+        let (mut ws_stream, _) = connect_async(harness.ws_url()).await.unwrap();
+
+        let subscribe_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "kora_subscribeTransactionEvents",
+            "params": { "user": Pubkey::new_unique().to_string() }
+        });
+        ws_stream.send(WsMessage::text(subscribe_request.to_string())).await.unwrap();
+
+        let response = ws_stream.next().await.unwrap().unwrap();
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+        assert!(response.get("result").is_some(), "Subscription should be accepted: {response}");
+
+        harness.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_transaction_events_rejects_invalid_user() {
+        let harness = TestHarness::builder().build().await.unwrap();
+
+        let (mut ws_stream, _) = connect_async(harness.ws_url()).await.unwrap();
+
+        let subscribe_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "kora_subscribeTransactionEvents",
+            "params": { "user": "not_a_pubkey" }
+        });
+        ws_stream.send(WsMessage::text(subscribe_request.to_string())).await.unwrap();
+
+        let response = ws_stream.next().await.unwrap().unwrap();
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], -32602);
+
+        harness.shutdown().await;
+    }
 }
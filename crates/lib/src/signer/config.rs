@@ -1,7 +1,16 @@
-use crate::{error::KoraError, sanitize_error, signer::utils::get_env_var_for_signer};
+use crate::{
+    constant::DEFAULT_SIGNER_UNHEALTHY_THRESHOLD,
+    error::KoraError,
+    sanitize_error,
+    signer::{
+        ledger::{HidLedgerTransport, LedgerSigner},
+        signer::{AwsKmsClient, AwsKmsSigner, KoraSigner},
+        utils::get_env_var_for_signer,
+    },
+};
 use serde::{Deserialize, Serialize};
 use solana_keychain::Signer;
-use std::{fmt, fs, path::Path};
+use std::{fmt, fs, path::Path, sync::Arc};
 
 /// Configuration for a pool of signers
 #[derive(Clone, Serialize, Deserialize)]
@@ -10,6 +19,42 @@ pub struct SignerPoolConfig {
     pub signer_pool: SignerPoolSettings,
     /// List of individual signer configurations
     pub signers: Vec<SignerConfig>,
+    /// Sign a test message with every signer at startup to catch bad credentials early
+    #[serde(default)]
+    pub verify_signing_capability: bool,
+    /// Refuse to start when the pool holds more signers than this
+    #[serde(default)]
+    pub max_signers: Option<usize>,
+    /// Signing operations each signer may run at once, requests beyond it are rejected with
+    /// `SignerBusy` unless `backpressure_timeout_ms` lets them queue. 0 (the default) means
+    /// unlimited
+    #[serde(default)]
+    pub concurrent_signing_limit: usize,
+    /// Periodic health checks taking failing signers out of selection
+    #[serde(default)]
+    pub health_check: SignerHealthCheckConfig,
+}
+
+/// Settings for the background signer health checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignerHealthCheckConfig {
+    /// Seconds between two rounds of health checks, 0 (the default) disables them
+    #[serde(default)]
+    pub interval_seconds: u64,
+    /// Consecutive failed checks after which a signer is skipped by the selection strategy,
+    /// a single passing check restores it
+    #[serde(default = "default_unhealthy_threshold")]
+    pub unhealthy_threshold: u32,
+}
+
+impl Default for SignerHealthCheckConfig {
+    fn default() -> Self {
+        Self { interval_seconds: 0, unhealthy_threshold: DEFAULT_SIGNER_UNHEALTHY_THRESHOLD }
+    }
+}
+
+fn default_unhealthy_threshold() -> u32 {
+    DEFAULT_SIGNER_UNHEALTHY_THRESHOLD
 }
 
 /// Settings for the signer pool behavior
@@ -27,6 +72,8 @@ pub enum SelectionStrategy {
     RoundRobin,
     Random,
     Weighted,
+    /// The signer with the fewest in-flight operations, ties go to the higher weight
+    LeastBusy,
 }
 
 impl fmt::Display for SelectionStrategy {
@@ -35,6 +82,7 @@ impl fmt::Display for SelectionStrategy {
             SelectionStrategy::RoundRobin => "round_robin",
             SelectionStrategy::Random => "random",
             SelectionStrategy::Weighted => "weighted",
+            SelectionStrategy::LeastBusy => "least_busy",
         };
         write!(f, "{s}")
     }
@@ -90,6 +138,25 @@ pub struct VaultSignerConfig {
     pub pubkey_env: String,
 }
 
+/// AWS KMS signer configuration, the key must be an `ECC_NIST_EDWARDS25519` signing key
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AwsKmsSignerConfig {
+    pub key_arn_env: String,
+    pub region_env: String,
+    /// Role assumed through STS before calling KMS, credentials come from the default AWS
+    /// provider chain otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assume_role_arn_env: Option<String>,
+}
+
+/// Ledger signer configuration, the device must be unlocked with the Solana app open
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LedgerSignerConfig {
+    /// Account of the `44'/501'/<account>'` derivation path
+    #[serde(default)]
+    pub account: u32,
+}
+
 /// Signer type-specific configuration
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -114,6 +181,16 @@ pub enum SignerTypeConfig {
         #[serde(flatten)]
         config: VaultSignerConfig,
     },
+    /// AWS KMS signer configuration
+    AwsKms {
+        #[serde(flatten)]
+        config: AwsKmsSignerConfig,
+    },
+    /// Ledger signer configuration
+    Ledger {
+        #[serde(flatten)]
+        config: LedgerSignerConfig,
+    },
 }
 
 impl SignerPoolConfig {
@@ -148,6 +225,7 @@ impl SignerPoolConfig {
 
         self.validate_signer_names()?;
         self.validate_strategy_weights()?;
+        self.validate_health_check()?;
 
         Ok(())
     }
@@ -189,12 +267,21 @@ impl SignerPoolConfig {
         }
         Ok(())
     }
+
+    pub fn validate_health_check(&self) -> Result<(), KoraError> {
+        if self.health_check.interval_seconds > 0 && self.health_check.unhealthy_threshold == 0 {
+            return Err(KoraError::ValidationError(
+                "health_check.unhealthy_threshold must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl SignerConfig {
     /// Build an external signer from configuration by resolving environment variables
-    pub async fn build_signer_from_config(config: &SignerConfig) -> Result<Signer, KoraError> {
-        match &config.config {
+    pub async fn build_signer_from_config(config: &SignerConfig) -> Result<KoraSigner, KoraError> {
+        let signer = match &config.config {
             SignerTypeConfig::Memory { config: memory_config } => {
                 Self::build_memory_signer(memory_config, &config.name)
             }
@@ -207,7 +294,15 @@ impl SignerConfig {
             SignerTypeConfig::Vault { config: vault_config } => {
                 Self::build_vault_signer(vault_config, &config.name)
             }
-        }
+            SignerTypeConfig::AwsKms { config: aws_kms_config } => {
+                return Self::build_aws_kms_signer(aws_kms_config, &config.name).await;
+            }
+            SignerTypeConfig::Ledger { config: ledger_config } => {
+                return Self::build_ledger_signer(ledger_config, &config.name).await;
+            }
+        };
+
+        signer.map(KoraSigner::from)
     }
 
     fn build_memory_signer(
@@ -281,6 +376,48 @@ impl SignerConfig {
         })
     }
 
+    async fn build_aws_kms_signer(
+        config: &AwsKmsSignerConfig,
+        signer_name: &str,
+    ) -> Result<KoraSigner, KoraError> {
+        let key_arn = get_env_var_for_signer(&config.key_arn_env, signer_name)?;
+        let region = get_env_var_for_signer(&config.region_env, signer_name)?;
+        let assume_role_arn = config
+            .assume_role_arn_env
+            .as_ref()
+            .map(|env| get_env_var_for_signer(env, signer_name))
+            .transpose()?;
+
+        let client = AwsKmsClient::new(region, assume_role_arn).await;
+        let signer = AwsKmsSigner::new(key_arn, Arc::new(client)).await.map_err(|e| {
+            KoraError::SigningError(format!(
+                "Failed to create AWS KMS signer '{signer_name}': {}",
+                sanitize_error!(e)
+            ))
+        })?;
+
+        Ok(KoraSigner::AwsKms(signer))
+    }
+
+    async fn build_ledger_signer(
+        config: &LedgerSignerConfig,
+        signer_name: &str,
+    ) -> Result<KoraSigner, KoraError> {
+        let signer = async {
+            let transport = HidLedgerTransport::open()?;
+            LedgerSigner::new(config.account, Box::new(transport)).await
+        }
+        .await
+        .map_err(|e| {
+            KoraError::SigningError(format!(
+                "Failed to create Ledger signer '{signer_name}': {}",
+                sanitize_error!(e)
+            ))
+        })?;
+
+        Ok(KoraSigner::Ledger(signer))
+    }
+
     /// Validate an individual signer configuration
     pub fn validate_individual_signer_config(&self, index: usize) -> Result<(), KoraError> {
         if self.name.is_empty() {
@@ -296,6 +433,10 @@ impl SignerConfig {
             }
             SignerTypeConfig::Privy { config } => Self::validate_privy_config(config, &self.name),
             SignerTypeConfig::Vault { config } => Self::validate_vault_config(config, &self.name),
+            SignerTypeConfig::AwsKms { config } => {
+                Self::validate_aws_kms_config(config, &self.name)
+            }
+            SignerTypeConfig::Ledger { config } => Self::validate_ledger_config(config, &self.name),
         }
     }
 
@@ -373,6 +514,40 @@ impl SignerConfig {
         }
         Ok(())
     }
+
+    fn validate_aws_kms_config(
+        config: &AwsKmsSignerConfig,
+        signer_name: &str,
+    ) -> Result<(), KoraError> {
+        let env_vars = [
+            ("key_arn_env", Some(&config.key_arn_env)),
+            ("region_env", Some(&config.region_env)),
+            ("assume_role_arn_env", config.assume_role_arn_env.as_ref()),
+        ];
+
+        for (field_name, env_var) in env_vars {
+            if env_var.is_some_and(|env_var| env_var.is_empty()) {
+                return Err(KoraError::ValidationError(format!(
+                    "AWS KMS signer '{signer_name}' must specify non-empty {field_name}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_ledger_config(
+        config: &LedgerSignerConfig,
+        signer_name: &str,
+    ) -> Result<(), KoraError> {
+        // The index is hardened, which takes its top bit
+        if config.account >= 1 << 31 {
+            return Err(KoraError::ValidationError(format!(
+                "Ledger signer '{signer_name}' has an account above {}",
+                (1u32 << 31) - 1
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -432,6 +607,10 @@ weight = 2
                     config: MemorySignerConfig { private_key_env: "TEST_PRIVATE_KEY".to_string() },
                 },
             }],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
         };
 
         assert!(config.validate_signer_config().is_ok());
@@ -443,6 +622,10 @@ weight = 2
         let config = SignerPoolConfig {
             signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
             signers: vec![],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
         };
 
         assert!(config.validate_signer_config().is_err());
@@ -472,9 +655,112 @@ weight = 2
                     },
                 },
             ],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
+        };
+
+        assert!(config.validate_signer_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_aws_kms_config() {
+        let toml_content = r#"
+[signer_pool]
+strategy = "round_robin"
+
+[[signers]]
+name = "kms_signer"
+type = "aws_kms"
+key_arn_env = "KMS_KEY_ARN"
+region_env = "KMS_REGION"
+assume_role_arn_env = "KMS_ROLE_ARN"
+"#;
+
+        let mut config: SignerPoolConfig = toml::from_str(toml_content).unwrap();
+        assert!(config.validate_signer_config().is_ok());
+
+        if let SignerTypeConfig::AwsKms { config } = &config.signers[0].config {
+            assert_eq!(config.key_arn_env, "KMS_KEY_ARN");
+            assert_eq!(config.region_env, "KMS_REGION");
+            assert_eq!(config.assume_role_arn_env.as_deref(), Some("KMS_ROLE_ARN"));
+        } else {
+            panic!("Expected AWS KMS signer config");
+        }
+
+        config.signers[0].config = SignerTypeConfig::AwsKms {
+            config: AwsKmsSignerConfig {
+                key_arn_env: "KMS_KEY_ARN".to_string(),
+                region_env: String::new(),
+                assume_role_arn_env: None,
+            },
         };
+        assert!(config.validate_signer_config().is_err());
+    }
 
+    #[test]
+    fn test_parse_ledger_config() {
+        let toml_content = r#"
+[signer_pool]
+strategy = "round_robin"
+
+[[signers]]
+name = "ledger_signer"
+type = "ledger"
+account = 2
+
+[[signers]]
+name = "default_account_ledger_signer"
+type = "ledger"
+"#;
+
+        let mut config: SignerPoolConfig = toml::from_str(toml_content).unwrap();
+        assert!(config.validate_signer_config().is_ok());
+
+        let accounts: Vec<u32> = config
+            .signers
+            .iter()
+            .map(|signer| match &signer.config {
+                SignerTypeConfig::Ledger { config } => config.account,
+                _ => panic!("Expected Ledger signer config"),
+            })
+            .collect();
+        assert_eq!(accounts, vec![2, 0]);
+
+        config.signers[0].config =
+            SignerTypeConfig::Ledger { config: LedgerSignerConfig { account: 1 << 31 } };
+        assert!(config.validate_signer_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_health_check_config() {
+        let toml_content = r#"
+[signer_pool]
+strategy = "round_robin"
+
+[health_check]
+interval_seconds = 30
+
+[[signers]]
+name = "test_signer"
+type = "memory"
+private_key_env = "TEST_PRIVATE_KEY"
+"#;
+
+        let mut config: SignerPoolConfig = toml::from_str(toml_content).unwrap();
+        assert!(config.validate_signer_config().is_ok());
+        assert_eq!(config.health_check.interval_seconds, 30);
+        assert_eq!(config.health_check.unhealthy_threshold, DEFAULT_SIGNER_UNHEALTHY_THRESHOLD);
+
+        config.health_check.unhealthy_threshold = 0;
         assert!(config.validate_signer_config().is_err());
+
+        // Without the section health checks are disabled
+        let config: SignerPoolConfig =
+            toml::from_str(&toml_content.replace("[health_check]\ninterval_seconds = 30\n", ""))
+                .unwrap();
+        assert_eq!(config.health_check.interval_seconds, 0);
     }
 
     #[test]
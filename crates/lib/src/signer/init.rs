@@ -2,7 +2,8 @@ use crate::{
     error::KoraError,
     rpc_server::RpcArgs,
     signer::{SignerPool, SignerPoolConfig},
-    state::init_signer_pool,
+    state::{get_signer_pool, init_signer_pool},
+    validator::signer_validator::SignerValidator,
 };
 
 /// Initialize signer(s) based on RPC args - supports multi-signer mode or skip signers
@@ -17,10 +18,12 @@ pub async fn init_signers(args: &RpcArgs) -> Result<(), KoraError> {
         log::info!("Initializing multi-signer mode from config: {}", config_path.display());
 
         let config = SignerPoolConfig::load_config(config_path)?;
-        let pool = SignerPool::from_config(config).await?;
+        let pool = build_signer_pool(config).await?;
 
         init_signer_pool(pool)?;
         log::info!("Multi-signer pool initialized successfully");
+
+        get_signer_pool()?.start_health_checks();
     } else {
         return Err(KoraError::ValidationError(
             "Signers configuration is required unless using --no-load-signer".to_string(),
@@ -30,15 +33,39 @@ pub async fn init_signers(args: &RpcArgs) -> Result<(), KoraError> {
     Ok(())
 }
 
+/// Load every configured signer, then enforce `max_signers` and the optional signing check
+async fn build_signer_pool(config: SignerPoolConfig) -> Result<SignerPool, KoraError> {
+    let verify_signing_capability = config.verify_signing_capability;
+    let max_signers = config.max_signers;
+    let pool = SignerPool::from_config(config).await?;
+
+    let signer_count = pool.get_signers_info().len();
+    if let Some(max_signers) = max_signers {
+        if signer_count > max_signers {
+            return Err(KoraError::ValidationError(format!(
+                "Signer pool has {signer_count} signers, more than max_signers ({max_signers})"
+            )));
+        }
+    }
+
+    if verify_signing_capability {
+        log::info!("Verifying signing capability of all signers");
+        SignerValidator::verify_signing_capability(&pool).await?;
+    }
+
+    Ok(pool)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         log::LoggingFormat,
         rpc_server::args::{AuthArgs, RpcArgs},
-        tests::config_mock::ConfigMockBuilder,
+        tests::config_mock::{ConfigMockBuilder, SignerPoolConfigBuilder},
     };
-    use std::path::PathBuf;
+    use solana_sdk::signature::Keypair;
+    use std::{path::PathBuf, sync::Arc};
 
     #[tokio::test]
     async fn test_init_signers_skip_signer() {
@@ -46,6 +73,7 @@ mod tests {
 
         let args = RpcArgs {
             port: 8080,
+            ws_port: 8081,
             logging_format: LoggingFormat::Standard,
             signers_config: None,
             skip_signer: true,
@@ -62,6 +90,7 @@ mod tests {
 
         let args = RpcArgs {
             port: 8080,
+            ws_port: 8081,
             logging_format: LoggingFormat::Standard,
             signers_config: None,
             skip_signer: false,
@@ -79,6 +108,7 @@ mod tests {
 
         let args = RpcArgs {
             port: 8080,
+            ws_port: 8081,
             logging_format: LoggingFormat::Standard,
             signers_config: Some(PathBuf::from("/nonexistent/config.toml")),
             skip_signer: false,
@@ -88,4 +118,41 @@ mod tests {
         let result = init_signers(&args).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_build_signer_pool_exceeds_max_signers() {
+        let mut builder = SignerPoolConfigBuilder::new().with_max_signers(10);
+        for i in 0..11 {
+            let env_var = format!("MAX_SIGNERS_TEST_PRIVATE_KEY_{i}");
+            std::env::set_var(&env_var, Keypair::new().to_base58_string());
+            builder = builder.with_memory_signer(format!("signer_{i}"), env_var, Some(1));
+        }
+
+        let result = build_signer_pool(builder.build()).await;
+
+        assert_eq!(
+            result.err(),
+            Some(KoraError::ValidationError(
+                "Signer pool has 11 signers, more than max_signers (10)".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_signer_pool_with_health_checks() {
+        std::env::set_var("HEALTH_CHECK_TEST_PRIVATE_KEY", Keypair::new().to_base58_string());
+        let config = SignerPoolConfigBuilder::new()
+            .with_memory_signer(
+                "signer".to_string(),
+                "HEALTH_CHECK_TEST_PRIVATE_KEY".to_string(),
+                Some(1),
+            )
+            .with_health_check(60, 2)
+            .build();
+
+        let pool = Arc::new(build_signer_pool(config).await.unwrap());
+        let handle = pool.start_health_checks().expect("health checks are enabled");
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
 }
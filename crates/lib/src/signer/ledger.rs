@@ -0,0 +1,518 @@
+//! Ledger hardware wallet signer, talking to the Solana app over HID
+//!
+//! Signing needs a confirmation on the device, so a Ledger signer signs one message at a time.
+
+use async_trait::async_trait;
+use hidapi::{HidApi, HidDevice};
+use mockall::automock;
+use parking_lot::Mutex;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use std::sync::Arc;
+
+use crate::{
+    error::KoraError,
+    sanitize_error,
+    signer::signer::{SignerError, SolanaSigner},
+};
+
+const LEDGER_VENDOR_ID: u16 = 0x2c97;
+const LEDGER_USAGE_PAGE: u16 = 0xffa0;
+const HID_PACKET_SIZE: usize = 64;
+/// Channel, tag and sequence index of every HID packet
+const HID_HEADER_SIZE: usize = 5;
+const HID_CHANNEL: u16 = 0x0101;
+const HID_TAG_APDU: u8 = 0x05;
+/// Long enough for the operator to review and approve a transaction on the device
+const HID_READ_TIMEOUT_MS: i32 = 120_000;
+
+const CLA: u8 = 0xe0;
+const INS_GET_APP_CONFIGURATION: u8 = 0x04;
+const INS_GET_PUBKEY: u8 = 0x05;
+const INS_SIGN_MESSAGE: u8 = 0x06;
+const P1_NON_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+/// The APDU continues a message sent by a previous one
+const P2_EXTEND: u8 = 0x01;
+/// More APDUs of the message follow
+const P2_MORE: u8 = 0x02;
+const MAX_APDU_DATA_SIZE: usize = 255;
+
+const SW_OK: u16 = 0x9000;
+const SW_USER_REJECTED: u16 = 0x6985;
+const SW_DEVICE_LOCKED: u16 = 0x5515;
+const SW_SECURITY_STATUS_NOT_SATISFIED: u16 = 0x6982;
+/// Returned by the dashboard or another app for Solana app instructions
+const SW_APP_NOT_OPEN: [u16; 5] = [0x6d00, 0x6d02, 0x6e00, 0x6e01, 0x6511];
+
+const HARDENED: u32 = 0x8000_0000;
+const BIP44_PURPOSE: u32 = 44;
+const SOLANA_COIN_TYPE: u32 = 501;
+
+/// Sends APDUs to a Ledger device, mocked in tests
+#[automock]
+pub trait LedgerTransport: Send {
+    /// Response data of `apdu` followed by the 2 byte status word
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, KoraError>;
+}
+
+/// First Ledger device found over USB HID
+pub struct HidLedgerTransport {
+    device: HidDevice,
+}
+
+impl HidLedgerTransport {
+    pub fn open() -> Result<Self, KoraError> {
+        let api = HidApi::new().map_err(|e| {
+            KoraError::SigningError(format!("Failed to access HID devices: {}", sanitize_error!(e)))
+        })?;
+        let info = api
+            .device_list()
+            .find(|device| {
+                device.vendor_id() == LEDGER_VENDOR_ID
+                    && (device.usage_page() == LEDGER_USAGE_PAGE || device.interface_number() == 0)
+            })
+            .ok_or_else(|| {
+                KoraError::SigningError(
+                    "No Ledger device found, check that it is plugged in".to_string(),
+                )
+            })?;
+        let device = info.open_device(&api).map_err(|e| {
+            KoraError::SigningError(format!("Failed to open Ledger device: {}", sanitize_error!(e)))
+        })?;
+
+        Ok(Self { device })
+    }
+
+    fn write_apdu(&self, apdu: &[u8]) -> Result<(), KoraError> {
+        let mut payload = (apdu.len() as u16).to_be_bytes().to_vec();
+        payload.extend_from_slice(apdu);
+
+        for (sequence, chunk) in payload.chunks(HID_PACKET_SIZE - HID_HEADER_SIZE).enumerate() {
+            // hidapi expects the report id before the packet
+            let mut report = vec![0u8; HID_PACKET_SIZE + 1];
+            report[1..3].copy_from_slice(&HID_CHANNEL.to_be_bytes());
+            report[3] = HID_TAG_APDU;
+            report[4..6].copy_from_slice(&(sequence as u16).to_be_bytes());
+            report[6..6 + chunk.len()].copy_from_slice(chunk);
+
+            self.device.write(&report).map_err(|e| {
+                KoraError::SigningError(format!(
+                    "Failed to write to Ledger device: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn read_response(&self) -> Result<Vec<u8>, KoraError> {
+        let mut response = Vec::new();
+        let mut response_len = 0;
+        let mut sequence: u16 = 0;
+
+        loop {
+            let mut packet = [0u8; HID_PACKET_SIZE];
+            let read = self.device.read_timeout(&mut packet, HID_READ_TIMEOUT_MS).map_err(|e| {
+                KoraError::SigningError(format!(
+                    "Failed to read from Ledger device: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+            if read == 0 {
+                return Err(KoraError::SigningError(
+                    "Timed out waiting for the Ledger device".to_string(),
+                ));
+            }
+            // The first packet also carries the response length
+            let header_size = if sequence == 0 { HID_HEADER_SIZE + 2 } else { HID_HEADER_SIZE };
+            if read < header_size
+                || packet[0..2] != HID_CHANNEL.to_be_bytes()
+                || packet[2] != HID_TAG_APDU
+                || packet[3..5] != sequence.to_be_bytes()
+            {
+                return Err(KoraError::SigningError(
+                    "Unexpected packet from Ledger device".to_string(),
+                ));
+            }
+
+            let mut data = &packet[HID_HEADER_SIZE..read];
+            if sequence == 0 {
+                response_len = u16::from_be_bytes([data[0], data[1]]) as usize;
+                data = &data[2..];
+            }
+            response.extend_from_slice(data);
+
+            if response.len() >= response_len {
+                response.truncate(response_len);
+                return Ok(response);
+            }
+            sequence += 1;
+        }
+    }
+}
+
+impl LedgerTransport for HidLedgerTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, KoraError> {
+        self.write_apdu(apdu)?;
+        self.read_response()
+    }
+}
+
+/// Signer for the `44'/501'/<account>'` key of the Ledger Solana app
+pub struct LedgerSigner {
+    derivation_path: Vec<u32>,
+    pubkey: Pubkey,
+    transport: Arc<Mutex<Box<dyn LedgerTransport>>>,
+}
+
+impl LedgerSigner {
+    /// Check that the device is unlocked with the Solana app open, then read the account pubkey
+    pub async fn new(account: u32, transport: Box<dyn LedgerTransport>) -> Result<Self, KoraError> {
+        let derivation_path =
+            vec![BIP44_PURPOSE | HARDENED, SOLANA_COIN_TYPE | HARDENED, account | HARDENED];
+        let transport = Arc::new(Mutex::new(transport));
+
+        let path = derivation_path.clone();
+        let pubkey = Self::run(&transport, move |transport| {
+            send_apdu(transport, INS_GET_APP_CONFIGURATION, P1_NON_CONFIRM, 0, &[])?;
+            send_apdu(transport, INS_GET_PUBKEY, P1_NON_CONFIRM, 0, &serialize_path(&path))
+        })
+        .await?;
+        let pubkey = Pubkey::try_from(pubkey.as_slice()).map_err(|_| {
+            KoraError::SigningError(format!(
+                "Ledger returned a {} byte public key, expected 32",
+                pubkey.len()
+            ))
+        })?;
+
+        Ok(Self { derivation_path, pubkey, transport })
+    }
+
+    /// Run `f` on a blocking thread with exclusive access to the device, so the APDUs of one
+    /// message are never interleaved with another's
+    async fn run<T, F>(
+        transport: &Arc<Mutex<Box<dyn LedgerTransport>>>,
+        f: F,
+    ) -> Result<T, KoraError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn LedgerTransport) -> Result<T, KoraError> + Send + 'static,
+    {
+        let transport = Arc::clone(transport);
+        tokio::task::spawn_blocking(move || f(transport.lock().as_ref())).await.map_err(|e| {
+            KoraError::InternalServerError(format!("Ledger task failed: {}", sanitize_error!(e)))
+        })?
+    }
+}
+
+#[async_trait]
+impl SolanaSigner for LedgerSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<Signature, SignerError> {
+        let num_signers = tx.message.header.num_required_signatures as usize;
+        let position = tx.message.account_keys[..num_signers]
+            .iter()
+            .position(|key| *key == self.pubkey)
+            .ok_or_else(|| {
+                SignerError::SigningFailed(format!(
+                    "{} is not a signer of the transaction",
+                    self.pubkey
+                ))
+            })?;
+
+        let signature = self.sign_message(&tx.message_data()).await?;
+        tx.signatures.resize(num_signers, Signature::default());
+        tx.signatures[position] = signature;
+        Ok(signature)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let path = serialize_path(&self.derivation_path);
+        let message = message.to_vec();
+        let bytes = Self::run(&self.transport, move |transport| {
+            sign_message_apdus(transport, &path, &message)
+        })
+        .await
+        .map_err(|e| SignerError::SigningFailed(e.to_string()))?;
+
+        Signature::try_from(bytes.as_slice()).map_err(|_| {
+            SignerError::SigningFailed(format!(
+                "Ledger returned a {} byte signature, expected 64",
+                bytes.len()
+            ))
+        })
+    }
+
+    async fn is_available(&self) -> bool {
+        Self::run(&self.transport, |transport| {
+            send_apdu(transport, INS_GET_APP_CONFIGURATION, P1_NON_CONFIRM, 0, &[])
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// Derivation path as the Solana app expects it: the number of indexes, then each index big-endian
+fn serialize_path(path: &[u32]) -> Vec<u8> {
+    let mut serialized = vec![path.len() as u8];
+    for index in path {
+        serialized.extend_from_slice(&index.to_be_bytes());
+    }
+    serialized
+}
+
+/// Send the message in as many APDUs as it takes, the first one carrying the signer path
+fn sign_message_apdus(
+    transport: &dyn LedgerTransport,
+    path: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>, KoraError> {
+    // A single signer, then its derivation path
+    let mut first = vec![1u8];
+    first.extend_from_slice(path);
+    let (head, mut rest) = message.split_at(message.len().min(MAX_APDU_DATA_SIZE - first.len()));
+    first.extend_from_slice(head);
+
+    let p2 = if rest.is_empty() { 0 } else { P2_MORE };
+    let mut response = send_apdu(transport, INS_SIGN_MESSAGE, P1_CONFIRM, p2, &first)?;
+    while !rest.is_empty() {
+        let (chunk, remaining) = rest.split_at(rest.len().min(MAX_APDU_DATA_SIZE));
+        rest = remaining;
+        let p2 = if rest.is_empty() { P2_EXTEND } else { P2_EXTEND | P2_MORE };
+        response = send_apdu(transport, INS_SIGN_MESSAGE, P1_CONFIRM, p2, chunk)?;
+    }
+
+    Ok(response)
+}
+
+/// Response data of an APDU, with its status word turned into an error the operator can act on
+fn send_apdu(
+    transport: &dyn LedgerTransport,
+    ins: u8,
+    p1: u8,
+    p2: u8,
+    data: &[u8],
+) -> Result<Vec<u8>, KoraError> {
+    let mut apdu = vec![CLA, ins, p1, p2, data.len() as u8];
+    apdu.extend_from_slice(data);
+
+    let mut response = transport.exchange(&apdu)?;
+    if response.len() < 2 {
+        return Err(KoraError::SigningError("Ledger response has no status word".to_string()));
+    }
+    let status = response.split_off(response.len() - 2);
+    match u16::from_be_bytes([status[0], status[1]]) {
+        SW_OK => Ok(response),
+        SW_DEVICE_LOCKED | SW_SECURITY_STATUS_NOT_SATISFIED => Err(KoraError::SigningError(
+            "Ledger device is locked, unlock it and open the Solana app".to_string(),
+        )),
+        status if SW_APP_NOT_OPEN.contains(&status) => {
+            Err(KoraError::SigningError("Solana app is not open on the Ledger device".to_string()))
+        }
+        SW_USER_REJECTED => {
+            Err(KoraError::SigningError("Signing was rejected on the Ledger device".to_string()))
+        }
+        status => Err(KoraError::SigningError(format!("Ledger returned status {status:#06x}"))),
+    }
+}
+
+/// Speculos, the Ledger emulator, started with `--apdu-port`. Each APDU is prefixed with its
+/// big-endian u32 length, responses are the length of their data, the data and the status word
+#[cfg(feature = "ledger-emulator")]
+pub struct SpeculosTransport {
+    stream: Mutex<std::net::TcpStream>,
+}
+
+#[cfg(feature = "ledger-emulator")]
+impl SpeculosTransport {
+    pub fn connect(addr: &str) -> Result<Self, KoraError> {
+        let stream = std::net::TcpStream::connect(addr).map_err(|e| {
+            KoraError::SigningError(format!(
+                "Failed to connect to Speculos: {}",
+                sanitize_error!(e)
+            ))
+        })?;
+        Ok(Self { stream: Mutex::new(stream) })
+    }
+}
+
+#[cfg(feature = "ledger-emulator")]
+impl LedgerTransport for SpeculosTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, KoraError> {
+        use std::io::{Read, Write};
+
+        let io_error =
+            |e: std::io::Error| KoraError::SigningError(format!("Speculos exchange failed: {e}"));
+        let mut stream = self.stream.lock();
+
+        stream.write_all(&(apdu.len() as u32).to_be_bytes()).map_err(io_error)?;
+        stream.write_all(apdu).map_err(io_error)?;
+
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).map_err(io_error)?;
+        let mut response = vec![0u8; u32::from_be_bytes(len) as usize + 2];
+        stream.read_exact(&mut response).map_err(io_error)?;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{pool::SignerWithMetadata, signer::KoraSigner, SignerPool};
+    use solana_sdk::signature::{Keypair, Signer as _};
+
+    const OK: [u8; 2] = SW_OK.to_be_bytes();
+
+    fn with_status(mut data: Vec<u8>, status: u16) -> Vec<u8> {
+        data.extend_from_slice(&status.to_be_bytes());
+        data
+    }
+
+    /// Device with the Solana app open holding `keypair` at every derivation path
+    fn mock_ledger(keypair: Keypair) -> MockLedgerTransport {
+        let mut pending = Vec::new();
+        let mut transport = MockLedgerTransport::new();
+        transport.expect_exchange().returning(move |apdu| {
+            let (ins, p2, data) = (apdu[1], apdu[3], &apdu[5..]);
+            assert_eq!(apdu[4] as usize, data.len());
+
+            let response = match ins {
+                INS_GET_APP_CONFIGURATION => with_status(vec![0, 0, 1, 6, 0], SW_OK),
+                INS_GET_PUBKEY => with_status(keypair.pubkey().to_bytes().to_vec(), SW_OK),
+                INS_SIGN_MESSAGE => {
+                    if p2 & P2_EXTEND == 0 {
+                        // Signer count, path length, then 3 indexes
+                        assert_eq!(&data[..2], &[1, 3]);
+                        pending = data[2 + 3 * 4..].to_vec();
+                    } else {
+                        pending.extend_from_slice(data);
+                    }
+                    if p2 & P2_MORE != 0 {
+                        OK.to_vec()
+                    } else {
+                        let signature = keypair.sign_message(&std::mem::take(&mut pending));
+                        with_status(signature.as_ref().to_vec(), SW_OK)
+                    }
+                }
+                _ => panic!("Unexpected instruction {ins:#x}"),
+            };
+            Ok(response)
+        });
+        transport
+    }
+
+    /// Device answering every APDU with `status`
+    fn failing_ledger(status: u16) -> MockLedgerTransport {
+        let mut transport = MockLedgerTransport::new();
+        transport.expect_exchange().returning(move |_| Ok(status.to_be_bytes().to_vec()));
+        transport
+    }
+
+    #[test]
+    fn test_serialize_path() {
+        assert_eq!(
+            serialize_path(&[44 | HARDENED, 501 | HARDENED, 2 | HARDENED]),
+            vec![3, 0x80, 0, 0, 44, 0x80, 0, 0x01, 0xf5, 0x80, 0, 0, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer_sign_message() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let signer = LedgerSigner::new(0, Box::new(mock_ledger(keypair))).await.unwrap();
+        assert_eq!(signer.pubkey(), pubkey);
+        assert!(signer.is_available().await);
+
+        // Short enough for one APDU, and long enough to need three
+        for message in [vec![7u8; 100], vec![7u8; 600]] {
+            let signature = signer.sign_message(&message).await.unwrap();
+            assert!(signature.verify(pubkey.as_ref(), &message));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer_device_errors() {
+        let result = LedgerSigner::new(0, Box::new(failing_ledger(SW_DEVICE_LOCKED))).await;
+        assert!(
+            matches!(result, Err(KoraError::SigningError(message)) if message.contains("locked"))
+        );
+
+        let result = LedgerSigner::new(0, Box::new(failing_ledger(0x6e01))).await;
+        assert!(
+            matches!(result, Err(KoraError::SigningError(message)) if message.contains("Solana app"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer_user_rejection() {
+        let pubkey = Keypair::new().pubkey();
+        let mut transport = MockLedgerTransport::new();
+        transport.expect_exchange().returning(move |apdu| {
+            Ok(match apdu[1] {
+                INS_GET_PUBKEY => with_status(pubkey.to_bytes().to_vec(), SW_OK),
+                INS_SIGN_MESSAGE => SW_USER_REJECTED.to_be_bytes().to_vec(),
+                _ => OK.to_vec(),
+            })
+        });
+        let signer = LedgerSigner::new(0, Box::new(transport)).await.unwrap();
+
+        match signer.sign_message(b"message").await {
+            Err(SignerError::SigningFailed(message)) => assert!(message.contains("rejected")),
+            other => panic!("Expected SigningFailed, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer_is_single_concurrency_in_pool() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let signer = LedgerSigner::new(0, Box::new(mock_ledger(keypair))).await.unwrap();
+        let pool = SignerPool::new(vec![SignerWithMetadata::new(
+            "ledger_signer".to_string(),
+            Arc::new(KoraSigner::Ledger(signer)),
+            1,
+        )
+        .with_signing_limit(0)]);
+
+        let permit = pool.try_acquire_signing_permit(&pubkey).unwrap();
+        assert!(permit.is_some());
+        assert!(matches!(pool.try_acquire_signing_permit(&pubkey), Err(KoraError::SignerBusy(_))));
+
+        drop(permit);
+        assert!(pool.try_acquire_signing_permit(&pubkey).unwrap().is_some());
+    }
+}
+
+/// Runs against Speculos with the Solana app, e.g.
+/// `speculos --model nanosp --apdu-port 9999 --seed "<mnemonic>" solana.elf` with an automation
+/// rule approving transactions, and `cargo test --features ledger-emulator`.
+/// `SPECULOS_APDU_ADDR` overrides the default `127.0.0.1:9999`
+#[cfg(all(test, feature = "ledger-emulator"))]
+mod emulator_tests {
+    use super::*;
+    use solana_message::Message;
+    use solana_system_interface::instruction::transfer;
+
+    #[tokio::test]
+    async fn test_ledger_signer_against_speculos() {
+        let addr =
+            std::env::var("SPECULOS_APDU_ADDR").unwrap_or_else(|_| "127.0.0.1:9999".to_string());
+        let transport = SpeculosTransport::connect(&addr).unwrap();
+        let signer = LedgerSigner::new(0, Box::new(transport)).await.unwrap();
+        assert!(signer.is_available().await);
+
+        // The Solana app only signs messages it can parse as transactions
+        let pubkey = signer.pubkey();
+        let message = Message::new(&[transfer(&pubkey, &Pubkey::new_unique(), 1)], Some(&pubkey));
+        let mut transaction = Transaction::new_unsigned(message);
+        signer.sign_transaction(&mut transaction).await.unwrap();
+
+        assert!(transaction.verify().is_ok());
+    }
+}
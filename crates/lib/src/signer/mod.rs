@@ -1,14 +1,18 @@
 pub mod config;
+pub mod dlq;
 pub mod init;
 pub mod keypair_util;
+pub mod ledger;
 pub mod pool;
 pub mod signer;
 pub mod utils;
 
 pub use config::{
-    MemorySignerConfig, PrivySignerConfig, SelectionStrategy, SignerConfig, SignerPoolConfig,
-    SignerTypeConfig, TurnkeySignerConfig, VaultSignerConfig,
+    AwsKmsSignerConfig, LedgerSignerConfig, MemorySignerConfig, PrivySignerConfig,
+    SelectionStrategy, SignerConfig, SignerHealthCheckConfig, SignerPoolConfig, SignerTypeConfig,
+    TurnkeySignerConfig, VaultSignerConfig,
 };
+pub use dlq::{DeadLetterEntry, DeadLetterQueue};
 pub use keypair_util::KeypairUtil;
 pub use pool::{SignerInfo, SignerPool};
-pub use signer::SolanaSigner;
+pub use signer::{KoraSigner, SolanaSigner};
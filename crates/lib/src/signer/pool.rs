@@ -1,30 +1,64 @@
 use crate::{
+    constant::{DEFAULT_DEAD_LETTER_QUEUE_CAPACITY, SIGNER_HEALTH_CHECK_TIMEOUT_SECONDS},
     error::KoraError,
-    signer::config::{SelectionStrategy, SignerConfig, SignerPoolConfig},
+    metrics::SignerMetrics,
+    signer::{
+        config::{
+            SelectionStrategy, SignerConfig, SignerHealthCheckConfig, SignerPoolConfig,
+            SignerTypeConfig,
+        },
+        dlq::{DeadLetterEntry, DeadLetterQueue},
+        signer::KoraSigner,
+    },
 };
-use rand::Rng;
-use solana_keychain::{Signer, SolanaSigner};
+use parking_lot::{Mutex, RwLock};
+use prometheus::IntGauge;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use solana_keychain::SolanaSigner;
 use solana_sdk::pubkey::Pubkey;
 use std::{
+    cmp::Reverse,
     str::FromStr,
     sync::{
-        atomic::{AtomicU64, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
+};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    task::JoinHandle,
+    time::{interval, timeout},
 };
 
 const DEFAULT_WEIGHT: u32 = 1;
 
+/// `result` label values of the `signatures_total` metric
+const SIGNATURE_SUCCESS: &str = "success";
+const SIGNATURE_ERROR: &str = "error";
+
 /// Metadata associated with a signer in the pool
 pub(crate) struct SignerWithMetadata {
     /// Human-readable name for this signer
     name: String,
     /// The actual signer instance
-    signer: Arc<Signer>,
+    signer: Arc<KoraSigner>,
     /// Weight for weighted selection (higher = more likely to be selected)
     weight: u32,
     /// Timestamp of last use (Unix timestamp in seconds)
     last_used: AtomicU64,
+    /// Number of transactions signed successfully
+    tx_count: AtomicU64,
+    /// Timestamp of the last successful signature (Unix timestamp in seconds)
+    last_active: AtomicU64,
+    /// Number of times the selection strategy picked this signer
+    selection_count: AtomicU64,
+    /// Operations holding an `InFlightGuard` of this signer
+    in_flight: Arc<AtomicUsize>,
+    /// Permits for concurrent signing operations, `None` when unlimited
+    signing_permits: Option<Arc<Semaphore>>,
+    /// Outcome of the background health checks
+    health: Arc<SignerHealth>,
 }
 
 impl Clone for SignerWithMetadata {
@@ -34,36 +68,158 @@ impl Clone for SignerWithMetadata {
             signer: self.signer.clone(),
             weight: self.weight,
             last_used: AtomicU64::new(self.last_used.load(Ordering::Relaxed)),
+            tx_count: AtomicU64::new(self.tx_count.load(Ordering::Relaxed)),
+            last_active: AtomicU64::new(self.last_active.load(Ordering::Relaxed)),
+            selection_count: AtomicU64::new(self.selection_count.load(Ordering::Relaxed)),
+            in_flight: Arc::clone(&self.in_flight),
+            signing_permits: self.signing_permits.clone(),
+            health: Arc::clone(&self.health),
         }
     }
 }
 
 impl SignerWithMetadata {
     /// Create a new signer with metadata
-    pub(crate) fn new(name: String, signer: Arc<Signer>, weight: u32) -> Self {
-        Self { name, signer, weight, last_used: AtomicU64::new(0) }
+    pub(crate) fn new(name: String, signer: Arc<KoraSigner>, weight: u32) -> Self {
+        Self {
+            name,
+            signer,
+            weight,
+            last_used: AtomicU64::new(0),
+            tx_count: AtomicU64::new(0),
+            last_active: AtomicU64::new(0),
+            selection_count: AtomicU64::new(0),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            signing_permits: None,
+            health: Arc::new(SignerHealth::default()),
+        }
+    }
+
+    /// Allow at most `limit` concurrent signing operations, 0 means unlimited. Signers with a
+    /// limit of their own, like Ledger devices, never get more than it
+    pub(crate) fn with_signing_limit(mut self, limit: usize) -> Self {
+        let limit = match self.signer.max_concurrent_signings() {
+            Some(max) if limit == 0 || limit > max => max,
+            _ => limit,
+        };
+        self.signing_permits = (limit > 0).then(|| Arc::new(Semaphore::new(limit)));
+        self
     }
 
     /// Update the last used timestamp to current time
     fn update_last_used(&self) {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        self.last_used.store(now, Ordering::Relaxed);
+        self.last_used.store(unix_now(), Ordering::Relaxed);
+    }
+
+    /// Count a successful signature and mark the signer as active now
+    fn record_signature(&self) {
+        self.tx_count.fetch_add(1, Ordering::Relaxed);
+        self.last_active.store(unix_now(), Ordering::Relaxed);
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.health.healthy.load(Ordering::Acquire)
+    }
+}
+
+/// Health of a signer, unhealthy signers are skipped by the selection strategies
+struct SignerHealth {
+    healthy: AtomicBool,
+    /// Failed health checks since the last passing one
+    consecutive_failures: AtomicU32,
+}
+
+impl Default for SignerHealth {
+    fn default() -> Self {
+        Self { healthy: AtomicBool::new(true), consecutive_failures: AtomicU32::new(0) }
+    }
+}
+
+impl SignerHealth {
+    /// Mark the signer unhealthy after `unhealthy_threshold` failed checks in a row, healthy again
+    /// after a passing one
+    fn record_check(&self, name: &str, result: Result<(), KoraError>, unhealthy_threshold: u32) {
+        match result {
+            Ok(()) => {
+                self.consecutive_failures.store(0, Ordering::Release);
+                if !self.healthy.swap(true, Ordering::AcqRel) {
+                    log::info!("Signer {name} passed its health check, selecting it again");
+                }
+            }
+            Err(e) => {
+                let failures = self.consecutive_failures.fetch_add(1, Ordering::AcqRel) + 1;
+                log::warn!("Health check of signer {name} failed ({failures} in a row): {e}");
+                if failures >= unhealthy_threshold && self.healthy.swap(false, Ordering::AcqRel) {
+                    log::error!(
+                        "Signer {name} failed {failures} health checks in a row, skipping it until \
+                         it recovers"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A signing slot of a signer, released when dropped
+pub struct SigningPermit {
+    _permit: OwnedSemaphorePermit,
+    queue_depth: IntGauge,
+}
+
+impl SigningPermit {
+    fn new(permit: OwnedSemaphorePermit, signer_name: &str) -> Self {
+        let queue_depth = SignerMetrics::get().queue_depth.with_label_values(&[signer_name]);
+        queue_depth.inc();
+        Self { _permit: permit, queue_depth }
+    }
+}
+
+impl Drop for SigningPermit {
+    fn drop(&mut self) {
+        self.queue_depth.dec();
+    }
+}
+
+/// An outstanding operation of a signer, counted by the least-busy strategy until dropped
+pub struct InFlightGuard {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl InFlightGuard {
+    fn new(in_flight: &Arc<AtomicUsize>) -> Self {
+        in_flight.fetch_add(1, Ordering::AcqRel);
+        Self { in_flight: Arc::clone(in_flight) }
     }
 }
 
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 /// A pool of signers with different selection strategies
 pub struct SignerPool {
-    /// List of signers with their metadata
-    signers: Vec<SignerWithMetadata>,
+    /// List of signers with their metadata, signers can be added at runtime
+    signers: RwLock<Vec<SignerWithMetadata>>,
     /// Strategy for selecting signers
     strategy: SelectionStrategy,
     /// Current index for round-robin selection
     current_index: AtomicUsize,
-    /// Total weight of all signers in the pool
-    total_weight: u32,
+    /// Total weight of all signers in the pool, only updated while holding the `signers` write lock
+    total_weight: AtomicU32,
+    /// Concurrent signing operations allowed per signer, 0 means unlimited
+    concurrent_signing_limit: usize,
+    /// Most recent failed signing attempts, kept for debugging
+    dead_letter_queue: DeadLetterQueue,
+    /// Source of randomness for the random and weighted strategies
+    rng: Mutex<StdRng>,
+    /// Background health checks of the signers
+    health_check: SignerHealthCheckConfig,
 }
 
 /// Information about a signer for monitoring/debugging
@@ -73,18 +229,34 @@ pub struct SignerInfo {
     pub name: String,
     pub weight: u32,
     pub last_used: u64, // Unix timestamp
+    /// Transactions signed successfully since the pool was created
+    pub tx_count: u64,
+    /// Unix timestamp of the last successful signature, 0 if the signer never signed
+    pub last_active: u64,
+    /// Times the selection strategy picked this signer since the pool was created
+    pub selection_count: u64,
+    /// Operations currently in flight on this signer
+    pub in_flight: usize,
+    /// False once the signer failed `unhealthy_threshold` health checks in a row
+    pub healthy: bool,
+    /// Failed health checks since the last passing one
+    pub consecutive_health_check_failures: u32,
 }
 
 impl SignerPool {
     #[cfg(test)]
     pub(crate) fn new(signers: Vec<SignerWithMetadata>) -> Self {
-        let total_weight: u32 = signers.iter().map(|s| s.weight).sum();
+        let total_weight = Self::sum_weights(&signers).expect("signer weights overflow u32");
 
         Self {
-            signers,
+            signers: RwLock::new(signers),
             strategy: SelectionStrategy::RoundRobin,
             current_index: AtomicUsize::new(0),
-            total_weight,
+            total_weight: AtomicU32::new(total_weight),
+            concurrent_signing_limit: 0,
+            dead_letter_queue: DeadLetterQueue::new(DEFAULT_DEAD_LETTER_QUEUE_CAPACITY),
+            rng: Mutex::new(StdRng::from_os_rng()),
+            health_check: SignerHealthCheckConfig::default(),
         }
     }
 
@@ -102,11 +274,10 @@ impl SignerPool {
             let signer = SignerConfig::build_signer_from_config(&signer_config).await?;
             let weight = signer_config.weight.unwrap_or(DEFAULT_WEIGHT);
 
-            signers.push(SignerWithMetadata::new(
-                signer_config.name.clone(),
-                Arc::new(signer),
-                weight,
-            ));
+            signers.push(
+                SignerWithMetadata::new(signer_config.name.clone(), Arc::new(signer), weight)
+                    .with_signing_limit(config.concurrent_signing_limit),
+            );
 
             log::info!(
                 "Successfully initialized signer: {} (weight: {})",
@@ -115,7 +286,7 @@ impl SignerPool {
             );
         }
 
-        let total_weight: u32 = signers.iter().map(|s| s.weight).sum();
+        let total_weight = Self::sum_weights(&signers)?;
 
         if matches!(config.signer_pool.strategy, SelectionStrategy::Weighted) && total_weight == 0 {
             return Err(KoraError::InternalServerError(
@@ -130,49 +301,211 @@ impl SignerPool {
         );
 
         Ok(Self {
-            signers,
+            signers: RwLock::new(signers),
             strategy: config.signer_pool.strategy,
             current_index: AtomicUsize::new(0),
-            total_weight,
+            total_weight: AtomicU32::new(total_weight),
+            concurrent_signing_limit: config.concurrent_signing_limit,
+            dead_letter_queue: DeadLetterQueue::new(DEFAULT_DEAD_LETTER_QUEUE_CAPACITY),
+            rng: Mutex::new(StdRng::from_os_rng()),
+            health_check: config.health_check,
         })
     }
 
+    /// Seed the random and weighted strategies, making their selections reproducible
+    pub fn with_rng_seed(self, seed: u64) -> Self {
+        *self.rng.lock() = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Build a new signer from `config` and add it to the pool without a restart
+    pub async fn hot_add_signer(
+        &self,
+        config: SignerTypeConfig,
+        alias: String,
+        weight: u32,
+    ) -> Result<(), KoraError> {
+        let signer_config = SignerConfig { name: alias, weight: Some(weight), config };
+        signer_config.validate_individual_signer_config(self.len())?;
+
+        if matches!(self.strategy, SelectionStrategy::Weighted) && weight == 0 {
+            return Err(KoraError::ValidationError(format!(
+                "Signer '{}' has weight of 0 in weighted strategy",
+                signer_config.name
+            )));
+        }
+
+        // Build outside the lock, remote signers may need network calls
+        let signer = SignerConfig::build_signer_from_config(&signer_config).await?;
+        let pubkey = signer.pubkey();
+
+        let mut signers = self.signers.write();
+        if signers.iter().any(|s| s.name == signer_config.name) {
+            return Err(KoraError::ValidationError(format!(
+                "Duplicate signer name: {}",
+                signer_config.name
+            )));
+        }
+        if signers.iter().any(|s| s.signer.pubkey() == pubkey) {
+            return Err(KoraError::ValidationError(format!(
+                "Signer with pubkey {pubkey} is already in the pool"
+            )));
+        }
+
+        self.total_weight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |total| total.checked_add(weight))
+            .map_err(|total| {
+                KoraError::ValidationError(format!(
+                    "Signer '{}' weight {weight} overflows the pool's total weight of {total}",
+                    signer_config.name
+                ))
+            })?;
+        signers.push(
+            SignerWithMetadata::new(signer_config.name.clone(), Arc::new(signer), weight)
+                .with_signing_limit(self.concurrent_signing_limit),
+        );
+
+        log::info!(
+            "Added signer: {} ({pubkey}, weight: {weight}), pool now has {} signers",
+            signer_config.name,
+            signers.len()
+        );
+
+        Ok(())
+    }
+
     /// Get the next signer according to the configured strategy
-    pub fn get_next_signer(&self) -> Result<Arc<Signer>, KoraError> {
-        if self.signers.is_empty() {
+    pub fn get_next_signer(&self) -> Result<Arc<KoraSigner>, KoraError> {
+        self.get_next_signer_with_guard().map(|(signer, _)| signer)
+    }
+
+    /// Get the next signer according to the configured strategy, counted as busy by the
+    /// least-busy strategy until the guard is dropped
+    pub fn get_next_signer_with_guard(
+        &self,
+    ) -> Result<(Arc<KoraSigner>, InFlightGuard), KoraError> {
+        let signers = self.signers.read();
+        if signers.is_empty() {
             return Err(KoraError::InternalServerError("Signer pool is empty".to_string()));
         }
 
+        let candidates = Self::selectable_signers(&signers);
         let signer_meta = match self.strategy {
-            SelectionStrategy::RoundRobin => self.round_robin_select(),
-            SelectionStrategy::Random => self.random_select(),
-            SelectionStrategy::Weighted => self.weighted_select(),
+            SelectionStrategy::RoundRobin => self.round_robin_select(&candidates),
+            SelectionStrategy::Random => self.random_select(&candidates),
+            SelectionStrategy::Weighted => {
+                self.weighted_select(&candidates, self.candidates_weight(&candidates, &signers))
+            }
+            SelectionStrategy::LeastBusy => Ok(Self::least_busy_select(
+                &candidates,
+                self.current_index.fetch_add(1, Ordering::AcqRel),
+            )),
         }?;
 
         signer_meta.update_last_used();
+        signer_meta.selection_count.fetch_add(1, Ordering::Relaxed);
+        Ok((Arc::clone(&signer_meta.signer), InFlightGuard::new(&signer_meta.in_flight)))
+    }
+
+    /// Count the signer with `pubkey` as busy until the guard is dropped, for signers picked by
+    /// signer_key instead of the selection strategy. `None` for unknown signers
+    pub fn track_in_flight(&self, pubkey: &Pubkey) -> Option<InFlightGuard> {
+        self.signers
+            .read()
+            .iter()
+            .find(|s| s.signer.pubkey() == *pubkey)
+            .map(|s| InFlightGuard::new(&s.in_flight))
+    }
+
+    /// The signer the next `get_next_signer` call would return, without advancing the selection
+    /// or marking it as used. Exact for round-robin, random and weighted strategies draw again on
+    /// every call
+    pub fn peek_next_signer(&self) -> Result<Arc<KoraSigner>, KoraError> {
+        let signers = self.signers.read();
+        if signers.is_empty() {
+            return Err(KoraError::InternalServerError("Signer pool is empty".to_string()));
+        }
+
+        let candidates = Self::selectable_signers(&signers);
+        let signer_meta = match self.strategy {
+            SelectionStrategy::RoundRobin => {
+                candidates[self.current_index.load(Ordering::Acquire) % candidates.len()]
+            }
+            SelectionStrategy::Random => self.random_select(&candidates)?,
+            SelectionStrategy::Weighted => {
+                self.weighted_select(&candidates, self.candidates_weight(&candidates, &signers))?
+            }
+            SelectionStrategy::LeastBusy => {
+                Self::least_busy_select(&candidates, self.current_index.load(Ordering::Acquire))
+            }
+        };
+
         Ok(Arc::clone(&signer_meta.signer))
     }
 
+    /// Signers the strategies choose from, the healthy ones. When every signer is unhealthy all of
+    /// them are, failing requests beat rejecting every request
+    fn selectable_signers(signers: &[SignerWithMetadata]) -> Vec<&SignerWithMetadata> {
+        let healthy: Vec<_> = signers.iter().filter(|s| s.is_healthy()).collect();
+        if healthy.is_empty() {
+            signers.iter().collect()
+        } else {
+            healthy
+        }
+    }
+
+    /// Total weight of `signers`, rejected when it doesn't fit the u32 weighted selection draws from
+    fn sum_weights(signers: &[SignerWithMetadata]) -> Result<u32, KoraError> {
+        signers.iter().try_fold(0u32, |total, s| total.checked_add(s.weight)).ok_or_else(|| {
+            KoraError::ValidationError(format!(
+                "Total signer weight exceeds the maximum of {}",
+                u32::MAX
+            ))
+        })
+    }
+
+    /// Total weight of `candidates`, the cached pool total when no signer is skipped
+    fn candidates_weight(
+        &self,
+        candidates: &[&SignerWithMetadata],
+        signers: &[SignerWithMetadata],
+    ) -> u32 {
+        if candidates.len() == signers.len() {
+            self.total_weight.load(Ordering::Acquire)
+        } else {
+            candidates.iter().map(|s| s.weight).sum()
+        }
+    }
+
     /// Round-robin selection strategy
-    fn round_robin_select(&self) -> Result<&SignerWithMetadata, KoraError> {
+    fn round_robin_select<'a>(
+        &self,
+        signers: &[&'a SignerWithMetadata],
+    ) -> Result<&'a SignerWithMetadata, KoraError> {
         let index = self.current_index.fetch_add(1, Ordering::AcqRel);
-        let signer_index = index % self.signers.len();
-        Ok(&self.signers[signer_index])
+        let signer_index = index % signers.len();
+        Ok(signers[signer_index])
     }
 
     /// Random selection strategy
-    fn random_select(&self) -> Result<&SignerWithMetadata, KoraError> {
-        let mut rng = rand::rng();
-        let index = rng.random_range(0..self.signers.len());
-        Ok(&self.signers[index])
+    fn random_select<'a>(
+        &self,
+        signers: &[&'a SignerWithMetadata],
+    ) -> Result<&'a SignerWithMetadata, KoraError> {
+        let index = self.rng.lock().random_range(0..signers.len());
+        Ok(signers[index])
     }
 
-    /// Weighted selection strategy (weighted random)
-    fn weighted_select(&self) -> Result<&SignerWithMetadata, KoraError> {
-        let mut rng = rand::rng();
-        let mut target = rng.random_range(0..self.total_weight);
+    /// Weighted selection strategy (weighted random), a signer with weight 3 is picked three
+    /// times as often as one with weight 1
+    fn weighted_select<'a>(
+        &self,
+        signers: &[&'a SignerWithMetadata],
+        total_weight: u32,
+    ) -> Result<&'a SignerWithMetadata, KoraError> {
+        let mut target = self.rng.lock().random_range(0..total_weight);
 
-        for signer in &self.signers {
+        for &signer in signers {
             if target < signer.weight {
                 return Ok(signer);
             }
@@ -180,30 +513,100 @@ impl SignerPool {
         }
 
         // Fallback to first signer (shouldn't happen)
-        Ok(&self.signers[0])
+        Ok(signers[0])
+    }
+
+    /// Least-busy selection strategy, the signer with the fewest in-flight operations. Ties go to
+    /// the higher weight, then to the first signer from `start` on so idle signers rotate
+    fn least_busy_select<'a>(
+        signers: &[&'a SignerWithMetadata],
+        start: usize,
+    ) -> &'a SignerWithMetadata {
+        (0..signers.len())
+            .map(|offset| signers[(start + offset) % signers.len()])
+            .min_by_key(|s| (s.in_flight.load(Ordering::Acquire), Reverse(s.weight)))
+            .unwrap_or(signers[0])
+    }
+
+    /// Run one health check of every signer at once, updating their health
+    pub async fn run_health_checks(&self) {
+        let signers: Vec<_> = self
+            .signers
+            .read()
+            .iter()
+            .map(|s| (s.name.clone(), Arc::clone(&s.signer), Arc::clone(&s.health)))
+            .collect();
+        let unhealthy_threshold = self.health_check.unhealthy_threshold;
+
+        futures::future::join_all(signers.iter().map(|(name, signer, health)| async move {
+            let result = timeout(
+                Duration::from_secs(SIGNER_HEALTH_CHECK_TIMEOUT_SECONDS),
+                signer.health_check(),
+            )
+            .await
+            .unwrap_or_else(|_| Err(KoraError::SigningError("Health check timed out".to_string())));
+            health.record_check(name, result, unhealthy_threshold);
+        }))
+        .await;
+    }
+
+    /// Start a background task running the health checks every `health_check.interval_seconds`,
+    /// `None` when they are disabled. The task stops once the pool is dropped
+    pub fn start_health_checks(self: &Arc<Self>) -> Option<JoinHandle<()>> {
+        let interval_seconds = self.health_check.interval_seconds;
+        if interval_seconds == 0 {
+            return None;
+        }
+
+        log::info!("Starting signer health checks with {interval_seconds}s interval");
+        let pool = Arc::downgrade(self);
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(interval_seconds));
+
+            loop {
+                interval.tick().await;
+
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                pool.run_health_checks().await;
+            }
+        });
+
+        Some(handle)
     }
 
     /// Get information about all signers in the pool
     pub fn get_signers_info(&self) -> Vec<SignerInfo> {
         self.signers
+            .read()
             .iter()
             .map(|s| SignerInfo {
                 public_key: s.signer.pubkey().to_string(),
                 name: s.name.clone(),
                 weight: s.weight,
                 last_used: s.last_used.load(Ordering::Relaxed),
+                tx_count: s.tx_count.load(Ordering::Relaxed),
+                last_active: s.last_active.load(Ordering::Relaxed),
+                selection_count: s.selection_count.load(Ordering::Relaxed),
+                in_flight: s.in_flight.load(Ordering::Acquire),
+                healthy: s.is_healthy(),
+                consecutive_health_check_failures: s
+                    .health
+                    .consecutive_failures
+                    .load(Ordering::Acquire),
             })
             .collect()
     }
 
     /// Get the number of signers in the pool
     pub fn len(&self) -> usize {
-        self.signers.len()
+        self.signers.read().len()
     }
 
     /// Check if the pool is empty
     pub fn is_empty(&self) -> bool {
-        self.signers.is_empty()
+        self.signers.read().is_empty()
     }
 
     /// Get the configured strategy
@@ -211,29 +614,134 @@ impl SignerPool {
         &self.strategy
     }
 
+    /// Whether `pubkey` belongs to one of the signers in the pool
+    pub fn contains_signer(&self, pubkey: &Pubkey) -> bool {
+        self.signers.read().iter().any(|s| s.signer.pubkey() == *pubkey)
+    }
+
+    /// Record a successful signature by the signer with `pubkey`, ignored for unknown signers
+    pub fn record_signature(&self, pubkey: &Pubkey) {
+        if let Some(signer_meta) = self.signers.read().iter().find(|s| s.signer.pubkey() == *pubkey)
+        {
+            signer_meta.record_signature();
+            SignerMetrics::get()
+                .signatures_total
+                .with_label_values(&[&signer_meta.name, SIGNATURE_SUCCESS])
+                .inc();
+        }
+    }
+
+    /// Name the signer with `pubkey` was configured with, `None` for unknown signers
+    pub fn get_signer_name(&self, pubkey: &Pubkey) -> Option<String> {
+        self.signers.read().iter().find(|s| s.signer.pubkey() == *pubkey).map(|s| s.name.clone())
+    }
+
+    /// Keep a failed signing attempt of the signer with `pubkey` in the dead-letter queue
+    pub fn record_signing_failure(
+        &self,
+        pubkey: &Pubkey,
+        error: impl std::fmt::Display,
+        message: &[u8],
+    ) {
+        let signer = self.get_signer_name(pubkey).unwrap_or_else(|| pubkey.to_string());
+
+        log::warn!("Signing failed with signer {signer}, adding it to the dead-letter queue");
+        SignerMetrics::get().signatures_total.with_label_values(&[&signer, SIGNATURE_ERROR]).inc();
+        self.dead_letter_queue.push(DeadLetterEntry::new(&signer, error, message));
+    }
+
+    pub fn dead_letter_queue(&self) -> &DeadLetterQueue {
+        &self.dead_letter_queue
+    }
+
+    /// Take a signing slot of the signer with `pubkey`, `SignerBusy` when all of them are in use.
+    /// `None` for unknown signers and signers without a concurrency limit
+    pub fn try_acquire_signing_permit(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<SigningPermit>, KoraError> {
+        let signers = self.signers.read();
+        let Some(signer_meta) = signers.iter().find(|s| s.signer.pubkey() == *pubkey) else {
+            return Ok(None);
+        };
+        let Some(permits) = &signer_meta.signing_permits else {
+            return Ok(None);
+        };
+
+        let permit = Arc::clone(permits).try_acquire_owned().map_err(|_| {
+            KoraError::SignerBusy(format!(
+                "Signer {} has reached its concurrent signing limit",
+                signer_meta.name
+            ))
+        })?;
+
+        Ok(Some(SigningPermit::new(permit, &signer_meta.name)))
+    }
+
+    /// Wait for a signing slot of the signer with `pubkey`. `None` for unknown signers and
+    /// signers without a concurrency limit
+    pub async fn acquire_signing_permit(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<SigningPermit>, KoraError> {
+        let (name, permits) = {
+            let signers = self.signers.read();
+            let Some(signer_meta) = signers.iter().find(|s| s.signer.pubkey() == *pubkey) else {
+                return Ok(None);
+            };
+            let Some(permits) = &signer_meta.signing_permits else {
+                return Ok(None);
+            };
+            (signer_meta.name.clone(), Arc::clone(permits))
+        };
+
+        let permit = permits.acquire_owned().await.map_err(|_| {
+            KoraError::InternalServerError(format!("Signing permits of signer {name} are closed"))
+        })?;
+
+        Ok(Some(SigningPermit::new(permit, &name)))
+    }
+
     /// Get a signer by public key (for client consistency signer keys)
-    pub fn get_signer_by_pubkey(&self, pubkey: &str) -> Result<Arc<Signer>, KoraError> {
+    pub fn get_signer_by_pubkey(&self, pubkey: &str) -> Result<Arc<KoraSigner>, KoraError> {
         // Try to parse as Pubkey to validate format
         let target_pubkey = Pubkey::from_str(pubkey).map_err(|_| {
             KoraError::ValidationError(format!("Invalid signer signer key pubkey: {pubkey}"))
         })?;
 
         // Find signer with matching public key
+        let signers = self.signers.read();
         let signer_meta =
-            self.signers.iter().find(|s| s.signer.pubkey() == target_pubkey).ok_or_else(|| {
+            signers.iter().find(|s| s.signer.pubkey() == target_pubkey).ok_or_else(|| {
                 KoraError::ValidationError(format!("Signer with pubkey {pubkey} not found in pool"))
             })?;
 
         signer_meta.update_last_used();
         Ok(Arc::clone(&signer_meta.signer))
     }
+
+    /// Get a signer by the name it was configured with, for operations directed to a
+    /// designated signer regardless of the selection strategy
+    pub fn get_signer_by_alias(&self, alias: &str) -> Result<Arc<KoraSigner>, KoraError> {
+        let signers = self.signers.read();
+        let signer_meta = signers.iter().find(|s| s.name == alias).ok_or_else(|| {
+            KoraError::ValidationError(format!("Signer with alias {alias} not found in pool"))
+        })?;
+
+        signer_meta.update_last_used();
+        Ok(Arc::clone(&signer_meta.signer))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use solana_sdk::signature::Keypair;
+    use solana_sdk::signature::{Keypair, Signer as _};
 
     use super::*;
+    use crate::signer::{
+        config::MemorySignerConfig,
+        signer::{AwsKmsSigner, MockKmsClient, ED25519_SPKI_PREFIX},
+    };
     use std::collections::HashMap;
 
     fn create_test_pool() -> SignerPool {
@@ -246,15 +754,10 @@ mod tests {
         let external_signer2 =
             solana_keychain::Signer::from_memory(&keypair2.to_base58_string()).unwrap();
 
-        SignerPool {
-            signers: vec![
-                SignerWithMetadata::new("signer_1".to_string(), Arc::new(external_signer1), 1),
-                SignerWithMetadata::new("signer_2".to_string(), Arc::new(external_signer2), 2),
-            ],
-            strategy: SelectionStrategy::RoundRobin,
-            current_index: AtomicUsize::new(0),
-            total_weight: 3,
-        }
+        SignerPool::new(vec![
+            SignerWithMetadata::new("signer_1".to_string(), Arc::new(external_signer1.into()), 1),
+            SignerWithMetadata::new("signer_2".to_string(), Arc::new(external_signer2.into()), 2),
+        ])
     }
 
     #[test]
@@ -280,8 +783,8 @@ mod tests {
         pool.strategy = SelectionStrategy::Weighted;
 
         // Store the public keys for comparison (signer_1 has weight 1, signer_2 has weight 2)
-        let signer1_pubkey = pool.signers[0].signer.pubkey().to_string();
-        let signer2_pubkey = pool.signers[1].signer.pubkey().to_string();
+        let signer1_pubkey = pool.signers.read()[0].signer.pubkey().to_string();
+        let signer2_pubkey = pool.signers.read()[1].signer.pubkey().to_string();
 
         // Test weighted selection over many iterations
         let mut selections = HashMap::new();
@@ -301,17 +804,443 @@ mod tests {
         assert!(*signer1_count > 50); // Should be around 100
     }
 
+    /// Pool with one memory signer per weight, seeded for reproducible selections
+    fn create_weighted_pool(weights: &[u32], seed: u64) -> SignerPool {
+        let signers = weights
+            .iter()
+            .enumerate()
+            .map(|(i, weight)| {
+                let keypair = Keypair::new();
+                let signer =
+                    solana_keychain::Signer::from_memory(&keypair.to_base58_string()).unwrap();
+                SignerWithMetadata::new(format!("signer_{i}"), Arc::new(signer.into()), *weight)
+            })
+            .collect();
+
+        let mut pool = SignerPool::new(signers).with_rng_seed(seed);
+        pool.strategy = SelectionStrategy::Weighted;
+        pool
+    }
+
     #[test]
-    fn test_empty_pool() {
-        let pool = SignerPool {
-            signers: vec![],
-            strategy: SelectionStrategy::RoundRobin,
-            current_index: AtomicUsize::new(0),
-            total_weight: 0,
+    fn test_weighted_selection_converges_to_weights() {
+        let weights = [1, 3, 6];
+        let pool = create_weighted_pool(&weights, 42);
+        let picks = 10_000;
+
+        for _ in 0..picks {
+            pool.get_next_signer().unwrap();
+        }
+
+        let total_weight: u32 = weights.iter().sum();
+        let info = pool.get_signers_info();
+        assert_eq!(info.iter().map(|s| s.selection_count).sum::<u64>(), picks);
+        for (signer, weight) in info.iter().zip(weights) {
+            let expected = weight as f64 / total_weight as f64;
+            let observed = signer.selection_count as f64 / picks as f64;
+            assert!(
+                (observed - expected).abs() < 0.02,
+                "{} picked {observed:.3} of the time, expected {expected:.3}",
+                signer.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_selection_is_reproducible_with_seed() {
+        // Positions of the picked signers, keypairs differ between pools
+        let selections = |seed| {
+            let pool = create_weighted_pool(&[1, 1, 1, 1], seed);
+            let pubkeys: Vec<Pubkey> =
+                pool.signers.read().iter().map(|s| s.signer.pubkey()).collect();
+            (0..50)
+                .map(|_| {
+                    let pubkey = pool.get_next_signer().unwrap().pubkey();
+                    pubkeys.iter().position(|p| *p == pubkey).unwrap()
+                })
+                .collect::<Vec<_>>()
         };
 
+        assert_eq!(selections(7), selections(7));
+        assert_ne!(selections(7), selections(8));
+    }
+
+    fn create_least_busy_pool(weights: &[u32]) -> SignerPool {
+        let mut pool = create_weighted_pool(weights, 0);
+        pool.strategy = SelectionStrategy::LeastBusy;
+        pool
+    }
+
+    fn signer_index(pool: &SignerPool, signer: &KoraSigner) -> usize {
+        pool.signers.read().iter().position(|s| s.signer.pubkey() == signer.pubkey()).unwrap()
+    }
+
+    #[test]
+    fn test_least_busy_selection_picks_fewest_in_flight() {
+        let pool = create_least_busy_pool(&[1, 1, 1]);
+
+        // Concurrent borrows spread over every signer
+        let (first, first_guard) = pool.get_next_signer_with_guard().unwrap();
+        let (second, _second_guard) = pool.get_next_signer_with_guard().unwrap();
+        let (third, _third_guard) = pool.get_next_signer_with_guard().unwrap();
+        let mut picked = vec![
+            signer_index(&pool, &first),
+            signer_index(&pool, &second),
+            signer_index(&pool, &third),
+        ];
+        picked.sort();
+        assert_eq!(picked, vec![0, 1, 2]);
+
+        // The first signer to finish is the only idle one
+        drop(first_guard);
+        let (next, _next_guard) = pool.get_next_signer_with_guard().unwrap();
+        assert_eq!(next.pubkey(), first.pubkey());
+        assert_eq!(
+            pool.peek_next_signer().unwrap().pubkey(),
+            pool.get_next_signer().unwrap().pubkey()
+        );
+    }
+
+    #[test]
+    fn test_least_busy_selection_breaks_ties_by_weight() {
+        let pool = create_least_busy_pool(&[1, 3]);
+
+        let (heavy, _heavy_guard) = pool.get_next_signer_with_guard().unwrap();
+        assert_eq!(signer_index(&pool, &heavy), 1);
+        let (light, _light_guard) = pool.get_next_signer_with_guard().unwrap();
+        assert_eq!(signer_index(&pool, &light), 0);
+    }
+
+    #[test]
+    fn test_least_busy_selection_balances_slow_signer() {
+        let pool = create_least_busy_pool(&[1, 1, 1]);
+        let slow = pool.signers.read()[0].signer.pubkey();
+
+        // Borrows by the slow signer last 10 picks, the others 1
+        let mut held: Vec<(usize, InFlightGuard)> = vec![];
+        for pick in 0..300 {
+            held.retain(|(release_at, _)| *release_at > pick);
+            let (signer, guard) = pool.get_next_signer_with_guard().unwrap();
+            let duration = if signer.pubkey() == slow { 10 } else { 1 };
+            held.push((pick + duration, guard));
+        }
+
+        let info = pool.get_signers_info();
+        assert!(info[0].selection_count * 3 < info[1].selection_count);
+        assert!(info[0].selection_count * 3 < info[2].selection_count);
+
+        drop(held);
+        assert!(pool.get_signers_info().iter().all(|s| s.in_flight == 0));
+    }
+
+    #[test]
+    fn test_in_flight_guard_released_on_failure() {
+        let pool = create_least_busy_pool(&[1]);
+        let sign = |pool: &SignerPool| -> Result<(), KoraError> {
+            let (_signer, _guard) = pool.get_next_signer_with_guard()?;
+            Err(KoraError::SigningError("signing failed".to_string()))
+        };
+
+        assert!(sign(&pool).is_err());
+        assert_eq!(pool.get_signers_info()[0].in_flight, 0);
+
+        let pubkey = pool.signers.read()[0].signer.pubkey();
+        let guard = pool.track_in_flight(&pubkey);
+        assert_eq!(pool.get_signers_info()[0].in_flight, 1);
+        drop(guard);
+        assert_eq!(pool.get_signers_info()[0].in_flight, 0);
+        assert!(pool.track_in_flight(&Pubkey::new_unique()).is_none());
+    }
+
+    /// AWS KMS signer whose key can only be looked up while `kms_up` is set
+    async fn create_flaky_kms_signer(kms_up: Arc<AtomicBool>) -> KoraSigner {
+        let keypair = Keypair::new();
+        let der = [ED25519_SPKI_PREFIX.as_slice(), keypair.pubkey().as_ref()].concat();
+        let mut client = MockKmsClient::new();
+        client.expect_get_public_key().returning(move |_| {
+            if kms_up.load(Ordering::Relaxed) {
+                Ok(der.clone())
+            } else {
+                Err(KoraError::SigningError("KMSInternalException".to_string()))
+            }
+        });
+
+        let key_arn = "arn:aws:kms:us-east-1:123456789012:key/flaky".to_string();
+        KoraSigner::AwsKms(AwsKmsSigner::new(key_arn, Arc::new(client)).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_signer_is_skipped_then_restored() {
+        let kms_up = Arc::new(AtomicBool::new(true));
+        let flaky = create_flaky_kms_signer(Arc::clone(&kms_up)).await;
+        let flaky_pubkey = flaky.pubkey();
+        let keypair = Keypair::new();
+        let signer = solana_keychain::Signer::from_memory(&keypair.to_base58_string()).unwrap();
+        let mut pool = SignerPool::new(vec![
+            SignerWithMetadata::new("healthy".to_string(), Arc::new(signer.into()), 1),
+            SignerWithMetadata::new("flaky".to_string(), Arc::new(flaky), 1),
+        ]);
+        pool.health_check.unhealthy_threshold = 2;
+
+        pool.run_health_checks().await;
+        assert!(pool.get_signers_info().iter().all(|s| s.healthy));
+
+        // A single failure doesn't take the signer out of selection
+        kms_up.store(false, Ordering::Relaxed);
+        pool.run_health_checks().await;
+        let info = pool.get_signers_info();
+        assert!(info[1].healthy);
+        assert_eq!(info[1].consecutive_health_check_failures, 1);
+        assert_eq!(info[0].consecutive_health_check_failures, 0);
+
+        pool.run_health_checks().await;
+        assert!(!pool.get_signers_info()[1].healthy);
+        for strategy in [
+            SelectionStrategy::RoundRobin,
+            SelectionStrategy::Random,
+            SelectionStrategy::Weighted,
+            SelectionStrategy::LeastBusy,
+        ] {
+            pool.strategy = strategy;
+            for _ in 0..10 {
+                assert_eq!(pool.get_next_signer().unwrap().pubkey(), keypair.pubkey());
+            }
+            assert_eq!(pool.peek_next_signer().unwrap().pubkey(), keypair.pubkey());
+        }
+
+        // One passing check restores it
+        kms_up.store(true, Ordering::Relaxed);
+        pool.run_health_checks().await;
+        let info = pool.get_signers_info();
+        assert!(info[1].healthy);
+        assert_eq!(info[1].consecutive_health_check_failures, 0);
+
+        pool.strategy = SelectionStrategy::RoundRobin;
+        let selected: Vec<Pubkey> =
+            (0..2).map(|_| pool.get_next_signer().unwrap().pubkey()).collect();
+        assert!(selected.contains(&flaky_pubkey));
+    }
+
+    #[tokio::test]
+    async fn test_all_unhealthy_signers_remain_selectable() {
+        let kms_up = Arc::new(AtomicBool::new(true));
+        let flaky = create_flaky_kms_signer(Arc::clone(&kms_up)).await;
+        kms_up.store(false, Ordering::Relaxed);
+        let mut pool =
+            SignerPool::new(vec![SignerWithMetadata::new("flaky".to_string(), Arc::new(flaky), 1)]);
+        pool.health_check.unhealthy_threshold = 1;
+
+        pool.run_health_checks().await;
+
+        assert!(!pool.get_signers_info()[0].healthy);
+        assert!(pool.get_next_signer().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_disabled_by_default() {
+        let pool = Arc::new(create_test_pool());
+
+        assert!(pool.start_health_checks().is_none());
+    }
+
+    #[test]
+    fn test_peek_next_signer_does_not_advance_round_robin() {
+        let pool = create_test_pool();
+
+        for _ in 0..3 {
+            let peeked = pool.peek_next_signer().unwrap();
+            assert_eq!(pool.peek_next_signer().unwrap().pubkey(), peeked.pubkey());
+            assert_eq!(pool.get_next_signer().unwrap().pubkey(), peeked.pubkey());
+        }
+
+        // Peeking doesn't mark the signer as used
+        let pool = create_test_pool();
+        pool.peek_next_signer().unwrap();
+        assert!(pool.get_signers_info().iter().all(|info| info.last_used == 0));
+    }
+
+    #[test]
+    fn test_get_signer_name() {
+        let pool = create_test_pool();
+        let signer2_pubkey = pool.signers.read()[1].signer.pubkey();
+
+        assert_eq!(pool.get_signer_name(&signer2_pubkey).as_deref(), Some("signer_2"));
+        assert_eq!(pool.get_signer_name(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn test_get_signer_by_alias() {
+        let pool = create_test_pool();
+        let signer2_pubkey = pool.signers.read()[1].signer.pubkey();
+
+        let signer = pool.get_signer_by_alias("signer_2").unwrap();
+        assert_eq!(signer.pubkey(), signer2_pubkey);
+        assert!(pool.get_signers_info()[1].last_used > 0);
+
+        let result = pool.get_signer_by_alias("unknown");
+        assert!(matches!(result, Err(KoraError::ValidationError(msg)) if msg.contains("unknown")));
+    }
+
+    #[test]
+    fn test_empty_pool() {
+        let pool = SignerPool::new(vec![]);
+
         assert!(pool.get_next_signer().is_err());
         assert!(pool.is_empty());
         assert_eq!(pool.len(), 0);
     }
+
+    #[test]
+    fn test_record_signature_increments_tx_count() {
+        let pool = create_test_pool();
+        let signer1_pubkey = pool.signers.read()[0].signer.pubkey();
+
+        for _ in 0..3 {
+            pool.record_signature(&signer1_pubkey);
+        }
+        // Selecting a signer is not a signature
+        pool.get_next_signer().unwrap();
+        // Unknown signers are ignored
+        pool.record_signature(&Pubkey::new_unique());
+
+        let info = pool.get_signers_info();
+        assert_eq!(info[0].tx_count, 3);
+        assert!(info[0].last_active > 0);
+        assert_eq!(info[1].tx_count, 0);
+        assert_eq!(info[1].last_active, 0);
+    }
+
+    #[tokio::test]
+    async fn test_hot_add_signer_joins_round_robin() {
+        let pool = create_test_pool();
+        let keypair = Keypair::new();
+        std::env::set_var("HOT_ADD_SIGNER_TEST_PRIVATE_KEY", keypair.to_base58_string());
+
+        pool.hot_add_signer(
+            SignerTypeConfig::Memory {
+                config: MemorySignerConfig {
+                    private_key_env: "HOT_ADD_SIGNER_TEST_PRIVATE_KEY".to_string(),
+                },
+            },
+            "signer_3".to_string(),
+            1,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.total_weight.load(Ordering::Acquire), 4);
+
+        let mut selections = HashMap::new();
+        for _ in 0..99 {
+            let signer = pool.get_next_signer().unwrap();
+            *selections.entry(signer.pubkey().to_string()).or_insert(0) += 1;
+        }
+
+        assert_eq!(selections.len(), 3);
+        assert_eq!(selections.get(&keypair.pubkey().to_string()), Some(&33));
+
+        // Adding the same alias again is rejected
+        let result = pool
+            .hot_add_signer(
+                SignerTypeConfig::Memory {
+                    config: MemorySignerConfig {
+                        private_key_env: "HOT_ADD_SIGNER_TEST_PRIVATE_KEY".to_string(),
+                    },
+                },
+                "signer_3".to_string(),
+                1,
+            )
+            .await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_hot_add_signer_rejects_weight_overflow() {
+        let pool = create_test_pool();
+        let keypair = Keypair::new();
+        std::env::set_var("HOT_ADD_OVERFLOW_TEST_PRIVATE_KEY", keypair.to_base58_string());
+
+        let result = pool
+            .hot_add_signer(
+                SignerTypeConfig::Memory {
+                    config: MemorySignerConfig {
+                        private_key_env: "HOT_ADD_OVERFLOW_TEST_PRIVATE_KEY".to_string(),
+                    },
+                },
+                "signer_3".to_string(),
+                u32::MAX,
+            )
+            .await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.total_weight.load(Ordering::Acquire), 3);
+    }
+
+    #[test]
+    fn test_sum_weights_rejects_overflow() {
+        let keypair = Keypair::new();
+        let signer: Arc<KoraSigner> = Arc::new(
+            solana_keychain::Signer::from_memory(&keypair.to_base58_string()).unwrap().into(),
+        );
+        let signers = vec![
+            SignerWithMetadata::new("signer_1".to_string(), signer.clone(), u32::MAX),
+            SignerWithMetadata::new("signer_2".to_string(), signer, 1),
+        ];
+
+        assert!(matches!(SignerPool::sum_weights(&signers), Err(KoraError::ValidationError(_))));
+        assert_eq!(SignerPool::sum_weights(&signers[..1]).unwrap(), u32::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_signing_limit() {
+        let keypair = Keypair::new();
+        let signer = solana_keychain::Signer::from_memory(&keypair.to_base58_string()).unwrap();
+        let pool = Arc::new(SignerPool::new(vec![SignerWithMetadata::new(
+            "concurrency_limited_signer".to_string(),
+            Arc::new(signer.into()),
+            1,
+        )
+        .with_signing_limit(2)]));
+        let queue_depth =
+            SignerMetrics::get().queue_depth.with_label_values(&["concurrency_limited_signer"]);
+
+        // Every request tries to sign before any of them finishes
+        let requests = 5;
+        let barrier = Arc::new(tokio::sync::Barrier::new(requests));
+        let handles: Vec<_> = (0..requests)
+            .map(|_| {
+                let (pool, barrier) = (Arc::clone(&pool), Arc::clone(&barrier));
+                let pubkey = keypair.pubkey();
+                tokio::spawn(async move {
+                    let permit = pool.try_acquire_signing_permit(&pubkey);
+                    barrier.wait().await;
+                    permit.map(|permit| permit.is_some())
+                })
+            })
+            .collect();
+
+        let mut acquired = 0;
+        let mut busy = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(true) => acquired += 1,
+                Err(KoraError::SignerBusy(_)) => busy += 1,
+                other => panic!("unexpected result: {other:?}"),
+            }
+        }
+        assert_eq!((acquired, busy), (2, 3));
+
+        // Permits are released on drop
+        assert_eq!(queue_depth.get(), 0);
+        let permit = pool.try_acquire_signing_permit(&keypair.pubkey()).unwrap();
+        assert!(permit.is_some());
+        assert_eq!(queue_depth.get(), 1);
+
+        // Signers without a limit never hand out permits
+        let unlimited = create_test_pool();
+        let pubkey = unlimited.signers.read()[0].signer.pubkey();
+        assert!(unlimited.try_acquire_signing_permit(&pubkey).unwrap().is_none());
+    }
 }
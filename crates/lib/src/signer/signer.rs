@@ -1,7 +1,403 @@
-//! Re-exports for external signer infrastructure
+//! Signers of the pool
 //!
-//! Kora uses solana-keychain crate as its signing infrastructure.
-//! This module exists only for re-exporting convenience.
+//! Kora uses the solana-keychain crate for its Memory, Turnkey, Privy and Vault signers. AWS KMS
+//! is not available there, `AwsKmsSigner` signs with the KMS `Sign` API instead. Ledger devices
+//! are handled in the `ledger` module.
+
+use async_trait::async_trait;
+use aws_config::{sts::AssumeRoleProvider, BehaviorVersion, Region};
+use aws_sdk_kms::{
+    error::DisplayErrorContext,
+    primitives::Blob,
+    types::{MessageType, SigningAlgorithmSpec},
+};
+use mockall::automock;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use std::sync::Arc;
+
+use crate::{error::KoraError, sanitize_error, signer::ledger::LedgerSigner};
 
 // Re-export the external signer for use throughout Kora
-pub use solana_keychain::{Signer, SolanaSigner};
+pub use solana_keychain::{Signer, SignerError, SolanaSigner};
+
+/// DER prefix of an ed25519 SubjectPublicKeyInfo, as returned by KMS `GetPublicKey`
+pub(crate) const ED25519_SPKI_PREFIX: [u8; 12] =
+    [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+/// Pure EdDSA over the raw message, the only KMS ed25519 algorithm that yields Solana signatures
+const KMS_ED25519_SIGNING_ALGORITHM: &str = "ED25519_SHA_512";
+const AWS_KMS_SESSION_NAME: &str = "kora-signer";
+/// Prepended to health check nonces so they can never be mistaken for a transaction message
+const HEALTH_CHECK_MESSAGE_PREFIX: &[u8] = b"kora-health-check:";
+
+/// A signer of the pool
+pub enum KoraSigner {
+    /// Memory, Turnkey, Privy and Vault signers
+    Keychain(Signer),
+    AwsKms(AwsKmsSigner),
+    Ledger(LedgerSigner),
+}
+
+impl KoraSigner {
+    /// Signing operations the signer can run at once, `None` when it has no limit of its own
+    pub fn max_concurrent_signings(&self) -> Option<usize> {
+        match self {
+            // Every signature waits for a confirmation on the device
+            KoraSigner::Ledger(_) => Some(1),
+            KoraSigner::Keychain(_) | KoraSigner::AwsKms(_) => None,
+        }
+    }
+
+    /// Lightweight check that the signer can still sign. Keychain signers sign a throwaway
+    /// nonce, AWS KMS keys are looked up and Ledger devices are pinged, a signature would wait
+    /// for a confirmation on the device
+    pub async fn health_check(&self) -> Result<(), KoraError> {
+        match self {
+            KoraSigner::Keychain(signer) => {
+                let nonce: [u8; 32] = rand::random();
+                let message = [HEALTH_CHECK_MESSAGE_PREFIX, nonce.as_slice()].concat();
+                let signature = signer.sign_message(&message).await.map_err(|e| {
+                    KoraError::SigningError(format!(
+                        "Health check signature failed: {}",
+                        sanitize_error!(e)
+                    ))
+                })?;
+                if !signature.verify(signer.pubkey().as_ref(), &message) {
+                    return Err(KoraError::SigningError(
+                        "Health check signature does not match the signer's public key".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            KoraSigner::AwsKms(_) | KoraSigner::Ledger(_) => {
+                if !self.is_available().await {
+                    return Err(KoraError::SigningError("Signer is unavailable".to_string()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<Signer> for KoraSigner {
+    fn from(signer: Signer) -> Self {
+        KoraSigner::Keychain(signer)
+    }
+}
+
+#[async_trait]
+impl SolanaSigner for KoraSigner {
+    fn pubkey(&self) -> Pubkey {
+        match self {
+            KoraSigner::Keychain(signer) => signer.pubkey(),
+            KoraSigner::AwsKms(signer) => signer.pubkey(),
+            KoraSigner::Ledger(signer) => signer.pubkey(),
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<Signature, SignerError> {
+        match self {
+            KoraSigner::Keychain(signer) => signer.sign_transaction(tx).await,
+            KoraSigner::AwsKms(signer) => signer.sign_transaction(tx).await,
+            KoraSigner::Ledger(signer) => signer.sign_transaction(tx).await,
+        }
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match self {
+            KoraSigner::Keychain(signer) => signer.sign_message(message).await,
+            KoraSigner::AwsKms(signer) => signer.sign_message(message).await,
+            KoraSigner::Ledger(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn is_available(&self) -> bool {
+        match self {
+            KoraSigner::Keychain(signer) => signer.is_available().await,
+            KoraSigner::AwsKms(signer) => signer.is_available().await,
+            KoraSigner::Ledger(signer) => signer.is_available().await,
+        }
+    }
+}
+
+/// The KMS calls `AwsKmsSigner` needs, mocked in tests
+#[automock]
+#[async_trait]
+pub trait KmsClient: Send + Sync {
+    /// DER-encoded SubjectPublicKeyInfo of `key_arn`
+    async fn get_public_key(&self, key_arn: &str) -> Result<Vec<u8>, KoraError>;
+
+    /// Signature of `message` by `key_arn`
+    async fn sign(&self, key_arn: &str, message: &[u8]) -> Result<Vec<u8>, KoraError>;
+}
+
+pub struct AwsKmsClient {
+    client: aws_sdk_kms::Client,
+}
+
+impl AwsKmsClient {
+    /// Client for `region` using the default AWS credential chain, assuming `assume_role_arn`
+    /// on top of it when set
+    pub async fn new(region: String, assume_role_arn: Option<String>) -> Self {
+        let base_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(region.clone()))
+            .load()
+            .await;
+
+        let config = match assume_role_arn {
+            Some(role_arn) => {
+                let provider = AssumeRoleProvider::builder(role_arn)
+                    .session_name(AWS_KMS_SESSION_NAME)
+                    .configure(&base_config)
+                    .build()
+                    .await;
+                aws_config::defaults(BehaviorVersion::latest())
+                    .region(Region::new(region))
+                    .credentials_provider(provider)
+                    .load()
+                    .await
+            }
+            None => base_config,
+        };
+
+        Self { client: aws_sdk_kms::Client::new(&config) }
+    }
+}
+
+#[async_trait]
+impl KmsClient for AwsKmsClient {
+    async fn get_public_key(&self, key_arn: &str) -> Result<Vec<u8>, KoraError> {
+        let output = self.client.get_public_key().key_id(key_arn).send().await.map_err(|e| {
+            KoraError::SigningError(format!(
+                "AWS KMS GetPublicKey failed: {}",
+                sanitize_error!(DisplayErrorContext(e))
+            ))
+        })?;
+
+        output.public_key.map(Blob::into_inner).ok_or_else(|| {
+            KoraError::SigningError("AWS KMS GetPublicKey returned no public key".to_string())
+        })
+    }
+
+    async fn sign(&self, key_arn: &str, message: &[u8]) -> Result<Vec<u8>, KoraError> {
+        let output = self
+            .client
+            .sign()
+            .key_id(key_arn)
+            .message(Blob::new(message))
+            .message_type(MessageType::Raw)
+            .signing_algorithm(SigningAlgorithmSpec::from(KMS_ED25519_SIGNING_ALGORITHM))
+            .send()
+            .await
+            .map_err(|e| {
+                KoraError::SigningError(format!(
+                    "AWS KMS Sign failed: {}",
+                    sanitize_error!(DisplayErrorContext(e))
+                ))
+            })?;
+
+        output.signature.map(Blob::into_inner).ok_or_else(|| {
+            KoraError::SigningError("AWS KMS Sign returned no signature".to_string())
+        })
+    }
+}
+
+/// Signer backed by an AWS KMS `ECC_NIST_EDWARDS25519` key, the private key never leaves KMS
+pub struct AwsKmsSigner {
+    key_arn: String,
+    pubkey: Pubkey,
+    client: Arc<dyn KmsClient>,
+}
+
+impl AwsKmsSigner {
+    /// Look up the public key of `key_arn`, failing for keys that are not ed25519
+    pub async fn new(key_arn: String, client: Arc<dyn KmsClient>) -> Result<Self, KoraError> {
+        let der = client.get_public_key(&key_arn).await?;
+        let pubkey = match der.strip_prefix(&ED25519_SPKI_PREFIX) {
+            Some(key) => Pubkey::try_from(key).ok(),
+            None => None,
+        }
+        .ok_or_else(|| {
+            KoraError::ValidationError(format!("AWS KMS key {key_arn} is not an ed25519 key"))
+        })?;
+
+        Ok(Self { key_arn, pubkey, client })
+    }
+}
+
+#[async_trait]
+impl SolanaSigner for AwsKmsSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<Signature, SignerError> {
+        let num_signers = tx.message.header.num_required_signatures as usize;
+        let position = tx.message.account_keys[..num_signers]
+            .iter()
+            .position(|key| *key == self.pubkey)
+            .ok_or_else(|| {
+                SignerError::SigningFailed(format!(
+                    "{} is not a signer of the transaction",
+                    self.pubkey
+                ))
+            })?;
+
+        let signature = self.sign_message(&tx.message_data()).await?;
+        tx.signatures.resize(num_signers, Signature::default());
+        tx.signatures[position] = signature;
+        Ok(signature)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let bytes = self
+            .client
+            .sign(&self.key_arn, message)
+            .await
+            .map_err(|e| SignerError::RemoteApiError(e.to_string()))?;
+
+        let signature = Signature::try_from(bytes.as_slice()).map_err(|_| {
+            SignerError::SigningFailed(format!(
+                "AWS KMS returned a {} byte signature, expected 64",
+                bytes.len()
+            ))
+        })?;
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::SigningFailed(
+                "AWS KMS signature does not match the key's public key".to_string(),
+            ));
+        }
+
+        Ok(signature)
+    }
+
+    async fn is_available(&self) -> bool {
+        self.client.get_public_key(&self.key_arn).await.is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_message::Message;
+    use solana_sdk::signature::{Keypair, Signer as _};
+    use solana_system_interface::instruction::transfer;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const KEY_ARN: &str =
+        "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab";
+
+    fn ed25519_public_key_der(pubkey: &Pubkey) -> Vec<u8> {
+        [ED25519_SPKI_PREFIX.as_slice(), pubkey.as_ref()].concat()
+    }
+
+    /// KMS client holding `keypair` as the private key of `KEY_ARN`
+    fn mock_kms_client(keypair: Keypair) -> MockKmsClient {
+        let der = ed25519_public_key_der(&keypair.pubkey());
+        let mut client = MockKmsClient::new();
+        client
+            .expect_get_public_key()
+            .withf(|key_arn| key_arn == KEY_ARN)
+            .returning(move |_| Ok(der.clone()));
+        client
+            .expect_sign()
+            .withf(|key_arn, _| key_arn == KEY_ARN)
+            .returning(move |_, message| Ok(keypair.sign_message(message).as_ref().to_vec()));
+        client
+    }
+
+    #[tokio::test]
+    async fn test_aws_kms_signer_sign_message() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let signer = AwsKmsSigner::new(KEY_ARN.to_string(), Arc::new(mock_kms_client(keypair)))
+            .await
+            .unwrap();
+
+        assert_eq!(signer.pubkey(), pubkey);
+        assert!(signer.is_available().await);
+
+        let signature = signer.sign_message(b"message").await.unwrap();
+        assert!(signature.verify(pubkey.as_ref(), b"message"));
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let keypair = Keypair::new();
+        let signer = KoraSigner::from(Signer::from_memory(&keypair.to_base58_string()).unwrap());
+        assert!(signer.health_check().await.is_ok());
+
+        // KMS goes down after the signer was created
+        let der = ed25519_public_key_der(&keypair.pubkey());
+        let kms_up = Arc::new(AtomicBool::new(true));
+        let mut client = MockKmsClient::new();
+        let up = Arc::clone(&kms_up);
+        client.expect_get_public_key().returning(move |_| {
+            if up.load(Ordering::Relaxed) {
+                Ok(der.clone())
+            } else {
+                Err(KoraError::SigningError("KMSInternalException".to_string()))
+            }
+        });
+        let signer = KoraSigner::AwsKms(
+            AwsKmsSigner::new(KEY_ARN.to_string(), Arc::new(client)).await.unwrap(),
+        );
+        assert!(signer.health_check().await.is_ok());
+        kms_up.store(false, Ordering::Relaxed);
+        assert!(signer.health_check().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_aws_kms_signer_sign_transaction() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let signer = KoraSigner::AwsKms(
+            AwsKmsSigner::new(KEY_ARN.to_string(), Arc::new(mock_kms_client(keypair)))
+                .await
+                .unwrap(),
+        );
+
+        let message = Message::new(&[transfer(&pubkey, &Pubkey::new_unique(), 1)], Some(&pubkey));
+        let mut transaction = Transaction::new_unsigned(message);
+        let signature = signer.sign_transaction(&mut transaction).await.unwrap();
+
+        assert_eq!(transaction.signatures, vec![signature]);
+        assert!(transaction.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_aws_kms_signer_rejects_non_ed25519_key() {
+        let mut client = MockKmsClient::new();
+        // A secp256k1 SubjectPublicKeyInfo
+        client.expect_get_public_key().returning(|_| Ok(vec![0x30, 0x56, 0x30, 0x10]));
+
+        let result = AwsKmsSigner::new(KEY_ARN.to_string(), Arc::new(client)).await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_aws_kms_signer_sign_errors() {
+        let pubkey = Keypair::new().pubkey();
+        let der = ed25519_public_key_der(&pubkey);
+
+        // KMS failure
+        let mut client = MockKmsClient::new();
+        let public_key = der.clone();
+        client.expect_get_public_key().returning(move |_| Ok(public_key.clone()));
+        client
+            .expect_sign()
+            .returning(|_, _| Err(KoraError::SigningError("AccessDeniedException".to_string())));
+        let signer = AwsKmsSigner::new(KEY_ARN.to_string(), Arc::new(client)).await.unwrap();
+        assert!(signer.sign_message(b"message").await.is_err());
+
+        // Signature by another key
+        let other_keypair = Keypair::new();
+        let mut client = MockKmsClient::new();
+        client.expect_get_public_key().returning(move |_| Ok(der.clone()));
+        client
+            .expect_sign()
+            .returning(move |_, message| Ok(other_keypair.sign_message(message).as_ref().to_vec()));
+        let signer = AwsKmsSigner::new(KEY_ARN.to_string(), Arc::new(client)).await.unwrap();
+        assert!(signer.sign_message(b"message").await.is_err());
+    }
+}
@@ -1,32 +1,74 @@
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
-use std::sync::{
-    atomic::{AtomicPtr, Ordering},
-    Arc,
+use solana_keychain::SolanaSigner;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Arc,
+    },
 };
 
-use crate::{config::Config, error::KoraError, signer::SignerPool};
+use crate::{
+    config::Config,
+    error::KoraError,
+    signer::{pool::InFlightGuard, KoraSigner, SignerPool},
+};
 
 // Global signer pool (for multi-signer support)
 static GLOBAL_SIGNER_POOL: Lazy<RwLock<Option<Arc<SignerPool>>>> = Lazy::new(|| RwLock::new(None));
 
+// Signer used for requests without a signer_key, set with `rpc start --signer-key`
+static DEFAULT_SIGNER_KEY: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
 // Global config with zero-cost reads and hot-reload capability
 static GLOBAL_CONFIG: AtomicPtr<Config> = AtomicPtr::new(std::ptr::null_mut());
 
-/// Get a request-scoped signer with optional signer_key for consistency across related calls
+/// Get a request-scoped signer with optional signer_key for consistency across related calls.
+/// The signer_key is either the signer's pubkey or its name in the signers config
 pub fn get_request_signer_with_signer_key(
     signer_key: Option<&str>,
-) -> Result<Arc<solana_keychain::Signer>, KoraError> {
+) -> Result<Arc<KoraSigner>, KoraError> {
+    get_request_signer_with_guard(signer_key).map(|(signer, _)| signer)
+}
+
+/// `get_request_signer_with_signer_key`, with the signer counted as busy by the least-busy
+/// strategy until the guard is dropped. Hold the guard for the whole request, failures included
+pub fn get_request_signer_with_guard(
+    signer_key: Option<&str>,
+) -> Result<(Arc<KoraSigner>, Option<InFlightGuard>), KoraError> {
     let pool = get_signer_pool()?;
+    let default_signer_key = DEFAULT_SIGNER_KEY.read().clone();
 
     // If client provided a signer signer_key, try to use that specific signer
-    if let Some(signer_key) = signer_key {
-        return pool.get_signer_by_pubkey(signer_key);
+    if let Some(signer_key) = signer_key.or(default_signer_key.as_deref()) {
+        let signer = match Pubkey::from_str(signer_key) {
+            Ok(_) => pool.get_signer_by_pubkey(signer_key),
+            Err(_) => pool.get_signer_by_alias(signer_key),
+        }?;
+        let guard = pool.track_in_flight(&signer.pubkey());
+        return Ok((signer, guard));
     }
 
     // Use configured selection strategy (defaults to round-robin if not specified)
-    pool.get_next_signer()
-        .map_err(|e| KoraError::InternalServerError(format!("Failed to get signer from pool: {e}")))
+    let (signer, guard) = pool.get_next_signer_with_guard().map_err(|e| {
+        KoraError::InternalServerError(format!("Failed to get signer from pool: {e}"))
+    })?;
+    Ok((signer, Some(guard)))
+}
+
+/// The signer the next request without a signer_key would use, without advancing the pool's
+/// selection strategy
+pub fn peek_request_signer() -> Result<Arc<KoraSigner>, KoraError> {
+    let pool = get_signer_pool()?;
+    let default_signer_key = DEFAULT_SIGNER_KEY.read().clone();
+
+    if let Some(signer_key) = default_signer_key {
+        return get_request_signer_with_signer_key(Some(&signer_key));
+    }
+
+    pool.peek_next_signer()
 }
 
 /// Initialize the global signer pool with a SignerPool instance
@@ -46,6 +88,16 @@ pub fn init_signer_pool(pool: SignerPool) -> Result<(), KoraError> {
     Ok(())
 }
 
+/// Direct requests without a signer_key to the signer with `signer_key` (pubkey or name)
+/// instead of the pool's selection strategy
+pub fn set_default_signer_key(signer_key: &str) -> Result<(), KoraError> {
+    let signer = get_request_signer_with_signer_key(Some(signer_key))?;
+    log::info!("Directing requests without a signer key to signer {}", signer.pubkey());
+
+    *DEFAULT_SIGNER_KEY.write() = Some(signer_key.to_string());
+    Ok(())
+}
+
 /// Get a reference to the global signer pool
 pub fn get_signer_pool() -> Result<Arc<SignerPool>, KoraError> {
     let pool_guard = GLOBAL_SIGNER_POOL.read();
@@ -72,12 +124,15 @@ pub fn update_signer_pool(new_pool: SignerPool) -> Result<(), KoraError> {
 }
 
 /// Initialize the global config with a Config instance
-pub fn init_config(config: Config) -> Result<(), KoraError> {
+pub fn init_config(mut config: Config) -> Result<(), KoraError> {
     let current_ptr = GLOBAL_CONFIG.load(Ordering::Acquire);
     if !current_ptr.is_null() {
         return Err(KoraError::InternalServerError("Config already initialized".to_string()));
     }
 
+    // The config file lists disallowed accounts as strings, validators look them up in a set
+    config.validation.init_disallowed_accounts()?;
+
     let config_ptr = Box::into_raw(Box::new(config));
     GLOBAL_CONFIG.store(config_ptr, Ordering::Release);
     Ok(())
@@ -11,6 +11,7 @@ use spl_token_2022_interface::{
         self,
         transfer_fee::{TransferFee, TransferFeeConfig},
         BaseStateWithExtensionsMut, ExtensionType, PodStateWithExtensionsMut,
+        StateWithExtensionsMut,
     },
     pod::PodMint,
     state::{
@@ -19,9 +20,12 @@ use spl_token_2022_interface::{
 };
 use spl_token_interface::state::{Account as TokenAccount, AccountState as SplAccountState, Mint};
 
-use crate::token::{
-    spl_token_2022::{Token2022Account, Token2022Mint},
-    spl_token_2022_util::ParsedExtension,
+use crate::{
+    token::{
+        spl_token_2022::{Token2022Account, Token2022Mint},
+        spl_token_2022_util::ParsedExtension,
+    },
+    validator::account_validator::METAPLEX_METADATA_PROGRAM_ID,
 };
 
 // Common default values used across mock builders
@@ -115,6 +119,7 @@ pub struct TokenAccountMockBuilder {
     // Configuration for different token types
     is_native_spl: COption<u64>,
     is_native_token2022: COption<u64>,
+    state: SplAccountState,
 }
 
 impl Default for TokenAccountMockBuilder {
@@ -137,6 +142,7 @@ impl TokenAccountMockBuilder {
             extensions: Vec::new(),
             is_native_spl: COption::Some(0),
             is_native_token2022: COption::None,
+            state: SplAccountState::Initialized,
         }
     }
 
@@ -195,12 +201,24 @@ impl TokenAccountMockBuilder {
         self
     }
 
-    /// Set account state (for SPL Token compatibility)
-    pub fn with_state(self, _state: SplAccountState) -> Self {
-        // State is handled automatically in build methods, this is for compatibility
+    /// Set account state, also used for Token2022 accounts
+    pub fn with_state(mut self, state: SplAccountState) -> Self {
+        self.state = state;
         self
     }
 
+    pub fn with_frozen(self) -> Self {
+        self.with_state(SplAccountState::Frozen)
+    }
+
+    fn token2022_state(&self) -> Token2022AccountState_ {
+        match self.state {
+            SplAccountState::Uninitialized => Token2022AccountState_::Uninitialized,
+            SplAccountState::Initialized => Token2022AccountState_::Initialized,
+            SplAccountState::Frozen => Token2022AccountState_::Frozen,
+        }
+    }
+
     /// Legacy method for backward compatibility (defaults to SPL Token behavior)
     pub fn with_native(self, native_amount: Option<u64>) -> Self {
         self.with_native_spl(native_amount)
@@ -231,7 +249,7 @@ impl TokenAccountMockBuilder {
             owner: self.owner,
             amount: self.amount,
             delegate: self.delegate,
-            state: SplAccountState::Initialized,
+            state: self.state,
             is_native: self.is_native_spl,
             delegated_amount: self.delegated_amount,
             close_authority: self.close_authority,
@@ -256,7 +274,7 @@ impl TokenAccountMockBuilder {
             owner: self.owner,
             amount: self.amount,
             delegate: self.delegate,
-            state: Token2022AccountState_::Initialized,
+            state: self.token2022_state(),
             is_native: self.is_native_token2022,
             delegated_amount: self.delegated_amount,
             close_authority: self.close_authority,
@@ -274,6 +292,55 @@ impl TokenAccountMockBuilder {
         }
     }
 
+    /// Build Token2022 account with its extensions initialized in the account data
+    pub fn build_token2022_with_extensions(self) -> Result<Account, Box<dyn std::error::Error>> {
+        let account_len =
+            ExtensionType::try_calculate_account_len::<Token2022AccountState>(&self.extensions)?;
+        let mut data = vec![0u8; account_len];
+
+        let mut state =
+            StateWithExtensionsMut::<Token2022AccountState>::unpack_uninitialized(&mut data)?;
+        for extension_type in &self.extensions {
+            match extension_type {
+                ExtensionType::MemoTransfer => {
+                    state.init_extension::<extension::memo_transfer::MemoTransfer>(true)?;
+                }
+                ExtensionType::CpiGuard => {
+                    state.init_extension::<extension::cpi_guard::CpiGuard>(true)?;
+                }
+                ExtensionType::ImmutableOwner => {
+                    state.init_extension::<extension::immutable_owner::ImmutableOwner>(true)?;
+                }
+                ExtensionType::TransferFeeAmount => {
+                    state.init_extension::<extension::transfer_fee::TransferFeeAmount>(true)?;
+                }
+                // Add other extension types as needed
+                _ => {}
+            }
+        }
+
+        state.base = Token2022AccountState {
+            mint: self.mint,
+            owner: self.owner,
+            amount: self.amount,
+            delegate: self.delegate,
+            state: self.token2022_state(),
+            is_native: self.is_native_token2022,
+            delegated_amount: self.delegated_amount,
+            close_authority: self.close_authority,
+        };
+        state.pack_base();
+        state.init_account_type()?;
+
+        Ok(Account {
+            lamports: self.lamports,
+            data,
+            owner: spl_token_2022_interface::id(),
+            executable: false,
+            rent_epoch: self.rent_epoch,
+        })
+    }
+
     /// Build Token2022 account as custom structure (for Token2022Account type)
     pub fn build_as_custom_token2022_token_account(
         self,
@@ -284,7 +351,7 @@ impl TokenAccountMockBuilder {
             owner: self.owner,
             amount: self.amount,
             delegate: into_rust_option(self.delegate),
-            state: Token2022AccountState_::Initialized.into(),
+            state: self.token2022_state().into(),
             is_native: into_rust_option(self.is_native_token2022),
             delegated_amount: self.delegated_amount,
             close_authority: into_rust_option(self.close_authority),
@@ -307,6 +374,8 @@ pub struct MintAccountMockBuilder {
     rent_epoch: u64,
     // Token2022-specific fields
     extensions: Vec<ExtensionType>,
+    transfer_fee_config: Option<TransferFeeConfig>,
+    transfer_hook_program_id: Option<Pubkey>,
 }
 
 impl Default for MintAccountMockBuilder {
@@ -326,6 +395,8 @@ impl MintAccountMockBuilder {
             lamports: 0,
             rent_epoch: DEFAULT_RENT_EPOCH,
             extensions: Vec::new(),
+            transfer_fee_config: None,
+            transfer_hook_program_id: None,
         }
     }
 
@@ -383,6 +454,18 @@ impl MintAccountMockBuilder {
         self
     }
 
+    /// Add the `TransferFeeConfig` extension with `transfer_fee_config` as its data
+    pub fn with_transfer_fee_config(mut self, transfer_fee_config: TransferFeeConfig) -> Self {
+        self.transfer_fee_config = Some(transfer_fee_config);
+        self.with_extension(ExtensionType::TransferFeeConfig)
+    }
+
+    /// Add the `TransferHook` extension pointing to `program_id`
+    pub fn with_transfer_hook_program(mut self, program_id: Pubkey) -> Self {
+        self.transfer_hook_program_id = Some(program_id);
+        self.with_extension(ExtensionType::TransferHook)
+    }
+
     pub fn build(self) -> Account {
         let mint_data = Mint {
             mint_authority: self.mint_authority,
@@ -464,7 +547,13 @@ impl MintAccountMockBuilder {
                             )?;
                     }
                     ExtensionType::TransferFeeConfig => {
-                        state.init_extension::<extension::transfer_fee::TransferFeeConfig>(true)?;
+                        let transfer_fee_config = state
+                            .init_extension::<extension::transfer_fee::TransferFeeConfig>(
+                            true,
+                        )?;
+                        if let Some(config) = self.transfer_fee_config {
+                            *transfer_fee_config = config;
+                        }
                     }
                     ExtensionType::PermanentDelegate => {
                         state.init_extension::<extension::permanent_delegate::PermanentDelegate>(
@@ -472,7 +561,12 @@ impl MintAccountMockBuilder {
                         )?;
                     }
                     ExtensionType::TransferHook => {
-                        state.init_extension::<extension::transfer_hook::TransferHook>(true)?;
+                        let transfer_hook =
+                            state.init_extension::<extension::transfer_hook::TransferHook>(true)?;
+                        if let Some(program_id) = self.transfer_hook_program_id {
+                            transfer_hook.program_id =
+                                OptionalNonZeroPubkey::try_from(Some(program_id))?;
+                        }
                     }
                     // Add other extension types as needed
                     _ => {}
@@ -536,6 +630,45 @@ pub fn create_mock_non_executable_account() -> Account {
     AccountMockBuilder::new().with_executable(false).build()
 }
 
+/// Metaplex metadata account with `update_authority`, sized like a real metadata account
+pub fn create_mock_metadata_account(update_authority: &Pubkey) -> Account {
+    let mut data = vec![4]; // Key::MetadataV1
+    data.extend_from_slice(update_authority.as_ref());
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.resize(679, 0);
+
+    AccountMockBuilder::new()
+        .with_owner(METAPLEX_METADATA_PROGRAM_ID)
+        .with_executable(false)
+        .with_data(data)
+        .build()
+}
+
+/// Metaplex metadata account of `mint`, strings NUL-padded to their maximum length like on-chain
+pub fn create_mock_token_metadata_account(
+    mint: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Account {
+    let mut data = vec![4]; // Key::MetadataV1
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(mint.as_ref());
+    for (value, max_len) in [(name, 32), (symbol, 10), (uri, 200)] {
+        let mut padded = value.as_bytes().to_vec();
+        padded.resize(max_len, 0);
+        data.extend_from_slice(&(max_len as u32).to_le_bytes());
+        data.extend_from_slice(&padded);
+    }
+    data.resize(679, 0);
+
+    AccountMockBuilder::new()
+        .with_owner(METAPLEX_METADATA_PROGRAM_ID)
+        .with_executable(false)
+        .with_data(data)
+        .build()
+}
+
 pub fn create_mock_token_account(owner: &Pubkey, mint: &Pubkey) -> Account {
     TokenAccountMockBuilder::new().with_owner(owner).with_mint(mint).build()
 }
@@ -18,8 +18,11 @@ use solana_sdk::{pubkey::Pubkey, signature::Keypair};
 // Re-export mock utilities for centralized access
 pub use account_mock::*;
 pub use rpc_mock::*;
+pub use setup::{TestHarness, TestHarnessBuilder};
 use solana_keychain::{Signer, SolanaSigner};
 
+pub mod setup;
+
 /// Setup or retrieve test signer for global state initialization
 ///
 /// Returns the signer's public key.
@@ -35,7 +38,7 @@ pub fn setup_or_get_test_signer() -> Pubkey {
 
     let pool = SignerPool::new(vec![SignerWithMetadata::new(
         "test_signer".to_string(),
-        Arc::new(external_signer),
+        Arc::new(external_signer.into()),
         1,
     )]);
 
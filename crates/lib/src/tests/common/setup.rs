@@ -0,0 +1,192 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, MutexGuard},
+    time::Duration,
+};
+
+use jsonrpsee::server::{
+    middleware::proxy_get_request::ProxyGetRequestLayer, ServerBuilder, ServerHandle,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_keychain::Signer;
+use solana_sdk::signature::Keypair;
+
+use crate::{
+    rpc_server::{
+        auth::{AdminApiKeyAuthLayer, ApiKeyAuthLayer, HmacAuthLayer, JwtAuthLayer},
+        rpc::KoraRpc,
+        server::{build_rpc_module, build_ws_rpc_module},
+    },
+    signer::{pool::SignerWithMetadata, SignerPool},
+    state::update_signer_pool,
+    tests::{
+        common::setup_or_get_test_signer,
+        config_mock::{mock_state::setup_config_mock, ConfigMockBuilder},
+        rpc_mock::RpcMockBuilder,
+    },
+    Config,
+};
+
+const LIVENESS_TIMEOUT: Duration = Duration::from_secs(5);
+const LIVENESS_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Running Kora RPC server bound to a random local port
+///
+/// Holds the config mock lock for its whole lifetime, so tests using it run one at a time
+/// with respect to every other test relying on the mocked config. Don't call
+/// `ConfigMockBuilder::build_and_setup` while a harness is alive, pass the config to the builder.
+pub struct TestHarness {
+    addr: SocketAddr,
+    handle: ServerHandle,
+    ws_addr: Option<SocketAddr>,
+    ws_handle: Option<ServerHandle>,
+    _config_guard: MutexGuard<'static, ()>,
+}
+
+impl TestHarness {
+    pub fn builder() -> TestHarnessBuilder {
+        TestHarnessBuilder::default()
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// URL of the WebSocket server, panics if `subscribe_transaction_events` is disabled
+    pub fn ws_url(&self) -> String {
+        let ws_addr = self.ws_addr.expect("WebSocket server not running");
+        format!("ws://{ws_addr}")
+    }
+
+    /// Stop the servers and wait until they have fully shut down
+    pub async fn shutdown(self) {
+        if self.handle.stop().is_ok() {
+            self.handle.stopped().await;
+        }
+        if let Some(ws_handle) = self.ws_handle {
+            if ws_handle.stop().is_ok() {
+                ws_handle.stopped().await;
+            }
+        }
+    }
+}
+
+/// Builder for [`TestHarness`], defaults to the mock config, the shared test signer and a
+/// mock RPC client
+#[derive(Default)]
+pub struct TestHarnessBuilder {
+    config: Option<Config>,
+    signer: Option<Keypair>,
+    rpc_client: Option<Arc<RpcClient>>,
+}
+
+impl TestHarnessBuilder {
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Replace the global signer pool with a pool containing only this keypair
+    pub fn with_signer(mut self, signer: Keypair) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Point the server at a real (or mockito) RPC endpoint instead of the default mock client
+    pub fn with_rpc_url(mut self, rpc_url: String) -> Self {
+        self.rpc_client = Some(Arc::new(RpcClient::new(rpc_url)));
+        self
+    }
+
+    pub fn with_rpc_client(mut self, rpc_client: Arc<RpcClient>) -> Self {
+        self.rpc_client = Some(rpc_client);
+        self
+    }
+
+    /// Start the server on a random port and wait until `/liveness` answers
+    pub async fn build(self) -> Result<TestHarness, anyhow::Error> {
+        let config = self.config.unwrap_or_else(|| ConfigMockBuilder::new().build());
+        let config_guard = setup_config_mock(config.clone());
+
+        match self.signer {
+            Some(keypair) => {
+                let signer = Signer::from_memory(&keypair.to_base58_string())
+                    .map_err(|e| anyhow::anyhow!("Failed to create test signer: {e}"))?;
+                update_signer_pool(SignerPool::new(vec![SignerWithMetadata::new(
+                    "test_harness_signer".to_string(),
+                    Arc::new(signer.into()),
+                    1,
+                )]))?;
+            }
+            None => {
+                setup_or_get_test_signer();
+            }
+        }
+
+        let rpc_client = self.rpc_client.unwrap_or_else(|| RpcMockBuilder::new().build());
+        let module = build_rpc_module(KoraRpc::new(rpc_client))?;
+
+        // Same auth layers as `run_rpc_server`, without the metrics and usage limit globals
+        let auth_layers =
+            tower::ServiceBuilder::new()
+                .option_layer(config.kora.auth.api_key.clone().map(ApiKeyAuthLayer::new))
+                .option_layer(
+                    config.kora.auth.hmac_secret.clone().map(|secret| {
+                        HmacAuthLayer::new(secret, config.kora.auth.max_timestamp_age)
+                    }),
+                )
+                .option_layer(JwtAuthLayer::from_config(&config.kora.auth)?)
+                .into_inner();
+        let middleware = tower::ServiceBuilder::new()
+            .layer(ProxyGetRequestLayer::new("/liveness", "liveness")?)
+            .layer(auth_layers.clone())
+            .option_layer(config.kora.auth.admin_api_key.clone().map(AdminApiKeyAuthLayer::new));
+
+        let server = ServerBuilder::default()
+            .set_middleware(middleware)
+            .http_only()
+            .build("127.0.0.1:0")
+            .await?;
+        let addr = server.local_addr()?;
+        let handle = server.start(module)?;
+
+        let (ws_addr, ws_handle) = if config.kora.enabled_methods.subscribe_transaction_events {
+            let ws_server = ServerBuilder::default()
+                .set_middleware(tower::ServiceBuilder::new().layer(auth_layers))
+                .ws_only()
+                .build("127.0.0.1:0")
+                .await?;
+            let ws_addr = ws_server.local_addr()?;
+            (Some(ws_addr), Some(ws_server.start(build_ws_rpc_module()?)?))
+        } else {
+            (None, None)
+        };
+
+        let harness = TestHarness { addr, handle, ws_addr, ws_handle, _config_guard: config_guard };
+        if let Err(e) = wait_for_liveness(&harness.http_url()).await {
+            harness.shutdown().await;
+            return Err(e);
+        }
+
+        Ok(harness)
+    }
+}
+
+async fn wait_for_liveness(base_url: &str) -> Result<(), anyhow::Error> {
+    let client = reqwest::Client::new();
+    let deadline = tokio::time::Instant::now() + LIVENESS_TIMEOUT;
+
+    loop {
+        match client.get(format!("{base_url}/liveness")).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            _ if tokio::time::Instant::now() >= deadline => {
+                return Err(anyhow::anyhow!("Server at {base_url} did not become live in time"));
+            }
+            _ => tokio::time::sleep(LIVENESS_POLL_INTERVAL).await,
+        }
+    }
+}
@@ -1,17 +1,24 @@
 use crate::{
     config::{
-        AuthConfig, CacheConfig, Config, EnabledMethods, FeePayerBalanceMetricsConfig,
-        FeePayerPolicy, KoraConfig, MetricsConfig, NonceInstructionPolicy, SplTokenConfig,
-        SplTokenInstructionPolicy, SystemInstructionPolicy, Token2022Config,
-        Token2022InstructionPolicy, UsageLimitConfig, ValidationConfig,
+        AnchorProgramConfig, AuthConfig, AutoComputeBudgetConfig, CacheConfig, Config,
+        EnabledMethods, FeePayerBalanceMetricsConfig, FeePayerPolicy, KoraConfig, MetricsConfig,
+        NonceInstructionPolicy, PushGatewayConfig, SplTokenConfig, SplTokenInstructionPolicy,
+        SystemInstructionPolicy, Token2022Config, Token2022InstructionPolicy, UsageLimitConfig,
+        ValidationConfig, WebhookConfig, WindowType,
     },
-    constant::DEFAULT_MAX_REQUEST_BODY_SIZE,
-    fee::price::PriceConfig,
-    oracle::PriceSource,
+    constant::{DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_REQUEST_BODY_SIZE},
+    fee::{
+        fee::FeeEstimationStrategy,
+        price::{PriceConfig, PriceModel},
+    },
+    oracle::{MockOracleConfig, PriceSource},
     signer::config::{
-        MemorySignerConfig, PrivySignerConfig, SelectionStrategy, SignerConfig, SignerPoolConfig,
+        AwsKmsSignerConfig, LedgerSignerConfig, MemorySignerConfig, PrivySignerConfig,
+        SelectionStrategy, SignerConfig, SignerHealthCheckConfig, SignerPoolConfig,
         SignerPoolSettings, SignerTypeConfig, TurnkeySignerConfig, VaultSignerConfig,
     },
+    token::spl_token_2022_util,
+    validator::account_validator::AccountType,
 };
 use solana_sdk::pubkey::Pubkey;
 
@@ -91,6 +98,21 @@ impl ConfigMockBuilder {
                     fee_payer_policy: FeePayerPolicy::default(),
                     price: PriceConfig::default(),
                     token_2022: Token2022Config::default(),
+                    validate_user_token_balance: false,
+                    account_type_allowlist: None,
+                    follow_cpi: false,
+                    mock_oracle: MockOracleConfig::default(),
+                    account_for_transfer_fee: false,
+                    strict_fee_payer: false,
+                    validate_nft_metadata: false,
+                    fee_estimation_strategy: FeeEstimationStrategy::default(),
+                    max_lookup_table_accounts: None,
+                    allow_program_upgrades: false,
+                    disallowed_programs: vec![],
+                    anchor_programs: vec![],
+                    max_blockhash_age_slots: None,
+                    use_rpc_fee_estimate: false,
+                    parsed_disallowed_accounts: None,
                 },
                 kora: KoraConfig {
                     rate_limit: 100,
@@ -103,8 +125,20 @@ impl ConfigMockBuilder {
                         enabled: true,
                         default_ttl: 300,
                         account_ttl: 60,
+                        token_balance_ttl: 5,
+                        token_metadata_ttl: 3600,
                     },
                     usage_limit: UsageLimitConfig::default(),
+                    webhook: None,
+                    auto_add_lookup_table: None,
+                    auto_compute_budget: None,
+                    preflight_simulate: false,
+                    preflight_simulation_commitment: "confirmed".to_string(),
+                    enable_rpc_proxy: false,
+                    proxy_allowed_methods: vec![],
+                    backpressure_timeout_ms: 0,
+                    sign_message_allow_transaction_bytes: false,
+                    max_batch_size: DEFAULT_MAX_BATCH_SIZE,
                 },
                 metrics: MetricsConfig::default(),
             },
@@ -150,6 +184,16 @@ impl ConfigMockBuilder {
         self
     }
 
+    pub fn with_price_model(mut self, model: PriceModel) -> Self {
+        self.config.validation.price.model = model;
+        self
+    }
+
+    pub fn with_mock_oracle(mut self, mock_oracle: MockOracleConfig) -> Self {
+        self.config.validation.mock_oracle = mock_oracle;
+        self
+    }
+
     pub fn with_allowed_programs(mut self, programs: Vec<String>) -> Self {
         self.config.validation.allowed_programs = programs;
         self
@@ -180,6 +224,17 @@ impl ConfigMockBuilder {
         self
     }
 
+    pub fn with_admin_api_key_auth(mut self, admin_api_key: String) -> Self {
+        self.config.kora.auth.admin_api_key = Some(admin_api_key);
+        self
+    }
+
+    pub fn with_jwt_auth(mut self, jwt_public_key: String, jwt_audience: String) -> Self {
+        self.config.kora.auth.jwt_public_key = Some(jwt_public_key);
+        self.config.kora.auth.jwt_audience = Some(jwt_audience);
+        self
+    }
+
     pub fn with_max_allowed_lamports(mut self, max_lamports: u64) -> Self {
         self.config.validation.max_allowed_lamports = max_lamports;
         self
@@ -195,6 +250,26 @@ impl ConfigMockBuilder {
         self
     }
 
+    pub fn with_system_policy(mut self, policy: SystemInstructionPolicy) -> Self {
+        self.config.validation.fee_payer_policy.system = policy;
+        self
+    }
+
+    pub fn with_nonce_policy(mut self, policy: NonceInstructionPolicy) -> Self {
+        self.config.validation.fee_payer_policy.system.nonce = policy;
+        self
+    }
+
+    pub fn with_spl_token_policy(mut self, policy: SplTokenInstructionPolicy) -> Self {
+        self.config.validation.fee_payer_policy.spl_token = policy;
+        self
+    }
+
+    pub fn with_token_2022_policy(mut self, policy: Token2022InstructionPolicy) -> Self {
+        self.config.validation.fee_payer_policy.token_2022 = policy;
+        self
+    }
+
     pub fn with_usage_limit_enabled(mut self, enabled: bool) -> Self {
         self.config.kora.usage_limit.enabled = enabled;
         self
@@ -215,6 +290,105 @@ impl ConfigMockBuilder {
         self
     }
 
+    pub fn with_usage_limit_sliding_window(
+        mut self,
+        window_seconds: u64,
+        precision_ms: u64,
+    ) -> Self {
+        self.config.kora.usage_limit.window_type = WindowType::Sliding { precision_ms };
+        self.config.kora.usage_limit.window_seconds = window_seconds;
+        self
+    }
+
+    pub fn with_webhook(mut self, webhook: WebhookConfig) -> Self {
+        self.config.kora.webhook = Some(webhook);
+        self
+    }
+
+    pub fn with_preflight_simulate(mut self, enabled: bool) -> Self {
+        self.config.kora.preflight_simulate = enabled;
+        self
+    }
+
+    pub fn with_rpc_proxy(mut self, proxy_allowed_methods: Vec<String>) -> Self {
+        self.config.kora.enable_rpc_proxy = true;
+        self.config.kora.proxy_allowed_methods = proxy_allowed_methods;
+        self
+    }
+
+    pub fn with_auto_add_lookup_table(mut self, lookup_table: Option<String>) -> Self {
+        self.config.kora.auto_add_lookup_table = lookup_table;
+        self
+    }
+
+    pub fn with_auto_compute_budget(
+        mut self,
+        auto_compute_budget: Option<AutoComputeBudgetConfig>,
+    ) -> Self {
+        self.config.kora.auto_compute_budget = auto_compute_budget;
+        self
+    }
+
+    pub fn with_account_for_transfer_fee(mut self, account_for_transfer_fee: bool) -> Self {
+        self.config.validation.account_for_transfer_fee = account_for_transfer_fee;
+        self
+    }
+
+    pub fn with_strict_fee_payer(mut self, strict_fee_payer: bool) -> Self {
+        self.config.validation.strict_fee_payer = strict_fee_payer;
+        self
+    }
+
+    pub fn with_validate_nft_metadata(mut self, validate_nft_metadata: bool) -> Self {
+        self.config.validation.validate_nft_metadata = validate_nft_metadata;
+        self
+    }
+
+    pub fn with_fee_estimation_strategy(mut self, strategy: FeeEstimationStrategy) -> Self {
+        self.config.validation.fee_estimation_strategy = strategy;
+        self
+    }
+
+    pub fn with_max_lookup_table_accounts(mut self, max_accounts: Option<usize>) -> Self {
+        self.config.validation.max_lookup_table_accounts = max_accounts;
+        self
+    }
+
+    pub fn with_disallowed_programs(mut self, programs: Vec<String>) -> Self {
+        self.config.validation.disallowed_programs = programs;
+        self
+    }
+
+    pub fn with_anchor_programs(mut self, programs: Vec<AnchorProgramConfig>) -> Self {
+        self.config.validation.anchor_programs = programs;
+        self
+    }
+
+    pub fn with_use_rpc_fee_estimate(mut self, use_rpc_fee_estimate: bool) -> Self {
+        self.config.validation.use_rpc_fee_estimate = use_rpc_fee_estimate;
+        self
+    }
+
+    pub fn with_max_blockhash_age_slots(mut self, max_age_slots: Option<u64>) -> Self {
+        self.config.validation.max_blockhash_age_slots = max_age_slots;
+        self
+    }
+
+    pub fn with_allow_program_upgrades(mut self, allow: bool) -> Self {
+        self.config.validation.allow_program_upgrades = allow;
+        self
+    }
+
+    pub fn with_account_type_allowlist(mut self, account_types: Vec<AccountType>) -> Self {
+        self.config.validation.account_type_allowlist = Some(account_types);
+        self
+    }
+
+    pub fn with_follow_cpi(mut self, follow_cpi: bool) -> Self {
+        self.config.validation.follow_cpi = follow_cpi;
+        self
+    }
+
     pub fn with_disallowed_accounts(mut self, accounts: Vec<String>) -> Self {
         self.config.validation.disallowed_accounts = accounts;
         self
@@ -232,9 +406,28 @@ impl ConfigMockBuilder {
         self
     }
 
+    /// Allow only the given mint and account extensions on payment tokens
+    pub fn with_token_2022_extension_allowlist(mut self, extensions: Vec<String>) -> Self {
+        self.config.validation.token_2022.allowed_extensions = extensions;
+        let _ = self.config.validation.token_2022.initialize();
+        self
+    }
+
+    /// Block the given extensions, sorting each name into the mint or account blocklist
+    pub fn with_token_2022_extension_blocklist(mut self, extensions: Vec<String>) -> Self {
+        let (account_extensions, mint_extensions): (Vec<String>, Vec<String>) = extensions
+            .into_iter()
+            .partition(|name| spl_token_2022_util::parse_account_extension_string(name).is_some());
+        self.config.validation.token_2022.blocked_mint_extensions = mint_extensions;
+        self.config.validation.token_2022.blocked_account_extensions = account_extensions;
+        let _ = self.config.validation.token_2022.initialize();
+        self
+    }
+
     /// Build and setup the config mock with mutex lock
     /// Returns a lock guard that should be held for the duration of the test
-    pub fn build_and_setup(self) -> std::sync::MutexGuard<'static, ()> {
+    pub fn build_and_setup(mut self) -> std::sync::MutexGuard<'static, ()> {
+        let _ = self.config.validation.init_disallowed_accounts();
         mock_state::setup_config_mock(self.config)
     }
 }
@@ -263,6 +456,21 @@ impl ValidationConfigBuilder {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig::default(),
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
         }
     }
@@ -331,8 +539,20 @@ impl KoraConfigBuilder {
                     enabled: true,
                     default_ttl: 300,
                     account_ttl: 60,
+                    token_balance_ttl: 5,
+                    token_metadata_ttl: 3600,
                 },
                 usage_limit: UsageLimitConfig::default(),
+                webhook: None,
+                auto_add_lookup_table: None,
+                auto_compute_budget: None,
+                preflight_simulate: false,
+                preflight_simulation_commitment: "confirmed".to_string(),
+                enable_rpc_proxy: false,
+                proxy_allowed_methods: vec![],
+                backpressure_timeout_ms: 0,
+                sign_message_allow_transaction_bytes: false,
+                max_batch_size: DEFAULT_MAX_BATCH_SIZE,
             },
         }
     }
@@ -351,6 +571,16 @@ impl KoraConfigBuilder {
         self
     }
 
+    pub fn with_sign_message_allow_transaction_bytes(mut self, allow: bool) -> Self {
+        self.config.sign_message_allow_transaction_bytes = allow;
+        self
+    }
+
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.config.max_batch_size = max_batch_size;
+        self
+    }
+
     pub fn with_auth(mut self, auth: AuthConfig) -> Self {
         self.config.auth = auth;
         self
@@ -385,6 +615,8 @@ impl CacheConfigBuilder {
                 enabled: true,
                 default_ttl: 300,
                 account_ttl: 60,
+                token_balance_ttl: 5,
+                token_metadata_ttl: 3600,
             },
         }
     }
@@ -413,8 +645,27 @@ impl CacheConfigBuilder {
         self
     }
 
+    pub fn with_token_balance_ttl(mut self, ttl: u64) -> Self {
+        self.config.token_balance_ttl = ttl;
+        self
+    }
+
+    pub fn with_token_metadata_ttl(mut self, ttl: u64) -> Self {
+        self.config.token_metadata_ttl = ttl;
+        self
+    }
+
     pub fn disabled() -> Self {
-        Self { config: CacheConfig { url: None, enabled: false, default_ttl: 0, account_ttl: 0 } }
+        Self {
+            config: CacheConfig {
+                url: None,
+                enabled: false,
+                default_ttl: 0,
+                account_ttl: 0,
+                token_balance_ttl: 0,
+                token_metadata_ttl: 0,
+            },
+        }
     }
 }
 
@@ -430,7 +681,16 @@ impl Default for AuthConfigBuilder {
 
 impl AuthConfigBuilder {
     pub fn new() -> Self {
-        Self { config: AuthConfig { api_key: None, hmac_secret: None, max_timestamp_age: 10 } }
+        Self {
+            config: AuthConfig {
+                api_key: None,
+                hmac_secret: None,
+                admin_api_key: None,
+                max_timestamp_age: 10,
+                jwt_public_key: None,
+                jwt_audience: None,
+            },
+        }
     }
 
     pub fn build(self) -> AuthConfig {
@@ -447,6 +707,17 @@ impl AuthConfigBuilder {
         self
     }
 
+    pub fn with_admin_api_key(mut self, admin_api_key: String) -> Self {
+        self.config.admin_api_key = Some(admin_api_key);
+        self
+    }
+
+    pub fn with_jwt(mut self, jwt_public_key: String, jwt_audience: String) -> Self {
+        self.config.jwt_public_key = Some(jwt_public_key);
+        self.config.jwt_audience = Some(jwt_audience);
+        self
+    }
+
     pub fn with_both_auth(mut self, api_key: String, hmac_secret: String) -> Self {
         self.config.api_key = Some(api_key);
         self.config.hmac_secret = Some(hmac_secret);
@@ -473,6 +744,16 @@ impl FeePayerPolicyBuilder {
         self.config
     }
 
+    pub fn with_instruction_type_allowlist(mut self, instruction_types: Vec<String>) -> Self {
+        self.config.instruction_type_allowlist = Some(instruction_types);
+        self
+    }
+
+    pub fn with_reject_fee_payer_as_unknown_signer(mut self, reject: bool) -> Self {
+        self.config.reject_fee_payer_as_unknown_signer = reject;
+        self
+    }
+
     pub fn with_sol_transfers(mut self, allow: bool) -> Self {
         self.config.system.allow_transfer = allow;
         self
@@ -585,6 +866,8 @@ impl FeePayerPolicyBuilder {
                         allow_withdraw: false,
                         allow_authorize: false,
                     },
+                    allowed_receivers: None,
+                    min_allowed_lamports: None,
                 },
                 spl_token: SplTokenInstructionPolicy {
                     allow_transfer: false,
@@ -599,6 +882,7 @@ impl FeePayerPolicyBuilder {
                     allow_initialize_mint: false,
                     allow_initialize_account: false,
                     allow_initialize_multisig: false,
+                    allowed_receivers: None,
                 },
                 token_2022: Token2022InstructionPolicy {
                     allow_transfer: false,
@@ -613,7 +897,11 @@ impl FeePayerPolicyBuilder {
                     allow_initialize_mint: false,
                     allow_initialize_account: false,
                     allow_initialize_multisig: false,
+                    allowed_receivers: None,
                 },
+                instruction_type_allowlist: None,
+                reject_fee_payer_as_unknown_signer: false,
+                program_instruction_allowlist: vec![],
             },
         }
     }
@@ -657,6 +945,11 @@ impl MetricsConfigBuilder {
         self
     }
 
+    pub fn with_push_gateway(mut self, push_gateway: PushGatewayConfig) -> Self {
+        self.config.push_gateway = Some(push_gateway);
+        self
+    }
+
     pub fn enabled_with_endpoint(endpoint: String) -> Self {
         Self {
             config: MetricsConfig {
@@ -665,6 +958,7 @@ impl MetricsConfigBuilder {
                 scrape_interval: 10,
                 endpoint,
                 fee_payer_balance: FeePayerBalanceMetricsConfig::default(),
+                push_gateway: None,
             },
         }
     }
@@ -686,6 +980,10 @@ impl SignerPoolConfigBuilder {
             config: SignerPoolConfig {
                 signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
                 signers: vec![],
+                verify_signing_capability: false,
+                max_signers: None,
+                concurrent_signing_limit: 0,
+                health_check: Default::default(),
             },
         }
     }
@@ -699,6 +997,22 @@ impl SignerPoolConfigBuilder {
         self
     }
 
+    pub fn with_max_signers(mut self, max_signers: usize) -> Self {
+        self.config.max_signers = Some(max_signers);
+        self
+    }
+
+    pub fn with_concurrent_signing_limit(mut self, limit: usize) -> Self {
+        self.config.concurrent_signing_limit = limit;
+        self
+    }
+
+    pub fn with_health_check(mut self, interval_seconds: u64, unhealthy_threshold: u32) -> Self {
+        self.config.health_check =
+            SignerHealthCheckConfig { interval_seconds, unhealthy_threshold };
+        self
+    }
+
     pub fn with_signers(mut self, signers: Vec<SignerConfig>) -> Self {
         self.config.signers = signers;
         self
@@ -795,6 +1109,35 @@ impl SignerPoolConfigBuilder {
         self.config.signers.push(signer);
         self
     }
+
+    pub fn with_aws_kms_signer(
+        mut self,
+        name: String,
+        key_arn_env: String,
+        region_env: String,
+        assume_role_arn_env: Option<String>,
+        weight: Option<u32>,
+    ) -> Self {
+        let signer = SignerConfig {
+            name,
+            weight,
+            config: SignerTypeConfig::AwsKms {
+                config: AwsKmsSignerConfig { key_arn_env, region_env, assume_role_arn_env },
+            },
+        };
+        self.config.signers.push(signer);
+        self
+    }
+
+    pub fn with_ledger_signer(mut self, name: String, account: u32, weight: Option<u32>) -> Self {
+        let signer = SignerConfig {
+            name,
+            weight,
+            config: SignerTypeConfig::Ledger { config: LedgerSignerConfig { account } },
+        };
+        self.config.signers.push(signer);
+        self
+    }
 }
 
 pub fn get_default_config() -> Config {
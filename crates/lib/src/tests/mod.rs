@@ -13,8 +13,14 @@ pub mod cache_mock;
 #[cfg(test)]
 pub mod rpc_mock;
 
+#[cfg(test)]
+pub mod signer_mock;
+
 #[cfg(test)]
 pub mod toml_mock;
 
 #[cfg(test)]
 pub mod transaction_mock;
+
+#[cfg(test)]
+pub mod regression;
@@ -0,0 +1,189 @@
+//! Fee estimation regression suite
+//!
+//! Canonical transactions are stored as base64 fixtures in
+//! `tests/common/fixtures/fee_estimation/`, each with the fee it is expected to cost. Every
+//! fixture is estimated against the same mocked RPC responses and must land within
+//! `KORA_FEE_REGRESSION_TOLERANCE_PERCENT` (10% by default) of its expected fee.
+//!
+//! Accounts in the fixtures are `[n; 32]` keys: `[1; 32]` is the Kora fee payer and the lookup
+//! table `[6; 32]` holds `[7; 32]`. Blockhashes and signatures are zeroed, estimates never check
+//! them.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_address_lookup_table_interface::state::{AddressLookupTable, LookupTableMeta};
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    constant::ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION,
+    fee::fee::{FeeConfigUtil, FeeEstimationStrategy},
+    oracle::PriceSource,
+    tests::{config_mock::ConfigMockBuilder, rpc_mock::RpcMockBuilder},
+    transaction::{TransactionUtil, VersionedTransactionResolved},
+};
+
+const TOLERANCE_ENV: &str = "KORA_FEE_REGRESSION_TOLERANCE_PERCENT";
+const DEFAULT_TOLERANCE_PERCENT: f64 = 10.0;
+
+const FEE_PAYER: Pubkey = Pubkey::new_from_array([1; 32]);
+const LOOKUP_TABLE_RECIPIENT: Pubkey = Pubkey::new_from_array([7; 32]);
+
+struct Canonical {
+    name: &'static str,
+    fixture: &'static str,
+    strategy: FeeEstimationStrategy,
+    /// `getFeeForMessage` answer of the mocked RPC node
+    fee_for_message: u64,
+    /// `unitsConsumed` of the mocked simulation
+    units_consumed: u64,
+    /// `innerInstructions` of the mocked simulation
+    inner_instructions: Value,
+    expected_fee: u64,
+}
+
+fn canonicals() -> Vec<Canonical> {
+    vec![
+        // Fee payer sends 100_000 lamports
+        Canonical {
+            name: "simple_transfer",
+            fixture: include_str!("../common/fixtures/fee_estimation/simple_transfer.b64"),
+            strategy: FeeEstimationStrategy::GetFeeForMessage,
+            fee_for_message: 5000,
+            units_consumed: 150,
+            inner_instructions: json!([]),
+            expected_fee: 5000 + 100_000 + ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION,
+        },
+        // User-signed swap whose program funds a 2_039_280 lamports account from the fee payer
+        // through a CPI, only visible in the simulated inner instructions
+        Canonical {
+            name: "swap_with_cpi",
+            fixture: include_str!("../common/fixtures/fee_estimation/swap_with_cpi.b64"),
+            strategy: FeeEstimationStrategy::GetFeeForMessage,
+            fee_for_message: 10_000,
+            units_consumed: 45_000,
+            inner_instructions: json!([{
+                "index": 0,
+                "instructions": [{
+                    "programIdIndex": 4,
+                    "accounts": [0, 2],
+                    "data": "3Bxs4h24hBtQy9rw",
+                    "stackHeight": 2
+                }]
+            }]),
+            expected_fee: 10_000 + 2_039_280 + ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION,
+        },
+        // Fee payer sends 250_000 lamports to a recipient only loaded from a lookup table
+        Canonical {
+            name: "v0_with_lookup_table",
+            fixture: include_str!("../common/fixtures/fee_estimation/v0_with_lookup_table.b64"),
+            strategy: FeeEstimationStrategy::GetFeeForMessage,
+            fee_for_message: 5000,
+            units_consumed: 150,
+            inner_instructions: json!([]),
+            expected_fee: 5000 + 250_000 + ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION,
+        },
+        // 200_000 unit limit at 50_000 micro-lamports per unit, 150_000 units consumed in
+        // simulation and a 10_000 lamports transfer
+        Canonical {
+            name: "priority_fee",
+            fixture: include_str!("../common/fixtures/fee_estimation/priority_fee.b64"),
+            strategy: FeeEstimationStrategy::Simulation { commitment: "confirmed".to_string() },
+            fee_for_message: 15_000,
+            units_consumed: 150_000,
+            inner_instructions: json!([]),
+            expected_fee: 5000 + 7500 + 10_000 + ESTIMATED_LAMPORTS_FOR_PAYMENT_INSTRUCTION,
+        },
+    ]
+}
+
+fn tolerance_percent() -> f64 {
+    std::env::var(TOLERANCE_ENV)
+        .ok()
+        .map(|value| value.parse().unwrap_or_else(|_| panic!("Invalid {TOLERANCE_ENV}: {value}")))
+        .unwrap_or(DEFAULT_TOLERANCE_PERCENT)
+}
+
+fn lookup_table_account() -> Value {
+    let lookup_table = AddressLookupTable {
+        meta: LookupTableMeta {
+            deactivation_slot: u64::MAX,
+            last_extended_slot: 0,
+            last_extended_slot_start_index: 0,
+            authority: Some(Pubkey::new_from_array([9; 32])),
+            _padding: 0,
+        },
+        addresses: vec![LOOKUP_TABLE_RECIPIENT].into(),
+    };
+
+    json!({
+        "context": { "slot": 1 },
+        "value": {
+            "data": [STANDARD.encode(lookup_table.serialize_for_tests().unwrap()), "base64"],
+            "executable": false,
+            "lamports": 0,
+            "owner": "AddressLookupTab1e1111111111111111111111111",
+            "rentEpoch": 0
+        }
+    })
+}
+
+async fn estimate(canonical: &Canonical) -> u64 {
+    let _m = ConfigMockBuilder::new()
+        .with_fee_estimation_strategy(canonical.strategy.clone())
+        .build_and_setup();
+
+    let rpc_client = RpcMockBuilder::new()
+        .with_fee_estimate(canonical.fee_for_message)
+        .with_custom_mock(RpcRequest::GetAccountInfo, lookup_table_account())
+        .with_custom_mock(
+            RpcRequest::SimulateTransaction,
+            json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "err": null,
+                    "logs": [],
+                    "accounts": null,
+                    "unitsConsumed": canonical.units_consumed,
+                    "innerInstructions": canonical.inner_instructions
+                }
+            }),
+        )
+        .build();
+
+    let transaction = TransactionUtil::decode_b64_transaction(canonical.fixture.trim())
+        .unwrap_or_else(|e| panic!("{}: invalid fixture: {e}", canonical.name));
+    let mut resolved =
+        VersionedTransactionResolved::from_transaction(&transaction, &rpc_client, false)
+            .await
+            .unwrap_or_else(|e| panic!("{}: failed to resolve: {e}", canonical.name));
+
+    FeeConfigUtil::estimate_kora_fee(
+        &rpc_client,
+        &mut resolved,
+        &FEE_PAYER,
+        true,
+        PriceSource::Mock,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("{}: failed to estimate: {e}", canonical.name))
+    .total_fee_lamports
+}
+
+#[tokio::test]
+async fn test_fee_estimation_regression() {
+    let tolerance = tolerance_percent();
+
+    for canonical in canonicals() {
+        let fee = estimate(&canonical).await;
+        let deviation =
+            fee.abs_diff(canonical.expected_fee) as f64 * 100.0 / canonical.expected_fee as f64;
+
+        assert!(
+            deviation <= tolerance,
+            "{}: estimated {fee} lamports, expected {} (off by {deviation:.2}%, tolerance {tolerance}%)",
+            canonical.name,
+            canonical.expected_fee,
+        );
+    }
+}
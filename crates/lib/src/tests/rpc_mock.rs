@@ -1,8 +1,18 @@
+use async_trait::async_trait;
 use base64::{self, engine::general_purpose::STANDARD, Engine};
+use parking_lot::Mutex;
 use serde_json::{json, Value};
-use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest};
-use solana_sdk::{account::Account, pubkey::Pubkey};
-use std::{collections::HashMap, sync::Arc};
+use solana_client::{
+    client_error::{ClientError, Result as ClientResult},
+    mock_sender::MockSender,
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::RpcClientConfig,
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{account::Account, hash::Hash};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::tests::account_mock::MintAccountMockBuilder;
 
@@ -11,6 +21,8 @@ pub const DEFAULT_LOCAL_RPC_URL: &str = "http://localhost:8899";
 /// Builder for creating mock RPC clients with different responses
 pub struct RpcMockBuilder {
     mocks: HashMap<RpcRequest, Value>,
+    blockhash_error: Option<ClientError>,
+    blockhash_delay: Option<Duration>,
 }
 
 impl Default for RpcMockBuilder {
@@ -21,7 +33,7 @@ impl Default for RpcMockBuilder {
 
 impl RpcMockBuilder {
     pub fn new() -> Self {
-        Self { mocks: HashMap::new() }
+        Self { mocks: HashMap::new(), blockhash_error: None, blockhash_delay: None }
     }
 
     pub fn with_account_info(mut self, account: &Account) -> Self {
@@ -42,6 +54,31 @@ impl RpcMockBuilder {
         self
     }
 
+    /// Mock `getMultipleAccounts`, `None` entries are returned as missing accounts
+    pub fn with_multiple_accounts(mut self, accounts: &[Option<Account>]) -> Self {
+        let values: Vec<Value> = accounts
+            .iter()
+            .map(|account| match account {
+                Some(account) => json!({
+                    "data": [STANDARD.encode(&account.data), "base64"],
+                    "executable": account.executable,
+                    "lamports": account.lamports,
+                    "owner": account.owner.to_string(),
+                    "rentEpoch": account.rent_epoch
+                }),
+                None => Value::Null,
+            })
+            .collect();
+        self.mocks.insert(
+            RpcRequest::GetMultipleAccounts,
+            json!({
+                "context": { "slot": 1 },
+                "value": values
+            }),
+        );
+        self
+    }
+
     pub fn with_account_not_found(mut self) -> Self {
         self.mocks.insert(
             RpcRequest::GetAccountInfo,
@@ -64,6 +101,23 @@ impl RpcMockBuilder {
         self
     }
 
+    pub fn with_simulation(mut self, units_consumed: u64) -> Self {
+        self.mocks.insert(
+            RpcRequest::SimulateTransaction,
+            json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "err": null,
+                    "logs": [],
+                    "accounts": null,
+                    "unitsConsumed": units_consumed,
+                    "innerInstructions": []
+                }
+            }),
+        );
+        self
+    }
+
     pub fn with_mint_account(self, decimals: u8) -> Self {
         let mint_account = MintAccountMockBuilder::new()
             .with_decimals(decimals)
@@ -83,10 +137,36 @@ impl RpcMockBuilder {
         self
     }
 
-    pub fn with_blockhash(mut self) -> Self {
+    pub fn with_blockhash(mut self, hash: Hash, last_valid_block_height: u64) -> Self {
         self.mocks.insert(
             RpcRequest::GetLatestBlockhash,
-            json!({ "context": { "slot": 1 }, "value": { "blockhash": Pubkey::new_unique().to_string(), "lastValidBlockHeight": 1000 } }),
+            json!({ "context": { "slot": 1 }, "value": { "blockhash": hash.to_string(), "lastValidBlockHeight": last_valid_block_height } }),
+        );
+        self
+    }
+
+    /// Fail the first `getLatestBlockhash` call with `error`, later calls are answered as usual
+    pub fn with_blockhash_error(mut self, error: ClientError) -> Self {
+        self.blockhash_error = Some(error);
+        self
+    }
+
+    /// Answer `getLatestBlockhash` after `ms` milliseconds
+    pub fn with_blockhash_delay(mut self, ms: u64) -> Self {
+        self.blockhash_delay = Some(Duration::from_millis(ms));
+        self
+    }
+
+    pub fn with_health(mut self) -> Self {
+        self.mocks.insert(RpcRequest::GetHealth, json!("ok"));
+        self
+    }
+
+    /// Simulate a node that fails its health check
+    pub fn with_unhealthy_rpc(mut self) -> Self {
+        self.mocks.insert(
+            RpcRequest::GetHealth,
+            json!({ "code": -32005, "message": "Node is unhealthy" }),
         );
         self
     }
@@ -130,7 +210,53 @@ impl RpcMockBuilder {
     }
 
     pub fn build(self) -> Arc<RpcClient> {
-        Arc::new(RpcClient::new_mock_with_mocks(DEFAULT_LOCAL_RPC_URL.to_string(), self.mocks))
+        if self.blockhash_error.is_none() && self.blockhash_delay.is_none() {
+            return Arc::new(RpcClient::new_mock_with_mocks(
+                DEFAULT_LOCAL_RPC_URL.to_string(),
+                self.mocks,
+            ));
+        }
+
+        let sender = BlockhashFaultSender {
+            inner: MockSender::new_with_mocks(DEFAULT_LOCAL_RPC_URL.to_string(), self.mocks),
+            error: Mutex::new(self.blockhash_error),
+            delay: self.blockhash_delay,
+        };
+        Arc::new(RpcClient::new_sender(
+            sender,
+            RpcClientConfig::with_commitment(CommitmentConfig::default()),
+        ))
+    }
+}
+
+/// Wraps the mock sender to fail or slow down `getLatestBlockhash`
+struct BlockhashFaultSender {
+    inner: MockSender,
+    error: Mutex<Option<ClientError>>,
+    delay: Option<Duration>,
+}
+
+#[async_trait]
+impl RpcSender for BlockhashFaultSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        if request == RpcRequest::GetLatestBlockhash {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            if let Some(error) = self.error.lock().take() {
+                return Err(error);
+            }
+        }
+
+        self.inner.send(request, params).await
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
     }
 }
 
@@ -0,0 +1,95 @@
+use std::{ops::Deref, sync::Arc};
+
+use parking_lot::Mutex;
+use solana_keychain::{Signer, SolanaSigner};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    transaction::VersionedTransaction,
+};
+
+use crate::signer::{pool::SignerWithMetadata, KoraSigner, SignerPool};
+
+/// Single in-memory signer pool that records every message it signs
+///
+/// Derefs to [`SignerPool`], so it can be used wherever the pool is read.
+///
+/// ```ignore
+/// let pool = TestSignerPool::new_with_keypair(Keypair::new());
+/// let transaction = TransactionMockBuilder::new()
+///     .with_fee_payer(pool.pubkey())
+///     .build_signed(&pool)
+///     .await;
+/// pool.assert_signed_count(1);
+/// ```
+pub struct TestSignerPool {
+    pool: SignerPool,
+    signer: Arc<KoraSigner>,
+    signed_messages: Mutex<Vec<Vec<u8>>>,
+}
+
+impl Default for TestSignerPool {
+    fn default() -> Self {
+        Self::new_with_keypair(Keypair::new())
+    }
+}
+
+impl TestSignerPool {
+    pub fn new_with_keypair(keypair: Keypair) -> Self {
+        let signer =
+            Arc::new(KoraSigner::from(Signer::from_memory(&keypair.to_base58_string()).unwrap()));
+        let pool = SignerPool::new(vec![SignerWithMetadata::new(
+            "test_signer".to_string(),
+            signer.clone(),
+            1,
+        )]);
+
+        Self { pool, signer, signed_messages: Mutex::new(vec![]) }
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    pub fn signer(&self) -> Arc<KoraSigner> {
+        self.signer.clone()
+    }
+
+    pub async fn sign_message(&self, message: &[u8]) -> Signature {
+        self.signed_messages.lock().push(message.to_vec());
+        self.signer.sign_message(message).await.unwrap()
+    }
+
+    /// Sign the transaction message and place the signature in the pool signer's slot
+    pub async fn sign_transaction(&self, transaction: &mut VersionedTransaction) -> Signature {
+        let pubkey = self.pubkey();
+        let index = transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .position(|key| *key == pubkey)
+            .filter(|index| *index < transaction.signatures.len())
+            .unwrap_or_else(|| panic!("{pubkey} is not a signer of the transaction"));
+
+        let signature = self.sign_message(&transaction.message.serialize()).await;
+        transaction.signatures[index] = signature;
+        signature
+    }
+
+    pub fn get_signed_messages(&self) -> Vec<Vec<u8>> {
+        self.signed_messages.lock().clone()
+    }
+
+    pub fn assert_signed_count(&self, n: usize) {
+        let signed = self.signed_messages.lock().len();
+        assert_eq!(signed, n, "expected {n} signed messages, got {signed}");
+    }
+}
+
+impl Deref for TestSignerPool {
+    type Target = SignerPool;
+
+    fn deref(&self) -> &SignerPool {
+        &self.pool
+    }
+}
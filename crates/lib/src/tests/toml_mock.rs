@@ -209,6 +209,10 @@ impl ConfigBuilder {
                 "[{}]",
                 tokens.iter().map(|t| format!("\"{t}\"")).collect::<Vec<_>>().join(", ")
             ),
+            SplTokenConfig::Blocklist(ref tokens) => format!(
+                "{{ Blocklist = [{}] }}",
+                tokens.iter().map(|t| format!("\"{t}\"")).collect::<Vec<_>>().join(", ")
+            ),
             SplTokenConfig::All => format!("\"{}\"", "All"),
         };
 
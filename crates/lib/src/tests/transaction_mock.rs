@@ -1,26 +1,258 @@
-use solana_message::{Message, VersionedMessage};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_message::{v0, AddressLookupTableAccount, Message, VersionedMessage};
 use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
     pubkey::Pubkey,
-    signature::Keypair,
-    signer::Signer,
     transaction::{Transaction, VersionedTransaction},
 };
 use solana_system_interface::instruction::transfer;
 
-use crate::transaction::TransactionUtil;
+use crate::{
+    tests::signer_mock::TestSignerPool,
+    transaction::{TransactionUtil, VersionedTransactionResolved},
+};
 
 pub fn create_mock_encoded_transaction() -> String {
-    let ix = transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1000000000);
-    let message = VersionedMessage::Legacy(Message::new(&[ix], Some(&Pubkey::new_unique())));
-    let transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
-
-    TransactionUtil::encode_versioned_transaction(&transaction).unwrap()
+    TransactionMockBuilder::new()
+        .with_sol_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1000000000)
+        .build_encoded()
 }
 
 pub fn create_mock_transaction() -> VersionedTransaction {
-    let keypair = Keypair::new();
-    let instruction = transfer(&keypair.pubkey(), &Pubkey::new_unique(), 1000);
-    let message = Message::new(&[instruction], Some(&keypair.pubkey()));
+    let sender = Pubkey::new_unique();
+    let instruction = transfer(&sender, &Pubkey::new_unique(), 1000);
+    let message = Message::new(&[instruction], Some(&sender));
     let transaction = Transaction::new_unsigned(message);
     VersionedTransaction::from(transaction)
 }
+
+/// Builder for unsigned test transactions, legacy unless `as_v0` is called
+///
+/// ```ignore
+/// let transaction = TransactionMockBuilder::new()
+///     .with_fee_payer(fee_payer)
+///     .with_compute_budget(200_000, 1_000)
+///     .with_spl_transfer(&source, &destination, &owner, 1_000)
+///     .build_resolved();
+/// ```
+pub struct TransactionMockBuilder {
+    fee_payer: Pubkey,
+    instructions: Vec<Instruction>,
+    /// `Some` once `as_v0` is called, holding the optional lookup table address
+    v0_lookup_table: Option<Option<Pubkey>>,
+}
+
+impl Default for TransactionMockBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionMockBuilder {
+    pub fn new() -> Self {
+        Self { fee_payer: Pubkey::new_unique(), instructions: vec![], v0_lookup_table: None }
+    }
+
+    pub fn with_fee_payer(mut self, fee_payer: Pubkey) -> Self {
+        self.fee_payer = fee_payer;
+        self
+    }
+
+    pub fn with_instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn with_sol_transfer(self, sender: &Pubkey, receiver: &Pubkey, lamports: u64) -> Self {
+        self.with_instruction(transfer(sender, receiver, lamports))
+    }
+
+    /// SPL Token `Transfer` from `source` signed by `authority`
+    pub fn with_spl_transfer(
+        self,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Self {
+        let instruction = spl_token_interface::instruction::transfer(
+            &spl_token_interface::id(),
+            source,
+            destination,
+            authority,
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.with_instruction(instruction)
+    }
+
+    /// Token-2022 `TransferChecked` from `source` signed by `authority`
+    pub fn with_token2022_transfer(
+        self,
+        source: &Pubkey,
+        mint: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> Self {
+        let instruction = spl_token_2022_interface::instruction::transfer_checked(
+            &spl_token_2022_interface::id(),
+            source,
+            mint,
+            destination,
+            authority,
+            &[],
+            amount,
+            decimals,
+        )
+        .unwrap();
+        self.with_instruction(instruction)
+    }
+
+    /// `SetComputeUnitLimit` and `SetComputeUnitPrice` (micro-lamports per unit)
+    pub fn with_compute_budget(self, limit: u32, price: u64) -> Self {
+        self.with_instruction(ComputeBudgetInstruction::set_compute_unit_limit(limit))
+            .with_instruction(ComputeBudgetInstruction::set_compute_unit_price(price))
+    }
+
+    pub fn as_legacy(mut self) -> Self {
+        self.v0_lookup_table = None;
+        self
+    }
+
+    /// Compile a V0 message, loading every non-signer account from a lookup table at
+    /// `lut_address` when one is given (see `lookup_table`)
+    pub fn as_v0(mut self, lut_address: Option<Pubkey>) -> Self {
+        self.v0_lookup_table = Some(lut_address);
+        self
+    }
+
+    /// Lookup table used by `as_v0`, so tests can mock the on-chain account
+    pub fn lookup_table(&self) -> Option<AddressLookupTableAccount> {
+        let key = self.v0_lookup_table.flatten()?;
+
+        let mut addresses: Vec<Pubkey> = vec![];
+        for meta in self.instructions.iter().flat_map(|ix| &ix.accounts) {
+            if !meta.is_signer && meta.pubkey != self.fee_payer && !addresses.contains(&meta.pubkey)
+            {
+                addresses.push(meta.pubkey);
+            }
+        }
+
+        Some(AddressLookupTableAccount { key, addresses })
+    }
+
+    pub fn build_message(&self) -> VersionedMessage {
+        match self.v0_lookup_table {
+            None => {
+                VersionedMessage::Legacy(Message::new(&self.instructions, Some(&self.fee_payer)))
+            }
+            Some(_) => {
+                let lookup_tables: Vec<_> = self.lookup_table().into_iter().collect();
+                VersionedMessage::V0(
+                    v0::Message::try_compile(
+                        &self.fee_payer,
+                        &self.instructions,
+                        &lookup_tables,
+                        Hash::default(),
+                    )
+                    .unwrap(),
+                )
+            }
+        }
+    }
+
+    pub fn build(&self) -> VersionedTransaction {
+        TransactionUtil::new_unsigned_versioned_transaction(self.build_message())
+    }
+
+    /// Resolved without RPC, so accounts loaded from a lookup table are not in `all_account_keys`
+    pub fn build_resolved(&self) -> VersionedTransactionResolved {
+        TransactionUtil::new_unsigned_versioned_transaction_resolved(self.build_message()).unwrap()
+    }
+
+    pub fn build_encoded(&self) -> String {
+        TransactionUtil::encode_versioned_transaction(&self.build()).unwrap()
+    }
+
+    /// Build and sign with the pool's signer, which must be the fee payer or another signer
+    pub async fn build_signed(&self, signer_pool: &TestSignerPool) -> VersionedTransaction {
+        let mut transaction = self.build();
+        signer_pool.sign_transaction(&mut transaction).await;
+        transaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_mock_builder_legacy() {
+        let fee_payer = Pubkey::new_unique();
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_compute_budget(200_000, 1_000)
+            .with_sol_transfer(&fee_payer, &Pubkey::new_unique(), 1_000)
+            .with_spl_transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), &fee_payer, 10)
+            .build();
+
+        assert!(matches!(transaction.message, VersionedMessage::Legacy(_)));
+        assert_eq!(transaction.message.instructions().len(), 4);
+        assert_eq!(transaction.message.static_account_keys()[0], fee_payer);
+        assert_eq!(transaction.signatures.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_mock_builder_signed() {
+        let signer_pool = TestSignerPool::default();
+        let transaction = TransactionMockBuilder::new()
+            .with_fee_payer(signer_pool.pubkey())
+            .with_sol_transfer(&signer_pool.pubkey(), &Pubkey::new_unique(), 1_000)
+            .build_signed(&signer_pool)
+            .await;
+
+        signer_pool.assert_signed_count(1);
+        let message = transaction.message.serialize();
+        assert_eq!(signer_pool.get_signed_messages(), vec![message.clone()]);
+        assert!(transaction.signatures[0].verify(signer_pool.pubkey().as_ref(), &message));
+    }
+
+    #[test]
+    fn test_transaction_mock_builder_v0_with_lookup_table() {
+        let fee_payer = Pubkey::new_unique();
+        let lut_address = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let builder = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_token2022_transfer(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &destination,
+                &fee_payer,
+                10,
+                6,
+            )
+            .as_v0(Some(lut_address));
+
+        let lookup_table = builder.lookup_table().unwrap();
+        assert_eq!(lookup_table.key, lut_address);
+        assert!(lookup_table.addresses.contains(&destination));
+
+        let VersionedMessage::V0(message) = builder.build_message() else {
+            panic!("expected a V0 message");
+        };
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert!(!message.account_keys.contains(&destination));
+
+        // Without a table every account is static
+        let VersionedMessage::V0(message) = builder.as_v0(None).build_message() else {
+            panic!("expected a V0 message");
+        };
+        assert!(message.address_table_lookups.is_empty());
+        assert!(message.account_keys.contains(&destination));
+    }
+}
@@ -8,6 +8,8 @@ pub trait TokenState: Any + Send + Sync {
     fn owner(&self) -> Pubkey;
     fn amount(&self) -> u64;
     fn decimals(&self) -> u8;
+    /// Frozen by the mint's freeze authority, tokens can't be moved out of the account
+    fn is_frozen(&self) -> bool;
 
     // Add method to support downcasting for Token2022 specific features
     fn as_any(&self) -> &dyn Any;
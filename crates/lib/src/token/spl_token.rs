@@ -38,6 +38,9 @@ impl TokenState for TokenAccount {
     fn decimals(&self) -> u8 {
         0
     }
+    fn is_frozen(&self) -> bool {
+        self.state == AccountState::Frozen as u8
+    }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -49,6 +49,9 @@ impl TokenState for Token2022Account {
     fn decimals(&self) -> u8 {
         0
     }
+    fn is_frozen(&self) -> bool {
+        self.state == AccountState::Frozen as u8
+    }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -212,6 +215,17 @@ impl Token2022Mint {
     pub fn has_permanent_delegate_extension(&self) -> bool {
         self.has_extension(ExtensionType::PermanentDelegate)
     }
+
+    /// Program every transfer of this mint CPIs into, `None` without a `TransferHook` extension
+    /// or when the hook program is unset
+    pub fn get_transfer_hook_program_id(&self) -> Option<Pubkey> {
+        match self.get_extension(ExtensionType::TransferHook) {
+            Some(ParsedExtension::Mint(MintExtension::TransferHook(hook))) => {
+                Option::<Pubkey>::from(hook.program_id)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Token2022Extensions for Token2022Mint {
@@ -1,4 +1,5 @@
 use crate::{
+    cache::token_balance_cache::TOKEN_BALANCE_CACHE,
     error::KoraError,
     oracle::{get_price_oracle, PriceSource, RetryingPriceOracle, TokenPrice},
     token::{
@@ -10,6 +11,7 @@ use crate::{
     transaction::{
         ParsedSPLInstructionData, ParsedSPLInstructionType, VersionedTransactionResolved,
     },
+    validator::transaction_validator::ValidationContext,
     CacheUtil,
 };
 use rust_decimal::{
@@ -97,7 +99,7 @@ impl TokenUtil {
         let decimals = Self::get_mint_decimals(rpc_client, mint).await?;
 
         let oracle =
-            RetryingPriceOracle::new(3, Duration::from_secs(1), get_price_oracle(price_source));
+            RetryingPriceOracle::new(3, Duration::from_secs(1), get_price_oracle(price_source)?);
 
         // Get token price in SOL directly
         let token_price = oracle
@@ -194,6 +196,7 @@ impl TokenUtil {
         fee_payer: &Pubkey,
         price_source: &PriceSource,
         rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
     ) -> Result<u64, KoraError> {
         // Collect all unique mints that need price lookups
         let mut mint_to_transfers: HashMap<
@@ -220,7 +223,10 @@ impl TokenUtil {
                     // We need to check the destination token account owner
                     if let Some(mint_pubkey) = mint {
                         // Get destination account to check owner
-                        match CacheUtil::get_account(rpc_client, destination_address, false).await {
+                        match validation_ctx
+                            .get_account(rpc_client, destination_address, false)
+                            .await
+                        {
                             Ok(dest_account) => {
                                 let token_program =
                                     TokenType::get_token_program_from_owner(&dest_account.owner)?;
@@ -289,7 +295,7 @@ impl TokenUtil {
         let oracle = RetryingPriceOracle::new(
             3,
             Duration::from_secs(1),
-            get_price_oracle(price_source.clone()),
+            get_price_oracle(price_source.clone())?,
         );
 
         let prices = oracle.get_token_prices(&mint_addresses).await?;
@@ -364,13 +370,14 @@ impl TokenUtil {
         source_address: &Pubkey,
         destination_address: &Pubkey,
         mint: &Pubkey,
+        validation_ctx: &ValidationContext,
     ) -> Result<(), KoraError> {
         let config = &get_config()?.validation.token_2022;
 
         let token_program = Token2022Program::new();
 
         // Get mint account data and validate mint extensions (force refresh in case extensions are added)
-        let mint_account = CacheUtil::get_account(rpc_client, mint, true).await?;
+        let mint_account = validation_ctx.get_account(rpc_client, mint, true).await?;
         let mint_data = mint_account.data;
 
         // Unpack the mint state with extensions
@@ -391,7 +398,7 @@ impl TokenUtil {
         }
 
         // Check source account extensions (force refresh in case extensions are added)
-        let source_account = CacheUtil::get_account(rpc_client, source_address, true).await?;
+        let source_account = validation_ctx.get_account(rpc_client, source_address, true).await?;
         let source_data = source_account.data;
 
         let source_state = token_program.unpack_token_account(&source_data)?;
@@ -411,7 +418,7 @@ impl TokenUtil {
 
         // Check destination account extensions (force refresh in case extensions are added)
         let destination_account =
-            CacheUtil::get_account(rpc_client, destination_address, true).await?;
+            validation_ctx.get_account(rpc_client, destination_address, true).await?;
         let destination_data = destination_account.data;
 
         let destination_state = token_program.unpack_token_account(&destination_data)?;
@@ -432,12 +439,34 @@ impl TokenUtil {
         Ok(())
     }
 
+    /// Check that the source token account holds at least `amount`, using cached balances within `ttl`
+    pub async fn validate_user_token_balance(
+        rpc_client: &RpcClient,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        source_address: &Pubkey,
+        amount: u64,
+        ttl: Duration,
+    ) -> Result<(), KoraError> {
+        let balance =
+            TOKEN_BALANCE_CACHE.get_balance(rpc_client, owner, mint, source_address, ttl).await?;
+
+        if balance < amount {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Insufficient token balance in {source_address}: {balance} < {amount}"
+            )));
+        }
+
+        Ok(())
+    }
+
     pub async fn verify_token_payment(
         transaction_resolved: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
         required_lamports: u64,
         // Wallet address of the owner of the destination token account
         expected_destination_owner: &Pubkey,
+        validation_ctx: &ValidationContext,
     ) -> Result<bool, KoraError> {
         let config = get_config()?;
         let mut total_lamport_value = 0u64;
@@ -453,7 +482,7 @@ impl TokenUtil {
                 mint,
                 amount,
                 is_2022,
-                ..
+                owner,
             } = instruction
             {
                 let token_program: Box<dyn TokenInterface> = if *is_2022 {
@@ -463,10 +492,10 @@ impl TokenUtil {
                 };
 
                 // Validate the destination account is that of the payment address (or signer if none provided)
-                let destination_account =
-                    CacheUtil::get_account(rpc_client, destination_address, false)
-                        .await
-                        .map_err(|e| KoraError::RpcError(e.to_string()))?;
+                let destination_account = validation_ctx
+                    .get_account(rpc_client, destination_address, false)
+                    .await
+                    .map_err(|e| KoraError::RpcError(e.to_string()))?;
 
                 let token_state =
                     token_program.unpack_token_account(&destination_account.data).map_err(|e| {
@@ -480,6 +509,7 @@ impl TokenUtil {
                         source_address,
                         destination_address,
                         &mint.unwrap_or(token_state.mint()),
+                        validation_ctx,
                     )
                     .await?;
                 }
@@ -497,6 +527,18 @@ impl TokenUtil {
                     continue;
                 }
 
+                if config.validation.validate_user_token_balance {
+                    TokenUtil::validate_user_token_balance(
+                        rpc_client,
+                        owner,
+                        &token_state.mint(),
+                        source_address,
+                        *amount,
+                        Duration::from_secs(config.kora.cache.token_balance_ttl),
+                    )
+                    .await?;
+                }
+
                 let lamport_value = TokenUtil::calculate_token_value_in_lamports(
                     *amount,
                     &token_state.mint(),
@@ -933,6 +975,52 @@ mod tests_token {
         }
     }
 
+    #[tokio::test]
+    async fn test_validate_user_token_balance_uses_cache() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"method": "getTokenAccountBalance"}"#.to_string(),
+            ))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"amount":"1000","decimals":6,"uiAmount":0.001,"uiAmountString":"0.001"}}}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let rpc_client = RpcClient::new(server.url());
+        let (owner, mint, source) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        for _ in 0..2 {
+            let result = TokenUtil::validate_user_token_balance(
+                &rpc_client,
+                &owner,
+                &mint,
+                &source,
+                1000,
+                Duration::from_secs(5),
+            )
+            .await;
+            assert!(result.is_ok(), "Balance should cover the payment: {result:?}");
+        }
+
+        let result = TokenUtil::validate_user_token_balance(
+            &rpc_client,
+            &owner,
+            &mint,
+            &source,
+            1001,
+            Duration::from_secs(5),
+        )
+        .await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_validate_token2022_extensions_for_payment_rpc_error() {
         let _lock = ConfigMockBuilder::new().build_and_setup();
@@ -948,6 +1036,7 @@ mod tests_token {
             &source_address,
             &destination_address,
             &mint_address,
+            &ValidationContext::new(),
         )
         .await;
 
@@ -973,6 +1062,7 @@ mod tests_token {
             &source_address,
             &destination_address,
             &mint_address,
+            &ValidationContext::new(),
         )
         .await;
 
@@ -986,19 +1076,16 @@ mod tests_token {
     fn test_config_token2022_extension_blocking() {
         use spl_token_2022_interface::extension::ExtensionType;
 
-        let mut config_builder = ConfigMockBuilder::new();
-        config_builder = config_builder
-            .with_blocked_token2022_mint_extensions(vec![
+        let _lock = ConfigMockBuilder::new()
+            .with_token_2022_extension_blocklist(vec![
                 "transfer_fee_config".to_string(),
                 "pausable".to_string(),
                 "non_transferable".to_string(),
-            ])
-            .with_blocked_token2022_account_extensions(vec![
                 "non_transferable_account".to_string(),
                 "cpi_guard".to_string(),
                 "memo_transfer".to_string(),
-            ]);
-        let _lock = config_builder.build_and_setup();
+            ])
+            .build_and_setup();
 
         let config = get_config().unwrap();
 
@@ -1055,4 +1142,57 @@ mod tests_token {
             .token_2022
             .is_account_extension_blocked(ExtensionType::CpiGuard));
     }
+
+    #[test]
+    fn test_config_token2022_extension_allowlist_only() {
+        use spl_token_2022_interface::extension::ExtensionType;
+
+        let _lock = ConfigMockBuilder::new()
+            .with_token_2022_extension_allowlist(vec![
+                "transfer_fee_config".to_string(),
+                "immutable_owner".to_string(),
+            ])
+            .build_and_setup();
+        let token_2022 = get_config().unwrap().validation.token_2022.clone();
+
+        assert!(!token_2022.is_mint_extension_blocked(ExtensionType::TransferFeeConfig));
+        assert!(!token_2022.is_account_extension_blocked(ExtensionType::ImmutableOwner));
+        // Anything outside the allowlist is blocked
+        assert!(token_2022.is_mint_extension_blocked(ExtensionType::PermanentDelegate));
+        assert!(token_2022.is_account_extension_blocked(ExtensionType::CpiGuard));
+    }
+
+    #[test]
+    fn test_config_token2022_extension_blocklist_only() {
+        use spl_token_2022_interface::extension::ExtensionType;
+
+        let _lock = ConfigMockBuilder::new()
+            .with_token_2022_extension_blocklist(vec![
+                "permanent_delegate".to_string(),
+                "cpi_guard".to_string(),
+            ])
+            .build_and_setup();
+        let token_2022 = get_config().unwrap().validation.token_2022.clone();
+
+        assert_eq!(token_2022.blocked_mint_extensions, vec!["permanent_delegate".to_string()]);
+        assert_eq!(token_2022.blocked_account_extensions, vec!["cpi_guard".to_string()]);
+        assert!(token_2022.is_mint_extension_blocked(ExtensionType::PermanentDelegate));
+        assert!(token_2022.is_account_extension_blocked(ExtensionType::CpiGuard));
+        assert!(!token_2022.is_mint_extension_blocked(ExtensionType::TransferFeeConfig));
+        assert!(!token_2022.is_account_extension_blocked(ExtensionType::MemoTransfer));
+    }
+
+    #[test]
+    fn test_config_token2022_extension_allowlist_and_blocklist_errors() {
+        let mut token_2022 = ConfigMockBuilder::new()
+            .with_token_2022_extension_allowlist(vec!["transfer_fee_config".to_string()])
+            .with_token_2022_extension_blocklist(vec!["permanent_delegate".to_string()])
+            .build()
+            .validation
+            .token_2022;
+
+        let result = token_2022.initialize();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("allowed_extensions cannot be combined"));
+    }
 }
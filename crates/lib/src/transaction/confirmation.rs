@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_sdk::signature::Signature;
+use utoipa::ToSchema;
+
+use crate::error::KoraError;
+
+/// Commitment level a client can wait for after a transaction is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitForCommitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl From<WaitForCommitment> for CommitmentConfig {
+    fn from(commitment: WaitForCommitment) -> Self {
+        let commitment = match commitment {
+            WaitForCommitment::Processed => CommitmentLevel::Processed,
+            WaitForCommitment::Confirmed => CommitmentLevel::Confirmed,
+            WaitForCommitment::Finalized => CommitmentLevel::Finalized,
+        };
+        CommitmentConfig { commitment }
+    }
+}
+
+/// Outcome of waiting for a signature, `confirmed` is false on timeout or when the transaction failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureConfirmation {
+    pub confirmed: bool,
+    /// Whether the transaction landed on-chain with an error
+    pub failed: bool,
+    /// Slot the transaction landed in, if it was seen on-chain
+    pub slot: Option<u64>,
+    pub error: Option<String>,
+}
+
+pub struct ConfirmationUtil {}
+
+impl ConfirmationUtil {
+    /// Poll `getSignatureStatuses` until `signature` reaches `commitment`, fails, or `timeout` elapses
+    pub async fn wait_for_signature(
+        rpc_client: &RpcClient,
+        signature: &Signature,
+        commitment: WaitForCommitment,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<SignatureConfirmation, KoraError> {
+        let mut last_seen_slot = None;
+        let poll = Self::poll_signature(
+            rpc_client,
+            signature,
+            CommitmentConfig::from(commitment),
+            poll_interval,
+            &mut last_seen_slot,
+        );
+
+        let result = tokio::select! {
+            result = poll => Some(result),
+            _ = tokio::time::sleep(timeout) => None,
+        };
+
+        match result {
+            Some(result) => result,
+            None => Ok(SignatureConfirmation {
+                confirmed: false,
+                failed: false,
+                slot: last_seen_slot,
+                error: Some(format!(
+                    "Timed out after {}ms waiting for {commitment:?} commitment",
+                    timeout.as_millis()
+                )),
+            }),
+        }
+    }
+
+    async fn poll_signature(
+        rpc_client: &RpcClient,
+        signature: &Signature,
+        commitment_config: CommitmentConfig,
+        poll_interval: Duration,
+        last_seen_slot: &mut Option<u64>,
+    ) -> Result<SignatureConfirmation, KoraError> {
+        loop {
+            let statuses = rpc_client
+                .get_signature_statuses(&[*signature])
+                .await
+                .map_err(|e| KoraError::RpcError(format!("Failed to get signature status: {e}")))?;
+
+            if let Some(Some(status)) = statuses.value.into_iter().next() {
+                *last_seen_slot = Some(status.slot);
+
+                if let Some(err) = status.err {
+                    return Ok(SignatureConfirmation {
+                        confirmed: false,
+                        failed: true,
+                        slot: Some(status.slot),
+                        error: Some(format!("Transaction failed: {err:?}")),
+                    });
+                }
+
+                if status.satisfies_commitment(commitment_config) {
+                    return Ok(SignatureConfirmation {
+                        confirmed: true,
+                        failed: false,
+                        slot: Some(status.slot),
+                        error: None,
+                    });
+                }
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server, ServerGuard};
+
+    async fn create_status_server(confirmation_status: &str) -> (ServerGuard, mockito::Mock) {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJsonString(
+                r#"{"method": "getSignatureStatuses"}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "context": { "slot": 101 },
+                        "value": [{
+                            "slot": 100,
+                            "confirmations": 1,
+                            "err": null,
+                            "status": { "Ok": null },
+                            "confirmationStatus": confirmation_status,
+                        }]
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        (server, mock)
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_signature_confirmed() {
+        let (server, _mock) = create_status_server("confirmed").await;
+        let rpc_client = RpcClient::new(server.url());
+
+        let result = ConfirmationUtil::wait_for_signature(
+            &rpc_client,
+            &Signature::default(),
+            WaitForCommitment::Confirmed,
+            Duration::from_secs(5),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result,
+            SignatureConfirmation { confirmed: true, failed: false, slot: Some(100), error: None }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_signature_times_out_below_commitment() {
+        let (server, _mock) = create_status_server("confirmed").await;
+        let rpc_client = RpcClient::new(server.url());
+
+        let result = ConfirmationUtil::wait_for_signature(
+            &rpc_client,
+            &Signature::default(),
+            WaitForCommitment::Finalized,
+            Duration::from_millis(100),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+
+        assert!(!result.confirmed);
+        assert!(!result.failed);
+        assert_eq!(result.slot, Some(100));
+        assert!(result.error.unwrap().contains("Timed out"));
+    }
+}
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use sha2::{Digest, Sha256};
 use solana_message::compiled_instruction::CompiledInstruction;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -23,7 +24,7 @@ pub enum ParsedSystemInstructionType {
     SystemInitializeNonceAccount,
     SystemAdvanceNonceAccount,
     SystemAuthorizeNonceAccount,
-    // Note: SystemUpgradeNonceAccount not included - no authority parameter
+    SystemUpgradeNonceAccount,
 }
 
 // Instruction type that we support to parse from the transaction
@@ -45,7 +46,8 @@ pub enum ParsedSystemInstructionData {
     SystemAdvanceNonceAccount { nonce_account: Pubkey, nonce_authority: Pubkey },
     // Authorize nonce account
     SystemAuthorizeNonceAccount { nonce_account: Pubkey, nonce_authority: Pubkey },
-    // Note: SystemUpgradeNonceAccount not included - no authority parameter
+    // Upgrade nonce account, no authority parameter so it's parsed for logging only
+    SystemUpgradeNonceAccount { nonce_account: Pubkey },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -64,6 +66,30 @@ pub enum ParsedSPLInstructionType {
     SplTokenThawAccount,
 }
 
+/// Names of the parsed instruction types, as accepted by `fee_payer_policy.instruction_type_allowlist`
+pub const PARSED_INSTRUCTION_TYPE_NAMES: &[&str] = &[
+    "SystemTransfer",
+    "SystemCreateAccount",
+    "SystemWithdrawNonceAccount",
+    "SystemAssign",
+    "SystemAllocate",
+    "SystemInitializeNonceAccount",
+    "SystemAdvanceNonceAccount",
+    "SystemAuthorizeNonceAccount",
+    "SplTokenTransfer",
+    "SplTokenBurn",
+    "SplTokenCloseAccount",
+    "SplTokenApprove",
+    "SplTokenRevoke",
+    "SplTokenSetAuthority",
+    "SplTokenMintTo",
+    "SplTokenInitializeMint",
+    "SplTokenInitializeAccount",
+    "SplTokenInitializeMultisig",
+    "SplTokenFreezeAccount",
+    "SplTokenThawAccount",
+];
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ParsedSPLInstructionData {
     // Includes transfer and transfer with seed (both spl and spl 2022)
@@ -93,6 +119,9 @@ pub enum ParsedSPLInstructionData {
     // Revoke
     SplTokenRevoke {
         owner: Pubkey,
+        source_address: Pubkey,
+        // Signers of the owner when it is a multisig account, empty otherwise
+        multisig_signers: Vec<Pubkey>,
         is_2022: bool,
     },
     // SetAuthority
@@ -173,6 +202,25 @@ macro_rules! parse_system_instruction {
 
 pub struct IxUtils;
 
+// Borsh variant indexes of `ComputeBudgetInstruction`
+const SET_COMPUTE_UNIT_LIMIT_DISCRIMINATOR: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_DISCRIMINATOR: u8 = 3;
+
+/// Compute budget values set by the transaction's own instructions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudgetSummary {
+    /// Compute unit limit from `SetComputeUnitLimit`
+    pub limit: Option<u32>,
+    /// Micro-lamports per compute unit from `SetComputeUnitPrice`
+    pub price_micro_lamports: Option<u64>,
+}
+
+impl ComputeBudgetSummary {
+    pub fn is_set(&self) -> bool {
+        self.limit.is_some() || self.price_micro_lamports.is_some()
+    }
+}
+
 pub const PARSED_DATA_FIELD_TYPE: &str = "type";
 pub const PARSED_DATA_FIELD_INFO: &str = "info";
 
@@ -242,6 +290,7 @@ pub const PARSED_DATA_FIELD_FREEZE_AUTHORITY: &str = "freezeAuthority";
 pub const PARSED_DATA_FIELD_AUTHORITY_TYPE: &str = "authorityType";
 pub const PARSED_DATA_FIELD_MULTISIG_ACCOUNT: &str = "multisig";
 pub const PARSED_DATA_FIELD_SIGNERS: &str = "signers";
+pub const PARSED_DATA_FIELD_MULTISIG_OWNER: &str = "multisigOwner";
 
 impl IxUtils {
     /// Helper method to extract a field as a string from JSON with proper error handling
@@ -339,6 +388,33 @@ impl IxUtils {
         CompiledInstruction { program_id_index, accounts: vec![], data: vec![] }
     }
 
+    /// Check that the program id and account indices of every instruction point into
+    /// `account_keys`, so malformed transactions are rejected before any account is looked up
+    pub fn validate_account_indices(
+        instructions: &[CompiledInstruction],
+        account_keys: &[Pubkey],
+    ) -> Result<(), KoraError> {
+        for (ix_index, ix) in instructions.iter().enumerate() {
+            if ix.program_id_index as usize >= account_keys.len() {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Instruction {ix_index} has program id index {} out of range for {} account keys",
+                    ix.program_id_index,
+                    account_keys.len()
+                )));
+            }
+
+            if let Some(index) = ix.accounts.iter().find(|idx| **idx as usize >= account_keys.len())
+            {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Instruction {ix_index} has account index {index} out of range for {} account keys",
+                    account_keys.len()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn uncompile_instructions(
         instructions: &[CompiledInstruction],
         account_keys: &[Pubkey],
@@ -973,22 +1049,47 @@ impl IxUtils {
             }
             PARSED_DATA_FIELD_REVOKE => {
                 let source = Self::get_field_as_pubkey(info, PARSED_DATA_FIELD_SOURCE)?;
-                let owner = Self::get_field_as_pubkey(info, PARSED_DATA_FIELD_OWNER)?;
+
+                // Multisig owners are reported as 'multisigOwner' with a 'signers' array
+                let (owner, signers) = if info.get(PARSED_DATA_FIELD_MULTISIG_OWNER).is_some() {
+                    let owner = Self::get_field_as_pubkey(info, PARSED_DATA_FIELD_MULTISIG_OWNER)?;
+                    let signers = info
+                        .get(PARSED_DATA_FIELD_SIGNERS)
+                        .and_then(|value| value.as_array())
+                        .ok_or_else(|| {
+                            KoraError::SerializationError("Missing 'signers' field".to_string())
+                        })?
+                        .iter()
+                        .map(|signer| {
+                            signer.as_str().and_then(|s| s.parse::<Pubkey>().ok()).ok_or_else(
+                                || {
+                                    KoraError::SerializationError(
+                                        "Field 'signers' contains an invalid pubkey".to_string(),
+                                    )
+                                },
+                            )
+                        })
+                        .collect::<Result<Vec<Pubkey>, KoraError>>()?;
+                    (owner, signers)
+                } else {
+                    (Self::get_field_as_pubkey(info, PARSED_DATA_FIELD_OWNER)?, vec![])
+                };
 
                 let source_idx = Self::get_account_index(account_keys_hashmap, &source)?;
                 let owner_idx = Self::get_account_index(account_keys_hashmap, &owner)?;
 
+                let mut accounts = vec![source_idx, owner_idx];
+                for signer in &signers {
+                    accounts.push(Self::get_account_index(account_keys_hashmap, signer)?);
+                }
+
                 let data = if parsed.program_id == spl_token_interface::ID.to_string() {
                     spl_token_interface::instruction::TokenInstruction::Revoke.pack()
                 } else {
                     spl_token_2022_interface::instruction::TokenInstruction::Revoke.pack()
                 };
 
-                Ok(CompiledInstruction {
-                    program_id_index,
-                    accounts: vec![source_idx, owner_idx],
-                    data,
-                })
+                Ok(CompiledInstruction { program_id_index, accounts, data })
             }
             PARSED_DATA_FIELD_SET_AUTHORITY => {
                 let account = Self::get_field_as_pubkey(info, PARSED_DATA_FIELD_ACCOUNT)?;
@@ -1191,6 +1292,104 @@ impl IxUtils {
         }
     }
 
+    /// Discriminator prefixing the data of the Anchor instruction `instruction_name`, the first
+    /// 8 bytes of `sha256("global:<instruction_name>")`
+    pub fn anchor_discriminator(instruction_name: &str) -> [u8; 8] {
+        let hash = Sha256::digest(format!("global:{instruction_name}").as_bytes());
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash[..8]);
+        discriminator
+    }
+
+    /// Collect the unique program IDs invoked by the transaction's instructions
+    pub fn extract_program_ids(transaction: &VersionedTransactionResolved) -> HashSet<Pubkey> {
+        transaction.all_instructions.iter().map(|instruction| instruction.program_id).collect()
+    }
+
+    /// Collect the accounts the transaction may write to, static keys and lookup table addresses
+    ///
+    /// Writability comes from the message header and the lookup tables' writable indexes, not
+    /// from the uncompiled instructions, which mark every account as writable.
+    pub fn extract_all_writable_accounts(
+        transaction: &VersionedTransactionResolved,
+    ) -> Vec<Pubkey> {
+        let message = &transaction.message;
+        let header = message.header();
+        let static_keys = message.static_account_keys();
+
+        let num_signed = header.num_required_signatures as usize;
+        let num_writable_signed =
+            num_signed.saturating_sub(header.num_readonly_signed_accounts as usize);
+        let num_writable_unsigned = static_keys
+            .len()
+            .saturating_sub(num_signed)
+            .saturating_sub(header.num_readonly_unsigned_accounts as usize);
+
+        let mut writable_accounts: Vec<Pubkey> = static_keys
+            .iter()
+            .take(num_writable_signed)
+            .chain(static_keys.iter().skip(num_signed).take(num_writable_unsigned))
+            .copied()
+            .collect();
+
+        // Lookup table addresses are resolved table by table, writable ones first
+        if let Some(lookups) = message.address_table_lookups() {
+            let mut loaded = transaction.all_account_keys.iter().skip(static_keys.len());
+            for lookup in lookups {
+                writable_accounts.extend(loaded.by_ref().take(lookup.writable_indexes.len()));
+                loaded.by_ref().take(lookup.readonly_indexes.len()).for_each(drop);
+            }
+        }
+
+        writable_accounts
+    }
+
+    /// Read the `SetComputeUnitPrice` and `SetComputeUnitLimit` instructions already in the transaction
+    ///
+    /// Only top-level instructions count, the compute budget program can't be invoked through CPI.
+    /// The runtime rejects a transaction setting the same value twice, so duplicates are an error.
+    pub fn parse_compute_budget_instructions(
+        transaction: &VersionedTransactionResolved,
+    ) -> Result<ComputeBudgetSummary, KoraError> {
+        let mut summary = ComputeBudgetSummary::default();
+
+        for instruction in transaction.outer_instructions() {
+            if instruction.program_id != solana_compute_budget_interface::ID {
+                continue;
+            }
+
+            match instruction.data.split_first() {
+                Some((&SET_COMPUTE_UNIT_PRICE_DISCRIMINATOR, data)) => {
+                    let price = data.try_into().map(u64::from_le_bytes).map_err(|_| {
+                        KoraError::InvalidTransaction(
+                            "Invalid SetComputeUnitPrice instruction data".to_string(),
+                        )
+                    })?;
+                    if summary.price_micro_lamports.replace(price).is_some() {
+                        return Err(KoraError::InvalidTransaction(
+                            "Duplicate SetComputeUnitPrice instruction".to_string(),
+                        ));
+                    }
+                }
+                Some((&SET_COMPUTE_UNIT_LIMIT_DISCRIMINATOR, data)) => {
+                    let limit = data.try_into().map(u32::from_le_bytes).map_err(|_| {
+                        KoraError::InvalidTransaction(
+                            "Invalid SetComputeUnitLimit instruction data".to_string(),
+                        )
+                    })?;
+                    if summary.limit.replace(limit).is_some() {
+                        return Err(KoraError::InvalidTransaction(
+                            "Duplicate SetComputeUnitLimit instruction".to_string(),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(summary)
+    }
+
     pub fn parse_system_instructions(
         transaction: &VersionedTransactionResolved,
     ) -> Result<HashMap<ParsedSystemInstructionType, Vec<ParsedSystemInstructionData>>, KoraError>
@@ -1303,10 +1502,24 @@ impl IxUtils {
                             nonce_authority: instruction_indexes::system_authorize_nonce_account::NONCE_AUTHORITY_INDEX
                         });
                     }
-                    // UpgradeNonceAccount: Not parsed - no authority parameter, cannot validate fee payer involvement
-                    // Anyone can upgrade any nonce account without signing
+                    // UpgradeNonceAccount has no authority parameter, anyone can upgrade any nonce
+                    // account without signing, so the fee payer policy has nothing to check
                     Ok(SystemInstruction::UpgradeNonceAccount) => {
-                        // Skip parsing
+                        parse_system_instruction!(
+                            parsed_instructions,
+                            instruction,
+                            system_upgrade_nonce_account,
+                            SystemUpgradeNonceAccount,
+                            SystemUpgradeNonceAccount {
+                                nonce_account: instruction_indexes::system_upgrade_nonce_account::NONCE_ACCOUNT_INDEX
+                            }
+                        );
+                        log::info!(
+                            "Transaction upgrades nonce account {}",
+                            instruction.accounts
+                                [instruction_indexes::system_upgrade_nonce_account::NONCE_ACCOUNT_INDEX]
+                                .pubkey
+                        );
                     }
                     _ => {}
                 }
@@ -1427,6 +1640,13 @@ impl IxUtils {
                                 instruction_indexes::spl_token_revoke::REQUIRED_NUMBER_OF_ACCOUNTS
                             );
 
+                            // Extract multisig signers (everything after source and owner)
+                            let multisig_signers: Vec<Pubkey> = instruction.accounts
+                                [instruction_indexes::spl_token_revoke::MULTISIG_SIGNERS_START_INDEX..]
+                                .iter()
+                                .map(|account| account.pubkey)
+                                .collect();
+
                             parsed_instructions
                                 .entry(ParsedSPLInstructionType::SplTokenRevoke)
                                 .or_default()
@@ -1434,6 +1654,10 @@ impl IxUtils {
                                     owner: instruction.accounts
                                         [instruction_indexes::spl_token_revoke::OWNER_INDEX]
                                         .pubkey,
+                                    source_address: instruction.accounts
+                                        [instruction_indexes::spl_token_revoke::SOURCE_ADDRESS_INDEX]
+                                        .pubkey,
+                                    multisig_signers,
                                     is_2022: false,
                                 });
                         }
@@ -1695,6 +1919,13 @@ impl IxUtils {
                                 instruction_indexes::spl_token_revoke::REQUIRED_NUMBER_OF_ACCOUNTS
                             );
 
+                            // Extract multisig signers (everything after source and owner)
+                            let multisig_signers: Vec<Pubkey> = instruction.accounts
+                                [instruction_indexes::spl_token_revoke::MULTISIG_SIGNERS_START_INDEX..]
+                                .iter()
+                                .map(|account| account.pubkey)
+                                .collect();
+
                             parsed_instructions
                                 .entry(ParsedSPLInstructionType::SplTokenRevoke)
                                 .or_default()
@@ -1702,6 +1933,10 @@ impl IxUtils {
                                     owner: instruction.accounts
                                         [instruction_indexes::spl_token_revoke::OWNER_INDEX]
                                         .pubkey,
+                                    source_address: instruction.accounts
+                                        [instruction_indexes::spl_token_revoke::SOURCE_ADDRESS_INDEX]
+                                        .pubkey,
+                                    multisig_signers,
                                     is_2022: true,
                                 });
                         }
@@ -1875,6 +2110,9 @@ impl IxUtils {
 mod tests {
 
     use super::*;
+    use crate::transaction::TransactionUtil;
+    use rand::Rng;
+    use solana_message::VersionedMessage;
     use solana_sdk::message::{AccountKeys, Message};
     use solana_transaction_status::parse_instruction;
 
@@ -2528,6 +2766,135 @@ mod tests {
         Ok(parsed)
     }
 
+    #[test]
+    fn test_anchor_discriminator() {
+        // Discriminators as found in the IDLs generated by Anchor
+        assert_eq!(
+            IxUtils::anchor_discriminator("initialize"),
+            [175, 175, 109, 31, 13, 152, 155, 237]
+        );
+        assert_eq!(
+            IxUtils::anchor_discriminator("deposit"),
+            [242, 35, 198, 137, 82, 225, 242, 182]
+        );
+        assert_eq!(
+            IxUtils::anchor_discriminator("set_authority"),
+            [133, 250, 37, 21, 110, 163, 26, 121]
+        );
+    }
+
+    #[test]
+    fn test_extract_program_ids_deduplicates_repeated_programs() {
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+
+        let instructions = vec![
+            solana_system_interface::instruction::transfer(&sender, &recipient, 1_000),
+            Instruction::new_with_bytes(other_program, &[1], vec![]),
+            solana_system_interface::instruction::transfer(&sender, &recipient, 2_000),
+            Instruction::new_with_bytes(other_program, &[2], vec![]),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&sender)));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let program_ids = IxUtils::extract_program_ids(&transaction);
+
+        assert_eq!(transaction.all_instructions.len(), 4);
+        assert_eq!(program_ids.len(), 2);
+        assert!(program_ids.contains(&SYSTEM_PROGRAM_ID));
+        assert!(program_ids.contains(&other_program));
+    }
+
+    #[test]
+    fn test_extract_program_ids_single_program() {
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        let instructions = vec![
+            solana_system_interface::instruction::transfer(&sender, &recipient, 1_000),
+            solana_system_interface::instruction::transfer(&sender, &recipient, 1_000),
+            solana_system_interface::instruction::transfer(&sender, &recipient, 1_000),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&sender)));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let program_ids = IxUtils::extract_program_ids(&transaction);
+
+        assert_eq!(program_ids, HashSet::from([SYSTEM_PROGRAM_ID]));
+    }
+
+    #[test]
+    fn test_extract_all_writable_accounts_legacy() {
+        let fee_payer = Pubkey::new_unique();
+        let writable = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+
+        let instruction = Instruction::new_with_bytes(
+            program_id,
+            &[],
+            vec![AccountMeta::new(writable, false), AccountMeta::new_readonly(readonly, false)],
+        );
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let writable_accounts = IxUtils::extract_all_writable_accounts(&transaction);
+
+        assert_eq!(writable_accounts, vec![fee_payer, writable]);
+    }
+
+    #[test]
+    fn test_extract_all_writable_accounts_includes_lookup_table_addresses() {
+        let fee_payer = Pubkey::new_unique();
+        let readonly_signer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let lookup_writable = Pubkey::new_unique();
+        let lookup_readonly = Pubkey::new_unique();
+        let second_lookup_writable = Pubkey::new_unique();
+
+        let message = VersionedMessage::V0(solana_message::v0::Message {
+            header: solana_message::MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 1,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![fee_payer, readonly_signer, program_id],
+            recent_blockhash: solana_sdk::hash::Hash::default(),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 2,
+                accounts: vec![0, 1],
+                data: vec![],
+            }],
+            address_table_lookups: vec![
+                solana_message::v0::MessageAddressTableLookup {
+                    account_key: Pubkey::new_unique(),
+                    writable_indexes: vec![0],
+                    readonly_indexes: vec![1],
+                },
+                solana_message::v0::MessageAddressTableLookup {
+                    account_key: Pubkey::new_unique(),
+                    writable_indexes: vec![0],
+                    readonly_indexes: vec![],
+                },
+            ],
+        });
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        transaction.all_account_keys.extend([
+            lookup_writable,
+            lookup_readonly,
+            second_lookup_writable,
+        ]);
+
+        let writable_accounts = IxUtils::extract_all_writable_accounts(&transaction);
+
+        assert_eq!(writable_accounts, vec![fee_payer, lookup_writable, second_lookup_writable]);
+    }
+
     #[test]
     fn test_uncompile_instructions() {
         let program_id = Pubkey::new_unique();
@@ -3358,6 +3725,176 @@ mod tests {
         assert_eq!(compiled.data, instruction.data);
     }
 
+    #[test]
+    fn test_reconstruct_spl_token_revoke_multisig_instruction() {
+        let source = Pubkey::new_unique();
+        let multisig_owner = Pubkey::new_unique();
+        let signer1 = Pubkey::new_unique();
+        let signer2 = Pubkey::new_unique();
+        let token_program_id = spl_token_interface::ID;
+        let account_keys = vec![token_program_id, source, multisig_owner, signer1, signer2];
+
+        let instruction = spl_token_interface::instruction::revoke(
+            &spl_token_interface::ID,
+            &source,
+            &multisig_owner,
+            &[&signer1, &signer2],
+        )
+        .expect("Failed to create revoke instruction");
+
+        let message = Message::new(&[instruction.clone()], None);
+        let solana_parsed = parse_instruction::parse(
+            &spl_token_interface::ID,
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None),
+            None,
+        )
+        .expect("Failed to create parsed instruction");
+
+        let result = IxUtils::reconstruct_spl_token_instruction(
+            &solana_parsed,
+            &IxUtils::build_account_keys_hashmap(&account_keys),
+        );
+
+        assert!(result.is_ok());
+        let compiled = result.unwrap();
+        assert_eq!(compiled.program_id_index, 0);
+        assert_eq!(compiled.accounts, vec![1, 2, 3, 4]); // source, multisig owner, signers
+        assert_eq!(compiled.data, instruction.data);
+    }
+
+    #[test]
+    fn test_parse_system_instructions_upgrade_nonce_account() {
+        let fee_payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+
+        let instruction =
+            solana_system_interface::instruction::upgrade_nonce_account(nonce_account);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let parsed = IxUtils::parse_system_instructions(&transaction).unwrap();
+        let upgrades = parsed.get(&ParsedSystemInstructionType::SystemUpgradeNonceAccount).unwrap();
+
+        assert_eq!(
+            upgrades,
+            &vec![ParsedSystemInstructionData::SystemUpgradeNonceAccount { nonce_account }]
+        );
+        assert_eq!(parsed.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_token_instructions_revoke() {
+        let fee_payer = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let instruction = spl_token_interface::instruction::revoke(
+            &spl_token_interface::ID,
+            &source,
+            &owner,
+            &[],
+        )
+        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let parsed = IxUtils::parse_token_instructions(&transaction).unwrap();
+        let revokes = parsed.get(&ParsedSPLInstructionType::SplTokenRevoke).unwrap();
+
+        assert_eq!(revokes.len(), 1);
+        match &revokes[0] {
+            ParsedSPLInstructionData::SplTokenRevoke {
+                owner: parsed_owner,
+                source_address,
+                multisig_signers,
+                is_2022,
+            } => {
+                assert_eq!(*parsed_owner, owner);
+                assert_eq!(*source_address, source);
+                assert!(multisig_signers.is_empty());
+                assert!(!is_2022);
+            }
+            _ => panic!("Expected SplTokenRevoke"),
+        }
+    }
+
+    #[test]
+    fn test_parse_token_instructions_revoke_multisig() {
+        let fee_payer = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let multisig_owner = Pubkey::new_unique();
+        let signer1 = Pubkey::new_unique();
+        let signer2 = Pubkey::new_unique();
+
+        let spl_revoke = spl_token_interface::instruction::revoke(
+            &spl_token_interface::ID,
+            &source,
+            &multisig_owner,
+            &[&signer1, &signer2],
+        )
+        .unwrap();
+        let token2022_revoke = spl_token_2022_interface::instruction::revoke(
+            &spl_token_2022_interface::ID,
+            &source,
+            &multisig_owner,
+            &[&signer2],
+        )
+        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(
+            &[spl_revoke, token2022_revoke],
+            Some(&fee_payer),
+        ));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let parsed = IxUtils::parse_token_instructions(&transaction).unwrap();
+        let revokes = parsed.get(&ParsedSPLInstructionType::SplTokenRevoke).unwrap();
+
+        assert_eq!(revokes.len(), 2);
+        match &revokes[0] {
+            ParsedSPLInstructionData::SplTokenRevoke {
+                owner,
+                source_address,
+                multisig_signers,
+                is_2022,
+            } => {
+                assert_eq!(*owner, multisig_owner);
+                assert_eq!(*source_address, source);
+                assert_eq!(*multisig_signers, vec![signer1, signer2]);
+                assert!(!is_2022);
+            }
+            _ => panic!("Expected SplTokenRevoke"),
+        }
+        match &revokes[1] {
+            ParsedSPLInstructionData::SplTokenRevoke { multisig_signers, is_2022, .. } => {
+                assert_eq!(*multisig_signers, vec![signer2]);
+                assert!(is_2022);
+            }
+            _ => panic!("Expected SplTokenRevoke"),
+        }
+    }
+
+    #[test]
+    fn test_parse_token_instructions_revoke_missing_accounts() {
+        let fee_payer = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+
+        // Revoke requires at least the source and owner accounts
+        let instruction = Instruction {
+            program_id: spl_token_interface::ID,
+            accounts: vec![AccountMeta::new(source, false)],
+            data: spl_token_interface::instruction::TokenInstruction::Revoke.pack(),
+        };
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        assert!(IxUtils::parse_token_instructions(&transaction).is_err());
+    }
+
     #[test]
     fn test_reconstruct_unsupported_program_creates_stub() {
         let unsupported_program = Pubkey::new_unique();
@@ -3386,4 +3923,148 @@ mod tests {
         assert!(compiled.accounts.is_empty());
         assert!(compiled.data.is_empty());
     }
+
+    fn parse_compute_budget(
+        instructions: &[Instruction],
+    ) -> Result<ComputeBudgetSummary, KoraError> {
+        let message =
+            VersionedMessage::Legacy(Message::new(instructions, Some(&Pubkey::new_unique())));
+        let transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        IxUtils::parse_compute_budget_instructions(&transaction)
+    }
+
+    #[test]
+    fn test_parse_compute_budget_instructions_combinations() {
+        use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+        let transfer = solana_system_interface::instruction::transfer(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            1_000,
+        );
+
+        for (price, limit) in [
+            (None, None),
+            (Some(50_000u64), None),
+            (None, Some(300_000u32)),
+            (Some(1), Some(1_400_000)),
+        ] {
+            let mut instructions = vec![];
+            if let Some(price) = price {
+                instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+            }
+            if let Some(limit) = limit {
+                instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+            }
+            instructions.push(transfer.clone());
+
+            let summary = parse_compute_budget(&instructions).unwrap();
+
+            assert_eq!(summary.price_micro_lamports, price);
+            assert_eq!(summary.limit, limit);
+            assert_eq!(summary.is_set(), price.is_some() || limit.is_some());
+        }
+    }
+
+    #[test]
+    fn test_parse_compute_budget_instructions_ignores_other_budget_instructions() {
+        use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+        let instructions = [ComputeBudgetInstruction::request_heap_frame(64 * 1024)];
+
+        let summary = parse_compute_budget(&instructions).unwrap();
+
+        assert!(!summary.is_set());
+    }
+
+    #[test]
+    fn test_parse_compute_budget_instructions_rejects_duplicates() {
+        use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+        let duplicate_price = [
+            ComputeBudgetInstruction::set_compute_unit_price(1),
+            ComputeBudgetInstruction::set_compute_unit_price(2),
+        ];
+        let duplicate_limit = [
+            ComputeBudgetInstruction::set_compute_unit_limit(1),
+            ComputeBudgetInstruction::set_compute_unit_limit(2),
+        ];
+
+        for instructions in [duplicate_price, duplicate_limit] {
+            let result = parse_compute_budget(&instructions);
+            assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+        }
+    }
+
+    #[test]
+    fn test_validate_account_indices() {
+        let account_keys = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let valid = CompiledInstruction { program_id_index: 2, accounts: vec![0, 1], data: vec![] };
+        assert!(IxUtils::validate_account_indices(&[valid.clone()], &account_keys).is_ok());
+
+        let bad_program =
+            CompiledInstruction { program_id_index: 3, accounts: vec![], data: vec![] };
+        let result =
+            IxUtils::validate_account_indices(&[valid.clone(), bad_program], &account_keys);
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains("Instruction 1 has program id index 3")));
+
+        let bad_account =
+            CompiledInstruction { program_id_index: 2, accounts: vec![0, 255], data: vec![] };
+        let result = IxUtils::validate_account_indices(&[bad_account], &account_keys);
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains("account index 255")));
+
+        // No keys at all can't satisfy any instruction
+        assert!(IxUtils::validate_account_indices(&[valid], &[]).is_err());
+    }
+
+    #[test]
+    fn test_validate_account_indices_fuzz() {
+        let mut rng = rand::rng();
+
+        for _ in 0..1000 {
+            let account_keys: Vec<Pubkey> =
+                (0..rng.random_range(0..=20)).map(|_| Pubkey::new_unique()).collect();
+            // Mostly in range, with the occasional index past the end
+            let random_index = |rng: &mut rand::rngs::ThreadRng| -> u8 {
+                if rng.random_ratio(1, 10) {
+                    rng.random()
+                } else {
+                    rng.random_range(0..=account_keys.len() as u8)
+                }
+            };
+            let instructions: Vec<CompiledInstruction> = (0..rng.random_range(1..=4))
+                .map(|_| {
+                    let program_id_index = random_index(&mut rng);
+                    let accounts =
+                        (0..rng.random_range(0..=6)).map(|_| random_index(&mut rng)).collect();
+                    CompiledInstruction { program_id_index, accounts, data: vec![] }
+                })
+                .collect();
+
+            let in_range = instructions.iter().all(|ix| {
+                std::iter::once(&ix.program_id_index)
+                    .chain(&ix.accounts)
+                    .all(|idx| (*idx as usize) < account_keys.len())
+            });
+
+            let result = IxUtils::validate_account_indices(&instructions, &account_keys);
+            assert_eq!(
+                result.is_ok(),
+                in_range,
+                "{instructions:?} with {} keys",
+                account_keys.len()
+            );
+            if in_range {
+                let uncompiled =
+                    IxUtils::uncompile_instructions(&instructions, &account_keys).unwrap();
+                assert_eq!(uncompiled.len(), instructions.len());
+            } else {
+                assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+            }
+        }
+    }
 }
@@ -1,7 +1,9 @@
+mod confirmation;
 mod instruction_util;
 mod transaction;
 mod versioned_message;
 mod versioned_transaction;
+pub use confirmation::*;
 pub use instruction_util::*;
 pub use transaction::*;
 pub use versioned_message::*;
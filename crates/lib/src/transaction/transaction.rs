@@ -6,6 +6,10 @@ use solana_sdk::{
 
 use crate::{error::KoraError, transaction::VersionedTransactionResolved};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use sha2::{Digest, Sha256};
+
+/// SHA-256 of a serialized transaction message, the same for every signed copy of a transaction
+pub type TransactionHash = [u8; 32];
 
 pub struct TransactionUtil {}
 
@@ -55,6 +59,10 @@ impl TransactionUtil {
         })?;
         Ok(STANDARD.encode(serialized))
     }
+
+    pub fn hash_message(message: &VersionedMessage) -> TransactionHash {
+        Sha256::digest(message.serialize()).into()
+    }
 }
 
 #[cfg(test)]
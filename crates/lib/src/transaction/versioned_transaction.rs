@@ -2,28 +2,48 @@ use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
 use solana_commitment_config::CommitmentConfig;
-use solana_keychain::{Signer, SolanaSigner};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_keychain::SolanaSigner;
 use solana_message::{
-    compiled_instruction::CompiledInstruction, v0::MessageAddressTableLookup, VersionedMessage,
+    compiled_instruction::CompiledInstruction,
+    v0::{self, MessageAddressTableLookup},
+    AddressLookupTableAccount, Message, VersionedMessage,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signature,
+    transaction::VersionedTransaction,
+};
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    time::Duration,
 };
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, transaction::VersionedTransaction};
-use std::{collections::HashMap, ops::Deref};
 
 use solana_transaction_status_client_types::{UiInstruction, UiTransactionEncoding};
 
 use crate::{
+    config::AutoComputeBudgetConfig,
     error::KoraError,
     fee::fee::{FeeConfigUtil, TransactionFeeUtil},
-    state::get_config,
+    rpc_server::backpressure::BackpressureController,
+    signer::KoraSigner,
+    state::get_signer_pool,
     transaction::{
         instruction_util::IxUtils, ParsedSPLInstructionData, ParsedSPLInstructionType,
-        ParsedSystemInstructionData, ParsedSystemInstructionType,
+        ParsedSystemInstructionData, ParsedSystemInstructionType, TransactionUtil,
     },
-    validator::transaction_validator::TransactionValidator,
+    validator::transaction_validator::{TransactionValidator, ValidationContext},
     CacheUtil,
 };
 use solana_address_lookup_table_interface::state::AddressLookupTable;
 
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
 /// A fully resolved transaction with lookup tables and inner instructions resolved
 pub struct VersionedTransactionResolved {
     pub transaction: VersionedTransaction,
@@ -34,6 +54,12 @@ pub struct VersionedTransactionResolved {
     // Includes all instructions, including inner instructions
     pub all_instructions: Vec<Instruction>,
 
+    // Number of top-level instructions at the start of `all_instructions`
+    outer_instruction_count: usize,
+
+    // False when simulation didn't return inner instructions or some couldn't be decoded
+    pub inner_instructions_complete: bool,
+
     // Parsed instructions by type (None if not parsed yet)
     parsed_system_instructions:
         Option<HashMap<ParsedSystemInstructionType, Vec<ParsedSystemInstructionData>>>,
@@ -51,21 +77,50 @@ impl Deref for VersionedTransactionResolved {
     }
 }
 
+/// Two resolved transactions are equal when their serialized messages are, signatures and
+/// parsed instruction caches are ignored
+impl PartialEq for VersionedTransactionResolved {
+    fn eq(&self, other: &Self) -> bool {
+        self.transaction.message.serialize() == other.transaction.message.serialize()
+    }
+}
+
+impl Eq for VersionedTransactionResolved {}
+
+impl Hash for VersionedTransactionResolved {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.transaction.message.serialize().hash(state);
+    }
+}
+
 #[async_trait]
 pub trait VersionedTransactionOps {
     fn encode_b64_transaction(&self) -> Result<String, KoraError>;
     fn find_signer_position(&self, signer_pubkey: &Pubkey) -> Result<usize, KoraError>;
 
+    /// Validate the transaction and its fee payment as `sign_transaction` does, returns the
+    /// transaction that would be signed, with its blockhash set
+    async fn validate_for_signing(
+        &mut self,
+        fee_payer: &Pubkey,
+        rpc_client: &RpcClient,
+    ) -> Result<VersionedTransaction, KoraError>;
     async fn sign_transaction(
         &mut self,
-        signer: &std::sync::Arc<Signer>,
+        signer: &std::sync::Arc<KoraSigner>,
         rpc_client: &RpcClient,
     ) -> Result<(VersionedTransaction, String), KoraError>;
     async fn sign_and_send_transaction(
         &mut self,
-        signer: &std::sync::Arc<Signer>,
+        signer: &std::sync::Arc<KoraSigner>,
         rpc_client: &RpcClient,
     ) -> Result<(String, String), KoraError>;
+    /// Sign and send without waiting for confirmation, returns the signature and the encoded transaction
+    async fn sign_and_send_transaction_unconfirmed(
+        &mut self,
+        signer: &std::sync::Arc<KoraSigner>,
+        rpc_client: &RpcClient,
+    ) -> Result<(Signature, String), KoraError>;
 }
 
 impl VersionedTransactionResolved {
@@ -74,14 +129,6 @@ impl VersionedTransactionResolved {
         rpc_client: &RpcClient,
         sig_verify: bool,
     ) -> Result<Self, KoraError> {
-        let mut resolved = Self {
-            transaction: transaction.clone(),
-            all_account_keys: vec![],
-            all_instructions: vec![],
-            parsed_system_instructions: None,
-            parsed_spl_instructions: None,
-        };
-
         // 1. Resolve lookup table addresses based on transaction type
         let resolved_addresses = match &transaction.message {
             VersionedMessage::Legacy(_) => {
@@ -90,6 +137,9 @@ impl VersionedTransactionResolved {
             }
             VersionedMessage::V0(v0_message) => {
                 // V0 transactions may have lookup tables
+                LookupTableUtil::validate_lookup_table_account_count(
+                    &v0_message.address_table_lookups,
+                )?;
                 LookupTableUtil::resolve_lookup_table_addresses(
                     rpc_client,
                     &v0_message.address_table_lookups,
@@ -98,17 +148,47 @@ impl VersionedTransactionResolved {
             }
         };
 
+        Self::from_transaction_with_lookup_addresses(
+            transaction,
+            rpc_client,
+            sig_verify,
+            resolved_addresses,
+        )
+        .await
+    }
+
+    /// Same as `from_transaction`, with the lookup table addresses already resolved by the caller
+    pub async fn from_transaction_with_lookup_addresses(
+        transaction: &VersionedTransaction,
+        rpc_client: &RpcClient,
+        sig_verify: bool,
+        resolved_addresses: Vec<Pubkey>,
+    ) -> Result<Self, KoraError> {
+        let mut resolved = Self {
+            transaction: transaction.clone(),
+            all_account_keys: vec![],
+            all_instructions: vec![],
+            outer_instruction_count: 0,
+            inner_instructions_complete: false,
+            parsed_system_instructions: None,
+            parsed_spl_instructions: None,
+        };
+
         // Set all accout keys
         let mut all_account_keys = transaction.message.static_account_keys().to_vec();
-        all_account_keys.extend(resolved_addresses.clone());
+        all_account_keys.extend(resolved_addresses);
         resolved.all_account_keys = all_account_keys.clone();
 
+        // Reject out-of-range indices before any account is looked up or simulated
+        IxUtils::validate_account_indices(transaction.message.instructions(), &all_account_keys)?;
+
         // 2. Fetch all instructions
         let outer_instructions =
             IxUtils::uncompile_instructions(transaction.message.instructions(), &all_account_keys)?;
 
         let inner_instructions = resolved.fetch_inner_instructions(rpc_client, sig_verify).await?;
 
+        resolved.outer_instruction_count = outer_instructions.len();
         resolved.all_instructions.extend(outer_instructions);
         resolved.all_instructions.extend(inner_instructions);
 
@@ -126,12 +206,105 @@ impl VersionedTransactionResolved {
                 transaction.message.instructions(),
                 transaction.message.static_account_keys(),
             )?,
+            outer_instruction_count: transaction.message.instructions().len(),
+            inner_instructions_complete: true,
             parsed_system_instructions: None,
             parsed_spl_instructions: None,
         })
     }
 
-    /// Fetch inner instructions via simulation
+    /// Recompile a transaction built by Kora into a V0 message that loads every account it can
+    /// from `lookup_table`. The message changes, so this must happen before anything is signed.
+    pub fn compress_with_lookup_table(
+        &mut self,
+        lookup_table: &AddressLookupTableAccount,
+    ) -> Result<(), KoraError> {
+        let fee_payer =
+            *self.transaction.message.static_account_keys().first().ok_or_else(|| {
+                KoraError::InvalidTransaction("Transaction has no fee payer".to_string())
+            })?;
+
+        let message = v0::Message::try_compile(
+            &fee_payer,
+            self.outer_instructions(),
+            std::slice::from_ref(lookup_table),
+            *self.transaction.message.recent_blockhash(),
+        )
+        .map_err(|e| {
+            KoraError::InvalidTransaction(format!(
+                "Failed to compile message with lookup table: {e}"
+            ))
+        })?;
+
+        // Same order the runtime loads them in: static keys, then writable and readonly lookups
+        let mut all_account_keys = message.account_keys.clone();
+        for lookup in &message.address_table_lookups {
+            all_account_keys.extend(
+                lookup
+                    .writable_indexes
+                    .iter()
+                    .chain(&lookup.readonly_indexes)
+                    .map(|&index| lookup_table.addresses[index as usize]),
+            );
+        }
+
+        self.transaction =
+            TransactionUtil::new_unsigned_versioned_transaction(VersionedMessage::V0(message));
+        self.all_account_keys = all_account_keys;
+        self.parsed_system_instructions = None;
+        self.parsed_spl_instructions = None;
+
+        Ok(())
+    }
+
+    /// Prepend the `auto_compute_budget` instructions for the values the transaction doesn't set
+    /// yet. Rebuilds the message, so like `compress_with_lookup_table` this is only for
+    /// transactions built by Kora, before anything is signed.
+    pub fn add_compute_budget_instructions(
+        &mut self,
+        auto_compute_budget: &AutoComputeBudgetConfig,
+    ) -> Result<(), KoraError> {
+        let compute_budget = IxUtils::parse_compute_budget_instructions(self)?;
+
+        let mut instructions = Vec::new();
+        if let (None, Some(limit)) = (compute_budget.limit, auto_compute_budget.compute_unit_limit)
+        {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+        if let (None, Some(price)) =
+            (compute_budget.price_micro_lamports, auto_compute_budget.compute_unit_price)
+        {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        if instructions.is_empty() {
+            return Ok(());
+        }
+
+        let fee_payer =
+            *self.transaction.message.static_account_keys().first().ok_or_else(|| {
+                KoraError::InvalidTransaction("Transaction has no fee payer".to_string())
+            })?;
+        instructions.extend_from_slice(self.outer_instructions());
+
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &instructions,
+            Some(&fee_payer),
+            self.transaction.message.recent_blockhash(),
+        ));
+        *self = Self::from_kora_built_transaction(
+            &TransactionUtil::new_unsigned_versioned_transaction(message),
+        )?;
+
+        Ok(())
+    }
+
+    /// Top-level instructions of the transaction, without the inner instructions resolved from simulation
+    pub fn outer_instructions(&self) -> &[Instruction] {
+        &self.all_instructions[..self.outer_instruction_count.min(self.all_instructions.len())]
+    }
+
+    /// Fetch inner instructions via simulation, recording in `inner_instructions_complete` whether
+    /// every one of them was resolved
     async fn fetch_inner_instructions(
         &mut self,
         rpc_client: &RpcClient,
@@ -161,26 +334,38 @@ impl VersionedTransactionResolved {
 
         if let Some(inner_instructions) = simulation_result.value.inner_instructions {
             let mut compiled_inner_instructions: Vec<CompiledInstruction> = vec![];
+            let mut complete = true;
 
             inner_instructions.iter().for_each(|ix| {
                 ix.instructions.iter().for_each(|inner_ix| match inner_ix {
                     UiInstruction::Compiled(ix) => {
+                        let data = bs58::decode(&ix.data).into_vec().unwrap_or_else(|_| {
+                            complete = false;
+                            vec![]
+                        });
                         compiled_inner_instructions.push(CompiledInstruction {
                             program_id_index: ix.program_id_index,
                             accounts: ix.accounts.clone(),
-                            data: bs58::decode(&ix.data).into_vec().unwrap_or_default(),
+                            data,
                         });
                     }
                     UiInstruction::Parsed(ui_parsed) => {
-                        if let Some(compiled) = IxUtils::reconstruct_instruction_from_ui(
+                        match IxUtils::reconstruct_instruction_from_ui(
                             &UiInstruction::Parsed(ui_parsed.clone()),
                             &self.all_account_keys,
                         ) {
-                            compiled_inner_instructions.push(compiled);
+                            Some(compiled) => compiled_inner_instructions.push(compiled),
+                            None => complete = false,
                         }
                     }
                 });
             });
+            self.inner_instructions_complete = complete;
+
+            IxUtils::validate_account_indices(
+                &compiled_inner_instructions,
+                &self.all_account_keys,
+            )?;
 
             return IxUtils::uncompile_instructions(
                 &compiled_inner_instructions,
@@ -188,6 +373,7 @@ impl VersionedTransactionResolved {
             );
         }
 
+        self.inner_instructions_complete = false;
         Ok(vec![])
     }
 
@@ -240,23 +426,23 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
             })
     }
 
-    async fn sign_transaction(
+    async fn validate_for_signing(
         &mut self,
-        signer: &std::sync::Arc<Signer>,
+        fee_payer: &Pubkey,
         rpc_client: &RpcClient,
-    ) -> Result<(VersionedTransaction, String), KoraError> {
-        let fee_payer = signer.pubkey();
+    ) -> Result<VersionedTransaction, KoraError> {
         let config = &get_config()?;
-        let validator = TransactionValidator::new(fee_payer)?;
+        let validator = TransactionValidator::new(*fee_payer)?;
+        let validation_ctx = ValidationContext::new();
 
         // Validate transaction and accounts (already resolved)
-        validator.validate_transaction(self, rpc_client).await?;
+        validator.validate_transaction_with_context(self, rpc_client, &validation_ctx).await?;
 
         // Calculate fee and validate payment if price model requires it
         let fee_calculation = FeeConfigUtil::estimate_kora_fee(
             rpc_client,
             self,
-            &fee_payer,
+            fee_payer,
             config.validation.is_payment_required(),
             config.validation.price_source.clone(),
         )
@@ -268,7 +454,7 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
         if required_lamports > 0 {
             log::info!("Payment validation: required_lamports={}", required_lamports);
             // Get the expected payment destination
-            let payment_destination = config.kora.get_payment_address(&fee_payer)?;
+            let payment_destination = config.kora.get_payment_address(fee_payer)?;
 
             // Validate token payment using the resolved transaction
             TransactionValidator::validate_token_payment(
@@ -276,6 +462,7 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
                 required_lamports,
                 rpc_client,
                 &payment_destination,
+                &validation_ctx,
             )
             .await?;
 
@@ -294,15 +481,51 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
         }
 
         // Validate transaction fee using resolved transaction
-        let estimated_fee = TransactionFeeUtil::get_estimate_fee_resolved(rpc_client, self).await?;
+        let estimated_fee = TransactionFeeUtil::estimate_base_fee(
+            rpc_client,
+            self,
+            &config.validation.fee_estimation_strategy,
+        )
+        .await?;
         validator.validate_lamport_fee(estimated_fee)?;
 
-        // Sign transaction
+        Ok(transaction)
+    }
+
+    async fn sign_transaction(
+        &mut self,
+        signer: &std::sync::Arc<KoraSigner>,
+        rpc_client: &RpcClient,
+    ) -> Result<(VersionedTransaction, String), KoraError> {
+        let fee_payer = signer.pubkey();
+        let config = &get_config()?;
+        let mut transaction = self.validate_for_signing(&fee_payer, rpc_client).await?;
+
+        // Sign transaction, holding one of the signer's signing slots until it completes
+        let pool = get_signer_pool().ok();
+        let _signing_permit = match &pool {
+            Some(pool) => {
+                BackpressureController::global()
+                    .acquire_signing_permit(
+                        pool,
+                        &fee_payer,
+                        Duration::from_millis(config.kora.backpressure_timeout_ms),
+                    )
+                    .await?
+            }
+            None => None,
+        };
         let message_bytes = transaction.message.serialize();
-        let signature = signer
-            .sign_message(&message_bytes)
-            .await
-            .map_err(|e| KoraError::SigningError(e.to_string()))?;
+        let signature = signer.sign_message(&message_bytes).await.map_err(|e| {
+            if let Some(pool) = &pool {
+                pool.record_signing_failure(&fee_payer, &e, &message_bytes);
+            }
+            KoraError::SigningError(e.to_string())
+        })?;
+
+        if let Some(pool) = &pool {
+            pool.record_signature(&fee_payer);
+        }
 
         // Find the fee payer position - don't assume it's at position 0
         let fee_payer_position = self.find_signer_position(&fee_payer)?;
@@ -317,7 +540,7 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
 
     async fn sign_and_send_transaction(
         &mut self,
-        signer: &std::sync::Arc<Signer>,
+        signer: &std::sync::Arc<KoraSigner>,
         rpc_client: &RpcClient,
     ) -> Result<(String, String), KoraError> {
         // Payment validation is handled in sign_transaction
@@ -331,30 +554,83 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
 
         Ok((signature.to_string(), encoded))
     }
+
+    async fn sign_and_send_transaction_unconfirmed(
+        &mut self,
+        signer: &std::sync::Arc<KoraSigner>,
+        rpc_client: &RpcClient,
+    ) -> Result<(Signature, String), KoraError> {
+        let (transaction, encoded) = self.sign_transaction(signer, rpc_client).await?;
+
+        let signature = rpc_client
+            .send_transaction(&transaction)
+            .await
+            .map_err(|e| KoraError::RpcError(e.to_string()))?;
+
+        Ok((signature, encoded))
+    }
 }
 
 pub struct LookupTableUtil {}
 
 impl LookupTableUtil {
+    /// Reject transactions loading more accounts from lookup tables than
+    /// `max_lookup_table_accounts`. Only the lookup indexes are counted, so this runs before any
+    /// lookup table is fetched
+    pub fn validate_lookup_table_account_count(
+        lookup_table_lookups: &[MessageAddressTableLookup],
+    ) -> Result<(), KoraError> {
+        if lookup_table_lookups.is_empty() {
+            return Ok(());
+        }
+
+        let Some(max_accounts) = get_config()?.validation.max_lookup_table_accounts else {
+            return Ok(());
+        };
+
+        let account_count: usize = lookup_table_lookups
+            .iter()
+            .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+            .sum();
+        if account_count > max_accounts {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Transaction loads {account_count} accounts from lookup tables, the maximum is {max_accounts}"
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Resolves addresses from lookup tables for V0 transactions
     pub async fn resolve_lookup_table_addresses(
         rpc_client: &RpcClient,
         lookup_table_lookups: &[MessageAddressTableLookup],
     ) -> Result<Vec<Pubkey>, KoraError> {
-        let mut resolved_addresses = Vec::new();
+        let mut lookup_tables = Vec::with_capacity(lookup_table_lookups.len());
 
         // Maybe we can use caching here, there's a chance the lookup tables get updated though, so tbd
         for lookup in lookup_table_lookups {
-            let lookup_table_account =
-                CacheUtil::get_account(rpc_client, &lookup.account_key, false).await.map_err(
-                    |e| KoraError::RpcError(format!("Failed to fetch lookup table: {e}")),
-                )?;
+            lookup_tables.push(Self::fetch_lookup_table(rpc_client, &lookup.account_key).await?);
+        }
 
-            // Parse the lookup table account data to get the actual addresses
-            let address_lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data)
-                .map_err(|e| {
+        Self::resolve_addresses_from_tables(lookup_table_lookups, &lookup_tables)
+    }
+
+    /// Resolves addresses of `lookup_table_lookups` from lookup tables that are already fetched
+    pub fn resolve_addresses_from_tables(
+        lookup_table_lookups: &[MessageAddressTableLookup],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<Vec<Pubkey>, KoraError> {
+        let mut resolved_addresses = Vec::new();
+
+        for lookup in lookup_table_lookups {
+            let address_lookup_table = lookup_tables
+                .iter()
+                .find(|table| table.key == lookup.account_key)
+                .ok_or_else(|| {
                     KoraError::InvalidTransaction(format!(
-                        "Failed to deserialize lookup table: {e}"
+                        "Lookup table {} was not fetched",
+                        lookup.account_key
                     ))
                 })?;
 
@@ -383,6 +659,59 @@ impl LookupTableUtil {
 
         Ok(resolved_addresses)
     }
+
+    /// Fetch and parse the lookup table stored at `address`
+    pub async fn fetch_lookup_table(
+        rpc_client: &RpcClient,
+        address: &Pubkey,
+    ) -> Result<AddressLookupTableAccount, KoraError> {
+        let lookup_table_account = CacheUtil::get_account(rpc_client, address, false)
+            .await
+            .map_err(|e| KoraError::RpcError(format!("Failed to fetch lookup table: {e}")))?;
+
+        // Parse the lookup table account data to get the actual addresses
+        let address_lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data)
+            .map_err(|e| {
+                KoraError::InvalidTransaction(format!("Failed to deserialize lookup table: {e}"))
+            })?;
+
+        Ok(AddressLookupTableAccount {
+            key: *address,
+            addresses: address_lookup_table.addresses.to_vec(),
+        })
+    }
+
+    /// Fetch and parse several lookup tables with a single cache read and a single
+    /// `getMultipleAccounts` call for the tables that aren't cached
+    pub async fn fetch_lookup_tables(
+        rpc_client: &RpcClient,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, KoraError> {
+        let accounts = CacheUtil::get_accounts(rpc_client, addresses)
+            .await
+            .map_err(|e| KoraError::RpcError(format!("Failed to fetch lookup tables: {e}")))?;
+
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account.ok_or_else(|| {
+                    KoraError::InvalidTransaction(format!("Lookup table {address} not found"))
+                })?;
+                let address_lookup_table =
+                    AddressLookupTable::deserialize(&account.data).map_err(|e| {
+                        KoraError::InvalidTransaction(format!(
+                            "Failed to deserialize lookup table: {e}"
+                        ))
+                    })?;
+
+                Ok(AddressLookupTableAccount {
+                    key: *address,
+                    addresses: address_lookup_table.addresses.to_vec(),
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -398,7 +727,7 @@ mod tests {
     };
     use serde_json::json;
     use solana_client::rpc_request::RpcRequest;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
     use super::*;
     use solana_address_lookup_table_interface::state::LookupTableMeta;
@@ -659,6 +988,143 @@ mod tests {
         assert_eq!(resolved.all_instructions[0].data, vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_resolved_transaction_equality_ignores_signatures() {
+        let keypair = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![AccountMeta::new(keypair.pubkey(), true)],
+        );
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[instruction],
+            Some(&keypair.pubkey()),
+            &Hash::new_unique(),
+        ));
+
+        let unsigned = VersionedTransactionResolved::from_kora_built_transaction(
+            &TransactionUtil::new_unsigned_versioned_transaction(message.clone()),
+        )
+        .unwrap();
+        let mut signed = VersionedTransactionResolved::from_kora_built_transaction(
+            &VersionedTransaction::try_new(message, &[&keypair]).unwrap(),
+        )
+        .unwrap();
+        signed.get_or_parse_system_instructions().unwrap();
+
+        let other =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(VersionedMessage::Legacy(
+                Message::new_with_blockhash(&[], Some(&keypair.pubkey()), &Hash::new_unique()),
+            ))
+            .unwrap();
+
+        assert!(unsigned == signed);
+        assert!(unsigned != other);
+
+        let unique: HashSet<VersionedTransactionResolved> =
+            [unsigned, signed, other].into_iter().collect();
+        assert_eq!(unique.len(), 2);
+    }
+
+    #[test]
+    fn test_compress_with_lookup_table() {
+        let fee_payer = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let recipients: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let instructions: Vec<Instruction> = recipients
+            .iter()
+            .map(|recipient| {
+                Instruction::new_with_bytes(
+                    program_id,
+                    &[1, 2, 3],
+                    vec![
+                        AccountMeta::new(fee_payer.pubkey(), true),
+                        AccountMeta::new(*recipient, false),
+                    ],
+                )
+            })
+            .collect();
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &instructions,
+            Some(&fee_payer.pubkey()),
+            &Hash::new_unique(),
+        ));
+        let mut resolved =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        let size_before = bincode::serialize(&resolved.transaction).unwrap().len();
+        let keys_before: HashSet<Pubkey> = resolved.all_account_keys.iter().copied().collect();
+        let instructions_before = resolved.outer_instructions().to_vec();
+
+        let lookup_table =
+            AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: recipients.clone() };
+        resolved.compress_with_lookup_table(&lookup_table).unwrap();
+
+        let VersionedMessage::V0(compressed) = &resolved.transaction.message else {
+            panic!("Expected a V0 message");
+        };
+        assert_eq!(compressed.address_table_lookups.len(), 1);
+        assert_eq!(compressed.address_table_lookups[0].writable_indexes.len(), recipients.len());
+        assert!(!compressed.account_keys.iter().any(|key| recipients.contains(key)));
+
+        let size_after = bincode::serialize(&resolved.transaction).unwrap().len();
+        assert!(size_after < size_before, "{size_after} should be smaller than {size_before}");
+
+        let keys_after: HashSet<Pubkey> = resolved.all_account_keys.iter().copied().collect();
+        assert_eq!(keys_before, keys_after);
+        assert_eq!(resolved.outer_instructions(), instructions_before.as_slice());
+        assert_eq!(resolved.transaction.signatures, vec![Signature::default()]);
+    }
+
+    #[test]
+    fn test_add_compute_budget_instructions() {
+        let fee_payer = Pubkey::new_unique();
+        let transfer =
+            solana_system_interface::instruction::transfer(&fee_payer, &Pubkey::new_unique(), 1);
+        let resolved = |instructions: &[Instruction]| {
+            let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+                instructions,
+                Some(&fee_payer),
+                &Hash::new_unique(),
+            ));
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap()
+        };
+        let auto_compute_budget = AutoComputeBudgetConfig {
+            compute_unit_limit: Some(50_000),
+            compute_unit_price: Some(1_000),
+        };
+
+        // Both values are added in front of the transaction's own instructions
+        let mut transaction = resolved(std::slice::from_ref(&transfer));
+        let blockhash = *transaction.transaction.message.recent_blockhash();
+        transaction.add_compute_budget_instructions(&auto_compute_budget).unwrap();
+        assert_eq!(
+            transaction.outer_instructions(),
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+                transfer.clone(),
+            ]
+        );
+        assert_eq!(transaction.transaction.message.recent_blockhash(), &blockhash);
+        assert_eq!(transaction.transaction.message.static_account_keys()[0], fee_payer);
+
+        // A price already set by the transaction is kept
+        let existing_price = ComputeBudgetInstruction::set_compute_unit_price(5);
+        let mut transaction = resolved(&[existing_price.clone(), transfer.clone()]);
+        transaction.add_compute_budget_instructions(&auto_compute_budget).unwrap();
+        assert_eq!(
+            transaction.outer_instructions(),
+            &[ComputeBudgetInstruction::set_compute_unit_limit(50_000), existing_price, transfer]
+        );
+        let compute_budget = IxUtils::parse_compute_budget_instructions(&transaction).unwrap();
+        assert_eq!(compute_budget.price_micro_lamports, Some(5));
+
+        // Nothing to add leaves the message untouched
+        let before = transaction.transaction.clone();
+        transaction.add_compute_budget_instructions(&auto_compute_budget).unwrap();
+        assert_eq!(transaction.transaction, before);
+    }
+
     #[tokio::test]
     async fn test_from_transaction_legacy() {
         let config = setup_test_config();
@@ -701,6 +1167,7 @@ mod tests {
         assert_eq!(resolved.transaction, transaction);
         assert_eq!(resolved.all_account_keys, transaction.message.static_account_keys());
         assert_eq!(resolved.all_instructions.len(), 1); // Only outer instruction since no inner instructions in mock
+        assert!(resolved.inner_instructions_complete);
 
         // Check instruction properties rather than direct equality since IxUtils::uncompile_instructions
         // properly sets signer status based on the transaction message
@@ -809,6 +1276,56 @@ mod tests {
         assert_eq!(resolved.all_account_keys[2], resolved_address);
     }
 
+    #[tokio::test]
+    async fn test_from_transaction_max_lookup_table_accounts() {
+        let mut config = setup_test_config();
+        config.validation.max_lookup_table_accounts = Some(128);
+        let _m = setup_config_mock(config);
+
+        let keypair = Keypair::new();
+        let lookup_table_account = Pubkey::new_unique();
+        // Indexes into a full 256 address lookup table, split between writable and readonly
+        let lookups = |count: usize| {
+            let indexes: Vec<u8> = (0..count).map(|i| i as u8).collect();
+            let (writable, readonly) = indexes.split_at(count / 2);
+            vec![solana_message::v0::MessageAddressTableLookup {
+                account_key: lookup_table_account,
+                writable_indexes: writable.to_vec(),
+                readonly_indexes: readonly.to_vec(),
+            }]
+        };
+
+        assert!(LookupTableUtil::validate_lookup_table_account_count(&lookups(128)).is_ok());
+        let result = LookupTableUtil::validate_lookup_table_account_count(&lookups(129));
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains("loads 129 accounts") && msg.contains("maximum is 128")));
+
+        let v0_message = v0::Message {
+            header: solana_message::MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![keypair.pubkey(), Pubkey::new_unique()],
+            recent_blockhash: Hash::new_unique(),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0, 2],
+                data: vec![],
+            }],
+            address_table_lookups: lookups(256),
+        };
+        let transaction =
+            VersionedTransaction::try_new(VersionedMessage::V0(v0_message), &[&keypair]).unwrap();
+
+        // Rejected before the lookup table is fetched, the RPC mock has no account to return
+        let rpc_client = RpcMockBuilder::new().build();
+        let result =
+            VersionedTransactionResolved::from_transaction(&transaction, &rpc_client, true).await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains("loads 256 accounts")));
+    }
+
     #[tokio::test]
     async fn test_from_transaction_simulation_failure() {
         let config = setup_test_config();
@@ -909,6 +1426,62 @@ mod tests {
 
         assert_eq!(inner_instructions.len(), 1);
         assert_eq!(inner_instructions[0].data, vec![10, 20, 30]);
+        assert!(resolved.inner_instructions_complete);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_inner_instructions_incomplete() {
+        let config = setup_test_config();
+        let _m = setup_config_mock(config);
+
+        let keypair = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![AccountMeta::new(keypair.pubkey(), true)],
+        );
+        let message =
+            VersionedMessage::Legacy(Message::new(&[instruction], Some(&keypair.pubkey())));
+        let transaction = VersionedTransaction::try_new(message, &[&keypair]).unwrap();
+
+        let simulation = |inner_instructions: serde_json::Value| {
+            let mut mocks = HashMap::new();
+            mocks.insert(
+                RpcRequest::SimulateTransaction,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "err": null,
+                        "logs": [],
+                        "accounts": null,
+                        "unitsConsumed": 1000,
+                        "innerInstructions": inner_instructions
+                    }
+                }),
+            );
+            RpcMockBuilder::new().with_custom_mocks(mocks).build()
+        };
+
+        // Inner instruction data that isn't valid base58
+        let rpc_client = simulation(json!([
+            {
+                "index": 0,
+                "instructions": [{ "programIdIndex": 1, "accounts": [0], "data": "0OIl" }]
+            }
+        ]));
+        let mut resolved =
+            VersionedTransactionResolved::from_kora_built_transaction(&transaction).unwrap();
+        resolved.fetch_inner_instructions(&rpc_client, true).await.unwrap();
+        assert!(!resolved.inner_instructions_complete);
+
+        // Simulation without inner instructions
+        let rpc_client = simulation(serde_json::Value::Null);
+        let mut resolved =
+            VersionedTransactionResolved::from_kora_built_transaction(&transaction).unwrap();
+        let inner_instructions =
+            resolved.fetch_inner_instructions(&rpc_client, true).await.unwrap();
+        assert!(inner_instructions.is_empty());
+        assert!(!resolved.inner_instructions_complete);
     }
 
     #[tokio::test]
@@ -1,5 +1,8 @@
 pub mod usage_store;
 pub mod usage_tracker;
 
-pub use usage_store::{InMemoryUsageStore, RedisUsageStore, UsageStore};
+pub use usage_store::{
+    InMemorySlidingWindowUsageStore, InMemoryUsageStore, RedisUsageStore, SlidingWindow,
+    SlidingWindowUsageStore, UsageStore,
+};
 pub use usage_tracker::UsageTracker;
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use async_trait::async_trait;
 use deadpool_redis::{Connection, Pool};
@@ -6,6 +10,16 @@ use redis::AsyncCommands;
 
 use crate::{error::KoraError, sanitize_error};
 
+pub mod sliding;
+
+pub use sliding::{InMemorySlidingWindowUsageStore, SlidingWindow, SlidingWindowUsageStore};
+
+type Clock = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+fn current_time_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
 /// Trait for storing and retrieving usage counts
 #[async_trait]
 pub trait UsageStore: Send + Sync {
@@ -15,6 +29,18 @@ pub trait UsageStore: Send + Sync {
     /// Get current usage count for a key (returns 0 if not found)
     async fn get(&self, key: &str) -> Result<u32, KoraError>;
 
+    /// Milliseconds until the oldest counted usage of a key leaves the window, `None` when
+    /// usage never expires
+    async fn retry_after_ms(&self, _key: &str) -> Result<Option<u64>, KoraError> {
+        Ok(None)
+    }
+
+    /// Drop keys whose window has expired and return how many were removed, stores expiring
+    /// keys on their own (Redis TTLs) have nothing to clean up
+    async fn cleanup_expired(&self) -> Result<usize, KoraError> {
+        Ok(0)
+    }
+
     /// Clear all usage data (mainly for testing)
     async fn clear(&self) -> Result<(), KoraError>;
 }
@@ -72,14 +98,48 @@ impl UsageStore for RedisUsageStore {
     }
 }
 
+/// Usage counted for a key since the start of its window
+struct UsageEntry {
+    window_start_ms: u64,
+    count: u32,
+}
+
 /// In-memory implementation for testing
+///
+/// Counts never reset unless a window is set, then a key starts over once `window_ms` has
+/// passed since its first usage in the window.
 pub struct InMemoryUsageStore {
-    data: Mutex<HashMap<String, u32>>,
+    data: RwLock<HashMap<String, UsageEntry>>,
+    window_ms: Option<u64>,
+    clock: Clock,
 }
 
 impl InMemoryUsageStore {
     pub fn new() -> Self {
-        Self { data: Mutex::new(HashMap::new()) }
+        Self {
+            data: RwLock::new(HashMap::new()),
+            window_ms: None,
+            clock: Arc::new(current_time_ms),
+        }
+    }
+
+    pub fn with_window(mut self, window_ms: u64) -> Self {
+        self.window_ms = Some(window_ms);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Number of keys currently held in memory
+    pub fn key_count(&self) -> usize {
+        self.data.read().map(|data| data.len()).unwrap_or_default()
+    }
+
+    fn is_expired(&self, entry: &UsageEntry, now_ms: u64) -> bool {
+        self.window_ms.is_some_and(|window_ms| now_ms >= entry.window_start_ms + window_ms)
     }
 }
 
@@ -92,29 +152,52 @@ impl Default for InMemoryUsageStore {
 #[async_trait]
 impl UsageStore for InMemoryUsageStore {
     async fn increment(&self, key: &str) -> Result<u32, KoraError> {
-        let mut data = self.data.lock().map_err(|e| {
+        let mut data = self.data.write().map_err(|e| {
             KoraError::InternalServerError(sanitize_error!(format!(
                 "Failed to lock usage store: {}",
                 e
             )))
         })?;
-        let count = data.entry(key.to_string()).or_insert(0);
-        *count += 1;
-        Ok(*count)
+        let now_ms = (self.clock)();
+        let entry =
+            data.entry(key.to_string()).or_insert(UsageEntry { window_start_ms: now_ms, count: 0 });
+        if self.is_expired(entry, now_ms) {
+            *entry = UsageEntry { window_start_ms: now_ms, count: 0 };
+        }
+        entry.count += 1;
+        Ok(entry.count)
     }
 
     async fn get(&self, key: &str) -> Result<u32, KoraError> {
-        let data = self.data.lock().map_err(|e| {
+        let data = self.data.read().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        let now_ms = (self.clock)();
+        Ok(data
+            .get(key)
+            .filter(|entry| !self.is_expired(entry, now_ms))
+            .map(|entry| entry.count)
+            .unwrap_or(0))
+    }
+
+    async fn cleanup_expired(&self) -> Result<usize, KoraError> {
+        let mut data = self.data.write().map_err(|e| {
             KoraError::InternalServerError(sanitize_error!(format!(
                 "Failed to lock usage store: {}",
                 e
             )))
         })?;
-        Ok(data.get(key).copied().unwrap_or(0))
+        let now_ms = (self.clock)();
+        let before = data.len();
+        data.retain(|_, entry| !self.is_expired(entry, now_ms));
+        Ok(before - data.len())
     }
 
     async fn clear(&self) -> Result<(), KoraError> {
-        let mut data = self.data.lock().map_err(|e| {
+        let mut data = self.data.write().map_err(|e| {
             KoraError::InternalServerError(sanitize_error!(format!(
                 "Failed to lock usage store: {}",
                 e
@@ -166,6 +249,7 @@ impl UsageStore for ErrorUsageStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
 
     #[tokio::test]
     async fn test_in_memory_usage_store() {
@@ -192,4 +276,28 @@ mod tests {
         assert_eq!(store.get("wallet1").await.unwrap(), 0);
         assert_eq!(store.get("wallet2").await.unwrap(), 0);
     }
+
+    #[tokio::test]
+    async fn test_in_memory_usage_store_window_expiry() {
+        let now = Arc::new(AtomicU64::new(0));
+        let clock = now.clone();
+        let store = InMemoryUsageStore::new()
+            .with_window(1_000)
+            .with_clock(move || clock.load(Ordering::SeqCst));
+
+        store.increment("wallet1").await.unwrap();
+        now.store(500, Ordering::SeqCst);
+        store.increment("wallet2").await.unwrap();
+        assert_eq!(store.increment("wallet1").await.unwrap(), 2);
+
+        // wallet1's window started at 0 and is over, wallet2's runs until 1_500
+        now.store(1_000, Ordering::SeqCst);
+        assert_eq!(store.get("wallet1").await.unwrap(), 0);
+        assert_eq!(store.get("wallet2").await.unwrap(), 1);
+        assert_eq!(store.cleanup_expired().await.unwrap(), 1);
+        assert_eq!(store.key_count(), 1);
+
+        // A new window starts on the next usage
+        assert_eq!(store.increment("wallet1").await.unwrap(), 1);
+    }
 }
@@ -0,0 +1,322 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use deadpool_redis::{Connection, Pool};
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use super::{current_time_ms, Clock, UsageStore};
+use crate::{error::KoraError, sanitize_error};
+
+/// Window math shared by the Redis and in-memory sliding window stores
+#[derive(Debug, Clone, Copy)]
+pub struct SlidingWindow {
+    window_ms: u64,
+    precision_ms: u64,
+}
+
+impl SlidingWindow {
+    pub fn new(window_ms: u64, precision_ms: u64) -> Self {
+        Self { window_ms, precision_ms: precision_ms.max(1) }
+    }
+
+    /// Score stored for a request made at `now_ms`, rounded up to the precision
+    fn score(&self, now_ms: u64) -> u64 {
+        now_ms.div_ceil(self.precision_ms) * self.precision_ms
+    }
+
+    /// Highest expired score at `now_ms`, everything strictly above it is still in the window
+    ///
+    /// Rounding scores up means a request is only dropped once a full `window_ms` has passed,
+    /// at worst `precision_ms` late, so requests at a window boundary can't slip through early.
+    fn expired_at(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.window_ms)
+    }
+
+    /// Expire idle keys once every entry has left the window
+    fn key_ttl_ms(&self) -> i64 {
+        (self.window_ms + self.precision_ms) as i64
+    }
+
+    /// Time left at `now_ms` until the entry scored `oldest_score` is expired, at least 1ms
+    fn retry_after_ms(&self, oldest_score: u64, now_ms: u64) -> u64 {
+        (oldest_score + self.window_ms).saturating_sub(now_ms).max(1)
+    }
+}
+
+/// Redis sorted set implementation, one member per request scored by its timestamp in ms
+pub struct SlidingWindowUsageStore {
+    pool: Pool,
+    window: SlidingWindow,
+    clock: Clock,
+}
+
+impl SlidingWindowUsageStore {
+    pub fn new(pool: Pool, window: SlidingWindow) -> Self {
+        Self { pool, window, clock: Arc::new(current_time_ms) }
+    }
+
+    async fn get_connection(&self) -> Result<Connection, KoraError> {
+        self.pool.get().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get Redis connection: {}",
+                e
+            )))
+        })
+    }
+
+    /// Drop expired entries, then add the request and count what is left, in a single round trip
+    fn increment_pipeline(&self, key: &str, now_ms: u64, member: &str) -> redis::Pipeline {
+        let expired_at = self.window.expired_at(now_ms);
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .zrembyscore(key, "-inf", expired_at)
+            .ignore()
+            .zadd(key, member, self.window.score(now_ms))
+            .ignore()
+            .zcount(key, format!("({expired_at}"), "+inf")
+            .pexpire(key, self.window.key_ttl_ms())
+            .ignore();
+        pipe
+    }
+
+    fn get_pipeline(&self, key: &str, now_ms: u64) -> redis::Pipeline {
+        let expired_at = self.window.expired_at(now_ms);
+        let mut pipe = redis::pipe();
+        pipe.atomic().zrembyscore(key, "-inf", expired_at).ignore().zcount(
+            key,
+            format!("({expired_at}"),
+            "+inf",
+        );
+        pipe
+    }
+
+    /// Drop expired entries, then read the oldest one left with its score
+    fn oldest_pipeline(&self, key: &str, now_ms: u64) -> redis::Pipeline {
+        let expired_at = self.window.expired_at(now_ms);
+        let mut pipe = redis::pipe();
+        pipe.atomic().zrembyscore(key, "-inf", expired_at).ignore().zrange_withscores(key, 0, 0);
+        pipe
+    }
+}
+
+#[async_trait]
+impl UsageStore for SlidingWindowUsageStore {
+    async fn increment(&self, key: &str) -> Result<u32, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let member = Uuid::new_v4().to_string();
+        let (count,): (u32,) = self
+            .increment_pipeline(key, (self.clock)(), &member)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to increment usage for {}: {}",
+                    key, e
+                )))
+            })?;
+        Ok(count)
+    }
+
+    async fn get(&self, key: &str) -> Result<u32, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let (count,): (u32,) =
+            self.get_pipeline(key, (self.clock)()).query_async(&mut conn).await.map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to get usage for {}: {}",
+                    key, e
+                )))
+            })?;
+        Ok(count)
+    }
+
+    async fn retry_after_ms(&self, key: &str) -> Result<Option<u64>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let now_ms = (self.clock)();
+        let (oldest,): (Vec<(String, u64)>,) =
+            self.oldest_pipeline(key, now_ms).query_async(&mut conn).await.map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to get usage window for {}: {}",
+                    key, e
+                )))
+            })?;
+        Ok(oldest.first().map(|(_, score)| self.window.retry_after_ms(*score, now_ms)))
+    }
+
+    async fn clear(&self) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let _: () = conn.flushdb().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!("Failed to clear Redis: {}", e)))
+        })?;
+        Ok(())
+    }
+}
+
+/// In-memory implementation for testing, mirrors the Redis pipelines with a settable clock
+pub struct InMemorySlidingWindowUsageStore {
+    data: Mutex<HashMap<String, Vec<u64>>>,
+    window: SlidingWindow,
+    clock: Clock,
+}
+
+impl InMemorySlidingWindowUsageStore {
+    pub fn new(window: SlidingWindow) -> Self {
+        Self { data: Mutex::new(HashMap::new()), window, clock: Arc::new(current_time_ms) }
+    }
+
+    pub fn with_clock(mut self, clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    fn count_in_window(&self, scores: &mut Vec<u64>) -> u32 {
+        let expired_at = self.window.expired_at((self.clock)());
+        scores.retain(|score| *score > expired_at);
+        scores.len() as u32
+    }
+}
+
+#[async_trait]
+impl UsageStore for InMemorySlidingWindowUsageStore {
+    async fn increment(&self, key: &str) -> Result<u32, KoraError> {
+        let mut data = self.data.lock().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        let scores = data.entry(key.to_string()).or_default();
+        scores.push(self.window.score((self.clock)()));
+        Ok(self.count_in_window(scores))
+    }
+
+    async fn get(&self, key: &str) -> Result<u32, KoraError> {
+        let mut data = self.data.lock().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        Ok(data.get_mut(key).map(|scores| self.count_in_window(scores)).unwrap_or(0))
+    }
+
+    async fn retry_after_ms(&self, key: &str) -> Result<Option<u64>, KoraError> {
+        let mut data = self.data.lock().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        let Some(scores) = data.get_mut(key) else {
+            return Ok(None);
+        };
+        self.count_in_window(scores);
+        let now_ms = (self.clock)();
+        Ok(scores.iter().min().map(|oldest| self.window.retry_after_ms(*oldest, now_ms)))
+    }
+
+    async fn cleanup_expired(&self) -> Result<usize, KoraError> {
+        let mut data = self.data.lock().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        let before = data.len();
+        data.retain(|_, scores| self.count_in_window(scores) > 0);
+        Ok(before - data.len())
+    }
+
+    async fn clear(&self) -> Result<(), KoraError> {
+        let mut data = self.data.lock().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        data.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn command_args(pipe: &redis::Pipeline) -> Vec<Vec<String>> {
+        pipe.cmd_iter()
+            .map(|cmd| {
+                cmd.args_iter()
+                    .map(|arg| match arg {
+                        redis::Arg::Simple(bytes) => String::from_utf8_lossy(bytes).to_string(),
+                        redis::Arg::Cursor => "<cursor>".to_string(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_increment_pipeline_commands() {
+        let pool = deadpool_redis::Config::from_url("redis://localhost:6379")
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap();
+        let store = SlidingWindowUsageStore::new(pool, SlidingWindow::new(60_000, 1_000));
+
+        let args = command_args(&store.increment_pipeline("wallet", 125_500, "req-1"));
+
+        // Scores are rounded up to the second, the window covers (65_500, 125_500]
+        let expected: Vec<Vec<String>> = [
+            vec!["ZREMRANGEBYSCORE", "wallet", "-inf", "65500"],
+            vec!["ZADD", "wallet", "126000", "req-1"],
+            vec!["ZCOUNT", "wallet", "(65500", "+inf"],
+            vec!["PEXPIRE", "wallet", "61000"],
+        ]
+        .into_iter()
+        .map(|cmd| cmd.into_iter().map(String::from).collect())
+        .collect();
+        assert_eq!(args, expected);
+    }
+
+    #[tokio::test]
+    async fn test_sliding_window_expires_old_requests() {
+        let now = Arc::new(AtomicU64::new(10_000));
+        let clock = now.clone();
+        let store = InMemorySlidingWindowUsageStore::new(SlidingWindow::new(1_000, 100))
+            .with_clock(move || clock.load(Ordering::SeqCst));
+
+        assert_eq!(store.increment("wallet").await.unwrap(), 1);
+        now.store(10_500, Ordering::SeqCst);
+        assert_eq!(store.increment("wallet").await.unwrap(), 2);
+
+        // First request leaves the window, second one is still in it
+        now.store(11_000, Ordering::SeqCst);
+        assert_eq!(store.get("wallet").await.unwrap(), 1);
+
+        now.store(11_500, Ordering::SeqCst);
+        assert_eq!(store.get("wallet").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sliding_window_retry_after() {
+        let now = Arc::new(AtomicU64::new(10_050));
+        let clock = now.clone();
+        let store = InMemorySlidingWindowUsageStore::new(SlidingWindow::new(1_000, 100))
+            .with_clock(move || clock.load(Ordering::SeqCst));
+
+        assert_eq!(store.retry_after_ms("wallet").await.unwrap(), None);
+
+        // Scored 10_100, expired once the clock reaches 11_100
+        store.increment("wallet").await.unwrap();
+        now.store(10_600, Ordering::SeqCst);
+        store.increment("wallet").await.unwrap();
+        assert_eq!(store.retry_after_ms("wallet").await.unwrap(), Some(500));
+
+        now.store(11_100, Ordering::SeqCst);
+        assert_eq!(store.retry_after_ms("wallet").await.unwrap(), Some(500));
+    }
+}
@@ -1,12 +1,16 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, fmt, future::Future, sync::Arc, time::Duration};
 
 use deadpool_redis::Runtime;
 use redis::AsyncCommands;
 use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
-use tokio::sync::OnceCell;
+use tokio::{
+    sync::OnceCell,
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
 
-use super::usage_store::{RedisUsageStore, UsageStore};
-use crate::{error::KoraError, sanitize_error, state::get_signer_pool};
+use super::usage_store::{RedisUsageStore, SlidingWindow, SlidingWindowUsageStore, UsageStore};
+use crate::{config::WindowType, error::KoraError, sanitize_error, state::get_signer_pool};
 
 #[cfg(not(test))]
 use crate::state::get_config;
@@ -19,6 +23,34 @@ const USAGE_CACHE_KEY: &str = "kora:usage_limit";
 /// Global usage limiter instance
 static USAGE_LIMITER: OnceCell<Option<UsageTracker>> = OnceCell::const_new();
 
+tokio::task_local! {
+    /// JWT subject of the request being handled, see `UsageTracker::with_caller`
+    static REQUEST_CALLER: String;
+}
+
+/// Who a transaction counts against: the authenticated caller of the request, or without one
+/// the transaction's first signer that isn't a Kora signer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsageSubject {
+    Caller(String),
+    Wallet(Pubkey),
+}
+
+impl From<&Pubkey> for UsageSubject {
+    fn from(wallet: &Pubkey) -> Self {
+        Self::Wallet(*wallet)
+    }
+}
+
+impl fmt::Display for UsageSubject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Caller(caller) => write!(f, "Caller {caller}"),
+            Self::Wallet(wallet) => write!(f, "Wallet {wallet}"),
+        }
+    }
+}
+
 pub struct UsageTracker {
     store: Arc<dyn UsageStore>,
     max_transactions: u64,
@@ -36,8 +68,11 @@ impl UsageTracker {
         Self { store, max_transactions, kora_signers, fallback_if_unavailable }
     }
 
-    fn get_usage_key(&self, wallet: &Pubkey) -> String {
-        format!("{USAGE_CACHE_KEY}:{wallet}")
+    fn get_usage_key(&self, subject: &UsageSubject) -> String {
+        match subject {
+            UsageSubject::Caller(caller) => format!("{USAGE_CACHE_KEY}:caller:{caller}"),
+            UsageSubject::Wallet(wallet) => format!("{USAGE_CACHE_KEY}:{wallet}"),
+        }
     }
 
     /// Handle store errors according to fallback configuration
@@ -45,9 +80,9 @@ impl UsageTracker {
         &self,
         error: KoraError,
         operation: &str,
-        wallet: &Pubkey,
+        subject: &UsageSubject,
     ) -> Result<(), KoraError> {
-        log::error!("Failed to {operation} for {wallet}: {error}");
+        log::error!("Failed to {operation} for {subject}: {error}");
         if self.fallback_if_unavailable {
             log::error!("Fallback enabled - allowing transaction due to store error");
             Ok(()) // Allow transaction when fallback is enabled
@@ -58,26 +93,31 @@ impl UsageTracker {
         }
     }
 
-    async fn check_usage_limit(&self, wallet: &Pubkey) -> Result<(), KoraError> {
+    async fn check_usage_limit(&self, subject: impl Into<UsageSubject>) -> Result<(), KoraError> {
         // Skip check if unlimited (0)
         if self.max_transactions == 0 {
             return Ok(());
         }
 
         // Check current count first, then increment only if allowed
-        let key = self.get_usage_key(wallet);
+        let subject = subject.into();
+        let key = self.get_usage_key(&subject);
 
         // Handle store.get() errors using helper
         let current_count = match self.store.get(&key).await {
             Ok(count) => count,
             Err(e) => {
-                return self.handle_store_error(e, "get usage count", wallet);
+                return self.handle_store_error(e, "get usage count", &subject);
             }
         };
 
         if current_count >= self.max_transactions as u32 {
+            // Windowed stores tell when the oldest transaction frees up, fixed counts never reset
+            if let Ok(Some(retry_after_ms)) = self.store.retry_after_ms(&key).await {
+                return Err(KoraError::RateLimited { retry_after_ms });
+            }
             return Err(KoraError::UsageLimitExceeded(format!(
-                "Wallet {wallet} exceeded limit: {}/{}",
+                "{subject} exceeded limit: {}/{}",
                 current_count + 1,
                 self.max_transactions
             )));
@@ -87,15 +127,58 @@ impl UsageTracker {
         let new_count = match self.store.increment(&key).await {
             Ok(count) => count,
             Err(e) => {
-                return self.handle_store_error(e, "increment usage count", wallet);
+                return self.handle_store_error(e, "increment usage count", &subject);
             }
         };
 
-        log::debug!("Usage check passed for {wallet}: {new_count}/{}", self.max_transactions);
+        log::debug!("Usage check passed for {subject}: {new_count}/{}", self.max_transactions);
 
         Ok(())
     }
 
+    /// Count `transaction` against the caller of the request, or its sender without one
+    async fn check_transaction(&self, transaction: &VersionedTransaction) -> Result<(), KoraError> {
+        if let Some(caller) = Self::current_caller() {
+            return self.check_usage_limit(UsageSubject::Caller(caller)).await;
+        }
+        if let Some(sender) = self.extract_transaction_sender(transaction)? {
+            self.check_usage_limit(&sender).await?;
+        }
+        Ok(())
+    }
+
+    /// Run `future` with `caller` as the authenticated caller that transactions are counted
+    /// against, the request is handled within the future
+    pub async fn with_caller<F: Future>(caller: Option<String>, future: F) -> F::Output {
+        match caller {
+            Some(caller) => REQUEST_CALLER.scope(caller, future).await,
+            None => future.await,
+        }
+    }
+
+    /// Authenticated caller of the request being handled, if any
+    pub fn current_caller() -> Option<String> {
+        REQUEST_CALLER.try_with(|caller| caller.clone()).ok()
+    }
+
+    /// Drop expired entries from the store every `period` so callers that went idle don't
+    /// pile up in memory
+    pub fn spawn_cleanup_task(&self, period: Duration) -> JoinHandle<()> {
+        let store = self.store.clone();
+        tokio::spawn(async move {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match store.cleanup_expired().await {
+                    Ok(0) => {}
+                    Ok(removed) => log::debug!("Usage limiter removed {removed} expired entries"),
+                    Err(e) => log::warn!("Usage limiter cleanup failed: {e}"),
+                }
+            }
+        })
+    }
+
     fn get_usage_limiter() -> Result<Option<&'static UsageTracker>, KoraError> {
         match USAGE_LIMITER.get() {
             Some(limiter) => Ok(limiter.as_ref()),
@@ -173,10 +256,27 @@ impl UsageTracker {
                 ))
             })?;
 
-            log::info!(
-                "Usage limiter initialized with max {} transactions",
-                config.kora.usage_limit.max_transactions
-            );
+            let usage_config = &config.kora.usage_limit;
+            let store: Arc<dyn UsageStore> = match usage_config.window_type {
+                WindowType::Fixed => {
+                    log::info!(
+                        "Usage limiter initialized with max {} transactions",
+                        usage_config.max_transactions
+                    );
+                    Arc::new(RedisUsageStore::new(pool))
+                }
+                WindowType::Sliding { precision_ms } => {
+                    log::info!(
+                        "Usage limiter initialized with max {} transactions per {}s sliding window",
+                        usage_config.max_transactions,
+                        usage_config.window_seconds
+                    );
+                    Arc::new(SlidingWindowUsageStore::new(
+                        pool,
+                        SlidingWindow::new(usage_config.window_seconds * 1000, precision_ms),
+                    ))
+                }
+            };
 
             let kora_signers = get_signer_pool()?
                 .get_signers_info()
@@ -184,13 +284,17 @@ impl UsageTracker {
                 .filter_map(|info| info.public_key.parse().ok())
                 .collect();
 
-            let store = Arc::new(RedisUsageStore::new(pool));
-            Some(UsageTracker::new(
+            let tracker = UsageTracker::new(
                 store,
-                config.kora.usage_limit.max_transactions,
+                usage_config.max_transactions,
                 kora_signers,
-                config.kora.usage_limit.fallback_if_unavailable,
-            ))
+                usage_config.fallback_if_unavailable,
+            );
+            if usage_config.cleanup_interval_seconds > 0 {
+                tracker
+                    .spawn_cleanup_task(Duration::from_secs(usage_config.cleanup_interval_seconds));
+            }
+            Some(tracker)
         } else {
             log::info!("Usage limiting enabled but no cache_url configured - disabled");
             None
@@ -210,11 +314,7 @@ impl UsageTracker {
         let config = get_config()?;
 
         if let Some(limiter) = Self::get_usage_limiter()? {
-            let sender = limiter.extract_transaction_sender(transaction)?;
-            if let Some(sender) = sender {
-                limiter.check_usage_limit(&sender).await?;
-            }
-            Ok(())
+            limiter.check_transaction(transaction).await
         } else if config.kora.usage_limit.enabled
             && !config.kora.usage_limit.fallback_if_unavailable
         {
@@ -234,8 +334,11 @@ mod tests {
     use super::*;
     use crate::{
         tests::{config_mock::ConfigMockBuilder, transaction_mock::create_mock_transaction},
-        usage_limit::{usage_store::ErrorUsageStore, InMemoryUsageStore},
+        usage_limit::{
+            usage_store::ErrorUsageStore, InMemorySlidingWindowUsageStore, InMemoryUsageStore,
+        },
     };
+    use std::sync::atomic::{AtomicU64, Ordering};
 
     #[tokio::test]
     async fn test_get_usage_key_format() {
@@ -285,6 +388,100 @@ mod tests {
         assert!(tracker.check_usage_limit(&wallet2).await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_authenticated_callers_have_separate_limits() {
+        let store = Arc::new(InMemoryUsageStore::new());
+        let tracker = UsageTracker::new(store, 2, HashSet::new(), false);
+
+        // Every caller sends the same transaction, from the same wallet
+        let transaction = create_mock_transaction();
+        let check = |caller: Option<&str>| {
+            UsageTracker::with_caller(
+                caller.map(str::to_string),
+                tracker.check_transaction(&transaction),
+            )
+        };
+
+        assert!(check(Some("user-1")).await.is_ok());
+        assert!(check(Some("user-1")).await.is_ok());
+        let result = check(Some("user-1")).await;
+        assert!(result.unwrap_err().to_string().contains("Caller user-1 exceeded limit"));
+
+        // user-2 has a budget of its own
+        assert!(check(Some("user-2")).await.is_ok());
+        assert!(check(Some("user-2")).await.is_ok());
+        assert!(check(Some("user-2")).await.is_err());
+
+        // Without a caller the transaction counts against its sender wallet
+        assert!(check(None).await.is_ok());
+        assert_eq!(UsageTracker::current_caller(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sliding_window_no_bypass_at_window_boundary() {
+        let now = Arc::new(AtomicU64::new(950));
+        let clock = now.clone();
+        let store = Arc::new(
+            InMemorySlidingWindowUsageStore::new(SlidingWindow::new(1_000, 100))
+                .with_clock(move || clock.load(Ordering::SeqCst)),
+        );
+        let tracker = UsageTracker::new(store, 3, HashSet::new(), false);
+        let wallet = Pubkey::new_unique();
+
+        // Burst right before the boundary of a 1s fixed window
+        for _ in 0..3 {
+            assert!(tracker.check_usage_limit(&wallet).await.is_ok());
+        }
+
+        // A fixed window would reset here and allow another burst, the sliding window doesn't
+        // and tells when the burst (scored 1_000) leaves it
+        for (time_ms, retry_after_ms) in [(1_000, 1_000), (1_500, 500), (1_999, 1)] {
+            now.store(time_ms, Ordering::SeqCst);
+            let result = tracker.check_usage_limit(&wallet).await;
+            assert_eq!(result.unwrap_err(), KoraError::RateLimited { retry_after_ms });
+        }
+
+        // Rejected attempts are not counted, the burst frees up once a full window has passed
+        // since the burst, rounded up to the 100ms precision
+        now.store(2_000, Ordering::SeqCst);
+        for _ in 0..3 {
+            assert!(tracker.check_usage_limit(&wallet).await.is_ok());
+        }
+        assert!(tracker.check_usage_limit(&wallet).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_task_bounds_memory_across_windows() {
+        let now = Arc::new(AtomicU64::new(0));
+        let clock = now.clone();
+        let store = Arc::new(
+            InMemoryUsageStore::new()
+                .with_window(1_000)
+                .with_clock(move || clock.load(Ordering::SeqCst)),
+        );
+        let tracker = UsageTracker::new(store.clone(), 1, HashSet::new(), false);
+        let cleanup = tracker.spawn_cleanup_task(Duration::from_millis(10));
+
+        for window in 0..5 {
+            now.store(window * 1_000, Ordering::SeqCst);
+            for _ in 0..1_000 {
+                assert!(tracker.check_usage_limit(&Pubkey::new_unique()).await.is_ok());
+            }
+            assert_eq!(store.key_count(), 1_000);
+
+            // Once the window is over the cleanup task drops every caller of it
+            now.store(window * 1_000 + 1_000, Ordering::SeqCst);
+            let mut waited = 0;
+            while store.key_count() > 0 {
+                assert!(waited < 100, "expired entries were not cleaned up");
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                waited += 1;
+            }
+        }
+
+        cleanup.abort();
+    }
+
     #[tokio::test]
     async fn test_unlimited_usage() {
         let store = Arc::new(InMemoryUsageStore::new());
@@ -1,8 +1,10 @@
+use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_program_pack::Pack;
 use solana_sdk::{account::Account, pubkey::Pubkey};
 use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
 use spl_token_2022_interface::{
+    extension::AccountType as Token2022AccountType,
     state::{Account as Token2022Account, Mint as Token2022Mint},
     ID as TOKEN_2022_PROGRAM_ID,
 };
@@ -11,17 +13,58 @@ use spl_token_interface::{
     ID as SPL_TOKEN_PROGRAM_ID,
 };
 
-use crate::{CacheUtil, KoraError};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, PartialEq)]
+use crate::{token::token::TokenType, CacheUtil, KoraError};
+
+/// Metaplex Token Metadata program
+pub const METAPLEX_METADATA_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// `Key::MetadataV1`, first byte of every Metaplex metadata account
+const METADATA_V1_KEY: u8 = 4;
+
+/// Key (1 byte), update authority and mint (32 bytes each)
+const METADATA_MIN_LEN: usize = 1 + 32 + 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum AccountType {
     Mint,
     TokenAccount,
     System,
     Program,
+    NftMetadata,
 }
 
 impl AccountType {
+    /// Classify an on-chain account, `None` for data accounts owned by any other program
+    pub fn detect(account: &Account, account_pubkey: &Pubkey) -> Option<AccountType> {
+        let candidate = if account.executable {
+            AccountType::Program
+        } else if account.owner == SYSTEM_PROGRAM_ID {
+            AccountType::System
+        } else if account.owner == SPL_TOKEN_PROGRAM_ID || account.owner == TOKEN_2022_PROGRAM_ID {
+            match account.data.len() {
+                Mint::LEN => AccountType::Mint,
+                SplTokenAccount::LEN => AccountType::TokenAccount,
+                // Token2022 accounts with extensions store their type right after the base account
+                len if len > SplTokenAccount::LEN => match account.data[SplTokenAccount::LEN] {
+                    t if t == Token2022AccountType::Mint as u8 => AccountType::Mint,
+                    t if t == Token2022AccountType::Account as u8 => AccountType::TokenAccount,
+                    _ => return None,
+                },
+                _ => return None,
+            }
+        } else if account.owner == METAPLEX_METADATA_PROGRAM_ID {
+            AccountType::NftMetadata
+        } else {
+            return None;
+        };
+
+        candidate.validate_account_type(account, account_pubkey).ok().map(|_| candidate)
+    }
+
     pub fn validate_account_type(
         self,
         account: &Account,
@@ -107,6 +150,16 @@ impl AccountType {
             AccountType::Program => {
                 should_be_executable = Some(true);
             }
+            AccountType::NftMetadata => {
+                should_be_executable = Some(false);
+                should_be_owned_by = Some(METAPLEX_METADATA_PROGRAM_ID);
+
+                if account.data.len() < METADATA_MIN_LEN || account.data[0] != METADATA_V1_KEY {
+                    return Err(KoraError::InternalServerError(format!(
+                        "Account {account_pubkey} has invalid data for a NftMetadata account"
+                    )));
+                }
+            }
         }
 
         if let Some(should_be_executable) = should_be_executable {
@@ -145,12 +198,58 @@ pub async fn validate_account(
     Ok(())
 }
 
+/// Reject a token account frozen by its mint's freeze authority, transfers out of it fail on-chain.
+/// Accounts that aren't token accounts are left to the other checks.
+pub fn validate_token_account_not_frozen_state(
+    account: &Account,
+    account_pubkey: &Pubkey,
+) -> Result<(), KoraError> {
+    let is_frozen = TokenType::get_token_program_from_owner(&account.owner)
+        .ok()
+        .and_then(|program| program.unpack_token_account(&account.data).ok())
+        .is_some_and(|token_account| token_account.is_frozen());
+
+    if is_frozen {
+        log::debug!("Token account {account_pubkey} is frozen");
+        return Err(KoraError::ValidationError("Token account is frozen".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Fetch a token account bypassing the cache, the freeze authority can freeze it at any time,
+/// and reject it if it is frozen
+pub async fn validate_token_account_not_frozen(
+    rpc_client: &RpcClient,
+    account_pubkey: &Pubkey,
+) -> Result<(), KoraError> {
+    let account = CacheUtil::get_account(rpc_client, account_pubkey, true).await?;
+    validate_token_account_not_frozen_state(&account, account_pubkey)
+}
+
+/// Validate a Metaplex metadata account, rejecting metadata the fee payer is the update authority of
+pub fn validate_nft_metadata(
+    account: &Account,
+    account_pubkey: &Pubkey,
+    fee_payer: &Pubkey,
+) -> Result<(), KoraError> {
+    AccountType::NftMetadata.validate_account_type(account, account_pubkey)?;
+
+    if account.data[1..33] == fee_payer.to_bytes() {
+        return Err(KoraError::InvalidTransaction(format!(
+            "Fee payer {fee_payer} is the update authority of metadata account {account_pubkey}"
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tests::{
         account_mock::{
-            create_mock_account, create_mock_account_with_owner,
+            create_mock_account, create_mock_account_with_owner, create_mock_metadata_account,
             create_mock_non_executable_account, create_mock_program_account,
             create_mock_spl_mint_account, create_mock_token2022_mint_account,
             create_mock_token_account, AccountMockBuilder,
@@ -159,6 +258,7 @@ mod tests {
         config_mock::ConfigMockBuilder,
         rpc_mock::{create_mock_rpc_client_account_not_found, create_mock_rpc_client_with_account},
     };
+    use spl_token_2022_interface::extension::ExtensionType;
 
     #[test]
     fn test_account_type_validate_spl_mint_success() {
@@ -408,4 +508,139 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("is not owned by"));
     }
+
+    #[tokio::test]
+    async fn test_validate_token_account_not_frozen() {
+        let _m = ConfigMockBuilder::new().with_cache_enabled(false).build_and_setup();
+        let account_pubkey = Pubkey::new_unique();
+        let frozen = KoraError::ValidationError("Token account is frozen".to_string());
+
+        let builder = |is_frozen: bool| {
+            let builder = TokenAccountMockBuilder::new();
+            if is_frozen {
+                builder.with_frozen()
+            } else {
+                builder
+            }
+        };
+
+        for is_frozen in [false, true] {
+            for account in [
+                builder(is_frozen).build(),
+                builder(is_frozen).build_token2022(),
+                builder(is_frozen)
+                    .with_extension(ExtensionType::ImmutableOwner)
+                    .build_token2022_with_extensions()
+                    .unwrap(),
+            ] {
+                let rpc_client = create_mock_rpc_client_with_account(&account);
+                let result = validate_token_account_not_frozen(&rpc_client, &account_pubkey).await;
+                if is_frozen {
+                    assert_eq!(result.unwrap_err(), frozen);
+                } else {
+                    assert!(result.is_ok());
+                }
+            }
+        }
+
+        // Not a token account, left to the other checks
+        assert!(validate_token_account_not_frozen_state(&create_mock_account(), &account_pubkey)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_account_type_detect() {
+        let account_pubkey = Pubkey::new_unique();
+        let detect = |account: &Account| AccountType::detect(account, &account_pubkey);
+
+        assert_eq!(detect(&create_mock_spl_mint_account(6)), Some(AccountType::Mint));
+        assert_eq!(detect(&create_mock_token2022_mint_account(9)), Some(AccountType::Mint));
+        assert_eq!(
+            detect(&create_mock_token_account(&Pubkey::new_unique(), &Pubkey::new_unique())),
+            Some(AccountType::TokenAccount)
+        );
+        assert_eq!(
+            detect(&TokenAccountMockBuilder::new().build_token2022()),
+            Some(AccountType::TokenAccount)
+        );
+        assert_eq!(
+            detect(&create_mock_account_with_owner(SYSTEM_PROGRAM_ID)),
+            Some(AccountType::System)
+        );
+        assert_eq!(detect(&create_mock_program_account()), Some(AccountType::Program));
+
+        // Data account owned by an arbitrary program
+        assert_eq!(detect(&create_mock_non_executable_account()), None);
+    }
+
+    #[test]
+    fn test_account_type_detect_token2022_with_extensions() {
+        let account_pubkey = Pubkey::new_unique();
+
+        let mint = MintAccountMockBuilder::new()
+            .with_extension(spl_token_2022_interface::extension::ExtensionType::MintCloseAuthority)
+            .build_token2022();
+        assert!(mint.data.len() > SplTokenAccount::LEN);
+        assert_eq!(AccountType::detect(&mint, &account_pubkey), Some(AccountType::Mint));
+
+        let token_account = TokenAccountMockBuilder::new()
+            .with_extension(spl_token_2022_interface::extension::ExtensionType::ImmutableOwner)
+            .build_token2022();
+        assert!(token_account.data.len() > SplTokenAccount::LEN);
+        assert_eq!(
+            AccountType::detect(&token_account, &account_pubkey),
+            Some(AccountType::TokenAccount)
+        );
+    }
+
+    #[test]
+    fn test_account_type_validate_nft_metadata() {
+        let account_pubkey = Pubkey::new_unique();
+        let metadata = create_mock_metadata_account(&Pubkey::new_unique());
+
+        assert!(AccountType::NftMetadata.validate_account_type(&metadata, &account_pubkey).is_ok());
+        assert_eq!(AccountType::detect(&metadata, &account_pubkey), Some(AccountType::NftMetadata));
+
+        let mut wrong_key = metadata.clone();
+        wrong_key.data[0] = 6; // Key::MasterEditionV2
+        let result = AccountType::NftMetadata.validate_account_type(&wrong_key, &account_pubkey);
+        assert!(result.unwrap_err().to_string().contains("invalid data for a NftMetadata"));
+        assert_eq!(AccountType::detect(&wrong_key, &account_pubkey), None);
+
+        let mut wrong_owner = metadata;
+        wrong_owner.owner = Pubkey::new_unique();
+        let result = AccountType::NftMetadata.validate_account_type(&wrong_owner, &account_pubkey);
+        assert!(result.unwrap_err().to_string().contains("is not owned by"));
+    }
+
+    #[test]
+    fn test_validate_nft_metadata_update_authority() {
+        let account_pubkey = Pubkey::new_unique();
+        let fee_payer = Pubkey::new_unique();
+
+        let metadata = create_mock_metadata_account(&Pubkey::new_unique());
+        assert!(validate_nft_metadata(&metadata, &account_pubkey, &fee_payer).is_ok());
+
+        let metadata = create_mock_metadata_account(&fee_payer);
+        let result = validate_nft_metadata(&metadata, &account_pubkey, &fee_payer);
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn test_account_type_serde() {
+        let parsed: Vec<AccountType> = serde_json::from_str(
+            r#"["mint", "token_account", "system", "program", "nft_metadata"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                AccountType::Mint,
+                AccountType::TokenAccount,
+                AccountType::System,
+                AccountType::Program,
+                AccountType::NftMetadata
+            ]
+        );
+    }
 }
@@ -0,0 +1,338 @@
+use crate::error::KoraError;
+use futures::future::try_join_all;
+use parking_lot::RwLock;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcBlockConfig};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_transaction_status_client_types::TransactionDetails;
+use std::{collections::HashMap, str::FromStr, sync::LazyLock};
+
+use crate::transaction::VersionedTransactionResolved;
+
+/// `SystemInstruction::AdvanceNonceAccount`, bincode encoded with a u32 tag
+const SYSTEM_ADVANCE_NONCE_ACCOUNT: u32 = 4;
+
+/// Slots of the blockhashes produced recently, shared by every validation
+pub static RECENT_BLOCKHASHES: LazyLock<RecentBlockhashes> = LazyLock::new(RecentBlockhashes::new);
+
+#[derive(Default)]
+struct RecentBlockhashesState {
+    slots: HashMap<Hash, u64>,
+    /// Highest slot whose block has been looked at
+    scanned_up_to: u64,
+}
+
+/// RPC nodes can't tell the slot of an arbitrary blockhash, so the blocks of the last slots are
+/// fetched and their blockhashes remembered. Each check only fetches the blocks produced since
+/// the previous one.
+#[derive(Default)]
+pub struct RecentBlockhashes {
+    state: RwLock<RecentBlockhashesState>,
+}
+
+impl RecentBlockhashes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Slot of `blockhash` if it was produced in the `max_age_slots` slots before `current_slot`
+    pub async fn get_slot(
+        &self,
+        rpc_client: &RpcClient,
+        blockhash: &Hash,
+        current_slot: u64,
+        max_age_slots: u64,
+    ) -> Result<Option<u64>, KoraError> {
+        let oldest_slot = current_slot.saturating_sub(max_age_slots);
+
+        let first_unscanned_slot = {
+            let state = self.state.read();
+            if let Some(slot) = state.slots.get(blockhash) {
+                return Ok(Some(*slot).filter(|slot| *slot >= oldest_slot));
+            }
+            oldest_slot.max(state.scanned_up_to + 1)
+        };
+
+        if first_unscanned_slot <= current_slot {
+            let blockhashes =
+                fetch_blockhashes(rpc_client, first_unscanned_slot, current_slot).await?;
+
+            let mut state = self.state.write();
+            state.slots.extend(blockhashes);
+            state.scanned_up_to = state.scanned_up_to.max(current_slot);
+            state.slots.retain(|_, slot| *slot >= oldest_slot);
+        }
+
+        Ok(self.state.read().slots.get(blockhash).copied().filter(|slot| *slot >= oldest_slot))
+    }
+}
+
+async fn fetch_blockhashes(
+    rpc_client: &RpcClient,
+    start_slot: u64,
+    end_slot: u64,
+) -> Result<Vec<(Hash, u64)>, KoraError> {
+    // Skipped slots have no block
+    let slots = rpc_client
+        .get_blocks_with_commitment(start_slot, Some(end_slot), CommitmentConfig::confirmed())
+        .await?;
+
+    let config = RpcBlockConfig {
+        transaction_details: Some(TransactionDetails::None),
+        rewards: Some(false),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+        ..RpcBlockConfig::default()
+    };
+
+    try_join_all(slots.into_iter().map(|slot| async move {
+        let block = rpc_client.get_block_with_config(slot, config).await?;
+        let blockhash = Hash::from_str(&block.blockhash)
+            .map_err(|e| KoraError::RpcError(format!("Invalid blockhash of block {slot}: {e}")))?;
+        Ok::<_, KoraError>((blockhash, slot))
+    }))
+    .await
+}
+
+/// Reject transactions whose blockhash is older than `max_age_slots`, narrowing the window in
+/// which a captured signed transaction can be replayed.
+///
+/// Durable nonce transactions are not checked, their blockhash is the nonce. Neither are
+/// transactions without signatures, Kora sets a fresh blockhash on those before signing.
+pub struct BlockhashAgeValidator<'a> {
+    pub max_age_slots: u64,
+    pub recent_blockhashes: &'a RecentBlockhashes,
+}
+
+impl BlockhashAgeValidator<'_> {
+    pub async fn validate(
+        &self,
+        transaction_resolved: &VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+    ) -> Result<(), KoraError> {
+        let transaction = &transaction_resolved.transaction;
+        if transaction.signatures.is_empty() || is_durable_nonce_transaction(transaction_resolved) {
+            return Ok(());
+        }
+
+        let blockhash = transaction.message.recent_blockhash();
+        let current_slot =
+            rpc_client.get_slot_with_commitment(CommitmentConfig::confirmed()).await?;
+
+        match self
+            .recent_blockhashes
+            .get_slot(rpc_client, blockhash, current_slot, self.max_age_slots)
+            .await?
+        {
+            Some(_) => Ok(()),
+            None => Err(KoraError::InvalidTransaction(format!(
+                "Blockhash {blockhash} is not from the last {} slots",
+                self.max_age_slots
+            ))),
+        }
+    }
+}
+
+fn is_durable_nonce_transaction(transaction_resolved: &VersionedTransactionResolved) -> bool {
+    let message = &transaction_resolved.transaction.message;
+    message.instructions().first().is_some_and(|instruction| {
+        message.static_account_keys().get(instruction.program_id_index as usize)
+            == Some(&solana_system_interface::program::ID)
+            && instruction.data.get(..4)
+                == Some(SYSTEM_ADVANCE_NONCE_ACCOUNT.to_le_bytes().as_slice())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use solana_client::{
+        client_error::Result as ClientResult,
+        rpc_client::RpcClientConfig,
+        rpc_request::RpcRequest,
+        rpc_sender::{RpcSender, RpcTransportStats},
+    };
+    use solana_message::{Message, VersionedMessage};
+    use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+    use solana_system_interface::instruction::{advance_nonce_account, transfer};
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    /// Chain where every slot except the skipped ones has a block with its own blockhash
+    struct ChainSender {
+        current_slot: Arc<AtomicU64>,
+        skipped_slots: Vec<u64>,
+        block_requests: Arc<AtomicU64>,
+    }
+
+    fn blockhash_at(slot: u64) -> Hash {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&(slot + 1).to_le_bytes());
+        Hash::new_from_array(bytes)
+    }
+
+    #[async_trait]
+    impl RpcSender for ChainSender {
+        async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+            Ok(match request {
+                RpcRequest::GetSlot => json!(self.current_slot.load(Ordering::SeqCst)),
+                RpcRequest::GetBlocks => {
+                    let start = params[0].as_u64().unwrap();
+                    let end = params[1].as_u64().unwrap();
+                    json!((start..=end)
+                        .filter(|slot| !self.skipped_slots.contains(slot))
+                        .collect::<Vec<_>>())
+                }
+                RpcRequest::GetBlock => {
+                    self.block_requests.fetch_add(1, Ordering::SeqCst);
+                    let slot = params[0].as_u64().unwrap();
+                    json!({
+                        "previousBlockhash": blockhash_at(slot.saturating_sub(1)).to_string(),
+                        "blockhash": blockhash_at(slot).to_string(),
+                        "parentSlot": slot.saturating_sub(1),
+                        "blockTime": null,
+                        "blockHeight": slot,
+                    })
+                }
+                _ => Value::Null,
+            })
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "chain".to_string()
+        }
+    }
+
+    struct Chain {
+        rpc_client: RpcClient,
+        current_slot: Arc<AtomicU64>,
+        block_requests: Arc<AtomicU64>,
+    }
+
+    fn chain(current_slot: u64, skipped_slots: Vec<u64>) -> Chain {
+        let current_slot = Arc::new(AtomicU64::new(current_slot));
+        let block_requests = Arc::new(AtomicU64::new(0));
+        let sender = ChainSender {
+            current_slot: current_slot.clone(),
+            skipped_slots,
+            block_requests: block_requests.clone(),
+        };
+        Chain {
+            rpc_client: RpcClient::new_sender(
+                sender,
+                RpcClientConfig::with_commitment(CommitmentConfig::default()),
+            ),
+            current_slot,
+            block_requests,
+        }
+    }
+
+    fn signed_transaction(
+        instructions: &[solana_sdk::instruction::Instruction],
+        blockhash: Hash,
+    ) -> VersionedTransactionResolved {
+        let fee_payer = Pubkey::new_unique();
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(&fee_payer),
+            &blockhash,
+        ));
+        let signatures =
+            vec![Signature::default(); message.header().num_required_signatures as usize];
+        let transaction = VersionedTransaction { signatures, message };
+        VersionedTransactionResolved::from_kora_built_transaction(&transaction).unwrap()
+    }
+
+    fn transfer_with_blockhash(blockhash: Hash) -> VersionedTransactionResolved {
+        let payer = Pubkey::new_unique();
+        signed_transaction(&[transfer(&payer, &Pubkey::new_unique(), 1)], blockhash)
+    }
+
+    #[tokio::test]
+    async fn test_blockhash_age_boundaries() {
+        let chain = chain(1_000, vec![]);
+        let recent_blockhashes = RecentBlockhashes::new();
+        let validator =
+            BlockhashAgeValidator { max_age_slots: 20, recent_blockhashes: &recent_blockhashes };
+
+        // Exactly max_age_slots old is accepted, one slot more is rejected
+        for (slot, accepted) in
+            [(1_000, true), (990, true), (980, true), (979, false), (900, false)]
+        {
+            let result = validator
+                .validate(&transfer_with_blockhash(blockhash_at(slot)), &chain.rpc_client)
+                .await;
+            assert_eq!(result.is_ok(), accepted, "blockhash of slot {slot}: {result:?}");
+        }
+
+        let result = validator
+            .validate(&transfer_with_blockhash(blockhash_at(979)), &chain.rpc_client)
+            .await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+
+        // Unknown blockhashes are rejected
+        let result = validator
+            .validate(&transfer_with_blockhash(Hash::new_unique()), &chain.rpc_client)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_blockhash_age_expires_as_slots_advance() {
+        let chain = chain(1_000, vec![995]);
+        let recent_blockhashes = RecentBlockhashes::new();
+        let validator =
+            BlockhashAgeValidator { max_age_slots: 10, recent_blockhashes: &recent_blockhashes };
+        let transaction = transfer_with_blockhash(blockhash_at(991));
+
+        assert!(validator.validate(&transaction, &chain.rpc_client).await.is_ok());
+        // 11 slots, minus the skipped one
+        assert_eq!(chain.block_requests.load(Ordering::SeqCst), 10);
+
+        // Known blockhashes don't need any block, it is still exactly 10 slots old
+        chain.current_slot.store(1_001, Ordering::SeqCst);
+        assert!(validator.validate(&transaction, &chain.rpc_client).await.is_ok());
+        assert_eq!(chain.block_requests.load(Ordering::SeqCst), 10);
+
+        chain.current_slot.store(1_002, Ordering::SeqCst);
+        assert!(validator.validate(&transaction, &chain.rpc_client).await.is_err());
+
+        // Only the blocks produced since the last lookup are fetched
+        let transaction = transfer_with_blockhash(blockhash_at(1_002));
+        assert!(validator.validate(&transaction, &chain.rpc_client).await.is_ok());
+        assert_eq!(chain.block_requests.load(Ordering::SeqCst), 12);
+    }
+
+    #[tokio::test]
+    async fn test_blockhash_age_skips_durable_nonce_and_unsigned_transactions() {
+        let chain = chain(1_000, vec![]);
+        let recent_blockhashes = RecentBlockhashes::new();
+        let validator =
+            BlockhashAgeValidator { max_age_slots: 10, recent_blockhashes: &recent_blockhashes };
+        let nonce_value = Hash::new_unique();
+
+        let authority = Pubkey::new_unique();
+        let nonce_transaction = signed_transaction(
+            &[
+                advance_nonce_account(&Pubkey::new_unique(), &authority),
+                transfer(&authority, &Pubkey::new_unique(), 1),
+            ],
+            nonce_value,
+        );
+        assert!(validator.validate(&nonce_transaction, &chain.rpc_client).await.is_ok());
+
+        let mut unsigned = transfer_with_blockhash(nonce_value);
+        unsigned.transaction.signatures.clear();
+        assert!(validator.validate(&unsigned, &chain.rpc_client).await.is_ok());
+
+        assert_eq!(chain.block_requests.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,7 +1,7 @@
 use deadpool_redis::Runtime;
 use redis::AsyncCommands;
 
-use crate::config::UsageLimitConfig;
+use crate::config::{UsageLimitConfig, WindowType};
 
 pub struct CacheValidator {}
 
@@ -60,6 +60,20 @@ impl CacheValidator {
             }
         }
 
+        if let WindowType::Sliding { precision_ms } = usage_config.window_type {
+            if usage_config.window_seconds == 0 {
+                errors.push("Usage limit sliding window requires window_seconds > 0".to_string());
+            }
+            if precision_ms == 0 {
+                errors.push("Usage limit sliding window requires precision_ms > 0".to_string());
+            } else if precision_ms > usage_config.window_seconds.saturating_mul(1000) {
+                warnings.push(format!(
+                    "Usage limit sliding window precision_ms ({precision_ms}) is larger than the {}s window - transactions stay counted for up to window_seconds + precision_ms",
+                    usage_config.window_seconds
+                ));
+            }
+        }
+
         // Warn about fallback configuration
         if !usage_config.fallback_if_unavailable {
             warnings.push(
@@ -109,6 +123,36 @@ mod tests {
         assert!(warnings.is_empty());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_usage_limit_sliding_window() {
+        let config = ConfigMockBuilder::new()
+            .with_usage_limit_enabled(true)
+            .with_usage_limit_cache_url(None)
+            .with_usage_limit_fallback(true)
+            .with_usage_limit_sliding_window(0, 0)
+            .build();
+
+        let (errors, _) = CacheValidator::validate(&config.kora.usage_limit).await;
+
+        assert!(errors.iter().any(|e| e.contains("requires window_seconds > 0")));
+        assert!(errors.iter().any(|e| e.contains("requires precision_ms > 0")));
+
+        let config = ConfigMockBuilder::new()
+            .with_usage_limit_enabled(true)
+            .with_usage_limit_cache_url(None)
+            .with_usage_limit_fallback(true)
+            .with_usage_limit_sliding_window(1, 5_000)
+            .build();
+
+        let (errors, warnings) = CacheValidator::validate(&config.kora.usage_limit).await;
+
+        assert!(errors.is_empty());
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("precision_ms (5000) is larger than the 1s window")));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_validate_usage_limit_enabled_no_cache_url_fallback_enabled() {
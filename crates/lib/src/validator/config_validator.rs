@@ -1,26 +1,39 @@
-use std::{path::Path, str::FromStr};
+use std::{
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
 
 use crate::{
     admin::token_util::find_missing_atas,
-    config::{FeePayerPolicy, SplTokenConfig, Token2022Config},
-    fee::price::PriceModel,
-    oracle::PriceSource,
+    config::{
+        FeePayerPolicy, ProgramInstructionRule, SplTokenConfig, Token2022Config, WebhookConfig,
+    },
+    constant::MAX_COMPUTE_UNITS_PER_TRANSACTION,
+    fee::{fee::FeeEstimationStrategy, price::PriceModel},
+    oracle::{MockOracleConfig, PriceSource},
+    rpc_server::auth::JwtAuthLayer,
     signer::SignerPoolConfig,
     state::get_config,
     token::{spl_token_2022_util, token::TokenUtil},
+    transaction::PARSED_INSTRUCTION_TYPE_NAMES,
     validator::{
         account_validator::{validate_account, AccountType},
         cache_validator::CacheValidator,
         signer_validator::SignerValidator,
+        validator_set::FeePayerWritableValidator,
     },
     KoraError,
 };
+use rust_decimal::Decimal;
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
 use solana_sdk::{account::Account, pubkey::Pubkey};
 use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
 use spl_token_2022_interface::{
     extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
-    state::Mint as Token2022MintState,
+    state::{Account as Token2022AccountState, Mint as Token2022MintState},
     ID as TOKEN_2022_PROGRAM_ID,
 };
 use spl_token_interface::ID as SPL_TOKEN_PROGRAM_ID;
@@ -83,6 +96,110 @@ impl ConfigValidator {
         }
     }
 
+    /// Check that Token2022 payment mints carry every required mint extension
+    async fn check_required_mint_extensions(
+        rpc_client: &RpcClient,
+        paid_tokens: &[String],
+        token_2022: &Token2022Config,
+        errors: &mut Vec<String>,
+    ) {
+        let required = token_2022.get_required_mint_extensions();
+        if required.is_empty() {
+            return;
+        }
+
+        for token_str in paid_tokens {
+            let Ok(token_pubkey) = Pubkey::from_str(token_str) else {
+                continue; // Reported by the paid token validation
+            };
+
+            let account = match rpc_client.get_account(&token_pubkey).await {
+                Ok(acc) => acc,
+                Err(e) => {
+                    errors.push(format!(
+                        "Failed to fetch SPL paid token {token_str} to check required extensions: {e}"
+                    ));
+                    continue;
+                }
+            };
+
+            if account.owner != TOKEN_2022_PROGRAM_ID {
+                continue;
+            }
+
+            let extension_types =
+                match StateWithExtensions::<Token2022MintState>::unpack(&account.data)
+                    .and_then(|mint| mint.get_extension_types())
+                {
+                    Ok(types) => types,
+                    Err(e) => {
+                        errors.push(format!("Failed to parse SPL paid token {token_str}: {e}"));
+                        continue;
+                    }
+                };
+
+            for ext in required.iter().filter(|ext| !extension_types.contains(ext)) {
+                errors.push(format!(
+                    "SPL paid token {token_str} is missing required mint extension {ext:?}"
+                ));
+            }
+        }
+    }
+
+    /// Check that the Token2022 payment token accounts of `payment_address` carry every
+    /// required account extension
+    async fn check_required_account_extensions(
+        rpc_client: &RpcClient,
+        payment_address: &Pubkey,
+        paid_tokens: &[String],
+        token_2022: &Token2022Config,
+        errors: &mut Vec<String>,
+    ) {
+        let required = token_2022.get_required_account_extensions();
+        if required.is_empty() {
+            return;
+        }
+
+        for token_str in paid_tokens {
+            let Ok(token_pubkey) = Pubkey::from_str(token_str) else {
+                continue; // Reported by the paid token validation
+            };
+
+            match rpc_client.get_account(&token_pubkey).await {
+                Ok(mint) if mint.owner == TOKEN_2022_PROGRAM_ID => {}
+                _ => continue,
+            }
+
+            let ata = get_associated_token_address_with_program_id(
+                payment_address,
+                &token_pubkey,
+                &TOKEN_2022_PROGRAM_ID,
+            );
+            let account = match rpc_client.get_account(&ata).await {
+                Ok(acc) => acc,
+                Err(_) => continue, // Reported by the missing ATA check
+            };
+
+            let extension_types =
+                match StateWithExtensions::<Token2022AccountState>::unpack(&account.data)
+                    .and_then(|token_account| token_account.get_extension_types())
+                {
+                    Ok(types) => types,
+                    Err(e) => {
+                        errors.push(format!("Failed to parse token account {ata}: {e}"));
+                        continue;
+                    }
+                };
+
+            for ext in required.iter().filter(|ext| !extension_types.contains(ext)) {
+                errors.push(format!(
+                    "Payment token account {ata} for {token_str} is missing required account \
+                     extension {ext:?}"
+                ));
+            }
+        }
+    }
+
     /// Validate fee payer policy and add warnings for enabled risky operations
     fn validate_fee_payer_policy(policy: &FeePayerPolicy, warnings: &mut Vec<String>) {
         macro_rules! check_fee_payer_policy {
@@ -257,6 +374,23 @@ impl ConfigValidator {
         rpc_client: &RpcClient,
         skip_rpc_validation: bool,
         signers_config_path: Option<P>,
+    ) -> Result<Vec<String>, Vec<String>> {
+        Self::validate_with_result_and_signers_to(
+            rpc_client,
+            skip_rpc_validation,
+            signers_config_path,
+            &mut std::io::stdout(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::validate_with_result_and_signers`], writing the human-readable report
+    /// to `report` instead of stdout
+    pub async fn validate_with_result_and_signers_to<P: AsRef<Path>, W: Write>(
+        rpc_client: &RpcClient,
+        skip_rpc_validation: bool,
+        signers_config_path: Option<P>,
+        report: &mut W,
     ) -> Result<Vec<String>, Vec<String>> {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
@@ -281,6 +415,92 @@ impl ConfigValidator {
             }
         }
 
+        // Validate auto lookup table address
+        if let Some(lookup_table) = &config.kora.auto_add_lookup_table {
+            if let Err(e) = Pubkey::from_str(lookup_table) {
+                errors.push(format!("Invalid auto_add_lookup_table address: {e}"));
+            }
+        }
+
+        // Validate auto compute budget limit
+        if let Some(limit) =
+            config.kora.auto_compute_budget.as_ref().and_then(|budget| budget.compute_unit_limit)
+        {
+            if limit as u64 > MAX_COMPUTE_UNITS_PER_TRANSACTION {
+                errors.push(format!(
+                    "auto_compute_budget compute_unit_limit {limit} exceeds the maximum of \
+                     {MAX_COMPUTE_UNITS_PER_TRANSACTION}"
+                ));
+            }
+        }
+
+        // Validate pre-flight simulation commitment
+        if config.kora.preflight_simulate
+            && CommitmentConfig::from_str(&config.kora.preflight_simulation_commitment).is_err()
+        {
+            errors.push(format!(
+                "Invalid preflight_simulation_commitment: {} (expected processed, confirmed or \
+                 finalized)",
+                config.kora.preflight_simulation_commitment
+            ));
+        }
+
+        // Validate signTransactionBatch batch size, the request body limit is raised to fit it
+        if config.kora.enabled_methods.sign_transaction_batch {
+            if config.kora.max_batch_size == 0 {
+                errors.push(
+                    "max_batch_size is 0 - signTransactionBatch would reject every batch"
+                        .to_string(),
+                );
+            }
+            let max_request_body_size = config.kora.get_max_request_body_size();
+            if max_request_body_size > config.kora.max_request_body_size {
+                warnings.push(format!(
+                    "max_request_body_size raised from {} to {max_request_body_size} bytes to fit \
+                     signTransactionBatch requests of max_batch_size ({}) transactions",
+                    config.kora.max_request_body_size, config.kora.max_batch_size
+                ));
+            }
+        }
+
+        // Validate request body limit, the server takes it as a u32
+        if u32::try_from(config.kora.get_max_request_body_size()).is_err() {
+            errors.push(format!(
+                "max_request_body_size of {} bytes exceeds the maximum of {} bytes",
+                config.kora.get_max_request_body_size(),
+                u32::MAX
+            ));
+        }
+
+        // Validate RPC proxy allowlist
+        if config.kora.enable_rpc_proxy {
+            if config.kora.proxy_allowed_methods.is_empty() {
+                warnings.push(
+                    "RPC proxy is enabled with no proxy_allowed_methods - nothing will be forwarded"
+                        .to_string(),
+                );
+            }
+            for method in &config.kora.proxy_allowed_methods {
+                if KoraConfig::is_kora_method(method) {
+                    warnings.push(format!(
+                        "Kora method {method} in proxy_allowed_methods - it is served by Kora and \
+                         never forwarded"
+                    ));
+                }
+            }
+        }
+
+        if config.kora.enabled_methods.sign_message
+            && config.kora.sign_message_allow_transaction_bytes
+        {
+            warnings.push(
+                "⚠️  SECURITY: signMessage signs transaction messages \
+                 (sign_message_allow_transaction_bytes) - clients can get fee payer signatures on \
+                 transactions that skipped transaction validation"
+                    .to_string(),
+            );
+        }
+
         // Validate enabled methods (warn if all false)
         let methods = &config.kora.enabled_methods;
         if !methods.iter().any(|enabled| enabled) {
@@ -303,13 +523,38 @@ impl ConfigValidator {
         // Validate price source (warn if Mock)
         if matches!(config.validation.price_source, PriceSource::Mock) {
             warnings.push("Using Mock price source - not suitable for production".to_string());
+
+            if let Err(e) = validate_mock_oracle_config(&config.validation.mock_oracle) {
+                errors.push(format!("Invalid mock oracle configuration: {e}"));
+            }
+        }
+
+        // Validate disallowed programs against allowed programs
+        let allowed_programs = &config.validation.allowed_programs;
+        let disallowed_programs = &config.validation.disallowed_programs;
+        if let Err(e) = TokenUtil::check_valid_tokens(disallowed_programs) {
+            errors.push(format!("Invalid disallowed program address: {e}"));
+        }
+        for program in disallowed_programs.iter().filter(|p| allowed_programs.contains(p)) {
+            warnings.push(format!(
+                "Program {program} is in both allowed_programs and disallowed_programs - it will be blocked"
+            ));
+        }
+        if !allowed_programs.is_empty() && !disallowed_programs.is_empty() {
+            errors.push("allowed_programs and disallowed_programs are both set - configure either an allowlist or a denylist of programs".to_string());
+        }
+        if disallowed_programs.contains(&SYSTEM_PROGRAM_ID.to_string()) {
+            warnings.push("System Program in disallowed programs - SOL transfers and account operations will be blocked".to_string());
         }
 
         // Validate allowed programs (warn if empty or missing system/token programs)
-        if config.validation.allowed_programs.is_empty() {
-            warnings.push(
-                "No allowed programs configured - this will block all transactions".to_string(),
-            );
+        if allowed_programs.is_empty() {
+            // With only disallowed programs, every other program is allowed
+            if disallowed_programs.is_empty() {
+                warnings.push(
+                    "No allowed programs configured - this will block all transactions".to_string(),
+                );
+            }
         } else {
             if !config.validation.allowed_programs.contains(&SYSTEM_PROGRAM_ID.to_string()) {
                 warnings.push("Missing System Program in allowed programs - SOL transfers and account operations will be blocked".to_string());
@@ -321,6 +566,19 @@ impl ConfigValidator {
             }
         }
 
+        // Validate Anchor programs
+        for anchor_program in &config.validation.anchor_programs {
+            if let Err(e) = Pubkey::from_str(&anchor_program.program_id) {
+                errors.push(format!("Invalid Anchor program address: {e}"));
+            }
+            if anchor_program.allowed_instructions.is_empty() {
+                warnings.push(format!(
+                    "Anchor program {} has no allowed instructions - every instruction of it will be blocked",
+                    anchor_program.program_id
+                ));
+            }
+        }
+
         // Validate allowed tokens
         if config.validation.allowed_tokens.is_empty() {
             errors.push("No allowed tokens configured".to_string());
@@ -334,21 +592,59 @@ impl ConfigValidator {
         {
             errors.push(format!("Invalid spl paid token address: {e}"));
         }
+        if let Err(e) = TokenUtil::check_valid_tokens(
+            config.validation.allowed_spl_paid_tokens.blocked_tokens(),
+        ) {
+            errors.push(format!("Invalid blocked spl paid token address: {e}"));
+        }
 
         // Warn if using "All" for allowed_spl_paid_tokens
-        if matches!(config.validation.allowed_spl_paid_tokens, SplTokenConfig::All) {
-            warnings.push(
+        match &config.validation.allowed_spl_paid_tokens {
+            SplTokenConfig::All => warnings.push(
                 "⚠️  Using 'All' for allowed_spl_paid_tokens - this accepts ANY SPL token for payment. \
                 Consider using an explicit allowlist to reduce volatility risk and protect against \
                 potentially malicious or worthless tokens being used for fees.".to_string()
-            );
+            ),
+            SplTokenConfig::Blocklist(_) => warnings.push(
+                "⚠️  Using a Blocklist for allowed_spl_paid_tokens - every SPL token that is not \
+                blocked is accepted for payment, including tokens created after the config was written. \
+                Consider using an explicit allowlist in production.".to_string()
+            ),
+            SplTokenConfig::Allowlist(_) => {}
         }
 
         // Validate disallowed accounts
-        if let Err(e) = TokenUtil::check_valid_tokens(&config.validation.disallowed_accounts) {
+        let disallowed_accounts = &config.validation.disallowed_accounts;
+        if let Err(e) = TokenUtil::check_valid_tokens(disallowed_accounts) {
             errors.push(format!("Invalid disallowed account address: {e}"));
         }
 
+        // Tokens can't be both allowed and disallowed
+        for (list, tokens) in [
+            ("allowed_tokens", config.validation.allowed_tokens.as_slice()),
+            ("allowed_spl_paid_tokens", config.validation.allowed_spl_paid_tokens.as_slice()),
+        ] {
+            let conflicts = find_list_conflicts(tokens, disallowed_accounts);
+            if !conflicts.is_empty() {
+                errors.push(format!(
+                    "Tokens in both {list} and disallowed_accounts: {}",
+                    conflicts.join(", ")
+                ));
+            }
+        }
+
+        // Validate fee payer policy allowed receivers
+        let policy = &config.validation.fee_payer_policy;
+        for (category, allowed_receivers) in [
+            ("system", &policy.system.allowed_receivers),
+            ("spl_token", &policy.spl_token.allowed_receivers),
+            ("token_2022", &policy.token_2022.allowed_receivers),
+        ] {
+            if let Some(Err(e)) = allowed_receivers.as_deref().map(TokenUtil::check_valid_tokens) {
+                errors.push(format!("Invalid {category} allowed receiver address: {e}"));
+            }
+        }
+
         // Validate Token2022 extensions
         if let Err(e) = validate_token2022_extensions(&config.validation.token_2022) {
             errors.push(format!("Token2022 extension validation failed: {e}"));
@@ -372,10 +668,15 @@ impl ConfigValidator {
 
         if fees_enabled {
             // If fees enabled, token or token22 must be enabled in allowed_programs
-            let has_token_program =
-                config.validation.allowed_programs.contains(&SPL_TOKEN_PROGRAM_ID.to_string());
-            let has_token22_program =
-                config.validation.allowed_programs.contains(&TOKEN_2022_PROGRAM_ID.to_string());
+            let is_program_allowed = |program: String| {
+                if allowed_programs.is_empty() && !disallowed_programs.is_empty() {
+                    !disallowed_programs.contains(&program)
+                } else {
+                    allowed_programs.contains(&program)
+                }
+            };
+            let has_token_program = is_program_allowed(SPL_TOKEN_PROGRAM_ID.to_string());
+            let has_token22_program = is_program_allowed(TOKEN_2022_PROGRAM_ID.to_string());
 
             if !has_token_program && !has_token22_program {
                 errors.push("When fees are enabled, at least one token program (SPL Token or Token2022) must be in allowed_programs".to_string());
@@ -407,6 +708,54 @@ impl ConfigValidator {
         // Validate fee payer policy - warn about enabled risky operations
         Self::validate_fee_payer_policy(&config.validation.fee_payer_policy, &mut warnings);
 
+        if let Err(e) = validate_instruction_type_allowlist(&config.validation.fee_payer_policy) {
+            errors.push(format!("Invalid fee payer policy: {e}"));
+        }
+        if let Err(e) = validate_program_instruction_allowlist(&config.validation.fee_payer_policy)
+        {
+            errors.push(format!("Invalid fee payer policy: {e}"));
+        }
+        if config.validation.fee_payer_policy.instruction_type_allowlist.is_some() {
+            warnings.push(
+                "fee_payer_policy.instruction_type_allowlist is set - the category-level allow_* \
+                 flags are ignored"
+                    .to_string(),
+            );
+        }
+
+        match &config.validation.account_type_allowlist {
+            Some(allowlist) if allowlist.is_empty() => {
+                errors.push(
+                    "account_type_allowlist is empty - every transaction would be rejected"
+                        .to_string(),
+                );
+            }
+            Some(allowlist) if !allowlist.contains(&AccountType::System) => {
+                warnings.push(
+                    "account_type_allowlist does not include 'system' - wallets and accounts \
+                     that don't exist yet will be rejected"
+                        .to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if config.validation.max_lookup_table_accounts == Some(0) {
+            warnings.push(
+                "max_lookup_table_accounts is 0 - every transaction using lookup tables will be \
+                 rejected"
+                    .to_string(),
+            );
+        }
+
+        if config.validation.max_blockhash_age_slots == Some(0) {
+            warnings.push(
+                "max_blockhash_age_slots is 0 - only transactions using the blockhash of the \
+                 current slot will be accepted"
+                    .to_string(),
+            );
+        }
+
         // Validate margin (error if negative)
         match &config.validation.price.model {
             PriceModel::Fixed { amount, token, strict } => {
@@ -424,8 +773,7 @@ impl ConfigValidator {
                 }
 
                 // Warn about dangerous configurations with fixed pricing
-                let has_auth =
-                    config.kora.auth.api_key.is_some() || config.kora.auth.hmac_secret.is_some();
+                let has_auth = config.kora.auth.has_client_auth();
                 if !has_auth {
                     warnings.push(
                         "⚠️  SECURITY: Fixed pricing with NO authentication enabled. \
@@ -445,26 +793,53 @@ impl ConfigValidator {
                 }
             }
             PriceModel::Margin { margin } => {
-                if *margin < 0.0 {
+                if *margin < Decimal::ZERO {
                     errors.push("Margin cannot be negative".to_string());
-                } else if *margin > 1.0 {
-                    warnings.push(format!("Margin is {}% - this is very high", margin * 100.0));
+                } else if *margin > Decimal::ONE {
+                    warnings.push(format!(
+                        "Margin is {}% - this is very high",
+                        (margin * Decimal::ONE_HUNDRED).normalize()
+                    ));
                 }
             }
             _ => {}
         };
 
+        match &config.validation.fee_estimation_strategy {
+            FeeEstimationStrategy::StaticCalculation { compute_units: 0, .. } => {
+                warnings.push(
+                    "fee_estimation_strategy compute_units is 0 - only signature fees will be \
+                     estimated"
+                        .to_string(),
+                );
+            }
+            FeeEstimationStrategy::Simulation { commitment } => {
+                if CommitmentConfig::from_str(commitment).is_err() {
+                    errors.push(format!(
+                        "Invalid fee_estimation_strategy commitment: {commitment} (expected \
+                         processed, confirmed or finalized)"
+                    ));
+                }
+            }
+            _ => {}
+        }
+
         // General authentication warning
-        let has_auth = config.kora.auth.api_key.is_some() || config.kora.auth.hmac_secret.is_some();
-        if !has_auth {
+        if !config.kora.auth.has_client_auth() {
             warnings.push(
-                "⚠️  SECURITY: No authentication configured (neither api_key nor hmac_secret). \
-                Authentication is strongly recommended for production deployments. \
-                Consider enabling api_key or hmac_secret in [kora.auth]."
+                "⚠️  SECURITY: No authentication configured (neither api_key, hmac_secret nor \
+                jwt_public_key). Authentication is strongly recommended for production \
+                deployments. Consider enabling api_key, hmac_secret or jwt_public_key in \
+                [kora.auth]."
                     .to_string(),
             );
         }
 
+        // Validate JWT authentication
+        if let Err(e) = JwtAuthLayer::from_config(&config.kora.auth) {
+            errors.push(format!("Invalid JWT authentication configuration: {e}"));
+        }
+
         // Validate usage limit configuration
         let usage_config = &config.kora.usage_limit;
         if usage_config.enabled {
@@ -473,6 +848,17 @@ impl ConfigValidator {
             warnings.extend(usage_warnings);
         }
 
+        // Validate webhook configuration
+        if let Some(webhook) = &config.kora.webhook {
+            if let Err(e) = validate_webhook_config(webhook) {
+                errors.push(format!("Invalid webhook configuration: {e}"));
+            }
+            if webhook.events.is_empty() {
+                warnings
+                    .push("Webhook configured with no events - nothing will be sent".to_string());
+            }
+        }
+
         // RPC validation - only if not skipped
         if !skip_rpc_validation {
             // Validate allowed programs - should be executable
@@ -517,9 +903,27 @@ impl ConfigValidator {
             )
             .await;
 
+            // Check Token2022 payment mints for required extensions
+            Self::check_required_mint_extensions(
+                rpc_client,
+                config.validation.allowed_spl_paid_tokens.as_slice(),
+                &config.validation.token_2022,
+                &mut errors,
+            )
+            .await;
+
             // Validate missing ATAs for payment address
             if let Some(payment_address) = &config.kora.payment_address {
                 if let Ok(payment_address) = Pubkey::from_str(payment_address) {
+                    Self::check_required_account_extensions(
+                        rpc_client,
+                        &payment_address,
+                        config.validation.allowed_spl_paid_tokens.as_slice(),
+                        &config.validation.token_2022,
+                        &mut errors,
+                    )
+                    .await;
+
                     match find_missing_atas(rpc_client, &payment_address).await {
                         Ok(atas_to_create) => {
                             if !atas_to_create.is_empty() {
@@ -537,6 +941,7 @@ impl ConfigValidator {
         }
 
         // Validate signers configuration if provided
+        let signers_validated = signers_config_path.is_some();
         if let Some(path) = signers_config_path {
             match SignerPoolConfig::load_config(path.as_ref()) {
                 Ok(signer_config) => {
@@ -549,40 +954,165 @@ impl ConfigValidator {
                     errors.push(format!("Failed to load signers config: {e}"));
                 }
             }
-        } else {
-            println!("ℹ️  Signers configuration not validated. Include --signers-config path/to/signers.toml to validate signers");
         }
 
-        // Output results
-        println!("=== Configuration Validation ===");
+        // A failed write only loses the report, the result is still returned
+        let _ = write_validation_report(report, signers_validated, &errors, &warnings);
+
         if errors.is_empty() {
-            println!("✓ Configuration validation successful!");
+            Ok(warnings)
         } else {
-            println!("✗ Configuration validation failed!");
-            println!("\n❌ Errors:");
-            for error in &errors {
-                println!("   - {error}");
-            }
-            println!("\nPlease fix the configuration errors above before deploying.");
+            Err(errors)
         }
+    }
+}
+
+fn write_validation_report<W: Write>(
+    report: &mut W,
+    signers_validated: bool,
+    errors: &[String],
+    warnings: &[String],
+) -> io::Result<()> {
+    if !signers_validated {
+        writeln!(report, "ℹ️  Signers configuration not validated. Include --signers-config path/to/signers.toml to validate signers")?;
+    }
+
+    writeln!(report, "=== Configuration Validation ===")?;
+    if errors.is_empty() {
+        writeln!(report, "✓ Configuration validation successful!")?;
+    } else {
+        writeln!(report, "✗ Configuration validation failed!")?;
+        writeln!(report, "\n❌ Errors:")?;
+        for error in errors {
+            writeln!(report, "   - {error}")?;
+        }
+        writeln!(report, "\nPlease fix the configuration errors above before deploying.")?;
+    }
+
+    if !warnings.is_empty() {
+        writeln!(report, "\n⚠️  Warnings:")?;
+        for warning in warnings {
+            writeln!(report, "   - {warning}")?;
+        }
+    }
 
-        if !warnings.is_empty() {
-            println!("\n⚠️  Warnings:");
-            for warning in &warnings {
-                println!("   - {warning}");
+    Ok(())
+}
+
+/// Validate that every entry of the fee payer instruction type allowlist is a known instruction type
+/// Entries of `list` that also appear in `other`, in `list` order and without duplicates
+fn find_list_conflicts<'a>(list: &'a [String], other: &[String]) -> Vec<&'a str> {
+    let mut conflicts: Vec<&str> = Vec::new();
+    for entry in list {
+        if other.contains(entry) && !conflicts.contains(&entry.as_str()) {
+            conflicts.push(entry);
+        }
+    }
+    conflicts
+}
+
+fn validate_instruction_type_allowlist(policy: &FeePayerPolicy) -> Result<(), String> {
+    for instruction_type in policy.instruction_type_allowlist.iter().flatten() {
+        if !PARSED_INSTRUCTION_TYPE_NAMES.contains(&instruction_type.as_str()) {
+            return Err(format!(
+                "Unknown instruction type '{instruction_type}' in instruction_type_allowlist. \
+                 Valid types are: {PARSED_INSTRUCTION_TYPE_NAMES:?}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that every program instruction rule names a program the policy doesn't already parse,
+/// a hex discriminator and known fee payer roles
+fn validate_program_instruction_allowlist(policy: &FeePayerPolicy) -> Result<(), String> {
+    for rule in &policy.program_instruction_allowlist {
+        let program_id = Pubkey::from_str(&rule.program_id).map_err(|e| {
+            format!(
+                "Invalid program address '{}' in program_instruction_allowlist: {e}",
+                rule.program_id
+            )
+        })?;
+        if FeePayerWritableValidator::POLICY_PROGRAMS.contains(&program_id) {
+            return Err(format!(
+                "Program {program_id} in program_instruction_allowlist is covered by the \
+                 category-level fee payer policy"
+            ));
+        }
+
+        if let Some(discriminator) = &rule.instruction_discriminator {
+            if discriminator.is_empty() || hex::decode(discriminator).is_err() {
+                return Err(format!(
+                    "Invalid instruction_discriminator '{discriminator}' for program {program_id}, \
+                     expected non-empty hex"
+                ));
             }
         }
 
-        if errors.is_empty() {
-            Ok(warnings)
-        } else {
-            Err(errors)
+        for role in &rule.allow_fee_payer_as {
+            if !ProgramInstructionRule::FEE_PAYER_ROLES.contains(&role.as_str()) {
+                return Err(format!(
+                    "Unknown fee payer role '{role}' for program {program_id}. Valid roles are: {:?}",
+                    ProgramInstructionRule::FEE_PAYER_ROLES
+                ));
+            }
         }
     }
+
+    Ok(())
+}
+
+/// Validate that mocked prices stay positive across the whole volatility band
+fn validate_mock_oracle_config(mock_oracle: &MockOracleConfig) -> Result<(), String> {
+    if !(mock_oracle.base_price_usd.is_finite() && mock_oracle.base_price_usd > 0.0) {
+        return Err(format!(
+            "base_price_usd must be greater than 0, got {}",
+            mock_oracle.base_price_usd
+        ));
+    }
+
+    if !(0.0..100.0).contains(&mock_oracle.volatility_percent) {
+        return Err(format!(
+            "volatility_percent must be between 0 and 100, got {}",
+            mock_oracle.volatility_percent
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate that the webhook points to an HTTP(S) endpoint and has a secret to sign payloads with
+fn validate_webhook_config(webhook: &WebhookConfig) -> Result<(), String> {
+    let url = reqwest::Url::parse(&webhook.url).map_err(|e| format!("invalid url: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("url must use http or https, got '{}'", url.scheme()));
+    }
+
+    if webhook.secret.is_empty() {
+        return Err("secret cannot be empty".to_string());
+    }
+
+    Ok(())
 }
 
 /// Validate Token2022 extension configuration
 fn validate_token2022_extensions(config: &Token2022Config) -> Result<(), String> {
+    if config.has_allowlist() && config.has_blocklist() {
+        return Err("allowed_extensions cannot be combined with blocked_mint_extensions or \
+             blocked_account_extensions"
+            .to_string());
+    }
+
+    // Validate allowed extensions, which may name either mint or account extensions
+    for ext_name in &config.allowed_extensions {
+        if spl_token_2022_util::parse_mint_extension_string(ext_name).is_none()
+            && spl_token_2022_util::parse_account_extension_string(ext_name).is_none()
+        {
+            return Err(format!("Invalid allowed extension name: '{ext_name}'"));
+        }
+    }
+
     // Validate blocked mint extensions
     for ext_name in &config.blocked_mint_extensions {
         if spl_token_2022_util::parse_mint_extension_string(ext_name).is_none() {
@@ -603,6 +1133,38 @@ fn validate_token2022_extensions(config: &Token2022Config) -> Result<(), String>
         }
     }
 
+    // Validate required mint extensions, which can't be blocked at the same time
+    for ext_name in &config.required_mint_extensions {
+        match spl_token_2022_util::parse_mint_extension_string(ext_name) {
+            Some(ext) if config.is_mint_extension_blocked(ext) => {
+                return Err(format!("Required mint extension '{ext_name}' is also blocked"));
+            }
+            Some(_) => {}
+            None => {
+                return Err(format!(
+                    "Invalid required mint extension name: '{ext_name}'. Valid names are: {:?}",
+                    spl_token_2022_util::get_all_mint_extension_names()
+                ));
+            }
+        }
+    }
+
+    // Validate required account extensions, which can't be blocked at the same time
+    for ext_name in &config.required_account_extensions {
+        match spl_token_2022_util::parse_account_extension_string(ext_name) {
+            Some(ext) if config.is_account_extension_blocked(ext) => {
+                return Err(format!("Required account extension '{ext_name}' is also blocked"));
+            }
+            Some(_) => {}
+            None => {
+                return Err(format!(
+                    "Invalid required account extension name: '{ext_name}'. Valid names are: {:?}",
+                    spl_token_2022_util::get_all_account_extension_names()
+                ));
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -610,16 +1172,16 @@ fn validate_token2022_extensions(config: &Token2022Config) -> Result<(), String>
 mod tests {
     use crate::{
         config::{
-            AuthConfig, CacheConfig, Config, EnabledMethods, FeePayerPolicy, KoraConfig,
-            MetricsConfig, NonceInstructionPolicy, SplTokenConfig, SplTokenInstructionPolicy,
-            SystemInstructionPolicy, Token2022InstructionPolicy, UsageLimitConfig,
-            ValidationConfig,
+            AnchorProgramConfig, AuthConfig, AutoComputeBudgetConfig, CacheConfig, Config,
+            EnabledMethods, FeePayerPolicy, KoraConfig, MetricsConfig, NonceInstructionPolicy,
+            SplTokenConfig, SplTokenInstructionPolicy, SystemInstructionPolicy,
+            Token2022InstructionPolicy, UsageLimitConfig, ValidationConfig, WebhookEvent,
         },
-        constant::DEFAULT_MAX_REQUEST_BODY_SIZE,
+        constant::{DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_REQUEST_BODY_SIZE},
         fee::price::PriceConfig,
         state::update_config,
         tests::{
-            account_mock::create_mock_token2022_mint_with_extensions,
+            account_mock::{create_mock_token2022_mint_with_extensions, TokenAccountMockBuilder},
             common::{
                 create_mock_non_executable_account, create_mock_program_account,
                 create_mock_rpc_client_account_not_found, create_mock_rpc_client_with_account,
@@ -628,6 +1190,7 @@ mod tests {
             config_mock::ConfigMockBuilder,
         },
     };
+    use rust_decimal_macros::dec;
     use serial_test::serial;
     use solana_commitment_config::CommitmentConfig;
     use spl_token_2022_interface::extension::ExtensionType;
@@ -649,6 +1212,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig::default(),
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             kora: KoraConfig::default(),
             metrics: MetricsConfig::default(),
@@ -690,6 +1268,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig::default(),
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             kora: KoraConfig::default(),
             metrics: MetricsConfig::default(),
@@ -726,6 +1319,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             kora: KoraConfig {
                 rate_limit: 0, // Should warn
@@ -740,11 +1348,30 @@ mod tests {
                     get_blockhash: false,
                     get_config: false,
                     get_payer_signer: false,
+                    subscribe_transaction_events: false,
+                    get_version: false,
+                    get_instruction_template: false,
+                    get_token_metadata: false,
+                    get_account_info: false,
+                    get_network_status: false,
+                    sign_message: false,
+                    sign_transaction_batch: false,
+                    simulate_transaction: false,
                 },
                 auth: AuthConfig::default(),
                 payment_address: None,
                 cache: CacheConfig::default(),
                 usage_limit: UsageLimitConfig::default(),
+                webhook: None,
+                auto_add_lookup_table: None,
+                auto_compute_budget: None,
+                preflight_simulate: false,
+                preflight_simulation_commitment: "confirmed".to_string(),
+                enable_rpc_proxy: false,
+                proxy_allowed_methods: vec![],
+                backpressure_timeout_ms: 0,
+                sign_message_allow_transaction_bytes: false,
+                max_batch_size: DEFAULT_MAX_BATCH_SIZE,
             },
             metrics: MetricsConfig::default(),
         };
@@ -784,6 +1411,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             kora: KoraConfig::default(),
             metrics: MetricsConfig::default(),
@@ -820,12 +1462,38 @@ mod tests {
                 price_source: PriceSource::Jupiter,
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig {
-                    model: PriceModel::Margin { margin: -0.1 }, // Error - negative margin
+                    model: PriceModel::Margin { margin: dec!(-0.1) }, // Error - negative margin
                 },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::Simulation {
+                    commitment: "invalid".to_string(), // Error - invalid commitment
+                },
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
-            kora: KoraConfig::default(),
+            kora: KoraConfig {
+                preflight_simulate: true,
+                preflight_simulation_commitment: "invalid".to_string(), // Error - invalid commitment
+                enable_rpc_proxy: false,
+                proxy_allowed_methods: vec![],
+                backpressure_timeout_ms: 0,
+                sign_message_allow_transaction_bytes: false,
+                max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+                ..Default::default()
+            },
         };
 
         let _ = update_config(config);
@@ -842,6 +1510,8 @@ mod tests {
         assert!(errors.iter().any(|e| e.contains("Invalid spl paid token address")));
         assert!(errors.iter().any(|e| e.contains("Invalid disallowed account address")));
         assert!(errors.iter().any(|e| e.contains("Margin cannot be negative")));
+        assert!(errors.iter().any(|e| e.contains("Invalid fee_estimation_strategy commitment")));
+        assert!(errors.iter().any(|e| e.contains("Invalid preflight_simulation_commitment")));
     }
 
     #[tokio::test]
@@ -867,6 +1537,21 @@ mod tests {
                     },
                 },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -908,6 +1593,21 @@ mod tests {
                     },
                 },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -957,6 +1657,21 @@ mod tests {
                     },
                 },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -990,8 +1705,23 @@ mod tests {
                 disallowed_accounts: vec![],
                 price_source: PriceSource::Jupiter,
                 fee_payer_policy: FeePayerPolicy::default(),
-                price: PriceConfig { model: PriceModel::Margin { margin: 0.1 } },
+                price: PriceConfig { model: PriceModel::Margin { margin: dec!(0.1) } },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1029,8 +1759,23 @@ mod tests {
                 disallowed_accounts: vec![],
                 price_source: PriceSource::Jupiter,
                 fee_payer_policy: FeePayerPolicy::default(),
-                price: PriceConfig { model: PriceModel::Margin { margin: 0.1 } },
+                price: PriceConfig { model: PriceModel::Margin { margin: dec!(0.1) } },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1049,6 +1794,61 @@ mod tests {
         assert!(warnings.iter().any(|w| w.contains("volatility risk")));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_fee_and_spl_token_blocklist() {
+        let config = Config {
+            validation: ValidationConfig {
+                max_allowed_lamports: 1_000_000,
+                max_signatures: 10,
+                allowed_programs: vec![
+                    SYSTEM_PROGRAM_ID.to_string(),
+                    SPL_TOKEN_PROGRAM_ID.to_string(),
+                ],
+                allowed_tokens: vec!["4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU".to_string()],
+                allowed_spl_paid_tokens: SplTokenConfig::Blocklist(vec![
+                    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+                ]), // Every token but USDC is allowed
+                disallowed_accounts: vec![],
+                price_source: PriceSource::Jupiter,
+                fee_payer_policy: FeePayerPolicy::default(),
+                price: PriceConfig { model: PriceModel::Margin { margin: dec!(0.1) } },
+                token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
+            },
+            metrics: MetricsConfig::default(),
+            kora: KoraConfig::default(),
+        };
+
+        let _ = update_config(config);
+
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+        assert!(result.is_ok());
+
+        // A blocklist counts as configured paid tokens, but warns that unknown tokens are accepted
+        let warnings = result.unwrap();
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("Using a Blocklist for allowed_spl_paid_tokens")));
+        assert!(!warnings.iter().any(|w| w.contains("Using 'All' for allowed_spl_paid_tokens")));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_validate_with_result_paid_tokens_not_in_allowed_tokens() {
@@ -1069,6 +1869,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1100,6 +1915,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1120,12 +1950,68 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
         }
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_and_signers_to_writes_report() {
+        let _ = update_config(create_program_only_config());
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let mut report = Vec::new();
+        let result = ConfigValidator::validate_with_result_and_signers_to(
+            &rpc_client,
+            true,
+            None::<&Path>,
+            &mut report,
+        )
+        .await;
+
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("Signers configuration not validated"));
+        assert!(report.contains("=== Configuration Validation ==="));
+        assert_eq!(report.contains("✓ Configuration validation successful!"), result.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_invalid_allowed_receivers() {
+        let mut config = create_program_only_config();
+        config.validation.fee_payer_policy.system.allowed_receivers =
+            Some(vec![Pubkey::new_unique().to_string()]);
+        config.validation.fee_payer_policy.spl_token.allowed_receivers =
+            Some(vec!["invalid_receiver".to_string()]);
+
+        let _ = update_config(config);
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("Invalid spl_token allowed receiver address")
+            && e.contains("invalid_receiver")));
+        assert!(!errors.iter().any(|e| e.contains("Invalid system allowed receiver address")));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_validate_with_result_rpc_validation_valid_program() {
@@ -1182,6 +2068,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1214,6 +2115,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1245,6 +2161,21 @@ mod tests {
                 fee_payer_policy: FeePayerPolicy::default(),
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1282,6 +2213,21 @@ mod tests {
                         vec!["memo_transfer".to_string(), "cpi_guard".to_string()];
                     config
                 },
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1316,6 +2262,21 @@ mod tests {
                     config.blocked_mint_extensions = vec!["invalid_mint_extension".to_string()];
                     config
                 },
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1354,6 +2315,21 @@ mod tests {
                         vec!["invalid_account_extension".to_string()];
                     config
                 },
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1406,6 +2382,143 @@ mod tests {
             .contains("Invalid account extension name: 'invalid_extension'"));
     }
 
+    #[test]
+    fn test_validate_token2022_extensions_allowlist_with_blocklist() {
+        let mut config = Token2022Config::default();
+        config.allowed_extensions = vec!["transfer_fee_config".to_string()];
+        config.blocked_account_extensions = vec!["cpi_guard".to_string()];
+
+        let result = validate_token2022_extensions(&config);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("allowed_extensions cannot be combined"));
+    }
+
+    #[test]
+    fn test_validate_token2022_extensions_invalid_allowed_extension() {
+        let mut config = Token2022Config::default();
+        config.allowed_extensions = vec!["memo_transfer".to_string(), "invalid".to_string()];
+
+        let result = validate_token2022_extensions(&config);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid allowed extension name: 'invalid'"));
+    }
+
+    #[test]
+    fn test_validate_token2022_extensions_required() {
+        let mut config = Token2022Config::default();
+        config.required_mint_extensions = vec!["transfer_fee_config".to_string()];
+        config.required_account_extensions = vec!["memo_transfer".to_string()];
+        assert!(validate_token2022_extensions(&config).is_ok());
+
+        config.required_mint_extensions = vec!["invalid".to_string()];
+        let result = validate_token2022_extensions(&config);
+        assert!(result.unwrap_err().contains("Invalid required mint extension name: 'invalid'"));
+
+        config.required_mint_extensions = vec!["transfer_fee_config".to_string()];
+        config.blocked_mint_extensions = vec!["transfer_fee_config".to_string()];
+        assert!(config.initialize().unwrap_err().contains("also blocked"));
+        let result = validate_token2022_extensions(&config);
+        assert!(result.unwrap_err().contains("'transfer_fee_config' is also blocked"));
+    }
+
+    #[test]
+    fn test_validate_instruction_type_allowlist() {
+        assert!(validate_instruction_type_allowlist(&FeePayerPolicy::default()).is_ok());
+
+        let policy = FeePayerPolicy {
+            instruction_type_allowlist: Some(vec![
+                "SystemTransfer".to_string(),
+                "SplTokenTransfer".to_string(),
+            ]),
+            ..Default::default()
+        };
+        assert!(validate_instruction_type_allowlist(&policy).is_ok());
+
+        let policy = FeePayerPolicy {
+            instruction_type_allowlist: Some(vec![
+                "SplTokenTransfer".to_string(),
+                "TokenBurn".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let result = validate_instruction_type_allowlist(&policy);
+        assert!(result.unwrap_err().contains("Unknown instruction type 'TokenBurn'"));
+    }
+
+    #[test]
+    fn test_validate_program_instruction_allowlist() {
+        let rule = |program_id: &str, discriminator: Option<&str>, roles: &[&str]| FeePayerPolicy {
+            program_instruction_allowlist: vec![ProgramInstructionRule {
+                program_id: program_id.to_string(),
+                instruction_discriminator: discriminator.map(str::to_string),
+                allow_fee_payer_as: roles.iter().map(|role| role.to_string()).collect(),
+            }],
+            ..Default::default()
+        };
+        let program_id = Pubkey::new_unique().to_string();
+
+        assert!(validate_program_instruction_allowlist(&rule(
+            &program_id,
+            Some("f223c68952e1f2b6"),
+            &["signer", "writable"]
+        ))
+        .is_ok());
+        assert!(validate_program_instruction_allowlist(&rule(&program_id, None, &[])).is_ok());
+
+        let result = validate_program_instruction_allowlist(&rule("invalid", None, &["signer"]));
+        assert!(result.unwrap_err().contains("Invalid program address 'invalid'"));
+
+        let result = validate_program_instruction_allowlist(&rule(
+            &SYSTEM_PROGRAM_ID.to_string(),
+            None,
+            &["signer"],
+        ));
+        assert!(result.unwrap_err().contains("covered by the category-level fee payer policy"));
+
+        let result =
+            validate_program_instruction_allowlist(&rule(&program_id, Some("0xzz"), &["signer"]));
+        assert!(result.unwrap_err().contains("Invalid instruction_discriminator '0xzz'"));
+
+        let result = validate_program_instruction_allowlist(&rule(&program_id, None, &["payer"]));
+        assert!(result.unwrap_err().contains("Unknown fee payer role 'payer'"));
+    }
+
+    #[test]
+    fn test_validate_webhook_config() {
+        let webhook = WebhookConfig {
+            url: "https://example.com/kora".to_string(),
+            secret: "secret".to_string(),
+            events: vec![WebhookEvent::Signed],
+        };
+        assert!(validate_webhook_config(&webhook).is_ok());
+
+        let invalid_scheme =
+            WebhookConfig { url: "ftp://example.com".to_string(), ..webhook.clone() };
+        assert!(validate_webhook_config(&invalid_scheme).unwrap_err().contains("http or https"));
+
+        let invalid_url = WebhookConfig { url: "not a url".to_string(), ..webhook.clone() };
+        assert!(validate_webhook_config(&invalid_url).unwrap_err().contains("invalid url"));
+
+        let empty_secret = WebhookConfig { secret: String::new(), ..webhook };
+        assert!(validate_webhook_config(&empty_secret).unwrap_err().contains("secret"));
+    }
+
+    #[test]
+    fn test_validate_mock_oracle_config() {
+        assert!(validate_mock_oracle_config(&MockOracleConfig::default()).is_ok());
+
+        let volatile = MockOracleConfig { volatility_percent: 25.0, ..Default::default() };
+        assert!(validate_mock_oracle_config(&volatile).is_ok());
+
+        let zero_price = MockOracleConfig { base_price_usd: 0.0, ..Default::default() };
+        assert!(validate_mock_oracle_config(&zero_price).unwrap_err().contains("base_price_usd"));
+
+        let too_volatile = MockOracleConfig { volatility_percent: 100.0, ..Default::default() };
+        assert!(validate_mock_oracle_config(&too_volatile)
+            .unwrap_err()
+            .contains("volatility_percent"));
+    }
+
     #[test]
     fn test_validate_token2022_extensions_empty() {
         let config = Token2022Config::default();
@@ -1444,6 +2557,8 @@ mod tests {
                             allow_withdraw: true,
                             allow_authorize: true,
                         },
+                        allowed_receivers: None,
+                        min_allowed_lamports: None,
                     },
                     spl_token: SplTokenInstructionPolicy {
                         allow_transfer: true,
@@ -1458,6 +2573,7 @@ mod tests {
                         allow_initialize_multisig: true,
                         allow_freeze_account: true,
                         allow_thaw_account: true,
+                        allowed_receivers: None,
                     },
                     token_2022: Token2022InstructionPolicy {
                         allow_transfer: true,
@@ -1472,10 +2588,29 @@ mod tests {
                         allow_initialize_multisig: true,
                         allow_freeze_account: true,
                         allow_thaw_account: true,
+                        allowed_receivers: None,
                     },
+                    instruction_type_allowlist: None,
+                    reject_fee_payer_as_unknown_signer: false,
+                    program_instruction_allowlist: vec![],
                 },
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
+                validate_user_token_balance: false,
+                account_type_allowlist: None,
+                follow_cpi: false,
+                mock_oracle: MockOracleConfig::default(),
+                account_for_transfer_fee: false,
+                strict_fee_payer: false,
+                validate_nft_metadata: false,
+                fee_estimation_strategy: FeeEstimationStrategy::default(),
+                max_lookup_table_accounts: None,
+                allow_program_upgrades: false,
+                disallowed_programs: vec![],
+                anchor_programs: vec![],
+                max_blockhash_age_slots: None,
+                use_rpc_fee_estimate: false,
+                parsed_disallowed_accounts: None,
             },
             metrics: MetricsConfig::default(),
             kora: KoraConfig::default(),
@@ -1610,6 +2745,301 @@ mod tests {
         }
     }
 
+    async fn validate_program_lists(
+        allowed_programs: Vec<String>,
+        disallowed_programs: Vec<String>,
+    ) -> Result<Vec<String>, Vec<String>> {
+        let config = ConfigMockBuilder::new()
+            .with_allowed_programs(allowed_programs)
+            .with_disallowed_programs(disallowed_programs)
+            .build();
+        let _ = update_config(config);
+
+        let rpc_client = create_mock_rpc_client_account_not_found();
+        ConfigValidator::validate_with_result(&rpc_client, true).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_anchor_programs() {
+        let rpc_client = create_mock_rpc_client_account_not_found();
+        let anchor_program = |program_id: String, allowed_instructions: Vec<String>| {
+            ConfigMockBuilder::new()
+                .with_anchor_programs(vec![AnchorProgramConfig {
+                    program_id,
+                    allowed_instructions,
+                }])
+                .build()
+        };
+
+        let _ = update_config(anchor_program(Pubkey::new_unique().to_string(), vec![]));
+        let warnings = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap();
+        assert!(warnings.iter().any(|w| w.contains("has no allowed instructions")));
+
+        let _ = update_config(anchor_program(
+            "invalid_program".to_string(),
+            vec!["deposit".to_string()],
+        ));
+        let errors = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("Invalid Anchor program address")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_rpc_proxy() {
+        let rpc_client = create_mock_rpc_client_account_not_found();
+
+        let _ = update_config(ConfigMockBuilder::new().with_rpc_proxy(vec![]).build());
+        let warnings = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap();
+        assert!(warnings.iter().any(|w| w.contains("no proxy_allowed_methods")));
+
+        let _ = update_config(
+            ConfigMockBuilder::new()
+                .with_rpc_proxy(vec!["getBalance".to_string(), "signTransaction".to_string()])
+                .build(),
+        );
+        let warnings = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap();
+        assert!(warnings.iter().any(|w| w.contains("Kora method signTransaction")));
+        assert!(!warnings.iter().any(|w| w.contains("getBalance")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_sign_message_transaction_bytes() {
+        let rpc_client = create_mock_rpc_client_account_not_found();
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.sign_message_allow_transaction_bytes = true;
+        let _ = update_config(config);
+
+        let warnings = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap();
+
+        assert!(warnings.iter().any(|w| w.contains("sign_message_allow_transaction_bytes")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_sign_transaction_batch_size() {
+        let rpc_client = create_mock_rpc_client_account_not_found();
+
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.max_batch_size = 0;
+        let _ = update_config(config);
+        let errors = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("max_batch_size is 0")));
+
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.max_request_body_size = 64 * 1024;
+        config.kora.max_batch_size = 100;
+        let _ = update_config(config);
+        let warnings = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap();
+        assert!(warnings.iter().any(|w| w.contains("max_request_body_size raised")));
+
+        // Disabled batches leave both settings alone
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.enabled_methods.sign_transaction_batch = false;
+        config.kora.max_batch_size = 0;
+        let _ = update_config(config);
+        let warnings = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap();
+        assert!(!warnings.iter().any(|w| w.contains("max_request_body_size raised")));
+
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.max_request_body_size = usize::MAX;
+        let _ = update_config(config);
+        let errors = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("exceeds the maximum")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_auto_compute_budget_limit() {
+        let rpc_client = create_mock_rpc_client_account_not_found();
+
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.auto_compute_budget = Some(AutoComputeBudgetConfig {
+            compute_unit_limit: Some(MAX_COMPUTE_UNITS_PER_TRANSACTION as u32 + 1),
+            compute_unit_price: Some(1_000),
+        });
+        let _ = update_config(config);
+        let errors = ConfigValidator::validate_with_result(&rpc_client, true).await.unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("auto_compute_budget compute_unit_limit")));
+
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.auto_compute_budget = Some(AutoComputeBudgetConfig {
+            compute_unit_limit: Some(MAX_COMPUTE_UNITS_PER_TRANSACTION as u32),
+            compute_unit_price: None,
+        });
+        let _ = update_config(config);
+        assert!(ConfigValidator::validate_with_result(&rpc_client, true).await.is_ok());
+    }
+
+    async fn validate_token_lists(
+        allowed_tokens: &[&Pubkey],
+        allowed_spl_paid_tokens: SplTokenConfig,
+        disallowed_accounts: &[&Pubkey],
+    ) -> Vec<String> {
+        let to_strings =
+            |keys: &[&Pubkey]| keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        let config = ConfigMockBuilder::new()
+            .with_allowed_tokens(to_strings(allowed_tokens))
+            .with_allowed_spl_paid_tokens(allowed_spl_paid_tokens)
+            .with_disallowed_accounts(to_strings(disallowed_accounts))
+            .build();
+        let _ = update_config(config);
+
+        let rpc_client = create_mock_rpc_client_account_not_found();
+        ConfigValidator::validate_with_result(&rpc_client, true).await.err().unwrap_or_default()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_token_list_conflicts() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let paid = |keys: &[&Pubkey]| {
+            SplTokenConfig::Allowlist(keys.iter().map(|key| key.to_string()).collect())
+        };
+        let allowed_conflict =
+            |keys: &str| format!("Tokens in both allowed_tokens and disallowed_accounts: {keys}");
+        let paid_conflict = |keys: &str| {
+            format!("Tokens in both allowed_spl_paid_tokens and disallowed_accounts: {keys}")
+        };
+        let not_allowed = |key: &Pubkey| {
+            format!("Token {key} in allowed_spl_paid_tokens must also be in allowed_tokens")
+        };
+
+        // (allowed_tokens, allowed_spl_paid_tokens, disallowed_accounts, expected errors)
+        let cases: Vec<(Vec<&Pubkey>, SplTokenConfig, Vec<&Pubkey>, Vec<String>)> = vec![
+            // Disjoint lists
+            (vec![&a, &b], paid(&[&a]), vec![&c], vec![]),
+            // Allowed token disallowed
+            (vec![&a, &b], paid(&[&a]), vec![&b], vec![allowed_conflict(&b.to_string())]),
+            // Paid token disallowed, it is an allowed token too
+            (
+                vec![&a, &b],
+                paid(&[&a]),
+                vec![&a],
+                vec![allowed_conflict(&a.to_string()), paid_conflict(&a.to_string())],
+            ),
+            // Every conflict is listed once, in allowed_tokens order
+            (
+                vec![&a, &b, &c, &a],
+                paid(&[&a]),
+                vec![&c, &b, &c],
+                vec![allowed_conflict(&format!("{b}, {c}"))],
+            ),
+            // Paid token missing from allowed_tokens
+            (vec![&a], paid(&[&a, &b]), vec![], vec![not_allowed(&b)]),
+            // Paid token missing from allowed_tokens and disallowed
+            (vec![&a], paid(&[&b]), vec![&b], vec![not_allowed(&b), paid_conflict(&b.to_string())]),
+            // Blocked paid tokens are never accepted, disallowing them too is fine
+            (vec![&a], SplTokenConfig::Blocklist(vec![c.to_string()]), vec![&c], vec![]),
+        ];
+
+        for (index, (allowed_tokens, paid_tokens, disallowed_accounts, expected)) in
+            cases.into_iter().enumerate()
+        {
+            let errors =
+                validate_token_lists(&allowed_tokens, paid_tokens, &disallowed_accounts).await;
+            let list_errors: Vec<&String> = errors
+                .iter()
+                .filter(|e| {
+                    e.contains("and disallowed_accounts") || e.contains("must also be in allowed")
+                })
+                .collect();
+
+            assert_eq!(list_errors.len(), expected.len(), "case {index}: {errors:?}");
+            for expected_error in &expected {
+                assert!(errors.contains(expected_error), "case {index}: {errors:?}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_disallowed_programs_only() {
+        let other_program = Pubkey::new_unique().to_string();
+        let warnings = validate_program_lists(vec![], vec![other_program]).await.unwrap();
+
+        assert!(!warnings.iter().any(|w| w.contains("No allowed programs configured")));
+        assert!(!warnings.iter().any(|w| w.contains("disallowed programs")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_allowed_and_disallowed_programs_conflict() {
+        let other_program = Pubkey::new_unique().to_string();
+        let errors = validate_program_lists(
+            vec![SYSTEM_PROGRAM_ID.to_string(), SPL_TOKEN_PROGRAM_ID.to_string()],
+            vec![other_program],
+        )
+        .await
+        .unwrap_err();
+
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("allowed_programs and disallowed_programs are both set")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_program_in_allowed_and_disallowed_programs() {
+        let rpc_client = create_mock_rpc_client_account_not_found();
+        let config = ConfigMockBuilder::new()
+            .with_allowed_programs(vec![
+                SYSTEM_PROGRAM_ID.to_string(),
+                SPL_TOKEN_PROGRAM_ID.to_string(),
+            ])
+            .with_disallowed_programs(vec![SPL_TOKEN_PROGRAM_ID.to_string()])
+            .build();
+        let _ = update_config(config);
+
+        // Errors are returned without the warnings, read them from the report instead
+        let mut report = Vec::new();
+        let result = ConfigValidator::validate_with_result_and_signers_to(
+            &rpc_client,
+            true,
+            None::<&str>,
+            &mut report,
+        )
+        .await;
+        assert!(result.is_err());
+
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains(&format!(
+            "Program {SPL_TOKEN_PROGRAM_ID} is in both allowed_programs and disallowed_programs"
+        )));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_system_program_disallowed() {
+        let warnings =
+            validate_program_lists(vec![], vec![SYSTEM_PROGRAM_ID.to_string()]).await.unwrap();
+
+        assert!(warnings.iter().any(|w| w.contains("System Program in disallowed programs")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_fees_need_token_program_not_disallowed() {
+        let errors = validate_program_lists(
+            vec![],
+            vec![SPL_TOKEN_PROGRAM_ID.to_string(), TOKEN_2022_PROGRAM_ID.to_string()],
+        )
+        .await
+        .unwrap_err();
+
+        assert!(errors.iter().any(|e| e.contains("at least one token program")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_invalid_disallowed_program() {
+        let errors =
+            validate_program_lists(vec![], vec!["not-a-program".to_string()]).await.unwrap_err();
+
+        assert!(errors.iter().any(|e| e.contains("Invalid disallowed program address")));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_check_token_mint_extensions_permanent_delegate() {
@@ -1662,6 +3092,106 @@ mod tests {
         assert!(warnings[0].contains("custom program executes on every transfer"));
     }
 
+    fn required_extensions_config(mint: &[&str], account: &[&str]) -> Token2022Config {
+        let mut config = Token2022Config::default();
+        config.required_mint_extensions = mint.iter().map(|ext| ext.to_string()).collect();
+        config.required_account_extensions = account.iter().map(|ext| ext.to_string()).collect();
+        config.initialize().unwrap();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_check_required_mint_extensions() {
+        let mint_pubkey = Pubkey::new_unique();
+        let token_2022 = required_extensions_config(&["transfer_fee_config"], &[]);
+
+        let mint =
+            create_mock_token2022_mint_with_extensions(6, vec![ExtensionType::TransferFeeConfig]);
+        let rpc_client = create_mock_rpc_client_with_account(&mint);
+        let mut errors = Vec::new();
+        ConfigValidator::check_required_mint_extensions(
+            &rpc_client,
+            &[mint_pubkey.to_string()],
+            &token_2022,
+            &mut errors,
+        )
+        .await;
+        assert!(errors.is_empty());
+
+        let mint =
+            create_mock_token2022_mint_with_extensions(6, vec![ExtensionType::MintCloseAuthority]);
+        let rpc_client = create_mock_rpc_client_with_account(&mint);
+        ConfigValidator::check_required_mint_extensions(
+            &rpc_client,
+            &[mint_pubkey.to_string()],
+            &token_2022,
+            &mut errors,
+        )
+        .await;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains(&mint_pubkey.to_string()));
+        assert!(errors[0].contains("missing required mint extension TransferFeeConfig"));
+    }
+
+    #[tokio::test]
+    async fn test_check_required_mint_extensions_ignores_spl_token_mints() {
+        let token_2022 = required_extensions_config(&["transfer_fee_config"], &[]);
+        let rpc_client = create_mock_rpc_client_with_mint(6);
+        let mut errors = Vec::new();
+
+        ConfigValidator::check_required_mint_extensions(
+            &rpc_client,
+            &[Pubkey::new_unique().to_string()],
+            &token_2022,
+            &mut errors,
+        )
+        .await;
+        assert!(errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_required_account_extensions() {
+        let (payment_address, mint_pubkey) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let token_2022 = required_extensions_config(&[], &["memo_transfer"]);
+
+        // The mock returns the same Token2022 account for the mint and the payment ATA
+        let token_account = TokenAccountMockBuilder::new()
+            .with_owner(&payment_address)
+            .with_mint(&mint_pubkey)
+            .with_extension(ExtensionType::MemoTransfer)
+            .build_token2022_with_extensions()
+            .unwrap();
+        let rpc_client = create_mock_rpc_client_with_account(&token_account);
+        let mut errors = Vec::new();
+        ConfigValidator::check_required_account_extensions(
+            &rpc_client,
+            &payment_address,
+            &[mint_pubkey.to_string()],
+            &token_2022,
+            &mut errors,
+        )
+        .await;
+        assert!(errors.is_empty());
+
+        let token_account = TokenAccountMockBuilder::new()
+            .with_owner(&payment_address)
+            .with_mint(&mint_pubkey)
+            .with_extension(ExtensionType::CpiGuard)
+            .build_token2022_with_extensions()
+            .unwrap();
+        let rpc_client = create_mock_rpc_client_with_account(&token_account);
+        ConfigValidator::check_required_account_extensions(
+            &rpc_client,
+            &payment_address,
+            &[mint_pubkey.to_string()],
+            &token_2022,
+            &mut errors,
+        )
+        .await;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("missing required account extension MemoTransfer"));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_check_token_mint_extensions_both() {
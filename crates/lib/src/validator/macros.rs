@@ -4,7 +4,9 @@ macro_rules! validate_system {
         for instruction in $instructions.get(&ParsedSystemInstructionType::$type).unwrap_or(&vec![])
         {
             if let $pattern = instruction {
-                if *$account == $self.fee_payer_pubkey && !$policy {
+                if *$account == $self.fee_payer_pubkey
+                    && !$self.fee_payer_policy.allows_instruction_type(stringify!($type), $policy)
+                {
                     return Err(KoraError::InvalidTransaction(format!(
                         "Fee payer cannot be used for '{}'",
                         $name
@@ -25,7 +27,9 @@ macro_rules! validate_spl {
                 } else {
                     ($spl_policy, $name_spl)
                 };
-                if *$account == $self.fee_payer_pubkey && !allowed {
+                if *$account == $self.fee_payer_pubkey
+                    && !$self.fee_payer_policy.allows_instruction_type(stringify!($type), allowed)
+                {
                     return Err(KoraError::InvalidTransaction(format!(
                         "Fee payer cannot be used for '{}'",
                         name
@@ -47,7 +51,9 @@ macro_rules! validate_spl_multisig {
                     ($spl_policy, $name_spl)
                 };
                 // Check if fee payer is one of the signers
-                if $signers.contains(&$self.fee_payer_pubkey) && !allowed {
+                if $signers.contains(&$self.fee_payer_pubkey)
+                    && !$self.fee_payer_policy.allows_instruction_type(stringify!($type), allowed)
+                {
                     return Err(KoraError::InvalidTransaction(format!(
                         "Fee payer cannot be used for '{}'",
                         name
@@ -1,4 +1,5 @@
 pub mod account_validator;
+pub mod blockhash_validator;
 pub mod cache_validator;
 pub mod config_validator;
 pub mod math_validator;
@@ -6,3 +7,4 @@ pub mod math_validator;
 pub mod macros;
 pub mod signer_validator;
 pub mod transaction_validator;
+pub mod validator_set;
@@ -1,7 +1,20 @@
 use crate::{
     error::KoraError,
-    signer::{SelectionStrategy, SignerPoolConfig},
+    signer::{
+        KeypairUtil, KoraSigner, SelectionStrategy, SignerConfig, SignerPool, SignerPoolConfig,
+        SignerTypeConfig,
+    },
 };
+use reqwest::Url;
+use solana_keychain::SolanaSigner;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{env, fmt::Display, future::Future, path::Path, time::Instant};
+
+/// Known 32-byte message signed by each signer when verifying signing capability
+pub const SIGNING_CAPABILITY_TEST_MESSAGE: &[u8; 32] = b"kora-signing-capability-check-v1";
+
+/// Pools with more signers than this get a warning during config validation
+const LARGE_SIGNER_POOL_THRESHOLD: usize = 10;
 
 pub struct SignerValidator {}
 
@@ -28,9 +41,224 @@ impl SignerValidator {
         // Generate strategy-specific warnings
         Self::validate_strategy_warnings(config, &mut warnings);
 
+        Self::validate_pool_size(config, &mut errors, &mut warnings);
+
         (warnings, errors)
     }
 
+    /// Validate a signers config file on its own, without RPC calls or touching global state.
+    /// On top of `validate_with_result`, checks the keys, pubkeys and URLs found in the
+    /// environment variables the signers reference.
+    pub fn validate_signers_only<P: AsRef<Path>>(path: P) -> Result<Vec<String>, Vec<String>> {
+        let (warnings, errors) = match SignerPoolConfig::load_config(path) {
+            Ok(config) => {
+                let (mut warnings, mut errors) = Self::validate_with_result(&config);
+                for signer in &config.signers {
+                    Self::validate_signer_env(signer, &mut errors, &mut warnings);
+                }
+                (warnings, errors)
+            }
+            Err(e) => (vec![], vec![format!("Failed to load signers config: {e}")]),
+        };
+
+        println!("=== Signers Validation ===");
+        if errors.is_empty() {
+            println!("✓ Signers validation successful!");
+        } else {
+            println!("✗ Signers validation failed!");
+            println!("\n❌ Errors:");
+            for error in &errors {
+                println!("   - {error}");
+            }
+        }
+
+        if !warnings.is_empty() {
+            println!("\n⚠️  Warnings:");
+            for warning in &warnings {
+                println!("   - {warning}");
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check the format of the values behind a signer's environment variables, unset variables
+    /// only warn since signers are often validated away from the machine holding the secrets
+    fn validate_signer_env(
+        signer: &SignerConfig,
+        errors: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) {
+        let name = &signer.name;
+        let mut read_env = |env_var: &str| match env::var(env_var) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                warnings.push(format!(
+                    "Environment variable '{env_var}' for signer '{name}' is not set, its format was not checked"
+                ));
+                None
+            }
+        };
+
+        match &signer.config {
+            SignerTypeConfig::Memory { config } => {
+                if let Some(private_key) = read_env(&config.private_key_env) {
+                    if let Err(e) = KeypairUtil::from_private_key_string(&private_key) {
+                        errors.push(format!("Signer '{name}' has an invalid private key: {e}"));
+                    }
+                }
+            }
+            SignerTypeConfig::Turnkey { config } => {
+                if let Some(public_key) = read_env(&config.public_key_env) {
+                    Self::validate_pubkey_value(name, &public_key, errors);
+                }
+            }
+            SignerTypeConfig::Vault { config } => {
+                if let Some(vault_addr) = read_env(&config.vault_addr_env) {
+                    Self::validate_url_value(name, &vault_addr, errors);
+                }
+                if let Some(pubkey) = read_env(&config.pubkey_env) {
+                    Self::validate_pubkey_value(name, &pubkey, errors);
+                }
+            }
+            SignerTypeConfig::AwsKms { config } => {
+                let key_arn = read_env(&config.key_arn_env);
+                let role_arn = config.assume_role_arn_env.as_deref().and_then(&mut read_env);
+
+                // KMS rejects malformed ARNs itself, a warning is enough to point at the typo
+                if key_arn.is_some_and(|arn| !Self::is_kms_key_arn(&arn)) {
+                    warnings.push(format!(
+                        "Signer '{name}' has a key ARN that is not a KMS key or alias ARN"
+                    ));
+                }
+                if role_arn.is_some_and(|arn| !Self::is_iam_role_arn(&arn)) {
+                    warnings.push(format!(
+                        "Signer '{name}' has a role ARN that is not an IAM role ARN"
+                    ));
+                }
+            }
+            // Privy only takes app and wallet ids, its API URL is fixed by the client
+            SignerTypeConfig::Privy { .. } => {}
+            // Ledger signers are configured without environment variables
+            SignerTypeConfig::Ledger { .. } => {}
+        }
+    }
+
+    /// Region and resource of `arn:<partition>:<service>:<region>:<account>:<resource>`
+    fn split_arn<'a>(value: &'a str, service: &str) -> Option<(&'a str, &'a str)> {
+        match value.splitn(6, ':').collect::<Vec<_>>().as_slice() {
+            ["arn", partition, arn_service, region, account, resource]
+                if partition.starts_with("aws")
+                    && *arn_service == service
+                    && account.len() == 12
+                    && account.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                Some((*region, *resource))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_kms_key_arn(value: &str) -> bool {
+        matches!(
+            Self::split_arn(value, "kms"),
+            Some((region, resource)) if !region.is_empty()
+                && resource
+                    .strip_prefix("key/")
+                    .or_else(|| resource.strip_prefix("alias/"))
+                    .is_some_and(|id| !id.is_empty())
+        )
+    }
+
+    /// IAM is global, its ARNs have no region
+    fn is_iam_role_arn(value: &str) -> bool {
+        matches!(
+            Self::split_arn(value, "iam"),
+            Some(("", resource)) if resource.strip_prefix("role/").is_some_and(|role| !role.is_empty())
+        )
+    }
+
+    /// A signer pubkey must be 32 base58-encoded bytes on the ed25519 curve
+    fn validate_pubkey_value(name: &str, value: &str, errors: &mut Vec<String>) {
+        let decoded = match bs58::decode(value).into_vec() {
+            Ok(decoded) => decoded,
+            Err(_) => {
+                errors.push(format!("Signer '{name}' has a public key that is not valid base58"));
+                return;
+            }
+        };
+
+        match Pubkey::try_from(decoded.as_slice()) {
+            Ok(pubkey) if pubkey.is_on_curve() => {}
+            Ok(_) => errors.push(format!("Signer '{name}' has a public key that is off the curve")),
+            Err(_) => errors.push(format!(
+                "Signer '{name}' has a public key of {} bytes, expected 32",
+                decoded.len()
+            )),
+        }
+    }
+
+    fn validate_url_value(name: &str, value: &str, errors: &mut Vec<String>) {
+        match Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {}
+            _ => errors.push(format!("Signer '{name}' has an invalid URL: {value}")),
+        }
+    }
+
+    /// Sign the test message with every signer in the pool, failing on the first signer that can't
+    pub async fn verify_signing_capability(pool: &SignerPool) -> Result<(), KoraError> {
+        for signer_info in pool.get_signers_info() {
+            let signer = pool.get_signer_by_pubkey(&signer_info.public_key)?;
+            // The Solana app only signs transactions, each needing a confirmation on the device.
+            // Ledger signers already proved they are reachable when the pool was built
+            if matches!(*signer, KoraSigner::Ledger(_)) {
+                continue;
+            }
+            Self::verify_signer_can_sign(
+                &signer_info.name,
+                &signer.pubkey(),
+                |message| async move { signer.sign_message(message).await },
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Run a single signing attempt and check the returned signature against `pubkey`
+    async fn verify_signer_can_sign<F, Fut, E>(
+        name: &str,
+        pubkey: &Pubkey,
+        sign: F,
+    ) -> Result<(), KoraError>
+    where
+        F: FnOnce(&'static [u8]) -> Fut,
+        Fut: Future<Output = Result<Signature, E>>,
+        E: Display,
+    {
+        let start = Instant::now();
+        let result = sign(SIGNING_CAPABILITY_TEST_MESSAGE).await;
+        let latency = start.elapsed();
+
+        let signature = result.map_err(|e| {
+            log::error!("Signer '{name}' failed to sign test message after {latency:?}: {e}");
+            KoraError::SigningError(format!("Signer '{name}' failed to sign test message: {e}"))
+        })?;
+
+        if !signature.verify(pubkey.as_ref(), SIGNING_CAPABILITY_TEST_MESSAGE) {
+            return Err(KoraError::SigningError(format!(
+                "Signer '{name}' produced an invalid signature for {pubkey}"
+            )));
+        }
+
+        log::info!("Signer '{name}' signed test message in {latency:?}");
+        Ok(())
+    }
+
     /// Helper method to convert Result to error string and add to errors vec
     fn try_result(result: Result<(), KoraError>, errors: &mut Vec<String>) {
         if let Err(KoraError::ValidationError(msg)) = result {
@@ -38,6 +266,29 @@ impl SignerValidator {
         }
     }
 
+    /// Enforce `max_signers`, and warn about large pools even when no limit is set
+    fn validate_pool_size(
+        config: &SignerPoolConfig,
+        errors: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) {
+        let signer_count = config.signers.len();
+
+        if let Some(max_signers) = config.max_signers {
+            if signer_count > max_signers {
+                errors.push(format!(
+                    "Signer pool has {signer_count} signers, more than max_signers ({max_signers})"
+                ));
+            }
+        }
+
+        if signer_count > LARGE_SIGNER_POOL_THRESHOLD {
+            warnings.push(format!(
+                "Signer pool has {signer_count} signers, large pools complicate key management - consider setting max_signers"
+            ));
+        }
+    }
+
     /// Generate strategy-specific warnings (warnings don't fail fast)
     fn validate_strategy_warnings(config: &SignerPoolConfig, warnings: &mut Vec<String>) {
         match config.signer_pool.strategy {
@@ -51,6 +302,8 @@ impl SignerValidator {
                     }
                 }
             }
+            // Weights break ties between equally busy signers
+            SelectionStrategy::LeastBusy => {}
             _ => {
                 // For non-weighted strategies, warn if weights are specified
                 for signer in &config.signers {
@@ -70,9 +323,13 @@ impl SignerValidator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::signer::config::{
-        MemorySignerConfig, SignerConfig, SignerPoolSettings, SignerTypeConfig,
+    use crate::signer::{
+        config::{AwsKmsSignerConfig, MemorySignerConfig, SignerPoolSettings, VaultSignerConfig},
+        pool::SignerWithMetadata,
     };
+    use solana_keychain::Signer;
+    use solana_sdk::signature::{Keypair, Signer as _};
+    use std::sync::Arc;
 
     #[test]
     fn test_validate_with_result_warnings() {
@@ -85,6 +342,10 @@ mod tests {
                     config: MemorySignerConfig { private_key_env: "TEST_KEY".to_string() },
                 },
             }],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
         };
 
         let (warnings, errors) = SignerValidator::validate_with_result(&config);
@@ -93,6 +354,108 @@ mod tests {
         assert!(warnings[0].contains("weight will be ignored"));
     }
 
+    #[test]
+    fn test_validate_signer_env_memory() {
+        let signer = SignerConfig {
+            name: "memory_signer".to_string(),
+            weight: None,
+            config: SignerTypeConfig::Memory {
+                config: MemorySignerConfig {
+                    private_key_env: "SIGNERS_ONLY_TEST_PRIVATE_KEY".to_string(),
+                },
+            },
+        };
+        let validate = || {
+            let (mut errors, mut warnings) = (vec![], vec![]);
+            SignerValidator::validate_signer_env(&signer, &mut errors, &mut warnings);
+            (errors, warnings)
+        };
+
+        let (errors, warnings) = validate();
+        assert!(errors.is_empty());
+        assert!(warnings[0].contains("is not set"));
+
+        std::env::set_var("SIGNERS_ONLY_TEST_PRIVATE_KEY", Keypair::new().to_base58_string());
+        assert_eq!(validate(), (vec![], vec![]));
+
+        // A pubkey is only 32 bytes
+        std::env::set_var("SIGNERS_ONLY_TEST_PRIVATE_KEY", Pubkey::new_unique().to_string());
+        let (errors, _) = validate();
+        assert!(errors[0].contains("invalid private key"));
+    }
+
+    #[test]
+    fn test_validate_signer_env_vault() {
+        let signer = SignerConfig {
+            name: "vault_signer".to_string(),
+            weight: None,
+            config: SignerTypeConfig::Vault {
+                config: VaultSignerConfig {
+                    vault_addr_env: "SIGNERS_ONLY_TEST_VAULT_ADDR".to_string(),
+                    vault_token_env: "SIGNERS_ONLY_TEST_VAULT_TOKEN".to_string(),
+                    key_name_env: "SIGNERS_ONLY_TEST_VAULT_KEY_NAME".to_string(),
+                    pubkey_env: "SIGNERS_ONLY_TEST_VAULT_PUBKEY".to_string(),
+                },
+            },
+        };
+        std::env::set_var("SIGNERS_ONLY_TEST_VAULT_ADDR", "https://vault.example.com:8200");
+        std::env::set_var("SIGNERS_ONLY_TEST_VAULT_PUBKEY", Keypair::new().pubkey().to_string());
+
+        let (mut errors, mut warnings) = (vec![], vec![]);
+        SignerValidator::validate_signer_env(&signer, &mut errors, &mut warnings);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        std::env::set_var("SIGNERS_ONLY_TEST_VAULT_ADDR", "vault.example.com");
+        // PDAs are off the curve
+        let (pda, _) = Pubkey::find_program_address(&[b"seed"], &Pubkey::new_unique());
+        std::env::set_var("SIGNERS_ONLY_TEST_VAULT_PUBKEY", pda.to_string());
+
+        let (mut errors, mut warnings) = (vec![], vec![]);
+        SignerValidator::validate_signer_env(&signer, &mut errors, &mut warnings);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("invalid URL"));
+        assert!(errors[1].contains("off the curve"));
+    }
+
+    #[test]
+    fn test_validate_signer_env_aws_kms() {
+        let signer = SignerConfig {
+            name: "kms_signer".to_string(),
+            weight: None,
+            config: SignerTypeConfig::AwsKms {
+                config: AwsKmsSignerConfig {
+                    key_arn_env: "SIGNERS_ONLY_TEST_KMS_KEY_ARN".to_string(),
+                    region_env: "SIGNERS_ONLY_TEST_KMS_REGION".to_string(),
+                    assume_role_arn_env: Some("SIGNERS_ONLY_TEST_KMS_ROLE_ARN".to_string()),
+                },
+            },
+        };
+        std::env::set_var(
+            "SIGNERS_ONLY_TEST_KMS_KEY_ARN",
+            "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab",
+        );
+        std::env::set_var("SIGNERS_ONLY_TEST_KMS_ROLE_ARN", "arn:aws:iam::123456789012:role/kora");
+
+        let (mut errors, mut warnings) = (vec![], vec![]);
+        SignerValidator::validate_signer_env(&signer, &mut errors, &mut warnings);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        // A bare key id and a role ARN with a region
+        std::env::set_var("SIGNERS_ONLY_TEST_KMS_KEY_ARN", "1234abcd-12ab-34cd-56ef-1234567890ab");
+        std::env::set_var(
+            "SIGNERS_ONLY_TEST_KMS_ROLE_ARN",
+            "arn:aws:iam:us-east-1:123456789012:role/kora",
+        );
+
+        let (mut errors, mut warnings) = (vec![], vec![]);
+        SignerValidator::validate_signer_env(&signer, &mut errors, &mut warnings);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("key ARN"));
+        assert!(warnings[1].contains("role ARN"));
+    }
+
     #[test]
     fn test_validate_duplicate_names() {
         let config = SignerPoolConfig {
@@ -113,6 +476,10 @@ mod tests {
                     },
                 },
             ],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
         };
 
         let (_warnings, errors) = SignerValidator::validate_with_result(&config);
@@ -131,6 +498,10 @@ mod tests {
                     config: MemorySignerConfig { private_key_env: "TEST_KEY".to_string() },
                 },
             }],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
         };
 
         let (_warnings, errors) = SignerValidator::validate_with_result(&config);
@@ -143,10 +514,106 @@ mod tests {
         let config = SignerPoolConfig {
             signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
             signers: vec![],
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
         };
 
         let (_warnings, errors) = SignerValidator::validate_with_result(&config);
         assert!(!errors.is_empty());
         assert!(errors.iter().any(|e| e.contains("At least one signer must be configured")));
     }
+
+    #[test]
+    fn test_validate_with_result_large_pool() {
+        let signers: Vec<SignerConfig> = (0..11)
+            .map(|i| SignerConfig {
+                name: format!("signer_{i}"),
+                weight: None,
+                config: SignerTypeConfig::Memory {
+                    config: MemorySignerConfig { private_key_env: format!("TEST_KEY_{i}") },
+                },
+            })
+            .collect();
+        let mut config = SignerPoolConfig {
+            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signers,
+            verify_signing_capability: false,
+            max_signers: None,
+            concurrent_signing_limit: 0,
+            health_check: Default::default(),
+        };
+
+        let (warnings, errors) = SignerValidator::validate_with_result(&config);
+        assert!(errors.is_empty());
+        assert!(warnings.iter().any(|w| w.contains("Signer pool has 11 signers")));
+
+        config.max_signers = Some(10);
+        let (_warnings, errors) = SignerValidator::validate_with_result(&config);
+        assert_eq!(errors, vec!["Signer pool has 11 signers, more than max_signers (10)"]);
+    }
+
+    #[tokio::test]
+    async fn test_verify_signer_can_sign_success() {
+        let keypair = Keypair::new();
+
+        let result =
+            SignerValidator::verify_signer_can_sign("test_signer", &keypair.pubkey(), |message| {
+                let signature = keypair.sign_message(message);
+                async move { Ok::<_, KoraError>(signature) }
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_signer_can_sign_signer_error() {
+        let result = SignerValidator::verify_signer_can_sign(
+            "vault_signer",
+            &Pubkey::new_unique(),
+            |_| async { Err::<Signature, _>("vault token expired") },
+        )
+        .await;
+
+        match result {
+            Err(KoraError::SigningError(msg)) => {
+                assert!(msg.contains("vault_signer"));
+                assert!(msg.contains("vault token expired"));
+            }
+            other => panic!("Expected SigningError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_signer_can_sign_invalid_signature() {
+        let keypair = Keypair::new();
+
+        // Signature from a different key must not pass as the signer's
+        let result = SignerValidator::verify_signer_can_sign(
+            "privy_signer",
+            &Pubkey::new_unique(),
+            |message| {
+                let signature = keypair.sign_message(message);
+                async move { Ok::<_, KoraError>(signature) }
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(KoraError::SigningError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_signing_capability_memory_pool() {
+        let keypair = Keypair::new();
+        let signer = Signer::from_memory(&keypair.to_base58_string()).unwrap();
+        let pool = SignerPool::new(vec![SignerWithMetadata::new(
+            "memory_signer".to_string(),
+            Arc::new(signer.into()),
+            1,
+        )]);
+
+        assert!(SignerValidator::verify_signing_capability(&pool).await.is_ok());
+    }
 }
@@ -1,30 +1,132 @@
 use crate::{
+    cache::CacheUtil,
     config::FeePayerPolicy,
     error::KoraError,
     fee::fee::{FeeConfigUtil, TotalFeeCalculation},
     oracle::PriceSource,
     state::get_config,
-    token::{interface::TokenMint, token::TokenUtil},
+    token::{
+        interface::TokenMint,
+        spl_token_2022::Token2022Mint,
+        token::{TokenType, TokenUtil},
+    },
     transaction::{
-        ParsedSPLInstructionData, ParsedSPLInstructionType, ParsedSystemInstructionData,
-        ParsedSystemInstructionType, VersionedTransactionResolved,
+        IxUtils, ParsedSPLInstructionData, ParsedSPLInstructionType, VersionedTransactionResolved,
+    },
+    validator::{
+        account_validator::{
+            validate_nft_metadata, validate_token_account_not_frozen_state, AccountType,
+        },
+        blockhash_validator::{BlockhashAgeValidator, RECENT_BLOCKHASHES},
+        validator_set::{
+            AllowlistValidator, AnchorInstructionValidator, DisallowedAccountsValidator,
+            FeePayerWritableValidator, FeePolicyValidator, InnerInstructionsValidator,
+            SizeValidator, ValidatorSet,
+        },
     },
 };
+use parking_lot::Mutex;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
-use std::str::FromStr;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
 
 use crate::fee::price::PriceModel;
 
+/// BPF Upgradeable Loader, `UpgradeableLoaderInstruction` is bincode encoded with a u32 tag
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111");
+const UPGRADEABLE_LOADER_UPGRADE: u32 = 3;
+const UPGRADEABLE_LOADER_SET_AUTHORITY: u32 = 4;
+const UPGRADEABLE_LOADER_SET_AUTHORITY_CHECKED: u32 = 7;
+
+struct ContextAccount {
+    /// `None` when the account doesn't exist
+    account: Option<Account>,
+    /// Fetched with `force_refresh`, so it didn't come from the cache
+    refreshed: bool,
+}
+
+/// Accounts fetched during a single validation run
+///
+/// Checks referencing the same account (outflow, account types, payment, extensions) share a
+/// single `getAccountInfo` call. Create one per transaction and drop it once validation is done.
+#[derive(Default)]
+pub struct ValidationContext {
+    accounts: Mutex<HashMap<Pubkey, ContextAccount>>,
+}
+
+impl ValidationContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same as `CacheUtil::get_account`, but an account already fetched in this run is reused,
+    /// unless `force_refresh` is set and the stored account may have come from the cache
+    pub async fn get_account(
+        &self,
+        rpc_client: &RpcClient,
+        pubkey: &Pubkey,
+        force_refresh: bool,
+    ) -> Result<Account, KoraError> {
+        if let Some(cached) = self.accounts.lock().get(pubkey) {
+            if cached.refreshed || !force_refresh {
+                return cached
+                    .account
+                    .clone()
+                    .ok_or_else(|| KoraError::AccountNotFound(pubkey.to_string()));
+            }
+        }
+
+        let account = match CacheUtil::get_account(rpc_client, pubkey, force_refresh).await {
+            Ok(account) => Some(account),
+            Err(KoraError::AccountNotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        self.accounts
+            .lock()
+            .insert(*pubkey, ContextAccount { account: account.clone(), refreshed: force_refresh });
+
+        account.ok_or_else(|| KoraError::AccountNotFound(pubkey.to_string()))
+    }
+
+    /// Detect the type of an account already fetched in this run, falls back to
+    /// `CacheUtil::get_account_type` (which has its own cache) otherwise
+    pub async fn get_account_type(
+        &self,
+        rpc_client: &RpcClient,
+        pubkey: &Pubkey,
+    ) -> Result<Option<AccountType>, KoraError> {
+        if let Some(cached) = self.accounts.lock().get(pubkey) {
+            return Ok(match &cached.account {
+                Some(account) => AccountType::detect(account, pubkey),
+                None => Some(AccountType::System),
+            });
+        }
+
+        CacheUtil::get_account_type(rpc_client, pubkey).await
+    }
+}
+
 pub struct TransactionValidator {
     fee_payer_pubkey: Pubkey,
     max_allowed_lamports: u64,
     allowed_programs: Vec<Pubkey>,
+    disallowed_programs: Vec<Pubkey>,
+    anchor_discriminators: HashMap<Pubkey, HashSet<[u8; 8]>>,
     max_signatures: u64,
     allowed_tokens: Vec<Pubkey>,
-    disallowed_accounts: Vec<Pubkey>,
+    disallowed_accounts: Arc<HashSet<Pubkey>>,
     _price_source: PriceSource,
     fee_payer_policy: FeePayerPolicy,
+    account_type_allowlist: Option<Vec<AccountType>>,
+    follow_cpi: bool,
+    validate_nft_metadata: bool,
+    allow_program_upgrades: bool,
+    max_blockhash_age_slots: Option<u64>,
 }
 
 impl TransactionValidator {
@@ -32,22 +134,44 @@ impl TransactionValidator {
         let config = &get_config()?.validation;
 
         // Convert string program IDs to Pubkeys
-        let allowed_programs = config
-            .allowed_programs
+        let parse_programs = |programs: &[String]| {
+            programs
+                .iter()
+                .map(|addr| {
+                    Pubkey::from_str(addr).map_err(|e| {
+                        KoraError::InternalServerError(format!(
+                            "Invalid program address in config: {e}"
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<Pubkey>, KoraError>>()
+        };
+        let allowed_programs = parse_programs(&config.allowed_programs)?;
+        let disallowed_programs = parse_programs(&config.disallowed_programs)?;
+        let anchor_discriminators = config
+            .anchor_programs
             .iter()
-            .map(|addr| {
-                Pubkey::from_str(addr).map_err(|e| {
+            .map(|program| {
+                let program_id = Pubkey::from_str(&program.program_id).map_err(|e| {
                     KoraError::InternalServerError(format!(
-                        "Invalid program address in config: {e}"
+                        "Invalid Anchor program address in config: {e}"
                     ))
-                })
+                })?;
+                let discriminators = program
+                    .allowed_instructions
+                    .iter()
+                    .map(|name| IxUtils::anchor_discriminator(name))
+                    .collect();
+                Ok((program_id, discriminators))
             })
-            .collect::<Result<Vec<Pubkey>, KoraError>>()?;
+            .collect::<Result<HashMap<_, _>, KoraError>>()?;
 
         Ok(Self {
             fee_payer_pubkey,
             max_allowed_lamports: config.max_allowed_lamports,
             allowed_programs,
+            disallowed_programs,
+            anchor_discriminators,
             max_signatures: config.max_signatures,
             _price_source: config.price_source.clone(),
             allowed_tokens: config
@@ -58,17 +182,13 @@ impl TransactionValidator {
                 .map_err(|e| {
                     KoraError::InternalServerError(format!("Invalid allowed token address: {e}"))
                 })?,
-            disallowed_accounts: config
-                .disallowed_accounts
-                .iter()
-                .map(|addr| Pubkey::from_str(addr))
-                .collect::<Result<Vec<Pubkey>, _>>()
-                .map_err(|e| {
-                    KoraError::InternalServerError(format!(
-                        "Invalid disallowed account address: {e}"
-                    ))
-                })?,
+            disallowed_accounts: config.get_disallowed_accounts()?,
             fee_payer_policy: config.fee_payer_policy.clone(),
+            account_type_allowlist: config.account_type_allowlist.clone(),
+            follow_cpi: config.follow_cpi,
+            validate_nft_metadata: config.validate_nft_metadata,
+            allow_program_upgrades: config.allow_program_upgrades,
+            max_blockhash_age_slots: config.max_blockhash_age_slots,
         })
     }
 
@@ -97,28 +217,70 @@ impl TransactionValidator {
         transaction_resolved: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
     ) -> Result<(), KoraError> {
-        if transaction_resolved.all_instructions.is_empty() {
-            return Err(KoraError::InvalidTransaction(
-                "Transaction contains no instructions".to_string(),
-            ));
+        self.validate_transaction_with_context(
+            transaction_resolved,
+            rpc_client,
+            &ValidationContext::new(),
+        )
+        .await
+    }
+
+    /// Validate a transaction, sharing fetched accounts with the other checks of the same run
+    pub async fn validate_transaction_with_context(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
+    ) -> Result<(), KoraError> {
+        if self.follow_cpi {
+            InnerInstructionsValidator.validate(transaction_resolved)?;
         }
 
-        if transaction_resolved.all_account_keys.is_empty() {
-            return Err(KoraError::InvalidTransaction(
-                "Transaction contains no account keys".to_string(),
-            ));
+        SizeValidator { max_signatures: self.max_signatures }
+            .chain(self.program_allowlist())
+            .chain(AnchorInstructionValidator {
+                allowed_discriminators: &self.anchor_discriminators,
+            })
+            .validate(transaction_resolved)?;
+
+        self.validate_program_upgrades(transaction_resolved)?;
+
+        if let Some(max_age_slots) = self.max_blockhash_age_slots {
+            BlockhashAgeValidator { max_age_slots, recent_blockhashes: &RECENT_BLOCKHASHES }
+                .validate(transaction_resolved, rpc_client)
+                .await?;
         }
 
-        self.validate_signatures(&transaction_resolved.transaction)?;
+        self.validate_transfer_amounts(transaction_resolved, rpc_client, validation_ctx).await?;
+        self.validate_transfer_hooks(transaction_resolved, rpc_client, validation_ctx).await?;
+        self.validate_transfer_sources_not_frozen(transaction_resolved, rpc_client, validation_ctx)
+            .await?;
 
-        self.validate_programs(transaction_resolved)?;
-        self.validate_transfer_amounts(transaction_resolved, rpc_client).await?;
-        self.validate_disallowed_accounts(transaction_resolved)?;
-        self.validate_fee_payer_usage(transaction_resolved)?;
+        DisallowedAccountsValidator { disallowed_accounts: &self.disallowed_accounts }
+            .chain(FeePolicyValidator {
+                fee_payer_pubkey: self.fee_payer_pubkey,
+                fee_payer_policy: &self.fee_payer_policy,
+            })
+            .chain(FeePayerWritableValidator {
+                fee_payer_pubkey: self.fee_payer_pubkey,
+                fee_payer_policy: &self.fee_payer_policy,
+            })
+            .validate(transaction_resolved)?;
+
+        self.validate_account_types(transaction_resolved, rpc_client, validation_ctx).await?;
+        self.validate_nft_metadata_accounts(transaction_resolved, rpc_client, validation_ctx)
+            .await?;
 
         Ok(())
     }
 
+    fn program_allowlist(&self) -> AllowlistValidator<'_> {
+        AllowlistValidator {
+            allowed_programs: &self.allowed_programs,
+            disallowed_programs: &self.disallowed_programs,
+        }
+    }
+
     pub fn validate_lamport_fee(&self, fee: u64) -> Result<(), KoraError> {
         if fee > self.max_allowed_lamports {
             return Err(KoraError::InvalidTransaction(format!(
@@ -129,194 +291,229 @@ impl TransactionValidator {
         Ok(())
     }
 
-    fn validate_signatures(&self, transaction: &VersionedTransaction) -> Result<(), KoraError> {
-        if transaction.signatures.len() > self.max_signatures as usize {
-            return Err(KoraError::InvalidTransaction(format!(
-                "Too many signatures: {} > {}",
-                transaction.signatures.len(),
-                self.max_signatures
-            )));
+    /// Reject program upgrades and upgrade authority changes, including ones made through CPI,
+    /// unless `allow_program_upgrades` is set
+    fn validate_program_upgrades(
+        &self,
+        transaction_resolved: &VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        if self.allow_program_upgrades {
+            return Ok(());
         }
 
-        if transaction.signatures.is_empty() {
-            return Err(KoraError::InvalidTransaction("No signatures found".to_string()));
+        for instruction in &transaction_resolved.all_instructions {
+            if instruction.program_id != BPF_LOADER_UPGRADEABLE_PROGRAM_ID {
+                continue;
+            }
+
+            let tag =
+                instruction.data.get(..4).map(|tag| u32::from_le_bytes(tag.try_into().unwrap()));
+            if matches!(
+                tag,
+                Some(
+                    UPGRADEABLE_LOADER_UPGRADE
+                        | UPGRADEABLE_LOADER_SET_AUTHORITY
+                        | UPGRADEABLE_LOADER_SET_AUTHORITY_CHECKED
+                )
+            ) {
+                return Err(KoraError::ValidationError(
+                    "Upgrade instructions not permitted".to_string(),
+                ));
+            }
         }
 
         Ok(())
     }
 
-    fn validate_programs(
+    async fn validate_transfer_amounts(
         &self,
-        transaction_resolved: &VersionedTransactionResolved,
+        transaction_resolved: &mut VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
     ) -> Result<(), KoraError> {
-        for instruction in &transaction_resolved.all_instructions {
-            if !self.allowed_programs.contains(&instruction.program_id) {
-                return Err(KoraError::InvalidTransaction(format!(
-                    "Program {} is not in the allowed list",
-                    instruction.program_id
-                )));
-            }
+        let total_outflow =
+            self.calculate_total_outflow(transaction_resolved, rpc_client, validation_ctx).await?;
+
+        if total_outflow > self.max_allowed_lamports {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Total transfer amount {} exceeds maximum allowed {}",
+                total_outflow, self.max_allowed_lamports
+            )));
         }
+
         Ok(())
     }
 
-    fn validate_fee_payer_usage(
+    /// Token2022 transfers CPI into the transfer hook program of their mint, reject hook programs
+    /// outside of `allowed_programs` before the transaction fails on-chain
+    async fn validate_transfer_hooks(
         &self,
         transaction_resolved: &mut VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
     ) -> Result<(), KoraError> {
-        let system_instructions = transaction_resolved.get_or_parse_system_instructions()?;
-
-        // Validate system program instructions
-        validate_system!(self, system_instructions, SystemTransfer,
-            ParsedSystemInstructionData::SystemTransfer { sender, .. } => sender,
-            self.fee_payer_policy.system.allow_transfer, "System Transfer");
-
-        validate_system!(self, system_instructions, SystemAssign,
-            ParsedSystemInstructionData::SystemAssign { authority } => authority,
-            self.fee_payer_policy.system.allow_assign, "System Assign");
-
-        validate_system!(self, system_instructions, SystemAllocate,
-            ParsedSystemInstructionData::SystemAllocate { account } => account,
-            self.fee_payer_policy.system.allow_allocate, "System Allocate");
-
-        validate_system!(self, system_instructions, SystemCreateAccount,
-            ParsedSystemInstructionData::SystemCreateAccount { payer, .. } => payer,
-            self.fee_payer_policy.system.allow_create_account, "System Create Account");
-
-        validate_system!(self, system_instructions, SystemInitializeNonceAccount,
-            ParsedSystemInstructionData::SystemInitializeNonceAccount { nonce_authority, .. } => nonce_authority,
-            self.fee_payer_policy.system.nonce.allow_initialize, "System Initialize Nonce Account");
-
-        validate_system!(self, system_instructions, SystemAdvanceNonceAccount,
-            ParsedSystemInstructionData::SystemAdvanceNonceAccount { nonce_authority, .. } => nonce_authority,
-            self.fee_payer_policy.system.nonce.allow_advance, "System Advance Nonce Account");
-
-        validate_system!(self, system_instructions, SystemAuthorizeNonceAccount,
-            ParsedSystemInstructionData::SystemAuthorizeNonceAccount { nonce_authority, .. } => nonce_authority,
-            self.fee_payer_policy.system.nonce.allow_authorize, "System Authorize Nonce Account");
-
-        // Note: SystemUpgradeNonceAccount not validated - no authority parameter
-
-        validate_system!(self, system_instructions, SystemWithdrawNonceAccount,
-            ParsedSystemInstructionData::SystemWithdrawNonceAccount { nonce_authority, .. } => nonce_authority,
-            self.fee_payer_policy.system.nonce.allow_withdraw, "System Withdraw Nonce Account");
-
-        // Validate SPL instructions
-        let spl_instructions = transaction_resolved.get_or_parse_spl_instructions()?;
-
-        validate_spl!(self, spl_instructions, SplTokenTransfer,
-            ParsedSPLInstructionData::SplTokenTransfer { owner, is_2022, .. } => { owner, is_2022 },
-            self.fee_payer_policy.spl_token.allow_transfer,
-            self.fee_payer_policy.token_2022.allow_transfer,
-            "SPL Token Transfer", "Token2022 Token Transfer");
-
-        validate_spl!(self, spl_instructions, SplTokenApprove,
-            ParsedSPLInstructionData::SplTokenApprove { owner, is_2022, .. } => { owner, is_2022 },
-            self.fee_payer_policy.spl_token.allow_approve,
-            self.fee_payer_policy.token_2022.allow_approve,
-            "SPL Token Approve", "Token2022 Token Approve");
-
-        validate_spl!(self, spl_instructions, SplTokenBurn,
-            ParsedSPLInstructionData::SplTokenBurn { owner, is_2022 } => { owner, is_2022 },
-            self.fee_payer_policy.spl_token.allow_burn,
-            self.fee_payer_policy.token_2022.allow_burn,
-            "SPL Token Burn", "Token2022 Token Burn");
-
-        validate_spl!(self, spl_instructions, SplTokenCloseAccount,
-            ParsedSPLInstructionData::SplTokenCloseAccount { owner, is_2022 } => { owner, is_2022 },
-            self.fee_payer_policy.spl_token.allow_close_account,
-            self.fee_payer_policy.token_2022.allow_close_account,
-            "SPL Token Close Account", "Token2022 Token Close Account");
-
-        validate_spl!(self, spl_instructions, SplTokenRevoke,
-            ParsedSPLInstructionData::SplTokenRevoke { owner, is_2022 } => { owner, is_2022 },
-            self.fee_payer_policy.spl_token.allow_revoke,
-            self.fee_payer_policy.token_2022.allow_revoke,
-            "SPL Token Revoke", "Token2022 Token Revoke");
-
-        validate_spl!(self, spl_instructions, SplTokenSetAuthority,
-            ParsedSPLInstructionData::SplTokenSetAuthority { authority, is_2022 } => { authority, is_2022 },
-            self.fee_payer_policy.spl_token.allow_set_authority,
-            self.fee_payer_policy.token_2022.allow_set_authority,
-            "SPL Token SetAuthority", "Token2022 Token SetAuthority");
-
-        validate_spl!(self, spl_instructions, SplTokenMintTo,
-            ParsedSPLInstructionData::SplTokenMintTo { mint_authority, is_2022 } => { mint_authority, is_2022 },
-            self.fee_payer_policy.spl_token.allow_mint_to,
-            self.fee_payer_policy.token_2022.allow_mint_to,
-            "SPL Token MintTo", "Token2022 Token MintTo");
-
-        validate_spl!(self, spl_instructions, SplTokenInitializeMint,
-            ParsedSPLInstructionData::SplTokenInitializeMint { mint_authority, is_2022 } => { mint_authority, is_2022 },
-            self.fee_payer_policy.spl_token.allow_initialize_mint,
-            self.fee_payer_policy.token_2022.allow_initialize_mint,
-            "SPL Token InitializeMint", "Token2022 Token InitializeMint");
-
-        validate_spl!(self, spl_instructions, SplTokenInitializeAccount,
-            ParsedSPLInstructionData::SplTokenInitializeAccount { owner, is_2022 } => { owner, is_2022 },
-            self.fee_payer_policy.spl_token.allow_initialize_account,
-            self.fee_payer_policy.token_2022.allow_initialize_account,
-            "SPL Token InitializeAccount", "Token2022 Token InitializeAccount");
-
-        validate_spl_multisig!(self, spl_instructions, SplTokenInitializeMultisig,
-            ParsedSPLInstructionData::SplTokenInitializeMultisig { signers, is_2022 } => { signers, is_2022 },
-            self.fee_payer_policy.spl_token.allow_initialize_multisig,
-            self.fee_payer_policy.token_2022.allow_initialize_multisig,
-            "SPL Token InitializeMultisig", "Token2022 Token InitializeMultisig");
-
-        validate_spl!(self, spl_instructions, SplTokenFreezeAccount,
-            ParsedSPLInstructionData::SplTokenFreezeAccount { freeze_authority, is_2022 } => { freeze_authority, is_2022 },
-            self.fee_payer_policy.spl_token.allow_freeze_account,
-            self.fee_payer_policy.token_2022.allow_freeze_account,
-            "SPL Token FreezeAccount", "Token2022 Token FreezeAccount");
-
-        validate_spl!(self, spl_instructions, SplTokenThawAccount,
-            ParsedSPLInstructionData::SplTokenThawAccount { freeze_authority, is_2022 } => { freeze_authority, is_2022 },
-            self.fee_payer_policy.spl_token.allow_thaw_account,
-            self.fee_payer_policy.token_2022.allow_thaw_account,
-            "SPL Token ThawAccount", "Token2022 Token ThawAccount");
+        let transfers: Vec<(Option<Pubkey>, Pubkey)> = transaction_resolved
+            .get_or_parse_spl_instructions()?
+            .get(&ParsedSPLInstructionType::SplTokenTransfer)
+            .into_iter()
+            .flatten()
+            .filter_map(|instruction| match instruction {
+                ParsedSPLInstructionData::SplTokenTransfer {
+                    mint,
+                    source_address,
+                    is_2022: true,
+                    ..
+                } => Some((*mint, *source_address)),
+                _ => None,
+            })
+            .collect();
+
+        let mut checked_mints = HashSet::new();
+        for (mint, source_address) in transfers {
+            // Plain `Transfer` doesn't reference the mint, it is read from the source account
+            let mint = match mint {
+                Some(mint) => mint,
+                None => {
+                    let source = match validation_ctx
+                        .get_account(rpc_client, &source_address, false)
+                        .await
+                    {
+                        Ok(account) => account,
+                        Err(KoraError::AccountNotFound(_)) => continue,
+                        Err(e) => return Err(e),
+                    };
+                    let token_account = TokenType::get_token_program_from_owner(&source.owner)
+                        .ok()
+                        .and_then(|program| program.unpack_token_account(&source.data).ok());
+                    match token_account {
+                        Some(token_account) => token_account.mint(),
+                        None => continue,
+                    }
+                }
+            };
+            if !checked_mints.insert(mint) {
+                continue;
+            }
+
+            let mint_account = match validation_ctx.get_account(rpc_client, &mint, false).await {
+                Ok(account) => account,
+                Err(KoraError::AccountNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            let token_mint = TokenType::get_token_program_from_owner(&mint_account.owner)?
+                .unpack_mint(&mint, &mint_account.data)
+                .map_err(|e| {
+                    KoraError::TokenOperationError(format!("Failed to unpack mint {mint}: {e}"))
+                })?;
+
+            let hook_program = token_mint
+                .as_any()
+                .downcast_ref::<Token2022Mint>()
+                .and_then(Token2022Mint::get_transfer_hook_program_id);
+            if let Some(hook_program) = hook_program {
+                if !self.program_allowlist().is_program_allowed(&hook_program) {
+                    return Err(KoraError::InvalidTransaction(format!(
+                        "Transfer hook program {hook_program} of mint {mint} is not allowed"
+                    )));
+                }
+            }
+        }
 
         Ok(())
     }
 
-    async fn validate_transfer_amounts(
+    /// Transfers out of a frozen token account fail on-chain, reject them before the fee payer signs
+    async fn validate_transfer_sources_not_frozen(
         &self,
         transaction_resolved: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
     ) -> Result<(), KoraError> {
-        let total_outflow = self.calculate_total_outflow(transaction_resolved, rpc_client).await?;
-
-        if total_outflow > self.max_allowed_lamports {
-            return Err(KoraError::InvalidTransaction(format!(
-                "Total transfer amount {} exceeds maximum allowed {}",
-                total_outflow, self.max_allowed_lamports
-            )));
+        let sources: HashSet<Pubkey> = transaction_resolved
+            .get_or_parse_spl_instructions()?
+            .get(&ParsedSPLInstructionType::SplTokenTransfer)
+            .into_iter()
+            .flatten()
+            .filter_map(|instruction| match instruction {
+                ParsedSPLInstructionData::SplTokenTransfer { source_address, .. } => {
+                    Some(*source_address)
+                }
+                _ => None,
+            })
+            .collect();
+
+        for source in sources {
+            let account = match validation_ctx.get_account(rpc_client, &source, false).await {
+                Ok(account) => account,
+                Err(KoraError::AccountNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            validate_token_account_not_frozen_state(&account, &source)?;
         }
 
         Ok(())
     }
 
-    fn validate_disallowed_accounts(
+    /// Check every account except programs and the fee payer against `account_type_allowlist`
+    async fn validate_account_types(
         &self,
         transaction_resolved: &VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
     ) -> Result<(), KoraError> {
-        for instruction in &transaction_resolved.all_instructions {
-            if self.disallowed_accounts.contains(&instruction.program_id) {
-                return Err(KoraError::InvalidTransaction(format!(
-                    "Program {} is disallowed",
-                    instruction.program_id
-                )));
+        let Some(allowlist) = &self.account_type_allowlist else {
+            return Ok(());
+        };
+
+        let program_ids = IxUtils::extract_program_ids(transaction_resolved);
+        for account in &transaction_resolved.all_account_keys {
+            if *account == self.fee_payer_pubkey || program_ids.contains(account) {
+                continue;
             }
 
-            for account_index in instruction.accounts.iter() {
-                if self.disallowed_accounts.contains(&account_index.pubkey) {
+            match validation_ctx.get_account_type(rpc_client, account).await? {
+                Some(account_type) if allowlist.contains(&account_type) => {}
+                Some(account_type) => {
+                    return Err(KoraError::InvalidTransaction(format!(
+                        "Account {account} of type {account_type:?} is not in the allowed account types"
+                    )));
+                }
+                None => {
                     return Err(KoraError::InvalidTransaction(format!(
-                        "Account {} is disallowed",
-                        account_index.pubkey
+                        "Account {account} has an unrecognized account type"
                     )));
                 }
             }
         }
+
+        Ok(())
+    }
+
+    /// With `validate_nft_metadata`, check every Metaplex metadata account the transaction references
+    async fn validate_nft_metadata_accounts(
+        &self,
+        transaction_resolved: &VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
+    ) -> Result<(), KoraError> {
+        if !self.validate_nft_metadata {
+            return Ok(());
+        }
+
+        let program_ids = IxUtils::extract_program_ids(transaction_resolved);
+        for account in &transaction_resolved.all_account_keys {
+            if *account == self.fee_payer_pubkey || program_ids.contains(account) {
+                continue;
+            }
+
+            if validation_ctx.get_account_type(rpc_client, account).await?
+                == Some(AccountType::NftMetadata)
+            {
+                let metadata = validation_ctx.get_account(rpc_client, account, false).await?;
+                validate_nft_metadata(&metadata, account, &self.fee_payer_pubkey)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -328,6 +525,7 @@ impl TransactionValidator {
         &self,
         transaction_resolved: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
+        validation_ctx: &ValidationContext,
     ) -> Result<u64, KoraError> {
         let config = get_config()?;
         FeeConfigUtil::calculate_fee_payer_outflow(
@@ -335,6 +533,7 @@ impl TransactionValidator {
             transaction_resolved,
             rpc_client,
             &config.validation.price_source,
+            validation_ctx,
         )
         .await
     }
@@ -344,12 +543,14 @@ impl TransactionValidator {
         required_lamports: u64,
         rpc_client: &RpcClient,
         expected_payment_destination: &Pubkey,
+        validation_ctx: &ValidationContext,
     ) -> Result<(), KoraError> {
         if TokenUtil::verify_token_payment(
             transaction_resolved,
             rpc_client,
             required_lamports,
             expected_payment_destination,
+            validation_ctx,
         )
         .await?
         {
@@ -393,16 +594,39 @@ impl TransactionValidator {
 #[cfg(test)]
 mod tests {
     use crate::{
-        config::FeePayerPolicy,
+        config::{
+            AnchorProgramConfig, FeePayerPolicy, NonceInstructionPolicy, SplTokenInstructionPolicy,
+            SystemInstructionPolicy, Token2022InstructionPolicy,
+        },
         state::update_config,
-        tests::{config_mock::ConfigMockBuilder, rpc_mock::RpcMockBuilder},
-        transaction::TransactionUtil,
+        tests::{
+            account_mock::{
+                create_mock_account_with_owner, create_mock_metadata_account,
+                create_mock_non_executable_account, create_mock_program_account,
+                create_mock_spl_mint_account, create_mock_token_account, MintAccountMockBuilder,
+                TokenAccountMockBuilder,
+            },
+            config_mock::{
+                mock_state::setup_config_mock, ConfigMockBuilder, FeePayerPolicyBuilder,
+            },
+            rpc_mock::RpcMockBuilder,
+            signer_mock::TestSignerPool,
+            transaction_mock::TransactionMockBuilder,
+        },
+        transaction::{TransactionUtil, VersionedTransactionOps},
     };
+    use rust_decimal_macros::dec;
     use serial_test::serial;
 
     use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use solana_client::rpc_request::RpcRequest;
     use solana_message::{Message, VersionedMessage};
-    use solana_sdk::instruction::Instruction;
+    use solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        signature::Signature,
+    };
     use solana_system_interface::{
         instruction::{
             assign, create_account, create_account_with_seed, transfer, transfer_with_seed,
@@ -412,41 +636,43 @@ mod tests {
 
     // Helper functions to reduce test duplication and setup config
     fn setup_default_config() {
-        let config = ConfigMockBuilder::new()
+        update_config(system_config_builder().build()).unwrap();
+    }
+
+    fn system_config_builder() -> ConfigMockBuilder {
+        ConfigMockBuilder::new()
             .with_price_source(PriceSource::Mock)
             .with_allowed_programs(vec![SYSTEM_PROGRAM_ID.to_string()])
             .with_max_allowed_lamports(1_000_000)
             .with_fee_payer_policy(FeePayerPolicy::default())
-            .build();
-        update_config(config).unwrap();
     }
 
-    fn setup_config_with_policy(policy: FeePayerPolicy) {
-        let config = ConfigMockBuilder::new()
-            .with_price_source(PriceSource::Mock)
-            .with_allowed_programs(vec![SYSTEM_PROGRAM_ID.to_string()])
-            .with_max_allowed_lamports(1_000_000)
-            .with_fee_payer_policy(policy)
-            .build();
-        update_config(config).unwrap();
+    fn setup_system_config(policy: SystemInstructionPolicy) {
+        update_config(system_config_builder().with_system_policy(policy).build()).unwrap();
+    }
+
+    fn setup_nonce_config(policy: NonceInstructionPolicy) {
+        update_config(system_config_builder().with_nonce_policy(policy).build()).unwrap();
     }
 
-    fn setup_spl_config_with_policy(policy: FeePayerPolicy) {
+    fn setup_spl_config(policy: SplTokenInstructionPolicy) {
         let config = ConfigMockBuilder::new()
             .with_price_source(PriceSource::Mock)
             .with_allowed_programs(vec![spl_token_interface::id().to_string()])
             .with_max_allowed_lamports(1_000_000)
-            .with_fee_payer_policy(policy)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .with_spl_token_policy(policy)
             .build();
         update_config(config).unwrap();
     }
 
-    fn setup_token2022_config_with_policy(policy: FeePayerPolicy) {
+    fn setup_token2022_config(policy: Token2022InstructionPolicy) {
         let config = ConfigMockBuilder::new()
             .with_price_source(PriceSource::Mock)
             .with_allowed_programs(vec![spl_token_2022_interface::id().to_string()])
             .with_max_allowed_lamports(1_000_000)
-            .with_fee_payer_policy(policy)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .with_token_2022_policy(policy)
             .build();
         update_config(config).unwrap();
     }
@@ -470,6 +696,235 @@ mod tests {
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
     }
 
+    async fn mock_account_info(
+        server: &mut mockito::ServerGuard,
+        pubkey: &Pubkey,
+        account: &Account,
+    ) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::PartialJsonString(r#"{"method": "getAccountInfo"}"#.to_string()),
+                mockito::Matcher::Regex(pubkey.to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "context": { "slot": 1 },
+                        "value": {
+                            "data": [STANDARD.encode(&account.data), "base64"],
+                            "executable": account.executable,
+                            "lamports": account.lamports,
+                            "owner": account.owner.to_string(),
+                            "rentEpoch": account.rent_epoch
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validation_context_fetches_shared_account_once() {
+        let fee_payer = Pubkey::new_unique();
+        let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![spl_token_interface::id().to_string()])
+            .with_max_allowed_lamports(1_000_000)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .with_account_type_allowlist(vec![
+                AccountType::System,
+                AccountType::Mint,
+                AccountType::TokenAccount,
+            ])
+            .build();
+        update_config(config.clone()).unwrap();
+        let _m = setup_config_mock(config);
+
+        let mut server = mockito::Server::new_async().await;
+        let account_mocks = [
+            mock_account_info(
+                &mut server,
+                &owner,
+                &create_mock_account_with_owner(SYSTEM_PROGRAM_ID),
+            )
+            .await,
+            mock_account_info(&mut server, &source, &create_mock_token_account(&owner, &mint))
+                .await,
+            mock_account_info(&mut server, &mint, &create_mock_spl_mint_account(6)).await,
+            // Read by the outflow, account type and payment checks
+            mock_account_info(
+                &mut server,
+                &destination,
+                &create_mock_token_account(&Pubkey::new_unique(), &mint),
+            )
+            .await,
+        ];
+        let rpc_client = RpcClient::new(server.url());
+
+        let instruction = spl_token_interface::instruction::transfer_checked(
+            &spl_token_interface::id(),
+            &source,
+            &mint,
+            &destination,
+            &owner,
+            &[],
+            1_000,
+            6,
+        )
+        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        let validation_ctx = ValidationContext::new();
+        validator
+            .validate_transaction_with_context(&mut transaction, &rpc_client, &validation_ctx)
+            .await
+            .unwrap();
+
+        // The transfer doesn't pay Kora, but the destination is still inspected
+        let result = TransactionValidator::validate_token_payment(
+            &mut transaction,
+            1_000,
+            &rpc_client,
+            &fee_payer,
+            &validation_ctx,
+        )
+        .await;
+        assert!(result.unwrap_err().to_string().contains("Insufficient token payment"));
+
+        for mock in account_mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_account_type_allowlist() {
+        let fee_payer = Pubkey::new_unique();
+        let instruction = transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 100_000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+
+        // The mock RPC answers every getAccountInfo with the same account
+        let accounts = [
+            (AccountType::System, create_mock_account_with_owner(SYSTEM_PROGRAM_ID)),
+            (AccountType::Mint, create_mock_spl_mint_account(6)),
+            (
+                AccountType::TokenAccount,
+                create_mock_token_account(&Pubkey::new_unique(), &Pubkey::new_unique()),
+            ),
+            (AccountType::Program, create_mock_program_account()),
+        ];
+
+        for (account_type, account) in accounts {
+            let rpc_client = RpcMockBuilder::new().with_account_info(&account).build();
+            let other_types: Vec<AccountType> = [
+                AccountType::System,
+                AccountType::Mint,
+                AccountType::TokenAccount,
+                AccountType::Program,
+            ]
+            .into_iter()
+            .filter(|other| *other != account_type)
+            .collect();
+
+            for (allowlist, should_pass) in [(vec![account_type], true), (other_types, false)] {
+                let config = system_config_builder().with_account_type_allowlist(allowlist).build();
+                update_config(config.clone()).unwrap();
+                let _m = setup_config_mock(config);
+
+                let validator = TransactionValidator::new(fee_payer).unwrap();
+                let mut transaction =
+                    TransactionUtil::new_unsigned_versioned_transaction_resolved(message.clone())
+                        .unwrap();
+                let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+
+                if should_pass {
+                    assert!(result.is_ok(), "{account_type:?} should be allowed: {result:?}");
+                } else {
+                    let err = result.unwrap_err().to_string();
+                    assert!(
+                        err.contains(&format!("of type {account_type:?} is not in the allowed")),
+                        "Unexpected error for {account_type:?}: {err}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_account_type_allowlist_rejects_unknown_accounts() {
+        let fee_payer = Pubkey::new_unique();
+        let config = system_config_builder()
+            .with_account_type_allowlist(vec![
+                AccountType::System,
+                AccountType::Mint,
+                AccountType::TokenAccount,
+                AccountType::Program,
+            ])
+            .build();
+        update_config(config.clone()).unwrap();
+        let _m = setup_config_mock(config);
+
+        // Data account owned by an arbitrary program
+        let rpc_client =
+            RpcMockBuilder::new().with_account_info(&create_mock_non_executable_account()).build();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let instruction = transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 100_000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let err = validator.validate_transaction(&mut transaction, &rpc_client).await.unwrap_err();
+        assert!(err.to_string().contains("has an unrecognized account type"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_nft_metadata() {
+        let fee_payer = Pubkey::new_unique();
+        let instruction = transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 100_000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+
+        // The mock RPC answers every getAccountInfo with the same metadata account
+        for (update_authority, enabled, should_pass) in
+            [(Pubkey::new_unique(), true, true), (fee_payer, true, false), (fee_payer, false, true)]
+        {
+            let config = system_config_builder().with_validate_nft_metadata(enabled).build();
+            update_config(config.clone()).unwrap();
+            let _m = setup_config_mock(config);
+
+            let rpc_client = RpcMockBuilder::new()
+                .with_account_info(&create_mock_metadata_account(&update_authority))
+                .build();
+            let validator = TransactionValidator::new(fee_payer).unwrap();
+            let mut transaction =
+                TransactionUtil::new_unsigned_versioned_transaction_resolved(message.clone())
+                    .unwrap();
+            let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+
+            if should_pass {
+                assert!(result.is_ok(), "Unexpected error: {result:?}");
+            } else {
+                assert!(result.unwrap_err().to_string().contains("is the update authority"));
+            }
+        }
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_transfer_amount_limits() {
@@ -530,6 +985,47 @@ mod tests {
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_programs_with_duplicate_program_references() {
+        let fee_payer = Pubkey::new_unique();
+        setup_default_config();
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        // Same allowed program referenced multiple times
+        let instructions = vec![
+            transfer(&sender, &recipient, 1000),
+            transfer(&sender, &recipient, 1000),
+            transfer(&sender, &recipient, 1000),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        // Disallowed program referenced multiple times alongside an allowed one
+        let fake_program = Pubkey::new_unique();
+        let instructions = vec![
+            transfer(&sender, &recipient, 1000),
+            Instruction::new_with_bincode(fake_program, &[0u8], vec![]),
+            Instruction::new_with_bincode(fake_program, &[1u8], vec![]),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+        assert_eq!(
+            result.unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Program {fake_program} is not in the allowed list"
+            ))
+        );
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_validate_signatures() {
@@ -639,9 +1135,7 @@ mod tests {
 
         // Test with allow_sol_transfers = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_transfer = true;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy { allow_transfer: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -654,9 +1148,10 @@ mod tests {
 
         // Test with allow_sol_transfers = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_transfer = false;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy {
+            allow_transfer: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -667,6 +1162,50 @@ mod tests {
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_follow_cpi() {
+        let fee_payer = Pubkey::new_unique();
+        let cpi_program = Pubkey::new_unique();
+        let rpc_client = RpcMockBuilder::new().build();
+        update_config(
+            system_config_builder()
+                .with_allowed_programs(vec![cpi_program.to_string(), SYSTEM_PROGRAM_ID.to_string()])
+                .with_follow_cpi(true)
+                .build(),
+        )
+        .unwrap();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let outer = Instruction::new_with_bytes(cpi_program, &[], vec![]);
+        let message = VersionedMessage::Legacy(Message::new(&[outer], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        // A transfer from the fee payer made through CPI is checked against the fee payer policy
+        let mut with_cpi_transfer =
+            VersionedTransactionResolved::from_kora_built_transaction(&transaction.transaction)
+                .unwrap();
+        with_cpi_transfer.all_instructions.push(transfer(&fee_payer, &Pubkey::new_unique(), 1000));
+        assert_eq!(
+            validator.validate_transaction(&mut with_cpi_transfer, &rpc_client).await.unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'System Transfer'".to_string()
+            )
+        );
+
+        // Without resolved inner instructions the CPIs can't be checked
+        transaction.inner_instructions_complete = false;
+        assert_eq!(
+            validator.validate_transaction(&mut transaction, &rpc_client).await.unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Inner instructions could not be resolved from simulation".to_string()
+            )
+        );
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_fee_payer_policy_assign() {
@@ -677,9 +1216,7 @@ mod tests {
 
         let rpc_client = RpcMockBuilder::new().build();
 
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_assign = true;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy { allow_assign: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -693,9 +1230,7 @@ mod tests {
 
         let rpc_client = RpcMockBuilder::new().build();
 
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_assign = false;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy { allow_assign: false, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -717,155 +1252,667 @@ mod tests {
         // Test with allow_spl_transfers = true
         let rpc_client = RpcMockBuilder::new().build();
 
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_transfer = true;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy { allow_transfer: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
-        let transfer_ix = spl_token_interface::instruction::transfer(
-            &spl_token_interface::id(),
-            &fee_payer_token_account,
-            &recipient_token_account,
-            &fee_payer, // fee payer is the signer
-            &[],
-            1000,
-        )
-        .unwrap();
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_spl_transfer(&fee_payer_token_account, &recipient_token_account, &fee_payer, 1000)
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
 
-        let message = VersionedMessage::Legacy(Message::new(&[transfer_ix], Some(&fee_payer)));
+        // Test with allow_spl_transfers = false
+        let rpc_client = RpcMockBuilder::new().build();
+
+        setup_spl_config(SplTokenInstructionPolicy { allow_transfer: false, ..Default::default() });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_spl_transfer(&fee_payer_token_account, &recipient_token_account, &fee_payer, 1000)
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
+
+        // Test with other account as source - should always pass
+        let other_signer = Pubkey::new_unique();
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_spl_transfer(
+                &fee_payer_token_account,
+                &recipient_token_account,
+                &other_signer,
+                1000,
+            )
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fee_payer_policy_sol_transfer_allowed_receivers() {
+        let fee_payer = Pubkey::new_unique();
+        let sender = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let other_receiver = Pubkey::new_unique();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        setup_system_config(SystemInstructionPolicy {
+            allowed_receivers: Some(vec![vault.to_string()]),
+            ..Default::default()
+        });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &vault, 1000)],
+            Some(&fee_payer),
+        ));
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
 
-        // Test with allow_spl_transfers = false
-        let rpc_client = RpcMockBuilder::new().build();
+        // Any sender is restricted, not only the fee payer
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &vault, 1000), transfer(&sender, &other_receiver, 1000)],
+            Some(&fee_payer),
+        ));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains(&other_receiver.to_string())));
 
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_transfer = false;
-        setup_spl_config_with_policy(policy);
+        // An empty list allows every receiver
+        setup_system_config(SystemInstructionPolicy {
+            allowed_receivers: Some(vec![]),
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
-        let transfer_ix = spl_token_interface::instruction::transfer(
-            &spl_token_interface::id(),
-            &fee_payer_token_account,
-            &recipient_token_account,
-            &fee_payer, // fee payer is the signer
-            &[],
-            1000,
-        )
-        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &other_receiver, 1000)],
+            Some(&fee_payer),
+        ));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fee_payer_policy_sol_transfer_min_allowed_lamports() {
+        let fee_payer = Pubkey::new_unique();
+        let sender = Pubkey::new_unique();
+        let receiver = Pubkey::new_unique();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        setup_system_config(SystemInstructionPolicy {
+            min_allowed_lamports: Some(1000),
+            ..Default::default()
+        });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &receiver, 1000)],
+            Some(&fee_payer),
+        ));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
 
-        let message = VersionedMessage::Legacy(Message::new(&[transfer_ix], Some(&fee_payer)));
+        // Every transfer must reach the minimum on its own
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &receiver, 1000), transfer(&sender, &receiver, 999)],
+            Some(&fee_payer),
+        ));
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains("999 lamports is below the minimum of 1000")));
+
+        // No minimum is enforced when the field is absent
+        setup_system_config(SystemInstructionPolicy::default());
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&sender, &receiver, 1)],
+            Some(&fee_payer),
+        ));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fee_payer_policy_spl_transfer_allowed_receivers() {
+        let fee_payer = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let source_token_account = Pubkey::new_unique();
+        let vault_token_account = Pubkey::new_unique();
+        let other_token_account = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let rpc_client = RpcMockBuilder::new().with_mint_account(2).build();
+        setup_spl_config(SplTokenInstructionPolicy {
+            allowed_receivers: Some(vec![vault_token_account.to_string()]),
+            ..Default::default()
+        });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_spl_transfer(&source_token_account, &vault_token_account, &owner, 1000)
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_spl_transfer(&source_token_account, &other_token_account, &owner, 1000)
+            .build_resolved();
+        let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(msg))
+            if msg.contains("SPL Token Transfer") && msg.contains(&other_token_account.to_string())));
+
+        // Token2022 transfers follow the token_2022 policy, which has no receiver list here
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_token2022_transfer(
+                &source_token_account,
+                &mint,
+                &other_token_account,
+                &owner,
+                1000,
+                2,
+            )
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fee_payer_policy_token2022_transfers() {
+        let fee_payer = Pubkey::new_unique();
+
+        let fee_payer_token_account = Pubkey::new_unique();
+        let recipient_token_account = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        // Test with allow_token2022_transfers = true
+        let rpc_client = RpcMockBuilder::new()
+            .with_mint_account(2) // Mock mint with 2 decimals for SPL outflow calculation
+            .build();
+        // Test with token_2022.allow_transfer = true
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_transfer: true,
+            ..Default::default()
+        });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_token2022_transfer(
+                &fee_payer_token_account,
+                &mint,
+                &recipient_token_account,
+                &fee_payer,
+                1,
+                2,
+            )
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        // Test with allow_token2022_transfers = false
+        let rpc_client = RpcMockBuilder::new()
+            .with_mint_account(2) // Mock mint with 2 decimals for SPL outflow calculation
+            .build();
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_transfer: false,
+            ..Default::default()
+        });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_token2022_transfer(
+                &fee_payer_token_account,
+                &mint,
+                &recipient_token_account,
+                &fee_payer,
+                1000,
+                2,
+            )
+            .build_resolved();
+
+        // Should fail because fee payer is not allowed to be source
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
 
-        // Test with other account as source - should always pass
-        let other_signer = Pubkey::new_unique();
-        let transfer_ix = spl_token_interface::instruction::transfer(
-            &spl_token_interface::id(),
-            &fee_payer_token_account,
-            &recipient_token_account,
-            &other_signer, // other account is the signer
-            &[],
-            1000,
-        )
-        .unwrap();
+        // Test with other account as source - should always pass
+        let other_signer = Pubkey::new_unique();
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_token2022_transfer(
+                &fee_payer_token_account,
+                &mint,
+                &recipient_token_account,
+                &other_signer,
+                1000,
+                2,
+            )
+            .build_resolved();
+
+        // Should pass because fee payer is not the source
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    fn anchor_instruction(program_id: Pubkey, name: &str, user: &Pubkey) -> Instruction {
+        let mut data = IxUtils::anchor_discriminator(name).to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        Instruction::new_with_bytes(program_id, &data, vec![AccountMeta::new(*user, true)])
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_anchor_program_instructions() {
+        let fee_payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let anchor_program = Pubkey::new_unique();
+        update_config(
+            system_config_builder()
+                .with_allowed_programs(vec![
+                    SYSTEM_PROGRAM_ID.to_string(),
+                    anchor_program.to_string(),
+                ])
+                .with_anchor_programs(vec![AnchorProgramConfig {
+                    program_id: anchor_program.to_string(),
+                    allowed_instructions: vec!["deposit".to_string(), "withdraw".to_string()],
+                }])
+                .build(),
+        )
+        .unwrap();
+        let rpc_client = RpcMockBuilder::new().build();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        for name in ["deposit", "withdraw"] {
+            let mut transaction = TransactionMockBuilder::new()
+                .with_fee_payer(fee_payer)
+                .with_instruction(anchor_instruction(anchor_program, name, &user))
+                .build_resolved();
+            let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+            assert!(result.is_ok(), "{name}: {result:?}");
+        }
+
+        // Internal instructions of the program can't be invoked
+        for name in ["initialize", "set_authority"] {
+            let mut transaction = TransactionMockBuilder::new()
+                .with_fee_payer(fee_payer)
+                .with_instruction(anchor_instruction(anchor_program, name, &user))
+                .build_resolved();
+            let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+            assert_eq!(
+                result.unwrap_err(),
+                KoraError::InvalidTransaction(format!(
+                    "Instruction of Anchor program {anchor_program} is not in the allowed instructions"
+                ))
+            );
+        }
+    }
+
+    fn upgradeable_loader_instruction(tag: u32, accounts: Vec<AccountMeta>) -> Instruction {
+        Instruction::new_with_bytes(BPF_LOADER_UPGRADEABLE_PROGRAM_ID, &tag.to_le_bytes(), accounts)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_program_upgrade_instructions_rejected() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let upgrade = upgradeable_loader_instruction(
+            UPGRADEABLE_LOADER_UPGRADE,
+            vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        );
+        let set_authority = upgradeable_loader_instruction(
+            UPGRADEABLE_LOADER_SET_AUTHORITY,
+            vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            ],
+        );
+        let set_authority_checked = upgradeable_loader_instruction(
+            UPGRADEABLE_LOADER_SET_AUTHORITY_CHECKED,
+            vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(Pubkey::new_unique(), true),
+            ],
+        );
+
+        // Even an operator allowing the loader program can't get upgrades sponsored
+        let config_builder = || {
+            system_config_builder().with_allowed_programs(vec![
+                SYSTEM_PROGRAM_ID.to_string(),
+                BPF_LOADER_UPGRADEABLE_PROGRAM_ID.to_string(),
+            ])
+        };
+        update_config(config_builder().build()).unwrap();
+        let rpc_client = RpcMockBuilder::new().build();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        for instruction in [upgrade.clone(), set_authority, set_authority_checked] {
+            // Hidden behind a harmless transfer
+            let mut transaction = TransactionMockBuilder::new()
+                .with_fee_payer(fee_payer)
+                .with_sol_transfer(&authority, &Pubkey::new_unique(), 1)
+                .with_instruction(instruction)
+                .build_resolved();
+            let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+            assert_eq!(
+                result.unwrap_err(),
+                KoraError::ValidationError("Upgrade instructions not permitted".to_string())
+            );
+        }
+
+        // Other loader instructions, e.g. writing to a buffer, are left to allowed_programs
+        let write = upgradeable_loader_instruction(
+            1,
+            vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+        );
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_instruction(write)
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        update_config(config_builder().with_allow_program_upgrades(true).build()).unwrap();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_instruction(upgrade)
+            .build_resolved();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    /// Node at `current_slot` whose only recent block, at `block_slot`, has the default blockhash
+    /// used by `TransactionMockBuilder`
+    fn chain_rpc_client(current_slot: u64, block_slot: u64) -> Arc<RpcClient> {
+        RpcMockBuilder::new()
+            .with_custom_mock(RpcRequest::GetSlot, serde_json::json!(current_slot))
+            .with_custom_mock(RpcRequest::GetBlocks, serde_json::json!([block_slot]))
+            .with_custom_mock(
+                RpcRequest::GetBlock,
+                serde_json::json!({
+                    "previousBlockhash": Hash::new_unique().to_string(),
+                    "blockhash": Hash::default().to_string(),
+                    "parentSlot": block_slot - 1,
+                    "blockTime": null,
+                    "blockHeight": block_slot,
+                }),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_max_blockhash_age_slots() {
+        let fee_payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        update_config(system_config_builder().with_max_blockhash_age_slots(Some(5)).build())
+            .unwrap();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_sol_transfer(&user, &Pubkey::new_unique(), 1)
+            .build_resolved();
+
+        // The blockhash is 5 slots old at slot 100_005 and 6 slots old one slot later
+        for (current_slot, accepted) in [(100_000, true), (100_005, true), (100_006, false)] {
+            let rpc_client = chain_rpc_client(current_slot, 100_000);
+            let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+            assert_eq!(result.is_ok(), accepted, "slot {current_slot}: {result:?}");
+        }
+
+        // Unlimited when unset
+        update_config(system_config_builder().build()).unwrap();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        let rpc_client = chain_rpc_client(200_000, 200_000);
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_program_upgrade_through_cpi_rejected() {
+        let fee_payer = Pubkey::new_unique();
+        let wrapper_program = Pubkey::new_unique();
+        update_config(
+            system_config_builder()
+                .with_allowed_programs(vec![
+                    wrapper_program.to_string(),
+                    BPF_LOADER_UPGRADEABLE_PROGRAM_ID.to_string(),
+                ])
+                .build(),
+        )
+        .unwrap();
+        let rpc_client = RpcMockBuilder::new().build();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        // A wrapper program performing the upgrade, only visible in the inner instructions
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(fee_payer)
+            .with_instruction(Instruction::new_with_bytes(wrapper_program, &[0], vec![]))
+            .build_resolved();
+        transaction.all_instructions.push(upgradeable_loader_instruction(
+            UPGRADEABLE_LOADER_UPGRADE,
+            vec![AccountMeta::new(Pubkey::new_unique(), false)],
+        ));
+
+        let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_frozen_transfer_source_rejected() {
+        let fee_payer = Pubkey::new_unique();
+        let (owner, source) = (Pubkey::new_unique(), Pubkey::new_unique());
+        update_config(
+            system_config_builder()
+                .with_allowed_programs(vec![spl_token_interface::id().to_string()])
+                .build(),
+        )
+        .unwrap();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        for (source_account, should_pass) in [
+            (TokenAccountMockBuilder::new().with_owner(&owner).build(), true),
+            (TokenAccountMockBuilder::new().with_owner(&owner).with_frozen().build(), false),
+            (
+                TokenAccountMockBuilder::new().with_owner(&owner).with_frozen().build_token2022(),
+                false,
+            ),
+        ] {
+            let rpc_client = RpcMockBuilder::new().with_account_info(&source_account).build();
+            let mut transaction = TransactionMockBuilder::new()
+                .with_fee_payer(fee_payer)
+                .with_spl_transfer(&source, &Pubkey::new_unique(), &owner, 1_000)
+                .build_resolved();
+
+            let result = validator.validate_transaction(&mut transaction, &rpc_client).await;
+            if should_pass {
+                assert!(result.is_ok(), "{result:?}");
+            } else {
+                assert_eq!(
+                    result.unwrap_err(),
+                    KoraError::ValidationError("Token account is frozen".to_string())
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_frozen_transfer_source_never_signed() {
+        let pool = TestSignerPool::default();
+        let (owner, source) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let config = system_config_builder()
+            .with_allowed_programs(vec![spl_token_interface::id().to_string()])
+            .build();
+        update_config(config.clone()).unwrap();
+        let _m = setup_config_mock(config);
+
+        let rpc_client = RpcMockBuilder::new()
+            .with_account_info(
+                &TokenAccountMockBuilder::new().with_owner(&owner).with_frozen().build(),
+            )
+            .build();
+        let mut transaction = TransactionMockBuilder::new()
+            .with_fee_payer(pool.pubkey())
+            .with_spl_transfer(&source, &Pubkey::new_unique(), &owner, 1_000)
+            .build_resolved();
+
+        let result = transaction.sign_transaction(&pool.signer(), &rpc_client).await;
 
-        let message = VersionedMessage::Legacy(Message::new(&[transfer_ix], Some(&fee_payer)));
-        let mut transaction =
-            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
-        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+        assert_eq!(
+            result.unwrap_err(),
+            KoraError::ValidationError("Token account is frozen".to_string())
+        );
+        assert!(transaction.signatures.iter().all(|signature| *signature == Signature::default()));
     }
 
     #[tokio::test]
     #[serial]
-    async fn test_fee_payer_policy_token2022_transfers() {
+    async fn test_token2022_transfer_hook_program() {
         let fee_payer = Pubkey::new_unique();
-
-        let fee_payer_token_account = Pubkey::new_unique();
-        let recipient_token_account = Pubkey::new_unique();
         let mint = Pubkey::new_unique();
+        let hook_program = Pubkey::new_unique();
+        let hook_mint = MintAccountMockBuilder::new()
+            .with_decimals(2)
+            .with_transfer_hook_program(hook_program)
+            .build_token2022();
+
+        let build_transaction = || {
+            TransactionMockBuilder::new()
+                .with_fee_payer(fee_payer)
+                .with_token2022_transfer(
+                    &Pubkey::new_unique(),
+                    &mint,
+                    &Pubkey::new_unique(),
+                    &fee_payer,
+                    1,
+                    2,
+                )
+                .build_resolved()
+        };
 
-        // Test with allow_token2022_transfers = true
-        let rpc_client = RpcMockBuilder::new()
-            .with_mint_account(2) // Mock mint with 2 decimals for SPL outflow calculation
+        // The hook program is not allowed, the transfer would fail on-chain
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_transfer: true,
+            ..Default::default()
+        });
+        let rpc_client = RpcMockBuilder::new().with_account_info(&hook_mint).build();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        let result = validator.validate_transaction(&mut build_transaction(), &rpc_client).await;
+        assert!(matches!(
+            result,
+            Err(KoraError::InvalidTransaction(msg)) if msg.contains(&hook_program.to_string())
+        ));
+
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![
+                spl_token_2022_interface::id().to_string(),
+                hook_program.to_string(),
+            ])
+            .with_max_allowed_lamports(1_000_000)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .with_token_2022_policy(Token2022InstructionPolicy {
+                allow_transfer: true,
+                ..Default::default()
+            })
             .build();
-        // Test with token_2022.allow_transfer = true
-        let mut policy = FeePayerPolicy::default();
-        policy.token_2022.allow_transfer = true;
-        setup_token2022_config_with_policy(policy);
+        update_config(config).unwrap();
+        let rpc_client = RpcMockBuilder::new().with_account_info(&hook_mint).build();
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        assert!(validator
+            .validate_transaction(&mut build_transaction(), &rpc_client)
+            .await
+            .is_ok());
+    }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_token2022_transfer_hook_source_lookup_errors() {
+        let fee_payer = Pubkey::new_unique();
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_transfer: true,
+            ..Default::default()
+        });
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
-        let transfer_ix = spl_token_2022_interface::instruction::transfer_checked(
+        // Plain `Transfer` doesn't reference the mint, it is read from the source account
+        #[allow(deprecated)]
+        let instruction = spl_token_2022_interface::instruction::transfer(
             &spl_token_2022_interface::id(),
-            &fee_payer_token_account,
-            &mint,
-            &recipient_token_account,
-            &fee_payer, // fee payer is the signer
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &fee_payer,
             &[],
             1,
-            2,
         )
         .unwrap();
+        let build_transaction = || {
+            TransactionMockBuilder::new()
+                .with_fee_payer(fee_payer)
+                .with_instruction(instruction.clone())
+                .build_resolved()
+        };
 
-        let message = VersionedMessage::Legacy(Message::new(&[transfer_ix], Some(&fee_payer)));
-        let mut transaction =
-            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
-        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
-
-        // Test with allow_token2022_transfers = false
+        // A missing source account is left to the other checks
+        let rpc_client = RpcMockBuilder::new().with_account_not_found().build();
+        assert!(validator
+            .validate_transfer_hooks(
+                &mut build_transaction(),
+                &rpc_client,
+                &ValidationContext::new()
+            )
+            .await
+            .is_ok());
+
+        // Any other failure can't be skipped, the hook would go unchecked
         let rpc_client = RpcMockBuilder::new()
-            .with_mint_account(2) // Mock mint with 2 decimals for SPL outflow calculation
+            .with_custom_mock(
+                RpcRequest::GetAccountInfo,
+                serde_json::json!({ "code": -32005, "message": "Node is unhealthy" }),
+            )
             .build();
-        let mut policy = FeePayerPolicy::default();
-        policy.token_2022.allow_transfer = false;
-        setup_token2022_config_with_policy(policy);
-
-        let validator = TransactionValidator::new(fee_payer).unwrap();
-
-        let transfer_ix = spl_token_2022_interface::instruction::transfer_checked(
-            &spl_token_2022_interface::id(),
-            &fee_payer_token_account,
-            &mint,
-            &recipient_token_account,
-            &fee_payer, // fee payer is the signer
-            &[],
-            1000,
-            2,
-        )
-        .unwrap();
-
-        let message = VersionedMessage::Legacy(Message::new(&[transfer_ix], Some(&fee_payer)));
-        let mut transaction =
-            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
-
-        // Should fail because fee payer is not allowed to be source
-        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
-
-        // Test with other account as source - should always pass
-        let other_signer = Pubkey::new_unique();
-        let transfer_ix = spl_token_2022_interface::instruction::transfer_checked(
-            &spl_token_2022_interface::id(),
-            &fee_payer_token_account,
-            &mint,
-            &recipient_token_account,
-            &other_signer, // other account is the signer
-            &[],
-            1000,
-            2,
-        )
-        .unwrap();
-
-        let message = VersionedMessage::Legacy(Message::new(&[transfer_ix], Some(&fee_payer)));
-        let mut transaction =
-            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
-
-        // Should pass because fee payer is not the source
-        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+        let result = validator
+            .validate_transfer_hooks(
+                &mut build_transaction(),
+                &rpc_client,
+                &ValidationContext::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(KoraError::RpcError(_))));
     }
 
     #[tokio::test]
@@ -1015,9 +2062,7 @@ mod tests {
         // Test with allow_burn = true
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_burn = true;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy { allow_burn: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1040,9 +2085,7 @@ mod tests {
         // Test with allow_burn = false
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_burn = false;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy { allow_burn: false, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1093,9 +2136,10 @@ mod tests {
         // Test with allow_close_account = true
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_close_account = true;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy {
+            allow_close_account: true,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1116,9 +2160,10 @@ mod tests {
 
         // Test with allow_close_account = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_close_account = false;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy {
+            allow_close_account: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1149,9 +2194,7 @@ mod tests {
         // Test with allow_approve = true
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_approve = true;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy { allow_approve: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1173,9 +2216,7 @@ mod tests {
 
         // Test with allow_approve = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.spl_token.allow_approve = false;
-        setup_spl_config_with_policy(policy);
+        setup_spl_config(SplTokenInstructionPolicy { allow_approve: false, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1219,6 +2260,110 @@ mod tests {
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_fee_payer_policy_revoke() {
+        let fee_payer = Pubkey::new_unique();
+        let fee_payer_token_account = Pubkey::new_unique();
+
+        // Test with allow_revoke = true
+        let rpc_client = RpcMockBuilder::new().build();
+        setup_spl_config(SplTokenInstructionPolicy { allow_revoke: true, ..Default::default() });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let revoke_ix = spl_token_interface::instruction::revoke(
+            &spl_token_interface::id(),
+            &fee_payer_token_account,
+            &fee_payer,
+            &[],
+        )
+        .unwrap();
+
+        let message =
+            VersionedMessage::Legacy(Message::new(&[revoke_ix.clone()], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        // Test with allow_revoke = false
+        let rpc_client = RpcMockBuilder::new().build();
+        setup_spl_config(SplTokenInstructionPolicy { allow_revoke: false, ..Default::default() });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(&[revoke_ix], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        // Should fail because fee payer is the owner being revoked from
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fee_payer_policy_revoke_multisig() {
+        let fee_payer = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let multisig_owner = Pubkey::new_unique();
+        let other_signer = Pubkey::new_unique();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        setup_spl_config(SplTokenInstructionPolicy { allow_revoke: false, ..Default::default() });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        // Fee payer as one of the multisig signers should be rejected
+        let revoke_ix = spl_token_interface::instruction::revoke(
+            &spl_token_interface::id(),
+            &token_account,
+            &multisig_owner,
+            &[&fee_payer, &other_signer],
+        )
+        .unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(&[revoke_ix], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
+
+        // Multisig revoke without the fee payer among the signers should pass
+        let revoke_ix = spl_token_interface::instruction::revoke(
+            &spl_token_interface::id(),
+            &token_account,
+            &multisig_owner,
+            &[&other_signer],
+        )
+        .unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(&[revoke_ix], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_ok());
+
+        // Same rules apply to Token2022 multisig revokes
+        let rpc_client = RpcMockBuilder::new().build();
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_revoke: false,
+            ..Default::default()
+        });
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+
+        let revoke_ix = spl_token_2022_interface::instruction::revoke(
+            &spl_token_2022_interface::id(),
+            &token_account,
+            &multisig_owner,
+            &[&other_signer, &fee_payer],
+        )
+        .unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(&[revoke_ix], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_fee_payer_policy_token2022_burn() {
@@ -1229,9 +2374,10 @@ mod tests {
         // Test with allow_burn = false for Token2022
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.token_2022.allow_burn = false;
-        setup_token2022_config_with_policy(policy);
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_burn: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1262,9 +2408,10 @@ mod tests {
         // Test with allow_close_account = false for Token2022
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.token_2022.allow_close_account = false;
-        setup_token2022_config_with_policy(policy);
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_close_account: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1294,9 +2441,10 @@ mod tests {
         // Test with allow_approve = true
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.token_2022.allow_approve = true;
-        setup_token2022_config_with_policy(policy);
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_approve: true,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1319,9 +2467,10 @@ mod tests {
         // Test with allow_approve = false
 
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.token_2022.allow_approve = false;
-        setup_token2022_config_with_policy(policy);
+        setup_token2022_config(Token2022InstructionPolicy {
+            allow_approve: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
 
@@ -1376,9 +2525,10 @@ mod tests {
 
         // Test with allow_create_account = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_create_account = true;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy {
+            allow_create_account: true,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = create_account(&fee_payer, &new_account, 1000, 100, &owner);
@@ -1389,9 +2539,10 @@ mod tests {
 
         // Test with allow_create_account = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_create_account = false;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy {
+            allow_create_account: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = create_account(&fee_payer, &new_account, 1000, 100, &owner);
@@ -1410,9 +2561,7 @@ mod tests {
 
         // Test with allow_allocate = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_allocate = true;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy { allow_allocate: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = allocate(&fee_payer, 100);
@@ -1423,9 +2572,10 @@ mod tests {
 
         // Test with allow_allocate = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.allow_allocate = false;
-        setup_config_with_policy(policy);
+        setup_system_config(SystemInstructionPolicy {
+            allow_allocate: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = allocate(&fee_payer, 100);
@@ -1445,9 +2595,7 @@ mod tests {
 
         // Test with allow_initialize = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_initialize = true;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_initialize: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instructions = create_nonce_account(&fee_payer, &nonce_account, &fee_payer, 1_000_000);
@@ -1460,9 +2608,10 @@ mod tests {
 
         // Test with allow_initialize = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_initialize = false;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy {
+            allow_initialize: false,
+            ..Default::default()
+        });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instructions = create_nonce_account(&fee_payer, &nonce_account, &fee_payer, 1_000_000);
@@ -1483,9 +2632,7 @@ mod tests {
 
         // Test with allow_advance = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_advance = true;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_advance: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = advance_nonce_account(&nonce_account, &fee_payer);
@@ -1496,9 +2643,7 @@ mod tests {
 
         // Test with allow_advance = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_advance = false;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_advance: false, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = advance_nonce_account(&nonce_account, &fee_payer);
@@ -1519,9 +2664,7 @@ mod tests {
 
         // Test with allow_withdraw = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_withdraw = true;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_withdraw: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = withdraw_nonce_account(&nonce_account, &fee_payer, &recipient, 1000);
@@ -1532,9 +2675,7 @@ mod tests {
 
         // Test with allow_withdraw = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_withdraw = false;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_withdraw: false, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = withdraw_nonce_account(&nonce_account, &fee_payer, &recipient, 1000);
@@ -1555,9 +2696,7 @@ mod tests {
 
         // Test with allow_authorize = true
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_authorize = true;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_authorize: true, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = authorize_nonce_account(&nonce_account, &fee_payer, &new_authority);
@@ -1568,9 +2707,7 @@ mod tests {
 
         // Test with allow_authorize = false
         let rpc_client = RpcMockBuilder::new().build();
-        let mut policy = FeePayerPolicy::default();
-        policy.system.nonce.allow_authorize = false;
-        setup_config_with_policy(policy);
+        setup_nonce_config(NonceInstructionPolicy { allow_authorize: false, ..Default::default() });
 
         let validator = TransactionValidator::new(fee_payer).unwrap();
         let instruction = authorize_nonce_account(&nonce_account, &fee_payer, &new_authority);
@@ -1580,6 +2717,155 @@ mod tests {
         assert!(validator.validate_transaction(&mut transaction, &rpc_client).await.is_err());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_system_policy_builder_restrictive_overrides() {
+        let fee_payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let base_policy =
+            FeePayerPolicyBuilder::new().with_sol_transfers(true).with_assign(true).build();
+        let config = system_config_builder()
+            .with_fee_payer_policy(base_policy)
+            .with_system_policy(FeePayerPolicyBuilder::restrictive().build().system)
+            .build();
+        update_config(config).unwrap();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        assert!(!validator.fee_payer_policy.system.allow_transfer);
+        assert!(!validator.fee_payer_policy.system.allow_assign);
+
+        let instruction = transfer(&fee_payer, &recipient, 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert_eq!(
+            validator.validate_transaction(&mut transaction, &rpc_client).await.unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'System Transfer'".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_nonce_policy_builder_restrictive_overrides() {
+        use solana_system_interface::instruction::advance_nonce_account;
+
+        let fee_payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let base_policy = FeePayerPolicyBuilder::new()
+            .with_sol_transfers(true)
+            .with_nonce_advance(true)
+            .with_nonce_withdraw(true)
+            .build();
+        let config = system_config_builder()
+            .with_fee_payer_policy(base_policy)
+            .with_nonce_policy(NonceInstructionPolicy::default())
+            .build();
+        update_config(config).unwrap();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        assert!(!validator.fee_payer_policy.system.nonce.allow_advance);
+        assert!(!validator.fee_payer_policy.system.nonce.allow_withdraw);
+        // Overriding the nonce policy must leave the rest of the system policy intact
+        assert!(validator.fee_payer_policy.system.allow_transfer);
+
+        let instruction = advance_nonce_account(&nonce_account, &fee_payer);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert_eq!(
+            validator.validate_transaction(&mut transaction, &rpc_client).await.unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'System Advance Nonce Account'".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_spl_token_policy_builder_restrictive_overrides() {
+        let fee_payer = Pubkey::new_unique();
+        let fee_payer_token_account = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![spl_token_interface::id().to_string()])
+            .with_max_allowed_lamports(1_000_000)
+            .with_fee_payer_policy(FeePayerPolicyBuilder::new().with_spl_burn(true).build())
+            .with_spl_token_policy(FeePayerPolicyBuilder::restrictive().build().spl_token)
+            .build();
+        update_config(config).unwrap();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        assert!(!validator.fee_payer_policy.spl_token.allow_burn);
+
+        let burn_ix = spl_token_interface::instruction::burn(
+            &spl_token_interface::id(),
+            &fee_payer_token_account,
+            &mint,
+            &fee_payer,
+            &[],
+            1000,
+        )
+        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(&[burn_ix], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert_eq!(
+            validator.validate_transaction(&mut transaction, &rpc_client).await.unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'SPL Token Burn'".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_token_2022_policy_builder_restrictive_overrides() {
+        let fee_payer = Pubkey::new_unique();
+        let fee_payer_token_account = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![spl_token_2022_interface::id().to_string()])
+            .with_max_allowed_lamports(1_000_000)
+            .with_fee_payer_policy(FeePayerPolicyBuilder::new().with_spl_approve(true).build())
+            .with_token_2022_policy(FeePayerPolicyBuilder::restrictive().build().token_2022)
+            .build();
+        update_config(config).unwrap();
+
+        let validator = TransactionValidator::new(fee_payer).unwrap();
+        assert!(!validator.fee_payer_policy.token_2022.allow_approve);
+
+        let approve_ix = spl_token_2022_interface::instruction::approve(
+            &spl_token_2022_interface::id(),
+            &fee_payer_token_account,
+            &delegate,
+            &fee_payer,
+            &[],
+            1000,
+        )
+        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new(&[approve_ix], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert_eq!(
+            validator.validate_transaction(&mut transaction, &rpc_client).await.unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'Token2022 Token Approve'".to_string()
+            )
+        );
+    }
+
     #[test]
     #[serial]
     fn test_strict_pricing_total_exceeds_fixed() {
@@ -1650,7 +2936,7 @@ mod tests {
         };
 
         let mut config = ConfigMockBuilder::new().build();
-        config.validation.price.model = PriceModel::Margin { margin: 0.1 };
+        config.validation.price.model = PriceModel::Margin { margin: dec!(0.1) };
         let _ = update_config(config);
 
         let fee_calc = TotalFeeCalculation::new(5000, 10000, 0, 0, 0, 0);
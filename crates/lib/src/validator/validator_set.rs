@@ -0,0 +1,1169 @@
+use crate::{
+    config::FeePayerPolicy,
+    error::KoraError,
+    transaction::{
+        IxUtils, ParsedSPLInstructionData, ParsedSPLInstructionType, ParsedSystemInstructionData,
+        ParsedSystemInstructionType, VersionedTransactionResolved,
+    },
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use std::collections::{HashMap, HashSet};
+
+/// A composable transaction check.
+///
+/// Individual rules implement this trait and are combined with [`ValidatorSet::chain`],
+/// so new rules can be added without touching existing ones. Checks run in chain order
+/// and the first failure is returned.
+pub trait ValidatorSet {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError>;
+
+    /// Run `next` after this validator succeeds
+    fn chain<V: ValidatorSet>(self, next: V) -> ChainedValidator<Self, V>
+    where
+        Self: Sized,
+    {
+        ChainedValidator { first: self, second: next }
+    }
+}
+
+/// Two validators run in sequence, created by [`ValidatorSet::chain`]
+pub struct ChainedValidator<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: ValidatorSet, B: ValidatorSet> ValidatorSet for ChainedValidator<A, B> {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        self.first.validate(transaction_resolved)?;
+        self.second.validate(transaction_resolved)
+    }
+}
+
+/// Rejects empty transactions and transactions with too many signatures
+pub struct SizeValidator {
+    pub max_signatures: u64,
+}
+
+impl ValidatorSet for SizeValidator {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        if transaction_resolved.all_instructions.is_empty() {
+            return Err(KoraError::InvalidTransaction(
+                "Transaction contains no instructions".to_string(),
+            ));
+        }
+
+        if transaction_resolved.all_account_keys.is_empty() {
+            return Err(KoraError::InvalidTransaction(
+                "Transaction contains no account keys".to_string(),
+            ));
+        }
+
+        let signatures = &transaction_resolved.transaction.signatures;
+        if signatures.len() > self.max_signatures as usize {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Too many signatures: {} > {}",
+                signatures.len(),
+                self.max_signatures
+            )));
+        }
+
+        if signatures.is_empty() {
+            return Err(KoraError::InvalidTransaction("No signatures found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects transactions whose inner instructions weren't all resolved from simulation
+///
+/// Used with `follow_cpi`, so the fee payer policy is known to have seen every instruction
+/// invoked through CPI.
+pub struct InnerInstructionsValidator;
+
+impl ValidatorSet for InnerInstructionsValidator {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        if !transaction_resolved.inner_instructions_complete {
+            return Err(KoraError::InvalidTransaction(
+                "Inner instructions could not be resolved from simulation".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions invoking programs outside the allowed list, or in the disallowed list
+///
+/// An empty allowed list with a non-empty disallowed list allows every other program.
+pub struct AllowlistValidator<'a> {
+    pub allowed_programs: &'a [Pubkey],
+    pub disallowed_programs: &'a [Pubkey],
+}
+
+impl AllowlistValidator<'_> {
+    pub fn is_program_allowed(&self, program_id: &Pubkey) -> bool {
+        if self.disallowed_programs.contains(program_id) {
+            return false;
+        }
+        if self.allowed_programs.is_empty() && !self.disallowed_programs.is_empty() {
+            return true;
+        }
+        self.allowed_programs.contains(program_id)
+    }
+}
+
+impl ValidatorSet for AllowlistValidator<'_> {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        for program_id in IxUtils::extract_program_ids(transaction_resolved) {
+            if self.disallowed_programs.contains(&program_id) {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Program {program_id} is disallowed"
+                )));
+            }
+            if !self.is_program_allowed(&program_id) {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Program {program_id} is not in the allowed list"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Restricts Anchor programs to the instructions whose discriminator is allowed
+///
+/// Instructions of the listed programs must start with one of the program's allowed 8-byte
+/// discriminators, so internal or admin instructions can't be invoked.
+pub struct AnchorInstructionValidator<'a> {
+    pub allowed_discriminators: &'a HashMap<Pubkey, HashSet<[u8; 8]>>,
+}
+
+impl ValidatorSet for AnchorInstructionValidator<'_> {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        for instruction in &transaction_resolved.all_instructions {
+            let Some(allowed) = self.allowed_discriminators.get(&instruction.program_id) else {
+                continue;
+            };
+
+            let discriminator =
+                instruction.data.get(..8).and_then(|data| <[u8; 8]>::try_from(data).ok());
+            if !discriminator.is_some_and(|discriminator| allowed.contains(&discriminator)) {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Instruction of Anchor program {} is not in the allowed instructions",
+                    instruction.program_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions referencing a disallowed program or account
+pub struct DisallowedAccountsValidator<'a> {
+    pub disallowed_accounts: &'a HashSet<Pubkey>,
+}
+
+impl ValidatorSet for DisallowedAccountsValidator<'_> {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        for instruction in &transaction_resolved.all_instructions {
+            if self.disallowed_accounts.contains(&instruction.program_id) {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Program {} is disallowed",
+                    instruction.program_id
+                )));
+            }
+
+            for account_index in instruction.accounts.iter() {
+                if self.disallowed_accounts.contains(&account_index.pubkey) {
+                    return Err(KoraError::InvalidTransaction(format!(
+                        "Account {} is disallowed",
+                        account_index.pubkey
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Enforces the fee payer policy for system and token instructions
+pub struct FeePolicyValidator<'a> {
+    pub fee_payer_pubkey: Pubkey,
+    pub fee_payer_policy: &'a FeePayerPolicy,
+}
+
+impl ValidatorSet for FeePolicyValidator<'_> {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        let system_instructions = transaction_resolved.get_or_parse_system_instructions()?;
+
+        // Validate system program instructions
+        validate_system!(self, system_instructions, SystemTransfer,
+            ParsedSystemInstructionData::SystemTransfer { sender, .. } => sender,
+            self.fee_payer_policy.system.allow_transfer, "System Transfer");
+
+        validate_system!(self, system_instructions, SystemAssign,
+            ParsedSystemInstructionData::SystemAssign { authority } => authority,
+            self.fee_payer_policy.system.allow_assign, "System Assign");
+
+        validate_system!(self, system_instructions, SystemAllocate,
+            ParsedSystemInstructionData::SystemAllocate { account } => account,
+            self.fee_payer_policy.system.allow_allocate, "System Allocate");
+
+        validate_system!(self, system_instructions, SystemCreateAccount,
+            ParsedSystemInstructionData::SystemCreateAccount { payer, .. } => payer,
+            self.fee_payer_policy.system.allow_create_account, "System Create Account");
+
+        validate_system!(self, system_instructions, SystemInitializeNonceAccount,
+            ParsedSystemInstructionData::SystemInitializeNonceAccount { nonce_authority, .. } => nonce_authority,
+            self.fee_payer_policy.system.nonce.allow_initialize, "System Initialize Nonce Account");
+
+        validate_system!(self, system_instructions, SystemAdvanceNonceAccount,
+            ParsedSystemInstructionData::SystemAdvanceNonceAccount { nonce_authority, .. } => nonce_authority,
+            self.fee_payer_policy.system.nonce.allow_advance, "System Advance Nonce Account");
+
+        validate_system!(self, system_instructions, SystemAuthorizeNonceAccount,
+            ParsedSystemInstructionData::SystemAuthorizeNonceAccount { nonce_authority, .. } => nonce_authority,
+            self.fee_payer_policy.system.nonce.allow_authorize, "System Authorize Nonce Account");
+
+        // Note: SystemUpgradeNonceAccount not validated - no authority parameter
+
+        validate_system!(self, system_instructions, SystemWithdrawNonceAccount,
+            ParsedSystemInstructionData::SystemWithdrawNonceAccount { nonce_authority, .. } => nonce_authority,
+            self.fee_payer_policy.system.nonce.allow_withdraw, "System Withdraw Nonce Account");
+
+        self.validate_system_receivers(system_instructions)?;
+        self.validate_transfer_amounts(system_instructions)?;
+
+        // Validate SPL instructions
+        let spl_instructions = transaction_resolved.get_or_parse_spl_instructions()?;
+
+        validate_spl!(self, spl_instructions, SplTokenTransfer,
+            ParsedSPLInstructionData::SplTokenTransfer { owner, is_2022, .. } => { owner, is_2022 },
+            self.fee_payer_policy.spl_token.allow_transfer,
+            self.fee_payer_policy.token_2022.allow_transfer,
+            "SPL Token Transfer", "Token2022 Token Transfer");
+
+        validate_spl!(self, spl_instructions, SplTokenApprove,
+            ParsedSPLInstructionData::SplTokenApprove { owner, is_2022, .. } => { owner, is_2022 },
+            self.fee_payer_policy.spl_token.allow_approve,
+            self.fee_payer_policy.token_2022.allow_approve,
+            "SPL Token Approve", "Token2022 Token Approve");
+
+        validate_spl!(self, spl_instructions, SplTokenBurn,
+            ParsedSPLInstructionData::SplTokenBurn { owner, is_2022 } => { owner, is_2022 },
+            self.fee_payer_policy.spl_token.allow_burn,
+            self.fee_payer_policy.token_2022.allow_burn,
+            "SPL Token Burn", "Token2022 Token Burn");
+
+        validate_spl!(self, spl_instructions, SplTokenCloseAccount,
+            ParsedSPLInstructionData::SplTokenCloseAccount { owner, is_2022 } => { owner, is_2022 },
+            self.fee_payer_policy.spl_token.allow_close_account,
+            self.fee_payer_policy.token_2022.allow_close_account,
+            "SPL Token Close Account", "Token2022 Token Close Account");
+
+        validate_spl!(self, spl_instructions, SplTokenRevoke,
+            ParsedSPLInstructionData::SplTokenRevoke { owner, is_2022, .. } => { owner, is_2022 },
+            self.fee_payer_policy.spl_token.allow_revoke,
+            self.fee_payer_policy.token_2022.allow_revoke,
+            "SPL Token Revoke", "Token2022 Token Revoke");
+
+        validate_spl_multisig!(self, spl_instructions, SplTokenRevoke,
+            ParsedSPLInstructionData::SplTokenRevoke { multisig_signers, is_2022, .. } => { multisig_signers, is_2022 },
+            self.fee_payer_policy.spl_token.allow_revoke,
+            self.fee_payer_policy.token_2022.allow_revoke,
+            "SPL Token Revoke", "Token2022 Token Revoke");
+
+        validate_spl!(self, spl_instructions, SplTokenSetAuthority,
+            ParsedSPLInstructionData::SplTokenSetAuthority { authority, is_2022 } => { authority, is_2022 },
+            self.fee_payer_policy.spl_token.allow_set_authority,
+            self.fee_payer_policy.token_2022.allow_set_authority,
+            "SPL Token SetAuthority", "Token2022 Token SetAuthority");
+
+        validate_spl!(self, spl_instructions, SplTokenMintTo,
+            ParsedSPLInstructionData::SplTokenMintTo { mint_authority, is_2022 } => { mint_authority, is_2022 },
+            self.fee_payer_policy.spl_token.allow_mint_to,
+            self.fee_payer_policy.token_2022.allow_mint_to,
+            "SPL Token MintTo", "Token2022 Token MintTo");
+
+        validate_spl!(self, spl_instructions, SplTokenInitializeMint,
+            ParsedSPLInstructionData::SplTokenInitializeMint { mint_authority, is_2022 } => { mint_authority, is_2022 },
+            self.fee_payer_policy.spl_token.allow_initialize_mint,
+            self.fee_payer_policy.token_2022.allow_initialize_mint,
+            "SPL Token InitializeMint", "Token2022 Token InitializeMint");
+
+        validate_spl!(self, spl_instructions, SplTokenInitializeAccount,
+            ParsedSPLInstructionData::SplTokenInitializeAccount { owner, is_2022 } => { owner, is_2022 },
+            self.fee_payer_policy.spl_token.allow_initialize_account,
+            self.fee_payer_policy.token_2022.allow_initialize_account,
+            "SPL Token InitializeAccount", "Token2022 Token InitializeAccount");
+
+        validate_spl_multisig!(self, spl_instructions, SplTokenInitializeMultisig,
+            ParsedSPLInstructionData::SplTokenInitializeMultisig { signers, is_2022 } => { signers, is_2022 },
+            self.fee_payer_policy.spl_token.allow_initialize_multisig,
+            self.fee_payer_policy.token_2022.allow_initialize_multisig,
+            "SPL Token InitializeMultisig", "Token2022 Token InitializeMultisig");
+
+        validate_spl!(self, spl_instructions, SplTokenFreezeAccount,
+            ParsedSPLInstructionData::SplTokenFreezeAccount { freeze_authority, is_2022 } => { freeze_authority, is_2022 },
+            self.fee_payer_policy.spl_token.allow_freeze_account,
+            self.fee_payer_policy.token_2022.allow_freeze_account,
+            "SPL Token FreezeAccount", "Token2022 Token FreezeAccount");
+
+        validate_spl!(self, spl_instructions, SplTokenThawAccount,
+            ParsedSPLInstructionData::SplTokenThawAccount { freeze_authority, is_2022 } => { freeze_authority, is_2022 },
+            self.fee_payer_policy.spl_token.allow_thaw_account,
+            self.fee_payer_policy.token_2022.allow_thaw_account,
+            "SPL Token ThawAccount", "Token2022 Token ThawAccount");
+
+        self.validate_spl_receivers(spl_instructions)?;
+        self.validate_program_instruction_rules(transaction_resolved)?;
+
+        if self.fee_payer_policy.reject_fee_payer_as_unknown_signer {
+            self.validate_unknown_signers(transaction_resolved)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FeePolicyValidator<'_> {
+    /// Rejects SOL transfers to receivers outside the system policy's `allowed_receivers`, for
+    /// any sender
+    fn validate_system_receivers(
+        &self,
+        system_instructions: &HashMap<
+            ParsedSystemInstructionType,
+            Vec<ParsedSystemInstructionData>,
+        >,
+    ) -> Result<(), KoraError> {
+        let transfers = system_instructions
+            .get(&ParsedSystemInstructionType::SystemTransfer)
+            .into_iter()
+            .flatten();
+        for instruction in transfers {
+            if let ParsedSystemInstructionData::SystemTransfer { receiver, .. } = instruction {
+                if !is_receiver_allowed(&self.fee_payer_policy.system.allowed_receivers, receiver) {
+                    return Err(KoraError::InvalidTransaction(format!(
+                        "System Transfer receiver {receiver} is not an allowed receiver"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rejects token transfers to receivers outside the token policies' `allowed_receivers`, for
+    /// any sender
+    fn validate_spl_receivers(
+        &self,
+        spl_instructions: &HashMap<ParsedSPLInstructionType, Vec<ParsedSPLInstructionData>>,
+    ) -> Result<(), KoraError> {
+        let transfers =
+            spl_instructions.get(&ParsedSPLInstructionType::SplTokenTransfer).into_iter().flatten();
+        for instruction in transfers {
+            if let ParsedSPLInstructionData::SplTokenTransfer {
+                destination_address, is_2022, ..
+            } = instruction
+            {
+                let (allowed_receivers, name) = if *is_2022 {
+                    (
+                        &self.fee_payer_policy.token_2022.allowed_receivers,
+                        "Token2022 Token Transfer",
+                    )
+                } else {
+                    (&self.fee_payer_policy.spl_token.allowed_receivers, "SPL Token Transfer")
+                };
+                if !is_receiver_allowed(allowed_receivers, destination_address) {
+                    return Err(KoraError::InvalidTransaction(format!(
+                        "{name} destination {destination_address} is not an allowed receiver"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rejects SOL transfers below the system policy's `min_allowed_lamports`, for any sender
+    fn validate_transfer_amounts(
+        &self,
+        system_instructions: &HashMap<
+            ParsedSystemInstructionType,
+            Vec<ParsedSystemInstructionData>,
+        >,
+    ) -> Result<(), KoraError> {
+        let Some(min_allowed_lamports) = self.fee_payer_policy.system.min_allowed_lamports else {
+            return Ok(());
+        };
+
+        let transfers = system_instructions
+            .get(&ParsedSystemInstructionType::SystemTransfer)
+            .into_iter()
+            .flatten();
+        for instruction in transfers {
+            if let ParsedSystemInstructionData::SystemTransfer { lamports, .. } = instruction {
+                if *lamports < min_allowed_lamports {
+                    return Err(KoraError::InvalidTransaction(format!(
+                        "System Transfer of {lamports} lamports is below the minimum of {min_allowed_lamports}"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rejects instructions of programs in `program_instruction_allowlist` that use the fee payer
+    /// as a signer or writable account without a rule allowing it
+    fn validate_program_instruction_rules(
+        &self,
+        transaction_resolved: &VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        let (is_signer, is_writable) =
+            fee_payer_privileges(transaction_resolved, &self.fee_payer_pubkey);
+        if !is_signer && !is_writable {
+            return Ok(());
+        }
+
+        for instruction in &transaction_resolved.all_instructions {
+            if references_account(instruction, &self.fee_payer_pubkey)
+                && self.fee_payer_policy.allows_program_instruction(
+                    instruction,
+                    is_signer,
+                    is_writable,
+                ) == Some(false)
+            {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Fee payer use in this instruction of program {} is not allowed by \
+                     program_instruction_allowlist",
+                    instruction.program_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects instructions outside the parsed programs that require the fee payer's signature,
+    /// those could make it sign for anything the program does
+    fn validate_unknown_signers(
+        &self,
+        transaction_resolved: &VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        let (is_signer, is_writable) =
+            fee_payer_privileges(transaction_resolved, &self.fee_payer_pubkey);
+        if !is_signer {
+            return Ok(());
+        }
+
+        for instruction in &transaction_resolved.all_instructions {
+            if FeePayerWritableValidator::POLICY_PROGRAMS.contains(&instruction.program_id)
+                || !references_account(instruction, &self.fee_payer_pubkey)
+                || self.fee_payer_policy.allows_program_instruction(
+                    instruction,
+                    is_signer,
+                    is_writable,
+                ) == Some(true)
+            {
+                continue;
+            }
+
+            return Err(KoraError::InvalidTransaction(format!(
+                "Fee payer cannot be a signer of program {}",
+                instruction.program_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Signer and writable privileges of `fee_payer` in the message. Uncompiled instructions don't
+/// carry per-account flags, the runtime passes the fee payer with these privileges to every
+/// instruction that references it
+fn fee_payer_privileges(
+    transaction_resolved: &VersionedTransactionResolved,
+    fee_payer: &Pubkey,
+) -> (bool, bool) {
+    let message = &transaction_resolved.transaction.message;
+    let is_signer = message
+        .static_account_keys()
+        .iter()
+        .position(|key| key == fee_payer)
+        .is_some_and(|index| index < message.header().num_required_signatures as usize);
+    let is_writable =
+        IxUtils::extract_all_writable_accounts(transaction_resolved).contains(fee_payer);
+    (is_signer, is_writable)
+}
+
+fn references_account(instruction: &Instruction, account: &Pubkey) -> bool {
+    instruction.accounts.iter().any(|meta| meta.pubkey == *account)
+}
+
+/// An unset or empty list allows every receiver
+fn is_receiver_allowed(allowed_receivers: &Option<Vec<String>>, receiver: &Pubkey) -> bool {
+    match allowed_receivers {
+        Some(allowed) if !allowed.is_empty() => {
+            let receiver = receiver.to_string();
+            allowed.iter().any(|allowed| *allowed == receiver)
+        }
+        _ => true,
+    }
+}
+
+/// Rejects instructions writing to the fee payer from programs the fee payer policy doesn't cover
+///
+/// System and token instructions are checked by [`FeePolicyValidator`], any other program
+/// receiving the fee payer as a writable account could spend from it unchecked, unless the
+/// policy's `program_instruction_allowlist` allows it.
+pub struct FeePayerWritableValidator<'a> {
+    pub fee_payer_pubkey: Pubkey,
+    pub fee_payer_policy: &'a FeePayerPolicy,
+}
+
+impl FeePayerWritableValidator<'_> {
+    /// Programs whose instructions the fee payer policy parses
+    pub(crate) const POLICY_PROGRAMS: [Pubkey; 3] =
+        [SYSTEM_PROGRAM_ID, spl_token_interface::ID, spl_token_2022_interface::ID];
+}
+
+impl ValidatorSet for FeePayerWritableValidator<'_> {
+    fn validate(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        if !IxUtils::extract_all_writable_accounts(transaction_resolved)
+            .contains(&self.fee_payer_pubkey)
+        {
+            return Ok(());
+        }
+
+        let (is_signer, _) = fee_payer_privileges(transaction_resolved, &self.fee_payer_pubkey);
+        for instruction in &transaction_resolved.all_instructions {
+            if Self::POLICY_PROGRAMS.contains(&instruction.program_id)
+                || self.fee_payer_policy.allows_program_instruction(instruction, is_signer, true)
+                    == Some(true)
+            {
+                continue;
+            }
+
+            if references_account(instruction, &self.fee_payer_pubkey) {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Fee payer cannot be written to by program {}",
+                    instruction.program_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{ProgramInstructionRule, SplTokenInstructionPolicy, SystemInstructionPolicy},
+        transaction::TransactionUtil,
+    };
+    use solana_message::{Message, VersionedMessage};
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_system_interface::instruction::transfer;
+    use spl_associated_token_account_interface::instruction::create_associated_token_account;
+    use spl_token_interface::instruction as spl_token_instruction;
+    use std::cell::Cell;
+
+    struct CountingValidator<'a> {
+        calls: &'a Cell<usize>,
+        fail: bool,
+    }
+
+    impl ValidatorSet for CountingValidator<'_> {
+        fn validate(&self, _: &mut VersionedTransactionResolved) -> Result<(), KoraError> {
+            self.calls.set(self.calls.get() + 1);
+            if self.fail {
+                return Err(KoraError::InvalidTransaction("rejected".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn transfer_transaction(sender: &Pubkey, fee_payer: &Pubkey) -> VersionedTransactionResolved {
+        let instruction = transfer(sender, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(fee_payer)));
+        TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap()
+    }
+
+    #[test]
+    fn test_chain_runs_all_validators_in_order() {
+        let first_calls = Cell::new(0);
+        let second_calls = Cell::new(0);
+        let mut transaction = transfer_transaction(&Pubkey::new_unique(), &Pubkey::new_unique());
+
+        let set = CountingValidator { calls: &first_calls, fail: false }
+            .chain(CountingValidator { calls: &second_calls, fail: false });
+
+        assert!(set.validate(&mut transaction).is_ok());
+        assert_eq!(first_calls.get(), 1);
+        assert_eq!(second_calls.get(), 1);
+    }
+
+    #[test]
+    fn test_chain_stops_at_first_failure() {
+        let first_calls = Cell::new(0);
+        let second_calls = Cell::new(0);
+        let mut transaction = transfer_transaction(&Pubkey::new_unique(), &Pubkey::new_unique());
+
+        let set = CountingValidator { calls: &first_calls, fail: true }
+            .chain(CountingValidator { calls: &second_calls, fail: false });
+
+        assert_eq!(
+            set.validate(&mut transaction).unwrap_err(),
+            KoraError::InvalidTransaction("rejected".to_string())
+        );
+        assert_eq!(first_calls.get(), 1);
+        assert_eq!(second_calls.get(), 0);
+    }
+
+    #[test]
+    fn test_size_validator() {
+        let fee_payer = Pubkey::new_unique();
+        let instructions: Vec<Instruction> =
+            (0..3).map(|_| transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1000)).collect();
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        assert!(SizeValidator { max_signatures: 4 }.validate(&mut transaction).is_ok());
+        assert_eq!(
+            SizeValidator { max_signatures: 2 }.validate(&mut transaction).unwrap_err(),
+            KoraError::InvalidTransaction("Too many signatures: 4 > 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_allowlist_validator() {
+        let mut transaction = transfer_transaction(&Pubkey::new_unique(), &Pubkey::new_unique());
+
+        let allowed = [SYSTEM_PROGRAM_ID];
+        assert!(AllowlistValidator { allowed_programs: &allowed, disallowed_programs: &[] }
+            .validate(&mut transaction)
+            .is_ok());
+
+        let other = [Pubkey::new_unique()];
+        assert_eq!(
+            AllowlistValidator { allowed_programs: &other, disallowed_programs: &[] }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Program {SYSTEM_PROGRAM_ID} is not in the allowed list"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_anchor_instruction_validator() {
+        let program_id = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        // Discriminators of `initialize` and `deposit`, as found in Anchor IDLs
+        let initialize = [175, 175, 109, 31, 13, 152, 155, 237];
+        let deposit = [242, 35, 198, 137, 82, 225, 242, 182];
+        let allowed = HashMap::from([(program_id, HashSet::from([deposit]))]);
+        let validator = AnchorInstructionValidator { allowed_discriminators: &allowed };
+
+        let anchor_transaction = |data: Vec<u8>| {
+            let instruction =
+                Instruction::new_with_bytes(program_id, &data, vec![AccountMeta::new(user, true)]);
+            let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&user)));
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap()
+        };
+        let expected = KoraError::InvalidTransaction(format!(
+            "Instruction of Anchor program {program_id} is not in the allowed instructions"
+        ));
+
+        let mut data = deposit.to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        assert!(validator.validate(&mut anchor_transaction(data)).is_ok());
+
+        assert_eq!(
+            validator.validate(&mut anchor_transaction(initialize.to_vec())).unwrap_err(),
+            expected
+        );
+        // Too short to hold a discriminator
+        assert_eq!(
+            validator.validate(&mut anchor_transaction(deposit[..4].to_vec())).unwrap_err(),
+            expected
+        );
+
+        // Other programs are left alone
+        let mut transaction = transfer_transaction(&user, &Pubkey::new_unique());
+        assert!(validator.validate(&mut transaction).is_ok());
+    }
+
+    #[test]
+    fn test_allowlist_validator_disallowed_programs() {
+        let mut transaction = transfer_transaction(&Pubkey::new_unique(), &Pubkey::new_unique());
+        let disallowed = [SYSTEM_PROGRAM_ID];
+        let expected =
+            KoraError::InvalidTransaction(format!("Program {SYSTEM_PROGRAM_ID} is disallowed"));
+
+        // Denylist only, every other program is allowed
+        let other = [Pubkey::new_unique()];
+        assert!(AllowlistValidator { allowed_programs: &[], disallowed_programs: &other }
+            .validate(&mut transaction)
+            .is_ok());
+        assert_eq!(
+            AllowlistValidator { allowed_programs: &[], disallowed_programs: &disallowed }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            expected
+        );
+
+        // The denylist wins over the allowlist
+        assert_eq!(
+            AllowlistValidator { allowed_programs: &disallowed, disallowed_programs: &disallowed }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            expected
+        );
+
+        // Without either list nothing is allowed
+        assert!(AllowlistValidator { allowed_programs: &[], disallowed_programs: &[] }
+            .validate(&mut transaction)
+            .is_err());
+    }
+
+    #[test]
+    fn test_inner_instructions_checked_against_fee_payer_policy() {
+        let fee_payer = Pubkey::new_unique();
+        let cpi_program = Pubkey::new_unique();
+        let outer =
+            Instruction::new_with_bytes(cpi_program, &[], vec![AccountMeta::new(fee_payer, true)]);
+        let message = VersionedMessage::Legacy(Message::new(&[outer], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        // Inner transfer resolved from simulation, signed by the fee payer through the outer program
+        transaction.all_instructions.push(transfer(&fee_payer, &Pubkey::new_unique(), 1000));
+
+        // Programs invoked through CPI must be allowed too
+        let allowed = [cpi_program];
+        assert_eq!(
+            AllowlistValidator { allowed_programs: &allowed, disallowed_programs: &[] }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Program {SYSTEM_PROGRAM_ID} is not in the allowed list"
+            ))
+        );
+
+        let allowed = [cpi_program, SYSTEM_PROGRAM_ID];
+        let restrictive = FeePayerPolicy::default();
+        assert_eq!(
+            InnerInstructionsValidator
+                .chain(AllowlistValidator { allowed_programs: &allowed, disallowed_programs: &[] })
+                .chain(FeePolicyValidator {
+                    fee_payer_pubkey: fee_payer,
+                    fee_payer_policy: &restrictive
+                })
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'System Transfer'".to_string()
+            )
+        );
+
+        // A CPI that couldn't be resolved can't be checked, so it's rejected
+        transaction.inner_instructions_complete = false;
+        assert_eq!(
+            InnerInstructionsValidator.validate(&mut transaction).unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Inner instructions could not be resolved from simulation".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_fee_payer_writable_validator() {
+        let fee_payer = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let policy = FeePayerPolicy::default();
+        let validator =
+            FeePayerWritableValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy };
+
+        // System transfers are left to the fee payer policy
+        assert!(validator.validate(&mut transfer_transaction(&fee_payer, &fee_payer)).is_ok());
+
+        let unrelated = Instruction::new_with_bytes(
+            other_program,
+            &[],
+            vec![AccountMeta::new(Pubkey::new_unique(), false)],
+        );
+        let message = VersionedMessage::Legacy(Message::new(&[unrelated], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator.validate(&mut transaction).is_ok());
+
+        let writes_fee_payer = Instruction::new_with_bytes(
+            other_program,
+            &[],
+            vec![AccountMeta::new(fee_payer, true)],
+        );
+        let message = VersionedMessage::Legacy(Message::new(&[writes_fee_payer], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert_eq!(
+            validator.validate(&mut transaction).unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Fee payer cannot be written to by program {other_program}"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_fee_payer_writable_validator_checks_associated_token_account() {
+        let fee_payer = Pubkey::new_unique();
+        let create_ata = create_associated_token_account(
+            &fee_payer,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &spl_token_interface::ID,
+        );
+        let message = VersionedMessage::Legacy(Message::new(&[create_ata], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        // The policy doesn't parse ATA instructions, funding them needs an explicit rule
+        let policy = FeePayerPolicy::default();
+        assert_eq!(
+            FeePayerWritableValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Fee payer cannot be written to by program {}",
+                spl_associated_token_account_interface::program::ID
+            ))
+        );
+
+        let policy = FeePayerPolicy {
+            program_instruction_allowlist: vec![ProgramInstructionRule {
+                program_id: spl_associated_token_account_interface::program::ID.to_string(),
+                instruction_discriminator: None,
+                allow_fee_payer_as: vec!["signer".to_string(), "writable".to_string()],
+            }],
+            ..Default::default()
+        };
+        assert!(FeePayerWritableValidator {
+            fee_payer_pubkey: fee_payer,
+            fee_payer_policy: &policy
+        }
+        .validate(&mut transaction)
+        .is_ok());
+    }
+
+    #[test]
+    fn test_fee_payer_writable_validator_skips_readonly_fee_payer() {
+        let fee_payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            program_id,
+            &[],
+            vec![AccountMeta::new_readonly(fee_payer, false)],
+        );
+        // Another account pays the transaction fee, the Kora fee payer is only read
+        let message =
+            VersionedMessage::Legacy(Message::new(&[instruction], Some(&Pubkey::new_unique())));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        assert!(FeePayerWritableValidator {
+            fee_payer_pubkey: fee_payer,
+            fee_payer_policy: &FeePayerPolicy::default(),
+        }
+        .validate(&mut transaction)
+        .is_ok());
+    }
+
+    #[test]
+    fn test_disallowed_accounts_validator() {
+        let sender = Pubkey::new_unique();
+        let mut transaction = transfer_transaction(&sender, &Pubkey::new_unique());
+
+        let unrelated = HashSet::from([Pubkey::new_unique()]);
+        assert!(DisallowedAccountsValidator { disallowed_accounts: &unrelated }
+            .validate(&mut transaction)
+            .is_ok());
+
+        let disallowed = HashSet::from([sender]);
+        assert_eq!(
+            DisallowedAccountsValidator { disallowed_accounts: &disallowed }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(format!("Account {sender} is disallowed"))
+        );
+    }
+
+    #[test]
+    fn test_fee_policy_validator() {
+        let fee_payer = Pubkey::new_unique();
+        let mut transaction = transfer_transaction(&fee_payer, &fee_payer);
+
+        let permissive = FeePayerPolicy {
+            system: SystemInstructionPolicy { allow_transfer: true, ..Default::default() },
+            ..Default::default()
+        };
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &permissive }
+            .validate(&mut transaction)
+            .is_ok());
+
+        let restrictive = FeePayerPolicy::default();
+        assert_eq!(
+            FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &restrictive }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'System Transfer'".to_string()
+            )
+        );
+    }
+
+    fn spl_transaction(
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+    ) -> VersionedTransactionResolved {
+        let message = VersionedMessage::Legacy(Message::new(instructions, Some(fee_payer)));
+        TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap()
+    }
+
+    #[test]
+    fn test_fee_policy_validator_instruction_type_allowlist() {
+        let fee_payer = Pubkey::new_unique();
+        let (source, destination, mint) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let sol_transfer = transfer(&fee_payer, &Pubkey::new_unique(), 1000);
+        let spl_transfer = spl_token_instruction::transfer(
+            &spl_token_interface::id(),
+            &source,
+            &destination,
+            &fee_payer,
+            &[],
+            1000,
+        )
+        .unwrap();
+        let spl_burn = spl_token_instruction::burn(
+            &spl_token_interface::id(),
+            &source,
+            &mint,
+            &fee_payer,
+            &[],
+            1000,
+        )
+        .unwrap();
+
+        // Category flags are all off, the allowlist alone decides
+        let policy = FeePayerPolicy {
+            instruction_type_allowlist: Some(vec![
+                "SystemTransfer".to_string(),
+                "SplTokenTransfer".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let validator =
+            FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy };
+
+        let mut transaction = spl_transaction(&[sol_transfer, spl_transfer.clone()], &fee_payer);
+        assert!(validator.validate(&mut transaction).is_ok());
+
+        let mut transaction = spl_transaction(&[spl_transfer, spl_burn.clone()], &fee_payer);
+        assert_eq!(
+            validator.validate(&mut transaction).unwrap_err(),
+            KoraError::InvalidTransaction(
+                "Fee payer cannot be used for 'SPL Token Burn'".to_string()
+            )
+        );
+
+        // Burns that don't involve the fee payer are unaffected
+        let other_burn = spl_token_instruction::burn(
+            &spl_token_interface::id(),
+            &source,
+            &mint,
+            &Pubkey::new_unique(),
+            &[],
+            1000,
+        )
+        .unwrap();
+        let mut transaction = spl_transaction(&[other_burn], &fee_payer);
+        assert!(validator.validate(&mut transaction).is_ok());
+    }
+
+    #[test]
+    fn test_fee_policy_validator_rejects_fee_payer_as_unknown_signer() {
+        let fee_payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        // Read-only signer, so it slips past the writable check
+        let custom_instruction = Instruction::new_with_bytes(
+            program_id,
+            &[7, 0, 0, 0],
+            vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(fee_payer, true),
+            ],
+        );
+        let mut transaction = spl_transaction(&[custom_instruction], &fee_payer);
+
+        let mut policy = FeePayerPolicy::default();
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+            .validate(&mut transaction)
+            .is_ok());
+
+        policy.reject_fee_payer_as_unknown_signer = true;
+        assert_eq!(
+            FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+                .validate(&mut transaction)
+                .unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Fee payer cannot be a signer of program {program_id}"
+            ))
+        );
+
+        // Known programs are left to the category-level flags
+        let sol_transfer = transfer(&fee_payer, &Pubkey::new_unique(), 1000);
+        policy.system.allow_transfer = true;
+        let mut transaction = spl_transaction(&[sol_transfer], &fee_payer);
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+            .validate(&mut transaction)
+            .is_ok());
+
+        // Other signers of unknown programs are fine
+        let other_signer = Instruction::new_with_bytes(
+            program_id,
+            &[7, 0, 0, 0],
+            vec![AccountMeta::new_readonly(Pubkey::new_unique(), true)],
+        );
+        let mut transaction = spl_transaction(&[other_signer], &fee_payer);
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+            .validate(&mut transaction)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_fee_policy_validator_allowlist_overrides_category_flags() {
+        let fee_payer = Pubkey::new_unique();
+        let spl_burn = spl_token_instruction::burn(
+            &spl_token_interface::id(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &fee_payer,
+            &[],
+            1000,
+        )
+        .unwrap();
+        let mut transaction = spl_transaction(&[spl_burn], &fee_payer);
+
+        let mut policy = FeePayerPolicy {
+            spl_token: SplTokenInstructionPolicy { allow_burn: true, ..Default::default() },
+            ..Default::default()
+        };
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+            .validate(&mut transaction)
+            .is_ok());
+
+        policy.instruction_type_allowlist = Some(vec!["SplTokenTransfer".to_string()]);
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+            .validate(&mut transaction)
+            .is_err());
+
+        policy.spl_token.allow_burn = false;
+        policy.instruction_type_allowlist = Some(vec!["SplTokenBurn".to_string()]);
+        assert!(FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }
+            .validate(&mut transaction)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_program_instruction_allowlist() {
+        let fee_payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let instruction = |data: &[u8], account: Pubkey| {
+            Instruction::new_with_bytes(program_id, data, vec![AccountMeta::new(account, true)])
+        };
+
+        let rule = |discriminator: &str, roles: &[&str]| ProgramInstructionRule {
+            program_id: program_id.to_string(),
+            instruction_discriminator: Some(discriminator.to_string()),
+            allow_fee_payer_as: roles.iter().map(|role| role.to_string()).collect(),
+        };
+        let policy = FeePayerPolicy {
+            reject_fee_payer_as_unknown_signer: true,
+            program_instruction_allowlist: vec![
+                rule("0102", &["signer", "writable"]),
+                rule("0304", &["signer"]),
+            ],
+            ..Default::default()
+        };
+        let validator =
+            FeePolicyValidator { fee_payer_pubkey: fee_payer, fee_payer_policy: &policy }.chain(
+                FeePayerWritableValidator {
+                    fee_payer_pubkey: fee_payer,
+                    fee_payer_policy: &policy,
+                },
+            );
+
+        // The rule overrides both reject_fee_payer_as_unknown_signer and the writable check
+        let mut transaction = spl_transaction(&[instruction(&[1, 2, 0, 0], fee_payer)], &fee_payer);
+        assert!(validator.validate(&mut transaction).is_ok());
+
+        // As the transaction fee payer it is also writable, which the second rule doesn't allow
+        let not_allowed = KoraError::InvalidTransaction(format!(
+            "Fee payer use in this instruction of program {program_id} is not allowed by \
+             program_instruction_allowlist"
+        ));
+        let mut transaction = spl_transaction(&[instruction(&[3, 4, 0, 0], fee_payer)], &fee_payer);
+        assert_eq!(validator.validate(&mut transaction).unwrap_err(), not_allowed);
+
+        // Instructions of the program without a matching rule can't use the fee payer
+        let mut transaction = spl_transaction(&[instruction(&[5, 6, 0, 0], fee_payer)], &fee_payer);
+        assert_eq!(validator.validate(&mut transaction).unwrap_err(), not_allowed);
+
+        let mut transaction =
+            spl_transaction(&[instruction(&[5, 6, 0, 0], Pubkey::new_unique())], &fee_payer);
+        assert!(validator.validate(&mut transaction).is_ok());
+
+        // Programs without rules keep the existing checks
+        let other_program = Pubkey::new_unique();
+        let other = Instruction::new_with_bytes(
+            other_program,
+            &[1, 2, 0, 0],
+            vec![AccountMeta::new(fee_payer, true)],
+        );
+        assert_eq!(
+            validator.validate(&mut spl_transaction(&[other], &fee_payer)).unwrap_err(),
+            KoraError::InvalidTransaction(format!(
+                "Fee payer cannot be a signer of program {other_program}"
+            ))
+        );
+    }
+}
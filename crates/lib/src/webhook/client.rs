@@ -0,0 +1,206 @@
+use std::{collections::HashSet, time::Duration};
+
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use solana_sdk::pubkey::Pubkey;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    config::WebhookEvent,
+    constant::{DEFAULT_WEBHOOK_TIMEOUT_SECONDS, X_KORA_SIGNATURE},
+    error::KoraError,
+    sanitize_error,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    /// Public key of the Kora signer handling the transaction
+    pub signer_pubkey: String,
+    /// Base64-encoded transaction, as received from the client for `failed` events
+    pub transaction: String,
+    /// Transaction signature, only present once the transaction has been submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Error returned to the client, only present on `failed` events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Unix timestamp in seconds at which the event happened
+    pub timestamp: i64,
+}
+
+impl WebhookPayload {
+    pub fn new(event: WebhookEvent, signer_pubkey: &Pubkey, transaction: &str) -> Self {
+        Self {
+            event,
+            signer_pubkey: signer_pubkey.to_string(),
+            transaction: transaction.to_string(),
+            signature: None,
+            error: None,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    pub fn with_signature(mut self, signature: String) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    pub fn with_error(mut self, error: String) -> Self {
+        self.error = Some(error);
+        self
+    }
+}
+
+/// HTTP client POSTing payloads to the webhook endpoint
+pub struct WebhookClient {
+    http_client: Client,
+    url: String,
+    secret: String,
+}
+
+impl WebhookClient {
+    pub fn new(url: String, secret: String) -> Result<Self, KoraError> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_WEBHOOK_TIMEOUT_SECONDS))
+            .build()
+            .map_err(|e| {
+                KoraError::InternalServerError(format!("Failed to build webhook client: {e}"))
+            })?;
+
+        Ok(Self { http_client, url, secret })
+    }
+
+    /// Hex-encoded HMAC-SHA256 of `body`, keyed with the webhook secret
+    pub fn sign(&self, body: &[u8]) -> Result<String, KoraError> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .map_err(|e| KoraError::InternalServerError(format!("Invalid webhook secret: {e}")))?;
+        mac.update(body);
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    pub async fn send(&self, payload: &WebhookPayload) -> Result<(), KoraError> {
+        let body = serde_json::to_vec(payload).map_err(|e| {
+            KoraError::SerializationError(format!("Failed to serialize webhook payload: {e}"))
+        })?;
+        let signature = self.sign(&body)?;
+
+        let response = self
+            .http_client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(X_KORA_SIGNATURE, signature)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to send webhook: {e}"
+                )))
+            })?;
+
+        if !response.status().is_success() {
+            return Err(KoraError::InternalServerError(format!(
+                "Webhook endpoint responded with status {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Queues payloads for a background task delivering them, so signing never waits on the endpoint
+pub struct WebhookDispatcher {
+    sender: mpsc::Sender<WebhookPayload>,
+    events: HashSet<WebhookEvent>,
+}
+
+impl WebhookDispatcher {
+    /// Spawn the delivery task, payloads are sent one at a time in the order they were queued.
+    /// The task stops once the dispatcher is dropped and the queue is drained.
+    pub fn spawn(
+        client: WebhookClient,
+        events: impl IntoIterator<Item = WebhookEvent>,
+        capacity: usize,
+    ) -> (Self, JoinHandle<()>) {
+        let (sender, mut receiver) = mpsc::channel::<WebhookPayload>(capacity);
+
+        let handle = tokio::spawn(async move {
+            while let Some(payload) = receiver.recv().await {
+                if let Err(e) = client.send(&payload).await {
+                    log::warn!("Failed to deliver {:?} webhook: {e}", payload.event);
+                }
+            }
+        });
+
+        (Self { sender, events: events.into_iter().collect() }, handle)
+    }
+
+    /// Queue a payload, skipped if its event is not subscribed and dropped if the queue is full
+    pub fn dispatch(&self, payload: WebhookPayload) {
+        if !self.events.contains(&payload.event) {
+            return;
+        }
+
+        if let Err(e) = self.sender.try_send(payload) {
+            log::warn!("Dropping webhook payload: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn test_dispatcher_posts_signed_payload() {
+        let mut server = Server::new_async().await;
+        let client =
+            WebhookClient::new(format!("{}/webhook", server.url()), "secret".to_string()).unwrap();
+        let signer_pubkey = Pubkey::new_unique();
+
+        let payload = WebhookPayload::new(WebhookEvent::Submitted, &signer_pubkey, "encoded_tx")
+            .with_signature("sig".to_string());
+        let body = serde_json::to_vec(&payload).unwrap();
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(&body);
+        let expected_signature = hex::encode(mac.finalize().into_bytes());
+
+        let mock = server
+            .mock("POST", "/webhook")
+            .match_header(X_KORA_SIGNATURE, expected_signature.as_str())
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::Exact(String::from_utf8(body).unwrap()))
+            .with_status(200)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let (dispatcher, handle) = WebhookDispatcher::spawn(client, [WebhookEvent::Submitted], 10);
+        dispatcher.dispatch(payload);
+        // Not subscribed, never delivered
+        dispatcher.dispatch(WebhookPayload::new(WebhookEvent::Signed, &signer_pubkey, "other_tx"));
+
+        drop(dispatcher);
+        handle.await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_on_error_status() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("POST", "/").with_status(500).create_async().await;
+        let client = WebhookClient::new(server.url(), "secret".to_string()).unwrap();
+
+        let payload = WebhookPayload::new(WebhookEvent::Failed, &Pubkey::new_unique(), "tx")
+            .with_error("boom".to_string());
+
+        assert!(matches!(client.send(&payload).await, Err(KoraError::InternalServerError(_))));
+    }
+}
@@ -0,0 +1,46 @@
+pub mod client;
+
+pub use client::{WebhookClient, WebhookDispatcher, WebhookPayload};
+
+use std::sync::OnceLock;
+
+use crate::{constant::DEFAULT_WEBHOOK_CHANNEL_CAPACITY, error::KoraError};
+
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
+/// Global dispatcher, `None` when no webhook is configured
+static WEBHOOK_DISPATCHER: OnceLock<Option<WebhookDispatcher>> = OnceLock::new();
+
+/// Start the webhook delivery task if `kora.webhook` is configured, must run inside the Tokio runtime
+pub fn init_webhook_dispatcher() -> Result<(), KoraError> {
+    let config = get_config()?;
+
+    let dispatcher = match &config.kora.webhook {
+        Some(webhook) => {
+            let client = WebhookClient::new(webhook.url.clone(), webhook.secret.clone())?;
+            let (dispatcher, _) = WebhookDispatcher::spawn(
+                client,
+                webhook.events.iter().copied(),
+                DEFAULT_WEBHOOK_CHANNEL_CAPACITY,
+            );
+            log::info!("Webhook notifications enabled for events {:?}", webhook.events);
+            Some(dispatcher)
+        }
+        None => None,
+    };
+
+    WEBHOOK_DISPATCHER.set(dispatcher).map_err(|_| {
+        KoraError::InternalServerError("Webhook dispatcher already initialized".to_string())
+    })
+}
+
+/// Queue a webhook delivery, no-op when webhooks are not configured
+pub fn notify(payload: WebhookPayload) {
+    if let Some(Some(dispatcher)) = WEBHOOK_DISPATCHER.get() {
+        dispatcher.dispatch(payload);
+    }
+}